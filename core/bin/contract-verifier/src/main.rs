@@ -1,7 +1,7 @@
 use std::{path::PathBuf, time::Duration};
 
 use anyhow::Context as _;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use tokio::sync::watch;
 use zksync_config::{
     configs::{ContractVerifierSecrets, PostgresSecrets},
@@ -11,11 +11,13 @@ use zksync_config::{
 };
 use zksync_contract_verifier_lib::{
     etherscan::{metrics::EtherscanVerifierMetrics, EtherscanVerifier},
-    ContractVerifier,
+    sourcify::SourcifyRepository,
+    ContractVerifier, VerificationPropagator,
 };
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_queued_job_processor::JobProcessor;
 use zksync_task_management::ManagedTasks;
+use zksync_types::{L2BlockNumber, L2ChainId};
 use zksync_vlog::prometheus::PrometheusExporterConfig;
 
 #[derive(Debug, Parser)]
@@ -30,6 +32,56 @@ struct Opt {
     /// Path to the secrets file.
     #[arg(long)]
     secrets_path: Option<PathBuf>,
+    /// Propagate verification info to contracts deployed starting from the specified L2 block,
+    /// rather than only to newly deployed contracts.
+    #[arg(long)]
+    propagation_backfill_from: Option<u32>,
+    /// One-off command to run instead of the verifier.
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// One-off commands working with a local copy of the [Sourcify repository](https://docs.sourcify.dev/docs/repository/).
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Exports verified contracts into the Sourcify repository layout.
+    ExportSourcify {
+        /// Root directory of the repository (i.e., the parent of the `contracts` directory).
+        #[arg(long)]
+        output: PathBuf,
+        /// L2 chain ID used in the repository paths.
+        #[arg(long)]
+        chain_id: u64,
+    },
+    /// Enqueues verification requests for contracts from the Sourcify repository deployed on this chain.
+    ImportSourcify {
+        /// Root directory of the repository (i.e., the parent of the `contracts` directory).
+        #[arg(long)]
+        input: PathBuf,
+        /// L2 chain ID used in the repository paths.
+        #[arg(long)]
+        chain_id: u64,
+    },
+}
+
+async fn run_command(command: Command, pool: &ConnectionPool<Core>) -> anyhow::Result<()> {
+    match command {
+        Command::ExportSourcify { output, chain_id } => {
+            let chain_id = L2ChainId::new(chain_id).map_err(anyhow::Error::msg)?;
+            let stats = SourcifyRepository::new(output, chain_id)
+                .export(pool)
+                .await?;
+            tracing::info!("Export finished: {stats:?}");
+        }
+        Command::ImportSourcify { input, chain_id } => {
+            let chain_id = L2ChainId::new(chain_id).map_err(anyhow::Error::msg)?;
+            let stats = SourcifyRepository::new(input, chain_id)
+                .import(pool)
+                .await?;
+            tracing::info!("Import finished: {stats:?}");
+        }
+    }
+    Ok(())
 }
 
 async fn perform_storage_migration(pool: &ConnectionPool<Core>) -> anyhow::Result<()> {
@@ -87,6 +139,9 @@ async fn main() -> anyhow::Result<()> {
     .await?;
 
     perform_storage_migration(&pool).await?;
+    if let Some(command) = opt.command {
+        return run_command(command, &pool).await;
+    }
 
     let (stop_sender, stop_receiver) = watch::channel(false);
     let etherscan_api_key = contract_verifier_secrets.etherscan_api_key;
@@ -100,6 +155,17 @@ async fn main() -> anyhow::Result<()> {
     .await
     .context("failed initializing contract verifier")?;
     let update_task = contract_verifier.sync_compiler_versions_task();
+    let propagator = verifier_config.propagation_enabled.then(|| {
+        let propagator = VerificationPropagator::new(
+            contract_verifier.clone(),
+            verifier_config.propagation_batch_size,
+            verifier_config.propagation_poll_interval,
+        );
+        match opt.propagation_backfill_from {
+            Some(l2_block) => propagator.with_backfill_from(L2BlockNumber(l2_block)),
+            None => propagator,
+        }
+    });
 
     let mut tasks = vec![
         tokio::spawn(update_task),
@@ -109,6 +175,12 @@ async fn main() -> anyhow::Result<()> {
                 .run(stop_receiver.clone()),
         ),
     ];
+    if let Some(propagator) = propagator {
+        tracing::info!("Verification info propagation is enabled");
+        tasks.push(tokio::spawn(propagator.run(stop_receiver.clone())));
+    } else {
+        tracing::info!("Verification info propagation is disabled");
+    }
     if etherscan_verifier_enabled {
         tracing::info!("Etherscan verifier is enabled");
         let etherscan_verifier = EtherscanVerifier::new(
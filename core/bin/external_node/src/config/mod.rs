@@ -102,7 +102,7 @@ impl LocalConfig {
     fn mock(temp_dir: &tempfile::TempDir, test_pool: &ConnectionPool<Core>) -> Self {
         use zksync_config::configs::{
             consensus::ConsensusSecrets, database::MerkleTreeConfig, secrets::PostgresSecrets,
-            ContractVerifierSecrets, ExperimentalDBConfig,
+            ContractVerifierSecrets, ExperimentalDBConfig, NotifierSecrets,
         };
 
         let mut api = ApiConfig::for_tests();
@@ -145,6 +145,7 @@ impl LocalConfig {
                 },
                 data_availability: None,
                 contract_verifier: ContractVerifierSecrets::default(),
+                notifier: NotifierSecrets::default(),
             },
             node_sync: NodeSyncConfig::default(),
         }
@@ -275,6 +275,10 @@ fn parsing_from_full_env() {
         EN_PRUNING_CHUNK_SIZE=5
         EN_PRUNING_REMOVAL_DELAY_SEC=120
         EN_PRUNING_DATA_RETENTION_SEC=86400
+        EN_PRUNING_ARCHIVE_MODE=FileBacked
+        EN_PRUNING_ARCHIVE_FILE_BACKED_BASE_PATH=/db/pruning_archives
+        EN_PRUNING_ARCHIVE_MAX_RETRIES=3
+        EN_PRUNING_ARCHIVE_LOCAL_MIRROR_PATH=/tmp/object-store
 
         EN_GATEWAY_URL=https://127.0.0.1:3150/
         EN_BRIDGE_ADDRESSES_REFRESH_INTERVAL_SEC=300
@@ -475,6 +479,15 @@ fn test_parsing_general_config(source: impl ConfigSource + Clone) {
     assert_eq!(config.chunk_size, NonZeroU32::new(5).unwrap());
     assert_eq!(config.removal_delay, Duration::from_secs(120));
     assert_eq!(config.data_retention, Duration::from_secs(86_400));
+    let archive = config.archive.unwrap();
+    let ObjectStoreMode::FileBacked {
+        file_backed_base_path,
+    } = archive.mode
+    else {
+        panic!("unexpected archive config: {archive:?}");
+    };
+    assert_eq!(file_backed_base_path.as_os_str(), "/db/pruning_archives");
+    assert_eq!(archive.max_retries, 3);
 
     let config: TimestampAsserterConfig =
         tester.for_config().test_complete(source.clone()).unwrap();
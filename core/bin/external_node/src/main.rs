@@ -10,6 +10,7 @@ use zksync_config::{
     DAClientConfig,
 };
 use zksync_types::L1BatchNumber;
+use zksync_vlog::opentelemetry::tx_lifecycle;
 
 use crate::config::{generate_consensus_secrets, ExternalNodeConfig, LocalConfig};
 
@@ -180,6 +181,7 @@ fn main() -> anyhow::Result<()> {
         let _rt_guard = runtime.enter();
         config_sources.observability()?.install()?
     };
+    tx_lifecycle::set_node_role(tx_lifecycle::NodeRole::External);
     let repo = config_sources.build_repository(&schema);
 
     let mut revert_to_l1_batch = None;
@@ -142,6 +142,10 @@ impl ExternalNodeBuilder {
             networks.main_node_url.clone(),
             networks.main_node_rate_limit_rps,
             networks.l2_chain_id,
+        )
+        .with_fallback_urls(
+            networks.fallback_main_node_urls.clone(),
+            networks.main_node_check_interval,
         );
         self.node.add_layer(layer);
         Ok(self)
@@ -247,11 +251,15 @@ impl ExternalNodeBuilder {
     fn add_pruning_layer(mut self) -> anyhow::Result<Self> {
         let config = &self.config.local.pruning;
         if config.enabled {
-            let layer = PruningLayer::new(
+            let mut layer = PruningLayer::new(
                 config.removal_delay,
                 config.chunk_size.get(),
                 config.data_retention,
             );
+            if let Some(archive_config) = &config.archive {
+                let l2_chain_id = self.config.local.networks.l2_chain_id;
+                layer = layer.with_archive(archive_config.clone(), l2_chain_id);
+            }
             self.node.add_layer(layer);
         } else {
             tracing::info!("Pruning is disabled");
@@ -407,6 +415,14 @@ impl ExternalNodeBuilder {
             layer = layer.with_pruning_config(pruning.removal_delay);
         }
 
+        // Add the object store for tree checkpoints if needed.
+        if snapshot_recovery.tree.checkpoint_l1_batch.is_some() {
+            let object_store_config = snapshot_recovery.object_store.clone().context(
+                "Snapshot object store must be configured to initialize Merkle tree from a checkpoint",
+            )?;
+            layer = layer.with_checkpoint_store(object_store_config);
+        }
+
         self.node.add_layer(layer);
         Ok(self)
     }
@@ -534,7 +550,13 @@ impl ExternalNodeBuilder {
         let config = &self.config.local.api.web3_json_rpc;
         // The refresh interval should be several times lower than the pruning removal delay, so that
         // soft-pruning will timely propagate to the API server.
-        let pruning_info_refresh_interval = self.config.local.pruning.removal_delay / 5;
+        let pruning_config = &self.config.local.pruning;
+        let pruning_info_refresh_interval = pruning_config.removal_delay / 5;
+        let pruning_archive = if pruning_config.enabled {
+            pruning_config.archive.clone()
+        } else {
+            None
+        };
 
         Ok(Web3ServerOptionalConfig {
             namespaces: config.api_namespaces.clone(),
@@ -544,6 +566,7 @@ impl ExternalNodeBuilder {
             response_body_size_limit: config.max_response_body_size(),
             with_extended_tracing: config.extended_api_tracing,
             pruning_info_refresh_interval,
+            pruning_archive,
             polling_interval: config.pubsub_polling_interval,
             request_timeout: config.request_timeout,
             websocket_requests_per_minute_limit: Some(config.websocket_requests_per_minute_limit),
@@ -288,6 +288,7 @@ pub(super) fn mock_l2_client(env: &TestEnvironment) -> MockClient<L2> {
                 number: L1BatchNumber(0),
                 commitment: Some(genesis_commitment),
                 base: utils::block_details_base(genesis_root_hash),
+                tee_attestation: None,
             })
         })
         .method("eth_blockNumber", || Ok(U64::from(0)))
@@ -0,0 +1,79 @@
+use std::path::Path;
+
+use anyhow::Context as _;
+use clap::Parser;
+use zksync_config::{
+    full_config_schema, sources::ConfigFilePaths, DBConfig, SnapshotsCreatorConfig,
+};
+use zksync_merkle_tree::{domain::ZkSyncTreeReader, RocksDBWrapper};
+use zksync_metadata_calculator::export_tree_checkpoint;
+use zksync_object_store::ObjectStoreFactory;
+use zksync_storage::RocksDB;
+use zksync_types::L1BatchNumber;
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "Merkle tree checkpoint exporter",
+    long_about = None
+)]
+struct Cli {
+    /// Specifies the version of the tree to be exported, expressed as a 0-based L1 batch number
+    /// applied to it last. If not specified, the latest tree version is exported.
+    #[arg(long = "l1-batch")]
+    l1_batch: Option<u32>,
+    /// Maximum number of tree nodes in a single checkpoint chunk.
+    #[arg(long = "chunk-size", default_value_t = 100_000)]
+    chunk_size: usize,
+}
+
+impl Cli {
+    async fn run(
+        self,
+        db_config: &DBConfig,
+        creator_config: SnapshotsCreatorConfig,
+    ) -> anyhow::Result<()> {
+        let db_path = db_config.merkle_tree.path.clone();
+        tracing::info!("Exporting checkpoint of Merkle tree at {db_path:?}");
+        let db = tokio::task::spawn_blocking(move || {
+            RocksDB::new(Path::new(&db_path)).map(RocksDBWrapper::from)
+        })
+        .await
+        .context("panicked opening Merkle tree RocksDB")?
+        .context("failed initializing Merkle tree RocksDB")?;
+
+        let l1_batch_number = if let Some(number) = self.l1_batch {
+            L1BatchNumber(number)
+        } else {
+            let reader =
+                ZkSyncTreeReader::new(db.clone()).context("cannot initialize Merkle tree")?;
+            let next_number = reader.next_l1_batch_number();
+            anyhow::ensure!(next_number > L1BatchNumber(0), "Merkle tree is empty");
+            next_number - 1
+        };
+        tracing::info!("L1 batch number to export: {l1_batch_number}");
+
+        let object_store = ObjectStoreFactory::new(creator_config.object_store)
+            .create_store()
+            .await?;
+        let manifest =
+            export_tree_checkpoint(db, l1_batch_number, &*object_store, self.chunk_size).await?;
+        tracing::info!("Exported Merkle tree checkpoint: {manifest:?}");
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let config_sources =
+        tokio::task::spawn_blocking(|| ConfigFilePaths::default().into_config_sources("ZKSYNC_"))
+            .await??;
+    let _observability_guard = config_sources.observability()?.install()?;
+
+    let schema = full_config_schema();
+    let mut repo = config_sources.build_repository(&schema);
+    let db_config: DBConfig = repo.parse()?;
+    let creator_config: SnapshotsCreatorConfig = repo.parse()?;
+    Cli::parse().run(&db_config, creator_config).await
+}
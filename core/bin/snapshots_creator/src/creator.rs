@@ -1,6 +1,6 @@
 //! [`SnapshotCreator`] and tightly related types.
 
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use anyhow::Context as _;
 use tokio::sync::Semaphore;
@@ -25,6 +25,8 @@ use crate::tests::HandleEvent;
 struct SnapshotProgress {
     version: SnapshotVersion,
     l1_batch_number: L1BatchNumber,
+    /// L1 batch of the base snapshot for differential snapshots.
+    base_l1_batch_number: Option<L1BatchNumber>,
     /// `true` if the snapshot is new (i.e., its progress is not recovered from Postgres).
     is_new_snapshot: bool,
     chunk_count: u64,
@@ -32,10 +34,16 @@ struct SnapshotProgress {
 }
 
 impl SnapshotProgress {
-    fn new(version: SnapshotVersion, l1_batch_number: L1BatchNumber, chunk_count: u64) -> Self {
+    fn new(
+        version: SnapshotVersion,
+        l1_batch_number: L1BatchNumber,
+        base_l1_batch_number: Option<L1BatchNumber>,
+        chunk_count: u64,
+    ) -> Self {
         Self {
             version,
             l1_batch_number,
+            base_l1_batch_number,
             is_new_snapshot: true,
             chunk_count,
             remaining_chunk_ids: (0..chunk_count).collect(),
@@ -53,6 +61,7 @@ impl SnapshotProgress {
         Self {
             version: snapshot.version,
             l1_batch_number: snapshot.l1_batch_number,
+            base_l1_batch_number: snapshot.base_l1_batch_number,
             is_new_snapshot: false,
             chunk_count: snapshot.storage_logs_filepaths.len() as u64,
             remaining_chunk_ids,
@@ -60,6 +69,22 @@ impl SnapshotProgress {
     }
 }
 
+/// Base snapshot for a differential snapshot.
+#[derive(Debug)]
+struct BaseSnapshot {
+    l1_batch_number: L1BatchNumber,
+    chunk_count: u64,
+}
+
+/// L2 blocks bounding the data included into a snapshot.
+#[derive(Debug, Clone, Copy)]
+struct SnapshotL2Blocks {
+    /// Last L2 block of the snapshot L1 batch.
+    last: L2BlockNumber,
+    /// Last L2 block of the base snapshot L1 batch (only for differential snapshots).
+    base: Option<L2BlockNumber>,
+}
+
 /// Creator of a single storage snapshot.
 #[derive(Debug)]
 pub(crate) struct SnapshotCreator {
@@ -81,9 +106,10 @@ impl SnapshotCreator {
         &self,
         semaphore: &Semaphore,
         progress: &SnapshotProgress,
-        l2_block_number: L2BlockNumber,
+        l2_blocks: SnapshotL2Blocks,
         chunk_id: u64,
     ) -> anyhow::Result<()> {
+        let l2_block_number = l2_blocks.last;
         let chunk_count = progress.chunk_count;
         let l1_batch_number = progress.l1_batch_number;
 
@@ -98,8 +124,11 @@ impl SnapshotCreator {
 
         let latency =
             METRICS.storage_logs_processing_duration[&StorageChunkStage::LoadFromPostgres].start();
-        let (output_filepath, latency) = match progress.version {
-            SnapshotVersion::Version0 => {
+        let (output_filepath, latency) = match (progress.version, l2_blocks.base) {
+            (SnapshotVersion::Version0, Some(_)) => {
+                anyhow::bail!("differential snapshots are not supported for version 0 snapshots");
+            }
+            (SnapshotVersion::Version0, None) => {
                 #[allow(deprecated)] // support of version 0 snapshots will be removed eventually
                 let logs = conn
                     .snapshots_creator_dal()
@@ -120,12 +149,22 @@ impl SnapshotCreator {
                 self.store_storage_logs_chunk(l1_batch_number, chunk_id, logs)
                     .await?
             }
-            SnapshotVersion::Version1 => {
-                let logs = conn
-                    .snapshots_creator_dal()
-                    .get_storage_logs_chunk(l2_block_number, l1_batch_number, hashed_keys_range)
-                    .await
-                    .context("error fetching storage logs")?;
+            (SnapshotVersion::Version1, base_l2_block_number) => {
+                let logs = if let Some(base_l2_block_number) = base_l2_block_number {
+                    conn.snapshots_creator_dal()
+                        .get_changed_storage_logs_chunk(
+                            base_l2_block_number,
+                            l2_block_number,
+                            l1_batch_number,
+                            hashed_keys_range,
+                        )
+                        .await
+                } else {
+                    conn.snapshots_creator_dal()
+                        .get_storage_logs_chunk(l2_block_number, l1_batch_number, hashed_keys_range)
+                        .await
+                };
+                let logs = logs.context("error fetching storage logs")?;
                 drop(conn);
 
                 let latency = latency.observe();
@@ -188,7 +227,7 @@ impl SnapshotCreator {
 
     async fn process_factory_deps(
         &self,
-        l2_block_number: L2BlockNumber,
+        l2_blocks: SnapshotL2Blocks,
         l1_batch_number: L1BatchNumber,
     ) -> anyhow::Result<String> {
         let mut conn = self.connect_to_replica().await?;
@@ -196,10 +235,15 @@ impl SnapshotCreator {
         tracing::info!("Loading factory deps from Postgres...");
         let latency =
             METRICS.factory_deps_processing_duration[&FactoryDepsStage::LoadFromPostgres].start();
-        let factory_deps = conn
-            .snapshots_creator_dal()
-            .get_all_factory_deps(l2_block_number)
-            .await?;
+        let factory_deps = if let Some(base_l2_block_number) = l2_blocks.base {
+            conn.snapshots_creator_dal()
+                .get_factory_deps_since(base_l2_block_number, l2_blocks.last)
+                .await?
+        } else {
+            conn.snapshots_creator_dal()
+                .get_all_factory_deps(l2_blocks.last)
+                .await?
+        };
         drop(conn);
         let latency = latency.observe();
         tracing::info!("Loaded {} factory deps in {latency:?}", factory_deps.len());
@@ -255,6 +299,22 @@ impl SnapshotCreator {
                 )
             })?;
 
+        let base_snapshot =
+            Self::select_base_snapshot(config, snapshot_version, l1_batch_number, conn).await?;
+        if let Some(base_snapshot) = base_snapshot {
+            // Chunking of a differential snapshot must coincide with the base snapshot chunking, so that
+            // chunks can be merged during recovery.
+            tracing::info!(
+                "Creating differential snapshot for L1 batch {l1_batch_number} based on {base_snapshot:?}"
+            );
+            return Ok(Some(SnapshotProgress::new(
+                snapshot_version,
+                l1_batch_number,
+                Some(base_snapshot.l1_batch_number),
+                base_snapshot.chunk_count,
+            )));
+        }
+
         let distinct_storage_logs_keys_count = conn
             .snapshots_creator_dal()
             .get_distinct_storage_logs_keys_count(l1_batch_number)
@@ -272,10 +332,96 @@ impl SnapshotCreator {
         Ok(Some(SnapshotProgress::new(
             snapshot_version,
             l1_batch_number,
+            None,
             chunk_count,
         )))
     }
 
+    /// Selects the base snapshot for a differential snapshot. Returns `Ok(None)` if a full snapshot should be created.
+    async fn select_base_snapshot(
+        config: &SnapshotsCreatorConfig,
+        snapshot_version: SnapshotVersion,
+        l1_batch_number: L1BatchNumber,
+        conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<Option<BaseSnapshot>> {
+        if config.max_differential_chain_length == 0 {
+            return Ok(None);
+        }
+        if snapshot_version == SnapshotVersion::Version0 {
+            tracing::info!("Differential snapshots are not supported for version 0 snapshots; creating a full snapshot");
+            return Ok(None);
+        }
+
+        let all_snapshots = conn.snapshots_dal().get_all_complete_snapshots().await?;
+        let full_snapshots = all_snapshots.snapshots_l1_batch_numbers.iter().copied();
+        let differential_snapshots = all_snapshots
+            .differential_snapshots
+            .iter()
+            .map(|snapshot| snapshot.l1_batch_number);
+        let Some(base_l1_batch_number) = full_snapshots
+            .chain(differential_snapshots)
+            .filter(|&number| number < l1_batch_number)
+            .max()
+        else {
+            tracing::info!(
+                "No complete snapshots before L1 batch {l1_batch_number}; creating a full snapshot"
+            );
+            return Ok(None);
+        };
+
+        // Compute the number of differential snapshots in the chain ending with the base snapshot.
+        let bases: HashMap<_, _> = all_snapshots
+            .differential_snapshots
+            .iter()
+            .map(|snapshot| (snapshot.l1_batch_number, snapshot.base_l1_batch_number))
+            .collect();
+        let mut chain_length = 1; // accounts for the created snapshot
+        let mut current = base_l1_batch_number;
+        while let Some(&base) = bases.get(&current) {
+            chain_length += 1;
+            current = base;
+        }
+        if chain_length > config.max_differential_chain_length {
+            tracing::info!(
+                "Differential snapshot chain based on L1 batch {base_l1_batch_number} would have length {chain_length}, \
+                 which exceeds the configured maximum; creating a full snapshot"
+            );
+            return Ok(None);
+        }
+
+        let base_snapshot = conn
+            .snapshots_dal()
+            .get_snapshot_metadata(base_l1_batch_number)
+            .await?
+            .with_context(|| format!("snapshot for L1 batch {base_l1_batch_number} disappeared"))?;
+        if base_snapshot.version != snapshot_version {
+            tracing::info!(
+                "Version of the base snapshot for L1 batch {base_l1_batch_number} ({:?}) differs from the requested version; \
+                 creating a full snapshot",
+                base_snapshot.version
+            );
+            return Ok(None);
+        }
+
+        // Storage logs after the base snapshot must not be pruned; otherwise, some changes could be lost.
+        let pruning_info = conn.pruning_dal().get_pruning_info().await?;
+        if let Some(last_hard_pruned) = pruning_info.last_hard_pruned {
+            if last_hard_pruned.l1_batch > base_l1_batch_number {
+                tracing::info!(
+                    "Data after the base snapshot L1 batch {base_l1_batch_number} is pruned (last pruned L1 batch: {}); \
+                     creating a full snapshot",
+                    last_hard_pruned.l1_batch
+                );
+                return Ok(None);
+            }
+        }
+
+        Ok(Some(BaseSnapshot {
+            l1_batch_number: base_l1_batch_number,
+            chunk_count: base_snapshot.storage_logs_filepaths.len() as u64,
+        }))
+    }
+
     /// Returns `Ok(None)` if a snapshot should not be created / resumed.
     async fn load_or_initialize_snapshot_progress(
         &self,
@@ -369,18 +515,34 @@ impl SnapshotCreator {
             .get_l2_block_range_of_l1_batch(progress.l1_batch_number)
             .await?
             .context("No L2 blocks for L1 batch")?;
+        let base_l2_block_number = if let Some(base_l1_batch_number) = progress.base_l1_batch_number
+        {
+            let (_, last_l2_block_number) = conn
+                .blocks_dal()
+                .get_l2_block_range_of_l1_batch(base_l1_batch_number)
+                .await?
+                .context("No L2 blocks for base L1 batch")?;
+            Some(last_l2_block_number)
+        } else {
+            None
+        };
         drop(conn);
+        let l2_blocks = SnapshotL2Blocks {
+            last: last_l2_block_number_in_batch,
+            base: base_l2_block_number,
+        };
 
         METRICS.storage_logs_chunks_count.set(progress.chunk_count);
         tracing::info!(
             "Creating snapshot for storage logs up to L2 block {last_l2_block_number_in_batch}, \
-            L1 batch {}",
-            progress.l1_batch_number
+            L1 batch {} (base L1 batch: {:?})",
+            progress.l1_batch_number,
+            progress.base_l1_batch_number
         );
 
         if progress.is_new_snapshot {
             let factory_deps_output_file = self
-                .process_factory_deps(last_l2_block_number_in_batch, progress.l1_batch_number)
+                .process_factory_deps(l2_blocks, progress.l1_batch_number)
                 .await?;
 
             let mut master_conn = self
@@ -392,6 +554,7 @@ impl SnapshotCreator {
                 .add_snapshot(
                     progress.version,
                     progress.l1_batch_number,
+                    progress.base_l1_batch_number,
                     progress.chunk_count,
                     &factory_deps_output_file,
                 )
@@ -407,12 +570,7 @@ impl SnapshotCreator {
             .iter()
             .copied()
             .map(|chunk_id| {
-                self.process_storage_logs_single_chunk(
-                    &semaphore,
-                    &progress,
-                    last_l2_block_number_in_batch,
-                    chunk_id,
-                )
+                self.process_storage_logs_single_chunk(&semaphore, &progress, l2_blocks, chunk_id)
             });
         futures::future::try_join_all(tasks).await?;
 
@@ -17,8 +17,9 @@ use zksync_object_store::{MockObjectStore, ObjectStore};
 use zksync_types::{
     block::{L1BatchHeader, L1BatchTreeData, L2BlockHeader},
     snapshots::{
-        SnapshotFactoryDependencies, SnapshotFactoryDependency, SnapshotStorageLog,
-        SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
+        uniform_hashed_keys_chunk, DifferentialSnapshotInfo, SnapshotFactoryDependencies,
+        SnapshotFactoryDependency, SnapshotStorageLog, SnapshotStorageLogsChunk,
+        SnapshotStorageLogsStorageKey,
     },
     AccountTreeId, Address, L1BatchNumber, L2BlockNumber, ProtocolVersion, StorageKey, StorageLog,
     H256,
@@ -32,6 +33,7 @@ fn test_config() -> SnapshotsCreatorConfig {
         l1_batch_number: None,
         storage_logs_chunk_size: 1_000_000,
         concurrent_queries_count: 10,
+        max_differential_chain_length: 0,
         object_store: ObjectStoreConfig::for_tests(),
     }
 }
@@ -635,3 +637,88 @@ async fn creator_fails_if_specified_l1_batch_is_missing() {
         .await
         .unwrap_err();
 }
+
+#[tokio::test]
+async fn creating_differential_snapshots() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut rng = thread_rng();
+    let object_store = MockObjectStore::arc();
+    let mut conn = pool.connection().await.unwrap();
+    let expected_outputs = prepare_postgres(&mut rng, &mut conn, 10).await;
+
+    let mut config = SnapshotsCreatorConfig {
+        max_differential_chain_length: 1,
+        ..test_config()
+    };
+    let snapshot_l1_batch_numbers = [L1BatchNumber(2), L1BatchNumber(5), L1BatchNumber(7)];
+    for snapshot_l1_batch_number in snapshot_l1_batch_numbers {
+        config.l1_batch_number = Some(snapshot_l1_batch_number);
+        SnapshotCreator::for_tests(object_store.clone(), pool.clone())
+            .run(config.clone(), MIN_CHUNK_COUNT)
+            .await
+            .unwrap();
+    }
+
+    let snapshots = conn
+        .snapshots_dal()
+        .get_all_complete_snapshots()
+        .await
+        .unwrap();
+    assert_eq!(
+        snapshots.snapshots_l1_batch_numbers,
+        [L1BatchNumber(7), L1BatchNumber(2)]
+    );
+    assert_eq!(
+        snapshots.differential_snapshots,
+        [DifferentialSnapshotInfo {
+            l1_batch_number: L1BatchNumber(5),
+            base_l1_batch_number: L1BatchNumber(2),
+        }]
+    );
+    assert_eq!(snapshots.newest_l1_batch_number(), Some(L1BatchNumber(7)));
+
+    let snapshot_metadata = conn
+        .snapshots_dal()
+        .get_snapshot_metadata(L1BatchNumber(5))
+        .await
+        .unwrap()
+        .expect("No snapshot metadata");
+    assert_eq!(
+        snapshot_metadata.base_l1_batch_number,
+        Some(L1BatchNumber(2))
+    );
+    assert_eq!(
+        snapshot_metadata.storage_logs_filepaths.len(),
+        MIN_CHUNK_COUNT as usize
+    );
+
+    // The differential snapshot must contain only the logs changed after the base snapshot.
+    let mut actual_logs = HashSet::new();
+    for chunk_id in 0..MIN_CHUNK_COUNT {
+        let key = SnapshotStorageLogsStorageKey {
+            l1_batch_number: L1BatchNumber(5),
+            chunk_id,
+        };
+        let chunk: SnapshotStorageLogsChunk = object_store.get(key).await.unwrap();
+        let hashed_keys_range = uniform_hashed_keys_chunk(chunk_id, MIN_CHUNK_COUNT);
+        for log in &chunk.storage_logs {
+            assert!(hashed_keys_range.contains(&log.key), "{log:?}");
+        }
+        actual_logs.extend(chunk.storage_logs);
+    }
+    let expected_logs: HashSet<_> = expected_outputs
+        .storage_logs
+        .iter()
+        .filter(|log| {
+            (L1BatchNumber(3)..=L1BatchNumber(5)).contains(&log.l1_batch_number_of_initial_write)
+        })
+        .cloned()
+        .collect();
+    assert_eq!(actual_logs, expected_logs);
+
+    let SnapshotFactoryDependencies { factory_deps } =
+        object_store.get(L1BatchNumber(5)).await.unwrap();
+    let actual_deps: HashSet<_> = factory_deps.into_iter().collect();
+    assert_eq!(actual_deps.len(), 30); // 10 factory deps in each of 3 L2 blocks
+    assert!(actual_deps.is_subset(&expected_outputs.deps));
+}
@@ -0,0 +1,94 @@
+use std::{num::NonZeroUsize, path::PathBuf};
+
+use anyhow::Context as _;
+use clap::Parser;
+use zksync_config::{full_config_schema, sources::ConfigFilePaths, SnapshotsCreatorConfig};
+use zksync_merkle_tree::{PatchSet, RocksDBWrapper};
+use zksync_object_store::ObjectStoreFactory;
+use zksync_storage::RocksDB;
+use zksync_types::{url::SensitiveUrl, L1BatchNumber, H256};
+use zksync_web3_decl::client::{Client, DynClient, L2};
+
+use crate::verifier::SnapshotVerifier;
+
+#[cfg(test)]
+mod tests;
+mod verifier;
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Matter Labs",
+    version,
+    about = "Offline snapshot verifier",
+    long_about = None
+)]
+struct Cli {
+    /// JSON-RPC URL of the main node used to fetch snapshot headers and L1 batch root hashes.
+    #[arg(long = "main-node-url")]
+    main_node_url: SensitiveUrl,
+    /// L1 batch number of the snapshot to verify. If not specified, the newest snapshot is verified.
+    #[arg(long = "l1-batch")]
+    l1_batch: Option<u32>,
+    /// Expected Merkle tree root hash after the snapshot L1 batch (e.g., taken from the batch commitment on L1).
+    /// If not specified, the root hash reported by the main node is used.
+    #[arg(long = "expected-root-hash")]
+    expected_root_hash: Option<H256>,
+    /// Path to an empty RocksDB directory used to recompute the Merkle tree. If not specified, the tree
+    /// is recomputed in memory, which is only feasible for small snapshots.
+    #[arg(long = "tree-path")]
+    tree_path: Option<PathBuf>,
+    /// Maximum number of storage log chunks loaded concurrently.
+    #[arg(long = "concurrency", default_value = "10")]
+    concurrency: NonZeroUsize,
+}
+
+impl Cli {
+    async fn run(self, creator_config: SnapshotsCreatorConfig) -> anyhow::Result<()> {
+        let client: Client<L2> = Client::http(self.main_node_url)
+            .context("failed creating main node client")?
+            .build();
+        let client = Box::new(client) as Box<DynClient<L2>>;
+        let object_store = ObjectStoreFactory::new(creator_config.object_store)
+            .create_store()
+            .await?;
+
+        let verifier = SnapshotVerifier {
+            source: &client,
+            object_store: &*object_store,
+            concurrency: self.concurrency,
+            expected_root_hash: self.expected_root_hash,
+        };
+        let l1_batch_number = self.l1_batch.map(L1BatchNumber);
+        let report = if let Some(tree_path) = self.tree_path {
+            tracing::info!("Recomputing Merkle tree in RocksDB at {tree_path:?}");
+            let db = tokio::task::spawn_blocking(move || {
+                RocksDB::new(&tree_path).map(RocksDBWrapper::from)
+            })
+            .await
+            .context("panicked opening Merkle tree RocksDB")?
+            .context("failed initializing Merkle tree RocksDB")?;
+            verifier.verify(l1_batch_number, db).await?
+        } else {
+            verifier
+                .verify(l1_batch_number, PatchSet::default())
+                .await?
+        };
+
+        println!("{report}");
+        anyhow::ensure!(report.is_passed(), "snapshot verification failed");
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let config_sources =
+        tokio::task::spawn_blocking(|| ConfigFilePaths::default().into_config_sources("ZKSYNC_"))
+            .await??;
+    let _observability_guard = config_sources.observability()?.install()?;
+
+    let schema = full_config_schema();
+    let mut repo = config_sources.build_repository(&schema);
+    let creator_config: SnapshotsCreatorConfig = repo.parse()?;
+    Cli::parse().run(creator_config).await
+}
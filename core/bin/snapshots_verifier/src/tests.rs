@@ -0,0 +1,338 @@
+//! Tests for the snapshot verifier.
+
+use std::{collections::HashMap, num::NonZeroUsize, sync::Arc};
+
+use async_trait::async_trait;
+use zksync_merkle_tree::{MerkleTree, PatchSet, TreeEntry};
+use zksync_object_store::{Bucket, MockObjectStore, ObjectStore, StoredObject};
+use zksync_types::{
+    bytecode::BytecodeHash,
+    snapshots::{
+        uniform_hashed_keys_chunk, SnapshotFactoryDependencies, SnapshotFactoryDependency,
+        SnapshotHeader, SnapshotStorageLog, SnapshotStorageLogsChunk,
+        SnapshotStorageLogsChunkMetadata, SnapshotStorageLogsStorageKey, SnapshotVersion,
+    },
+    web3::Bytes,
+    L1BatchNumber, L2BlockNumber, StorageValue, H256, U256,
+};
+
+use crate::verifier::{Check, SnapshotMetadataSource, SnapshotVerifier};
+
+const CHUNK_COUNT: u64 = 4;
+
+#[derive(Debug, Default)]
+struct MockSource {
+    headers: HashMap<L1BatchNumber, SnapshotHeader>,
+    root_hashes: HashMap<L1BatchNumber, H256>,
+}
+
+#[async_trait]
+impl SnapshotMetadataSource for MockSource {
+    async fn fetch_newest_snapshot_l1_batch_number(&self) -> anyhow::Result<Option<L1BatchNumber>> {
+        Ok(self.headers.keys().max().copied())
+    }
+
+    async fn fetch_snapshot(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Option<SnapshotHeader>> {
+        Ok(self.headers.get(&l1_batch_number).cloned())
+    }
+
+    async fn fetch_l1_batch_root_hash(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Option<H256>> {
+        Ok(self.root_hashes.get(&l1_batch_number).copied())
+    }
+}
+
+fn random_storage_logs(
+    l1_batch_number: L1BatchNumber,
+    enumeration_indices: std::ops::Range<u64>,
+) -> Vec<SnapshotStorageLog> {
+    enumeration_indices
+        .map(|enumeration_index| SnapshotStorageLog {
+            key: H256::random(),
+            value: StorageValue::random(),
+            l1_batch_number_of_initial_write: l1_batch_number,
+            enumeration_index,
+        })
+        .collect()
+}
+
+fn compute_root_hash<'a>(logs: impl Iterator<Item = &'a SnapshotStorageLog>) -> H256 {
+    let entries = logs
+        .map(|log| {
+            let key = U256::from_little_endian(log.key.as_bytes());
+            TreeEntry::new(key, log.enumeration_index, log.value)
+        })
+        .collect();
+    let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+    tree.extend(entries).unwrap().root_hash
+}
+
+fn mock_factory_deps() -> SnapshotFactoryDependencies {
+    let bytecode: Vec<u8> = (0..32).collect();
+    SnapshotFactoryDependencies {
+        factory_deps: vec![SnapshotFactoryDependency {
+            hash: Some(BytecodeHash::for_bytecode(&bytecode).value()),
+            bytecode: Bytes::from(bytecode),
+        }],
+    }
+}
+
+fn storage_logs_key(l1_batch_number: L1BatchNumber, chunk_id: u64) -> String {
+    SnapshotStorageLogsChunk::<H256>::encode_key(SnapshotStorageLogsStorageKey {
+        l1_batch_number,
+        chunk_id,
+    })
+}
+
+#[derive(Debug)]
+struct TestSnapshots {
+    source: MockSource,
+    object_store: Arc<dyn ObjectStore>,
+}
+
+impl TestSnapshots {
+    fn new() -> Self {
+        Self {
+            source: MockSource::default(),
+            object_store: MockObjectStore::arc(),
+        }
+    }
+
+    /// Persists a snapshot with the specified storage logs. `root_hash` is the reference root hash for the snapshot L1 batch.
+    async fn persist(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        base_l1_batch_number: Option<L1BatchNumber>,
+        logs: &[SnapshotStorageLog],
+        root_hash: H256,
+    ) {
+        for chunk_id in 0..CHUNK_COUNT {
+            let hashed_keys_range = uniform_hashed_keys_chunk(chunk_id, CHUNK_COUNT);
+            let chunk = SnapshotStorageLogsChunk {
+                storage_logs: logs
+                    .iter()
+                    .filter(|log| hashed_keys_range.contains(&log.key))
+                    .cloned()
+                    .collect(),
+            };
+            let key = SnapshotStorageLogsStorageKey {
+                l1_batch_number,
+                chunk_id,
+            };
+            self.object_store.put(key, &chunk).await.unwrap();
+        }
+        self.object_store
+            .put(l1_batch_number, &mock_factory_deps())
+            .await
+            .unwrap();
+
+        let header = SnapshotHeader {
+            version: SnapshotVersion::Version1.into(),
+            l1_batch_number,
+            l2_block_number: L2BlockNumber(l1_batch_number.0 * 2),
+            base_l1_batch_number,
+            storage_logs_chunks: (0..CHUNK_COUNT)
+                .map(|chunk_id| SnapshotStorageLogsChunkMetadata {
+                    chunk_id,
+                    filepath: storage_logs_key(l1_batch_number, chunk_id),
+                })
+                .collect(),
+            factory_deps_filepath: "factory_deps".to_owned(),
+        };
+        self.source.headers.insert(l1_batch_number, header);
+        self.source.root_hashes.insert(l1_batch_number, root_hash);
+    }
+
+    fn verifier(&self) -> SnapshotVerifier<'_> {
+        SnapshotVerifier {
+            source: &self.source,
+            object_store: &*self.object_store,
+            concurrency: NonZeroUsize::new(2).unwrap(),
+            expected_root_hash: None,
+        }
+    }
+}
+
+async fn prepare_full_snapshot() -> (TestSnapshots, Vec<SnapshotStorageLog>) {
+    let l1_batch_number = L1BatchNumber(10);
+    let logs = random_storage_logs(l1_batch_number, 1..101);
+    let root_hash = compute_root_hash(logs.iter());
+    let mut snapshots = TestSnapshots::new();
+    snapshots
+        .persist(l1_batch_number, None, &logs, root_hash)
+        .await;
+    (snapshots, logs)
+}
+
+#[tokio::test]
+async fn verifying_valid_snapshot() {
+    let (snapshots, _) = prepare_full_snapshot().await;
+    let report = snapshots
+        .verifier()
+        .verify(None, PatchSet::default())
+        .await
+        .unwrap();
+    assert!(report.is_passed(), "{report}");
+    assert!(report.to_string().contains("[PASS] root hash"), "{report}");
+
+    let mut verifier = snapshots.verifier();
+    verifier.expected_root_hash = Some(H256::repeat_byte(1));
+    let report = verifier
+        .verify(Some(L1BatchNumber(10)), PatchSet::default())
+        .await
+        .unwrap();
+    assert_eq!(report.failed_checks(), [Check::RootHash]);
+}
+
+#[tokio::test]
+async fn verifying_snapshot_with_missing_chunk() {
+    let (snapshots, _) = prepare_full_snapshot().await;
+    snapshots
+        .object_store
+        .remove_raw(
+            Bucket::StorageSnapshot,
+            &storage_logs_key(L1BatchNumber(10), 1),
+        )
+        .await
+        .unwrap();
+
+    let report = snapshots
+        .verifier()
+        .verify(None, PatchSet::default())
+        .await
+        .unwrap();
+    assert_eq!(
+        report.failed_checks(),
+        [Check::ChunkCompleteness, Check::RootHash]
+    );
+}
+
+#[tokio::test]
+async fn verifying_snapshot_with_tampered_storage_log() {
+    let l1_batch_number = L1BatchNumber(10);
+    let mut logs = random_storage_logs(l1_batch_number, 1..101);
+    let root_hash = compute_root_hash(logs.iter());
+    logs[42].value = H256::repeat_byte(0xff);
+    let mut snapshots = TestSnapshots::new();
+    snapshots
+        .persist(l1_batch_number, None, &logs, root_hash)
+        .await;
+
+    let report = snapshots
+        .verifier()
+        .verify(None, PatchSet::default())
+        .await
+        .unwrap();
+    assert_eq!(report.failed_checks(), [Check::RootHash]);
+}
+
+#[tokio::test]
+async fn verifying_snapshot_with_misplaced_storage_log() {
+    let (snapshots, logs) = prepare_full_snapshot().await;
+    let l1_batch_number = L1BatchNumber(10);
+    let first_chunk_range = uniform_hashed_keys_chunk(0, CHUNK_COUNT);
+    let (mut first_chunk_logs, other_logs): (Vec<_>, Vec<_>) = logs
+        .into_iter()
+        .partition(|log| first_chunk_range.contains(&log.key));
+    first_chunk_logs.push(other_logs[0].clone());
+    let key = SnapshotStorageLogsStorageKey {
+        l1_batch_number,
+        chunk_id: 0,
+    };
+    let chunk = SnapshotStorageLogsChunk {
+        storage_logs: first_chunk_logs,
+    };
+    snapshots.object_store.put(key, &chunk).await.unwrap();
+
+    let report = snapshots
+        .verifier()
+        .verify(None, PatchSet::default())
+        .await
+        .unwrap();
+    assert_eq!(report.failed_checks(), [Check::StorageLogs]);
+    assert!(
+        report.to_string().contains("outside the chunk key range"),
+        "{report}"
+    );
+}
+
+#[tokio::test]
+async fn verifying_snapshot_with_invalid_factory_dep() {
+    let (snapshots, _) = prepare_full_snapshot().await;
+    let mut factory_deps = mock_factory_deps();
+    factory_deps.factory_deps[0].bytecode.0[0] = 1;
+    snapshots
+        .object_store
+        .put(L1BatchNumber(10), &factory_deps)
+        .await
+        .unwrap();
+
+    let report = snapshots
+        .verifier()
+        .verify(None, PatchSet::default())
+        .await
+        .unwrap();
+    assert_eq!(report.failed_checks(), [Check::FactoryDeps]);
+}
+
+#[tokio::test]
+async fn verifying_differential_snapshot() {
+    let (mut snapshots, logs) = prepare_full_snapshot().await;
+    let l1_batch_number = L1BatchNumber(20);
+    let mut changed_logs: Vec<_> = logs
+        .iter()
+        .step_by(3)
+        .map(|log| SnapshotStorageLog {
+            value: StorageValue::random(),
+            ..log.clone()
+        })
+        .collect();
+    changed_logs.extend(random_storage_logs(l1_batch_number, 101..121));
+
+    let mut merged_logs: HashMap<_, _> = logs.iter().map(|log| (log.key, log)).collect();
+    merged_logs.extend(changed_logs.iter().map(|log| (log.key, log)));
+    let root_hash = compute_root_hash(merged_logs.into_values());
+    snapshots
+        .persist(
+            l1_batch_number,
+            Some(L1BatchNumber(10)),
+            &changed_logs,
+            root_hash,
+        )
+        .await;
+
+    let report = snapshots
+        .verifier()
+        .verify(None, PatchSet::default())
+        .await
+        .unwrap();
+    assert!(report.is_passed(), "{report}");
+    assert!(
+        report.to_string().contains("1 differential snapshot(s)"),
+        "{report}"
+    );
+
+    // Break the chain by removing a chunk of the base snapshot.
+    snapshots
+        .object_store
+        .remove_raw(
+            Bucket::StorageSnapshot,
+            &storage_logs_key(L1BatchNumber(10), 0),
+        )
+        .await
+        .unwrap();
+    let report = snapshots
+        .verifier()
+        .verify(None, PatchSet::default())
+        .await
+        .unwrap();
+    assert_eq!(
+        report.failed_checks(),
+        [Check::ChunkCompleteness, Check::RootHash]
+    );
+}
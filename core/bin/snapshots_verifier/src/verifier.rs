@@ -0,0 +1,665 @@
+//! Snapshot verification logic.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    num::NonZeroUsize,
+};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use futures::{StreamExt, TryStreamExt};
+use zksync_merkle_tree::{recovery::MerkleTreeRecovery, PruneDatabase, TreeEntry};
+use zksync_object_store::{ObjectStore, ObjectStoreError};
+use zksync_types::{
+    bytecode::{BytecodeHash, BytecodeMarker},
+    snapshots::{
+        uniform_hashed_keys_chunk, SnapshotFactoryDependencies, SnapshotHeader, SnapshotStorageLog,
+        SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey, SnapshotVersion,
+    },
+    L1BatchNumber, StorageKey, H256, U256,
+};
+use zksync_web3_decl::{
+    client::{DynClient, L2},
+    error::ClientRpcContext,
+    namespaces::{SnapshotsNamespaceClient, ZksNamespaceClient},
+};
+
+/// Maximum number of individual problems (e.g., misplaced storage logs) listed in the report for a single check.
+const MAX_REPORTED_PROBLEMS: usize = 10;
+/// Upper bound on enumeration indices accepted by the verifier. Guards against allocating a huge bit set
+/// for a corrupted index; real indices are bounded by the number of storage slots, which is orders of magnitude lower.
+const MAX_ENUMERATION_INDEX: u64 = 1 << 36;
+
+/// Source of snapshot metadata. Normally, this is the JSON-RPC API of the main node.
+#[async_trait]
+pub(crate) trait SnapshotMetadataSource: fmt::Debug + Send + Sync {
+    async fn fetch_newest_snapshot_l1_batch_number(&self) -> anyhow::Result<Option<L1BatchNumber>>;
+
+    async fn fetch_snapshot(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Option<SnapshotHeader>>;
+
+    /// Fetches the Merkle tree root hash after the specified L1 batch.
+    async fn fetch_l1_batch_root_hash(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Option<H256>>;
+}
+
+#[async_trait]
+impl SnapshotMetadataSource for Box<DynClient<L2>> {
+    async fn fetch_newest_snapshot_l1_batch_number(&self) -> anyhow::Result<Option<L1BatchNumber>> {
+        let snapshots = self
+            .get_all_snapshots()
+            .rpc_context("get_all_snapshots")
+            .await?;
+        Ok(snapshots.newest_l1_batch_number())
+    }
+
+    async fn fetch_snapshot(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Option<SnapshotHeader>> {
+        Ok(self
+            .get_snapshot_by_l1_batch_number(l1_batch_number)
+            .rpc_context("get_snapshot_by_l1_batch_number")
+            .with_arg("number", &l1_batch_number)
+            .await?)
+    }
+
+    async fn fetch_l1_batch_root_hash(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Option<H256>> {
+        let details = self
+            .get_l1_batch_details(l1_batch_number)
+            .rpc_context("get_l1_batch_details")
+            .with_arg("number", &l1_batch_number)
+            .await?;
+        Ok(details.and_then(|details| details.base.root_hash))
+    }
+}
+
+/// Check performed by [`SnapshotVerifier`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Check {
+    /// Snapshot headers are well-formed; for a differential snapshot, the chain of base snapshots is consistent.
+    Header,
+    /// Factory dependency hashes match the bytecodes.
+    FactoryDeps,
+    /// All storage log chunks are present in the object store.
+    ChunkCompleteness,
+    /// Storage logs are placed into the chunks covering their keys, and keys and enumeration indices are unique.
+    StorageLogs,
+    /// Merkle tree root hash recomputed from storage logs matches the reference root hash.
+    RootHash,
+}
+
+impl Check {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Header => "snapshot header",
+            Self::FactoryDeps => "factory deps",
+            Self::ChunkCompleteness => "chunk completeness",
+            Self::StorageLogs => "storage logs",
+            Self::RootHash => "root hash",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CheckOutcome {
+    check: Check,
+    /// Details of the outcome; `Ok(_)` for a passed check, `Err(_)` for a failed one.
+    result: Result<String, String>,
+}
+
+/// Pass / fail report produced by [`SnapshotVerifier`].
+#[derive(Debug)]
+pub(crate) struct VerificationReport {
+    l1_batch_number: L1BatchNumber,
+    outcomes: Vec<CheckOutcome>,
+}
+
+impl VerificationReport {
+    fn new(l1_batch_number: L1BatchNumber) -> Self {
+        Self {
+            l1_batch_number,
+            outcomes: vec![],
+        }
+    }
+
+    fn record(&mut self, check: Check, result: anyhow::Result<String>) {
+        let result = result.map_err(|err| format!("{err:#}"));
+        match &result {
+            Ok(details) => tracing::info!("Check `{}` passed: {details}", check.as_str()),
+            Err(details) => tracing::warn!("Check `{}` failed: {details}", check.as_str()),
+        }
+        self.outcomes.push(CheckOutcome { check, result });
+    }
+
+    pub(crate) fn is_passed(&self) -> bool {
+        self.outcomes.iter().all(|outcome| outcome.result.is_ok())
+    }
+
+    #[cfg(test)]
+    pub(crate) fn failed_checks(&self) -> Vec<Check> {
+        self.outcomes
+            .iter()
+            .filter(|outcome| outcome.result.is_err())
+            .map(|outcome| outcome.check)
+            .collect()
+    }
+}
+
+impl fmt::Display for VerificationReport {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            formatter,
+            "Verification report for snapshot at L1 batch #{}:",
+            self.l1_batch_number
+        )?;
+        for outcome in &self.outcomes {
+            let (status, details) = match &outcome.result {
+                Ok(details) => ("PASS", details),
+                Err(details) => ("FAIL", details),
+            };
+            writeln!(
+                formatter,
+                "  [{status}] {}: {details}",
+                outcome.check.as_str()
+            )?;
+        }
+        let summary = if self.is_passed() { "PASSED" } else { "FAILED" };
+        write!(formatter, "Snapshot verification {summary}")
+    }
+}
+
+/// Snapshot together with all its base snapshots.
+#[derive(Debug)]
+struct SnapshotChain {
+    version: SnapshotVersion,
+    chunk_count: u64,
+    /// Ordered starting from the full snapshot; the last header corresponds to the verified snapshot.
+    headers: Vec<SnapshotHeader>,
+}
+
+impl SnapshotChain {
+    fn l1_batch_numbers(&self) -> impl Iterator<Item = L1BatchNumber> + '_ {
+        self.headers.iter().map(|header| header.l1_batch_number)
+    }
+
+    fn describe(&self) -> String {
+        let differential_count = self.headers.len() - 1;
+        let mut description = format!(
+            "version {:?}, {} storage log chunks",
+            self.version, self.chunk_count
+        );
+        if differential_count > 0 {
+            let full_l1_batch_number = self.headers[0].l1_batch_number;
+            description += &format!(
+                ", {differential_count} differential snapshot(s) on top of the full snapshot \
+                 for L1 batch #{full_l1_batch_number}"
+            );
+        }
+        description
+    }
+}
+
+/// Storage logs for a single chunk loaded for all snapshots in the chain.
+#[derive(Debug, Default)]
+struct ChainChunk {
+    logs: Vec<(L1BatchNumber, Vec<SnapshotStorageLog>)>,
+    /// L1 batches of the snapshots for which the chunk is missing in the object store.
+    missing: Vec<L1BatchNumber>,
+}
+
+/// Checks storage logs chunk by chunk, merging logs from differential snapshots into base ones.
+#[derive(Debug)]
+struct StorageLogsChecker {
+    chunk_count: u64,
+    /// Bit set of the encountered enumeration indices.
+    enumeration_indices: Vec<u64>,
+    max_enumeration_index: u64,
+    log_count: u64,
+    problems: Vec<String>,
+    problem_count: usize,
+}
+
+impl StorageLogsChecker {
+    fn new(chunk_count: u64) -> Self {
+        Self {
+            chunk_count,
+            enumeration_indices: vec![],
+            max_enumeration_index: 0,
+            log_count: 0,
+            problems: vec![],
+            problem_count: 0,
+        }
+    }
+
+    fn report_problem(&mut self, problem: String) {
+        self.problem_count += 1;
+        if self.problems.len() < MAX_REPORTED_PROBLEMS {
+            self.problems.push(problem);
+        }
+    }
+
+    /// Records the enumeration index of a storage log, returning `false` if it is invalid.
+    fn check_enumeration_index(&mut self, log: &SnapshotStorageLog) -> bool {
+        let index = log.enumeration_index;
+        if index == 0 || index > MAX_ENUMERATION_INDEX {
+            self.report_problem(format!("storage log {log:?} has invalid enumeration index"));
+            return false;
+        }
+
+        let (word, bit) = ((index / 64) as usize, index % 64);
+        if word >= self.enumeration_indices.len() {
+            self.enumeration_indices.resize(word + 1, 0);
+        }
+        if self.enumeration_indices[word] & (1 << bit) != 0 {
+            self.report_problem(format!(
+                "storage log {log:?} has non-unique enumeration index"
+            ));
+            return false;
+        }
+        self.enumeration_indices[word] |= 1 << bit;
+        self.max_enumeration_index = self.max_enumeration_index.max(index);
+        true
+    }
+
+    /// Checks and merges storage logs in a chunk, returning the corresponding tree entries.
+    fn check_chunk(
+        &mut self,
+        chunk_id: u64,
+        chunk: Vec<(L1BatchNumber, Vec<SnapshotStorageLog>)>,
+    ) -> Vec<TreeEntry> {
+        let hashed_keys_range = uniform_hashed_keys_chunk(chunk_id, self.chunk_count);
+        let mut merged_logs = HashMap::<H256, SnapshotStorageLog>::new();
+        for (i, (l1_batch_number, logs)) in chunk.into_iter().enumerate() {
+            let is_differential = i > 0;
+            let mut snapshot_keys = HashSet::with_capacity(logs.len());
+            for log in logs {
+                if !hashed_keys_range.contains(&log.key) {
+                    self.report_problem(format!(
+                        "storage log {log:?} in chunk {chunk_id} of snapshot for L1 batch #{l1_batch_number} \
+                         is outside the chunk key range {hashed_keys_range:?}"
+                    ));
+                    continue;
+                }
+                if !snapshot_keys.insert(log.key) {
+                    self.report_problem(format!(
+                        "key {:?} is duplicated in chunk {chunk_id} of snapshot for L1 batch #{l1_batch_number}",
+                        log.key
+                    ));
+                    continue;
+                }
+                if log.l1_batch_number_of_initial_write > l1_batch_number {
+                    self.report_problem(format!(
+                        "storage log {log:?} in snapshot for L1 batch #{l1_batch_number} has initial write from the future"
+                    ));
+                }
+                if is_differential {
+                    if let Some(prev_log) = merged_logs.get(&log.key) {
+                        if prev_log.enumeration_index != log.enumeration_index
+                            || prev_log.l1_batch_number_of_initial_write
+                                != log.l1_batch_number_of_initial_write
+                        {
+                            self.report_problem(format!(
+                                "storage log {log:?} in differential snapshot for L1 batch #{l1_batch_number} \
+                                 is inconsistent with the base snapshot log {prev_log:?}"
+                            ));
+                        }
+                    }
+                }
+                merged_logs.insert(log.key, log);
+            }
+        }
+
+        let mut entries = Vec::with_capacity(merged_logs.len());
+        for log in merged_logs.into_values() {
+            self.log_count += 1;
+            if self.check_enumeration_index(&log) {
+                let key = U256::from_little_endian(log.key.as_bytes());
+                entries.push(TreeEntry::new(key, log.enumeration_index, log.value));
+            }
+        }
+        entries
+    }
+
+    fn finish(self, is_complete: bool) -> anyhow::Result<String> {
+        if self.problem_count > 0 {
+            anyhow::bail!(
+                "found {} problem(s) with storage logs, e.g.: {}",
+                self.problem_count,
+                self.problems.join("; ")
+            );
+        }
+        // Enumeration indices are unique at this point, so it's sufficient to compare the max index and the number of logs.
+        if is_complete {
+            anyhow::ensure!(
+                self.max_enumeration_index == self.log_count,
+                "enumeration indices are not contiguous: max index is {}, while there are {} storage logs",
+                self.max_enumeration_index,
+                self.log_count
+            );
+        }
+        Ok(format!(
+            "{} storage logs with unique keys and enumeration indices placed into correct chunks",
+            self.log_count
+        ))
+    }
+}
+
+/// Verifies snapshots without applying them to a node.
+#[derive(Debug)]
+pub(crate) struct SnapshotVerifier<'a> {
+    pub source: &'a dyn SnapshotMetadataSource,
+    pub object_store: &'a dyn ObjectStore,
+    /// Maximum number of storage log chunks loaded concurrently.
+    pub concurrency: NonZeroUsize,
+    /// Reference root hash for the snapshot L1 batch (e.g., taken from L1). If not set, the root hash
+    /// is fetched from the metadata source.
+    pub expected_root_hash: Option<H256>,
+}
+
+impl SnapshotVerifier<'_> {
+    /// Verifies the snapshot for the specified L1 batch (or the newest snapshot if the L1 batch is not specified).
+    /// The Merkle tree is recomputed in `tree_db`, which must be empty.
+    ///
+    /// Problems with the snapshot data are recorded in the returned report; errors are only returned
+    /// if the verification cannot proceed (e.g., the object store is unreachable).
+    pub async fn verify<DB>(
+        &self,
+        l1_batch_number: Option<L1BatchNumber>,
+        tree_db: DB,
+    ) -> anyhow::Result<VerificationReport>
+    where
+        DB: PruneDatabase + Send + 'static,
+    {
+        let l1_batch_number = match l1_batch_number {
+            Some(number) => number,
+            None => self
+                .source
+                .fetch_newest_snapshot_l1_batch_number()
+                .await?
+                .context("no snapshots are available")?,
+        };
+        tracing::info!("Verifying snapshot for L1 batch #{l1_batch_number}");
+        let mut report = VerificationReport::new(l1_batch_number);
+
+        let chain = match self.load_snapshot_chain(l1_batch_number).await {
+            Ok(chain) => {
+                report.record(Check::Header, Ok(chain.describe()));
+                chain
+            }
+            Err(err) => {
+                report.record(Check::Header, Err(err));
+                return Ok(report);
+            }
+        };
+        let factory_deps_result = self.check_factory_deps(&chain).await;
+        report.record(Check::FactoryDeps, factory_deps_result);
+
+        let recovered_version = l1_batch_number.0.into();
+        let mut recovery = tokio::task::spawn_blocking(move || {
+            MerkleTreeRecovery::new(tree_db, recovered_version)
+        })
+        .await
+        .context("panicked initializing Merkle tree recovery")??;
+
+        let chunk_count = chain.chunk_count;
+        let mut checker = StorageLogsChecker::new(chunk_count);
+        let mut missing_chunks = vec![];
+        let chain_ref = &chain;
+        let mut chunks = futures::stream::iter(0..chunk_count)
+            .map(move |chunk_id| async move {
+                let chunk = self.load_chunk(chain_ref, chunk_id).await?;
+                anyhow::Ok((chunk_id, chunk))
+            })
+            .buffered(self.concurrency.get());
+
+        while let Some((chunk_id, chunk)) = chunks.try_next().await? {
+            if !chunk.missing.is_empty() {
+                missing_chunks.extend(
+                    chunk
+                        .missing
+                        .into_iter()
+                        .map(|l1_batch_number| (l1_batch_number, chunk_id)),
+                );
+                continue;
+            }
+
+            let entries = checker.check_chunk(chunk_id, chunk.logs);
+            recovery = tokio::task::spawn_blocking(move || {
+                recovery.extend_random(entries)?;
+                anyhow::Ok(recovery)
+            })
+            .await
+            .context("panicked extending Merkle tree")??;
+            tracing::info!(
+                "Processed {}/{chunk_count} storage log chunks",
+                chunk_id + 1
+            );
+        }
+
+        let is_complete = missing_chunks.is_empty();
+        let completeness_result = if is_complete {
+            Ok(format!(
+                "all {chunk_count} chunks are present for {} snapshot(s)",
+                chain.headers.len()
+            ))
+        } else {
+            let reported_chunks: Vec<_> = missing_chunks
+                .iter()
+                .take(MAX_REPORTED_PROBLEMS)
+                .map(|(l1_batch_number, chunk_id)| {
+                    format!("chunk {chunk_id} for L1 batch #{l1_batch_number}")
+                })
+                .collect();
+            Err(anyhow::anyhow!(
+                "{} chunk(s) are missing, e.g.: {}",
+                missing_chunks.len(),
+                reported_chunks.join(", ")
+            ))
+        };
+        report.record(Check::ChunkCompleteness, completeness_result);
+        report.record(Check::StorageLogs, checker.finish(is_complete));
+
+        let root_hash_result = if is_complete {
+            let root_hash = recovery.root_hash();
+            self.check_root_hash(l1_batch_number, root_hash).await
+        } else {
+            Err(anyhow::anyhow!(
+                "cannot recompute root hash since some storage log chunks are missing"
+            ))
+        };
+        report.record(Check::RootHash, root_hash_result);
+        Ok(report)
+    }
+
+    async fn load_snapshot_chain(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<SnapshotChain> {
+        let mut headers = vec![];
+        let mut next_l1_batch_number = Some(l1_batch_number);
+        while let Some(number) = next_l1_batch_number {
+            let header = self
+                .source
+                .fetch_snapshot(number)
+                .await?
+                .with_context(|| format!("snapshot for L1 batch #{number} does not exist"))?;
+            anyhow::ensure!(
+                header.l1_batch_number == number,
+                "requested snapshot for L1 batch #{number}, but got snapshot for L1 batch #{}",
+                header.l1_batch_number
+            );
+            if let Some(base_l1_batch_number) = header.base_l1_batch_number {
+                anyhow::ensure!(
+                    base_l1_batch_number < number,
+                    "base L1 batch #{base_l1_batch_number} of snapshot for L1 batch #{number} is not older than the snapshot"
+                );
+            }
+            next_l1_batch_number = header.base_l1_batch_number;
+            headers.push(header);
+        }
+        headers.reverse();
+
+        let raw_version = headers[0].version;
+        let version = SnapshotVersion::try_from(raw_version)
+            .with_context(|| format!("unrecognized snapshot version: {raw_version}"))?;
+        let chunk_count = headers[0].storage_logs_chunks.len() as u64;
+        anyhow::ensure!(chunk_count > 0, "snapshot has no storage log chunks");
+        anyhow::ensure!(
+            headers.len() == 1 || version == SnapshotVersion::Version1,
+            "differential snapshots are only supported for version 1 snapshots"
+        );
+
+        for header in &headers {
+            let number = header.l1_batch_number;
+            anyhow::ensure!(
+                header.version == raw_version,
+                "snapshot for L1 batch #{number} has version {}, while the full snapshot has version {raw_version}",
+                header.version
+            );
+            anyhow::ensure!(
+                header.storage_logs_chunks.len() as u64 == chunk_count,
+                "snapshot for L1 batch #{number} has {} storage log chunks, while the full snapshot has {chunk_count}",
+                header.storage_logs_chunks.len()
+            );
+            for (i, chunk) in header.storage_logs_chunks.iter().enumerate() {
+                anyhow::ensure!(
+                    chunk.chunk_id == i as u64,
+                    "storage log chunks of snapshot for L1 batch #{number} are not contiguous: \
+                     expected chunk ID {i}, got {}",
+                    chunk.chunk_id
+                );
+            }
+        }
+        Ok(SnapshotChain {
+            version,
+            chunk_count,
+            headers,
+        })
+    }
+
+    async fn check_factory_deps(&self, chain: &SnapshotChain) -> anyhow::Result<String> {
+        let mut dep_count = 0;
+        let mut unhashed_dep_count = 0;
+        for l1_batch_number in chain.l1_batch_numbers() {
+            let deps: SnapshotFactoryDependencies = self
+                .object_store
+                .get(l1_batch_number)
+                .await
+                .with_context(|| {
+                    format!(
+                        "failed loading factory deps for snapshot at L1 batch #{l1_batch_number}"
+                    )
+                })?;
+            for dep in &deps.factory_deps {
+                dep_count += 1;
+                let Some(hash) = dep.hash else {
+                    // Old snapshots don't include bytecode hashes, so there's nothing to compare with.
+                    unhashed_dep_count += 1;
+                    continue;
+                };
+                let parsed_hash = BytecodeHash::try_from(hash).with_context(|| {
+                    format!(
+                        "invalid bytecode hash {hash:?} in snapshot at L1 batch #{l1_batch_number}"
+                    )
+                })?;
+                let restored_hash = match parsed_hash.marker() {
+                    BytecodeMarker::EraVm => BytecodeHash::for_bytecode(&dep.bytecode.0),
+                    BytecodeMarker::Evm => {
+                        BytecodeHash::for_evm_bytecode(parsed_hash.len_in_bytes(), &dep.bytecode.0)
+                    }
+                };
+                anyhow::ensure!(
+                    parsed_hash == restored_hash,
+                    "bytecode hash {hash:?} in snapshot at L1 batch #{l1_batch_number} doesn't match \
+                     the restored hash {:?}",
+                    restored_hash.value()
+                );
+            }
+        }
+
+        let mut details = format!(
+            "{} factory deps have matching hashes",
+            dep_count - unhashed_dep_count
+        );
+        if unhashed_dep_count > 0 {
+            details += &format!(
+                "; {unhashed_dep_count} deps without hashes (legacy snapshot format) were not checked"
+            );
+        }
+        Ok(details)
+    }
+
+    async fn load_chunk(&self, chain: &SnapshotChain, chunk_id: u64) -> anyhow::Result<ChainChunk> {
+        let mut chunk = ChainChunk::default();
+        for l1_batch_number in chain.l1_batch_numbers() {
+            let key = SnapshotStorageLogsStorageKey {
+                l1_batch_number,
+                chunk_id,
+            };
+            match self.load_storage_logs(key, chain.version).await {
+                Ok(logs) => chunk.logs.push((l1_batch_number, logs)),
+                Err(ObjectStoreError::KeyNotFound(_)) => chunk.missing.push(l1_batch_number),
+                Err(err) => {
+                    return Err(anyhow::Error::from(err)
+                        .context(format!("failed loading storage logs chunk {key:?}")));
+                }
+            }
+        }
+        Ok(chunk)
+    }
+
+    async fn load_storage_logs(
+        &self,
+        key: SnapshotStorageLogsStorageKey,
+        version: SnapshotVersion,
+    ) -> Result<Vec<SnapshotStorageLog>, ObjectStoreError> {
+        Ok(match version {
+            SnapshotVersion::Version0 => {
+                let chunk: SnapshotStorageLogsChunk<StorageKey> =
+                    self.object_store.get(key).await?;
+                chunk
+                    .storage_logs
+                    .into_iter()
+                    .map(SnapshotStorageLog::drop_key_preimage)
+                    .collect()
+            }
+            SnapshotVersion::Version1 => {
+                let chunk: SnapshotStorageLogsChunk = self.object_store.get(key).await?;
+                chunk.storage_logs
+            }
+        })
+    }
+
+    async fn check_root_hash(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        root_hash: H256,
+    ) -> anyhow::Result<String> {
+        let (expected_root_hash, source) = if let Some(hash) = self.expected_root_hash {
+            (hash, "expected")
+        } else {
+            let hash = self
+                .source
+                .fetch_l1_batch_root_hash(l1_batch_number)
+                .await?
+                .with_context(|| format!("root hash for L1 batch #{l1_batch_number} is unknown"))?;
+            (hash, "reported")
+        };
+        anyhow::ensure!(
+            root_hash == expected_root_hash,
+            "recomputed root hash {root_hash:?} differs from the {source} root hash {expected_root_hash:?}"
+        );
+        Ok(format!(
+            "recomputed root hash {root_hash:?} matches the {source} root hash"
+        ))
+    }
+}
@@ -43,6 +43,10 @@ pub(crate) enum Component {
     ExternalProofIntegrationApi,
     /// VM runner-based component that allows to test experimental VM features. Doesn't save any data to Postgres.
     VmPlayground,
+    /// Component pushing alerts about the node state to webhooks.
+    Notifier,
+    /// Authenticated HTTP API allowing to pause / resume tasks and update dynamic configuration at runtime.
+    AdminApi,
 }
 
 #[derive(Debug)]
@@ -90,6 +94,8 @@ impl FromStr for Components {
             "external_proof_integration_api" => {
                 Ok(Components(vec![Component::ExternalProofIntegrationApi]))
             }
+            "notifier" => Ok(Components(vec![Component::Notifier])),
+            "admin_api" => Ok(Components(vec![Component::AdminApi])),
             other => Err(format!("{} is not a valid component name", other)),
         }
     }
@@ -4,16 +4,21 @@
 use std::{mem, time::Duration};
 
 use anyhow::{bail, Context};
+use zksync_admin_api::node::AdminApiLayer;
 use zksync_base_token_adjuster::node::{
     BaseTokenRatioPersisterLayer, BaseTokenRatioProviderLayer, ExternalPriceApiLayer,
 };
-use zksync_circuit_breaker::node::{CircuitBreakerCheckerLayer, ReplicationLagCheckerLayer};
+use zksync_circuit_breaker::node::{
+    CircuitBreakerCheckerLayer, CommitToExecuteLagCheckerLayer, DaBalanceCheckerLayer,
+    L1BalanceCheckerLayer, ReplicationLagCheckerLayer, UnprovenBatchesCheckerLayer,
+};
 use zksync_commitment_generator::node::{
     CommitmentGeneratorLayer, L1BatchCommitmentModeValidationLayer,
 };
 use zksync_config::{
     configs::{
         api::Namespace,
+        chain::CircuitBreakerMode,
         consensus::ConsensusConfig,
         contracts::{
             chain::{L2Contracts, ProofManagerContracts},
@@ -58,8 +63,8 @@ use zksync_metadata_calculator::{
 use zksync_node_api_server::{
     node::{
         DeploymentAllowListLayer, HealthCheckLayer, MasterPoolSinkLayer, MempoolCacheLayer,
-        PostgresStorageCachesConfig, TxSenderLayer, Web3ServerLayer, Web3ServerOptionalConfig,
-        WhitelistedMasterPoolSinkLayer,
+        PostgresStorageCachesConfig, TxPolicyLayer, TxSenderLayer, Web3ServerLayer,
+        Web3ServerOptionalConfig, WhitelistedMasterPoolSinkLayer,
     },
     tx_sender::TxSenderConfig,
     web3::state::InternalApiConfigBase,
@@ -70,6 +75,7 @@ use zksync_node_framework::service::{ZkStackService, ZkStackServiceBuilder};
 use zksync_node_storage_init::node::{
     main_node_strategy::MainNodeInitStrategyLayer, NodeStorageInitializerLayer,
 };
+use zksync_notifier::node::NotifierLayer;
 use zksync_object_store::node::ObjectStoreLayer;
 use zksync_proof_data_handler::node::ProofDataHandlerLayer;
 use zksync_settlement_layer_data::{MainNodeConfig, SettlementLayerData};
@@ -274,7 +280,11 @@ impl MainNodeBuilder {
             sk_config.shared.save_call_traces,
             OPTIONAL_BYTECODE_COMPRESSION,
         )
-        .with_fast_vm_mode(experimental_vm_config.state_keeper_fast_vm_mode);
+        .with_fast_vm_mode(experimental_vm_config.state_keeper_fast_vm_mode)
+        .with_speculation(
+            experimental_vm_config.state_keeper_speculation_workers,
+            experimental_vm_config.state_keeper_speculation_lookahead,
+        );
 
         let rocksdb_options = RocksdbStorageOptions {
             block_cache_capacity: db_config
@@ -382,6 +392,15 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_tx_policy_layer(mut self) -> anyhow::Result<Self> {
+        let tx_policy = try_load_config!(self.configs.state_keeper_config).tx_policy;
+
+        if tx_policy.is_enabled() {
+            self.node.add_layer(TxPolicyLayer { config: tx_policy });
+        }
+        Ok(self)
+    }
+
     fn add_bridge_addresses_updater_layer(mut self) -> anyhow::Result<Self> {
         self.node.add_layer(BridgeAddressesUpdaterLayer {
             refresh_interval: Duration::from_secs(30),
@@ -467,6 +486,7 @@ impl MainNodeBuilder {
             with_extended_tracing: rpc_config.extended_api_tracing,
             // Pruning isn't supposed to be enabled for the main node at the moment, but we use a reasonable value just in case.
             pruning_info_refresh_interval: Duration::from_secs(10),
+            pruning_archive: None,
             polling_interval: rpc_config.pubsub_polling_interval,
         };
         let base =
@@ -531,6 +551,22 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_notifier_layer(mut self) -> anyhow::Result<Self> {
+        self.node.add_layer(NotifierLayer {
+            config: self.configs.notifier.clone(),
+            secrets: self.secrets.notifier.clone(),
+        });
+        Ok(self)
+    }
+
+    fn add_admin_api_layer(mut self) -> anyhow::Result<Self> {
+        self.node.add_layer(AdminApiLayer {
+            config: self.configs.admin_api.clone(),
+            secrets: self.secrets.admin_api.clone(),
+        });
+        Ok(self)
+    }
+
     fn add_commitment_generator_layer(mut self) -> anyhow::Result<Self> {
         self.node.add_layer(CommitmentGeneratorLayer::default());
 
@@ -553,9 +589,73 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_l1_balance_checker_layer(mut self) -> anyhow::Result<Self> {
+        if let Some(config) = self.configs.circuit_breaker_config.l1_balance.clone() {
+            self.node.add_layer(L1BalanceCheckerLayer(config));
+        }
+        Ok(self)
+    }
+
+    fn add_da_balance_checker_layer(mut self) -> anyhow::Result<Self> {
+        if let Some(config) = self.configs.circuit_breaker_config.da_balance.clone() {
+            self.node.add_layer(DaBalanceCheckerLayer(config));
+        }
+        Ok(self)
+    }
+
+    fn add_l1_batch_lag_checker_layers(mut self) -> anyhow::Result<Self> {
+        let circuit_breaker_config = &self.configs.circuit_breaker_config;
+        if let Some(config) = circuit_breaker_config.unproven_batches.clone() {
+            self.node.add_layer(UnprovenBatchesCheckerLayer(config));
+        }
+        if let Some(config) = circuit_breaker_config.commit_to_execute_lag.clone() {
+            self.node.add_layer(CommitToExecuteLagCheckerLayer(config));
+        }
+        Ok(self)
+    }
+
+    /// Balance circuit breakers run together with the components they monitor, while pausing transaction acceptance
+    /// only affects the API server in the same process. Thus, the pausing mode is rejected if it would have no effect.
+    fn validate_circuit_breaker_modes(&self, components: &[Component]) -> anyhow::Result<()> {
+        if components.contains(&Component::HttpApi) || components.contains(&Component::WsApi) {
+            return Ok(());
+        }
+
+        let circuit_breaker_config = &self.configs.circuit_breaker_config;
+        let pauses_txs = |mode: CircuitBreakerMode| mode == CircuitBreakerMode::PauseTxs;
+        let runs_l1_balance_checker = components.contains(&Component::EthTxAggregator)
+            || components.contains(&Component::EthTxManager);
+        if runs_l1_balance_checker
+            && circuit_breaker_config
+                .l1_balance
+                .as_ref()
+                .is_some_and(|config| pauses_txs(config.mode))
+        {
+            bail!(
+                "L1 balance circuit breaker in `pause_txs` mode requires `http_api` or `ws_api` to run in the same process \
+                 as `eth_tx_aggregator` / `eth_tx_manager`"
+            );
+        }
+        if components.contains(&Component::DADispatcher)
+            && circuit_breaker_config
+                .da_balance
+                .as_ref()
+                .is_some_and(|config| pauses_txs(config.mode))
+        {
+            bail!(
+                "DA balance circuit breaker in `pause_txs` mode requires `http_api` or `ws_api` to run in the same process \
+                 as `da_dispatcher`"
+            );
+        }
+        Ok(())
+    }
+
     fn add_contract_verification_api_layer(mut self) -> anyhow::Result<Self> {
         let config = self.configs.contract_verifier.clone();
-        self.node.add_layer(ContractVerificationApiLayer(config));
+        self.node.add_layer(ContractVerificationApiLayer {
+            config,
+            l2_chain_id: self.genesis_config.l2_chain_id,
+        });
         Ok(self)
     }
 
@@ -756,6 +856,7 @@ impl MainNodeBuilder {
             .add_pools_layer()?
             .add_object_store_layer()?
             .add_circuit_breaker_checker_layer()?
+            .add_l1_batch_lag_checker_layers()?
             .add_healthcheck_layer()?
             .add_prometheus_exporter_layer()?
             .add_query_eth_client_layer()?
@@ -780,11 +881,14 @@ impl MainNodeBuilder {
             // Default priority.
             _ => 0,
         });
+        self.validate_circuit_breaker_modes(&components)?;
 
         if components.contains(&Component::EthTxAggregator)
             || components.contains(&Component::EthTxManager)
         {
-            self = self.add_pk_signing_client_layer()?;
+            self = self
+                .add_pk_signing_client_layer()?
+                .add_l1_balance_checker_layer()?;
         }
         if components.contains(&Component::HttpApi) || components.contains(&Component::WsApi) {
             self = self.add_replication_lag_checker_layer()?;
@@ -799,6 +903,7 @@ impl MainNodeBuilder {
                     // which is why we consider it to be responsible for the storage initialization.
                     self = self
                         .add_allow_list_task_layer()?
+                        .add_tx_policy_layer()?
                         .add_l1_gas_layer()?
                         .add_storage_initialization_layer(LayerKind::Task)?
                         .add_state_keeper_layer()?
@@ -807,6 +912,7 @@ impl MainNodeBuilder {
                 Component::HttpApi => {
                     self = self
                         .add_allow_list_task_layer()?
+                        .add_tx_policy_layer()?
                         .add_bridge_addresses_updater_layer()?
                         .add_l1_gas_layer()?
                         .add_tx_sender_layer()?
@@ -817,6 +923,7 @@ impl MainNodeBuilder {
                 Component::WsApi => {
                     self = self
                         .add_allow_list_task_layer()?
+                        .add_tx_policy_layer()?
                         .add_bridge_addresses_updater_layer()?
                         .add_l1_gas_layer()?
                         .add_tx_sender_layer()?
@@ -866,7 +973,10 @@ impl MainNodeBuilder {
                     self = self.add_commitment_generator_layer()?;
                 }
                 Component::DADispatcher => {
-                    self = self.add_da_client_layer()?.add_da_dispatcher_layer()?;
+                    self = self
+                        .add_da_client_layer()?
+                        .add_da_dispatcher_layer()?
+                        .add_da_balance_checker_layer()?;
                 }
                 Component::VmRunnerProtectiveReads => {
                     self = self.add_vm_runner_protective_reads_layer()?;
@@ -886,6 +996,12 @@ impl MainNodeBuilder {
                 Component::ExternalProofIntegrationApi => {
                     self = self.add_external_proof_integration_api_layer()?;
                 }
+                Component::Notifier => {
+                    self = self.add_notifier_layer()?;
+                }
+                Component::AdminApi => {
+                    self = self.add_admin_api_layer()?;
+                }
             }
         }
         Ok(self.node.build())
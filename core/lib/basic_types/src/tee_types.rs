@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{fmt, str::FromStr};
 
 use serde::{Deserialize, Serialize};
 
@@ -21,6 +21,19 @@ impl fmt::Display for TeeType {
     }
 }
 
+impl FromStr for TeeType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(TeeType::None),
+            "sgx" => Ok(TeeType::Sgx),
+            "tdx" => Ok(TeeType::Tdx),
+            _ => Err(format!("unknown TEE type `{s}`")),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json;
@@ -59,6 +72,14 @@ mod tests {
         assert_eq!(json_str, "\"tdx\"");
     }
 
+    #[test]
+    fn tee_type_string_roundtrip() {
+        for tee_type in [TeeType::None, TeeType::Sgx, TeeType::Tdx] {
+            assert_eq!(tee_type.to_string().parse::<TeeType>().unwrap(), tee_type);
+        }
+        assert!("SGX".parse::<TeeType>().is_err());
+    }
+
     #[test]
     fn test_display_teetype() {
         assert_eq!(TeeType::Sgx.to_string(), "sgx");
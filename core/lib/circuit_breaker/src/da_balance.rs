@@ -0,0 +1,41 @@
+use anyhow::Context as _;
+use zksync_da_client::DataAvailabilityClient;
+
+use crate::{metrics::METRICS, CircuitBreaker, CircuitBreakerAction, CircuitBreakerError};
+
+/// Checks that the DA client wallet has enough funds to dispatch blobs.
+#[derive(Debug)]
+pub struct DaBalanceChecker {
+    pub client: Box<dyn DataAvailabilityClient>,
+    pub min_balance: u64,
+    pub action: CircuitBreakerAction,
+}
+
+#[async_trait::async_trait]
+impl CircuitBreaker for DaBalanceChecker {
+    fn name(&self) -> &'static str {
+        "da_balance"
+    }
+
+    fn action(&self) -> CircuitBreakerAction {
+        self.action
+    }
+
+    async fn check(&self) -> Result<(), CircuitBreakerError> {
+        let balance = self
+            .client
+            .balance()
+            .await
+            .map_err(|err| err.error)
+            .context("failed getting DA client balance")?;
+        METRICS.da_balance.set(balance);
+
+        if balance < self.min_balance {
+            return Err(CircuitBreakerError::LowDaBalance {
+                balance,
+                threshold: self.min_balance,
+            });
+        }
+        Ok(())
+    }
+}
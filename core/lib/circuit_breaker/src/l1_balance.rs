@@ -0,0 +1,53 @@
+use anyhow::Context as _;
+use zksync_eth_client::BoundEthInterface;
+use zksync_types::U256;
+
+use crate::{metrics::METRICS, CircuitBreaker, CircuitBreakerAction, CircuitBreakerError};
+
+/// Checks that all operator accounts have enough ETH on L1 to pay for L1 transactions.
+#[derive(Debug)]
+pub struct L1BalanceChecker {
+    pub clients: Vec<Box<dyn BoundEthInterface>>,
+    pub min_balance: U256,
+    pub action: CircuitBreakerAction,
+}
+
+#[async_trait::async_trait]
+impl CircuitBreaker for L1BalanceChecker {
+    fn name(&self) -> &'static str {
+        "l1_balance"
+    }
+
+    fn action(&self) -> CircuitBreakerAction {
+        self.action
+    }
+
+    async fn check(&self) -> Result<(), CircuitBreakerError> {
+        let mut lowest_balance = None::<(U256, _)>;
+        for client in &self.clients {
+            let address = client.sender_account();
+            let balance = client
+                .sender_eth_balance()
+                .await
+                .with_context(|| format!("failed getting L1 balance of operator {address:?}"))?;
+            if lowest_balance.map_or(true, |(lowest, _)| balance < lowest) {
+                lowest_balance = Some((balance, address));
+            }
+        }
+
+        let Some((balance, address)) = lowest_balance else {
+            return Ok(());
+        };
+        METRICS
+            .l1_balance
+            .set(balance.low_u128() as f64 / 1_000_000_000_000_000_000.0);
+        if balance < self.min_balance {
+            return Err(CircuitBreakerError::LowL1Balance {
+                address,
+                balance,
+                threshold: self.min_balance,
+            });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+
+use crate::{metrics::METRICS, CircuitBreaker, CircuitBreakerAction, CircuitBreakerError};
+
+/// Checks that proving of L1 batches on L1 keeps up with sealing.
+#[derive(Debug)]
+pub struct UnprovenBatchesChecker {
+    pub pool: ConnectionPool<Core>,
+    /// Maximum number of sealed L1 batches not proven on L1.
+    pub max_batches: u32,
+    pub action: CircuitBreakerAction,
+}
+
+#[async_trait::async_trait]
+impl CircuitBreaker for UnprovenBatchesChecker {
+    fn name(&self) -> &'static str {
+        "unproven_batches"
+    }
+
+    fn action(&self) -> CircuitBreakerAction {
+        self.action
+    }
+
+    async fn check(&self) -> Result<(), CircuitBreakerError> {
+        let mut storage = self.pool.connection_tagged("circuit_breaker").await?;
+        let Some(sealed_batch) = storage.blocks_dal().get_sealed_l1_batch_number().await? else {
+            return Ok(());
+        };
+        let last_proven_batch = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_proven_on_eth()
+            .await?;
+        drop(storage);
+
+        let backlog = sealed_batch
+            .0
+            .saturating_sub(last_proven_batch.map_or(0, |number| number.0));
+        METRICS.unproven_l1_batches.set(backlog.into());
+
+        if backlog > self.max_batches {
+            return Err(CircuitBreakerError::UnprovenBatchBacklog {
+                backlog,
+                threshold: self.max_batches,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Checks that execution of L1 batches on L1 keeps up with their commitment.
+#[derive(Debug)]
+pub struct CommitToExecuteLagChecker {
+    pub pool: ConnectionPool<Core>,
+    /// Maximum time since the oldest non-executed L1 batch was committed on L1.
+    pub max_lag: Duration,
+    pub action: CircuitBreakerAction,
+}
+
+#[async_trait::async_trait]
+impl CircuitBreaker for CommitToExecuteLagChecker {
+    fn name(&self) -> &'static str {
+        "commit_to_execute_lag"
+    }
+
+    fn action(&self) -> CircuitBreakerAction {
+        self.action
+    }
+
+    async fn check(&self) -> Result<(), CircuitBreakerError> {
+        let mut storage = self.pool.connection_tagged("circuit_breaker").await?;
+        let Some(sealed_batch) = storage.blocks_dal().get_sealed_l1_batch_number().await? else {
+            return Ok(());
+        };
+        let last_executed_batch = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_executed_on_eth()
+            .await?;
+        // The genesis L1 batch is never committed, so the first candidate is batch #1.
+        let oldest_non_executed_batch = last_executed_batch.map_or(1, |number| number.0 + 1);
+        let committed_at = if oldest_non_executed_batch <= sealed_batch.0 {
+            storage
+                .blocks_web3_dal()
+                .get_l1_batch_details(oldest_non_executed_batch.into())
+                .await?
+                .and_then(|details| details.base.committed_at)
+        } else {
+            None
+        };
+        drop(storage);
+
+        let lag = committed_at.map_or(Duration::ZERO, |committed_at| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            let committed_at = Duration::from_millis(committed_at.timestamp_millis().max(0) as u64);
+            now.saturating_sub(committed_at)
+        });
+        METRICS.commit_to_execute_lag.set(lag);
+
+        if lag > self.max_lag {
+            return Err(CircuitBreakerError::CommitToExecuteLag {
+                lag,
+                threshold: self.max_lag,
+            });
+        }
+        Ok(())
+    }
+}
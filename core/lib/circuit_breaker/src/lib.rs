@@ -1,20 +1,59 @@
-use std::{fmt, sync::Arc, time::Duration};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
 
 use thiserror::Error;
 use tokio::sync::{watch, Mutex};
+use zksync_types::{Address, U256};
 
+use crate::metrics::METRICS;
+
+pub mod da_balance;
+pub mod l1_balance;
+pub mod l1_batch_lag;
 pub mod l1_txs;
 mod metrics;
 #[cfg(feature = "node_framework")]
 pub mod node;
 pub mod replication_lag;
+#[cfg(test)]
+mod tests;
+
+/// Tripped circuit breakers keyed by their names.
+pub type TrippedCircuitBreakers = BTreeMap<&'static str, TrippedCircuitBreaker>;
+
+#[derive(Debug)]
+pub struct CircuitBreakers {
+    breakers: Mutex<Vec<Box<dyn CircuitBreaker>>>,
+    tx_acceptance: TxAcceptance,
+    tripped: watch::Sender<TrippedCircuitBreakers>,
+}
+
+impl Default for CircuitBreakers {
+    fn default() -> Self {
+        Self {
+            breakers: Mutex::default(),
+            tx_acceptance: TxAcceptance::default(),
+            tripped: watch::channel(BTreeMap::new()).0,
+        }
+    }
+}
 
-#[derive(Default, Debug)]
-pub struct CircuitBreakers(Mutex<Vec<Box<dyn CircuitBreaker>>>);
+/// Information about a tripped circuit breaker.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrippedCircuitBreaker {
+    pub name: &'static str,
+    pub action: CircuitBreakerAction,
+    /// Error returned by the breaker check.
+    pub error: String,
+}
 
 impl CircuitBreakers {
     pub async fn insert(&self, circuit_breaker: Box<dyn CircuitBreaker>) {
-        let mut guard = self.0.lock().await;
+        let mut guard = self.breakers.lock().await;
         if !guard
             .iter()
             .any(|existing_breaker| existing_breaker.name() == circuit_breaker.name())
@@ -23,20 +62,152 @@ impl CircuitBreakers {
         }
     }
 
+    /// Returns the switch controlling transaction acceptance, which is paused by breakers with
+    /// [`CircuitBreakerAction::PauseTxAcceptance`].
+    pub fn tx_acceptance(&self) -> TxAcceptance {
+        self.tx_acceptance.clone()
+    }
+
+    /// Returns circuit breakers that have tripped on the latest check.
+    pub fn tripped(&self) -> Vec<TrippedCircuitBreaker> {
+        self.tripped.borrow().values().cloned().collect()
+    }
+
+    /// Subscribes to changes in the set of tripped circuit breakers. The receiver is notified immediately
+    /// when a breaker trips or recovers; in particular, it's notified before [`Self::check()`] returns an error
+    /// for a breaker with [`CircuitBreakerAction::Halt`].
+    pub fn subscribe(&self) -> watch::Receiver<TrippedCircuitBreakers> {
+        self.tripped.subscribe()
+    }
+
+    fn set_tripped(&self, name: &'static str, tripped: Option<TrippedCircuitBreaker>) {
+        self.tripped.send_if_modified(|guard| {
+            if let Some(tripped) = tripped {
+                guard.insert(name, tripped.clone()) != Some(tripped)
+            } else {
+                guard.remove(name).is_some()
+            }
+        });
+    }
+
+    /// Runs checks for all circuit breakers. Returns an error if a breaker with [`CircuitBreakerAction::Halt`] has tripped;
+    /// other breakers are handled according to their action.
     pub async fn check(&self) -> Result<(), CircuitBreakerError> {
-        for circuit_breaker in self.0.lock().await.iter() {
-            circuit_breaker.check().await?;
+        for circuit_breaker in self.breakers.lock().await.iter() {
+            let name = circuit_breaker.name();
+            let action = circuit_breaker.action();
+            match circuit_breaker.check().await {
+                Ok(()) => {
+                    METRICS.tripped[&name].set(0);
+                    self.set_tripped(name, None);
+                    if self.tx_acceptance.resume(name) {
+                        tracing::info!("Circuit breaker `{name}` has recovered; resuming transaction acceptance");
+                    }
+                }
+                Err(err) if action == CircuitBreakerAction::Halt => {
+                    let tripped = TrippedCircuitBreaker {
+                        name,
+                        action,
+                        error: err.to_string(),
+                    };
+                    self.set_tripped(name, Some(tripped));
+                    return Err(err);
+                }
+                Err(CircuitBreakerError::Internal(err)) => {
+                    // Keep the previous state of the breaker; the check will be retried on the next iteration.
+                    tracing::warn!("Failed running circuit breaker `{name}`: {err:#}");
+                }
+                Err(err) => {
+                    METRICS.tripped[&name].set(1);
+                    let tripped = TrippedCircuitBreaker {
+                        name,
+                        action,
+                        error: err.to_string(),
+                    };
+                    self.set_tripped(name, Some(tripped));
+                    if action == CircuitBreakerAction::PauseTxAcceptance {
+                        if self.tx_acceptance.pause(name) {
+                            tracing::error!(
+                                "Circuit breaker `{name}` has tripped; pausing transaction acceptance: {err}"
+                            );
+                        }
+                    } else {
+                        tracing::error!("Circuit breaker `{name}` has tripped: {err}");
+                    }
+                }
+            }
         }
         Ok(())
     }
 }
 
+/// Switch pausing acceptance of new transactions by the API server. Cheaply cloneable.
+#[derive(Debug, Clone, Default)]
+pub struct TxAcceptance(Arc<RwLock<BTreeSet<&'static str>>>);
+
+impl TxAcceptance {
+    /// Checks whether transaction acceptance is paused by at least one circuit breaker.
+    pub fn is_paused(&self) -> bool {
+        !self
+            .0
+            .read()
+            .expect("tx acceptance lock is poisoned")
+            .is_empty()
+    }
+
+    /// Returns names of circuit breakers that currently pause transaction acceptance.
+    pub fn paused_by(&self) -> Vec<&'static str> {
+        let guard = self.0.read().expect("tx acceptance lock is poisoned");
+        guard.iter().copied().collect()
+    }
+
+    /// Returns `true` if the breaker has not paused acceptance before.
+    fn pause(&self, breaker: &'static str) -> bool {
+        self.0
+            .write()
+            .expect("tx acceptance lock is poisoned")
+            .insert(breaker)
+    }
+
+    /// Returns `true` if the breaker has paused acceptance before.
+    fn resume(&self, breaker: &'static str) -> bool {
+        self.0
+            .write()
+            .expect("tx acceptance lock is poisoned")
+            .remove(breaker)
+    }
+}
+
+/// Reaction to a tripped circuit breaker.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CircuitBreakerAction {
+    /// Stop the node.
+    #[default]
+    Halt,
+    /// Reject new transactions submitted via the API until the breaker recovers.
+    PauseTxAcceptance,
+    /// Only log an error and report the breaker as tripped via metrics.
+    Alert,
+}
+
 #[derive(Debug, Error)]
 pub enum CircuitBreakerError {
     #[error("System has failed L1 transaction")]
     FailedL1Transaction,
     #[error("Replication lag ({lag:?}) is above the threshold ({threshold:?})")]
     ReplicationLag { lag: Duration, threshold: Duration },
+    #[error("L1 balance of operator {address:?} ({balance} wei) is below the threshold ({threshold} wei)")]
+    LowL1Balance {
+        address: Address,
+        balance: U256,
+        threshold: U256,
+    },
+    #[error("DA client balance ({balance}) is below the threshold ({threshold})")]
+    LowDaBalance { balance: u64, threshold: u64 },
+    #[error("Number of unproven L1 batches ({backlog}) is above the threshold ({threshold})")]
+    UnprovenBatchBacklog { backlog: u32, threshold: u32 },
+    #[error("L1 batch commit-to-execute lag ({lag:?}) is above the threshold ({threshold:?})")]
+    CommitToExecuteLag { lag: Duration, threshold: Duration },
     #[error("Internal error running circuit breaker checks")]
     Internal(#[from] anyhow::Error),
 }
@@ -58,6 +229,11 @@ pub struct CircuitBreakerChecker {
 pub trait CircuitBreaker: fmt::Debug + Send + Sync {
     fn name(&self) -> &'static str;
 
+    /// Reaction to this breaker tripping. By default, the node is halted.
+    fn action(&self) -> CircuitBreakerAction {
+        CircuitBreakerAction::Halt
+    }
+
     async fn check(&self) -> Result<(), CircuitBreakerError>;
 }
 
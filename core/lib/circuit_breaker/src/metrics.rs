@@ -2,13 +2,24 @@
 
 use std::time::Duration;
 
-use vise::{Gauge, Global, Metrics};
+use vise::{Gauge, Global, LabeledFamily, Metrics};
 
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "circuit_breaker")]
 pub(crate) struct CircuitBreakerMetrics {
     /// Replication lag for Postgres in seconds.
     pub replication_lag: Gauge<Duration>,
+    /// Whether a circuit breaker is currently tripped (1) or not (0).
+    #[metrics(labels = ["breaker"])]
+    pub tripped: LabeledFamily<&'static str, Gauge<u64>>,
+    /// Lowest balance among operator accounts on L1, in ETH.
+    pub l1_balance: Gauge<f64>,
+    /// Balance of the DA client wallet, in the units reported by the client.
+    pub da_balance: Gauge<u64>,
+    /// Number of sealed L1 batches not yet proven on L1.
+    pub unproven_l1_batches: Gauge<u64>,
+    /// Time elapsed since committing the oldest non-executed L1 batch.
+    pub commit_to_execute_lag: Gauge<Duration>,
 }
 
 #[vise::register]
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use zksync_config::configs::chain::DaBalanceBreakerConfig;
+use zksync_da_client::DataAvailabilityClient;
+use zksync_node_framework::{FromContext, WiringError, WiringLayer};
+
+use crate::{da_balance::DaBalanceChecker, CircuitBreakers};
+
+/// Layer adding [`DaBalanceChecker`] to circuit breakers.
+#[derive(Debug)]
+pub struct DaBalanceCheckerLayer(pub DaBalanceBreakerConfig);
+
+#[derive(Debug, FromContext)]
+pub struct Input {
+    #[context(default)]
+    circuit_breakers: Arc<CircuitBreakers>,
+    da_client: Box<dyn DataAvailabilityClient>,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for DaBalanceCheckerLayer {
+    type Input = Input;
+    type Output = ();
+
+    fn layer_name(&self) -> &'static str {
+        "circuit_breakers/da_balance"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let checker = DaBalanceChecker {
+            client: input.da_client,
+            min_balance: self.0.min_balance,
+            action: self.0.mode.into(),
+        };
+        input.circuit_breakers.insert(Box::new(checker)).await;
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use zksync_config::configs::chain::L1BalanceBreakerConfig;
+use zksync_eth_client::{node::BoundEthInterfaceForBlobsResource, BoundEthInterface};
+use zksync_node_framework::{FromContext, WiringError, WiringLayer};
+use zksync_types::U256;
+
+use crate::{l1_balance::L1BalanceChecker, CircuitBreakers};
+
+/// Layer adding [`L1BalanceChecker`] to circuit breakers.
+#[derive(Debug)]
+pub struct L1BalanceCheckerLayer(pub L1BalanceBreakerConfig);
+
+#[derive(Debug, FromContext)]
+pub struct Input {
+    #[context(default)]
+    circuit_breakers: Arc<CircuitBreakers>,
+    eth_client: Box<dyn BoundEthInterface>,
+    eth_client_blobs: Option<BoundEthInterfaceForBlobsResource>,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for L1BalanceCheckerLayer {
+    type Input = Input;
+    type Output = ();
+
+    fn layer_name(&self) -> &'static str {
+        "circuit_breakers/l1_balance"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let mut clients = vec![input.eth_client];
+        clients.extend(input.eth_client_blobs.map(|resource| resource.0));
+        let checker = L1BalanceChecker {
+            clients,
+            min_balance: U256::from(self.0.min_balance_gwei) * U256::exp10(9),
+            action: self.0.mode.into(),
+        };
+        input.circuit_breakers.insert(Box::new(checker)).await;
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use zksync_config::configs::chain::{
+    CommitToExecuteLagBreakerConfig, UnprovenBatchesBreakerConfig,
+};
+use zksync_dal::node::{PoolResource, ReplicaPool};
+use zksync_node_framework::{FromContext, WiringError, WiringLayer};
+
+use crate::{
+    l1_batch_lag::{CommitToExecuteLagChecker, UnprovenBatchesChecker},
+    CircuitBreakers,
+};
+
+#[derive(Debug, FromContext)]
+pub struct Input {
+    #[context(default)]
+    circuit_breakers: Arc<CircuitBreakers>,
+    replica_pool: PoolResource<ReplicaPool>,
+}
+
+/// Layer adding [`UnprovenBatchesChecker`] to circuit breakers.
+#[derive(Debug)]
+pub struct UnprovenBatchesCheckerLayer(pub UnprovenBatchesBreakerConfig);
+
+#[async_trait::async_trait]
+impl WiringLayer for UnprovenBatchesCheckerLayer {
+    type Input = Input;
+    type Output = ();
+
+    fn layer_name(&self) -> &'static str {
+        "circuit_breakers/unproven_batches"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let checker = UnprovenBatchesChecker {
+            pool: input.replica_pool.get().await?,
+            max_batches: self.0.max_batches,
+            action: self.0.mode.into(),
+        };
+        input.circuit_breakers.insert(Box::new(checker)).await;
+        Ok(())
+    }
+}
+
+/// Layer adding [`CommitToExecuteLagChecker`] to circuit breakers.
+#[derive(Debug)]
+pub struct CommitToExecuteLagCheckerLayer(pub CommitToExecuteLagBreakerConfig);
+
+#[async_trait::async_trait]
+impl WiringLayer for CommitToExecuteLagCheckerLayer {
+    type Input = Input;
+    type Output = ();
+
+    fn layer_name(&self) -> &'static str {
+        "circuit_breakers/commit_to_execute_lag"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let checker = CommitToExecuteLagChecker {
+            pool: input.replica_pool.get().await?,
+            max_lag: self.0.max_lag,
+            action: self.0.mode.into(),
+        };
+        input.circuit_breakers.insert(Box::new(checker)).await;
+        Ok(())
+    }
+}
@@ -1,13 +1,32 @@
 //! Dependency injection for circuit breakers.
 
+use zksync_config::configs::chain::CircuitBreakerMode;
 use zksync_node_framework::{resource, Resource};
 
-pub use self::{checker::CircuitBreakerCheckerLayer, replication_lag::ReplicationLagCheckerLayer};
-use crate::CircuitBreakers;
+pub use self::{
+    checker::CircuitBreakerCheckerLayer,
+    da_balance::DaBalanceCheckerLayer,
+    l1_balance::L1BalanceCheckerLayer,
+    l1_batch_lag::{CommitToExecuteLagCheckerLayer, UnprovenBatchesCheckerLayer},
+    replication_lag::ReplicationLagCheckerLayer,
+};
+use crate::{CircuitBreakerAction, CircuitBreakers};
 
 mod checker;
+mod da_balance;
+mod l1_balance;
+mod l1_batch_lag;
 mod replication_lag;
 
+impl From<CircuitBreakerMode> for CircuitBreakerAction {
+    fn from(mode: CircuitBreakerMode) -> Self {
+        match mode {
+            CircuitBreakerMode::PauseTxs => Self::PauseTxAcceptance,
+            CircuitBreakerMode::Alert => Self::Alert,
+        }
+    }
+}
+
 impl Resource<resource::Shared> for CircuitBreakers {
     fn name() -> String {
         "common/circuit_breakers".into()
@@ -0,0 +1,359 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use chrono::{DateTime, Utc};
+use zksync_da_client::types::{
+    ClientType, DAError, DispatchResponse, FinalityResponse, InclusionData,
+};
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_eth_client::clients::MockSettlementLayer;
+use zksync_node_test_utils::{create_l1_batch, create_l2_block};
+use zksync_types::{
+    aggregated_operations::L1BatchAggregatedActionType, eth_sender::EthTxFinalityStatus, Address,
+    L1BatchNumber, ProtocolVersion, SLChainId, H256, U256,
+};
+
+use super::*;
+use crate::{
+    da_balance::DaBalanceChecker,
+    l1_balance::L1BalanceChecker,
+    l1_batch_lag::{CommitToExecuteLagChecker, UnprovenBatchesChecker},
+};
+
+#[derive(Debug)]
+struct MockBreaker {
+    name: &'static str,
+    action: CircuitBreakerAction,
+    tripped: Arc<AtomicBool>,
+}
+
+impl MockBreaker {
+    fn new(name: &'static str, action: CircuitBreakerAction) -> (Self, Arc<AtomicBool>) {
+        let tripped = Arc::<AtomicBool>::default();
+        let this = Self {
+            name,
+            action,
+            tripped: tripped.clone(),
+        };
+        (this, tripped)
+    }
+}
+
+#[async_trait::async_trait]
+impl CircuitBreaker for MockBreaker {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn action(&self) -> CircuitBreakerAction {
+        self.action
+    }
+
+    async fn check(&self) -> Result<(), CircuitBreakerError> {
+        if self.tripped.load(Ordering::SeqCst) {
+            Err(CircuitBreakerError::LowDaBalance {
+                balance: 0,
+                threshold: 1,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[tokio::test]
+async fn pausing_and_resuming_tx_acceptance() {
+    let breakers = CircuitBreakers::default();
+    let (pausing_breaker, pausing_tripped) =
+        MockBreaker::new("pausing", CircuitBreakerAction::PauseTxAcceptance);
+    let (alerting_breaker, alerting_tripped) =
+        MockBreaker::new("alerting", CircuitBreakerAction::Alert);
+    breakers.insert(Box::new(pausing_breaker)).await;
+    breakers.insert(Box::new(alerting_breaker)).await;
+    let tx_acceptance = breakers.tx_acceptance();
+
+    breakers.check().await.unwrap();
+    assert!(!tx_acceptance.is_paused());
+
+    alerting_tripped.store(true, Ordering::SeqCst);
+    breakers.check().await.unwrap();
+    assert!(!tx_acceptance.is_paused());
+
+    pausing_tripped.store(true, Ordering::SeqCst);
+    breakers.check().await.unwrap();
+    assert_eq!(tx_acceptance.paused_by(), ["pausing"]);
+    let tripped: Vec<_> = breakers.tripped().iter().map(|info| info.name).collect();
+    assert_eq!(tripped, ["alerting", "pausing"]);
+
+    pausing_tripped.store(false, Ordering::SeqCst);
+    breakers.check().await.unwrap();
+    assert!(!tx_acceptance.is_paused());
+    assert_eq!(breakers.tripped().len(), 1);
+}
+
+#[tokio::test]
+async fn halting_breaker_returns_error() {
+    let breakers = CircuitBreakers::default();
+    let (breaker, tripped) = MockBreaker::new("halting", CircuitBreakerAction::Halt);
+    breakers.insert(Box::new(breaker)).await;
+
+    let mut trips = breakers.subscribe();
+
+    breakers.check().await.unwrap();
+    assert!(!trips.has_changed().unwrap());
+    tripped.store(true, Ordering::SeqCst);
+    let err = breakers.check().await.unwrap_err();
+    assert!(
+        matches!(err, CircuitBreakerError::LowDaBalance { .. }),
+        "{err:?}"
+    );
+    assert!(!breakers.tx_acceptance().is_paused());
+
+    // Subscribers must be notified about the trip even though the node is going to halt.
+    assert!(trips.has_changed().unwrap());
+    let tripped_names: Vec<_> = trips.borrow_and_update().keys().copied().collect();
+    assert_eq!(tripped_names, ["halting"]);
+
+    // Repeated trips with the same error must not spam subscribers.
+    breakers.check().await.unwrap_err();
+    assert!(!trips.has_changed().unwrap());
+}
+
+#[tokio::test]
+async fn l1_balance_checker_thresholds() {
+    let operator = MockSettlementLayer::builder()
+        .with_sender(Address::repeat_byte(1))
+        .build();
+    let blob_operator = MockSettlementLayer::builder()
+        .with_sender(Address::repeat_byte(2))
+        .build();
+    operator.set_balance(Address::repeat_byte(1), 1_000.into());
+    blob_operator.set_balance(Address::repeat_byte(2), 500.into());
+    let mut checker = L1BalanceChecker {
+        clients: vec![Box::new(operator), Box::new(blob_operator)],
+        min_balance: 500.into(),
+        action: CircuitBreakerAction::PauseTxAcceptance,
+    };
+    checker.check().await.unwrap();
+
+    // The lowest balance among operators must be compared with the threshold.
+    checker.min_balance = 501.into();
+    let err = checker.check().await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            CircuitBreakerError::LowL1Balance { address, balance, threshold }
+                if address == Address::repeat_byte(2) && balance == 500.into() && threshold == 501.into()
+        ),
+        "{err:?}"
+    );
+
+    checker.clients.clear();
+    checker.check().await.unwrap();
+}
+
+#[derive(Debug, Clone, Default)]
+struct MockDaClient {
+    balance: Arc<AtomicU64>,
+}
+
+#[async_trait::async_trait]
+impl DataAvailabilityClient for MockDaClient {
+    async fn dispatch_blob(
+        &self,
+        _batch_number: u32,
+        _data: Vec<u8>,
+    ) -> Result<DispatchResponse, DAError> {
+        unimplemented!("not used in tests")
+    }
+
+    async fn ensure_finality(
+        &self,
+        _dispatch_request_id: String,
+        _dispatched_at: DateTime<Utc>,
+    ) -> Result<Option<FinalityResponse>, DAError> {
+        unimplemented!("not used in tests")
+    }
+
+    async fn get_inclusion_data(&self, _blob_id: &str) -> Result<Option<InclusionData>, DAError> {
+        unimplemented!("not used in tests")
+    }
+
+    fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
+        Box::new(self.clone())
+    }
+
+    fn blob_size_limit(&self) -> Option<usize> {
+        None
+    }
+
+    fn client_type(&self) -> ClientType {
+        ClientType::NoDA
+    }
+
+    async fn balance(&self) -> Result<u64, DAError> {
+        Ok(self.balance.load(Ordering::SeqCst))
+    }
+}
+
+#[tokio::test]
+async fn da_balance_checker_thresholds() {
+    let client = MockDaClient::default();
+    let checker = DaBalanceChecker {
+        client: Box::new(client.clone()),
+        min_balance: 100,
+        action: CircuitBreakerAction::Alert,
+    };
+    let err = checker.check().await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            CircuitBreakerError::LowDaBalance {
+                balance: 0,
+                threshold: 100
+            }
+        ),
+        "{err:?}"
+    );
+
+    client.balance.store(100, Ordering::SeqCst);
+    checker.check().await.unwrap();
+    client.balance.store(99, Ordering::SeqCst);
+    let err = checker.check().await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            CircuitBreakerError::LowDaBalance {
+                balance: 99,
+                threshold: 100
+            }
+        ),
+        "{err:?}"
+    );
+}
+
+async fn seal_l1_batch(storage: &mut Connection<'_, Core>, number: u32) {
+    let l2_block = create_l2_block(number);
+    storage
+        .blocks_dal()
+        .insert_l2_block(&l2_block)
+        .await
+        .unwrap();
+    let l1_batch = create_l1_batch(number);
+    storage
+        .blocks_dal()
+        .insert_mock_l1_batch(&l1_batch)
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .mark_l2_blocks_as_executed_in_l1_batch(L1BatchNumber(number))
+        .await
+        .unwrap();
+}
+
+async fn confirm_l1_batch_action(
+    storage: &mut Connection<'_, Core>,
+    number: u32,
+    action: L1BatchAggregatedActionType,
+) {
+    let tx_hash = H256::random();
+    storage
+        .eth_sender_dal()
+        .insert_pending_received_eth_tx(L1BatchNumber(number), action, tx_hash, Some(SLChainId(1)))
+        .await
+        .unwrap();
+    storage
+        .eth_sender_dal()
+        .confirm_tx(tx_hash, EthTxFinalityStatus::Finalized, U256::zero())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn unproven_batches_checker_reports_backlog() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut checker = UnprovenBatchesChecker {
+        pool: pool.clone(),
+        max_batches: 2,
+        action: CircuitBreakerAction::PauseTxAcceptance,
+    };
+    // There are no L1 batches yet.
+    checker.check().await.unwrap();
+
+    let mut storage = pool.connection().await.unwrap();
+    storage
+        .protocol_versions_dal()
+        .save_protocol_version_with_tx(&ProtocolVersion::default())
+        .await
+        .unwrap();
+    for number in 0..=3 {
+        seal_l1_batch(&mut storage, number).await;
+    }
+
+    let err = checker.check().await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            CircuitBreakerError::UnprovenBatchBacklog {
+                backlog: 3,
+                threshold: 2
+            }
+        ),
+        "{err:?}"
+    );
+    checker.max_batches = 3;
+    checker.check().await.unwrap();
+
+    checker.max_batches = 1;
+    for number in 1..=2 {
+        confirm_l1_batch_action(&mut storage, number, L1BatchAggregatedActionType::Commit).await;
+        confirm_l1_batch_action(
+            &mut storage,
+            number,
+            L1BatchAggregatedActionType::PublishProofOnchain,
+        )
+        .await;
+    }
+    checker.check().await.unwrap();
+}
+
+#[tokio::test]
+async fn commit_to_execute_lag_checker_reports_lag() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut checker = CommitToExecuteLagChecker {
+        pool: pool.clone(),
+        max_lag: Duration::ZERO,
+        action: CircuitBreakerAction::PauseTxAcceptance,
+    };
+
+    let mut storage = pool.connection().await.unwrap();
+    storage
+        .protocol_versions_dal()
+        .save_protocol_version_with_tx(&ProtocolVersion::default())
+        .await
+        .unwrap();
+    for number in 0..=2 {
+        seal_l1_batch(&mut storage, number).await;
+    }
+    // Non-committed batches don't contribute to the lag.
+    checker.check().await.unwrap();
+
+    confirm_l1_batch_action(&mut storage, 1, L1BatchAggregatedActionType::Commit).await;
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    let err = checker.check().await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            CircuitBreakerError::CommitToExecuteLag { lag, threshold }
+                if lag > Duration::ZERO && threshold == Duration::ZERO
+        ),
+        "{err:?}"
+    );
+    checker.max_lag = Duration::from_secs(3_600);
+    checker.check().await.unwrap();
+
+    // Once the batch is executed, the lag is measured for the next batch, which is not committed yet.
+    checker.max_lag = Duration::ZERO;
+    confirm_l1_batch_action(&mut storage, 1, L1BatchAggregatedActionType::Execute).await;
+    checker.check().await.unwrap();
+}
@@ -0,0 +1,61 @@
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+use smart_config::{de::Serde, DescribeConfig, DeserializeConfig};
+
+/// Configuration for the admin API allowing to list node tasks, pause / resume them and update dynamic configuration
+/// at runtime. The API requires an auth token configured as a [secret](crate::configs::secrets::AdminApiSecrets).
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+#[config(derive(Default))]
+pub struct AdminApiConfig {
+    /// Port to bind the admin API server to.
+    #[config(default_t = 3_075)]
+    pub port: u16,
+    /// IP address to bind the admin API server to. By default, the server is only accessible from localhost.
+    #[config(with = Serde![str], default_t = Ipv4Addr::LOCALHOST.into())]
+    pub bind_ip: IpAddr,
+}
+
+impl AdminApiConfig {
+    pub fn bind_addr(&self) -> SocketAddr {
+        SocketAddr::new(self.bind_ip, self.port)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use smart_config::{testing::test_complete, Environment, Yaml};
+
+    use super::*;
+
+    fn expected_config() -> AdminApiConfig {
+        AdminApiConfig {
+            port: 3_080,
+            bind_ip: Ipv4Addr::UNSPECIFIED.into(),
+        }
+    }
+
+    #[test]
+    fn parsing_from_env() {
+        let env = r#"
+            ADMIN_API_PORT=3080
+            ADMIN_API_BIND_IP=0.0.0.0
+        "#;
+        let env = Environment::from_dotenv("test.env", env)
+            .unwrap()
+            .strip_prefix("ADMIN_API_");
+
+        let config: AdminApiConfig = test_complete(env).unwrap();
+        assert_eq!(config, expected_config());
+    }
+
+    #[test]
+    fn parsing_from_yaml() {
+        let yaml = r#"
+          port: 3080
+          bind_ip: 0.0.0.0
+        "#;
+        let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
+        let config: AdminApiConfig = test_complete(yaml).unwrap();
+        assert_eq!(config, expected_config());
+    }
+}
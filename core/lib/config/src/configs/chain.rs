@@ -1,8 +1,15 @@
-use std::{collections::HashSet, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    num::NonZeroU32,
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
 
+use anyhow::Context as _;
 use serde::{Deserialize, Serialize};
 use smart_config::{
-    de::Serde,
+    de::{Entries, NamedEntries, OrString, Serde, ToEntries, WellKnown},
     metadata::{SizeUnit, TimeUnit},
     ByteSize, DescribeConfig, DeserializeConfig,
 };
@@ -95,6 +102,9 @@ pub struct SealCriteriaConfig {
     /// the recursion layers' circuits.
     #[config(default_t = 31_100)]
     pub max_circuits_per_batch: usize,
+    /// Seals L1 batches based on their estimated proving cost. If not set, the proving cost is not limited.
+    #[config(nest)]
+    pub proving_cost: Option<ProvingCostConfig>,
 }
 
 impl SealCriteriaConfig {
@@ -111,6 +121,7 @@ impl SealCriteriaConfig {
             close_block_at_eth_params_percentage: 0.95,
             close_block_at_gas_percentage: 0.95,
             max_circuits_per_batch: 24100,
+            proving_cost: None,
         }
     }
 }
@@ -171,6 +182,13 @@ pub struct StateKeeperConfig {
     /// Allowed deployers for L2 transactions.
     #[config(nest)]
     pub deployment_allowlist: Option<DeploymentAllowlist>,
+    /// Congestion-based adjustment of the fair L2 gas price. If not set, the fair L2 gas price
+    /// does not depend on the L2 load.
+    #[config(nest)]
+    pub congestion_pricing: Option<CongestionPricingConfig>,
+    /// Policies applied to L2 transactions both on submission via the API and before inclusion into a block.
+    #[config(nest)]
+    pub tx_policy: TxPolicyConfig,
 }
 
 impl StateKeeperConfig {
@@ -194,10 +212,261 @@ impl StateKeeperConfig {
             fee_model_version: FeeModelVersion::V2,
             validation_computational_gas_limit: 300000,
             deployment_allowlist: None,
+            congestion_pricing: None,
+            tx_policy: TxPolicyConfig::default(),
+        }
+    }
+}
+
+/// EIP-1559-style adjustment of the fair L2 gas price based on how full recent L2 blocks were.
+///
+/// Each sealed L2 block moves the multiplier applied to `minimal_l2_gas_price` towards the block utilization,
+/// i.e. the share of the batch capacity (as defined by seal criteria) the block has consumed relative
+/// to the share expected for a single L2 block.
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+pub struct CongestionPricingConfig {
+    /// Utilization of L2 blocks at which the price stays the same. Fuller blocks increase the price, emptier ones decrease it.
+    #[config(default_t = 0.5, validate(ZERO_TO_ONE))]
+    pub target_utilization: f64,
+    /// Maximum relative change of the price per L2 block. The default matches the EIP-1559 base fee change denominator.
+    #[config(default_t = 0.125, validate(ZERO_TO_ONE))]
+    pub max_change_per_block: f64,
+    /// Upper bound for the multiplier applied to `minimal_l2_gas_price`. The multiplier never drops below 1.
+    pub max_multiplier: f64,
+    /// Interval between checks for newly sealed L2 blocks.
+    #[config(default_t = Duration::from_secs(1))]
+    pub poll_interval: Duration,
+}
+
+/// Estimation of the proving cost of L1 batches.
+///
+/// The prover time for a batch is estimated as the sum of proving times of its base layer circuits
+/// (i.e., without recursion layers), based on the circuit counts predicted by the VM.
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+pub struct ProvingCostConfig {
+    /// Estimated prover time for a single base layer circuit. Applies to all circuit types without an override.
+    #[config(default_t = Duration::from_secs(1))]
+    pub circuit_proving_time: Duration,
+    /// Per-type overrides for `circuit_proving_time` in milliseconds. Circuit types are named after
+    /// the fields of the VM circuit statistic, e.g. `main_vm` or `storage_application`.
+    #[config(default = CircuitProvingTimeOverrides::empty)]
+    pub circuit_proving_time_overrides: CircuitProvingTimeOverrides,
+    /// Cost of an hour of prover time. Units are arbitrary (e.g., USD); they only need to match `max_cost_per_batch`.
+    #[config(default_t = 1.0)]
+    pub prover_cost_per_hour: f64,
+    /// Budget for the estimated prover time of an L1 batch.
+    pub max_proving_time_per_batch: Option<Duration>,
+    /// Budget for the estimated proving cost of an L1 batch, measured in the units of `prover_cost_per_hour`.
+    pub max_cost_per_batch: Option<f64>,
+}
+
+impl ProvingCostConfig {
+    /// Returns the effective budget for the prover time of an L1 batch, taking both the time and cost budgets into account.
+    pub fn proving_time_budget(&self) -> Option<Duration> {
+        let cost_budget = self.max_cost_per_batch.and_then(|max_cost| {
+            (self.prover_cost_per_hour > 0.0)
+                .then(|| Duration::from_secs_f64(max_cost * 3_600.0 / self.prover_cost_per_hour))
+        });
+        match (self.max_proving_time_per_batch, cost_budget) {
+            (Some(time_budget), Some(cost_budget)) => Some(time_budget.min(cost_budget)),
+            (time_budget, cost_budget) => time_budget.or(cost_budget),
+        }
+    }
+
+    /// Converts prover time to the proving cost.
+    pub fn proving_cost(&self, proving_time: Duration) -> f64 {
+        proving_time.as_secs_f64() * self.prover_cost_per_hour / 3_600.0
+    }
+}
+
+/// Overrides of proving times for specific base layer circuit types, in milliseconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CircuitProvingTimeOverrides(HashMap<String, u64>);
+
+impl CircuitProvingTimeOverrides {
+    pub fn empty() -> Self {
+        Self(HashMap::new())
+    }
+
+    /// Gets the override for the specified circuit type, or `None` if it's not set.
+    pub fn get(&self, circuit_type: &str) -> Option<Duration> {
+        self.0.get(circuit_type).copied().map(Duration::from_millis)
+    }
+
+    /// Iterates over circuit types with overridden proving times.
+    pub fn circuit_types(&self) -> impl Iterator<Item = &str> + '_ {
+        self.0.keys().map(String::as_str)
+    }
+}
+
+impl<S: Into<String>> FromIterator<(S, u64)> for CircuitProvingTimeOverrides {
+    fn from_iter<I: IntoIterator<Item = (S, u64)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(circuit_type, time_ms)| (circuit_type.into(), time_ms))
+                .collect(),
+        )
+    }
+}
+
+impl ToEntries<String, u64> for CircuitProvingTimeOverrides {
+    fn to_entries(&self) -> impl Iterator<Item = (&String, &u64)> {
+        self.0.iter()
+    }
+}
+
+impl FromStr for CircuitProvingTimeOverrides {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut overrides = HashMap::new();
+        for part in s.split(',') {
+            let (circuit_type, time_ms) = part
+                .split_once('=')
+                .with_context(|| format!("Part `{part}` doesn't have form <circuit_type>=<int>"))?;
+            let circuit_type = circuit_type.trim();
+            let time_ms = time_ms.trim();
+            let time_ms = time_ms.parse().with_context(|| {
+                format!(
+                    "`{time_ms}` specified for circuit type `{circuit_type}` is not a valid time"
+                )
+            })?;
+
+            if let Some(prev_time_ms) = overrides.insert(circuit_type.to_owned(), time_ms) {
+                anyhow::bail!(
+                    "Proving time for `{circuit_type}` is redefined from {prev_time_ms}ms to {time_ms}ms"
+                );
+            }
+        }
+        Ok(Self(overrides))
+    }
+}
+
+impl WellKnown for CircuitProvingTimeOverrides {
+    type Deserializer = OrString<NamedEntries<String, u64>>;
+    const DE: Self::Deserializer = OrString(Entries::WELL_KNOWN.named("circuit", "time_ms"));
+}
+
+/// Policies applied by the sequencer to L2 transactions. All policies are disabled by default.
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+#[config(derive(Default))]
+pub struct TxPolicyConfig {
+    /// Addresses that are not allowed to send or receive L2 transactions.
+    #[config(default)]
+    pub denied_addresses: HashSet<Address>,
+    /// Denied function selectors, either in the `0xaabbccdd` form (denied for all contracts)
+    /// or in the `<contract address>:0xaabbccdd` form (denied for a specific contract).
+    #[config(default)]
+    pub denied_selectors: Vec<String>,
+    /// Maximum number of transactions calling a contract accepted by the API server within `rate_limit_window`.
+    #[config(default = ContractRateLimits::empty)]
+    pub contract_rate_limits: ContractRateLimits,
+    /// Sliding time window for `contract_rate_limits`.
+    #[config(default_t = 1 * TimeUnit::Minutes)]
+    pub rate_limit_window: Duration,
+    /// Externally maintained screening list. Screened addresses are treated in the same way as `denied_addresses`.
+    #[config(nest)]
+    pub screening_list: Option<ScreeningListConfig>,
+}
+
+impl TxPolicyConfig {
+    /// Checks whether at least one policy is configured.
+    pub fn is_enabled(&self) -> bool {
+        !self.denied_addresses.is_empty()
+            || !self.denied_selectors.is_empty()
+            || !self.contract_rate_limits.0.is_empty()
+            || self.screening_list.is_some()
+    }
+}
+
+/// Per-contract limits for the number of transactions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ContractRateLimits(HashMap<Address, NonZeroU32>);
+
+impl ContractRateLimits {
+    pub fn empty() -> Self {
+        Self(HashMap::new())
+    }
+
+    pub fn into_inner(self) -> HashMap<Address, NonZeroU32> {
+        self.0
+    }
+}
+
+impl FromIterator<(Address, NonZeroU32)> for ContractRateLimits {
+    fn from_iter<I: IntoIterator<Item = (Address, NonZeroU32)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl ToEntries<Address, NonZeroU32> for ContractRateLimits {
+    fn to_entries(&self) -> impl Iterator<Item = (&Address, &NonZeroU32)> {
+        self.0.iter()
+    }
+}
+
+impl FromStr for ContractRateLimits {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut limits = HashMap::new();
+        for part in s.split(',') {
+            let (contract, limit) = part
+                .split_once('=')
+                .with_context(|| format!("Part `{part}` doesn't have form <address>=<int>"))?;
+            let contract = contract.trim();
+            let contract: Address = contract
+                .parse()
+                .with_context(|| format!("`{contract}` is not a valid address"))?;
+            let limit = limit.trim();
+            let limit = limit.parse().with_context(|| {
+                format!("`{limit}` specified for contract {contract:?} is not a valid limit")
+            })?;
+
+            if let Some(prev_limit) = limits.insert(contract, limit) {
+                anyhow::bail!(
+                    "Rate limit for {contract:?} is redefined from {prev_limit} to {limit}"
+                );
+            }
         }
+        Ok(Self(limits))
     }
 }
 
+impl WellKnown for ContractRateLimits {
+    type Deserializer = OrString<NamedEntries<Address, NonZeroU32>>;
+    const DE: Self::Deserializer = OrString(Entries::WELL_KNOWN.named("contract", "max_txs"));
+}
+
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+#[config(tag = "source")]
+pub enum ScreeningListConfig {
+    /// Screening list is fetched via HTTP.
+    Http(ScreeningListHttp),
+    /// Screening list is read from a local file.
+    File(ScreeningListFile),
+}
+
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+pub struct ScreeningListHttp {
+    /// HTTP URL to fetch the list from.
+    pub http_file_url: String,
+    /// Refresh interval between fetches.
+    #[config(default_t = 5 * TimeUnit::Minutes)]
+    pub refresh_interval: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+pub struct ScreeningListFile {
+    /// Path to the local file with the list.
+    pub path: PathBuf,
+    /// Refresh interval between file reads.
+    #[config(default_t = 1 * TimeUnit::Minutes)]
+    pub refresh_interval: Duration,
+}
+
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
 #[config(derive(Default))]
 pub struct CircuitBreakerConfig {
@@ -208,6 +477,75 @@ pub struct CircuitBreakerConfig {
     /// but the circuit breaker still checks that the replica DB is reachable.
     #[config(default_t = Some(Duration::from_secs(100)))]
     pub replication_lag_limit: Option<Duration>,
+    /// Checks the ETH balance of the operator accounts on L1. If not set, the balance is not checked.
+    #[config(nest)]
+    pub l1_balance: Option<L1BalanceBreakerConfig>,
+    /// Checks the balance of the data availability client wallet. If not set, the balance is not checked.
+    #[config(nest)]
+    pub da_balance: Option<DaBalanceBreakerConfig>,
+    /// Checks how many sealed L1 batches are not proven on L1. If not set, the backlog is not checked.
+    #[config(nest)]
+    pub unproven_batches: Option<UnprovenBatchesBreakerConfig>,
+    /// Checks how far L1 batch execution lags behind commitment. If not set, the lag is not checked.
+    #[config(nest)]
+    pub commit_to_execute_lag: Option<CommitToExecuteLagBreakerConfig>,
+}
+
+/// Reaction of an optional circuit breaker to a violated condition.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitBreakerMode {
+    /// Stop accepting transactions via the API until the condition is resolved.
+    ///
+    /// Transaction acceptance is paused only in the process running the circuit breaker. L1 batch breakers run
+    /// in every process, but balance breakers run together with the components they monitor (the L1 balance breaker
+    /// with `eth_tx_aggregator` / `eth_tx_manager`, the DA balance breaker with `da_dispatcher`). Hence, the node
+    /// refuses to start if a balance breaker in this mode runs in a process without `http_api` or `ws_api`.
+    PauseTxs,
+    /// Only log an error and report the violation via metrics.
+    #[default]
+    Alert,
+}
+
+impl WellKnown for CircuitBreakerMode {
+    type Deserializer = Serde![str];
+    const DE: Self::Deserializer = Serde![str];
+}
+
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+pub struct L1BalanceBreakerConfig {
+    /// Minimum ETH balance (in gwei) of each operator account, including the blob operator if it is configured.
+    pub min_balance_gwei: u64,
+    /// Reaction to the balance dropping below `min_balance_gwei`.
+    #[config(default)]
+    pub mode: CircuitBreakerMode,
+}
+
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+pub struct DaBalanceBreakerConfig {
+    /// Minimum balance of the DA client wallet, in the units reported by the DA client.
+    pub min_balance: u64,
+    /// Reaction to the balance dropping below `min_balance`.
+    #[config(default)]
+    pub mode: CircuitBreakerMode,
+}
+
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+pub struct UnprovenBatchesBreakerConfig {
+    /// Maximum number of sealed L1 batches not yet proven on L1.
+    pub max_batches: u32,
+    /// Reaction to the backlog exceeding `max_batches`.
+    #[config(default)]
+    pub mode: CircuitBreakerMode,
+}
+
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+pub struct CommitToExecuteLagBreakerConfig {
+    /// Maximum time between committing the oldest non-executed L1 batch and now.
+    pub max_lag: Duration,
+    /// Reaction to the lag exceeding `max_lag`.
+    #[config(default)]
+    pub mode: CircuitBreakerMode,
 }
 
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
@@ -297,6 +635,15 @@ mod tests {
                 reject_tx_at_gas_percentage: 0.5,
                 max_pubdata_per_batch: ByteSize(131_072),
                 max_circuits_per_batch: 24100,
+                proving_cost: Some(ProvingCostConfig {
+                    circuit_proving_time: Duration::from_millis(1500),
+                    circuit_proving_time_overrides: CircuitProvingTimeOverrides::from_iter([(
+                        "main_vm", 3_000,
+                    )]),
+                    prover_cost_per_hour: 2.5,
+                    max_proving_time_per_batch: Some(Duration::from_secs(3_600)),
+                    max_cost_per_batch: Some(2.0),
+                }),
             },
             l1_batch_commit_deadline: Duration::from_millis(2500),
             l2_block_max_payload_size: ByteSize(1_000_000),
@@ -313,6 +660,28 @@ mod tests {
                 http_file_url: "http://deployment-allowlist/".to_owned(),
                 refresh_interval: Duration::from_secs(120),
             })),
+            congestion_pricing: Some(CongestionPricingConfig {
+                target_utilization: 0.6,
+                max_change_per_block: 0.1,
+                max_multiplier: 5.0,
+                poll_interval: Duration::from_millis(500),
+            }),
+            tx_policy: TxPolicyConfig {
+                denied_addresses: HashSet::from([Address::repeat_byte(0xde)]),
+                denied_selectors: vec![
+                    "0xa9059cbb".to_owned(),
+                    "0x1111111111111111111111111111111111111111:0x095ea7b3".to_owned(),
+                ],
+                contract_rate_limits: ContractRateLimits::from_iter([(
+                    Address::repeat_byte(0x22),
+                    NonZeroU32::new(100).unwrap(),
+                )]),
+                rate_limit_window: Duration::from_secs(30),
+                screening_list: Some(ScreeningListConfig::Http(ScreeningListHttp {
+                    http_file_url: "http://screening-list/".to_owned(),
+                    refresh_interval: Duration::from_secs(60),
+                })),
+            },
         }
     }
 
@@ -339,6 +708,11 @@ mod tests {
             CHAIN_STATE_KEEPER_MAX_GAS_PER_BATCH="200000000"
             CHAIN_STATE_KEEPER_MAX_PUBDATA_PER_BATCH="131072"
             CHAIN_STATE_KEEPER_MAX_CIRCUITS_PER_BATCH="24100"
+            CHAIN_STATE_KEEPER_PROVING_COST_CIRCUIT_PROVING_TIME_MS=1500
+            CHAIN_STATE_KEEPER_PROVING_COST_CIRCUIT_PROVING_TIME_OVERRIDES="main_vm=3000"
+            CHAIN_STATE_KEEPER_PROVING_COST_PROVER_COST_PER_HOUR=2.5
+            CHAIN_STATE_KEEPER_PROVING_COST_MAX_PROVING_TIME_PER_BATCH=60 min
+            CHAIN_STATE_KEEPER_PROVING_COST_MAX_COST_PER_BATCH=2.0
             CHAIN_STATE_KEEPER_FEE_MODEL_VERSION="V2"
             CHAIN_STATE_KEEPER_VALIDATION_COMPUTATIONAL_GAS_LIMIT="10000000"
             CHAIN_STATE_KEEPER_SAVE_CALL_TRACES="false"
@@ -346,6 +720,17 @@ mod tests {
             CHAIN_STATE_KEEPER_DEPLOYMENT_ALLOWLIST_SOURCE=Dynamic
             CHAIN_STATE_KEEPER_DEPLOYMENT_ALLOWLIST_HTTP_FILE_URL=http://deployment-allowlist/
             CHAIN_STATE_KEEPER_DEPLOYMENT_ALLOWLIST_REFRESH_INTERVAL=2 min
+            CHAIN_STATE_KEEPER_CONGESTION_PRICING_TARGET_UTILIZATION=0.6
+            CHAIN_STATE_KEEPER_CONGESTION_PRICING_MAX_CHANGE_PER_BLOCK=0.1
+            CHAIN_STATE_KEEPER_CONGESTION_PRICING_MAX_MULTIPLIER=5.0
+            CHAIN_STATE_KEEPER_CONGESTION_PRICING_POLL_INTERVAL_MS=500
+            CHAIN_STATE_KEEPER_TX_POLICY_DENIED_ADDRESSES=0xdededededededededededededededededededede
+            CHAIN_STATE_KEEPER_TX_POLICY_DENIED_SELECTORS="0xa9059cbb,0x1111111111111111111111111111111111111111:0x095ea7b3"
+            CHAIN_STATE_KEEPER_TX_POLICY_CONTRACT_RATE_LIMITS="0x2222222222222222222222222222222222222222=100"
+            CHAIN_STATE_KEEPER_TX_POLICY_RATE_LIMIT_WINDOW=30s
+            CHAIN_STATE_KEEPER_TX_POLICY_SCREENING_LIST_SOURCE=Http
+            CHAIN_STATE_KEEPER_TX_POLICY_SCREENING_LIST_HTTP_FILE_URL=http://screening-list/
+            CHAIN_STATE_KEEPER_TX_POLICY_SCREENING_LIST_REFRESH_INTERVAL=1 min
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -379,12 +764,39 @@ mod tests {
           validation_computational_gas_limit: 10000000
           save_call_traces: false
           max_circuits_per_batch: 24100
+          proving_cost:
+            circuit_proving_time_ms: 1500
+            circuit_proving_time_overrides:
+              - circuit: main_vm
+                time_ms: 3000
+            prover_cost_per_hour: 2.5
+            max_proving_time_per_batch_secs: 3600
+            max_cost_per_batch: 2.0
           l2_block_max_payload_size: 1000000
           protective_reads_persistence_enabled: true
           deployment_allowlist:
             source: Url
             http_file_url: http://deployment-allowlist/
             refresh_interval_secs: 120
+          congestion_pricing:
+            target_utilization: 0.6
+            max_change_per_block: 0.1
+            max_multiplier: 5.0
+            poll_interval_ms: 500
+          tx_policy:
+            denied_addresses:
+              - "0xdededededededededededededededededededede"
+            denied_selectors:
+              - "0xa9059cbb"
+              - "0x1111111111111111111111111111111111111111:0x095ea7b3"
+            contract_rate_limits:
+              - contract: "0x2222222222222222222222222222222222222222"
+                max_txs: 100
+            rate_limit_window_secs: 30
+            screening_list:
+              source: Http
+              http_file_url: http://screening-list/
+              refresh_interval_secs: 60
         "#;
 
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
@@ -417,12 +829,37 @@ mod tests {
           validation_computational_gas_limit: 10000000
           save_call_traces: false
           max_circuits_per_batch: 24100
+          proving_cost:
+            circuit_proving_time: 1500ms
+            circuit_proving_time_overrides:
+              main_vm: 3000
+            prover_cost_per_hour: 2.5
+            max_proving_time_per_batch: 60 min
+            max_cost_per_batch: 2.0
           l2_block_max_payload_size: 1000000 bytes
           protective_reads_persistence_enabled: true
           deployment_allowlist:
             source: Url
             http_file_url: http://deployment-allowlist/
             refresh_interval: 2min
+          congestion_pricing:
+            target_utilization: 0.6
+            max_change_per_block: 0.1
+            max_multiplier: 5.0
+            poll_interval: 500ms
+          tx_policy:
+            denied_addresses:
+              - "0xdededededededededededededededededededede"
+            denied_selectors:
+              - "0xa9059cbb"
+              - "0x1111111111111111111111111111111111111111:0x095ea7b3"
+            contract_rate_limits:
+              "0x2222222222222222222222222222222222222222": 100
+            rate_limit_window: 30s
+            screening_list:
+              source: Http
+              http_file_url: http://screening-list/
+              refresh_interval: 1 min
         "#;
 
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
@@ -506,6 +943,22 @@ mod tests {
         CircuitBreakerConfig {
             sync_interval: Duration::from_secs(1),
             replication_lag_limit: Some(Duration::from_secs(10)),
+            l1_balance: Some(L1BalanceBreakerConfig {
+                min_balance_gwei: 500_000_000,
+                mode: CircuitBreakerMode::PauseTxs,
+            }),
+            da_balance: Some(DaBalanceBreakerConfig {
+                min_balance: 1_000,
+                mode: CircuitBreakerMode::Alert,
+            }),
+            unproven_batches: Some(UnprovenBatchesBreakerConfig {
+                max_batches: 50,
+                mode: CircuitBreakerMode::PauseTxs,
+            }),
+            commit_to_execute_lag: Some(CommitToExecuteLagBreakerConfig {
+                max_lag: Duration::from_secs(7_200),
+                mode: CircuitBreakerMode::Alert,
+            }),
         }
     }
 
@@ -516,6 +969,14 @@ mod tests {
             CHAIN_CIRCUIT_BREAKER_HTTP_REQ_MAX_RETRY_NUMBER="5"
             CHAIN_CIRCUIT_BREAKER_HTTP_REQ_RETRY_INTERVAL_SEC="2"
             CHAIN_CIRCUIT_BREAKER_REPLICATION_LAG_LIMIT_SEC="10"
+            CHAIN_CIRCUIT_BREAKER_L1_BALANCE_MIN_BALANCE_GWEI="500000000"
+            CHAIN_CIRCUIT_BREAKER_L1_BALANCE_MODE="pause_txs"
+            CHAIN_CIRCUIT_BREAKER_DA_BALANCE_MIN_BALANCE="1000"
+            CHAIN_CIRCUIT_BREAKER_DA_BALANCE_MODE="alert"
+            CHAIN_CIRCUIT_BREAKER_UNPROVEN_BATCHES_MAX_BATCHES="50"
+            CHAIN_CIRCUIT_BREAKER_UNPROVEN_BATCHES_MODE="pause_txs"
+            CHAIN_CIRCUIT_BREAKER_COMMIT_TO_EXECUTE_LAG_MAX_LAG_SEC="7200"
+            CHAIN_CIRCUIT_BREAKER_COMMIT_TO_EXECUTE_LAG_MODE="alert"
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -531,6 +992,18 @@ mod tests {
           http_req_max_retry_number: 5
           http_req_retry_interval_sec: 2
           replication_lag_limit_sec: 10
+          l1_balance:
+            min_balance_gwei: 500000000
+            mode: pause_txs
+          da_balance:
+            min_balance: 1000
+            mode: alert
+          unproven_batches:
+            max_batches: 50
+            mode: pause_txs
+          commit_to_execute_lag:
+            max_lag: 2 hours
+            mode: alert
         "#;
 
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
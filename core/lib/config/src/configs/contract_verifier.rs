@@ -1,5 +1,6 @@
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
+    num::NonZeroU32,
     time::Duration,
 };
 
@@ -22,6 +23,16 @@ pub struct ContractVerifierConfig {
     /// Etherscan API URL that is used for contract verification in Etherscan.
     /// If not set, the Etherscan verification is disabled.
     pub etherscan_api_url: Option<String>,
+    /// Whether to propagate verification info to newly deployed contracts with bytecode matching
+    /// an already verified contract.
+    #[config(default_t = true)]
+    pub propagation_enabled: bool,
+    /// Maximum number of L2 blocks scanned for deployments during a single propagation iteration.
+    #[config(default_t = NonZeroU32::new(1_000).unwrap())]
+    pub propagation_batch_size: NonZeroU32,
+    /// Polling interval for propagation once all sealed L2 blocks are processed.
+    #[config(default_t = Duration::from_secs(5))]
+    pub propagation_poll_interval: Duration,
 }
 
 impl ContractVerifierConfig {
@@ -42,6 +53,9 @@ mod tests {
             prometheus_port: 3314,
             port: 3070,
             etherscan_api_url: Some("https://api.etherscan.io/".to_owned()),
+            propagation_enabled: false,
+            propagation_batch_size: NonZeroU32::new(500).unwrap(),
+            propagation_poll_interval: Duration::from_secs(10),
         }
     }
 
@@ -52,6 +66,9 @@ mod tests {
             CONTRACT_VERIFIER_PROMETHEUS_PORT=3314
             CONTRACT_VERIFIER_PORT=3070
             CONTRACT_VERIFIER_ETHERSCAN_API_URL="https://api.etherscan.io/"
+            CONTRACT_VERIFIER_PROPAGATION_ENABLED=false
+            CONTRACT_VERIFIER_PROPAGATION_BATCH_SIZE=500
+            CONTRACT_VERIFIER_PROPAGATION_POLL_INTERVAL_MS=10000
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -68,6 +85,9 @@ mod tests {
           compilation_timeout: 30
           prometheus_port: 3314
           etherscan_api_url: https://api.etherscan.io/
+          propagation_enabled: false
+          propagation_batch_size: 500
+          propagation_poll_interval_ms: 10000
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
         let config: ContractVerifierConfig = test_complete(yaml).unwrap();
@@ -81,6 +101,9 @@ mod tests {
           compilation_timeout: 30s
           prometheus_port: 3314
           etherscan_api_url: https://api.etherscan.io/
+          propagation_enabled: false
+          propagation_batch_size: 500
+          propagation_poll_interval: 10s
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
         let config: ContractVerifierConfig = test_complete(yaml).unwrap();
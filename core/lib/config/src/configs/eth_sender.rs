@@ -156,6 +156,7 @@ pub struct SenderConfig {
     #[config(with = Serde![str])]
     pub pubdata_sending_mode: PubdataSendingMode,
     /// Special mode specifically for gateway migration to allow all inflight txs to be processed.
+    /// This is the initial state; aggregation can be paused / resumed at runtime via the admin API.
     #[config(default)]
     pub tx_aggregation_paused: bool,
     /// Special mode specifically for gateway migration to decrease number of non-executed batches.
@@ -55,6 +55,14 @@ pub struct ExperimentalVmConfig {
     /// or transaction validation), so the legacy VM will always be used for them.
     #[config(default, with = Serde![str])]
     pub api_fast_vm_mode: FastVmMode,
+    /// Number of worker threads speculatively pre-executing upcoming mempool transactions in the state keeper
+    /// to prefetch storage slots they read. Transactions are still executed sequentially, and speculative results
+    /// are never reused, so this never affects produced blocks. If set to 0 (the default), prefetching is disabled.
+    #[config(default)]
+    pub state_keeper_speculation_workers: usize,
+    /// Maximum number of upcoming mempool transactions considered for speculative prefetching at a time.
+    #[config(default_t = 16)]
+    pub state_keeper_speculation_lookahead: usize,
 }
 
 #[cfg(test)]
@@ -69,6 +77,8 @@ mod tests {
     fn assert_experimental_vm_config(config: ExperimentalVmConfig) {
         assert_eq!(config.state_keeper_fast_vm_mode, FastVmMode::New);
         assert_eq!(config.api_fast_vm_mode, FastVmMode::Shadow);
+        assert_eq!(config.state_keeper_speculation_workers, 4);
+        assert_eq!(config.state_keeper_speculation_lookahead, 32);
         assert_eq!(config.playground.fast_vm_mode, FastVmMode::Shadow);
         assert_eq!(
             config.playground.db_path.unwrap().as_os_str(),
@@ -84,6 +94,8 @@ mod tests {
         let env = r#"
             EXPERIMENTAL_VM_STATE_KEEPER_FAST_VM_MODE=new
             EXPERIMENTAL_VM_API_FAST_VM_MODE=shadow
+            EXPERIMENTAL_VM_STATE_KEEPER_SPECULATION_WORKERS=4
+            EXPERIMENTAL_VM_STATE_KEEPER_SPECULATION_LOOKAHEAD=32
             EXPERIMENTAL_VM_PLAYGROUND_FAST_VM_MODE=shadow
             EXPERIMENTAL_VM_PLAYGROUND_DB_PATH=/db/vm_playground
             EXPERIMENTAL_VM_PLAYGROUND_FIRST_PROCESSED_BATCH=123
@@ -108,6 +120,8 @@ mod tests {
             window_size: 1
           state_keeper_fast_vm_mode: NEW
           api_fast_vm_mode: SHADOW
+          state_keeper_speculation_workers: 4
+          state_keeper_speculation_lookahead: 32
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
         let config: ExperimentalVmConfig = Tester::default()
@@ -1,6 +1,7 @@
-use std::time::Duration;
+use std::{num::NonZeroUsize, time::Duration};
 
-use smart_config::{DescribeConfig, DeserializeConfig};
+use smart_config::{de::Delimited, DescribeConfig, DeserializeConfig};
+use zksync_basic_types::Address;
 
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
 #[config(derive(Default))]
@@ -18,6 +19,73 @@ pub struct ForcedPriceClientConfig {
     pub next_value_fluctuation: u32,
 }
 
+/// Configuration of the Chainlink-compatible aggregator feed price client (used if `source` is set to `chainlink`).
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+pub struct ChainlinkPriceFeedConfig {
+    /// L1 address of the base token priced by the feed. Ratios for other tokens cannot be fetched.
+    pub token_address: Address,
+    /// L1 address of the aggregator feed quoting the base token price in ETH.
+    pub feed_address: Address,
+    /// Whether the feed quotes the ETH price in the base token (i.e., is an ETH / base token feed).
+    #[config(default)]
+    pub inverted: bool,
+    /// Maximum age of the latest feed round. Older rounds are considered stale and are not used.
+    #[config(default_t = Duration::from_secs(3_600))]
+    pub max_staleness: Duration,
+}
+
+/// Configuration of the Uniswap V3 pool TWAP price client (used if `source` is set to `uniswap_v3`).
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+pub struct UniswapV3TwapConfig {
+    /// L1 address of the base token priced by the pool. Ratios for other tokens cannot be fetched.
+    pub token_address: Address,
+    /// L1 address of the Uniswap V3 pool pairing the base token with WETH.
+    pub pool_address: Address,
+    /// Window over which the time-weighted average price is computed. The pool must have enough
+    /// observations to cover the window.
+    #[config(default_t = Duration::from_secs(1_800))]
+    pub twap_window: Duration,
+    /// Maximum age of the latest pool observation (i.e., of the latest swap or liquidity change).
+    /// If the pool wasn't touched for longer, its price is considered stale and is not used.
+    #[config(default_t = Duration::from_secs(86_400))]
+    pub max_staleness: Duration,
+}
+
+/// Endpoint and credentials of a single price source queried by the aggregating client.
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+#[config(derive(Default))]
+pub struct PriceSourceConfig {
+    pub base_url: Option<String>,
+    pub api_key: Option<String>,
+}
+
+/// Configuration of the aggregating price client (used if `source` is set to `aggregated`). The client queries
+/// all `sources` concurrently and returns the median of their ratios.
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+pub struct PriceAggregationConfig {
+    /// Price sources to aggregate, e.g. `coingecko,coinmarketcap`. Each source must be specified at most once.
+    #[config(with = Delimited(","))]
+    pub sources: Vec<String>,
+    /// Minimum number of sources that must return a ratio within the deviation band from the median.
+    /// If the quorum isn't reached, fetching the ratio fails.
+    #[config(default_t = NonZeroUsize::new(2).unwrap())]
+    pub min_quorum: NonZeroUsize,
+    /// Maximum deviation of a source ratio from the median of all sources, in percent. Ratios outside
+    /// this band are rejected as outliers.
+    #[config(default_t = 5.0, validate(0.0.., "must be non-negative"))]
+    pub max_deviation_percent: f64,
+    /// Maximum change of the aggregated ratio compared to the previously returned one, in percent.
+    /// Larger changes are capped to this value. If not set, changes are not capped.
+    #[config(validate(0.0.., "must be non-negative"))]
+    pub max_change_percent: Option<f64>,
+    /// CoinGecko endpoint and credentials. If not set, top-level `base_url` and `api_key` are used.
+    #[config(nest)]
+    pub coingecko: Option<PriceSourceConfig>,
+    /// CoinMarketCap endpoint and credentials. If not set, top-level `base_url` and `api_key` are used.
+    #[config(nest)]
+    pub coinmarketcap: Option<PriceSourceConfig>,
+}
+
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
 #[config(derive(Default))]
 pub struct ExternalPriceApiClientConfig {
@@ -29,6 +97,12 @@ pub struct ExternalPriceApiClientConfig {
     pub client_timeout: Duration,
     #[config(nest)]
     pub forced: Option<ForcedPriceClientConfig>,
+    #[config(nest)]
+    pub aggregation: Option<PriceAggregationConfig>,
+    #[config(nest)]
+    pub chainlink: Option<ChainlinkPriceFeedConfig>,
+    #[config(nest)]
+    pub uniswap_v3: Option<UniswapV3TwapConfig>,
 }
 
 #[cfg(test)]
@@ -49,6 +123,40 @@ mod tests {
                 fluctuation: Some(10),
                 next_value_fluctuation: 3,
             }),
+            aggregation: Some(PriceAggregationConfig {
+                sources: vec!["coingecko".to_owned(), "coinmarketcap".to_owned()],
+                min_quorum: NonZeroUsize::new(2).unwrap(),
+                max_deviation_percent: 3.5,
+                max_change_percent: Some(10.0),
+                coingecko: Some(PriceSourceConfig {
+                    base_url: Some("https://api.coingecko.com".to_owned()),
+                    api_key: Some("coingecko-key".to_owned()),
+                }),
+                coinmarketcap: Some(PriceSourceConfig {
+                    base_url: Some("https://pro-api.coinmarketcap.com".to_owned()),
+                    api_key: Some("cmc-key".to_owned()),
+                }),
+            }),
+            chainlink: Some(ChainlinkPriceFeedConfig {
+                token_address: "0x1f9840a85d5af5bf1d1762f925bdaddc4201f984"
+                    .parse()
+                    .unwrap(),
+                feed_address: "0xd6aa3d25116d8da79ea0246c4826eb951872e02e"
+                    .parse()
+                    .unwrap(),
+                inverted: true,
+                max_staleness: Duration::from_secs(7_200),
+            }),
+            uniswap_v3: Some(UniswapV3TwapConfig {
+                token_address: "0x1f9840a85d5af5bf1d1762f925bdaddc4201f984"
+                    .parse()
+                    .unwrap(),
+                pool_address: "0x1d42064fc4beb5f8aaf85f4617ae8b3b5b8bd801"
+                    .parse()
+                    .unwrap(),
+                twap_window: Duration::from_secs(600),
+                max_staleness: Duration::from_secs(3_600),
+            }),
         }
     }
 
@@ -63,6 +171,22 @@ mod tests {
             EXTERNAL_PRICE_API_CLIENT_FORCED_DENOMINATOR=1
             EXTERNAL_PRICE_API_CLIENT_FORCED_FLUCTUATION=10
             EXTERNAL_PRICE_API_CLIENT_FORCED_NEXT_VALUE_FLUCTUATION=3
+            EXTERNAL_PRICE_API_CLIENT_AGGREGATION_SOURCES=coingecko,coinmarketcap
+            EXTERNAL_PRICE_API_CLIENT_AGGREGATION_MIN_QUORUM=2
+            EXTERNAL_PRICE_API_CLIENT_AGGREGATION_MAX_DEVIATION_PERCENT=3.5
+            EXTERNAL_PRICE_API_CLIENT_AGGREGATION_MAX_CHANGE_PERCENT=10
+            EXTERNAL_PRICE_API_CLIENT_AGGREGATION_COINGECKO_BASE_URL=https://api.coingecko.com
+            EXTERNAL_PRICE_API_CLIENT_AGGREGATION_COINGECKO_API_KEY=coingecko-key
+            EXTERNAL_PRICE_API_CLIENT_AGGREGATION_COINMARKETCAP_BASE_URL=https://pro-api.coinmarketcap.com
+            EXTERNAL_PRICE_API_CLIENT_AGGREGATION_COINMARKETCAP_API_KEY=cmc-key
+            EXTERNAL_PRICE_API_CLIENT_CHAINLINK_TOKEN_ADDRESS=0x1f9840a85d5af5bf1d1762f925bdaddc4201f984
+            EXTERNAL_PRICE_API_CLIENT_CHAINLINK_FEED_ADDRESS=0xd6aa3d25116d8da79ea0246c4826eb951872e02e
+            EXTERNAL_PRICE_API_CLIENT_CHAINLINK_INVERTED=true
+            EXTERNAL_PRICE_API_CLIENT_CHAINLINK_MAX_STALENESS_MS=7200000
+            EXTERNAL_PRICE_API_CLIENT_UNISWAP_V3_TOKEN_ADDRESS=0x1f9840a85d5af5bf1d1762f925bdaddc4201f984
+            EXTERNAL_PRICE_API_CLIENT_UNISWAP_V3_POOL_ADDRESS=0x1d42064fc4beb5f8aaf85f4617ae8b3b5b8bd801
+            EXTERNAL_PRICE_API_CLIENT_UNISWAP_V3_TWAP_WINDOW_MS=600000
+            EXTERNAL_PRICE_API_CLIENT_UNISWAP_V3_MAX_STALENESS_MS=3600000
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -84,6 +208,27 @@ mod tests {
           forced_next_value_fluctuation: 3
           forced:
             fluctuation: 10
+          aggregation:
+            sources: [coingecko, coinmarketcap]
+            min_quorum: 2
+            max_deviation_percent: 3.5
+            max_change_percent: 10
+            coingecko:
+              base_url: "https://api.coingecko.com"
+              api_key: coingecko-key
+            coinmarketcap:
+              base_url: "https://pro-api.coinmarketcap.com"
+              api_key: cmc-key
+          chainlink:
+            token_address: "0x1f9840a85d5af5bf1d1762f925bdaddc4201f984"
+            feed_address: "0xd6aa3d25116d8da79ea0246c4826eb951872e02e"
+            inverted: true
+            max_staleness_ms: 7200000
+          uniswap_v3:
+            token_address: "0x1f9840a85d5af5bf1d1762f925bdaddc4201f984"
+            pool_address: "0x1d42064fc4beb5f8aaf85f4617ae8b3b5b8bd801"
+            twap_window_ms: 600000
+            max_staleness_ms: 3600000
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
         let config: ExternalPriceApiClientConfig = test_complete(yaml).unwrap();
@@ -2,12 +2,14 @@ use smart_config::{ConfigSchema, DescribeConfig, DeserializeConfig};
 
 use crate::{
     configs::{
+        admin_api::AdminApiConfig,
         base_token_adjuster::BaseTokenAdjusterConfig,
         chain::{CircuitBreakerConfig, MempoolConfig, StateKeeperConfig, TimestampAsserterConfig},
         consensus::ConsensusConfig,
         da_dispatcher::DADispatcherConfig,
         eth_proof_manager::EthProofManagerConfig,
         house_keeper::HouseKeeperConfig,
+        notifier::NotifierConfig,
         prover_job_monitor::ProverJobMonitorConfig,
         pruning::PruningConfig,
         snapshot_recovery::SnapshotRecoveryConfig,
@@ -39,6 +41,10 @@ pub struct GeneralConfig {
     pub state_keeper_config: Option<StateKeeperConfig>,
     #[config(nest, rename = "house_keeper")]
     pub house_keeper_config: HouseKeeperConfig,
+    #[config(nest)]
+    pub notifier: NotifierConfig,
+    #[config(nest)]
+    pub admin_api: AdminApiConfig,
 
     #[config(nest, rename = "proof_compressor", alias = "fri_proof_compressor")]
     pub proof_compressor_config: Option<FriProofCompressorConfig>,
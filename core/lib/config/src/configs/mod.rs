@@ -1,5 +1,6 @@
 // Public re-exports
 pub use self::{
+    admin_api::AdminApiConfig,
     api::ApiConfig,
     base_token_adjuster::BaseTokenAdjusterConfig,
     commitment_generator::CommitmentGeneratorConfig,
@@ -27,13 +28,15 @@ pub use self::{
     general::{full_config_schema, GeneralConfig},
     genesis::{GenesisConfig, GenesisConfigWrapper},
     node_sync::NodeSyncConfig,
+    notifier::NotifierConfig,
     object_store::ObjectStoreConfig,
     observability::{ObservabilityConfig, OpentelemetryConfig},
     proof_data_handler::ProofDataHandlerConfig,
     prover_job_monitor::ProverJobMonitorConfig,
     pruning::PruningConfig,
     secrets::{
-        ContractVerifierSecrets, DataAvailabilitySecrets, L1Secrets, PostgresSecrets, Secrets,
+        AdminApiSecrets, ContractVerifierSecrets, DataAvailabilitySecrets, L1Secrets,
+        NotifierSecrets, PostgresSecrets, Secrets,
     },
     snapshot_recovery::SnapshotRecoveryConfig,
     snapshots_creator::SnapshotsCreatorConfig,
@@ -42,6 +45,7 @@ pub use self::{
     vm_runner::{BasicWitnessInputProducerConfig, ProtectiveReadsWriterConfig},
 };
 
+pub mod admin_api;
 pub mod api;
 pub mod base_token_adjuster;
 pub mod chain;
@@ -70,6 +74,7 @@ pub mod genesis;
 pub mod house_keeper;
 pub mod networks;
 pub mod node_sync;
+pub mod notifier;
 pub mod object_store;
 pub mod observability;
 pub mod proof_data_handler;
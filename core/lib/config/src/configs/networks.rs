@@ -1,7 +1,7 @@
 use std::{num::NonZeroUsize, time::Duration};
 
 use smart_config::{
-    de::{Optional, Serde},
+    de::{Delimited, Optional, Serde},
     DescribeConfig, DeserializeConfig,
 };
 use zksync_basic_types::{url::SensitiveUrl, Address, L1ChainId, L2ChainId, SLChainId};
@@ -33,6 +33,15 @@ pub struct NetworksConfig {
     /// Rate limiting configuration for the L2 peer node.
     #[config(default_t = NonZeroUsize::new(100).unwrap())]
     pub main_node_rate_limit_rps: NonZeroUsize,
+    /// Additional L2 peer nodes (e.g., trusted external nodes) used if the node at `main_node_url` is unavailable,
+    /// and to spread the load of fetching historical data. A node is only used while it agrees
+    /// with `main_node_url` on block hashes. Rate limiting is applied to each node separately.
+    #[config(secret, default, with = Delimited(","))]
+    pub fallback_main_node_urls: Vec<SensitiveUrl>,
+    /// Interval between reachability and consistency checks for L2 peer nodes. Only used
+    /// if `fallback_main_node_urls` are specified.
+    #[config(default_t = Duration::from_secs(10))]
+    pub main_node_check_interval: Duration,
 
     #[config(default_t = Duration::from_secs(60))]
     pub bridge_addresses_refresh_interval: Duration,
@@ -45,6 +54,8 @@ impl NetworksConfig {
             l1_chain_id: L1ChainId(9),
             main_node_url: "http://localhost:3050/".parse().unwrap(),
             main_node_rate_limit_rps: 100.try_into().unwrap(),
+            fallback_main_node_urls: vec![],
+            main_node_check_interval: Duration::from_secs(10),
             bridge_addresses_refresh_interval: Duration::from_secs(60),
             gateway_chain_id: None,
         }
@@ -64,6 +75,11 @@ mod tests {
             gateway_chain_id: Some(SLChainId(123)),
             main_node_url: "http://127.0.0.1:3050/".parse().unwrap(),
             main_node_rate_limit_rps: NonZeroUsize::new(200).unwrap(),
+            fallback_main_node_urls: vec![
+                "http://127.0.0.1:3060/".parse().unwrap(),
+                "http://127.0.0.1:3070/".parse().unwrap(),
+            ],
+            main_node_check_interval: Duration::from_secs(5),
             bridge_addresses_refresh_interval: Duration::from_secs(15),
         }
     }
@@ -86,6 +102,8 @@ mod tests {
             EN_GATEWAY_CHAIN_ID=123
             EN_MAIN_NODE_URL=http://127.0.0.1:3050/
             EN_MAIN_NODE_RATE_LIMIT_RPS=200
+            EN_FALLBACK_MAIN_NODE_URLS=http://127.0.0.1:3060/,http://127.0.0.1:3070/
+            EN_MAIN_NODE_CHECK_INTERVAL="5s"
             EN_BRIDGE_ADDRESSES_REFRESH_INTERVAL="15s"
         "#;
         let env = Environment::from_dotenv("test.env", env)
@@ -103,6 +121,10 @@ mod tests {
         let yaml = r#"
             main_node_url: http://127.0.0.1:3050/
             main_node_rate_limit_rps: 200
+            fallback_main_node_urls:
+              - http://127.0.0.1:3060/
+              - http://127.0.0.1:3070/
+            main_node_check_interval: 5s
             gateway_url: null
             l2_chain_id: 271
             l1_chain_id: 9
@@ -123,6 +145,10 @@ mod tests {
           external_node:
             main_node_url: http://127.0.0.1:3050/
             main_node_rate_limit_rps: 200
+            fallback_main_node_urls:
+              - http://127.0.0.1:3060/
+              - http://127.0.0.1:3070/
+            main_node_check_interval: 5s
             gateway_url: null
             l2_chain_id: 271
             l1_chain_id: 9
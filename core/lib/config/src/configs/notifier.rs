@@ -0,0 +1,83 @@
+use std::{num::NonZeroU32, time::Duration};
+
+use smart_config::{metadata::TimeUnit, DescribeConfig, DeserializeConfig};
+
+/// Configuration for the notifier pushing alerts about the node state to webhooks.
+/// Webhook URLs are configured as [secrets](crate::configs::secrets::NotifierSecrets).
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+#[config(derive(Default))]
+pub struct NotifierConfig {
+    /// Interval between checks of the node state.
+    #[config(default_t = Duration::from_secs(30))]
+    pub poll_interval: Duration,
+    /// Name of the node included into notifications.
+    #[config(default_t = "zksync_server".into())]
+    pub source: String,
+    /// Interval after which a notification for a still active alert is repeated.
+    #[config(default_t = 1 * TimeUnit::Hours)]
+    pub repeat_interval: Duration,
+    /// Maximum number of notifications sent to each webhook per minute. Notifications exceeding the limit
+    /// are postponed until the next check.
+    #[config(default_t = NonZeroU32::new(10).unwrap())]
+    pub max_notifications_per_minute: NonZeroU32,
+    /// Maximum age of the oldest L1 batch not committed on L1. If not set, the commit lag is not checked.
+    pub max_commit_lag: Option<Duration>,
+    /// Maximum age of the oldest L1 batch not proven on L1. If not set, the proving lag is not checked.
+    pub max_prove_lag: Option<Duration>,
+    /// Maximum age of the oldest L1 batch not executed on L1. If not set, the execution lag is not checked.
+    pub max_execute_lag: Option<Duration>,
+}
+
+#[cfg(test)]
+mod tests {
+    use smart_config::{testing::test_complete, Environment, Yaml};
+
+    use super::*;
+
+    fn expected_config() -> NotifierConfig {
+        NotifierConfig {
+            poll_interval: Duration::from_secs(10),
+            source: "mainnet-sequencer".into(),
+            repeat_interval: Duration::from_secs(1_800),
+            max_notifications_per_minute: NonZeroU32::new(5).unwrap(),
+            max_commit_lag: Some(Duration::from_secs(600)),
+            max_prove_lag: Some(Duration::from_secs(7_200)),
+            max_execute_lag: Some(Duration::from_secs(86_400)),
+        }
+    }
+
+    #[test]
+    fn parsing_from_env() {
+        let env = r#"
+            NOTIFIER_POLL_INTERVAL_SEC=10
+            NOTIFIER_SOURCE=mainnet-sequencer
+            NOTIFIER_REPEAT_INTERVAL_SEC=1800
+            NOTIFIER_MAX_NOTIFICATIONS_PER_MINUTE=5
+            NOTIFIER_MAX_COMMIT_LAG_SEC=600
+            NOTIFIER_MAX_PROVE_LAG_SEC=7200
+            NOTIFIER_MAX_EXECUTE_LAG="1 day"
+        "#;
+        let env = Environment::from_dotenv("test.env", env)
+            .unwrap()
+            .strip_prefix("NOTIFIER_");
+
+        let config: NotifierConfig = test_complete(env).unwrap();
+        assert_eq!(config, expected_config());
+    }
+
+    #[test]
+    fn parsing_from_yaml() {
+        let yaml = r#"
+          poll_interval: 10s
+          source: mainnet-sequencer
+          repeat_interval: 30 min
+          max_notifications_per_minute: 5
+          max_commit_lag: 10 min
+          max_prove_lag: 2 hours
+          max_execute_lag: 1 day
+        "#;
+        let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
+        let config: NotifierConfig = test_complete(yaml).unwrap();
+        assert_eq!(config, expected_config());
+    }
+}
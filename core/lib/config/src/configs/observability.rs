@@ -86,6 +86,11 @@ pub struct OpentelemetryConfig {
     /// suboptimal in production environments.
     #[config(fallback = &fallback::Env("OTLP_LOGS_ENDPOINT"))]
     pub logs_endpoint: Option<String>,
+    /// Whether to trace the transaction lifecycle, with a separate trace per transaction spanning its submission,
+    /// execution by the state keeper, and committing / proving / executing the containing L1 batch on L1.
+    /// Produces several spans per transaction, so it may be expensive for high-throughput chains.
+    #[config(default, fallback = &fallback::Env("OPENTELEMETRY_TRACE_TX_LIFECYCLE"))]
+    pub trace_tx_lifecycle: bool,
 }
 
 #[cfg(test)]
@@ -107,6 +112,7 @@ mod tests {
                 level: "info".into(),
                 endpoint: "http://otlp-collector/v1/traces".into(),
                 logs_endpoint: Some("http://otlp-collector/v1/logs".into()),
+                trace_tx_lifecycle: true,
             }),
             log_format: LogFormat::Json,
             log_directives: "zksync=info,zksync_state_keeper=debug".into(),
@@ -124,6 +130,7 @@ mod tests {
             .set_env("OPENTELEMETRY_LEVEL", "info")
             .set_env("OTLP_ENDPOINT", "http://otlp-collector/v1/traces")
             .set_env("OTLP_LOGS_ENDPOINT", "http://otlp-collector/v1/logs")
+            .set_env("OPENTELEMETRY_TRACE_TX_LIFECYCLE", "true")
             .test_complete(smart_config::config!())
             .unwrap();
         assert_eq!(config, expected_config());
@@ -140,6 +147,7 @@ mod tests {
               level: info
               endpoint: http://otlp-collector/v1/traces
               logs_endpoint: http://otlp-collector/v1/logs
+              trace_tx_lifecycle: true
             log_directives: zksync=info,zksync_state_keeper=debug
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
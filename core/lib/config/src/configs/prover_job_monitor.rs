@@ -53,6 +53,14 @@ pub struct ProverJobMonitorConfig {
     /// The interval between runs for Witness Job Queuer.
     #[config(default_t = Duration::from_secs(10))]
     pub witness_job_queuer_run_interval: Duration,
+    /// The interval between runs for Job Priority Ager. Each run increments the priority of jobs that have been
+    /// queued for longer than this interval, so that low-priority batches are not starved.
+    #[config(default_t = 5 * TimeUnit::Minutes)]
+    pub job_priority_ager_run_interval: Duration,
+    /// Priority up to which queued jobs are aged. Deprioritized batches eventually catch up with regular ones,
+    /// while manually raised priorities above this value keep precedence.
+    #[config(default_t = 0)]
+    pub job_priority_ager_max_priority: i32,
     /// HTTP port of the ProverJobMonitor to send requests to.
     pub http_port: u16,
 }
@@ -80,6 +88,8 @@ mod tests {
             prover_queue_reporter_run_interval: Duration::from_secs(10),
             witness_generator_queue_reporter_run_interval: Duration::from_secs(10),
             witness_job_queuer_run_interval: Duration::from_secs(10),
+            job_priority_ager_run_interval: Duration::from_secs(300),
+            job_priority_ager_max_priority: 1,
             http_port: 3074,
         }
     }
@@ -102,6 +112,8 @@ mod tests {
             PROVER_JOB_MONITOR_PROVER_QUEUE_REPORTER_RUN_INTERVAL_MS=10000
             PROVER_JOB_MONITOR_WITNESS_GENERATOR_QUEUE_REPORTER_RUN_INTERVAL_MS=10000
             PROVER_JOB_MONITOR_WITNESS_JOB_QUEUER_RUN_INTERVAL_MS=10000
+            PROVER_JOB_MONITOR_JOB_PRIORITY_AGER_RUN_INTERVAL_MS=300000
+            PROVER_JOB_MONITOR_JOB_PRIORITY_AGER_MAX_PRIORITY=1
             PROVER_JOB_MONITOR_HTTP_PORT=3074
         "#;
         let env = Environment::from_dotenv("test.env", env)
@@ -130,6 +142,8 @@ mod tests {
           prover_queue_reporter_run_interval_ms: 10000
           witness_generator_queue_reporter_run_interval_ms: 10000
           witness_job_queuer_run_interval_ms: 10000
+          job_priority_ager_run_interval_ms: 300000
+          job_priority_ager_max_priority: 1
           http_port: 3074
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
@@ -155,6 +169,8 @@ mod tests {
           prover_queue_reporter_run_interval: '10 sec'
           witness_generator_queue_reporter_run_interval: '10s'
           witness_job_queuer_run_interval: '10s'
+          job_priority_ager_run_interval: '5 min'
+          job_priority_ager_max_priority: 1
           http_port: 3074
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
@@ -2,6 +2,8 @@ use std::{num::NonZeroU32, time::Duration};
 
 use smart_config::{metadata::TimeUnit, DescribeConfig, DeserializeConfig};
 
+use crate::ObjectStoreConfig;
+
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
 #[config(derive(Default))]
 pub struct PruningConfig {
@@ -23,6 +25,11 @@ pub struct PruningConfig {
     /// If set to 0, L1 batches will not be retained based on their timestamp.
     #[config(default_t = 1 * TimeUnit::Hours)]
     pub data_retention: Duration,
+    /// Object store to archive pruned L2 blocks, transactions, events and storage logs to. If set, the pruner exports
+    /// data to the store before pruning it, and the web3 API server falls back to archives for requests
+    /// to pruned L2 blocks. If not set, pruned data is discarded.
+    #[config(nest)]
+    pub archive: Option<ObjectStoreConfig>,
 }
 
 #[cfg(test)]
@@ -30,6 +37,7 @@ mod tests {
     use smart_config::{testing::test_complete, Environment, Yaml};
 
     use super::*;
+    use crate::configs::object_store::ObjectStoreMode;
 
     fn expected_config() -> PruningConfig {
         PruningConfig {
@@ -37,6 +45,13 @@ mod tests {
             chunk_size: NonZeroU32::new(10).unwrap(),
             removal_delay: Duration::from_secs(60),
             data_retention: Duration::from_secs(3600),
+            archive: Some(ObjectStoreConfig {
+                mode: ObjectStoreMode::FileBacked {
+                    file_backed_base_path: "./artifacts/".into(),
+                },
+                max_retries: 10,
+                local_mirror_path: Some("/var/cache".into()),
+            }),
         }
     }
 
@@ -47,6 +62,10 @@ mod tests {
             EN_PRUNING_DATA_RETENTION_SEC=3600
             EN_PRUNING_CHUNK_SIZE=10
             EN_PRUNING_REMOVAL_DELAY_SEC=60
+            EN_PRUNING_ARCHIVE_MODE=FileBacked
+            EN_PRUNING_ARCHIVE_FILE_BACKED_BASE_PATH=./artifacts/
+            EN_PRUNING_ARCHIVE_MAX_RETRIES=10
+            EN_PRUNING_ARCHIVE_LOCAL_MIRROR_PATH=/var/cache
         "#;
         let env = Environment::from_dotenv("test.env", env)
             .unwrap()
@@ -63,6 +82,11 @@ mod tests {
             data_retention_sec: 3600
             enabled: true
             removal_delay_sec: 60
+            archive:
+              mode: FileBacked
+              file_backed_base_path: ./artifacts/
+              max_retries: 10
+              local_mirror_path: /var/cache
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
         let config: PruningConfig = test_complete(yaml).unwrap();
@@ -3,7 +3,9 @@
 use anyhow::Context;
 use smart_config::{
     de::{FromSecretString, Optional, Serde},
-    fallback, DescribeConfig, DeserializeConfig,
+    fallback,
+    value::SecretString,
+    DescribeConfig, DeserializeConfig,
 };
 use zksync_basic_types::{secrets::APIKey, url::SensitiveUrl};
 
@@ -69,6 +71,28 @@ pub struct ContractVerifierSecrets {
     pub etherscan_api_key: Option<APIKey>,
 }
 
+/// Webhooks used by the notifier. Each configured webhook receives all notifications.
+#[derive(Debug, Clone, DescribeConfig, DeserializeConfig)]
+#[config(derive(Default))]
+pub struct NotifierSecrets {
+    /// URL receiving notifications as generic JSON objects.
+    #[config(secret, with = Optional(Serde![str]))]
+    pub webhook_url: Option<SensitiveUrl>,
+    /// Slack incoming webhook URL.
+    #[config(secret, with = Optional(Serde![str]))]
+    pub slack_webhook_url: Option<SensitiveUrl>,
+    /// Routing key of a PagerDuty service integration using Events API v2.
+    pub pagerduty_routing_key: Option<SecretString>,
+}
+
+/// Secrets used by the admin API.
+#[derive(Debug, Clone, DescribeConfig, DeserializeConfig)]
+#[config(derive(Default))]
+pub struct AdminApiSecrets {
+    /// Bearer token required by all admin API requests. The admin API cannot be started without it.
+    pub auth_token: Option<SecretString>,
+}
+
 #[derive(Debug, Clone, DescribeConfig, DeserializeConfig)]
 pub struct Secrets {
     #[config(nest)]
@@ -81,6 +105,10 @@ pub struct Secrets {
     pub data_availability: Option<DataAvailabilitySecrets>,
     #[config(nest)]
     pub contract_verifier: ContractVerifierSecrets,
+    #[config(nest)]
+    pub notifier: NotifierSecrets,
+    #[config(nest)]
+    pub admin_api: AdminApiSecrets,
 }
 
 impl PostgresSecrets {
@@ -152,6 +180,26 @@ mod tests {
             avail.gas_relay_api_key.unwrap().0.expose_secret(),
             "SUPER_SECRET"
         );
+        assert_eq!(
+            secrets.notifier.slack_webhook_url.unwrap().expose_str(),
+            "https://hooks.slack.com/services/T000/B000/XXXX"
+        );
+        assert_eq!(
+            secrets
+                .notifier
+                .pagerduty_routing_key
+                .unwrap()
+                .expose_secret(),
+            "R0UT1NGK3Y"
+        );
+        assert_eq!(
+            secrets.notifier.webhook_url.unwrap().expose_str(),
+            "https://alerts.example.com/zksync"
+        );
+        assert_eq!(
+            secrets.admin_api.auth_token.unwrap().expose_secret(),
+            "4DM1N_T0K3N"
+        );
     }
 
     // Migration path: change `DA_SECRETS_*` -> `DA_*`
@@ -173,6 +221,12 @@ mod tests {
 
             CONTRACT_VERIFIER_ETHERSCAN_API_KEY=correct horse battery staple
 
+            NOTIFIER_WEBHOOK_URL=https://alerts.example.com/zksync
+            NOTIFIER_SLACK_WEBHOOK_URL=https://hooks.slack.com/services/T000/B000/XXXX
+            NOTIFIER_PAGERDUTY_ROUTING_KEY=R0UT1NGK3Y
+
+            ADMIN_API_AUTH_TOKEN=4DM1N_T0K3N
+
             CONSENSUS_VALIDATOR_KEY="validator:secret:bls12_381:2e78025015c2b4ba44b081d404c5446442dac74d5a20334c90af90a0b9987866"
             CONSENSUS_NODE_KEY="node:secret:ed25519:d1aaab7e5bc33cce10418d832a43b6aa00f67f2499d48a62fe79a190f1d6b0a3"
         "#;
@@ -196,6 +250,12 @@ mod tests {
               node_key: node:secret:ed25519:d1aaab7e5bc33cce10418d832a43b6aa00f67f2499d48a62fe79a190f1d6b0a3
             contract_verifier:
               etherscan_api_key: null
+            notifier:
+              webhook_url: https://alerts.example.com/zksync
+              slack_webhook_url: https://hooks.slack.com/services/T000/B000/XXXX
+              pagerduty_routing_key: R0UT1NGK3Y
+            admin_api:
+              auth_token: 4DM1N_T0K3N
             da:
               client: Avail
               seed_phrase: 'correct horse battery staple'
@@ -23,6 +23,11 @@ pub struct TreeRecoveryConfig {
     ///
     /// If not set, parallel persistence will be disabled.
     pub parallel_persistence_buffer: Option<NonZeroUsize>,
+    /// If set, the Merkle tree will be imported from a checkpoint for this L1 batch stored in the snapshot object store
+    /// instead of being recovered from storage logs in Postgres. The root hash of the checkpoint is verified
+    /// against Postgres, so the L1 batch must be either the snapshot L1 batch, or a batch with a known root hash.
+    #[config(with = Optional(Serde![int]))]
+    pub checkpoint_l1_batch: Option<L1BatchNumber>,
 }
 
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
@@ -74,6 +79,7 @@ mod tests {
             tree: TreeRecoveryConfig {
                 chunk_size: 250000,
                 parallel_persistence_buffer: Some(NonZeroUsize::new(4).unwrap()),
+                checkpoint_l1_batch: Some(L1BatchNumber(1200)),
             },
             postgres: PostgresRecoveryConfig {
                 max_concurrency: NonZeroUsize::new(10).unwrap(),
@@ -114,6 +120,7 @@ mod tests {
             EN_SNAPSHOTS_RECOVERY_DROP_STORAGE_KEY_PREIMAGES=true
             EN_SNAPSHOTS_RECOVERY_TREE_CHUNK_SIZE=250000
             EN_SNAPSHOTS_RECOVERY_TREE_PARALLEL_PERSISTENCE_BUFFER=4
+            EN_SNAPSHOTS_RECOVERY_TREE_CHECKPOINT_L1_BATCH=1200
 
             EN_SNAPSHOTS_OBJECT_STORE_MODE=FileBacked
             EN_SNAPSHOTS_OBJECT_STORE_MAX_RETRIES=100
@@ -141,6 +148,7 @@ mod tests {
           tree:
             chunk_size: 250000
             parallel_persistence_buffer: 4
+            checkpoint_l1_batch: 1200
           object_store:
             # file_backed:
             #   file_backed_base_path: ./chains/era/artifacts/
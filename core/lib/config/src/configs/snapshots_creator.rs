@@ -26,6 +26,12 @@ pub struct SnapshotsCreatorConfig {
     /// Limiter for concurrent Postgres queries. Right now, applied when persisting storage log chunks.
     #[config(default_t = 25)]
     pub concurrent_queries_count: u32,
+    /// Maximum number of differential snapshots in a chain starting from a full snapshot. If positive, the creator
+    /// creates a differential snapshot based on the newest complete snapshot (i.e., containing only storage logs
+    /// and factory deps changed since the base snapshot), unless the chain would become too long; in the latter case,
+    /// a full snapshot is created. If set to 0 (the default), only full snapshots are created.
+    #[config(default)]
+    pub max_differential_chain_length: u32,
     /// Object store to persist the snapshot into.
     #[config(nest)]
     pub object_store: ObjectStoreConfig,
@@ -62,6 +68,7 @@ mod tests {
             l1_batch_number: Some(L1BatchNumber(1234)),
             storage_logs_chunk_size: 200000,
             concurrent_queries_count: 20,
+            max_differential_chain_length: 5,
             object_store: ObjectStoreConfig {
                 mode: ObjectStoreMode::FileBacked {
                     file_backed_base_path: "./chains/era/artifacts/".into(),
@@ -80,6 +87,7 @@ mod tests {
             SNAPSHOTS_CREATOR_CONCURRENT_QUERIES_COUNT=20
             SNAPSHOTS_CREATOR_VERSION=0
             SNAPSHOTS_CREATOR_L1_BATCH_NUMBER=1234
+            SNAPSHOTS_CREATOR_MAX_DIFFERENTIAL_CHAIN_LENGTH=5
 
             SNAPSHOTS_OBJECT_STORE_MODE=FileBacked
             SNAPSHOTS_OBJECT_STORE_MAX_RETRIES=100
@@ -107,6 +115,7 @@ mod tests {
             max_retries: 100
           version: 0
           l1_batch_number: 1234
+          max_differential_chain_length: 5
         "#;
         let yaml = Yaml::new("test.yml", serde_yaml::from_str(yaml).unwrap()).unwrap();
         let schema = create_schema();
@@ -1,8 +1,45 @@
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
-use smart_config::{de::Serde, metadata::TimeUnit, DescribeConfig, DeserializeConfig};
+use smart_config::{
+    de::{Delimited, Serde},
+    metadata::TimeUnit,
+    DescribeConfig, DeserializeConfig,
+};
 use zksync_basic_types::L1BatchNumber;
 
+/// Policy for verifying Intel DCAP attestation quotes submitted by TEE provers.
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+pub struct TeeAttestationPolicyConfig {
+    /// Directory with the local DCAP collateral: `root_ca.pem` with the Intel SGX root CA certificate,
+    /// `tcb_signing_chain.pem` with the TCB signing certificate chain, `tcb_info/*.json` and `qe_identity/*.json`
+    /// with signed documents returned by Intel PCS / PCCS, and `crl/*.pem` with the root CA and PCK CA CRLs.
+    /// The collateral is re-read on each registration, so it can be refreshed without a restart.
+    pub collateral_path: PathBuf,
+    /// Hex-encoded MRENCLAVE values of SGX enclaves allowed to register keys.
+    #[config(default, with = Delimited(","))]
+    pub allowed_sgx_mrenclaves: Vec<String>,
+    /// Hex-encoded MRTD values of TDX trust domains allowed to register keys.
+    #[config(default, with = Delimited(","))]
+    pub allowed_tdx_mrtds: Vec<String>,
+    /// Accepted TCB statuses of the attested platform, e.g. `UpToDate` or `SWHardeningNeeded`.
+    #[config(default_t = vec!["UpToDate".into()], with = Delimited(","))]
+    pub allowed_tcb_statuses: Vec<String>,
+}
+
+/// Quorum policy for aggregating TEE proofs into a single attestation status per batch.
+#[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
+pub struct TeeQuorumConfig {
+    /// Minimum number of distinct TEE keys that must sign the batch root hash.
+    #[config(default_t = 2)]
+    pub min_signers: u32,
+    /// Minimum number of distinct TEE types (e.g., SGX and TDX) among the signers.
+    #[config(default_t = 1)]
+    pub min_tee_types: u32,
+    /// Interval between checks for newly attested batches.
+    #[config(default_t = 5 * TimeUnit::Seconds)]
+    pub poll_interval: Duration,
+}
+
 #[derive(Debug, Clone, PartialEq, DescribeConfig, DeserializeConfig)]
 pub struct TeeProofDataHandlerConfig {
     pub http_port: u16,
@@ -17,6 +54,15 @@ pub struct TeeProofDataHandlerConfig {
     /// Timeout in hours after which a batch will be permanently ignored if repeated retries failed.
     #[config(default_t = 10 * TimeUnit::Days)]
     pub batch_permanently_ignored_timeout: Duration,
+    /// Attestation verification policy. If not specified, attestation quotes are stored without
+    /// verification, and proofs are accepted from any registered key.
+    #[config(nest)]
+    pub attestation_policy: Option<TeeAttestationPolicyConfig>,
+    /// Quorum policy for batch attestation. If specified, batches are marked as TEE-attested once the policy
+    /// is satisfied. If the attestation policy is specified as well, only signatures from keys with valid
+    /// verified attestations are counted.
+    #[config(nest)]
+    pub quorum: Option<TeeQuorumConfig>,
 }
 
 #[cfg(test)]
@@ -31,6 +77,19 @@ mod tests {
             first_processed_batch: L1BatchNumber(123),
             proof_generation_timeout: Duration::from_secs(90),
             batch_permanently_ignored_timeout: 5 * TimeUnit::Days,
+            attestation_policy: Some(TeeAttestationPolicyConfig {
+                collateral_path: "/etc/tee/collateral".into(),
+                allowed_sgx_mrenclaves: vec![
+                    "0101010101010101010101010101010101010101010101010101010101010101".into(),
+                ],
+                allowed_tdx_mrtds: vec![],
+                allowed_tcb_statuses: vec!["UpToDate".into(), "SWHardeningNeeded".into()],
+            }),
+            quorum: Some(TeeQuorumConfig {
+                min_signers: 3,
+                min_tee_types: 2,
+                poll_interval: Duration::from_secs(10),
+            }),
         }
     }
 
@@ -41,6 +100,15 @@ mod tests {
           first_processed_batch: 123
           proof_generation_timeout_in_secs: 90
           batch_permanently_ignored_timeout_in_hours: 120
+          attestation_policy:
+            collateral_path: /etc/tee/collateral
+            allowed_sgx_mrenclaves: 0101010101010101010101010101010101010101010101010101010101010101
+            allowed_tdx_mrtds: []
+            allowed_tcb_statuses: UpToDate,SWHardeningNeeded
+          quorum:
+            min_signers: 3
+            min_tee_types: 2
+            poll_interval_ms: 10000
         "#;
         let yaml = serde_yaml::from_str(yaml).unwrap();
         let yaml = Yaml::new("test.yml", yaml).unwrap();
@@ -56,6 +124,16 @@ mod tests {
           first_processed_batch: 123
           proof_generation_timeout: 90s
           batch_permanently_ignored_timeout: 5 days
+          attestation_policy:
+            collateral_path: /etc/tee/collateral
+            allowed_sgx_mrenclaves:
+              - "0101010101010101010101010101010101010101010101010101010101010101"
+            allowed_tdx_mrtds: []
+            allowed_tcb_statuses: [UpToDate, SWHardeningNeeded]
+          quorum:
+            min_signers: 3
+            min_tee_types: 2
+            poll_interval: 10s
         "#;
         let yaml = serde_yaml::from_str(yaml).unwrap();
         let yaml = Yaml::new("test.yml", yaml).unwrap();
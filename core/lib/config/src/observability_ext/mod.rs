@@ -71,6 +71,7 @@ impl TryFrom<ObservabilityConfig> for Option<zksync_vlog::OpenTelemetry> {
                     Some(config.endpoint),
                     config.logs_endpoint,
                 )
+                .map(|otlp| otlp.with_tx_lifecycle_tracing(config.trace_tx_lifecycle))
             })
             .transpose()?)
     }
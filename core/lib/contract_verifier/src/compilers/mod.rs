@@ -241,11 +241,19 @@ fn parse_standard_json_output(
         return Err(err.into());
     }
 
+    // Metadata is only relevant for EVM contracts; it's a JSON string in `solc` output.
+    let metadata = if get_deployed_bytecode {
+        contract["metadata"].as_str().map(str::to_owned)
+    } else {
+        None
+    };
+
     Ok(CompilationArtifacts {
         bytecode,
         deployed_bytecode,
         abi,
         immutable_refs,
+        metadata,
     })
 }
 
@@ -40,7 +40,7 @@ impl Solc {
         let (file_name, contract_name) = process_contract_name(&req.contract_name, "sol");
         let default_output_selection = serde_json::json!({
             "*": {
-                "*": [ "abi", "evm.bytecode", "evm.deployedBytecode" ],
+                "*": [ "abi", "evm.bytecode", "evm.deployedBytecode", "metadata" ],
                  "": [ "abi", "evm.bytecode", "evm.deployedBytecode" ],
             }
         });
@@ -184,6 +184,7 @@ impl ZkSolc {
             deployed_bytecode: None,
             abi: serde_json::Value::Array(Vec::new()),
             immutable_refs: Default::default(),
+            metadata: None,
         })
     }
 
@@ -476,6 +476,7 @@ mod tests {
                 evm_specific: VerificationEvmSettings {
                     evm_version: Some("evm version".to_string()),
                     optimizer_runs: Some(200),
+                    constructor_arguments_from_deployment: false,
                 },
             },
         }
@@ -278,6 +278,7 @@ mod tests {
             evm_specific: VerificationEvmSettings {
                 evm_version: Some("evm version".to_string()),
                 optimizer_runs: Some(200),
+                constructor_arguments_from_deployment: false,
             },
         }
     }
@@ -382,6 +383,7 @@ mod tests {
             evm_specific: VerificationEvmSettings {
                 evm_version: Some("evm version".to_string()),
                 optimizer_runs: Some(200),
+                constructor_arguments_from_deployment: false,
             },
         };
 
@@ -25,6 +25,7 @@ use zksync_types::{
     Address, CONTRACT_DEPLOYER_ADDRESS,
 };
 
+pub use crate::propagation::VerificationPropagator;
 use crate::{
     compilers::{Solc, VyperInput, ZkSolc},
     error::ContractVerifierError,
@@ -36,7 +37,9 @@ mod compilers;
 pub mod error;
 pub mod etherscan;
 mod metrics;
+mod propagation;
 mod resolver;
+pub mod sourcify;
 #[cfg(test)]
 mod tests;
 
@@ -327,6 +330,14 @@ impl ContractVerifier {
         }
 
         match constructor_args {
+            ConstructorArgs::Check(args)
+                if request
+                    .req
+                    .evm_specific
+                    .constructor_arguments_from_deployment =>
+            {
+                request.req.constructor_arguments = args.into();
+            }
             ConstructorArgs::Check(args) => {
                 let provided_constructor_args = &request.req.constructor_arguments.0;
                 if *provided_constructor_args != args {
@@ -1,6 +1,9 @@
 use std::time::Duration;
 
-use vise::{Buckets, Counter, Gauge, Histogram, LabeledFamily, Metrics};
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, LabeledFamily,
+    Metrics,
+};
 
 // Starting bucket from 5 sec as there is a 5 second pause between
 // the verification request and the time verification status is checked for the first time.
@@ -21,11 +24,23 @@ pub(crate) struct ApiContractVerifierMetrics {
     pub successful_verifications: LabeledFamily<&'static str, Counter, 1>,
 }
 
+/// Kind of bytecode match for propagated verification info.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet, EncodeLabelValue)]
+#[metrics(label = "match", rename_all = "snake_case")]
+pub(crate) enum PropagatedMatch {
+    Full,
+    Partial,
+}
+
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "contract_verifier")]
 pub(crate) struct ContractVerifierMetrics {
     #[metrics(labels = ["service_name"])]
     pub number_of_queued_requests: LabeledFamily<&'static str, Gauge<u64>>,
+    /// Number of contracts that got verification info propagated from a contract with matching bytecode.
+    pub propagated_verifications: Family<PropagatedMatch, Counter>,
+    /// Next L2 block to be scanned for deployments by the verification info propagator.
+    pub propagation_next_l2_block: Gauge<u64>,
 }
 
 #[vise::register]
@@ -0,0 +1,304 @@
+//! Propagation of existing verification info to contracts with matching bytecode.
+
+use std::{collections::HashMap, num::NonZeroU32, time::Duration};
+
+use anyhow::Context as _;
+use chrono::Utc;
+use tokio::sync::watch;
+use zksync_dal::{Connection, Core, CoreDal};
+use zksync_types::{
+    bytecode::{trim_padded_evm_bytecode, BytecodeHash, BytecodeMarker},
+    contract_verification::{
+        api::{VerificationInfo, VerificationProblem},
+        contract_identifier::{ContractIdentifier, Match},
+    },
+    Address, L2BlockNumber, H256,
+};
+
+use crate::{
+    error::ContractVerifierError,
+    metrics::{PropagatedMatch, CONTRACT_VERIFIER_METRICS},
+    ConstructorArgs, ContractVerifier,
+};
+
+/// Verified contract with bytecode matching a deployed bytecode.
+#[derive(Debug)]
+struct BytecodeMatch {
+    info: VerificationInfo,
+    /// Identifier of the deployed bytecode.
+    deployed_identifier: ContractIdentifier,
+    /// Hashes of the compiled bytecode stored together with `info`.
+    bytecode_keccak256: H256,
+    bytecode_without_metadata_keccak256: H256,
+    kind: PropagatedMatch,
+}
+
+/// Propagates verification info of already verified contracts to newly deployed contracts with the matching bytecode,
+/// so that e.g. contracts deployed by factories don't need to be verified one by one.
+///
+/// Deployments are scanned in sealed L2 blocks in order; the next L2 block to scan is persisted in Postgres.
+/// Bytecodes are matched by their keccak256 hash; if the hashes differ only in the metadata hash
+/// (e.g., because of different source paths), the contract is marked as a partial match,
+/// similarly to partial matches during verification.
+///
+/// Contracts with immutables are only matched if their deployed immutable values are identical to the verified contract,
+/// and contracts deployed before the matching contract was verified are only processed during backfill
+/// (see [`Self::with_backfill_from()`]).
+#[derive(Debug)]
+pub struct VerificationPropagator {
+    verifier: ContractVerifier,
+    batch_size: NonZeroU32,
+    poll_interval: Duration,
+    backfill_from: Option<L2BlockNumber>,
+}
+
+impl VerificationPropagator {
+    pub fn new(
+        verifier: ContractVerifier,
+        batch_size: NonZeroU32,
+        poll_interval: Duration,
+    ) -> Self {
+        Self {
+            verifier,
+            batch_size,
+            poll_interval,
+            backfill_from: None,
+        }
+    }
+
+    /// Restarts scanning deployments from the specified L2 block. If not set, the propagator continues from
+    /// the persisted position, or starts from the next L2 block after the latest sealed one on the first run.
+    #[must_use]
+    pub fn with_backfill_from(mut self, l2_block: L2BlockNumber) -> Self {
+        self.backfill_from = Some(l2_block);
+        self
+    }
+
+    async fn connection(&self) -> anyhow::Result<Connection<'static, Core>> {
+        Ok(self
+            .verifier
+            .connection_pool
+            .connection_tagged("contract_verifier")
+            .await?)
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        if let Some(l2_block) = self.backfill_from {
+            tracing::info!("Backfilling verification info propagation from L2 block #{l2_block}");
+            self.connection()
+                .await?
+                .contract_verification_dal()
+                .set_next_propagation_l2_block(l2_block)
+                .await?;
+        }
+
+        while !*stop_receiver.borrow() {
+            let has_more_blocks = self.process_next_batch().await?;
+            if !has_more_blocks {
+                tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                    .await
+                    .ok();
+            }
+        }
+        tracing::info!("Stop request received, verification info propagator is shutting down");
+        Ok(())
+    }
+
+    /// Processes the next batch of L2 blocks. Returns `true` if there may be more sealed L2 blocks to process.
+    pub(crate) async fn process_next_batch(&self) -> anyhow::Result<bool> {
+        let mut storage = self.connection().await?;
+        let Some(sealed_l2_block) = storage.blocks_dal().get_sealed_l2_block_number().await? else {
+            return Ok(false);
+        };
+        let Some(next_l2_block) = storage
+            .contract_verification_dal()
+            .get_next_propagation_l2_block()
+            .await?
+        else {
+            let next_l2_block = sealed_l2_block + 1;
+            tracing::info!("Starting verification info propagation from L2 block #{next_l2_block}");
+            storage
+                .contract_verification_dal()
+                .set_next_propagation_l2_block(next_l2_block)
+                .await?;
+            return Ok(false);
+        };
+        if next_l2_block > sealed_l2_block {
+            return Ok(false);
+        }
+
+        let last_l2_block = sealed_l2_block.min(next_l2_block + (self.batch_size.get() - 1));
+        let deployments = storage
+            .contract_verification_dal()
+            .get_unverified_contract_deployments(next_l2_block..=last_l2_block)
+            .await?;
+        tracing::debug!(
+            "Processing {} unverified deployments in L2 blocks #{next_l2_block}..=#{last_l2_block}",
+            deployments.len()
+        );
+
+        // Deployments often share bytecodes, so we cache matches within the batch.
+        let mut matches = HashMap::new();
+        for (address, bytecode_hash) in deployments {
+            if !matches.contains_key(&bytecode_hash) {
+                let bytecode_match = Self::find_match(&mut storage, bytecode_hash).await?;
+                matches.insert(bytecode_hash, bytecode_match);
+            }
+            if let Some(bytecode_match) = &matches[&bytecode_hash] {
+                self.propagate(&mut storage, address, bytecode_hash, bytecode_match)
+                    .await?;
+            }
+        }
+
+        storage
+            .contract_verification_dal()
+            .set_next_propagation_l2_block(last_l2_block + 1)
+            .await?;
+        CONTRACT_VERIFIER_METRICS
+            .propagation_next_l2_block
+            .set((last_l2_block.0 + 1).into());
+        Ok(last_l2_block < sealed_l2_block)
+    }
+
+    async fn find_match(
+        storage: &mut Connection<'_, Core>,
+        bytecode_hash: H256,
+    ) -> anyhow::Result<Option<BytecodeMatch>> {
+        let Ok(parsed_hash) = BytecodeHash::try_from(bytecode_hash) else {
+            tracing::warn!("Deployed bytecode hash {bytecode_hash:?} has unknown format");
+            return Ok(None);
+        };
+        let Some(bytecode) = storage
+            .factory_deps_dal()
+            .get_sealed_factory_dep(bytecode_hash)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let bytecode_marker = parsed_hash.marker();
+        let deployed_bytecode = match bytecode_marker {
+            BytecodeMarker::EraVm => bytecode.as_slice(),
+            BytecodeMarker::Evm => trim_padded_evm_bytecode(parsed_hash, &bytecode)
+                .context("invalid stored EVM bytecode")?,
+        };
+        let deployed_identifier =
+            ContractIdentifier::from_bytecode(bytecode_marker, deployed_bytecode);
+        let Some((info, bytecode_keccak256, bytecode_without_metadata_keccak256)) = storage
+            .contract_verification_dal()
+            .get_partial_match_verification_info(
+                deployed_identifier.bytecode_keccak256,
+                deployed_identifier.bytecode_without_metadata_keccak256,
+            )
+            .await?
+        else {
+            return Ok(None);
+        };
+        if info.bytecode_marker() != bytecode_marker {
+            return Ok(None);
+        }
+
+        let stored_identifier = ContractIdentifier {
+            bytecode_marker,
+            bytecode_keccak256,
+            bytecode_without_metadata_keccak256,
+            detected_metadata: None,
+        };
+        let kind = match deployed_identifier.matches(&stored_identifier) {
+            Match::Full => PropagatedMatch::Full,
+            // Without detected metadata, the hash without metadata is the hash of the entire bytecode.
+            Match::Partial if deployed_identifier.detected_metadata.is_some() => {
+                PropagatedMatch::Partial
+            }
+            Match::Partial | Match::None => return Ok(None),
+        };
+        Ok(Some(BytecodeMatch {
+            info,
+            deployed_identifier,
+            bytecode_keccak256,
+            bytecode_without_metadata_keccak256,
+            kind,
+        }))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(address = ?address))]
+    async fn propagate(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        address: Address,
+        bytecode_hash: H256,
+        bytecode_match: &BytecodeMatch,
+    ) -> anyhow::Result<()> {
+        let Some(deployed_contract) = storage
+            .contract_verification_dal()
+            .get_contract_info_for_verification(address)
+            .await?
+        else {
+            return Ok(());
+        };
+        if deployed_contract.bytecode_hash != bytecode_hash {
+            // The contract was redeployed with another bytecode; it will be processed together with the redeployment.
+            return Ok(());
+        }
+
+        let source = &bytecode_match.info;
+        let constructor_args = match bytecode_match.deployed_identifier.bytecode_marker {
+            BytecodeMarker::EraVm => self
+                .verifier
+                .decode_era_vm_constructor_args(&deployed_contract, address)
+                .map_err(ContractVerifierError::Internal),
+            BytecodeMarker::Evm => {
+                let compiled_identifier = ContractIdentifier::from_bytecode(
+                    BytecodeMarker::Evm,
+                    source.artifacts.deployed_bytecode(),
+                );
+                ContractVerifier::decode_evm_constructor_args(
+                    source.request.id,
+                    &deployed_contract,
+                    &source.artifacts.bytecode,
+                    &compiled_identifier,
+                    &bytecode_match.deployed_identifier,
+                )
+            }
+        };
+        let constructor_args = match constructor_args {
+            Ok(ConstructorArgs::Check(args)) => args,
+            Ok(ConstructorArgs::Ignore) => vec![],
+            Err(err) => {
+                tracing::info!(
+                    "Not propagating verification info from {:?}: {err:?}",
+                    source.request.req.contract_address
+                );
+                return Ok(());
+            }
+        };
+
+        let source_address = source.request.req.contract_address;
+        let mut info = source.clone();
+        info.request.req.contract_address = address;
+        info.request.req.constructor_arguments = constructor_args.into();
+        info.verified_at = Utc::now();
+        info.verification_problems = match bytecode_match.kind {
+            PropagatedMatch::Full => vec![],
+            PropagatedMatch::Partial => vec![VerificationProblem::IncorrectMetadata],
+        };
+
+        let inserted = storage
+            .contract_verification_dal()
+            .save_propagated_verification_info(
+                info,
+                bytecode_match.bytecode_keccak256,
+                bytecode_match.bytecode_without_metadata_keccak256,
+                source_address,
+            )
+            .await?;
+        if inserted {
+            tracing::info!(
+                "Propagated verification info from {source_address:?} ({:?} match)",
+                bytecode_match.kind
+            );
+            CONTRACT_VERIFIER_METRICS.propagated_verifications[&bytecode_match.kind].inc();
+        }
+        Ok(())
+    }
+}
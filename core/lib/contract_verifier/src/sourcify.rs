@@ -0,0 +1,300 @@
+//! Export and import of verified contracts in the [Sourcify repository layout].
+//!
+//! [Sourcify repository layout]: https://docs.sourcify.dev/docs/repository/
+
+use std::{
+    collections::HashMap,
+    io,
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::Context as _;
+use tokio::fs;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_types::{
+    bytecode::BytecodeMarker,
+    contract_verification::sourcify::{
+        checksum_address, SolcMetadata, SourcifyContractFiles, SourcifyMatch, METADATA_FILE_NAME,
+        SOURCES_DIR_NAME,
+    },
+    Address, L2ChainId,
+};
+
+/// Number of verified contracts loaded from Postgres at once during export.
+const EXPORT_PAGE_SIZE: usize = 100;
+
+/// Statistics of exporting verified contracts.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ExportStats {
+    pub exported: usize,
+    /// Contracts that cannot be represented in the Sourcify format (e.g., EraVM contracts or Vyper contracts).
+    pub skipped: usize,
+}
+
+/// Outcome of importing a single contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ImportOutcome {
+    Enqueued,
+    AlreadyVerified,
+    NotDeployed,
+    /// The contract is not an EVM contract deployed by a deployment transaction, so its creation bytecode
+    /// cannot be checked.
+    NoCreationBytecode,
+    Invalid,
+}
+
+/// Statistics of importing Sourcify verifications.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ImportStats {
+    /// Contracts for which a verification request was enqueued.
+    pub enqueued: usize,
+    /// Contracts already verified or having an active verification request.
+    pub already_verified: usize,
+    /// Contracts not deployed on this chain, or deployed without a deployment transaction.
+    pub not_deployed: usize,
+    /// Contracts with invalid metadata or sources.
+    pub invalid: usize,
+}
+
+impl ImportStats {
+    fn record(&mut self, outcome: ImportOutcome) {
+        match outcome {
+            ImportOutcome::Enqueued => self.enqueued += 1,
+            ImportOutcome::AlreadyVerified => self.already_verified += 1,
+            ImportOutcome::NotDeployed | ImportOutcome::NoCreationBytecode => {
+                self.not_deployed += 1
+            }
+            ImportOutcome::Invalid => self.invalid += 1,
+        }
+    }
+}
+
+/// Local copy of the Sourcify repository, i.e. the `contracts/{full_match|partial_match}/{chainId}/{address}/`
+/// directories with `metadata.json` and contract sources.
+#[derive(Debug)]
+pub struct SourcifyRepository {
+    root: PathBuf,
+    chain_id: L2ChainId,
+}
+
+impl SourcifyRepository {
+    pub fn new(root: PathBuf, chain_id: L2ChainId) -> Self {
+        Self { root, chain_id }
+    }
+
+    fn chain_dir(&self, match_kind: SourcifyMatch) -> PathBuf {
+        self.root
+            .join("contracts")
+            .join(match_kind.repository_dir())
+            .join(self.chain_id.as_u64().to_string())
+    }
+
+    fn contract_dir(&self, match_kind: SourcifyMatch, address: &Address) -> PathBuf {
+        self.chain_dir(match_kind).join(checksum_address(address))
+    }
+
+    /// Exports all verified contracts that can be represented in the Sourcify format.
+    pub async fn export(&self, pool: &ConnectionPool<Core>) -> anyhow::Result<ExportStats> {
+        let mut storage = pool.connection_tagged("contract_verifier").await?;
+        let mut stats = ExportStats::default();
+        let mut last_address = None;
+        loop {
+            let page = storage
+                .contract_verification_dal()
+                .get_verification_info_page(last_address, EXPORT_PAGE_SIZE)
+                .await?;
+            let Some(last_info) = page.last() else {
+                break;
+            };
+            last_address = Some(last_info.request.req.contract_address);
+
+            for info in page {
+                let address = info.request.req.contract_address;
+                match SourcifyContractFiles::from_verification_info(&info) {
+                    Some(files) => {
+                        self.export_contract(&address, &files)
+                            .await
+                            .with_context(|| format!("failed exporting contract {address:?}"))?;
+                        stats.exported += 1;
+                    }
+                    None => {
+                        tracing::debug!(
+                            "Contract {address:?} cannot be exported in Sourcify format"
+                        );
+                        stats.skipped += 1;
+                    }
+                }
+            }
+        }
+        tracing::info!("Exported verified contracts to {:?}: {stats:?}", self.root);
+        Ok(stats)
+    }
+
+    async fn export_contract(
+        &self,
+        address: &Address,
+        files: &SourcifyContractFiles,
+    ) -> anyhow::Result<()> {
+        // Remove contract files possibly left from the previous export, e.g. with another match kind.
+        for match_kind in SourcifyMatch::ALL {
+            let dir = self.contract_dir(match_kind, address);
+            match fs::remove_dir_all(&dir).await {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => {
+                    return Err(anyhow::Error::new(err).context(format!("failed removing {dir:?}")));
+                }
+                _ => {}
+            }
+        }
+
+        let contract_dir = self.contract_dir(files.match_kind, address);
+        let sources_dir = contract_dir.join(SOURCES_DIR_NAME);
+        fs::create_dir_all(&sources_dir).await?;
+        fs::write(contract_dir.join(METADATA_FILE_NAME), &files.metadata).await?;
+        for (path, content) in &files.sources {
+            let relative_path = sanitize_source_path(path)
+                .with_context(|| format!("source path {path:?} is not relative"))?;
+            let path = sources_dir.join(relative_path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).await?;
+            }
+            fs::write(&path, content).await?;
+        }
+        Ok(())
+    }
+
+    /// Enqueues verification requests for contracts in the repository that are deployed on this chain
+    /// and not verified yet. Requests take constructor arguments from the deployment, so that they succeed
+    /// iff the creation bytecode compiled from the imported sources matches the deployment.
+    pub async fn import(&self, pool: &ConnectionPool<Core>) -> anyhow::Result<ImportStats> {
+        let mut stats = ImportStats::default();
+        for match_kind in SourcifyMatch::ALL {
+            let chain_dir = self.chain_dir(match_kind);
+            let mut entries = match fs::read_dir(&chain_dir).await {
+                Ok(entries) => entries,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => {
+                    return Err(
+                        anyhow::Error::new(err).context(format!("failed reading {chain_dir:?}"))
+                    );
+                }
+            };
+
+            while let Some(entry) = entries.next_entry().await? {
+                let file_name = entry.file_name();
+                let Some(address) = file_name.to_str().and_then(|name| name.parse().ok()) else {
+                    tracing::warn!("Skipping {:?}: not an address", entry.path());
+                    continue;
+                };
+                let outcome = self
+                    .import_contract(pool, address, &entry.path())
+                    .await
+                    .with_context(|| format!("failed importing contract {address:?}"))?;
+                tracing::debug!("Imported contract {address:?}: {outcome:?}");
+                stats.record(outcome);
+            }
+        }
+        tracing::info!(
+            "Imported Sourcify verifications from {:?}: {stats:?}",
+            self.root
+        );
+        Ok(stats)
+    }
+
+    async fn import_contract(
+        &self,
+        pool: &ConnectionPool<Core>,
+        address: Address,
+        contract_dir: &Path,
+    ) -> anyhow::Result<ImportOutcome> {
+        let metadata_path = contract_dir.join(METADATA_FILE_NAME);
+        let metadata = fs::read(&metadata_path)
+            .await
+            .with_context(|| format!("failed reading {metadata_path:?}"))?;
+        let metadata: SolcMetadata = match serde_json::from_slice(&metadata) {
+            Ok(metadata) => metadata,
+            Err(err) => {
+                tracing::warn!("Invalid metadata for contract {address:?}: {err}");
+                return Ok(ImportOutcome::Invalid);
+            }
+        };
+        let sources = read_sources(&contract_dir.join(SOURCES_DIR_NAME)).await?;
+        let request = match metadata.to_verification_request(address, &sources) {
+            Ok(request) => request,
+            Err(err) => {
+                tracing::warn!("Cannot import contract {address:?}: {err}");
+                return Ok(ImportOutcome::Invalid);
+            }
+        };
+
+        let mut storage = pool.connection_tagged("contract_verifier").await?;
+        let mut dal = storage.contract_verification_dal();
+        let verification_info = dal.get_contract_verification_info(address).await?;
+        if verification_info.is_some_and(|info| info.is_perfect_match()) {
+            return Ok(ImportOutcome::AlreadyVerified);
+        }
+        if dal
+            .get_active_verification_request(address)
+            .await?
+            .is_some()
+        {
+            return Ok(ImportOutcome::AlreadyVerified);
+        }
+
+        let Some(deployed_contract) = dal.get_contract_info_for_verification(address).await? else {
+            return Ok(ImportOutcome::NotDeployed);
+        };
+        let is_evm =
+            BytecodeMarker::new(deployed_contract.bytecode_hash) == Some(BytecodeMarker::Evm);
+        // Only deployment transactions (as opposed to deployments by other contracts) allow checking the creation bytecode.
+        let is_deployment_tx =
+            deployed_contract.calldata.is_some() && deployed_contract.contract_address.is_none();
+        if !is_evm || !is_deployment_tx {
+            return Ok(ImportOutcome::NoCreationBytecode);
+        }
+
+        let request_id = dal.add_contract_verification_request(&request).await?;
+        tracing::info!("Enqueued verification request #{request_id} for contract {address:?} imported from Sourcify");
+        Ok(ImportOutcome::Enqueued)
+    }
+}
+
+/// Converts a source path to a relative filesystem path, or returns `None` if it can escape the sources directory.
+fn sanitize_source_path(path: &str) -> Option<PathBuf> {
+    let path = Path::new(path);
+    let is_safe = path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)));
+    is_safe.then(|| path.to_owned())
+}
+
+/// Recursively reads all sources in `root`, keyed by their paths relative to `root` (using `/` as a separator).
+async fn read_sources(root: &Path) -> anyhow::Result<HashMap<String, String>> {
+    let mut sources = HashMap::new();
+    let mut pending_dirs = vec![root.to_owned()];
+    while let Some(dir) = pending_dirs.pop() {
+        let mut entries = match fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => {
+                return Err(anyhow::Error::new(err).context(format!("failed reading {dir:?}")));
+            }
+        };
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if entry.file_type().await?.is_dir() {
+                pending_dirs.push(path);
+                continue;
+            }
+            let relative_path = path.strip_prefix(root)?;
+            let relative_path: Vec<_> = relative_path
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect();
+            let content = fs::read_to_string(&path)
+                .await
+                .with_context(|| format!("failed reading {path:?}"))?;
+            sources.insert(relative_path.join("/"), content);
+        }
+    }
+    Ok(sources)
+}
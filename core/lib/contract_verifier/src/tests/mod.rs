@@ -26,7 +26,9 @@ use crate::{
     resolver::{Compiler, SupportedCompilerVersions},
 };
 
+mod propagation;
 mod real;
+mod sourcify;
 
 const SOLC_VERSION: &str = "0.8.27";
 const ZKSOLC_VERSION: &str = "1.5.4";
@@ -133,10 +135,35 @@ async fn mock_deployment(
     address: Address,
     bytecode: Vec<u8>,
     constructor_args: &[Token],
+) {
+    mock_deployment_in_block(
+        storage,
+        L2BlockNumber(0),
+        address,
+        bytecode,
+        constructor_args,
+    )
+    .await;
+}
+
+async fn mock_deployment_in_block(
+    storage: &mut Connection<'_, Core>,
+    l2_block: L2BlockNumber,
+    address: Address,
+    bytecode: Vec<u8>,
+    constructor_args: &[Token],
 ) {
     let bytecode_hash = BytecodeHash::for_bytecode(&bytecode).value();
     let deployment = Execute::for_deploy(H256::zero(), bytecode.clone(), constructor_args);
-    mock_deployment_inner(storage, address, bytecode_hash, bytecode, deployment).await;
+    mock_deployment_inner(
+        storage,
+        l2_block,
+        address,
+        bytecode_hash,
+        bytecode,
+        deployment,
+    )
+    .await;
 }
 
 async fn mock_evm_deployment(
@@ -145,6 +172,25 @@ async fn mock_evm_deployment(
     creation_bytecode: Vec<u8>,
     deployed_bytecode: &[u8],
     constructor_args: &[Token],
+) {
+    mock_evm_deployment_in_block(
+        storage,
+        L2BlockNumber(0),
+        address,
+        creation_bytecode,
+        deployed_bytecode,
+        constructor_args,
+    )
+    .await;
+}
+
+async fn mock_evm_deployment_in_block(
+    storage: &mut Connection<'_, Core>,
+    l2_block: L2BlockNumber,
+    address: Address,
+    creation_bytecode: Vec<u8>,
+    deployed_bytecode: &[u8],
+    constructor_args: &[Token],
 ) {
     let mut calldata = creation_bytecode;
     calldata.extend_from_slice(&ethabi::encode(constructor_args));
@@ -156,11 +202,20 @@ async fn mock_evm_deployment(
     };
     let bytecode = pad_evm_bytecode(deployed_bytecode);
     let bytecode_hash = BytecodeHash::for_evm_bytecode(deployed_bytecode.len(), &bytecode).value();
-    mock_deployment_inner(storage, address, bytecode_hash, bytecode, deployment).await;
+    mock_deployment_inner(
+        storage,
+        l2_block,
+        address,
+        bytecode_hash,
+        bytecode,
+        deployment,
+    )
+    .await;
 }
 
 async fn mock_deployment_inner(
     storage: &mut Connection<'_, Core>,
+    l2_block: L2BlockNumber,
     address: Address,
     bytecode_hash: H256,
     bytecode: Vec<u8>,
@@ -172,13 +227,13 @@ async fn mock_deployment_inner(
     ];
     storage
         .storage_logs_dal()
-        .append_storage_logs(L2BlockNumber(0), &logs)
+        .append_storage_logs(l2_block, &logs)
         .await
         .unwrap();
     storage
         .factory_deps_dal()
         .insert_factory_deps(
-            L2BlockNumber(0),
+            l2_block,
             &HashMap::from([(bytecode_hash, bytecode.clone())]),
         )
         .await
@@ -190,7 +245,8 @@ async fn mock_deployment_inner(
         received_timestamp_ms: 0,
         raw_bytes: Some(vec![0; 128].into()),
     };
-    deploy_tx.set_input(vec![0; 128], H256::repeat_byte(0x23));
+    // Make transaction hashes unique for each deployed contract.
+    deploy_tx.set_input(vec![0; 128], address_to_h256(&address));
     storage
         .transactions_dal()
         .insert_transaction_l2(
@@ -219,7 +275,7 @@ async fn mock_deployment_inner(
     };
     storage
         .events_dal()
-        .save_events(L2BlockNumber(0), &[(location, vec![&deploy_event])])
+        .save_events(l2_block, &[(location, vec![&deploy_event])])
         .await
         .unwrap();
 }
@@ -426,6 +482,7 @@ async fn contract_verifier_basics(contract: TestContract) {
             deployed_bytecode: None,
             abi: counter_contract_abi(),
             immutable_refs: Default::default(),
+            metadata: None,
         }
     });
     let verifier = ContractVerifier::with_resolver(
@@ -547,6 +604,7 @@ async fn verifying_evm_bytecode(contract: TestContract) {
         deployed_bytecode: Some(deployed_bytecode),
         abi: counter_contract_abi(),
         immutable_refs: Default::default(),
+        metadata: None,
     };
     let mock_resolver = MockCompilerResolver::solc(move |input| {
         assert_eq!(input.standard_json.language, "Solidity");
@@ -591,6 +649,7 @@ async fn bytecode_mismatch_error() {
         deployed_bytecode: None,
         abi: counter_contract_abi(),
         immutable_refs: Default::default(),
+        metadata: None,
     });
     let verifier = ContractVerifier::with_resolver(
         Duration::from_secs(60),
@@ -673,12 +732,14 @@ async fn args_mismatch_error(contract: TestContract, bytecode_kind: BytecodeMark
             deployed_bytecode: None,
             abi: counter_contract_abi(),
             immutable_refs: Default::default(),
+            metadata: None,
         }),
         BytecodeMarker::Evm => MockCompilerResolver::solc(move |_| CompilationArtifacts {
             bytecode: vec![3_u8; 48],
             deployed_bytecode: Some(bytecode.clone()),
             abi: counter_contract_abi(),
             immutable_refs: Default::default(),
+            metadata: None,
         }),
     };
     let verifier = ContractVerifier::with_resolver(
@@ -746,6 +807,7 @@ async fn creation_bytecode_mismatch() {
             deployed_bytecode: Some(deployed_bytecode.clone()),
             abi: counter_contract_abi(),
             immutable_refs: Default::default(),
+            metadata: None,
         }
     });
     let verifier = ContractVerifier::with_resolver(
@@ -871,6 +933,7 @@ async fn verifying_evm_with_immutables() {
         deployed_bytecode: Some(deployed_bytecode_compiled),
         abi: counter_contract_abi(),
         immutable_refs: imm_map,
+        metadata: None,
     };
 
     let mock_resolver = MockCompilerResolver::solc(move |_| artifacts.clone());
@@ -0,0 +1,318 @@
+//! Tests for propagating verification info to contracts with matching bytecode.
+
+use std::num::NonZeroU32;
+
+use super::*;
+
+const SOURCE_ADDRESS: Address = Address::repeat_byte(1);
+
+/// Returns a mock EVM bytecode with the CBOR-encoded IPFS metadata hash filled with `metadata_byte`.
+fn evm_bytecode_with_metadata(code: &[u8], metadata_byte: u8) -> Vec<u8> {
+    let mut bytecode = code.to_vec();
+    bytecode.extend_from_slice(&hex::decode("a1646970667358221220").unwrap());
+    bytecode.extend_from_slice(&[metadata_byte; 32]);
+    bytecode.extend_from_slice(&[0, 0x2a]);
+    bytecode
+}
+
+async fn save_verification_info(
+    storage: &mut Connection<'_, Core>,
+    address: Address,
+    artifacts: CompilationArtifacts,
+) {
+    let mut req = test_request(address, COUNTER_CONTRACT);
+    let bytecode_marker = if artifacts.deployed_bytecode.is_some() {
+        req.compiler_versions = CompilerVersions::Solc {
+            compiler_solc_version: SOLC_VERSION.to_owned(),
+            compiler_zksolc_version: None,
+        };
+        BytecodeMarker::Evm
+    } else {
+        BytecodeMarker::EraVm
+    };
+    let id = storage
+        .contract_verification_dal()
+        .add_contract_verification_request(&req)
+        .await
+        .unwrap();
+
+    let identifier =
+        ContractIdentifier::from_bytecode(bytecode_marker, artifacts.deployed_bytecode());
+    let info = VerificationInfo {
+        request: VerificationRequest { id, req },
+        artifacts,
+        verified_at: Utc::now(),
+        verification_problems: vec![],
+    };
+    storage
+        .contract_verification_dal()
+        .save_verification_info(
+            info,
+            identifier.bytecode_keccak256,
+            identifier.bytecode_without_metadata_keccak256,
+        )
+        .await
+        .unwrap();
+}
+
+async fn create_propagator(pool: &ConnectionPool<Core>) -> VerificationPropagator {
+    let mock_resolver =
+        MockCompilerResolver::zksolc(|input| panic!("unexpected compilation: {input:?}"));
+    let verifier = ContractVerifier::with_resolver(
+        Duration::from_secs(60),
+        pool.clone(),
+        Arc::new(mock_resolver),
+        false,
+    )
+    .await
+    .unwrap();
+    VerificationPropagator::new(
+        verifier,
+        NonZeroU32::new(10).unwrap(),
+        Duration::from_millis(10),
+    )
+}
+
+async fn assert_propagated_info(
+    storage: &mut Connection<'_, Core>,
+    address: Address,
+    constructor_args: &[Token],
+    verification_problems: &[VerificationProblem],
+) {
+    let info = storage
+        .contract_verification_dal()
+        .get_contract_verification_info(address)
+        .await
+        .unwrap()
+        .expect("no propagated verification info");
+    assert_eq!(info.request.req.contract_address, address);
+    assert_eq!(
+        info.request.req.constructor_arguments.0,
+        ethabi::encode(constructor_args)
+    );
+    assert_eq!(info.verification_problems, verification_problems);
+    assert_eq!(
+        without_internal_types(info.artifacts.abi),
+        without_internal_types(counter_contract_abi())
+    );
+
+    let source = storage
+        .contract_verification_dal()
+        .get_propagation_source(address)
+        .await
+        .unwrap();
+    assert_eq!(source, Some(SOURCE_ADDRESS));
+}
+
+async fn is_verified(storage: &mut Connection<'_, Core>, address: Address) -> bool {
+    storage
+        .contract_verification_dal()
+        .get_contract_verification_info(address)
+        .await
+        .unwrap()
+        .is_some()
+}
+
+#[test_casing(2, TestContract::ALL)]
+#[tokio::test]
+async fn propagating_verification_info_for_era_vm_contract(contract: TestContract) {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    let bytecode = vec![0_u8; 32];
+
+    prepare_storage(&mut storage).await;
+    mock_deployment(&mut storage, SOURCE_ADDRESS, bytecode.clone(), &[]).await;
+    let old_address = Address::repeat_byte(2);
+    mock_deployment(&mut storage, old_address, bytecode.clone(), &[]).await;
+    let artifacts = CompilationArtifacts {
+        bytecode: bytecode.clone(),
+        deployed_bytecode: None,
+        abi: counter_contract_abi(),
+        immutable_refs: Default::default(),
+        metadata: None,
+    };
+    save_verification_info(&mut storage, SOURCE_ADDRESS, artifacts).await;
+
+    let propagator = create_propagator(&pool).await;
+    // The first iteration should start propagation from the next L2 block.
+    assert!(!propagator.process_next_batch().await.unwrap());
+    let next_l2_block = storage
+        .contract_verification_dal()
+        .get_next_propagation_l2_block()
+        .await
+        .unwrap();
+    assert_eq!(next_l2_block, Some(L2BlockNumber(1)));
+
+    storage
+        .blocks_dal()
+        .insert_l2_block(&create_l2_block(1))
+        .await
+        .unwrap();
+    let new_address = Address::repeat_byte(3);
+    mock_deployment_in_block(
+        &mut storage,
+        L2BlockNumber(1),
+        new_address,
+        bytecode.clone(),
+        contract.constructor_args(),
+    )
+    .await;
+    let other_address = Address::repeat_byte(4);
+    mock_deployment_in_block(
+        &mut storage,
+        L2BlockNumber(1),
+        other_address,
+        vec![1; 32],
+        &[],
+    )
+    .await;
+
+    assert!(!propagator.process_next_batch().await.unwrap());
+    assert_propagated_info(&mut storage, new_address, contract.constructor_args(), &[]).await;
+    assert!(!is_verified(&mut storage, other_address).await);
+    // Contracts deployed before the processed L2 blocks should not be touched.
+    assert!(!is_verified(&mut storage, old_address).await);
+
+    // Backfill propagation for the contract deployed before the verification.
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let propagator = create_propagator(&pool)
+        .await
+        .with_backfill_from(L2BlockNumber(0));
+    let propagator_task = tokio::spawn(propagator.run(stop_receiver));
+    while !is_verified(&mut storage, old_address).await {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    stop_sender.send_replace(true);
+    propagator_task.await.unwrap().unwrap();
+
+    assert_propagated_info(&mut storage, old_address, &[], &[]).await;
+    let next_l2_block = storage
+        .contract_verification_dal()
+        .get_next_propagation_l2_block()
+        .await
+        .unwrap();
+    assert_eq!(next_l2_block, Some(L2BlockNumber(2)));
+}
+
+#[test_casing(2, TestContract::ALL)]
+#[tokio::test]
+async fn propagating_verification_info_for_evm_contract(contract: TestContract) {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    let code = [5_u8; 10];
+    let creation_code = [3_u8; 20];
+
+    prepare_storage(&mut storage).await;
+    let deployed_bytecode = evm_bytecode_with_metadata(&code, 1);
+    let creation_bytecode = evm_bytecode_with_metadata(&creation_code, 1);
+    mock_evm_deployment(
+        &mut storage,
+        SOURCE_ADDRESS,
+        creation_bytecode.clone(),
+        &deployed_bytecode,
+        &[],
+    )
+    .await;
+    let artifacts = CompilationArtifacts {
+        bytecode: creation_bytecode.clone(),
+        deployed_bytecode: Some(deployed_bytecode.clone()),
+        abi: counter_contract_abi(),
+        immutable_refs: Default::default(),
+        metadata: None,
+    };
+    save_verification_info(&mut storage, SOURCE_ADDRESS, artifacts).await;
+
+    let propagator = create_propagator(&pool).await;
+    assert!(!propagator.process_next_batch().await.unwrap());
+
+    storage
+        .blocks_dal()
+        .insert_l2_block(&create_l2_block(1))
+        .await
+        .unwrap();
+    let full_match_address = Address::repeat_byte(2);
+    mock_evm_deployment_in_block(
+        &mut storage,
+        L2BlockNumber(1),
+        full_match_address,
+        creation_bytecode,
+        &deployed_bytecode,
+        contract.constructor_args(),
+    )
+    .await;
+    // Bytecode differing from the verified one only in the metadata hash.
+    let partial_match_address = Address::repeat_byte(3);
+    mock_evm_deployment_in_block(
+        &mut storage,
+        L2BlockNumber(1),
+        partial_match_address,
+        evm_bytecode_with_metadata(&creation_code, 2),
+        &evm_bytecode_with_metadata(&code, 2),
+        contract.constructor_args(),
+    )
+    .await;
+    let mismatch_address = Address::repeat_byte(4);
+    mock_evm_deployment_in_block(
+        &mut storage,
+        L2BlockNumber(1),
+        mismatch_address,
+        evm_bytecode_with_metadata(&[3; 21], 1),
+        &evm_bytecode_with_metadata(&[5; 11], 1),
+        &[],
+    )
+    .await;
+
+    assert!(!propagator.process_next_batch().await.unwrap());
+    assert_propagated_info(
+        &mut storage,
+        full_match_address,
+        contract.constructor_args(),
+        &[],
+    )
+    .await;
+    assert_propagated_info(
+        &mut storage,
+        partial_match_address,
+        contract.constructor_args(),
+        &[VerificationProblem::IncorrectMetadata],
+    )
+    .await;
+    assert!(!is_verified(&mut storage, mismatch_address).await);
+}
+
+#[tokio::test]
+async fn direct_verification_overrides_propagated_info() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    let bytecode = vec![0_u8; 32];
+
+    prepare_storage(&mut storage).await;
+    mock_deployment(&mut storage, SOURCE_ADDRESS, bytecode.clone(), &[]).await;
+    let address = Address::repeat_byte(2);
+    mock_deployment(&mut storage, address, bytecode.clone(), &[]).await;
+    let artifacts = CompilationArtifacts {
+        bytecode,
+        deployed_bytecode: None,
+        abi: counter_contract_abi(),
+        immutable_refs: Default::default(),
+        metadata: None,
+    };
+    save_verification_info(&mut storage, SOURCE_ADDRESS, artifacts.clone()).await;
+
+    let propagator = create_propagator(&pool).await;
+    storage
+        .contract_verification_dal()
+        .set_next_propagation_l2_block(L2BlockNumber(0))
+        .await
+        .unwrap();
+    assert!(!propagator.process_next_batch().await.unwrap());
+    assert_propagated_info(&mut storage, address, &[], &[]).await;
+
+    save_verification_info(&mut storage, address, artifacts).await;
+    let source = storage
+        .contract_verification_dal()
+        .get_propagation_source(address)
+        .await
+        .unwrap();
+    assert_eq!(source, None);
+}
@@ -0,0 +1,302 @@
+//! Tests for export / import of verified contracts in the Sourcify repository layout.
+
+use std::path::{Path, PathBuf};
+
+use zksync_types::{
+    contract_verification::sourcify::{checksum_address, SolcMetadata},
+    web3::keccak256,
+    L2ChainId,
+};
+
+use super::*;
+use crate::sourcify::{ExportStats, ImportStats, SourcifyRepository};
+
+const SOURCE_PATH: &str = "contracts/Counter.sol";
+
+fn chain_id() -> L2ChainId {
+    L2ChainId::new(270).unwrap()
+}
+
+fn mock_metadata(source: &str) -> serde_json::Value {
+    serde_json::json!({
+        "compiler": { "version": format!("{SOLC_VERSION}+commit.e11b9ed9") },
+        "language": "Solidity",
+        "output": { "abi": counter_contract_abi() },
+        "settings": {
+            "compilationTarget": { SOURCE_PATH: "Counter" },
+            "evmVersion": "cancun",
+            "optimizer": { "enabled": true, "runs": 200 },
+        },
+        "sources": {
+            SOURCE_PATH: { "keccak256": H256(keccak256(source.as_bytes())) },
+        },
+        "version": 1,
+    })
+}
+
+fn contract_dir(root: &Path, match_dir: &str, address: &Address) -> PathBuf {
+    root.join("contracts")
+        .join(match_dir)
+        .join(chain_id().as_u64().to_string())
+        .join(checksum_address(address))
+}
+
+async fn save_evm_verification_info(
+    storage: &mut Connection<'_, Core>,
+    address: Address,
+    source: &str,
+    verification_problems: Vec<VerificationProblem>,
+) {
+    let metadata: SolcMetadata = serde_json::from_value(mock_metadata(source)).unwrap();
+    let sources = HashMap::from([(SOURCE_PATH.to_owned(), source.to_owned())]);
+    let req = metadata.to_verification_request(address, &sources).unwrap();
+    let id = storage
+        .contract_verification_dal()
+        .add_contract_verification_request(&req)
+        .await
+        .unwrap();
+
+    let info = VerificationInfo {
+        request: VerificationRequest { id, req },
+        artifacts: CompilationArtifacts {
+            bytecode: vec![3; 20],
+            deployed_bytecode: Some(vec![5; 10]),
+            abi: counter_contract_abi(),
+            immutable_refs: Default::default(),
+            metadata: Some(mock_metadata(source).to_string()),
+        },
+        verified_at: Utc::now(),
+        verification_problems,
+    };
+    storage
+        .contract_verification_dal()
+        .save_verification_info(info, H256::zero(), H256::zero())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn exporting_verified_contracts() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    let evm_address = Address::repeat_byte(1);
+    save_evm_verification_info(&mut storage, evm_address, COUNTER_CONTRACT, vec![]).await;
+
+    // EraVM contracts cannot be exported.
+    let era_vm_address = Address::repeat_byte(2);
+    let req = test_request(era_vm_address, COUNTER_CONTRACT);
+    let id = storage
+        .contract_verification_dal()
+        .add_contract_verification_request(&req)
+        .await
+        .unwrap();
+    let info = VerificationInfo {
+        request: VerificationRequest { id, req },
+        artifacts: CompilationArtifacts {
+            bytecode: vec![0; 32],
+            deployed_bytecode: None,
+            abi: counter_contract_abi(),
+            immutable_refs: Default::default(),
+            metadata: None,
+        },
+        verified_at: Utc::now(),
+        verification_problems: vec![],
+    };
+    storage
+        .contract_verification_dal()
+        .save_verification_info(info, H256::zero(), H256::zero())
+        .await
+        .unwrap();
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let repo = SourcifyRepository::new(temp_dir.path().to_owned(), chain_id());
+    let stats = repo.export(&pool).await.unwrap();
+    assert_eq!(
+        stats,
+        ExportStats {
+            exported: 1,
+            skipped: 1
+        }
+    );
+
+    let full_match_dir = contract_dir(temp_dir.path(), "full_match", &evm_address);
+    let metadata = std::fs::read_to_string(full_match_dir.join("metadata.json")).unwrap();
+    let metadata: serde_json::Value = serde_json::from_str(&metadata).unwrap();
+    assert_eq!(metadata, mock_metadata(COUNTER_CONTRACT));
+    let source = std::fs::read_to_string(full_match_dir.join("sources").join(SOURCE_PATH)).unwrap();
+    assert_eq!(source, COUNTER_CONTRACT);
+
+    // Re-verification with a partial match should move the contract to the `partial_match` dir.
+    save_evm_verification_info(
+        &mut storage,
+        evm_address,
+        COUNTER_CONTRACT,
+        vec![VerificationProblem::IncorrectMetadata],
+    )
+    .await;
+    repo.export(&pool).await.unwrap();
+    assert!(!full_match_dir.exists());
+    let partial_match_dir = contract_dir(temp_dir.path(), "partial_match", &evm_address);
+    assert!(partial_match_dir.join("metadata.json").exists());
+}
+
+fn write_contract(root: &Path, address: &Address, metadata: &str, source: &str) {
+    let dir = contract_dir(root, "full_match", address);
+    let sources_dir = dir.join("sources").join("contracts");
+    std::fs::create_dir_all(&sources_dir).unwrap();
+    std::fs::write(dir.join("metadata.json"), metadata).unwrap();
+    std::fs::write(sources_dir.join("Counter.sol"), source).unwrap();
+}
+
+#[test_casing(2, TestContract::ALL)]
+#[tokio::test]
+async fn importing_sourcify_verifications(contract: TestContract) {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    let creation_bytecode = vec![3_u8; 20];
+    let deployed_bytecode = vec![5_u8; 10];
+
+    prepare_storage(&mut storage).await;
+    let address = Address::repeat_byte(1);
+    mock_evm_deployment(
+        &mut storage,
+        address,
+        creation_bytecode.clone(),
+        &deployed_bytecode,
+        contract.constructor_args(),
+    )
+    .await;
+    // EraVM contracts cannot be verified using Sourcify metadata.
+    let era_vm_address = Address::repeat_byte(2);
+    mock_deployment(&mut storage, era_vm_address, vec![0; 32], &[]).await;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let metadata = mock_metadata(contract.source()).to_string();
+    write_contract(temp_dir.path(), &address, &metadata, contract.source());
+    write_contract(
+        temp_dir.path(),
+        &era_vm_address,
+        &metadata,
+        contract.source(),
+    );
+    let not_deployed_address = Address::repeat_byte(3);
+    write_contract(
+        temp_dir.path(),
+        &not_deployed_address,
+        &metadata,
+        contract.source(),
+    );
+    let invalid_address = Address::repeat_byte(4);
+    write_contract(
+        temp_dir.path(),
+        &invalid_address,
+        &metadata,
+        "contract Other {}",
+    );
+
+    let repo = SourcifyRepository::new(temp_dir.path().to_owned(), chain_id());
+    let stats = repo.import(&pool).await.unwrap();
+    assert_eq!(
+        stats,
+        ImportStats {
+            enqueued: 1,
+            already_verified: 0,
+            not_deployed: 2,
+            invalid: 1,
+        }
+    );
+    // Repeated import shouldn't enqueue duplicate requests.
+    let stats = repo.import(&pool).await.unwrap();
+    assert_eq!(stats.enqueued, 0);
+    assert_eq!(stats.already_verified, 1);
+
+    let artifacts = CompilationArtifacts {
+        bytecode: creation_bytecode.clone(),
+        deployed_bytecode: Some(deployed_bytecode),
+        abi: counter_contract_abi(),
+        immutable_refs: Default::default(),
+        metadata: Some(metadata),
+    };
+    let mock_resolver = MockCompilerResolver::solc(move |input| {
+        assert_eq!(input.file_name, SOURCE_PATH);
+        assert_eq!(input.contract_name, "Counter");
+        assert_eq!(input.standard_json.settings.other["evmVersion"], "cancun");
+        artifacts.clone()
+    });
+    let verifier = ContractVerifier::with_resolver(
+        Duration::from_secs(60),
+        pool.clone(),
+        Arc::new(mock_resolver),
+        false,
+    )
+    .await
+    .unwrap();
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    verifier.run(stop_receiver, Some(1)).await.unwrap();
+
+    let info = storage
+        .contract_verification_dal()
+        .get_contract_verification_info(address)
+        .await
+        .unwrap()
+        .expect("contract not verified");
+    assert!(info.is_perfect_match());
+    // Constructor args must be taken from the deployment.
+    assert_eq!(
+        info.request.req.constructor_arguments.0,
+        ethabi::encode(contract.constructor_args())
+    );
+}
+
+#[tokio::test]
+async fn importing_contract_with_mismatched_creation_bytecode() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    let deployed_bytecode = vec![5_u8; 10];
+
+    prepare_storage(&mut storage).await;
+    let address = Address::repeat_byte(1);
+    mock_evm_deployment(&mut storage, address, vec![3; 20], &deployed_bytecode, &[]).await;
+    let temp_dir = tempfile::tempdir().unwrap();
+    let metadata = mock_metadata(COUNTER_CONTRACT).to_string();
+    write_contract(temp_dir.path(), &address, &metadata, COUNTER_CONTRACT);
+    let repo = SourcifyRepository::new(temp_dir.path().to_owned(), chain_id());
+    let stats = repo.import(&pool).await.unwrap();
+    assert_eq!(stats.enqueued, 1);
+    let request_id = storage
+        .contract_verification_dal()
+        .get_active_verification_request(address)
+        .await
+        .unwrap()
+        .expect("no imported request");
+
+    let mock_resolver = MockCompilerResolver::solc(move |_| CompilationArtifacts {
+        bytecode: vec![4; 20], // differs from the deployed creation bytecode
+        deployed_bytecode: Some(deployed_bytecode.clone()),
+        abi: counter_contract_abi(),
+        immutable_refs: Default::default(),
+        metadata: None,
+    });
+    let verifier = ContractVerifier::with_resolver(
+        Duration::from_secs(60),
+        pool.clone(),
+        Arc::new(mock_resolver),
+        false,
+    )
+    .await
+    .unwrap();
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    verifier.run(stop_receiver, Some(1)).await.unwrap();
+
+    let status = storage
+        .contract_verification_dal()
+        .get_verification_request_status(request_id)
+        .await
+        .unwrap()
+        .expect("no status");
+    assert_eq!(status.status, "failed");
+    assert_eq!(
+        status.error.unwrap(),
+        ContractVerifierError::CreationBytecodeMismatch.to_string()
+    );
+}
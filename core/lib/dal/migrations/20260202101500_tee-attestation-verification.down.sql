@@ -0,0 +1,4 @@
+ALTER TABLE tee_attestations DROP COLUMN IF EXISTS expires_at;
+ALTER TABLE tee_attestations DROP COLUMN IF EXISTS verified_at;
+ALTER TABLE tee_attestations DROP COLUMN IF EXISTS tcb_status;
+ALTER TABLE tee_attestations DROP COLUMN IF EXISTS tee_type;
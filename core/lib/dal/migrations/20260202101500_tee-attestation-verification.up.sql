@@ -0,0 +1,4 @@
+ALTER TABLE tee_attestations ADD COLUMN IF NOT EXISTS tee_type TEXT;
+ALTER TABLE tee_attestations ADD COLUMN IF NOT EXISTS tcb_status TEXT;
+ALTER TABLE tee_attestations ADD COLUMN IF NOT EXISTS verified_at TIMESTAMP;
+ALTER TABLE tee_attestations ADD COLUMN IF NOT EXISTS expires_at TIMESTAMP;
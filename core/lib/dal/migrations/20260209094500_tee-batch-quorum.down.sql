@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS tee_batch_attestations;
+DROP TABLE IF EXISTS tee_proof_signatures;
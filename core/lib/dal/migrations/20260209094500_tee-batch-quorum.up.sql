@@ -0,0 +1,21 @@
+-- All signatures submitted by TEE provers. Unlike `tee_proof_generation_details`, which only keeps
+-- the latest proof per TEE type, this table keeps a signature per key so that quorums can be computed.
+-- Like `tee_proof_generation_details`, it doesn't reference `l1_batches`; signatures are only matched
+-- against batch root hashes when computing quorums.
+CREATE TABLE IF NOT EXISTS tee_proof_signatures (
+    l1_batch_number BIGINT NOT NULL,
+    pubkey BYTEA NOT NULL REFERENCES tee_attestations (pubkey) ON DELETE CASCADE,
+    tee_type TEXT NOT NULL,
+    root_hash BYTEA NOT NULL,
+    signature BYTEA NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (l1_batch_number, pubkey)
+);
+
+CREATE TABLE IF NOT EXISTS tee_batch_attestations (
+    l1_batch_number BIGINT NOT NULL PRIMARY KEY REFERENCES l1_batches (number) ON DELETE CASCADE,
+    root_hash BYTEA NOT NULL,
+    signers INT NOT NULL,
+    tee_types TEXT[] NOT NULL,
+    attested_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1,11 @@
+-- Membership of L2 transactions in atomic bundles. Bundle members are stored in `transactions` as usual,
+-- but they are loaded into the mempool only as complete bundles. This table intentionally doesn't reference
+-- `transactions`: if a member is replaced or removed, the bundle becomes incomplete and is never executed.
+CREATE TABLE IF NOT EXISTS transaction_bundles (
+    tx_hash BYTEA NOT NULL PRIMARY KEY,
+    bundle_hash BYTEA NOT NULL,
+    index_in_bundle INT NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS transaction_bundles_bundle_hash_idx ON transaction_bundles (bundle_hash);
@@ -0,0 +1,3 @@
+ALTER TABLE l1_batches
+    DROP COLUMN IF EXISTS estimated_proving_time_ms,
+    DROP COLUMN IF EXISTS estimated_proving_cost;
@@ -0,0 +1,5 @@
+-- Proving cost of the batch estimated by the state keeper based on predicted circuit counts. Used to compare
+-- with actual prover data; only populated if proving cost estimation is enabled in the state keeper.
+ALTER TABLE l1_batches
+    ADD COLUMN IF NOT EXISTS estimated_proving_time_ms BIGINT,
+    ADD COLUMN IF NOT EXISTS estimated_proving_cost DOUBLE PRECISION;
@@ -0,0 +1,3 @@
+-- L1 batch of the snapshot that a differential snapshot is based on. `NULL` for full snapshots.
+ALTER TABLE snapshots
+    ADD COLUMN IF NOT EXISTS base_l1_batch_number BIGINT;
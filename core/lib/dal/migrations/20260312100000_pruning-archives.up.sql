@@ -0,0 +1,6 @@
+-- Archives of pruned L2 blocks persisted to the object store by the DB pruner.
+CREATE TABLE IF NOT EXISTS pruning_archives (
+    first_l2_block BIGINT NOT NULL,
+    last_l2_block BIGINT NOT NULL PRIMARY KEY,
+    created_at TIMESTAMP NOT NULL
+);
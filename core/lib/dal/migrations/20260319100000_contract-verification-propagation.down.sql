@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS contract_verification_propagation;
+ALTER TABLE contract_verification_info_v2
+    DROP COLUMN IF EXISTS propagated_from_addr;
@@ -0,0 +1,10 @@
+-- Address of the verified contract that verification info was propagated from. `NULL` for contracts verified directly.
+ALTER TABLE contract_verification_info_v2
+    ADD COLUMN IF NOT EXISTS propagated_from_addr BYTEA;
+
+-- Single-row table tracking progress of propagating verification info to newly deployed contracts.
+CREATE TABLE IF NOT EXISTS contract_verification_propagation (
+    id BOOLEAN NOT NULL PRIMARY KEY DEFAULT TRUE CHECK (id),
+    next_l2_block BIGINT NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS pruned_l2_block_hashes;
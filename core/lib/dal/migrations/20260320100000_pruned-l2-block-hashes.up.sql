@@ -0,0 +1,5 @@
+-- Hashes of archived L2 blocks, so that pruned blocks can be looked up by hash in the pruning archives.
+CREATE TABLE IF NOT EXISTS pruned_l2_block_hashes (
+    hash BYTEA NOT NULL PRIMARY KEY,
+    number BIGINT NOT NULL
+);
@@ -3,6 +3,7 @@ use std::{
     convert::{Into, TryInto},
     ops,
     sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
 };
 
 use anyhow::Context as _;
@@ -19,8 +20,8 @@ use zksync_types::{
         AggregatedActionType, L1BatchAggregatedActionType, L2BlockAggregatedActionType,
     },
     block::{
-        CommonBlockStatistics, CommonL1BatchHeader, L1BatchHeader, L1BatchTreeData, L2BlockHeader,
-        StorageOracleInfo, UnsealedL1BatchHeader,
+        CommonBlockStatistics, CommonL1BatchHeader, L1BatchHeader, L1BatchTreeData,
+        L2BlockGasUsage, L2BlockHeader, StorageOracleInfo, UnsealedL1BatchHeader,
     },
     commitment::{L1BatchCommitmentArtifacts, L1BatchWithMetadata, PubdataParams},
     l2_to_l1_log::{BatchAndChainMerklePath, UserL2ToL1Log},
@@ -35,7 +36,7 @@ use crate::{
         parse_protocol_version,
         storage_block::{
             CommonStorageL1BatchHeader, StorageL1Batch, StorageL1BatchHeader, StorageL2BlockHeader,
-            StoragePubdataParams, UnsealedStorageL1Batch,
+            StoragePubdataParams, UnsealedStorageL1Batch, LEGACY_BLOCK_GAS_LIMIT,
         },
         storage_eth_tx::L2BlockWithEthTx,
         storage_event::StorageL2ToL1Log,
@@ -558,6 +559,44 @@ impl BlocksDal<'_, '_> {
         .collect())
     }
 
+    /// Returns hashes of transactions in L1 batches committed, proven or executed by the specified `eth_tx`,
+    /// together with the containing L1 batch numbers.
+    pub async fn get_tx_hashes_for_eth_tx_id(
+        &mut self,
+        eth_tx_id: u32,
+    ) -> DalResult<Vec<(L1BatchNumber, H256)>> {
+        Ok(sqlx::query!(
+            r#"
+            SELECT
+                l1_batches.number,
+                transactions.hash
+            FROM
+                l1_batches
+            JOIN transactions ON l1_batches.number = transactions.l1_batch_number
+            WHERE
+                l1_batches.eth_commit_tx_id = $1
+                OR l1_batches.eth_prove_tx_id = $1
+                OR l1_batches.eth_execute_tx_id = $1
+            ORDER BY
+                transactions.miniblock_number,
+                transactions.index_in_block
+            "#,
+            eth_tx_id as i32
+        )
+        .instrument("get_tx_hashes_for_eth_tx_id")
+        .with_arg("eth_tx_id", &eth_tx_id)
+        .fetch_all(self.storage)
+        .await?
+        .into_iter()
+        .map(|row| {
+            (
+                L1BatchNumber(row.number as u32),
+                H256::from_slice(&row.hash),
+            )
+        })
+        .collect())
+    }
+
     async fn get_storage_l1_batch(
         &mut self,
         number: L1BatchNumber,
@@ -1262,6 +1301,72 @@ impl BlocksDal<'_, '_> {
         Ok(())
     }
 
+    /// Records the proving cost of a sealed L1 batch estimated by the state keeper.
+    pub async fn set_l1_batch_proving_cost_estimate(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        proving_time: Duration,
+        cost: f64,
+    ) -> DalResult<()> {
+        let proving_time_ms = i64::try_from(proving_time.as_millis()).unwrap_or(i64::MAX);
+        let instrumentation = Instrumented::new("set_l1_batch_proving_cost_estimate")
+            .with_arg("l1_batch_number", &l1_batch_number)
+            .with_arg("proving_time", &proving_time);
+        let query = sqlx::query!(
+            r#"
+            UPDATE l1_batches
+            SET
+                estimated_proving_time_ms = $2,
+                estimated_proving_cost = $3,
+                updated_at = NOW()
+            WHERE
+                number = $1
+            "#,
+            i64::from(l1_batch_number.0),
+            proving_time_ms,
+            cost
+        );
+        let result = instrumentation
+            .clone()
+            .with(query)
+            .execute(self.storage)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            let err = anyhow::anyhow!("L1 batch #{l1_batch_number} is not present in the database");
+            return Err(instrumentation.arg_error("l1_batch_number", err));
+        }
+        Ok(())
+    }
+
+    /// Returns the estimated prover time and proving cost recorded for the specified L1 batch.
+    pub async fn get_l1_batch_proving_cost_estimate(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Option<(Duration, f64)>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                estimated_proving_time_ms,
+                estimated_proving_cost
+            FROM
+                l1_batches
+            WHERE
+                number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_l1_batch_proving_cost_estimate")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.and_then(|row| {
+            let proving_time = Duration::from_millis(row.estimated_proving_time_ms? as u64);
+            Some((proving_time, row.estimated_proving_cost?))
+        }))
+    }
+
     pub async fn get_blobs_amount_for_range(
         &mut self,
         lower_bound: L1BatchNumber,
@@ -3170,6 +3275,52 @@ impl BlocksDal<'_, '_> {
         Ok(Some((L2BlockNumber(min as u32), L2BlockNumber(max as u32))))
     }
 
+    /// Returns gas usage for sealed L2 blocks in the specified range, ordered by the block number.
+    /// Blocks missing from the storage are skipped.
+    pub async fn get_l2_blocks_gas_usage(
+        &mut self,
+        numbers: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<Vec<L2BlockGasUsage>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                miniblocks.number,
+                miniblocks.l1_tx_count,
+                miniblocks.l2_tx_count,
+                miniblocks.gas_limit,
+                COALESCE(
+                    SUM(transactions.gas_limit - transactions.refunded_gas),
+                    0
+                ) AS "gas_used!"
+            FROM
+                miniblocks
+            LEFT JOIN transactions ON transactions.miniblock_number = miniblocks.number
+            WHERE
+                miniblocks.number BETWEEN $1 AND $2
+            GROUP BY
+                miniblocks.number
+            ORDER BY
+                miniblocks.number
+            "#,
+            i64::from(numbers.start().0),
+            i64::from(numbers.end().0)
+        )
+        .instrument("get_l2_blocks_gas_usage")
+        .with_arg("numbers", &numbers)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| L2BlockGasUsage {
+                number: L2BlockNumber(row.number as u32),
+                tx_count: (row.l1_tx_count + row.l2_tx_count) as u32,
+                gas_used: row.gas_used.to_u64().unwrap_or(u64::MAX),
+                gas_limit: row.gas_limit.unwrap_or(i64::from(LEGACY_BLOCK_GAS_LIMIT)) as u64,
+            })
+            .collect())
+    }
+
     /// Returns `true` if there exists a non-sealed batch (i.e. there is one+ stored L2 block that isn't assigned
     /// to any batch yet).
     pub async fn pending_batch_exists(&mut self) -> DalResult<bool> {
@@ -3770,10 +3921,14 @@ mod tests {
         aggregated_operations::AggregatedActionType, tx::IncludedTxLocation, Address,
         ProtocolVersion,
     };
+    use zksync_vm_interface::{tracer::ValidationTraces, TransactionExecutionMetrics};
 
     use super::*;
     use crate::{
-        tests::{create_l1_batch_header, create_l2_block_header, create_l2_to_l1_log},
+        tests::{
+            create_l1_batch_header, create_l2_block_header, create_l2_to_l1_log,
+            mock_execution_result, mock_l2_transaction,
+        },
         ConnectionPool, Core, CoreDal,
     };
 
@@ -3893,6 +4048,78 @@ mod tests {
             .is_err());
     }
 
+    #[tokio::test]
+    async fn getting_tx_hashes_for_eth_tx() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .insert_l2_block(&create_l2_block_header(1))
+            .await
+            .unwrap();
+
+        let mut tx_results = vec![];
+        for _ in 0..2 {
+            let tx = mock_l2_transaction();
+            conn.transactions_dal()
+                .insert_transaction_l2(
+                    &tx,
+                    TransactionExecutionMetrics::default(),
+                    ValidationTraces::default(),
+                )
+                .await
+                .unwrap();
+            tx_results.push(mock_execution_result(tx));
+        }
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_l2_block(
+                L2BlockNumber(1),
+                &tx_results,
+                1.into(),
+                ProtocolVersionId::latest(),
+                false,
+            )
+            .await
+            .unwrap();
+        insert_mock_l1_batch_header(&mut conn, &create_l1_batch_header(1)).await;
+        let tx_hashes: Vec<_> = tx_results.iter().map(|tx_result| tx_result.hash).collect();
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_l1_batch(L1BatchNumber(1), &tx_hashes)
+            .await
+            .unwrap();
+
+        save_mock_eth_tx(L1BatchAggregatedActionType::Commit, &mut conn).await;
+        conn.blocks_dal()
+            .set_eth_tx_id_for_l1_batches(
+                L1BatchNumber(1)..=L1BatchNumber(1),
+                1,
+                AggregatedActionType::L1Batch(L1BatchAggregatedActionType::Commit),
+            )
+            .await
+            .unwrap();
+
+        let hashes = conn
+            .blocks_dal()
+            .get_tx_hashes_for_eth_tx_id(1)
+            .await
+            .unwrap();
+        let expected_hashes: Vec<_> = tx_hashes
+            .into_iter()
+            .map(|hash| (L1BatchNumber(1), hash))
+            .collect();
+        assert_eq!(hashes, expected_hashes);
+
+        let hashes = conn
+            .blocks_dal()
+            .get_tx_hashes_for_eth_tx_id(2)
+            .await
+            .unwrap();
+        assert!(hashes.is_empty());
+    }
+
     #[tokio::test]
     async fn persisting_evm_emulator_hash() {
         let pool = ConnectionPool::<Core>::test_pool().await;
@@ -3986,4 +4213,38 @@ mod tests {
             .unwrap()
             .is_none());
     }
+
+    #[tokio::test]
+    async fn persisting_proving_cost_estimate() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        insert_mock_l1_batch_header(&mut conn, &mock_l1_batch_header()).await;
+
+        let estimate = conn
+            .blocks_dal()
+            .get_l1_batch_proving_cost_estimate(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(estimate, None);
+
+        conn.blocks_dal()
+            .set_l1_batch_proving_cost_estimate(L1BatchNumber(1), Duration::from_secs(1_800), 2.5)
+            .await
+            .unwrap();
+        let estimate = conn
+            .blocks_dal()
+            .get_l1_batch_proving_cost_estimate(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(estimate, Some((Duration::from_secs(1_800), 2.5)));
+
+        conn.blocks_dal()
+            .set_l1_batch_proving_cost_estimate(L1BatchNumber(2), Duration::from_secs(1), 1.0)
+            .await
+            .unwrap_err();
+    }
 }
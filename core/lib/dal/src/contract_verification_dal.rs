@@ -2,6 +2,7 @@
 
 use std::{
     fmt::{Display, Formatter},
+    ops,
     time::Duration,
 };
 
@@ -20,7 +21,7 @@ use zksync_types::{
         },
         contract_identifier::ContractIdentifier,
     },
-    web3, Address, CONTRACT_DEPLOYER_ADDRESS, H256,
+    web3, Address, L2BlockNumber, CONTRACT_DEPLOYER_ADDRESS, H256,
 };
 use zksync_vm_interface::VmEvent;
 
@@ -265,7 +266,8 @@ impl ContractVerificationDal<'_, '_> {
             SET
             bytecode_keccak256 = $2,
             bytecode_without_metadata_keccak256 = $3,
-            verification_info = $4
+            verification_info = $4,
+            propagated_from_addr = NULL
             "#,
             address.as_bytes(),
             bytecode_keccak256.as_bytes(),
@@ -281,6 +283,110 @@ impl ContractVerificationDal<'_, '_> {
         transaction.commit().await
     }
 
+    /// Inserts verification info propagated from the `source_address` contract with matching bytecode.
+    /// Does nothing if the contract already has verification info. Returns whether the info was inserted.
+    pub async fn save_propagated_verification_info(
+        &mut self,
+        verification_info: VerificationInfo,
+        bytecode_keccak256: H256,
+        bytecode_without_metadata_keccak256: H256,
+        source_address: Address,
+    ) -> DalResult<bool> {
+        let address = verification_info.request.req.contract_address;
+        // Serialization should always succeed.
+        let verification_info_json = serde_json::to_value(verification_info)
+            .expect("Failed to serialize verification info into serde_json");
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO
+            contract_verification_info_v2 (
+                initial_contract_addr,
+                bytecode_keccak256,
+                bytecode_without_metadata_keccak256,
+                verification_info,
+                propagated_from_addr
+            )
+            VALUES
+            ($1, $2, $3, $4, $5)
+            ON CONFLICT (initial_contract_addr) DO NOTHING
+            "#,
+            address.as_bytes(),
+            bytecode_keccak256.as_bytes(),
+            bytecode_without_metadata_keccak256.as_bytes(),
+            &verification_info_json,
+            source_address.as_bytes()
+        )
+        .instrument("save_propagated_verification_info")
+        .with_arg("address", &address)
+        .with_arg("source_address", &source_address)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Returns the address of the contract that verification info was propagated from, or `None`
+    /// if the contract is verified directly or is not verified.
+    pub async fn get_propagation_source(&mut self, address: Address) -> DalResult<Option<Address>> {
+        Ok(sqlx::query!(
+            r#"
+            SELECT
+                propagated_from_addr
+            FROM
+                contract_verification_info_v2
+            WHERE
+                initial_contract_addr = $1
+            "#,
+            address.as_bytes(),
+        )
+        .instrument("get_propagation_source")
+        .with_arg("address", &address)
+        .fetch_optional(self.storage)
+        .await?
+        .and_then(|row| row.propagated_from_addr)
+        .map(|address| Address::from_slice(&address)))
+    }
+
+    /// Returns the next L2 block to be scanned for verification info propagation.
+    pub async fn get_next_propagation_l2_block(&mut self) -> DalResult<Option<L2BlockNumber>> {
+        Ok(sqlx::query!(
+            r#"
+            SELECT
+                next_l2_block
+            FROM
+                contract_verification_propagation
+            "#
+        )
+        .instrument("get_next_propagation_l2_block")
+        .fetch_optional(self.storage)
+        .await?
+        .map(|row| L2BlockNumber(row.next_l2_block as u32)))
+    }
+
+    pub async fn set_next_propagation_l2_block(
+        &mut self,
+        l2_block: L2BlockNumber,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            contract_verification_propagation (next_l2_block, updated_at)
+            VALUES
+            ($1, NOW())
+            ON CONFLICT (id) DO
+            UPDATE
+            SET
+            next_l2_block = $1,
+            updated_at = NOW()
+            "#,
+            i64::from(l2_block.0)
+        )
+        .instrument("set_next_propagation_l2_block")
+        .with_arg("l2_block", &l2_block)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
     pub async fn save_verification_error(
         &mut self,
         id: usize,
@@ -385,6 +491,29 @@ impl ContractVerificationDal<'_, '_> {
         .await
     }
 
+    /// Returns the address of the contract being verified by the specified request.
+    pub async fn get_verification_request_address(
+        &mut self,
+        id: usize,
+    ) -> DalResult<Option<Address>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                contract_address
+            FROM
+                contract_verification_requests
+            WHERE
+                id = $1
+            "#,
+            id as i64,
+        )
+        .instrument("get_verification_request_address")
+        .with_arg("id", &id)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(|row| Address::from_slice(&row.contract_address)))
+    }
+
     /// Returns bytecode and calldata from the contract and the transaction that created it.
     pub async fn get_contract_info_for_verification(
         &mut self,
@@ -449,6 +578,54 @@ impl ContractVerificationDal<'_, '_> {
         .await
     }
 
+    /// Returns contracts deployed in the specified L2 blocks that do not have verification info,
+    /// together with their bytecode hashes. Deployments are ordered by their position in the chain.
+    pub async fn get_unverified_contract_deployments(
+        &mut self,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<Vec<(Address, H256)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                topic4 AS deployed_address,
+                topic3 AS bytecode_hash
+            FROM
+                events
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+                AND address = $3
+                AND topic1 = $4
+                AND NOT EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        contract_verification_info_v2
+                    WHERE
+                        initial_contract_addr = SUBSTRING(events.topic4 FROM 13)
+                )
+            ORDER BY
+                miniblock_number,
+                event_index_in_block
+            "#,
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0),
+            CONTRACT_DEPLOYER_ADDRESS.as_bytes(),
+            VmEvent::DEPLOY_EVENT_SIGNATURE.as_bytes(),
+        )
+        .instrument("get_unverified_contract_deployments")
+        .with_arg("l2_blocks", &l2_blocks)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let address = Address::from_slice(&row.deployed_address[12..]);
+                (address, H256::from_slice(&row.bytecode_hash))
+            })
+            .collect())
+    }
+
     async fn get_compiler_versions(&mut self, compiler: Compiler) -> DalResult<Vec<String>> {
         let compiler = format!("{compiler}");
         let versions: Vec<_> = sqlx::query!(
@@ -662,6 +839,41 @@ impl ContractVerificationDal<'_, '_> {
         .flatten())
     }
 
+    /// Returns up to `limit` verification infos for contracts with addresses greater than `after`, ordered by address.
+    /// Intended for iterating over all verified contracts, e.g. to export them.
+    pub async fn get_verification_info_page(
+        &mut self,
+        after: Option<Address>,
+        limit: usize,
+    ) -> DalResult<Vec<VerificationInfo>> {
+        // An empty byte string is less than any address.
+        let after_bytes = after.as_ref().map_or(&[][..], Address::as_bytes);
+        sqlx::query!(
+            r#"
+            SELECT
+                verification_info
+            FROM
+                contract_verification_info_v2
+            WHERE
+                initial_contract_addr > $1
+            ORDER BY
+                initial_contract_addr
+            LIMIT
+                $2
+            "#,
+            after_bytes,
+            limit as i64
+        )
+        .try_map(|row| {
+            serde_json::from_value(row.verification_info).decode_column("verification_info")
+        })
+        .instrument("get_verification_info_page")
+        .with_arg("after", &after)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await
+    }
+
     /// Returns verification info for the contract.
     /// Tries to find the full bytecode match first. If it's not found, tries to find the partial match for the
     /// bytecode without metadata.
@@ -893,7 +1105,8 @@ mod tests {
 
     use zksync_types::{
         bytecode::BytecodeHash,
-        contract_verification::api::{CompilerVersions, SourceCodeData},
+        contract_verification::api::{CompilationArtifacts, CompilerVersions, SourceCodeData},
+        l2::L2Tx,
         tx::IncludedTxLocation,
         Execute, L1BatchNumber, L2BlockNumber, ProtocolVersion,
     };
@@ -905,11 +1118,7 @@ mod tests {
         ConnectionPool, CoreDal,
     };
 
-    #[tokio::test]
-    async fn getting_contract_info_for_verification() {
-        let pool = ConnectionPool::<Core>::test_pool().await;
-        let mut conn = pool.connection().await.unwrap();
-
+    async fn mock_deployment(conn: &mut Connection<'_, Core>, deployed_address: Address) -> L2Tx {
         conn.protocol_versions_dal()
             .save_protocol_version_with_tx(&ProtocolVersion::default())
             .await
@@ -920,7 +1129,6 @@ mod tests {
             .unwrap();
 
         // Add a transaction, its bytecode and the bytecode deployment event.
-        let deployed_address = Address::repeat_byte(12);
         let mut tx = mock_l2_transaction();
         let bytecode = vec![1; 32];
         let bytecode_hash = BytecodeHash::for_bytecode(&bytecode).value();
@@ -959,6 +1167,49 @@ mod tests {
             .save_events(L2BlockNumber(0), &[(location, vec![&deploy_event])])
             .await
             .unwrap();
+        tx
+    }
+
+    fn mock_verification_info(address: Address) -> VerificationInfo {
+        VerificationInfo {
+            request: VerificationRequest {
+                id: 1,
+                req: VerificationIncomingRequest {
+                    contract_address: address,
+                    source_code_data: SourceCodeData::SolSingleFile("contract Test {}".to_owned()),
+                    contract_name: "Test".to_owned(),
+                    compiler_versions: CompilerVersions::Solc {
+                        compiler_zksolc_version: Some("1.5.7".to_owned()),
+                        compiler_solc_version: "0.8.27".to_owned(),
+                    },
+                    optimization_used: true,
+                    optimizer_mode: None,
+                    constructor_arguments: web3::Bytes(vec![]),
+                    is_system: false,
+                    force_evmla: false,
+                    evm_specific: Default::default(),
+                },
+            },
+            artifacts: CompilationArtifacts {
+                bytecode: vec![1; 32],
+                deployed_bytecode: None,
+                abi: serde_json::json!([]),
+                immutable_refs: Default::default(),
+                metadata: None,
+            },
+            verified_at: Default::default(),
+            verification_problems: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn getting_contract_info_for_verification() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let deployed_address = Address::repeat_byte(12);
+        let tx = mock_deployment(&mut conn, deployed_address).await;
+        let bytecode = vec![1; 32];
+        let bytecode_hash = BytecodeHash::for_bytecode(&bytecode).value();
 
         let contract = conn
             .contract_verification_dal()
@@ -972,6 +1223,107 @@ mod tests {
         assert_eq!(contract.calldata.unwrap(), tx.execute.calldata);
     }
 
+    #[tokio::test]
+    async fn propagating_verification_info() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let deployed_address = Address::repeat_byte(12);
+        mock_deployment(&mut conn, deployed_address).await;
+        let bytecode_hash = BytecodeHash::for_bytecode(&[1; 32]).value();
+
+        let mut dal = conn.contract_verification_dal();
+        let deployments = dal
+            .get_unverified_contract_deployments(L2BlockNumber(0)..=L2BlockNumber(0))
+            .await
+            .unwrap();
+        assert_eq!(deployments, [(deployed_address, bytecode_hash)]);
+        let deployments = dal
+            .get_unverified_contract_deployments(L2BlockNumber(1)..=L2BlockNumber(10))
+            .await
+            .unwrap();
+        assert!(deployments.is_empty(), "{deployments:?}");
+
+        let source_address = Address::repeat_byte(11);
+        let info = mock_verification_info(deployed_address);
+        let inserted = dal
+            .save_propagated_verification_info(
+                info.clone(),
+                H256::zero(),
+                H256::zero(),
+                source_address,
+            )
+            .await
+            .unwrap();
+        assert!(inserted);
+        let inserted = dal
+            .save_propagated_verification_info(info, H256::zero(), H256::zero(), source_address)
+            .await
+            .unwrap();
+        assert!(!inserted);
+
+        let source = dal.get_propagation_source(deployed_address).await.unwrap();
+        assert_eq!(source, Some(source_address));
+        let deployments = dal
+            .get_unverified_contract_deployments(L2BlockNumber(0)..=L2BlockNumber(0))
+            .await
+            .unwrap();
+        assert!(deployments.is_empty(), "{deployments:?}");
+
+        assert_eq!(dal.get_next_propagation_l2_block().await.unwrap(), None);
+        dal.set_next_propagation_l2_block(L2BlockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(
+            dal.get_next_propagation_l2_block().await.unwrap(),
+            Some(L2BlockNumber(1))
+        );
+        dal.set_next_propagation_l2_block(L2BlockNumber(5))
+            .await
+            .unwrap();
+        assert_eq!(
+            dal.get_next_propagation_l2_block().await.unwrap(),
+            Some(L2BlockNumber(5))
+        );
+    }
+
+    #[tokio::test]
+    async fn paginating_verification_info() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.contract_verification_dal();
+        let addresses = [3, 1, 2].map(Address::repeat_byte);
+        for address in addresses {
+            dal.save_verification_info(mock_verification_info(address), H256::zero(), H256::zero())
+                .await
+                .unwrap();
+        }
+
+        let page = dal.get_verification_info_page(None, 2).await.unwrap();
+        let page_addresses: Vec<_> = page
+            .iter()
+            .map(|info| info.request.req.contract_address)
+            .collect();
+        assert_eq!(
+            page_addresses,
+            [Address::repeat_byte(1), Address::repeat_byte(2)]
+        );
+
+        let page = dal
+            .get_verification_info_page(Some(Address::repeat_byte(2)), 2)
+            .await
+            .unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(
+            page[0].request.req.contract_address,
+            Address::repeat_byte(3)
+        );
+        let page = dal
+            .get_verification_info_page(Some(Address::repeat_byte(3)), 2)
+            .await
+            .unwrap();
+        assert!(page.is_empty());
+    }
+
     async fn test_working_with_verification_requests(zksolc: Option<&str>) {
         let request = VerificationIncomingRequest {
             contract_address: Address::repeat_byte(11),
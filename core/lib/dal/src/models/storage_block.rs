@@ -601,6 +601,7 @@ impl From<StorageL1BatchDetails> for api::L1BatchDetails {
             base,
             commitment: details.commitment.map(|c| H256::from_slice(&c)),
             number: L1BatchNumber(details.number as u32),
+            tee_attestation: None,
         }
     }
 }
@@ -1,7 +1,7 @@
 use std::ops;
 
 use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
-use zksync_types::{L1BatchNumber, L2BlockNumber, H256};
+use zksync_types::{pruning::PrunedL2BlocksArchiveKey, L1BatchNumber, L2BlockNumber, H256};
 
 use crate::Core;
 
@@ -470,4 +470,117 @@ impl PruningDal<'_, '_> {
         .await?;
         Ok(())
     }
+
+    /// Records that an archive of the specified L2 blocks was persisted to the object store.
+    /// Should be called in the same transaction as [`Self::insert_soft_pruning_log()`].
+    pub async fn insert_pruning_archive(&mut self, key: PrunedL2BlocksArchiveKey) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            pruning_archives (first_l2_block, last_l2_block, created_at)
+            VALUES
+            ($1, $2, NOW())
+            ON CONFLICT (last_l2_block) DO NOTHING
+            "#,
+            i64::from(key.first_l2_block.0),
+            i64::from(key.last_l2_block.0)
+        )
+        .instrument("insert_pruning_archive")
+        .with_arg("key", &key)
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Records hashes of archived L2 blocks, so that they can be looked up by hash after pruning.
+    /// Should be called in the same transaction as [`Self::insert_pruning_archive()`].
+    pub async fn insert_pruned_l2_block_hashes(
+        &mut self,
+        l2_blocks: &[(L2BlockNumber, H256)],
+    ) -> DalResult<()> {
+        let (numbers, hashes): (Vec<_>, Vec<_>) = l2_blocks
+            .iter()
+            .map(|(number, hash)| (i64::from(number.0), hash.as_bytes()))
+            .unzip();
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            pruned_l2_block_hashes (hash, number)
+            SELECT
+                u.hash,
+                u.number
+            FROM
+                UNNEST($1::bytea [], $2::bigint []) AS u (hash, number)
+            ON CONFLICT (hash) DO NOTHING
+            "#,
+            &hashes as &[&[u8]],
+            &numbers
+        )
+        .instrument("insert_pruned_l2_block_hashes")
+        .with_arg("l2_blocks.len", &l2_blocks.len())
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the number of an archived L2 block with the specified hash.
+    pub async fn get_pruned_l2_block_number(
+        &mut self,
+        hash: H256,
+    ) -> DalResult<Option<L2BlockNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                number
+            FROM
+                pruned_l2_block_hashes
+            WHERE
+                hash = $1
+            "#,
+            hash.as_bytes()
+        )
+        .instrument("get_pruned_l2_block_number")
+        .with_arg("hash", &hash)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(|row| L2BlockNumber(row.number as u32)))
+    }
+
+    /// Returns keys of archives intersecting with the specified range of L2 blocks, ordered by ascending L2 block.
+    pub async fn get_pruning_archives(
+        &mut self,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<Vec<PrunedL2BlocksArchiveKey>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                first_l2_block,
+                last_l2_block
+            FROM
+                pruning_archives
+            WHERE
+                last_l2_block >= $1
+                AND first_l2_block <= $2
+            ORDER BY
+                last_l2_block
+            "#,
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0)
+        )
+        .instrument("get_pruning_archives")
+        .with_arg("l2_blocks", &l2_blocks)
+        .report_latency()
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| PrunedL2BlocksArchiveKey {
+                first_l2_block: L2BlockNumber(row.first_l2_block as u32),
+                last_l2_block: L2BlockNumber(row.last_l2_block as u32),
+            })
+            .collect())
+    }
 }
@@ -496,3 +496,70 @@ async fn transactions_are_handled_correctly_after_pruning() {
         .unwrap();
     assert!(transaction_details.is_none(), "{transaction_details:?}");
 }
+
+#[tokio::test]
+async fn pruning_archives_can_be_queried() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+
+    let archive_keys: Vec<_> = [0..=4, 5..=9, 10..=19]
+        .into_iter()
+        .map(|range| {
+            PrunedL2BlocksArchiveKey::from(
+                L2BlockNumber(*range.start())..=L2BlockNumber(*range.end()),
+            )
+        })
+        .collect();
+    for &key in &archive_keys {
+        conn.pruning_dal()
+            .insert_pruning_archive(key)
+            .await
+            .unwrap();
+    }
+    // Inserting an archive is idempotent.
+    conn.pruning_dal()
+        .insert_pruning_archive(archive_keys[1])
+        .await
+        .unwrap();
+
+    let archives = conn
+        .pruning_dal()
+        .get_pruning_archives(L2BlockNumber(3)..=L2BlockNumber(3))
+        .await
+        .unwrap();
+    assert_eq!(archives, [archive_keys[0]]);
+    let archives = conn
+        .pruning_dal()
+        .get_pruning_archives(L2BlockNumber(4)..=L2BlockNumber(10))
+        .await
+        .unwrap();
+    assert_eq!(archives, archive_keys);
+    let archives = conn
+        .pruning_dal()
+        .get_pruning_archives(L2BlockNumber(20)..=L2BlockNumber(30))
+        .await
+        .unwrap();
+    assert!(archives.is_empty());
+
+    let block_hashes: Vec<_> = (0..5)
+        .map(|number| (L2BlockNumber(number), H256::repeat_byte(number as u8 + 1)))
+        .collect();
+    conn.pruning_dal()
+        .insert_pruned_l2_block_hashes(&block_hashes)
+        .await
+        .unwrap();
+    for (number, hash) in block_hashes {
+        let resolved_number = conn
+            .pruning_dal()
+            .get_pruned_l2_block_number(hash)
+            .await
+            .unwrap();
+        assert_eq!(resolved_number, Some(number));
+    }
+    let missing_number = conn
+        .pruning_dal()
+        .get_pruned_l2_block_number(H256::zero())
+        .await
+        .unwrap();
+    assert_eq!(missing_number, None);
+}
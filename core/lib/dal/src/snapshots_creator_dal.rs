@@ -109,6 +109,78 @@ impl SnapshotsCreatorDal<'_, '_> {
         Ok(storage_logs)
     }
 
+    /// Constructs a differential `storage_logs` chunk, i.e., returns the state AFTER processing `[0..l1_batch_number]`
+    /// batches for the keys touched in L2 blocks `(base_l2_block_number..=l2_block_number)`. `l2_block_number` MUST
+    /// be the last L2 block of the `l1_batch_number` batch, and `base_l2_block_number` MUST be the last L2 block
+    /// of the base snapshot L1 batch.
+    pub async fn get_changed_storage_logs_chunk(
+        &mut self,
+        base_l2_block_number: L2BlockNumber,
+        l2_block_number: L2BlockNumber,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys_range: std::ops::RangeInclusive<H256>,
+    ) -> DalResult<Vec<SnapshotStorageLog>> {
+        // Filtering by `l1_batch_number` is required for the same reason as in `get_storage_logs_chunk()`.
+        let storage_logs = sqlx::query!(
+            r#"
+            SELECT
+                storage_logs.hashed_key AS "hashed_key!",
+                storage_logs.value AS "value!",
+                storage_logs.miniblock_number AS "miniblock_number!",
+                initial_writes.l1_batch_number AS "l1_batch_number!",
+                initial_writes.index
+            FROM
+                (
+                    SELECT
+                        hashed_key,
+                        MAX(ARRAY[miniblock_number, operation_number]::INT []) AS op
+                    FROM
+                        storage_logs
+                    WHERE
+                        miniblock_number > $1
+                        AND miniblock_number <= $2
+                        AND hashed_key >= $4
+                        AND hashed_key <= $5
+                    GROUP BY
+                        hashed_key
+                    ORDER BY
+                        hashed_key
+                ) AS keys
+            INNER JOIN storage_logs
+                ON
+                    keys.hashed_key = storage_logs.hashed_key
+                    AND storage_logs.miniblock_number = keys.op[1]
+                    AND storage_logs.operation_number = keys.op[2]
+            INNER JOIN initial_writes ON keys.hashed_key = initial_writes.hashed_key
+            WHERE
+                initial_writes.l1_batch_number <= $3
+            "#,
+            i64::from(base_l2_block_number.0),
+            i64::from(l2_block_number.0),
+            i64::from(l1_batch_number.0),
+            hashed_keys_range.start().as_bytes(),
+            hashed_keys_range.end().as_bytes()
+        )
+        .instrument("get_changed_storage_logs_chunk")
+        .with_arg("base_l2_block_number", &base_l2_block_number)
+        .with_arg("l2_block_number", &l2_block_number)
+        .with_arg("min_hashed_key", &hashed_keys_range.start())
+        .with_arg("max_hashed_key", &hashed_keys_range.end())
+        .report_latency()
+        .expect_slow_query()
+        .fetch_all(self.storage)
+        .await?
+        .iter()
+        .map(|row| SnapshotStorageLog {
+            key: H256::from_slice(&row.hashed_key),
+            value: H256::from_slice(&row.value),
+            l1_batch_number_of_initial_write: L1BatchNumber(row.l1_batch_number as u32),
+            enumeration_index: row.index as u64,
+        })
+        .collect();
+        Ok(storage_logs)
+    }
+
     /// Same as [`Self::get_storage_logs_chunk()`], but returns full keys.
     #[deprecated(
         note = "will fail if called on a node restored from a v1 snapshot; use `get_storage_logs_chunk()` instead"
@@ -208,6 +280,39 @@ impl SnapshotsCreatorDal<'_, '_> {
             .map(|row| (H256::from_slice(&row.bytecode_hash), row.bytecode))
             .collect())
     }
+
+    /// Returns factory dependencies added in L2 blocks `(base_l2_block_number..=l2_block_number)`.
+    pub async fn get_factory_deps_since(
+        &mut self,
+        base_l2_block_number: L2BlockNumber,
+        l2_block_number: L2BlockNumber,
+    ) -> DalResult<Vec<(H256, Vec<u8>)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                bytecode_hash,
+                bytecode
+            FROM
+                factory_deps
+            WHERE
+                miniblock_number > $1
+                AND miniblock_number <= $2
+            "#,
+            i64::from(base_l2_block_number.0),
+            i64::from(l2_block_number.0),
+        )
+        .instrument("get_factory_deps_since")
+        .with_arg("base_l2_block_number", &base_l2_block_number)
+        .with_arg("l2_block_number", &l2_block_number)
+        .report_latency()
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (H256::from_slice(&row.bytecode_hash), row.bytecode))
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -330,6 +435,85 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn getting_changed_storage_logs() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+
+        let logs: Vec<_> = (0..20)
+            .map(|i| {
+                let key = StorageKey::new(AccountTreeId::default(), H256::from_low_u64_be(i));
+                StorageLog::new_write_log(key, H256::repeat_byte(1))
+            })
+            .collect();
+        conn.storage_logs_dal()
+            .insert_storage_logs(L2BlockNumber(1), &logs)
+            .await
+            .unwrap();
+        let written_keys: Vec<_> = logs.iter().map(|log| log.key.hashed_key()).collect();
+        conn.storage_logs_dedup_dal()
+            .insert_initial_writes(L1BatchNumber(1), &written_keys)
+            .await
+            .unwrap();
+
+        let new_log = StorageLog::new_write_log(
+            StorageKey::new(AccountTreeId::default(), H256::from_low_u64_be(100)),
+            H256::repeat_byte(2),
+        );
+        let updated_logs = logs.iter().step_by(4).map(|&log| StorageLog {
+            value: H256::repeat_byte(3),
+            ..log
+        });
+        let all_new_logs: Vec<_> = updated_logs.chain([new_log]).collect();
+        conn.storage_logs_dal()
+            .insert_storage_logs(L2BlockNumber(2), &all_new_logs)
+            .await
+            .unwrap();
+        conn.storage_logs_dedup_dal()
+            .insert_initial_writes(L1BatchNumber(2), &[new_log.key.hashed_key()])
+            .await
+            .unwrap();
+
+        let mut changed_logs = conn
+            .snapshots_creator_dal()
+            .get_changed_storage_logs_chunk(
+                L2BlockNumber(1),
+                L2BlockNumber(2),
+                L1BatchNumber(2),
+                H256::zero()..=H256::repeat_byte(0xff),
+            )
+            .await
+            .unwrap();
+        changed_logs.sort_unstable_by_key(|log| log.key);
+        let mut expected_logs = all_new_logs;
+        expected_logs.sort_unstable_by_key(|log| log.key.hashed_key());
+        assert_eq!(changed_logs.len(), expected_logs.len());
+        for (log, expected_log) in changed_logs.iter().zip(&expected_logs) {
+            assert_eq!(log.key, expected_log.key.hashed_key());
+            assert_eq!(log.value, expected_log.value);
+        }
+        let new_log_in_snapshot = changed_logs
+            .iter()
+            .find(|log| log.key == new_log.key.hashed_key())
+            .unwrap();
+        assert_eq!(
+            new_log_in_snapshot.l1_batch_number_of_initial_write,
+            L1BatchNumber(2)
+        );
+
+        let changed_logs = conn
+            .snapshots_creator_dal()
+            .get_changed_storage_logs_chunk(
+                L2BlockNumber(2),
+                L2BlockNumber(2),
+                L1BatchNumber(2),
+                H256::zero()..=H256::repeat_byte(0xff),
+            )
+            .await
+            .unwrap();
+        assert_eq!(changed_logs, []);
+    }
+
     #[tokio::test]
     async fn phantom_writes_are_filtered_out() {
         let pool = ConnectionPool::<Core>::test_pool().await;
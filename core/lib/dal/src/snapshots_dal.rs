@@ -4,7 +4,7 @@ use zksync_db_connection::{
     instrument::InstrumentExt,
 };
 use zksync_types::{
-    snapshots::{AllSnapshots, SnapshotMetadata, SnapshotVersion},
+    snapshots::{AllSnapshots, DifferentialSnapshotInfo, SnapshotMetadata, SnapshotVersion},
     L1BatchNumber,
 };
 
@@ -14,6 +14,7 @@ use crate::Core;
 struct StorageSnapshotMetadata {
     version: i32,
     l1_batch_number: i64,
+    base_l1_batch_number: Option<i64>,
     storage_logs_filepaths: Vec<String>,
     factory_deps_filepath: String,
 }
@@ -28,6 +29,9 @@ impl TryFrom<StorageSnapshotMetadata> for SnapshotMetadata {
         Ok(Self {
             version,
             l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+            base_l1_batch_number: row
+                .base_l1_batch_number
+                .map(|number| L1BatchNumber(number as u32)),
             storage_logs_filepaths: row
                 .storage_logs_filepaths
                 .into_iter()
@@ -44,10 +48,13 @@ pub struct SnapshotsDal<'a, 'c> {
 }
 
 impl SnapshotsDal<'_, '_> {
+    /// Adds a new snapshot. If `base_l1_batch_number` is specified, the snapshot is differential
+    /// and is based on the snapshot for the specified L1 batch.
     pub async fn add_snapshot(
         &mut self,
         version: SnapshotVersion,
         l1_batch_number: L1BatchNumber,
+        base_l1_batch_number: Option<L1BatchNumber>,
         storage_logs_chunk_count: u64,
         factory_deps_filepaths: &str,
     ) -> DalResult<()> {
@@ -57,22 +64,25 @@ impl SnapshotsDal<'_, '_> {
             snapshots (
                 version,
                 l1_batch_number,
+                base_l1_batch_number,
                 storage_logs_filepaths,
                 factory_deps_filepath,
                 created_at,
                 updated_at
             )
             VALUES
-            ($1, $2, ARRAY_FILL(''::TEXT, ARRAY[$3::INTEGER]), $4, NOW(), NOW())
+            ($1, $2, $3, ARRAY_FILL(''::TEXT, ARRAY[$4::INTEGER]), $5, NOW(), NOW())
             "#,
             version as i32,
             l1_batch_number.0 as i32,
+            base_l1_batch_number.map(|number| i64::from(number.0)),
             storage_logs_chunk_count as i32,
             factory_deps_filepaths,
         )
         .instrument("add_snapshot")
         .with_arg("version", &version)
         .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("base_l1_batch_number", &base_l1_batch_number)
         .report_latency()
         .execute(self.storage)
         .await?;
@@ -111,7 +121,8 @@ impl SnapshotsDal<'_, '_> {
         let rows = sqlx::query!(
             r#"
             SELECT
-                l1_batch_number
+                l1_batch_number,
+                base_l1_batch_number
             FROM
                 snapshots
             WHERE
@@ -125,13 +136,23 @@ impl SnapshotsDal<'_, '_> {
         .fetch_all(self.storage)
         .await?;
 
-        let snapshots_l1_batch_numbers = rows
-            .into_iter()
-            .map(|row| L1BatchNumber(row.l1_batch_number as u32))
-            .collect();
+        let mut snapshots_l1_batch_numbers = vec![];
+        let mut differential_snapshots = vec![];
+        for row in rows {
+            let l1_batch_number = L1BatchNumber(row.l1_batch_number as u32);
+            if let Some(base_l1_batch_number) = row.base_l1_batch_number {
+                differential_snapshots.push(DifferentialSnapshotInfo {
+                    l1_batch_number,
+                    base_l1_batch_number: L1BatchNumber(base_l1_batch_number as u32),
+                });
+            } else {
+                snapshots_l1_batch_numbers.push(l1_batch_number);
+            }
+        }
 
         Ok(AllSnapshots {
             snapshots_l1_batch_numbers,
+            differential_snapshots,
         })
     }
 
@@ -142,6 +163,7 @@ impl SnapshotsDal<'_, '_> {
             SELECT
                 VERSION,
                 L1_BATCH_NUMBER,
+                BASE_L1_BATCH_NUMBER,
                 FACTORY_DEPS_FILEPATH,
                 STORAGE_LOGS_FILEPATHS
             FROM
@@ -169,6 +191,7 @@ impl SnapshotsDal<'_, '_> {
             SELECT
                 VERSION,
                 L1_BATCH_NUMBER,
+                BASE_L1_BATCH_NUMBER,
                 FACTORY_DEPS_FILEPATH,
                 STORAGE_LOGS_FILEPATHS
             FROM
@@ -200,6 +223,7 @@ impl SnapshotsDal<'_, '_> {
             RETURNING
             version,
             l1_batch_number,
+            base_l1_batch_number,
             factory_deps_filepath,
             storage_logs_filepaths
             "#,
@@ -231,6 +255,7 @@ mod tests {
         dal.add_snapshot(
             SnapshotVersion::Version0,
             l1_batch_number,
+            None,
             2,
             "gs:///bucket/factory_deps.bin",
         )
@@ -276,6 +301,7 @@ mod tests {
         dal.add_snapshot(
             SnapshotVersion::Version0,
             l1_batch_number,
+            None,
             2,
             "gs:///bucket/factory_deps.bin",
         )
@@ -339,6 +365,7 @@ mod tests {
         dal.add_snapshot(
             SnapshotVersion::Version0,
             l1_batch_number,
+            None,
             2,
             "gs:///bucket/factory_deps.bin",
         )
@@ -209,6 +209,49 @@ impl StorageLogsDal<'_, '_> {
             .collect())
     }
 
+    /// Returns all storage logs in the specified L2 block range, ordered by the L2 block and operation number.
+    pub async fn get_storage_logs_in_l2_blocks(
+        &mut self,
+        l2_block_numbers: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<Vec<DbStorageLog>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                hashed_key,
+                address,
+                key,
+                value,
+                operation_number,
+                miniblock_number
+            FROM
+                storage_logs
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+            ORDER BY
+                miniblock_number,
+                operation_number
+            "#,
+            i64::from(l2_block_numbers.start().0),
+            i64::from(l2_block_numbers.end().0)
+        )
+        .instrument("get_storage_logs_in_l2_blocks")
+        .with_arg("l2_block_numbers", &l2_block_numbers)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| DbStorageLog {
+                hashed_key: H256::from_slice(&row.hashed_key),
+                address: row.address.as_deref().map(H160::from_slice),
+                key: row.key.as_deref().map(H256::from_slice),
+                value: H256::from_slice(&row.value),
+                operation_number: row.operation_number as u64,
+                l2_block_number: L2BlockNumber(row.miniblock_number as u32),
+            })
+            .collect())
+    }
+
     /// Removes all storage logs with a L2 block number strictly greater than the specified `block_number`.
     pub async fn roll_back_storage_logs(&mut self, block_number: L2BlockNumber) -> DalResult<()> {
         sqlx::query!(
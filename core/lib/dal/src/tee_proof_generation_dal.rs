@@ -10,7 +10,11 @@ use zksync_db_connection::{
     interpolate_query, match_query_as,
     utils::pg_interval_from_duration,
 };
-use zksync_types::{tee_types::TeeType, L1BatchNumber};
+use zksync_types::{
+    api::{TeeAttestationStatus, TeeBatchAttestation},
+    tee_types::TeeType,
+    L1BatchNumber,
+};
 
 use crate::{
     models::storage_tee_proof::{StorageLockedBatch, StorageTeeProof},
@@ -268,6 +272,83 @@ impl TeeProofGenerationDal<'_, '_> {
         Ok(())
     }
 
+    /// Saves an attestation that has been verified against the attestation policy. Unlike
+    /// [`Self::save_attestation()`], overwrites an existing attestation for the same key, so that
+    /// re-registering a key with fresh collateral extends its validity.
+    pub async fn save_verified_attestation(
+        &mut self,
+        pubkey: &[u8],
+        attestation: &[u8],
+        tee_type: TeeType,
+        tcb_status: &str,
+        expires_at: DateTime<Utc>,
+    ) -> DalResult<()> {
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO
+            tee_attestations (pubkey, attestation, tee_type, tcb_status, verified_at, expires_at)
+            VALUES
+            ($1, $2, $3, $4, NOW(), $5)
+            ON CONFLICT (pubkey) DO
+            UPDATE
+            SET
+            attestation = excluded.attestation,
+            tee_type = excluded.tee_type,
+            tcb_status = excluded.tcb_status,
+            verified_at = excluded.verified_at,
+            expires_at = excluded.expires_at
+            "#,
+            pubkey,
+            attestation,
+            tee_type.to_string(),
+            tcb_status,
+            expires_at.naive_utc(),
+        );
+        let instrumentation = Instrumented::new("save_verified_attestation")
+            .with_arg("pubkey", &pubkey)
+            .with_arg("tee_type", &tee_type)
+            .with_arg("tcb_status", &tcb_status)
+            .with_arg("expires_at", &expires_at);
+        instrumentation
+            .clone()
+            .with(query)
+            .execute(self.storage)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns the expiration time of a verified attestation for the specified key and TEE type,
+    /// or `None` if there is no such attestation.
+    pub async fn get_attestation_expiration(
+        &mut self,
+        pubkey: &[u8],
+        tee_type: TeeType,
+    ) -> DalResult<Option<DateTime<Utc>>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                expires_at AS "expires_at!"
+            FROM
+                tee_attestations
+            WHERE
+                pubkey = $1
+                AND tee_type = $2
+                AND verified_at IS NOT NULL
+                AND expires_at IS NOT NULL
+            "#,
+            pubkey,
+            tee_type.to_string(),
+        )
+        .instrument("get_attestation_expiration")
+        .with_arg("pubkey", &pubkey)
+        .with_arg("tee_type", &tee_type)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| DateTime::<Utc>::from_naive_utc_and_offset(row.expires_at, Utc)))
+    }
+
     pub async fn get_tee_proofs(
         &mut self,
         batch_number: L1BatchNumber,
@@ -310,6 +391,186 @@ impl TeeProofGenerationDal<'_, '_> {
         Ok(proofs)
     }
 
+    /// Records a signature of the batch root hash made by a TEE key. Unlike proof artifacts saved by
+    /// [`Self::save_proof_artifacts_metadata()`], signatures are kept for every key, so that they can be aggregated
+    /// by [`Self::mark_attested_batches()`].
+    pub async fn save_proof_signature(
+        &mut self,
+        batch_number: L1BatchNumber,
+        tee_type: TeeType,
+        pubkey: &[u8],
+        signature: &[u8],
+        root_hash: &[u8],
+    ) -> DalResult<()> {
+        let batch_number = i64::from(batch_number.0);
+        let query = sqlx::query!(
+            r#"
+            INSERT INTO
+            tee_proof_signatures (
+                l1_batch_number, pubkey, tee_type, root_hash, signature, created_at
+            )
+            VALUES
+            ($1, $2, $3, $4, $5, NOW())
+            ON CONFLICT (l1_batch_number, pubkey) DO
+            UPDATE
+            SET
+            tee_type = excluded.tee_type,
+            root_hash = excluded.root_hash,
+            signature = excluded.signature,
+            created_at = excluded.created_at
+            "#,
+            batch_number,
+            pubkey,
+            tee_type.to_string(),
+            root_hash,
+            signature,
+        );
+        let instrumentation = Instrumented::new("save_proof_signature")
+            .with_arg("l1_batch_number", &batch_number)
+            .with_arg("tee_type", &tee_type)
+            .with_arg("pubkey", &pubkey);
+        instrumentation
+            .clone()
+            .with(query)
+            .execute(self.storage)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Marks batches as TEE-attested if their root hash is signed by at least `min_signers` distinct keys
+    /// of at least `min_tee_types` distinct TEE types. If `require_verified_attestations` is set, only signatures
+    /// made while the signing key had a valid verified attestation are counted.
+    ///
+    /// Returns numbers of the newly attested batches together with the number of signers.
+    pub async fn mark_attested_batches(
+        &mut self,
+        min_signers: u32,
+        min_tee_types: u32,
+        require_verified_attestations: bool,
+    ) -> DalResult<Vec<(L1BatchNumber, u32)>> {
+        let rows = sqlx::query!(
+            r#"
+            INSERT INTO
+            tee_batch_attestations (l1_batch_number, root_hash, signers, tee_types, attested_at)
+            SELECT
+                signatures.l1_batch_number,
+                l1_batches.hash,
+                COUNT(DISTINCT signatures.pubkey),
+                ARRAY_AGG(DISTINCT signatures.tee_type ORDER BY signatures.tee_type),
+                NOW()
+            FROM
+                tee_proof_signatures AS signatures
+            INNER JOIN l1_batches
+                ON
+                    signatures.l1_batch_number = l1_batches.number
+                    AND signatures.root_hash = l1_batches.hash
+            INNER JOIN tee_attestations AS attestations
+                ON signatures.pubkey = attestations.pubkey
+            LEFT JOIN tee_batch_attestations AS batch_attestations
+                ON signatures.l1_batch_number = batch_attestations.l1_batch_number
+            WHERE
+                batch_attestations.l1_batch_number IS NULL
+                AND (
+                    NOT $3
+                    OR (
+                        attestations.verified_at IS NOT NULL
+                        AND attestations.tee_type = signatures.tee_type
+                        AND attestations.expires_at > signatures.created_at
+                    )
+                )
+            GROUP BY
+                signatures.l1_batch_number,
+                l1_batches.hash
+            HAVING
+                COUNT(DISTINCT signatures.pubkey) >= $1
+                AND COUNT(DISTINCT signatures.tee_type) >= $2
+            ON CONFLICT (l1_batch_number) DO NOTHING
+            RETURNING
+            l1_batch_number,
+            signers
+            "#,
+            i64::from(min_signers),
+            i64::from(min_tee_types),
+            require_verified_attestations
+        )
+        .instrument("mark_attested_batches")
+        .with_arg("min_signers", &min_signers)
+        .with_arg("min_tee_types", &min_tee_types)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                (
+                    L1BatchNumber(row.l1_batch_number as u32),
+                    row.signers as u32,
+                )
+            })
+            .collect())
+    }
+
+    /// Returns the TEE attestation status for the specified batch, or `None` if no TEE proofs were submitted for it.
+    pub async fn get_batch_attestation(
+        &mut self,
+        batch_number: L1BatchNumber,
+    ) -> DalResult<Option<TeeBatchAttestation>> {
+        let attested = sqlx::query!(
+            r#"
+            SELECT
+                signers,
+                tee_types,
+                attested_at
+            FROM
+                tee_batch_attestations
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(batch_number.0)
+        )
+        .instrument("get_batch_attestation")
+        .with_arg("l1_batch_number", &batch_number)
+        .fetch_optional(self.storage)
+        .await?;
+
+        if let Some(row) = attested {
+            return Ok(Some(TeeBatchAttestation {
+                status: TeeAttestationStatus::Attested,
+                signers: row.signers as u32,
+                tee_types: parse_tee_types(&row.tee_types),
+                attested_at: Some(DateTime::<Utc>::from_naive_utc_and_offset(
+                    row.attested_at,
+                    Utc,
+                )),
+            }));
+        }
+
+        let pending = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(DISTINCT pubkey) AS "signers!",
+                ARRAY_AGG(DISTINCT tee_type ORDER BY tee_type) AS "tee_types?"
+            FROM
+                tee_proof_signatures
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(batch_number.0)
+        )
+        .instrument("get_batch_attestation#pending")
+        .with_arg("l1_batch_number", &batch_number)
+        .fetch_one(self.storage)
+        .await?;
+
+        Ok((pending.signers > 0).then(|| TeeBatchAttestation {
+            status: TeeAttestationStatus::Pending,
+            signers: pending.signers as u32,
+            tee_types: parse_tee_types(&pending.tee_types.unwrap_or_default()),
+            attested_at: None,
+        }))
+    }
+
     /// For testing purposes only.
     pub async fn insert_tee_proof_generation_job(
         &mut self,
@@ -369,3 +630,118 @@ impl TeeProofGenerationDal<'_, '_> {
         Ok(batch_number)
     }
 }
+
+fn parse_tee_types(tee_types: &[String]) -> Vec<TeeType> {
+    tee_types
+        .iter()
+        .filter_map(|tee_type| tee_type.parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{block::L1BatchTreeData, ProtocolVersion, H256};
+
+    use super::*;
+    use crate::{tests::create_l1_batch_header, ConnectionPool, CoreDal};
+
+    async fn prepare_batch(conn: &mut Connection<'_, Core>, number: u32, root_hash: H256) {
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&create_l1_batch_header(number))
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .save_l1_batch_tree_data(
+                L1BatchNumber(number),
+                &L1BatchTreeData {
+                    hash: root_hash,
+                    rollup_last_leaf_index: 1,
+                },
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn batch_attestation_quorum() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        let root_hash = H256::repeat_byte(1);
+        prepare_batch(&mut conn, 1, root_hash).await;
+
+        let mut dal = conn.tee_proof_generation_dal();
+        let keys = [
+            (b"sgx_0", TeeType::Sgx),
+            (b"sgx_1", TeeType::Sgx),
+            (b"tdx_0", TeeType::Tdx),
+        ];
+        for (pubkey, _) in &keys {
+            dal.save_attestation(*pubkey, b"quote").await.unwrap();
+        }
+        assert_eq!(
+            dal.get_batch_attestation(L1BatchNumber(1)).await.unwrap(),
+            None
+        );
+
+        for (pubkey, tee_type) in &keys[..2] {
+            dal.save_proof_signature(
+                L1BatchNumber(1),
+                *tee_type,
+                *pubkey,
+                b"sig",
+                root_hash.as_bytes(),
+            )
+            .await
+            .unwrap();
+        }
+        // A signature over a wrong root hash must not count.
+        let (pubkey, tee_type) = keys[2];
+        dal.save_proof_signature(L1BatchNumber(1), tee_type, pubkey, b"sig", &[0; 32])
+            .await
+            .unwrap();
+
+        let attested = dal.mark_attested_batches(2, 2, false).await.unwrap();
+        assert!(attested.is_empty());
+        let status = dal
+            .get_batch_attestation(L1BatchNumber(1))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(status.status, TeeAttestationStatus::Pending);
+        assert_eq!(status.signers, 3);
+        assert_eq!(status.tee_types, [TeeType::Sgx, TeeType::Tdx]);
+
+        // Unverified attestations are not counted if verification is required.
+        dal.save_proof_signature(
+            L1BatchNumber(1),
+            tee_type,
+            pubkey,
+            b"sig",
+            root_hash.as_bytes(),
+        )
+        .await
+        .unwrap();
+        let attested = dal.mark_attested_batches(2, 2, true).await.unwrap();
+        assert!(attested.is_empty());
+
+        let attested = dal.mark_attested_batches(3, 2, false).await.unwrap();
+        assert_eq!(attested, [(L1BatchNumber(1), 3)]);
+        let status = dal
+            .get_batch_attestation(L1BatchNumber(1))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(status.status, TeeAttestationStatus::Attested);
+        assert_eq!(status.signers, 3);
+        assert_eq!(status.tee_types, [TeeType::Sgx, TeeType::Tdx]);
+        assert!(status.attested_at.is_some());
+
+        // Attested batches are not returned again.
+        let attested = dal.mark_attested_batches(1, 1, false).await.unwrap();
+        assert!(attested.is_empty());
+    }
+}
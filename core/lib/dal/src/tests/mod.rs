@@ -10,7 +10,7 @@ use zksync_types::{
     fee_model::BatchFeeInput,
     helpers::unix_timestamp_ms,
     l1::{L1Tx, OpProcessingType, PriorityQueueType},
-    l2::L2Tx,
+    l2::{bundle::L2TxBundle, L2Tx},
     l2_to_l1_log::{L2ToL1Log, UserL2ToL1Log},
     protocol_upgrade::{ProtocolUpgradeTx, ProtocolUpgradeTxCommonData},
     snapshots::SnapshotRecoveryStatus,
@@ -27,7 +27,7 @@ use crate::{
     protocol_versions_dal::ProtocolVersionsDal,
     transactions_dal::{L2TxSubmissionResult, TransactionsDal},
     transactions_web3_dal::TransactionsWeb3Dal,
-    Connection, Core,
+    Connection, Core, CoreDal,
 };
 
 const DEFAULT_GAS_PER_PUBDATA: u32 = 100;
@@ -407,3 +407,56 @@ async fn remove_stuck_txs() {
 
     assert_eq!(receipts.len(), 1);
 }
+
+#[tokio::test]
+async fn remove_stuck_bundles() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = connection_pool.connection().await.unwrap();
+    storage
+        .protocol_versions_dal()
+        .save_protocol_version_with_tx(&ProtocolVersion::default())
+        .await
+        .unwrap();
+
+    let stuck_bundle = L2TxBundle::new(vec![mock_l2_transaction(), mock_l2_transaction()]);
+    let members = vec![Default::default(); stuck_bundle.len()];
+    storage
+        .transactions_dal()
+        .insert_transaction_bundle(&stuck_bundle, members)
+        .await
+        .unwrap();
+    // Only one of bundle transactions is stuck, but the bundle must be removed as a whole.
+    let old_timestamp_ms = unix_timestamp_ms() - 1_000_000;
+    force_transaction_timestamp(&mut storage, stuck_bundle.txs()[0].hash(), old_timestamp_ms).await;
+
+    let bundle = L2TxBundle::new(vec![mock_l2_transaction(), mock_l2_transaction()]);
+    let members = vec![Default::default(); bundle.len()];
+    storage
+        .transactions_dal()
+        .insert_transaction_bundle(&bundle, members)
+        .await
+        .unwrap();
+
+    let removed_txs = storage
+        .transactions_dal()
+        .remove_stuck_txs(Duration::from_secs(500))
+        .await
+        .unwrap();
+    assert_eq!(removed_txs, 2);
+    for tx in stuck_bundle.txs() {
+        let stored_tx = storage
+            .transactions_dal()
+            .get_storage_tx_by_hash(tx.hash())
+            .await
+            .unwrap();
+        assert!(stored_tx.is_none());
+    }
+
+    let bundles = storage
+        .transactions_dal()
+        .sync_mempool_bundles(&[], 0, 0, 100)
+        .await
+        .unwrap();
+    assert_eq!(bundles.len(), 1);
+    assert_eq!(bundles[0].0.hash(), bundle.hash());
+}
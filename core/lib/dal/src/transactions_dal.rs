@@ -10,10 +10,14 @@ use zksync_db_connection::{
     utils::pg_interval_from_duration,
 };
 use zksync_types::{
-    block::L2BlockExecutionData, debug_flat_call::CallTraceMeta, l1::L1Tx, l2::L2Tx,
-    protocol_upgrade::ProtocolUpgradeTx, Address, ExecuteTransactionCommon, L1BatchNumber,
-    L1BlockNumber, L2BlockNumber, PriorityOpId, ProtocolVersionId, Transaction,
-    TransactionTimeRangeConstraint, H256, PROTOCOL_UPGRADE_TX_TYPE, U256,
+    block::L2BlockExecutionData,
+    debug_flat_call::CallTraceMeta,
+    l1::L1Tx,
+    l2::{bundle::L2TxBundle, L2Tx},
+    protocol_upgrade::ProtocolUpgradeTx,
+    Address, ExecuteTransactionCommon, L1BatchNumber, L1BlockNumber, L2BlockNumber, PriorityOpId,
+    ProtocolVersionId, Transaction, TransactionTimeRangeConstraint, H256, PROTOCOL_UPGRADE_TX_TYPE,
+    U256,
 };
 use zksync_vm_interface::{
     tracer::ValidationTraces, Call, TransactionExecutionMetrics, TransactionExecutionResult,
@@ -36,6 +40,9 @@ pub enum L2TxSubmissionResult {
     Duplicate,
     Proxied,
     InsertionInProgress,
+    /// Transaction conflicts with a pending transaction with the same initiator and nonce, and wasn't inserted.
+    /// Only returned for transaction bundles, which never replace pending transactions.
+    Conflict,
 }
 
 impl fmt::Display for L2TxSubmissionResult {
@@ -47,6 +54,7 @@ impl fmt::Display for L2TxSubmissionResult {
             Self::Duplicate => "duplicate",
             Self::Proxied => "proxied",
             Self::InsertionInProgress => "insertion_in_progress",
+            Self::Conflict => "conflict",
         })
     }
 }
@@ -527,6 +535,74 @@ impl TransactionsDal<'_, '_> {
         Ok(l2_tx_insertion_result)
     }
 
+    /// Inserts an atomic bundle of L2 transactions. `members` must contain execution info for each bundle transaction
+    /// in the bundle order. Either all transactions are inserted, or none are; in the latter case, the returned result
+    /// is the one for the first transaction that couldn't be inserted.
+    ///
+    /// Unlike with [`Self::insert_transaction_l2()`], bundle transactions never replace pending mempool transactions
+    /// with the same initiator and nonce. If any bundle transaction conflicts with a pending transaction, the bundle
+    /// is not inserted, and [`L2TxSubmissionResult::Conflict`] is returned.
+    pub async fn insert_transaction_bundle(
+        &mut self,
+        bundle: &L2TxBundle,
+        members: Vec<(TransactionExecutionMetrics, ValidationTraces)>,
+    ) -> DalResult<L2TxSubmissionResult> {
+        assert_eq!(
+            bundle.len(),
+            members.len(),
+            "execution info must be provided for each bundle transaction"
+        );
+
+        let mut transaction = self.storage.start_transaction().await?;
+        for (tx, (exec_info, validation_traces)) in bundle.txs().iter().zip(members) {
+            let result = transaction
+                .transactions_dal()
+                .insert_transaction_l2(tx, exec_info, validation_traces)
+                .await?;
+            match result {
+                L2TxSubmissionResult::Added => {}
+                // The DB transaction is rolled back on drop, which also reverts replacement of a pending transaction.
+                L2TxSubmissionResult::Replaced => return Ok(L2TxSubmissionResult::Conflict),
+                _ => return Ok(result),
+            }
+        }
+
+        let bundle_hash = bundle.hash();
+        let tx_hashes: Vec<_> = bundle.txs().iter().map(|tx| tx.hash()).collect();
+        let tx_hashes: Vec<_> = tx_hashes.iter().map(H256::as_bytes).collect();
+        let indices: Vec<_> = (0..bundle.len() as i32).collect();
+        sqlx::query!(
+            r#"
+            INSERT INTO
+            transaction_bundles (tx_hash, bundle_hash, index_in_bundle, created_at)
+            SELECT
+                u.tx_hash,
+                $2,
+                u.index_in_bundle,
+                NOW()
+            FROM
+                UNNEST($1::bytea [], $3::INT []) AS u (tx_hash, index_in_bundle)
+            ON CONFLICT (tx_hash) DO
+            UPDATE
+            SET
+            bundle_hash = excluded.bundle_hash,
+            index_in_bundle = excluded.index_in_bundle,
+            created_at = excluded.created_at
+            "#,
+            &tx_hashes as &[&[u8]],
+            bundle_hash.as_bytes(),
+            &indices
+        )
+        .instrument("insert_transaction_bundle")
+        .with_arg("bundle_hash", &bundle_hash)
+        .with_arg("bundle.len", &bundle.len())
+        .execute(&mut transaction)
+        .await?;
+
+        transaction.commit().await?;
+        Ok(L2TxSubmissionResult::Added)
+    }
+
     pub async fn mark_txs_as_executed_in_l1_batch(
         &mut self,
         l1_batch_number: L1BatchNumber,
@@ -1716,8 +1792,45 @@ impl TransactionsDal<'_, '_> {
         Ok(())
     }
 
+    /// Removes pending transactions received more than `stuck_tx_timeout` ago. Transaction bundles are removed
+    /// as a whole if any of their transactions is stuck.
     pub async fn remove_stuck_txs(&mut self, stuck_tx_timeout: Duration) -> DalResult<usize> {
         let stuck_tx_timeout = pg_interval_from_duration(stuck_tx_timeout);
+        let bundle_rows = sqlx::query!(
+            r#"
+            DELETE FROM transactions
+            WHERE
+                miniblock_number IS NULL
+                AND hash IN (
+                    SELECT
+                        members.tx_hash
+                    FROM
+                        transaction_bundles AS members
+                    WHERE
+                        members.bundle_hash IN (
+                            SELECT
+                                transaction_bundles.bundle_hash
+                            FROM
+                                transaction_bundles
+                            INNER JOIN
+                                transactions AS stuck
+                                ON stuck.hash = transaction_bundles.tx_hash
+                            WHERE
+                                stuck.miniblock_number IS NULL
+                                AND stuck.received_at < NOW() - $1::INTERVAL
+                                AND stuck.error IS NULL
+                        )
+                )
+            RETURNING
+            hash
+            "#,
+            stuck_tx_timeout
+        )
+        .instrument("remove_stuck_txs#delete_bundles")
+        .with_arg("stuck_tx_timeout", &stuck_tx_timeout)
+        .fetch_all(self.storage)
+        .await?;
+
         let rows = sqlx::query!(
             r#"
             DELETE FROM transactions
@@ -1736,7 +1849,29 @@ impl TransactionsDal<'_, '_> {
         .fetch_all(self.storage)
         .await?;
 
-        Ok(rows.len())
+        // Remove bundle membership for transactions that no longer exist (e.g., were removed above or replaced).
+        sqlx::query!(
+            r#"
+            DELETE FROM transaction_bundles
+            WHERE
+                created_at < NOW() - $1::INTERVAL
+                AND NOT EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        transactions
+                    WHERE
+                        transactions.hash = transaction_bundles.tx_hash
+                )
+            "#,
+            stuck_tx_timeout
+        )
+        .instrument("remove_stuck_txs#delete_orphaned_bundles")
+        .with_arg("stuck_tx_timeout", &stuck_tx_timeout)
+        .execute(self.storage)
+        .await?;
+
+        Ok(bundle_rows.len() + rows.len())
     }
 
     pub async fn get_priority_txs_in_mempool(&mut self) -> DalResult<usize> {
@@ -1807,6 +1942,14 @@ impl TransactionsDal<'_, '_> {
             WHERE
                 transactions.in_mempool = TRUE
                 AND transactions.initiator_address = s.address
+                AND NOT EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        transaction_bundles
+                    WHERE
+                        transaction_bundles.tx_hash = transactions.hash
+                )
             "#,
             &stashed_addresses as &[&[u8]],
         )
@@ -1831,6 +1974,14 @@ impl TransactionsDal<'_, '_> {
             WHERE
                 in_mempool = TRUE
                 AND initiator_address = ANY($1)
+                AND NOT EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        transaction_bundles
+                    WHERE
+                        transaction_bundles.tx_hash = transactions.hash
+                )
             "#,
             &purged_addresses as &[&[u8]]
         )
@@ -1879,6 +2030,14 @@ impl TransactionsDal<'_, '_> {
                                     )
                                 )
                                 AND tx_format != $4
+                                AND NOT EXISTS (
+                                    SELECT
+                                        1
+                                    FROM
+                                        transaction_bundles
+                                    WHERE
+                                        transaction_bundles.tx_hash = transactions.hash
+                                )
                             ORDER BY
                                 is_priority DESC,
                                 priority_op_id,
@@ -1918,6 +2077,193 @@ impl TransactionsDal<'_, '_> {
         Ok(transactions_with_constraints)
     }
 
+    /// Fetches complete transaction bundles for mempool, marking their transactions as being in mempool. A bundle is returned
+    /// only if all its transactions are persisted, not yet executed or rejected, and satisfy the fee requirements.
+    /// Bundles are returned in the order they were received, with their transactions in the execution order.
+    ///
+    /// Bundles purged from the mempool (e.g., because of its capacity) are removed from the storage as a whole.
+    pub async fn sync_mempool_bundles(
+        &mut self,
+        purged_bundles: &[H256],
+        gas_per_pubdata: u32,
+        fee_per_gas: u64,
+        limit: usize,
+    ) -> DalResult<Vec<(L2TxBundle, Vec<TransactionTimeRangeConstraint>)>> {
+        let mut transaction = self.storage.start_transaction().await?;
+        if !purged_bundles.is_empty() {
+            let purged_hashes: Vec<_> = purged_bundles.iter().map(H256::as_bytes).collect();
+            let result = sqlx::query!(
+                r#"
+                DELETE FROM transactions
+                WHERE
+                    miniblock_number IS NULL
+                    AND hash IN (
+                        SELECT
+                            tx_hash
+                        FROM
+                            transaction_bundles
+                        WHERE
+                            bundle_hash = ANY($1)
+                    )
+                "#,
+                &purged_hashes as &[&[u8]]
+            )
+            .instrument("sync_mempool_bundles#delete_purged_txs")
+            .with_arg("purged_bundles.len", &purged_hashes.len())
+            .execute(&mut transaction)
+            .await?;
+            tracing::trace!(
+                "Deleted {} transactions for {} purged bundles",
+                result.rows_affected(),
+                purged_hashes.len()
+            );
+
+            sqlx::query!(
+                r#"
+                DELETE FROM transaction_bundles
+                WHERE
+                    bundle_hash = ANY($1)
+                "#,
+                &purged_hashes as &[&[u8]]
+            )
+            .instrument("sync_mempool_bundles#delete_purged")
+            .with_arg("purged_bundles.len", &purged_hashes.len())
+            .execute(&mut transaction)
+            .await?;
+        }
+
+        // Note, that transactions are updated in order of their hashes to avoid deadlocks with other UPDATE queries.
+        let transactions = sqlx::query_as!(
+            StorageTransaction,
+            r#"
+            UPDATE transactions
+            SET
+                in_mempool = TRUE
+            FROM
+                (
+                    SELECT
+                        transaction_bundles.tx_hash
+                    FROM
+                        transaction_bundles
+                    WHERE
+                        transaction_bundles.bundle_hash IN (
+                            SELECT
+                                bundles.bundle_hash
+                            FROM
+                                transaction_bundles AS bundles
+                            LEFT JOIN transactions AS members ON members.hash = bundles.tx_hash
+                            WHERE
+                                bundles.bundle_hash IN (
+                                    SELECT
+                                        candidates.bundle_hash
+                                    FROM
+                                        transactions
+                                    INNER JOIN
+                                        transaction_bundles AS candidates
+                                        ON candidates.tx_hash = transactions.hash
+                                    WHERE
+                                        transactions.miniblock_number IS NULL
+                                        AND transactions.in_mempool = FALSE
+                                        AND transactions.error IS NULL
+                                )
+                            GROUP BY
+                                bundles.bundle_hash
+                            HAVING
+                                BOOL_AND(
+                                    COALESCE(
+                                        members.hash IS NOT NULL
+                                        AND members.miniblock_number IS NULL
+                                        AND members.in_mempool = FALSE
+                                        AND members.error IS NULL
+                                        AND members.max_fee_per_gas >= $2
+                                        AND members.gas_per_pubdata_limit >= $3,
+                                        FALSE
+                                    )
+                                )
+                            ORDER BY
+                                MIN(bundles.created_at)
+                            LIMIT
+                                $1
+                        )
+                    ORDER BY
+                        transaction_bundles.tx_hash
+                ) AS subquery
+            WHERE
+                transactions.hash = subquery.tx_hash
+            RETURNING
+            transactions.*
+            "#,
+            limit as i32,
+            BigDecimal::from(fee_per_gas),
+            BigDecimal::from(gas_per_pubdata)
+        )
+        .instrument("sync_mempool_bundles")
+        .with_arg("fee_per_gas", &fee_per_gas)
+        .with_arg("gas_per_pubdata", &gas_per_pubdata)
+        .with_arg("limit", &limit)
+        .fetch_all(&mut transaction)
+        .await?;
+
+        if transactions.is_empty() {
+            return Ok(vec![]);
+        }
+        let tx_hashes: Vec<_> = transactions.iter().map(|tx| tx.hash.as_slice()).collect();
+        let membership = sqlx::query!(
+            r#"
+            SELECT
+                tx_hash,
+                bundle_hash,
+                index_in_bundle
+            FROM
+                transaction_bundles
+            WHERE
+                tx_hash = ANY($1)
+            "#,
+            &tx_hashes as &[&[u8]]
+        )
+        .instrument("sync_mempool_bundles#get_membership")
+        .with_arg("tx_hashes.len", &tx_hashes.len())
+        .fetch_all(&mut transaction)
+        .await?;
+        transaction.commit().await?;
+
+        let membership: HashMap<_, _> = membership
+            .into_iter()
+            .map(|row| {
+                let bundle_hash = H256::from_slice(&row.bundle_hash);
+                (row.tx_hash, (bundle_hash, row.index_in_bundle))
+            })
+            .collect();
+        let mut bundles = HashMap::<_, Vec<_>>::new();
+        for tx in transactions {
+            let (bundle_hash, index) = membership[&tx.hash];
+            bundles.entry(bundle_hash).or_default().push((index, tx));
+        }
+
+        let mut bundles: Vec<_> = bundles
+            .into_values()
+            .map(|mut members| {
+                members.sort_unstable_by_key(|(index, _)| *index);
+                let received_at = members.iter().map(|(_, tx)| tx.received_at).min();
+                let (txs, constraints): (Vec<_>, Vec<_>) = members
+                    .into_iter()
+                    .map(|(_, tx)| {
+                        let constraint = TransactionTimeRangeConstraint::from(&tx);
+                        let tx = L2Tx::try_from(Transaction::from(tx))
+                            .expect("bundled transaction is not an L2 transaction");
+                        (tx, constraint)
+                    })
+                    .unzip();
+                (received_at, L2TxBundle::new(txs), constraints)
+            })
+            .collect();
+        bundles.sort_unstable_by_key(|(received_at, ..)| *received_at);
+        Ok(bundles
+            .into_iter()
+            .map(|(_, bundle, constraints)| (bundle, constraints))
+            .collect())
+    }
+
     pub async fn reset_mempool(&mut self) -> DalResult<()> {
         sqlx::query!(
             r#"
@@ -2531,4 +2877,100 @@ mod tests {
             .unwrap();
         assert_eq!(tx_from_db[0].hash, tx_hash);
     }
+
+    #[tokio::test]
+    async fn syncing_mempool_with_bundles() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+
+        let tx = mock_l2_transaction();
+        conn.transactions_dal()
+            .insert_transaction_l2(
+                &tx,
+                TransactionExecutionMetrics::default(),
+                ValidationTraces::default(),
+            )
+            .await
+            .unwrap();
+        let bundle = L2TxBundle::new(vec![mock_l2_transaction(), mock_l2_transaction()]);
+        let members = vec![Default::default(); bundle.len()];
+        let result = conn
+            .transactions_dal()
+            .insert_transaction_bundle(&bundle, members)
+            .await
+            .unwrap();
+        assert_eq!(result, L2TxSubmissionResult::Added);
+
+        // A bundle containing an already persisted transaction must not be inserted.
+        let invalid_bundle = L2TxBundle::new(vec![mock_l2_transaction(), tx.clone()]);
+        let members = vec![Default::default(); invalid_bundle.len()];
+        let result = conn
+            .transactions_dal()
+            .insert_transaction_bundle(&invalid_bundle, members)
+            .await
+            .unwrap();
+        assert_eq!(result, L2TxSubmissionResult::Duplicate);
+        let first_tx_hash = invalid_bundle.txs()[0].hash();
+        let first_tx = conn
+            .transactions_dal()
+            .get_storage_tx_by_hash(first_tx_hash)
+            .await
+            .unwrap();
+        assert!(first_tx.is_none());
+
+        // A bundle must not replace a pending transaction with the same initiator and nonce.
+        let mut conflicting_tx = tx.clone();
+        conflicting_tx.set_input(H256::random().0.to_vec(), H256::random());
+        let conflicting_bundle =
+            L2TxBundle::new(vec![mock_l2_transaction(), conflicting_tx.clone()]);
+        let members = vec![Default::default(); conflicting_bundle.len()];
+        let result = conn
+            .transactions_dal()
+            .insert_transaction_bundle(&conflicting_bundle, members)
+            .await
+            .unwrap();
+        assert_eq!(result, L2TxSubmissionResult::Conflict);
+        for bundle_tx in conflicting_bundle.txs() {
+            let stored_tx = conn
+                .transactions_dal()
+                .get_storage_tx_by_hash(bundle_tx.hash())
+                .await
+                .unwrap();
+            assert!(stored_tx.is_none());
+        }
+        let stored_tx = conn
+            .transactions_dal()
+            .get_storage_tx_by_hash(tx.hash())
+            .await
+            .unwrap();
+        assert!(stored_tx.is_some());
+
+        let txs = conn
+            .transactions_dal()
+            .sync_mempool(&[], &[], 0, 0, true, 100)
+            .await
+            .unwrap();
+        let tx_hashes: Vec<_> = txs.iter().map(|(tx, _)| tx.hash()).collect();
+        assert_eq!(tx_hashes, [tx.hash()]);
+
+        let bundles = conn
+            .transactions_dal()
+            .sync_mempool_bundles(&[], 0, 0, 100)
+            .await
+            .unwrap();
+        assert_eq!(bundles.len(), 1);
+        let (synced_bundle, constraints) = &bundles[0];
+        assert_eq!(synced_bundle.hash(), bundle.hash());
+        let synced_hashes: Vec<_> = synced_bundle.txs().iter().map(L2Tx::hash).collect();
+        let expected_hashes: Vec<_> = bundle.txs().iter().map(L2Tx::hash).collect();
+        assert_eq!(synced_hashes, expected_hashes);
+        assert_eq!(constraints.len(), bundle.len());
+
+        let bundles = conn
+            .transactions_dal()
+            .sync_mempool_bundles(&[], 0, 0, 100)
+            .await
+            .unwrap();
+        assert!(bundles.is_empty());
+    }
 }
@@ -97,6 +97,7 @@ struct MockSettlementLayerInner {
     current_nonce: u64,
     pending_nonce: u64,
     nonces: BTreeMap<u64, u64>,
+    balances: HashMap<Address, U256>,
     pub sender: Address,
     pub return_error_on_tx_request: bool,
 }
@@ -112,6 +113,7 @@ impl Default for MockSettlementLayerInner {
             current_nonce: 0,
             pending_nonce: 0,
             nonces: Default::default(),
+            balances: Default::default(),
             sender: MOCK_SENDER_ACCOUNT,
             return_error_on_tx_request: false,
         }
@@ -437,6 +439,13 @@ impl<Net: SupportedMockSLNetwork> MockSettlementLayerBuilder<Net> {
                     Ok(inner.read().unwrap().get_transaction_count(address, block))
                 }
             })
+            .method("eth_getBalance", {
+                let inner = self.inner.clone();
+                move |address: Address, _block: web3::BlockNumber| {
+                    let inner = inner.read().unwrap();
+                    Ok(inner.balances.get(&address).copied().unwrap_or_default())
+                }
+            })
             .method("eth_gasPrice", move || Ok(self.max_fee_per_gas))
             .method("eth_call", {
                 let inner = self.inner.clone();
@@ -737,6 +746,15 @@ impl<Net: SupportedMockSLNetwork> MockSettlementLayer<Net> {
     pub fn set_return_error_on_tx_request(&self, value: bool) {
         self.inner.write().unwrap().return_error_on_tx_request = value;
     }
+
+    /// Sets the ETH balance of the specified account returned by `eth_getBalance`. Balances of all accounts are zero by default.
+    pub fn set_balance(&self, address: Address, balance: U256) {
+        self.inner
+            .write()
+            .unwrap()
+            .balances
+            .insert(address, balance);
+    }
 }
 
 impl<T: SupportedMockSLNetwork> AsRef<dyn EthInterface> for MockSettlementLayer<T> {
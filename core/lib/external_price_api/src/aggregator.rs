@@ -0,0 +1,293 @@
+//! Price API client aggregating ratios from multiple sources.
+
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+use zksync_config::configs::external_price_api_client::PriceAggregationConfig;
+use zksync_types::{base_token_ratio::BaseTokenApiRatio, fee_model::ConversionRatio};
+
+use crate::{
+    metrics::{SourceStatus, METRICS},
+    utils::get_fraction,
+    APIToken, PriceApiClient,
+};
+
+/// [`PriceApiClient`] querying several sources concurrently and returning the median of their ratios.
+///
+/// - Source ratios deviating from the median of all sources by more than `max_deviation_percent`
+///   are rejected as outliers.
+/// - If fewer than `min_quorum` sources return a ratio within the deviation band, fetching fails.
+/// - If `max_change_percent` is set, the returned ratio differs from the previously returned one
+///   for the same token by at most this value.
+#[derive(Debug)]
+pub struct AggregatingPriceApiClient {
+    sources: Vec<(String, Arc<dyn PriceApiClient>)>,
+    min_quorum: usize,
+    max_deviation_percent: f64,
+    max_change_percent: Option<f64>,
+    previous_ratios: Mutex<HashMap<APIToken, f64>>,
+}
+
+impl AggregatingPriceApiClient {
+    /// Creates a client without sources. Sources should be added using [`Self::with_source()`].
+    pub fn new(config: &PriceAggregationConfig) -> Self {
+        Self {
+            sources: vec![],
+            min_quorum: config.min_quorum.get(),
+            max_deviation_percent: config.max_deviation_percent,
+            max_change_percent: config.max_change_percent,
+            previous_ratios: Mutex::default(),
+        }
+    }
+
+    /// Adds a source with the specified name. The name is used in logs and metrics.
+    pub fn with_source(mut self, name: impl Into<String>, client: Arc<dyn PriceApiClient>) -> Self {
+        self.sources.push((name.into(), client));
+        self
+    }
+
+    fn aggregate(&self, ratios: &[(&str, BaseTokenApiRatio)]) -> anyhow::Result<BaseTokenApiRatio> {
+        let values: Vec<_> = ratios.iter().map(|(_, ratio)| ratio_value(ratio)).collect();
+        let Some(median) = compute_median(values.clone()) else {
+            anyhow::bail!("all {} price sources failed", self.sources.len());
+        };
+
+        let mut accepted = vec![];
+        for ((source, ratio), value) in ratios.iter().zip(values) {
+            let deviation_percent = (value - median).abs() / median * 100.0;
+            METRICS.source_ratio[&source.to_string()].set(value);
+            METRICS.source_deviation_percent[&source.to_string()].set(deviation_percent);
+
+            let status = if deviation_percent <= self.max_deviation_percent {
+                accepted.push((value, ratio.ratio_timestamp));
+                SourceStatus::Accepted
+            } else {
+                tracing::warn!(
+                    "Rejecting ratio {value} from price source `{source}`: it deviates from median {median} by {deviation_percent:.2}%"
+                );
+                SourceStatus::Outlier
+            };
+            METRICS.source_contributions[&(source.to_string(), status)].inc();
+        }
+
+        anyhow::ensure!(
+            accepted.len() >= self.min_quorum,
+            "only {} price source(s) returned ratios within {}% from the median {median}; at least {} are required",
+            accepted.len(),
+            self.max_deviation_percent,
+            self.min_quorum
+        );
+        let value = compute_median(accepted.iter().map(|&(value, _)| value).collect())
+            .expect("no accepted ratios");
+        // Use the oldest timestamp among the used quotes.
+        let ratio_timestamp = accepted
+            .iter()
+            .map(|&(_, timestamp)| timestamp)
+            .min()
+            .expect("no accepted ratios");
+        let (numerator, denominator) = get_fraction(value)?;
+        Ok(BaseTokenApiRatio {
+            ratio: ConversionRatio {
+                numerator,
+                denominator,
+            },
+            ratio_timestamp,
+        })
+    }
+
+    fn cap_change(
+        &self,
+        previous_value: f64,
+        ratio: BaseTokenApiRatio,
+    ) -> anyhow::Result<BaseTokenApiRatio> {
+        let Some(max_change_percent) = self.max_change_percent else {
+            return Ok(ratio);
+        };
+        let value = ratio_value(&ratio);
+        let min_value = previous_value * (1.0 - max_change_percent / 100.0);
+        let max_value = previous_value * (1.0 + max_change_percent / 100.0);
+        if (min_value..=max_value).contains(&value) {
+            return Ok(ratio);
+        }
+
+        let capped_value = value.clamp(min_value, max_value);
+        tracing::warn!(
+            "Aggregated ratio {value} changed by more than {max_change_percent}% from the previous ratio {previous_value}; capping it to {capped_value}"
+        );
+        METRICS.capped_changes.inc();
+        let (numerator, denominator) = get_fraction(capped_value)?;
+        Ok(BaseTokenApiRatio {
+            ratio: ConversionRatio {
+                numerator,
+                denominator,
+            },
+            ratio_timestamp: ratio.ratio_timestamp,
+        })
+    }
+}
+
+fn ratio_value(ratio: &BaseTokenApiRatio) -> f64 {
+    ratio.ratio.numerator.get() as f64 / ratio.ratio.denominator.get() as f64
+}
+
+fn compute_median(mut values: Vec<f64>) -> Option<f64> {
+    values.sort_unstable_by(f64::total_cmp);
+    let len = values.len();
+    match len {
+        0 => None,
+        _ if len % 2 == 1 => Some(values[len / 2]),
+        _ => Some((values[len / 2 - 1] + values[len / 2]) / 2.0),
+    }
+}
+
+#[async_trait]
+impl PriceApiClient for AggregatingPriceApiClient {
+    async fn fetch_ratio(&self, token: APIToken) -> anyhow::Result<BaseTokenApiRatio> {
+        let fetches = self
+            .sources
+            .iter()
+            .map(|(_, client)| client.fetch_ratio(token));
+        let results = futures::future::join_all(fetches).await;
+
+        let mut ratios = vec![];
+        for ((source, _), result) in self.sources.iter().zip(results) {
+            match result {
+                Ok(ratio) => ratios.push((source.as_str(), ratio)),
+                Err(err) => {
+                    tracing::warn!(
+                        "Failed fetching ratio for {token:?} from price source `{source}`: {err:#}"
+                    );
+                    METRICS.source_contributions[&(source.clone(), SourceStatus::Error)].inc();
+                }
+            }
+        }
+
+        let ratio = self.aggregate(&ratios)?;
+        let mut previous_ratios = self.previous_ratios.lock().await;
+        let ratio = match previous_ratios.get(&token) {
+            Some(&previous_value) => self.cap_change(previous_value, ratio)?,
+            None => ratio,
+        };
+        let value = ratio_value(&ratio);
+        previous_ratios.insert(token, value);
+        METRICS.aggregated_ratio.set(value);
+        Ok(ratio)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use chrono::Utc;
+    use zksync_types::Address;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct MockSource(Mutex<Option<f64>>);
+
+    impl MockSource {
+        fn new(value: Option<f64>) -> Arc<Self> {
+            Arc::new(Self(Mutex::new(value)))
+        }
+
+        async fn set(&self, value: Option<f64>) {
+            *self.0.lock().await = value;
+        }
+    }
+
+    #[async_trait]
+    impl PriceApiClient for MockSource {
+        async fn fetch_ratio(&self, _token: APIToken) -> anyhow::Result<BaseTokenApiRatio> {
+            let value = self
+                .0
+                .lock()
+                .await
+                .ok_or_else(|| anyhow::anyhow!("source is down"))?;
+            let (numerator, denominator) = get_fraction(value)?;
+            Ok(BaseTokenApiRatio {
+                ratio: ConversionRatio {
+                    numerator,
+                    denominator,
+                },
+                ratio_timestamp: Utc::now(),
+            })
+        }
+    }
+
+    fn config(min_quorum: usize, max_change_percent: Option<f64>) -> PriceAggregationConfig {
+        PriceAggregationConfig {
+            sources: vec![],
+            min_quorum: NonZeroUsize::new(min_quorum).unwrap(),
+            max_deviation_percent: 5.0,
+            max_change_percent,
+            coingecko: None,
+            coinmarketcap: None,
+        }
+    }
+
+    fn token() -> APIToken {
+        APIToken::ERC20(Address::repeat_byte(1))
+    }
+
+    #[test]
+    fn computing_median() {
+        assert_eq!(compute_median(vec![]), None);
+        assert_eq!(compute_median(vec![3.0]), Some(3.0));
+        assert_eq!(compute_median(vec![3.0, 1.0, 2.0]), Some(2.0));
+        assert_eq!(compute_median(vec![4.0, 1.0, 3.0, 2.0]), Some(2.5));
+    }
+
+    #[tokio::test]
+    async fn aggregating_ratios_with_outlier() {
+        let client = AggregatingPriceApiClient::new(&config(2, None))
+            .with_source("first", MockSource::new(Some(100.0)))
+            .with_source("second", MockSource::new(Some(102.0)))
+            .with_source("bogus", MockSource::new(Some(1_000.0)));
+        let ratio = client.fetch_ratio(token()).await.unwrap();
+        assert_eq!(ratio_value(&ratio), 101.0);
+    }
+
+    #[tokio::test]
+    async fn aggregation_requires_quorum() {
+        let failing_source = MockSource::new(None);
+        let client = AggregatingPriceApiClient::new(&config(2, None))
+            .with_source("first", MockSource::new(Some(100.0)))
+            .with_source("second", MockSource::new(Some(150.0)))
+            .with_source("failing", failing_source.clone());
+        let err = client.fetch_ratio(token()).await.unwrap_err().to_string();
+        assert!(err.contains("at least 2 are required"), "{err}");
+
+        failing_source.set(Some(145.0)).await;
+        let ratio = client.fetch_ratio(token()).await.unwrap();
+        assert_eq!(ratio_value(&ratio), 147.5);
+
+        let client = AggregatingPriceApiClient::new(&config(1, None))
+            .with_source("failing", MockSource::new(None));
+        let err = client.fetch_ratio(token()).await.unwrap_err().to_string();
+        assert!(err.contains("all 1 price sources failed"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn capping_ratio_changes() {
+        let source = MockSource::new(Some(100.0));
+        let client = AggregatingPriceApiClient::new(&config(1, Some(10.0)))
+            .with_source("source", source.clone());
+        let ratio = client.fetch_ratio(token()).await.unwrap();
+        assert_eq!(ratio_value(&ratio), 100.0);
+
+        source.set(Some(105.0)).await;
+        let ratio = client.fetch_ratio(token()).await.unwrap();
+        assert_eq!(ratio_value(&ratio), 105.0);
+
+        source.set(Some(50.0)).await;
+        let ratio = client.fetch_ratio(token()).await.unwrap();
+        assert_eq!(ratio_value(&ratio), 94.5);
+
+        // Other tokens are tracked separately.
+        let ratio = client.fetch_ratio(APIToken::ZK).await.unwrap();
+        assert_eq!(ratio_value(&ratio), 50.0);
+    }
+}
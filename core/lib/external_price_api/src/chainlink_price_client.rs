@@ -0,0 +1,243 @@
+use anyhow::Context as _;
+use async_trait::async_trait;
+use tokio::sync::OnceCell;
+use zksync_config::configs::external_price_api_client::ChainlinkPriceFeedConfig;
+use zksync_eth_client::clients::{DynClient, L1};
+use zksync_types::{base_token_ratio::BaseTokenApiRatio, ethabi, fee_model::ConversionRatio, U256};
+
+use crate::{
+    utils::{call_view_function, check_staleness, eth_price_to_base_token_ratio, get_fraction},
+    APIToken, PriceApiClient,
+};
+
+/// Subset of the Chainlink `AggregatorV3Interface` ABI used by the client.
+const AGGREGATOR_ABI: &str = r#"[
+    {
+        "type": "function",
+        "name": "decimals",
+        "inputs": [],
+        "outputs": [{ "name": "", "type": "uint8" }],
+        "stateMutability": "view"
+    },
+    {
+        "type": "function",
+        "name": "latestRoundData",
+        "inputs": [],
+        "outputs": [
+            { "name": "roundId", "type": "uint80" },
+            { "name": "answer", "type": "int256" },
+            { "name": "startedAt", "type": "uint256" },
+            { "name": "updatedAt", "type": "uint256" },
+            { "name": "answeredInRound", "type": "uint80" }
+        ],
+        "stateMutability": "view"
+    }
+]"#;
+
+/// Price client reading the base token price from a Chainlink-compatible aggregator feed on L1.
+#[derive(Debug)]
+pub struct ChainlinkPriceClient {
+    config: ChainlinkPriceFeedConfig,
+    eth_client: Box<DynClient<L1>>,
+    contract: ethabi::Contract,
+    decimals: OnceCell<u8>,
+}
+
+impl ChainlinkPriceClient {
+    pub fn new(config: ChainlinkPriceFeedConfig, eth_client: Box<DynClient<L1>>) -> Self {
+        Self {
+            config,
+            eth_client: eth_client.for_component("chainlink_price_client"),
+            contract: ethabi::Contract::load(AGGREGATOR_ABI.as_bytes())
+                .expect("invalid aggregator ABI"),
+            decimals: OnceCell::new(),
+        }
+    }
+
+    async fn call(&self, name: &str) -> anyhow::Result<Vec<ethabi::Token>> {
+        let function = self.contract.function(name)?;
+        call_view_function(&self.eth_client, self.config.feed_address, function, &[]).await
+    }
+
+    async fn decimals(&self) -> anyhow::Result<u8> {
+        let decimals = self
+            .decimals
+            .get_or_try_init(|| async {
+                let output = self.call("decimals").await?;
+                let decimals = output
+                    .into_iter()
+                    .next()
+                    .and_then(ethabi::Token::into_uint)
+                    .filter(|&decimals| decimals <= U256::from(u8::MAX))
+                    .context("invalid `decimals` output")?;
+                anyhow::Ok(decimals.as_u32() as u8)
+            })
+            .await?;
+        Ok(*decimals)
+    }
+
+    async fn fetch_feed_ratio(&self) -> anyhow::Result<BaseTokenApiRatio> {
+        let output = self.call("latestRoundData").await?;
+        let [ethabi::Token::Uint(round_id), ethabi::Token::Int(answer), _, ethabi::Token::Uint(updated_at), ethabi::Token::Uint(answered_in_round)] =
+            output.as_slice()
+        else {
+            anyhow::bail!("unexpected `latestRoundData` output: {output:?}");
+        };
+
+        anyhow::ensure!(
+            answered_in_round >= round_id,
+            "feed round #{round_id} is incomplete (answered in round #{answered_in_round})"
+        );
+        // `answer` is a two's complement signed integer; we only accept positive values fitting into 128 bits.
+        anyhow::ensure!(
+            !answer.is_zero() && answer.bits() <= 128,
+            "feed returned invalid answer {answer:#x}"
+        );
+        anyhow::ensure!(
+            updated_at.bits() <= 64,
+            "feed returned invalid update timestamp {updated_at}"
+        );
+        let ratio_timestamp = check_staleness(updated_at.as_u64(), self.config.max_staleness)?;
+
+        let decimals = self.decimals().await?;
+        let price = answer.as_u128() as f64 / 10_f64.powi(decimals.into());
+        let ratio = if self.config.inverted {
+            // The feed quotes ETH in the base token, which is exactly the ratio we need.
+            let (numerator, denominator) = get_fraction(price)?;
+            ConversionRatio {
+                numerator,
+                denominator,
+            }
+        } else {
+            eth_price_to_base_token_ratio(price)?.ratio
+        };
+        Ok(BaseTokenApiRatio {
+            ratio,
+            ratio_timestamp,
+        })
+    }
+}
+
+#[async_trait]
+impl PriceApiClient for ChainlinkPriceClient {
+    async fn fetch_ratio(&self, token: APIToken) -> anyhow::Result<BaseTokenApiRatio> {
+        match token {
+            APIToken::Eth => Ok(BaseTokenApiRatio::identity()),
+            APIToken::ERC20(address) if address == self.config.token_address => {
+                self.fetch_feed_ratio().await
+            }
+            _ => anyhow::bail!(
+                "Chainlink price client is only configured for token {:?}; requested {token:?}",
+                self.config.token_address
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::Utc;
+    use zksync_eth_client::clients::MockSettlementLayer;
+    use zksync_types::Address;
+
+    use super::*;
+    use crate::tests::approximate_value;
+
+    const TOKEN_ADDRESS: Address = Address::repeat_byte(1);
+    const FEED_ADDRESS: Address = Address::repeat_byte(2);
+
+    fn mock_client(answer: U256, updated_at: u64) -> Box<DynClient<L1>> {
+        let contract = ethabi::Contract::load(AGGREGATOR_ABI.as_bytes()).unwrap();
+        let decimals_selector = contract.function("decimals").unwrap().short_signature();
+        let round_data_selector = contract
+            .function("latestRoundData")
+            .unwrap()
+            .short_signature();
+
+        let mock = MockSettlementLayer::<L1>::builder()
+            .with_call_handler(move |req, _| {
+                assert_eq!(req.to, Some(FEED_ADDRESS));
+                let selector = &req.data.as_ref().unwrap().0[..4];
+                if selector == decimals_selector {
+                    ethabi::Token::Uint(18.into())
+                } else if selector == round_data_selector {
+                    ethabi::Token::Tuple(vec![
+                        ethabi::Token::Uint(5.into()),
+                        ethabi::Token::Int(answer),
+                        ethabi::Token::Uint(updated_at.into()),
+                        ethabi::Token::Uint(updated_at.into()),
+                        ethabi::Token::Uint(5.into()),
+                    ])
+                } else {
+                    panic!("unexpected call: {req:?}");
+                }
+            })
+            .build();
+        Box::new(mock.into_client())
+    }
+
+    fn config(inverted: bool) -> ChainlinkPriceFeedConfig {
+        ChainlinkPriceFeedConfig {
+            token_address: TOKEN_ADDRESS,
+            feed_address: FEED_ADDRESS,
+            inverted,
+            max_staleness: Duration::from_secs(3_600),
+        }
+    }
+
+    fn now() -> u64 {
+        Utc::now().timestamp() as u64
+    }
+
+    #[tokio::test]
+    async fn fetching_ratio_from_feed() {
+        // 1 token = 0.0025 ETH
+        let answer = U256::from(25) * U256::exp10(14);
+        let client = ChainlinkPriceClient::new(config(false), mock_client(answer, now() - 60));
+        let ratio = client
+            .fetch_ratio(APIToken::ERC20(TOKEN_ADDRESS))
+            .await
+            .unwrap();
+        assert_eq!(approximate_value(&ratio), 400.0);
+
+        let ratio = client.fetch_ratio(APIToken::Eth).await.unwrap();
+        assert_eq!(ratio.ratio, BaseTokenApiRatio::identity().ratio);
+        client
+            .fetch_ratio(APIToken::ERC20(Address::repeat_byte(3)))
+            .await
+            .unwrap_err();
+        client.fetch_ratio(APIToken::ZK).await.unwrap_err();
+
+        // 1 ETH = 400 tokens
+        let answer = U256::from(400) * U256::exp10(18);
+        let client = ChainlinkPriceClient::new(config(true), mock_client(answer, now() - 60));
+        let ratio = client
+            .fetch_ratio(APIToken::ERC20(TOKEN_ADDRESS))
+            .await
+            .unwrap();
+        assert_eq!(approximate_value(&ratio), 400.0);
+    }
+
+    #[tokio::test]
+    async fn rejecting_stale_or_invalid_feed_data() {
+        let answer = U256::from(25) * U256::exp10(14);
+        let client = ChainlinkPriceClient::new(config(false), mock_client(answer, now() - 7_200));
+        let err = client
+            .fetch_ratio(APIToken::ERC20(TOKEN_ADDRESS))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("exceeds max staleness"), "{err}");
+
+        // Negative answer (-1 in two's complement)
+        let client = ChainlinkPriceClient::new(config(false), mock_client(U256::MAX, now()));
+        let err = client
+            .fetch_ratio(APIToken::ERC20(TOKEN_ADDRESS))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("invalid answer"), "{err}");
+    }
+}
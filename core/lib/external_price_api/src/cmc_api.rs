@@ -197,6 +197,9 @@ mod tests {
             api_key,
             client_timeout: Duration::from_secs(5),
             forced: None,
+            aggregation: None,
+            chainlink: None,
+            uniswap_v3: None,
         }))
     }
 
@@ -357,6 +360,9 @@ mod tests {
             client_timeout: Duration::from_secs(5),
             source: "coinmarketcap".to_string(),
             forced: None,
+            aggregation: None,
+            chainlink: None,
+            uniswap_v3: None,
         });
 
         let tether: Address = "0xdac17f958d2ee523a2206206994597c13d831ec7"
@@ -1,10 +1,14 @@
+pub mod aggregator;
+pub mod chainlink_price_client;
 pub mod cmc_api;
 pub mod coingecko_api;
 pub mod forced_price_client;
+mod metrics;
 #[cfg(feature = "node_framework")]
 pub mod node;
 #[cfg(test)]
 mod tests;
+pub mod uniswap_twap_price_client;
 mod utils;
 
 use std::fmt;
@@ -13,7 +17,7 @@ use async_trait::async_trait;
 use zksync_types::{base_token_ratio::BaseTokenApiRatio, Address, ZK_L1_ADDRESS};
 
 /// Enum representing the token for which the ratio is fetched.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum APIToken {
     Eth,            // we return price in ETH right now so this will always return identity
     ERC20(Address), // on Ethereum
@@ -0,0 +1,35 @@
+//! Metrics for price API clients.
+
+use vise::{Counter, EncodeLabelValue, Gauge, LabeledFamily, Metrics};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub(crate) enum SourceStatus {
+    /// Source ratio was used to compute the aggregated ratio.
+    Accepted,
+    /// Source ratio deviated too much from the median and was rejected.
+    Outlier,
+    /// Source returned an error.
+    Error,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "external_price_api_aggregator")]
+pub(crate) struct AggregatorMetrics {
+    /// Latest ratio returned by each source.
+    #[metrics(labels = ["source"])]
+    pub source_ratio: LabeledFamily<String, Gauge<f64>>,
+    /// Latest deviation of each source ratio from the median of all sources, in percent.
+    #[metrics(labels = ["source"])]
+    pub source_deviation_percent: LabeledFamily<String, Gauge<f64>>,
+    /// Number of ratios fetched from each source, grouped by whether they contributed to the aggregated ratio.
+    #[metrics(labels = ["source", "status"])]
+    pub source_contributions: LabeledFamily<(String, SourceStatus), Counter, 2>,
+    /// Latest aggregated ratio.
+    pub aggregated_ratio: Gauge<f64>,
+    /// Number of times the aggregated ratio change was capped.
+    pub capped_changes: Counter,
+}
+
+#[vise::register]
+pub(crate) static METRICS: vise::Global<AggregatorMetrics> = vise::Global::new();
@@ -0,0 +1,426 @@
+use anyhow::Context as _;
+use async_trait::async_trait;
+use chrono::Utc;
+use tokio::sync::OnceCell;
+use zksync_config::configs::external_price_api_client::UniswapV3TwapConfig;
+use zksync_eth_client::clients::{DynClient, L1};
+use zksync_types::{
+    base_token_ratio::BaseTokenApiRatio, ethabi, fee_model::ConversionRatio, Address, U256,
+};
+
+use crate::{
+    utils::{call_view_function, check_staleness, eth_price_to_base_token_ratio, get_fraction},
+    APIToken, PriceApiClient,
+};
+
+/// Subset of the Uniswap V3 pool ABI used by the client. Also includes the ERC-20 `decimals` function
+/// called on the pool tokens.
+const POOL_ABI: &str = r#"[
+    {
+        "type": "function",
+        "name": "token0",
+        "inputs": [],
+        "outputs": [{ "name": "", "type": "address" }],
+        "stateMutability": "view"
+    },
+    {
+        "type": "function",
+        "name": "token1",
+        "inputs": [],
+        "outputs": [{ "name": "", "type": "address" }],
+        "stateMutability": "view"
+    },
+    {
+        "type": "function",
+        "name": "slot0",
+        "inputs": [],
+        "outputs": [
+            { "name": "sqrtPriceX96", "type": "uint160" },
+            { "name": "tick", "type": "int24" },
+            { "name": "observationIndex", "type": "uint16" },
+            { "name": "observationCardinality", "type": "uint16" },
+            { "name": "observationCardinalityNext", "type": "uint16" },
+            { "name": "feeProtocol", "type": "uint8" },
+            { "name": "unlocked", "type": "bool" }
+        ],
+        "stateMutability": "view"
+    },
+    {
+        "type": "function",
+        "name": "observations",
+        "inputs": [{ "name": "index", "type": "uint256" }],
+        "outputs": [
+            { "name": "blockTimestamp", "type": "uint32" },
+            { "name": "tickCumulative", "type": "int56" },
+            { "name": "secondsPerLiquidityCumulativeX128", "type": "uint160" },
+            { "name": "initialized", "type": "bool" }
+        ],
+        "stateMutability": "view"
+    },
+    {
+        "type": "function",
+        "name": "observe",
+        "inputs": [{ "name": "secondsAgos", "type": "uint32[]" }],
+        "outputs": [
+            { "name": "tickCumulatives", "type": "int56[]" },
+            { "name": "secondsPerLiquidityCumulativeX128s", "type": "uint160[]" }
+        ],
+        "stateMutability": "view"
+    },
+    {
+        "type": "function",
+        "name": "decimals",
+        "inputs": [],
+        "outputs": [{ "name": "", "type": "uint8" }],
+        "stateMutability": "view"
+    }
+]"#;
+
+/// Immutable pool information loaded once.
+#[derive(Debug, Clone, Copy)]
+struct PoolInfo {
+    /// Whether the base token is `token0` in the pool (i.e., the pool price is quoted in ETH per base token).
+    base_token_is_token0: bool,
+    /// Difference between `token0` and `token1` decimals.
+    decimals_diff: i32,
+}
+
+/// Price client computing the base token price as a time-weighted average price (TWAP) of a Uniswap V3 pool
+/// pairing the base token with WETH on L1.
+#[derive(Debug)]
+pub struct UniswapV3TwapPriceClient {
+    config: UniswapV3TwapConfig,
+    eth_client: Box<DynClient<L1>>,
+    contract: ethabi::Contract,
+    pool_info: OnceCell<PoolInfo>,
+}
+
+impl UniswapV3TwapPriceClient {
+    pub fn new(config: UniswapV3TwapConfig, eth_client: Box<DynClient<L1>>) -> Self {
+        Self {
+            config,
+            eth_client: eth_client.for_component("uniswap_twap_price_client"),
+            contract: ethabi::Contract::load(POOL_ABI.as_bytes()).expect("invalid pool ABI"),
+            pool_info: OnceCell::new(),
+        }
+    }
+
+    async fn call(
+        &self,
+        address: Address,
+        name: &str,
+        params: &[ethabi::Token],
+    ) -> anyhow::Result<Vec<ethabi::Token>> {
+        let function = self.contract.function(name)?;
+        call_view_function(&self.eth_client, address, function, params).await
+    }
+
+    async fn token_address(&self, name: &str) -> anyhow::Result<Address> {
+        let output = self.call(self.config.pool_address, name, &[]).await?;
+        output
+            .into_iter()
+            .next()
+            .and_then(ethabi::Token::into_address)
+            .with_context(|| format!("invalid `{name}` output"))
+    }
+
+    async fn token_decimals(&self, token: Address) -> anyhow::Result<i32> {
+        let output = self.call(token, "decimals", &[]).await?;
+        let decimals = output
+            .into_iter()
+            .next()
+            .and_then(ethabi::Token::into_uint)
+            .filter(|&decimals| decimals <= U256::from(u8::MAX))
+            .with_context(|| format!("invalid `decimals` output for token {token:?}"))?;
+        Ok(decimals.as_u32() as i32)
+    }
+
+    async fn pool_info(&self) -> anyhow::Result<PoolInfo> {
+        let info = self
+            .pool_info
+            .get_or_try_init(|| async {
+                let token0 = self.token_address("token0").await?;
+                let token1 = self.token_address("token1").await?;
+                let base_token_is_token0 = if token0 == self.config.token_address {
+                    true
+                } else if token1 == self.config.token_address {
+                    false
+                } else {
+                    anyhow::bail!(
+                        "pool {:?} (tokens: {token0:?}, {token1:?}) doesn't contain base token {:?}",
+                        self.config.pool_address,
+                        self.config.token_address
+                    );
+                };
+                let decimals_diff =
+                    self.token_decimals(token0).await? - self.token_decimals(token1).await?;
+                anyhow::Ok(PoolInfo {
+                    base_token_is_token0,
+                    decimals_diff,
+                })
+            })
+            .await?;
+        Ok(*info)
+    }
+
+    /// Checks that the latest pool observation is recent enough.
+    async fn check_last_observation(&self) -> anyhow::Result<()> {
+        let slot0 = self.call(self.config.pool_address, "slot0", &[]).await?;
+        let observation_index = slot0
+            .get(2)
+            .cloned()
+            .and_then(ethabi::Token::into_uint)
+            .context("invalid `slot0` output")?;
+        let observation = self
+            .call(
+                self.config.pool_address,
+                "observations",
+                &[ethabi::Token::Uint(observation_index)],
+            )
+            .await?;
+        let [ethabi::Token::Uint(block_timestamp), _, _, ethabi::Token::Bool(true)] =
+            observation.as_slice()
+        else {
+            anyhow::bail!("unexpected `observations` output: {observation:?}");
+        };
+        check_staleness(block_timestamp.low_u64(), self.config.max_staleness)?;
+        Ok(())
+    }
+
+    /// Returns the average tick over the TWAP window.
+    async fn average_tick(&self) -> anyhow::Result<f64> {
+        let window_secs = u32::try_from(self.config.twap_window.as_secs())
+            .ok()
+            .filter(|&secs| secs > 0)
+            .context("invalid TWAP window")?;
+        let seconds_agos = ethabi::Token::Array(vec![
+            ethabi::Token::Uint(window_secs.into()),
+            ethabi::Token::Uint(0.into()),
+        ]);
+        let output = self
+            .call(self.config.pool_address, "observe", &[seconds_agos])
+            .await?;
+        let tick_cumulatives = output
+            .into_iter()
+            .next()
+            .and_then(ethabi::Token::into_array)
+            .context("invalid `observe` output")?;
+        let [ethabi::Token::Int(start), ethabi::Token::Int(end)] = tick_cumulatives.as_slice()
+        else {
+            anyhow::bail!("unexpected tick cumulatives: {tick_cumulatives:?}");
+        };
+        // Tick cumulatives are `int56` values sign-extended to 256 bits, so the lowest 64 bits are
+        // their two's complement representation.
+        let tick_delta = (end.low_u64() as i64).wrapping_sub(start.low_u64() as i64);
+        Ok(tick_delta as f64 / f64::from(window_secs))
+    }
+
+    async fn fetch_pool_ratio(&self) -> anyhow::Result<BaseTokenApiRatio> {
+        let pool_info = self.pool_info().await?;
+        self.check_last_observation().await?;
+        let average_tick = self.average_tick().await?;
+
+        // Price of `token0` in `token1` in whole token units.
+        let price = 1.0001_f64.powf(average_tick) * 10_f64.powi(pool_info.decimals_diff);
+        anyhow::ensure!(
+            price.is_finite() && price > 0.0,
+            "invalid pool price {price}"
+        );
+        if pool_info.base_token_is_token0 {
+            eth_price_to_base_token_ratio(price)
+        } else {
+            // `price` is the price of ETH in the base token, which is exactly the ratio we need.
+            let (numerator, denominator) = get_fraction(price)?;
+            Ok(BaseTokenApiRatio {
+                ratio: ConversionRatio {
+                    numerator,
+                    denominator,
+                },
+                ratio_timestamp: Utc::now(),
+            })
+        }
+    }
+}
+
+#[async_trait]
+impl PriceApiClient for UniswapV3TwapPriceClient {
+    async fn fetch_ratio(&self, token: APIToken) -> anyhow::Result<BaseTokenApiRatio> {
+        match token {
+            APIToken::Eth => Ok(BaseTokenApiRatio::identity()),
+            APIToken::ERC20(address) if address == self.config.token_address => {
+                self.fetch_pool_ratio().await
+            }
+            _ => anyhow::bail!(
+                "Uniswap V3 price client is only configured for token {:?}; requested {token:?}",
+                self.config.token_address
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use zksync_eth_client::clients::MockSettlementLayer;
+
+    use super::*;
+    use crate::tests::approximate_value;
+
+    const TOKEN_ADDRESS: Address = Address::repeat_byte(1);
+    const WETH_ADDRESS: Address = Address::repeat_byte(2);
+    const POOL_ADDRESS: Address = Address::repeat_byte(3);
+    const TWAP_WINDOW_SECS: u64 = 600;
+    /// Tick corresponding to 1 base token (6 decimals) = 0.0025 WETH (18 decimals).
+    const TICK: i64 = 216_406;
+
+    /// Converts a signed integer to an `intN` token.
+    fn int_token(value: i64) -> ethabi::Token {
+        let value = if value >= 0 {
+            U256::from(value)
+        } else {
+            U256::MAX - U256::from(value.unsigned_abs()) + 1
+        };
+        ethabi::Token::Int(value)
+    }
+
+    /// Returns a token whose encoding is equal to the encoding of `tokens`. Necessary because the mock client
+    /// encodes a single token, and the encoding of a tuple with dynamic fields differs from the encoding
+    /// of multiple outputs.
+    fn raw_outputs(tokens: &[ethabi::Token]) -> ethabi::Token {
+        let encoded = ethabi::encode(tokens);
+        let words = encoded
+            .chunks(32)
+            .map(|word| ethabi::Token::FixedBytes(word.to_vec()))
+            .collect();
+        ethabi::Token::Tuple(words)
+    }
+
+    fn mock_client(
+        token0: Address,
+        token1: Address,
+        last_observation_timestamp: u64,
+    ) -> Box<DynClient<L1>> {
+        let contract = ethabi::Contract::load(POOL_ABI.as_bytes()).unwrap();
+        let selector = move |name: &str| contract.function(name).unwrap().short_signature();
+        let selectors = [
+            "token0",
+            "token1",
+            "slot0",
+            "observations",
+            "observe",
+            "decimals",
+        ]
+        .map(|name| (selector(name), name));
+
+        let mock = MockSettlementLayer::<L1>::builder()
+            .with_call_handler(move |req, _| {
+                let selector = &req.data.as_ref().unwrap().0[..4];
+                let (_, name) = selectors
+                    .iter()
+                    .find(|(sig, _)| sig == selector)
+                    .unwrap_or_else(|| panic!("unexpected call: {req:?}"));
+                let address = req.to.unwrap();
+                if *name == "decimals" {
+                    return if address == TOKEN_ADDRESS {
+                        ethabi::Token::Uint(6.into())
+                    } else if address == WETH_ADDRESS {
+                        ethabi::Token::Uint(18.into())
+                    } else {
+                        panic!("unexpected call: {req:?}");
+                    };
+                }
+                assert_eq!(address, POOL_ADDRESS);
+
+                match *name {
+                    "token0" => ethabi::Token::Address(token0),
+                    "token1" => ethabi::Token::Address(token1),
+                    "slot0" => ethabi::Token::Tuple(vec![
+                        ethabi::Token::Uint(U256::one() << 96),
+                        int_token(TICK),
+                        ethabi::Token::Uint(7.into()),
+                        ethabi::Token::Uint(10.into()),
+                        ethabi::Token::Uint(10.into()),
+                        ethabi::Token::Uint(0.into()),
+                        ethabi::Token::Bool(true),
+                    ]),
+                    "observations" => ethabi::Token::Tuple(vec![
+                        ethabi::Token::Uint(last_observation_timestamp.into()),
+                        int_token(0),
+                        ethabi::Token::Uint(0.into()),
+                        ethabi::Token::Bool(true),
+                    ]),
+                    "observe" => {
+                        let start = -1_000_000;
+                        let end = start + TICK * TWAP_WINDOW_SECS as i64;
+                        raw_outputs(&[
+                            ethabi::Token::Array(vec![int_token(start), int_token(end)]),
+                            ethabi::Token::Array(vec![
+                                ethabi::Token::Uint(0.into()),
+                                ethabi::Token::Uint(0.into()),
+                            ]),
+                        ])
+                    }
+                    _ => unreachable!(),
+                }
+            })
+            .build();
+        Box::new(mock.into_client())
+    }
+
+    fn config() -> UniswapV3TwapConfig {
+        UniswapV3TwapConfig {
+            token_address: TOKEN_ADDRESS,
+            pool_address: POOL_ADDRESS,
+            twap_window: Duration::from_secs(TWAP_WINDOW_SECS),
+            max_staleness: Duration::from_secs(3_600),
+        }
+    }
+
+    fn now() -> u64 {
+        Utc::now().timestamp() as u64
+    }
+
+    #[tokio::test]
+    async fn fetching_ratio_from_pool() {
+        let client = UniswapV3TwapPriceClient::new(
+            config(),
+            mock_client(TOKEN_ADDRESS, WETH_ADDRESS, now() - 60),
+        );
+        let ratio = client
+            .fetch_ratio(APIToken::ERC20(TOKEN_ADDRESS))
+            .await
+            .unwrap();
+        let value = approximate_value(&ratio);
+        assert!((value - 400.0).abs() < 0.1, "{value}");
+
+        let ratio = client.fetch_ratio(APIToken::Eth).await.unwrap();
+        assert_eq!(ratio.ratio, BaseTokenApiRatio::identity().ratio);
+        client.fetch_ratio(APIToken::ZK).await.unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn rejecting_stale_pool_or_unrelated_pool() {
+        let client = UniswapV3TwapPriceClient::new(
+            config(),
+            mock_client(TOKEN_ADDRESS, WETH_ADDRESS, now() - 7_200),
+        );
+        let err = client
+            .fetch_ratio(APIToken::ERC20(TOKEN_ADDRESS))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("exceeds max staleness"), "{err}");
+
+        let client = UniswapV3TwapPriceClient::new(
+            config(),
+            mock_client(WETH_ADDRESS, Address::repeat_byte(4), now()),
+        );
+        let err = client
+            .fetch_ratio(APIToken::ERC20(TOKEN_ADDRESS))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("doesn't contain base token"), "{err}");
+    }
+}
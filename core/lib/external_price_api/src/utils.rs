@@ -1,8 +1,12 @@
-use std::num::NonZeroU64;
+use std::{num::NonZeroU64, time::Duration};
 
-use chrono::Utc;
+use anyhow::Context as _;
+use chrono::{DateTime, Utc};
 use fraction::GenericFraction;
-use zksync_types::{base_token_ratio::BaseTokenApiRatio, fee_model::ConversionRatio};
+use zksync_eth_client::EthInterface;
+use zksync_types::{
+    base_token_ratio::BaseTokenApiRatio, ethabi, fee_model::ConversionRatio, web3, Address,
+};
 
 /// Using the base token price and eth price, calculate the fraction of the base token to eth.
 pub fn get_fraction(ratio_f64: f64) -> anyhow::Result<(NonZeroU64, NonZeroU64)> {
@@ -51,6 +55,45 @@ pub fn eth_price_to_base_token_ratio(price: f64) -> anyhow::Result<BaseTokenApiR
     })
 }
 
+/// Calls a view function of an L1 contract at the latest block and decodes its outputs.
+pub(crate) async fn call_view_function(
+    client: &dyn EthInterface,
+    contract_address: Address,
+    function: &ethabi::Function,
+    params: &[ethabi::Token],
+) -> anyhow::Result<Vec<ethabi::Token>> {
+    let request = web3::CallRequest {
+        to: Some(contract_address),
+        data: Some(web3::Bytes(function.encode_input(params)?)),
+        ..web3::CallRequest::default()
+    };
+    let output = client
+        .call_contract_function(request, None)
+        .await
+        .with_context(|| format!("failed calling `{}` on {contract_address:?}", function.name))?;
+    function
+        .decode_output(&output.0)
+        .with_context(|| format!("failed decoding `{}` output", function.name))
+}
+
+/// Checks that an on-chain price updated at `updated_at` (a UNIX timestamp in seconds) is not older than `max_staleness`.
+/// Returns the update timestamp.
+pub(crate) fn check_staleness(
+    updated_at: u64,
+    max_staleness: Duration,
+) -> anyhow::Result<DateTime<Utc>> {
+    let updated_at = i64::try_from(updated_at)
+        .ok()
+        .and_then(|timestamp| DateTime::<Utc>::from_timestamp(timestamp, 0))
+        .with_context(|| format!("invalid price update timestamp: {updated_at}"))?;
+    let age = (Utc::now() - updated_at).to_std().unwrap_or_default();
+    anyhow::ensure!(
+        age <= max_staleness,
+        "price was last updated at {updated_at} ({age:?} ago), which exceeds max staleness {max_staleness:?}"
+    );
+    Ok(updated_at)
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use super::*;
@@ -1,8 +1,10 @@
-use std::collections::{hash_map, BTreeMap, BTreeSet, HashMap};
+use std::collections::{hash_map, BTreeMap, BTreeSet, HashMap, VecDeque};
 
 use zksync_types::{
-    l1::L1Tx, l2::L2Tx, Address, ExecuteTransactionCommon, Nonce, PriorityOpId, ProtocolVersionId,
-    Transaction, TransactionTimeRangeConstraint,
+    l1::L1Tx,
+    l2::{bundle::L2TxBundle, L2Tx},
+    Address, ExecuteTransactionCommon, Nonce, PriorityOpId, ProtocolVersionId, Transaction,
+    TransactionTimeRangeConstraint, H256,
 };
 
 use crate::types::{AccountTransactions, AdvanceInput, L2TxFilter, MempoolScore};
@@ -11,6 +13,8 @@ use crate::types::{AccountTransactions, AdvanceInput, L2TxFilter, MempoolScore};
 pub struct MempoolInfo {
     pub stashed_accounts: Vec<Address>,
     pub purged_accounts: Vec<Address>,
+    /// Hashes of transaction bundles purged from the mempool.
+    pub purged_bundles: Vec<H256>,
 }
 
 #[derive(Debug)]
@@ -18,6 +22,7 @@ pub struct MempoolStats {
     pub l1_transaction_count: usize,
     pub l2_transaction_count: u64,
     pub l2_priority_queue_size: usize,
+    pub bundle_count: usize,
 }
 
 #[derive(Debug)]
@@ -39,7 +44,12 @@ pub struct MempoolStore {
     /// Next priority operation
     next_priority_id: PriorityOpId,
     stashed_accounts: Vec<Address>,
-    /// Number of L2 transactions in the mempool.
+    /// Pending atomic transaction bundles in the order of their arrival. Bundles are not tracked in per-account queues;
+    /// their transactions count towards the mempool capacity.
+    bundles: VecDeque<(L2TxBundle, Vec<TransactionTimeRangeConstraint>)>,
+    /// Number of transactions in `bundles`.
+    bundles_size: u64,
+    /// Number of L2 transactions in the mempool, excluding bundle transactions.
     size: u64,
     capacity: u64,
 }
@@ -61,6 +71,8 @@ impl MempoolStore {
             high_priority_l2_tx_protocol_version,
             next_priority_id,
             stashed_accounts: vec![],
+            bundles: VecDeque::new(),
+            bundles_size: 0,
             size: 0,
             capacity,
         }
@@ -283,6 +295,115 @@ impl MempoolStore {
         Some((transaction.into(), constraint))
     }
 
+    /// Returns up to `limit` L2 transactions that are likely to be returned by [`Self::next_transaction()`] next,
+    /// without removing them from the mempool. Only the next transaction for each account is returned,
+    /// since subsequent transactions depend on the results of the preceding ones.
+    pub fn peek_l2_transactions(&self, filter: &L2TxFilter, limit: usize) -> Vec<Transaction> {
+        let include_high_priority = self
+            .high_priority_l2_tx_protocol_version
+            .is_some_and(|version| filter.protocol_version >= version);
+        let high_priority_txs = include_high_priority
+            .then(|| {
+                Self::peek_l2_queue(
+                    &self.high_priority_l2_transactions_per_account,
+                    &self.high_priority_l2_priority_queue,
+                    filter,
+                )
+            })
+            .into_iter()
+            .flatten();
+        let txs = Self::peek_l2_queue(
+            &self.l2_transactions_per_account,
+            &self.l2_priority_queue,
+            filter,
+        );
+        high_priority_txs
+            .chain(txs)
+            .take(limit)
+            .map(|tx| tx.clone().into())
+            .collect()
+    }
+
+    fn peek_l2_queue<'a>(
+        txs_per_account: &'a HashMap<Address, AccountTransactions>,
+        priority_queue: &'a BTreeSet<MempoolScore>,
+        filter: &'a L2TxFilter,
+    ) -> impl Iterator<Item = &'a L2Tx> + 'a {
+        priority_queue
+            .iter()
+            .rev()
+            .filter(|score| score.matches_filter(filter))
+            .filter_map(|score| txs_per_account.get(&score.account)?.peek())
+    }
+
+    /// Inserts atomic transaction bundles to the mempool. Each bundle must be accompanied by time range constraints
+    /// for all its transactions.
+    pub fn insert_bundles(
+        &mut self,
+        bundles: Vec<(L2TxBundle, Vec<TransactionTimeRangeConstraint>)>,
+    ) {
+        for (bundle, constraints) in bundles {
+            assert_eq!(
+                bundle.len(),
+                constraints.len(),
+                "mempool: invalid bundle constraints"
+            );
+            tracing::trace!("inserting transaction bundle {:?}", bundle.hash());
+            self.bundles_size += bundle.len() as u64;
+            self.bundles.push_back((bundle, constraints));
+        }
+    }
+
+    /// Returns `true` if there is a transaction bundle in the mempool all transactions of which satisfy the filter.
+    /// Bundles are only served after all pending L1 transactions, so this returns `false` if there's a pending L1 transaction.
+    pub fn has_next_bundle(&self, filter: &L2TxFilter) -> bool {
+        !self.has_pending_l1_transaction()
+            && self
+                .bundles
+                .iter()
+                .any(|(bundle, _)| Self::bundle_matches_filter(bundle, filter))
+    }
+
+    /// Returns the earliest transaction bundle all transactions of which satisfy the filter. Like [`Self::has_next_bundle()`],
+    /// returns `None` if there's a pending L1 transaction.
+    pub fn next_bundle(
+        &mut self,
+        filter: &L2TxFilter,
+    ) -> Option<(L2TxBundle, Vec<TransactionTimeRangeConstraint>)> {
+        if self.has_pending_l1_transaction() {
+            return None;
+        }
+        let position = self
+            .bundles
+            .iter()
+            .position(|(bundle, _)| Self::bundle_matches_filter(bundle, filter))?;
+        let (bundle, constraints) = self.bundles.remove(position)?;
+        self.bundles_size -= bundle.len() as u64;
+        Some((bundle, constraints))
+    }
+
+    fn has_pending_l1_transaction(&self) -> bool {
+        self.l1_transactions.contains_key(&self.next_priority_id)
+    }
+
+    fn bundle_matches_filter(bundle: &L2TxBundle, filter: &L2TxFilter) -> bool {
+        bundle.txs().iter().all(|tx| {
+            tx.common_data.fee.max_fee_per_gas >= filter.fee_per_gas.into()
+                && tx.common_data.fee.gas_per_pubdata_limit >= filter.gas_per_pubdata.into()
+        })
+    }
+
+    /// Returns a bundle previously obtained via [`Self::next_bundle()`] back to the mempool, e.g. after
+    /// the state keeper has rolled it back. The bundle will be returned first on the next call.
+    pub fn rollback_bundle(
+        &mut self,
+        bundle: L2TxBundle,
+        constraints: Vec<TransactionTimeRangeConstraint>,
+    ) {
+        self.bundles_size += bundle.len() as u64;
+        self.bundles.push_front((bundle, constraints));
+    }
+
     /// When a state_keeper starts the block over after a rejected transaction,
     /// we have to rollback the nonces/ids in the mempool and
     /// reinsert the transactions from the block back into mempool.
@@ -347,9 +468,11 @@ impl MempoolStore {
     }
 
     pub fn get_mempool_info(&mut self) -> MempoolInfo {
+        let purged_bundles = self.gc_bundles();
         MempoolInfo {
             stashed_accounts: std::mem::take(&mut self.stashed_accounts),
             purged_accounts: self.gc(),
+            purged_bundles,
         }
     }
 
@@ -358,7 +481,28 @@ impl MempoolStore {
             l1_transaction_count: self.l1_transactions.len(),
             l2_transaction_count: self.size,
             l2_priority_queue_size: self.l2_priority_queue.len(),
+            bundle_count: self.bundles.len(),
+        }
+    }
+
+    /// Purges the most recently received bundles until the mempool fits into its capacity. Bundles are purged
+    /// before regular transactions, so that submitting bundles cannot evict regular transactions from the mempool.
+    fn gc_bundles(&mut self) -> Vec<H256> {
+        let mut purged = vec![];
+        while self.size + self.bundles_size > self.capacity {
+            let Some((bundle, _)) = self.bundles.pop_back() else {
+                break;
+            };
+            self.bundles_size -= bundle.len() as u64;
+            purged.push(bundle.hash());
+        }
+        if !purged.is_empty() {
+            tracing::debug!(
+                "Purged {} transaction bundles exceeding mempool capacity",
+                purged.len()
+            );
         }
+        purged
     }
 
     fn gc(&mut self) -> Vec<Address> {
@@ -7,7 +7,7 @@ use zksync_types::{
     fee::Fee,
     helpers::unix_timestamp_ms,
     l1::{OpProcessingType, PriorityQueueType},
-    l2::L2Tx,
+    l2::{bundle::L2TxBundle, L2Tx},
     Address, Execute, ExecuteTransactionCommon, L1TxCommonData, Nonce, PriorityOpId,
     ProtocolVersionId, Transaction, TransactionTimeRangeConstraint, H256, U256,
 };
@@ -218,6 +218,140 @@ fn two_ready_txs() {
     );
 }
 
+#[test]
+fn peeking_l2_transactions() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100, None, None);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let transactions = vec![
+        gen_l2_tx_with_timestamp(account0, Nonce(0), 1),
+        gen_l2_tx_with_timestamp(account0, Nonce(1), 2),
+        gen_l2_tx_with_timestamp(account1, Nonce(0), 3),
+    ];
+    mempool.insert_without_constraints(transactions, HashMap::new());
+
+    let filter = L2TxFilter::default();
+    let peeked = mempool.peek_l2_transactions(&filter, 10);
+    let peeked: Vec<_> = peeked
+        .iter()
+        .map(|tx| (tx.initiator_account(), tx.nonce().unwrap().0))
+        .collect();
+    // Only the next transaction is returned for each account.
+    assert_eq!(peeked, [(account0, 0), (account1, 0)]);
+    assert_eq!(mempool.peek_l2_transactions(&filter, 1).len(), 1);
+    // Peeking doesn't modify the mempool.
+    assert_eq!(mempool.stats().l2_transaction_count, 3);
+
+    assert_eq!(view(mempool.next_transaction(&filter)), (account0, 0));
+    let peeked = mempool.peek_l2_transactions(&filter, 10);
+    let peeked: Vec<_> = peeked
+        .iter()
+        .map(|tx| (tx.initiator_account(), tx.nonce().unwrap().0))
+        .collect();
+    assert_eq!(peeked, [(account0, 1), (account1, 0)]);
+}
+
+#[test]
+fn transaction_bundles() {
+    let filter_non_zero = L2TxFilter {
+        fee_input: Default::default(),
+        fee_per_gas: 0u64,
+        gas_per_pubdata: 1u32,
+        protocol_version: ProtocolVersionId::latest(),
+    };
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100, None, None);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    // The first bundle contains a transaction not matching the non-zero filter.
+    let (first_bundle, first_constraints) = gen_bundle(gen_transactions_for_filtering(vec![
+        (account0, Nonce(0), unix_timestamp_ms(), 1),
+        (account0, Nonce(1), unix_timestamp_ms(), 0),
+    ]));
+    let (second_bundle, second_constraints) = gen_bundle(gen_transactions_for_filtering(vec![
+        (account1, Nonce(0), unix_timestamp_ms(), 1),
+        (account1, Nonce(1), unix_timestamp_ms(), 1),
+    ]));
+    mempool.insert_bundles(vec![
+        (first_bundle.clone(), first_constraints),
+        (second_bundle.clone(), second_constraints),
+    ]);
+    assert_eq!(mempool.stats().bundle_count, 2);
+    // Bundles are not accessible as separate transactions.
+    assert!(!mempool.has_next(&L2TxFilter::default()));
+    assert_eq!(mempool.stats().l2_transaction_count, 0);
+
+    assert!(mempool.has_next_bundle(&filter_non_zero));
+    let (bundle, constraints) = mempool.next_bundle(&filter_non_zero).unwrap();
+    assert_eq!(bundle, second_bundle);
+    assert!(!mempool.has_next_bundle(&filter_non_zero));
+    assert!(mempool.next_bundle(&filter_non_zero).is_none());
+
+    mempool.rollback_bundle(bundle, constraints);
+    let (bundle, _) = mempool.next_bundle(&L2TxFilter::default()).unwrap();
+    assert_eq!(bundle, second_bundle);
+    let (bundle, _) = mempool.next_bundle(&L2TxFilter::default()).unwrap();
+    assert_eq!(bundle, first_bundle);
+    assert_eq!(mempool.stats().bundle_count, 0);
+}
+
+#[test]
+fn l1_transactions_are_served_before_bundles() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100, None, None);
+    let (bundle, constraints) = gen_bundle(vec![
+        gen_l2_tx(Address::random(), Nonce(0)),
+        gen_l2_tx(Address::random(), Nonce(0)),
+    ]);
+    mempool.insert_bundles(vec![(bundle.clone(), constraints)]);
+    mempool.insert_without_constraints(vec![gen_l1_tx(PriorityOpId(0), None)], HashMap::new());
+
+    let filter = L2TxFilter::default();
+    assert!(!mempool.has_next_bundle(&filter));
+    assert!(mempool.next_bundle(&filter).is_none());
+    let (tx, _) = mempool.next_transaction(&filter).unwrap();
+    assert!(tx.is_l1());
+
+    assert!(mempool.has_next_bundle(&filter));
+    let (next_bundle, _) = mempool.next_bundle(&filter).unwrap();
+    assert_eq!(next_bundle, bundle);
+}
+
+#[test]
+fn bundles_count_towards_capacity() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 4, None, None);
+    let bundles: Vec<_> = (0..3)
+        .map(|_| {
+            gen_bundle(vec![
+                gen_l2_tx(Address::random(), Nonce(0)),
+                gen_l2_tx(Address::random(), Nonce(0)),
+            ])
+        })
+        .collect();
+    let bundle_hashes: Vec<_> = bundles.iter().map(|(bundle, _)| bundle.hash()).collect();
+    mempool.insert_bundles(bundles);
+    let account = Address::random();
+    mempool.insert_without_constraints(
+        vec![gen_l2_tx(account, Nonce(0)), gen_l2_tx(account, Nonce(1))],
+        HashMap::new(),
+    );
+
+    // The most recent bundles must be purged first; regular transactions must be retained.
+    let info = mempool.get_mempool_info();
+    assert_eq!(
+        info.purged_bundles,
+        bundle_hashes[1..].iter().rev().copied().collect::<Vec<_>>()
+    );
+    assert!(info.purged_accounts.is_empty());
+    assert_eq!(mempool.stats().bundle_count, 1);
+    assert_eq!(mempool.stats().l2_transaction_count, 2);
+
+    let (bundle, constraints) = mempool.next_bundle(&L2TxFilter::default()).unwrap();
+    assert_eq!(bundle.hash(), bundle_hashes[0]);
+    mempool.rollback_bundle(bundle, constraints);
+    let info = mempool.get_mempool_info();
+    assert!(info.purged_bundles.is_empty());
+    assert_eq!(mempool.stats().bundle_count, 1);
+}
+
 #[test]
 fn mempool_size() {
     let mut mempool = MempoolStore::new(PriorityOpId(0), 100, None, None);
@@ -632,6 +766,21 @@ fn gen_l2_tx_with_timestamp(address: Address, nonce: Nonce, received_at_ms: u64)
     txn.into()
 }
 
+fn gen_bundle(txs: Vec<Transaction>) -> (L2TxBundle, Vec<TransactionTimeRangeConstraint>) {
+    let txs = txs
+        .into_iter()
+        .map(|tx| {
+            let mut tx = L2Tx::try_from(tx).unwrap();
+            // Bundles are identified by transaction hashes, so transactions must have input data.
+            tx.set_input(vec![], H256::random());
+            tx
+        })
+        .collect();
+    let bundle = L2TxBundle::new(txs);
+    let constraints = vec![TransactionTimeRangeConstraint::default(); bundle.len()];
+    (bundle, constraints)
+}
+
 fn gen_l1_tx(priority_id: PriorityOpId, address: Option<Address>) -> Transaction {
     let execute = Execute {
         contract_address: Some(Address::repeat_byte(0x11)),
@@ -103,6 +103,11 @@ impl AccountTransactions {
             .map(|(tx, c)| (Self::score_for_transaction(tx), c.clone()))
     }
 
+    /// Returns the next transaction to be included in block without removing it.
+    pub fn peek(&self) -> Option<&L2Tx> {
+        self.transactions.get(&self.nonce).map(|(tx, _)| tx)
+    }
+
     pub fn len(&self) -> usize {
         self.transactions.len()
     }
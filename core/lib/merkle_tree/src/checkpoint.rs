@@ -0,0 +1,501 @@
+//! Merkle tree checkpoints.
+//!
+//! # Overview
+//!
+//! A **checkpoint** is a copy of all tree nodes reachable from the root of a specific tree version, in the same
+//! raw form as they are stored in RocksDB. Unlike [recovery](crate::recovery), importing a checkpoint doesn't require
+//! building the tree from its entries; nodes are written to the database as is. An imported tree is identical
+//! to the original tree with all versions before the checkpoint version pruned.
+//!
+//! Checkpoints are exported in chunks using [`TreeCheckpointExporter`] and imported using [`TreeCheckpointImporter`].
+//!
+//! # Integrity
+//!
+//! The exporter outputs nodes so that a parent node always precedes its children. This allows the importer
+//! to authenticate each imported node using the hash in the child reference of its parent, and the root node
+//! using the externally provided root hash. Thus, the imported tree is guaranteed to be valid provided that chunks
+//! are imported in the same order they were exported. Each node is hashed only once, so import is much cheaper
+//! than recovery or [verifying tree consistency](crate::MerkleTree::verify_consistency()).
+
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+use zksync_crypto_primitives::hasher::blake2::Blake2Hasher;
+
+use crate::{
+    hasher::HasherWithStats,
+    storage::{Database, PatchSet, RocksDBWrapper},
+    types::{
+        ChildRef, InternalNode, Manifest, Nibbles, Node, NodeKey, Root, TreeTags, ValueHash,
+        KEY_SIZE,
+    },
+};
+
+/// Tree node in the raw form, as it is stored in RocksDB.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckpointNode {
+    /// Raw node key (tree version + nibbles).
+    pub key: Vec<u8>,
+    /// Serialized node.
+    pub value: Vec<u8>,
+}
+
+/// General information about a tree checkpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckpointInfo {
+    /// Tree version of the checkpoint.
+    pub version: u64,
+    /// Root hash of the tree at the checkpoint version.
+    pub root_hash: ValueHash,
+    /// Number of leaves in the tree at the checkpoint version.
+    pub leaf_count: u64,
+}
+
+/// Exporter of a [checkpoint](self) for a certain tree version.
+///
+/// The exporter can be used on a tree that is being updated concurrently. Pruning the checkpoint version
+/// while the checkpoint is being exported will lead to an export error.
+#[derive(Debug)]
+pub struct TreeCheckpointExporter {
+    db: RocksDBWrapper,
+    info: CheckpointInfo,
+    is_root_exported: bool,
+    /// Keys of the nodes to be exported together with an indicator whether the node is a leaf.
+    /// Used as a stack, which makes the export order depth-first and bounds the stack size by ~16 * tree depth.
+    pending_keys: Vec<(NodeKey, bool)>,
+}
+
+impl TreeCheckpointExporter {
+    /// Creates an exporter for the specified tree `version`.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the tree is being recovered, or if the requested version is missing or pruned.
+    pub fn new(db: RocksDBWrapper, version: u64) -> anyhow::Result<Self> {
+        let manifest = db.try_manifest()?.context("Merkle tree is empty")?;
+        anyhow::ensure!(
+            manifest.recovered_version().is_none(),
+            "Cannot export a checkpoint for a tree being recovered"
+        );
+        if let Some(tags) = &manifest.tags {
+            tags.ensure_consistency(&Blake2Hasher, false)?;
+        }
+        anyhow::ensure!(
+            version < manifest.version_count,
+            "Requested tree version {version} is not present in the tree; it has {} versions",
+            manifest.version_count
+        );
+        let root = db.try_root(version)?.with_context(|| {
+            format!("Root for tree version {version} is missing; the version is probably pruned")
+        })?;
+
+        let info = CheckpointInfo {
+            version,
+            root_hash: root.hash(&Blake2Hasher),
+            leaf_count: root.leaf_count(),
+        };
+        let mut pending_keys = vec![];
+        if let Root::Filled {
+            node: Node::Internal(node),
+            ..
+        } = &root
+        {
+            pending_keys.extend(child_keys(Nibbles::EMPTY, node));
+        }
+
+        Ok(Self {
+            db,
+            info,
+            is_root_exported: false,
+            pending_keys,
+        })
+    }
+
+    /// Returns information about the exported checkpoint.
+    pub fn info(&self) -> CheckpointInfo {
+        self.info
+    }
+
+    /// Exports the next chunk of nodes containing at most `max_node_count` nodes. Returns `Ok(None)`
+    /// once all nodes are exported.
+    ///
+    /// # Errors
+    ///
+    /// Errors if a node cannot be read from the database, e.g. because it was pruned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_node_count` is zero.
+    pub fn next_chunk(
+        &mut self,
+        max_node_count: usize,
+    ) -> anyhow::Result<Option<Vec<CheckpointNode>>> {
+        assert!(max_node_count > 0, "Chunk must contain at least 1 node");
+
+        let mut chunk = Vec::with_capacity(max_node_count.min(self.pending_keys.len() + 1));
+        if !self.is_root_exported {
+            let root_key = NodeKey::empty(self.info.version);
+            let raw_root = self.db.raw_nodes(&[root_key]).pop().flatten();
+            let raw_root = raw_root.with_context(|| {
+                format!("Root {root_key} disappeared; the version is probably pruned")
+            })?;
+            chunk.push(CheckpointNode {
+                key: root_key.to_db_key(),
+                value: raw_root.to_vec(),
+            });
+            self.is_root_exported = true;
+        }
+
+        while chunk.len() < max_node_count && !self.pending_keys.is_empty() {
+            let batch_len = (max_node_count - chunk.len()).min(self.pending_keys.len());
+            let keys = self
+                .pending_keys
+                .split_off(self.pending_keys.len() - batch_len);
+            let raw_nodes = self.db.raw_nodes(&keys);
+
+            for ((key, is_leaf), raw_node) in keys.into_iter().zip(raw_nodes) {
+                let raw_node = raw_node.with_context(|| {
+                    format!("Node {key} is missing; the exported version is probably pruned")
+                })?;
+                if !is_leaf {
+                    let node = RocksDBWrapper::deserialize_node(&raw_node, &key, false)?;
+                    if let Node::Internal(node) = &node {
+                        self.pending_keys.extend(child_keys(key.nibbles, node));
+                    }
+                }
+                chunk.push(CheckpointNode {
+                    key: key.to_db_key(),
+                    value: raw_node.to_vec(),
+                });
+            }
+        }
+        Ok(if chunk.is_empty() { None } else { Some(chunk) })
+    }
+}
+
+fn child_keys(nibbles: Nibbles, node: &InternalNode) -> impl Iterator<Item = (NodeKey, bool)> + '_ {
+    node.children().map(move |(nibble, child_ref)| {
+        let child_nibbles = nibbles
+            .push(nibble)
+            .expect("internal node at terminal level");
+        (
+            child_nibbles.with_version(child_ref.version),
+            child_ref.is_leaf,
+        )
+    })
+}
+
+/// Importer of a [checkpoint](self) into an empty tree.
+///
+/// If an import error occurs, the importer should be discarded; the database may be reused
+/// for importing the same checkpoint from scratch.
+#[derive(Debug)]
+pub struct TreeCheckpointImporter {
+    db: RocksDBWrapper,
+    version: u64,
+    expected_root_hash: ValueHash,
+    leaf_count: Option<u64>,
+    /// Child references of the imported internal nodes that were not matched with imported nodes yet.
+    pending_refs: HashMap<NodeKey, ChildRef>,
+    imported_node_count: u64,
+}
+
+impl TreeCheckpointImporter {
+    /// Creates an importer for a checkpoint with the specified tree `version` and root hash.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the tree is not empty.
+    pub fn new(
+        db: RocksDBWrapper,
+        version: u64,
+        expected_root_hash: ValueHash,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            db.try_manifest()?.is_none(),
+            "Merkle tree is not empty; a checkpoint can only be imported into an empty tree"
+        );
+        Ok(Self {
+            db,
+            version,
+            expected_root_hash,
+            leaf_count: None,
+            pending_refs: HashMap::new(),
+            imported_node_count: 0,
+        })
+    }
+
+    /// Returns the number of nodes imported so far.
+    pub fn imported_node_count(&self) -> u64 {
+        self.imported_node_count
+    }
+
+    /// Imports the next chunk of checkpoint nodes. Chunks must be imported in the order they were exported.
+    ///
+    /// # Errors
+    ///
+    /// Errors if any of the nodes in the chunk cannot be authenticated, or on database I/O errors.
+    pub fn import_chunk(&mut self, nodes: &[CheckpointNode]) -> anyhow::Result<()> {
+        let mut hasher = HasherWithStats::new(&Blake2Hasher);
+        for node in nodes {
+            self.verify_node(node, &mut hasher)?;
+        }
+        self.db.write_raw_nodes(nodes)?;
+        self.imported_node_count += nodes.len() as u64;
+        Ok(())
+    }
+
+    fn verify_node(
+        &mut self,
+        node: &CheckpointNode,
+        hasher: &mut HasherWithStats<'_>,
+    ) -> anyhow::Result<()> {
+        let key = parse_node_key(&node.key)?;
+        anyhow::ensure!(
+            key.version <= self.version,
+            "Node {key} has version greater than the checkpoint version {}",
+            self.version
+        );
+
+        if self.leaf_count.is_none() {
+            anyhow::ensure!(
+                key == NodeKey::empty(self.version),
+                "Checkpoint must start with the root node {}, got {key}",
+                NodeKey::empty(self.version)
+            );
+            let root = Root::deserialize(&node.value, false)?;
+            let root_hash = root.hash(&Blake2Hasher);
+            anyhow::ensure!(
+                root_hash == self.expected_root_hash,
+                "Checkpoint root hash {root_hash:?} differs from the expected root hash {:?}",
+                self.expected_root_hash
+            );
+            if let Root::Filled {
+                node: Node::Internal(node),
+                ..
+            } = &root
+            {
+                self.add_child_refs(Nibbles::EMPTY, node);
+            }
+            self.leaf_count = Some(root.leaf_count());
+            return Ok(());
+        }
+
+        let child_ref = self.pending_refs.remove(&key).with_context(|| {
+            format!("Node {key} is not referenced by any previously imported node")
+        })?;
+        let tree_node = RocksDBWrapper::deserialize_node(&node.value, &key, child_ref.is_leaf)?;
+        let nibble_count = key.nibbles.nibble_count();
+        if let Node::Leaf(leaf) = &tree_node {
+            anyhow::ensure!(
+                Nibbles::new(&leaf.full_key, nibble_count) == key.nibbles,
+                "Full key {:0>64x} of leaf {key} doesn't match the leaf position in the tree",
+                leaf.full_key
+            );
+        }
+        let hash = tree_node.hash(hasher, nibble_count * 4);
+        anyhow::ensure!(
+            hash == child_ref.hash,
+            "Hash of node {key} ({hash:?}) differs from the hash in its parent reference ({:?})",
+            child_ref.hash
+        );
+        if let Node::Internal(node) = &tree_node {
+            self.add_child_refs(key.nibbles, node);
+        }
+        Ok(())
+    }
+
+    fn add_child_refs(&mut self, nibbles: Nibbles, node: &InternalNode) {
+        for (nibble, child_ref) in node.children() {
+            let Some(child_nibbles) = nibbles.push(nibble) else {
+                continue; // will be caught as a hash mismatch, since internal nodes cannot be at the terminal level
+            };
+            self.pending_refs
+                .insert(child_nibbles.with_version(child_ref.version), *child_ref);
+        }
+    }
+
+    /// Finalizes the import, checking that all nodes were imported and marking the tree as initialized.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the imported checkpoint is incomplete, or on database I/O errors.
+    pub fn finalize(mut self) -> anyhow::Result<RocksDBWrapper> {
+        let leaf_count = self
+            .leaf_count
+            .context("Checkpoint doesn't contain any nodes")?;
+        if let Some(missing_key) = self.pending_refs.keys().next() {
+            anyhow::bail!(
+                "Checkpoint is incomplete: {} referenced nodes are missing, e.g. {missing_key}",
+                self.pending_refs.len()
+            );
+        }
+        tracing::debug!(
+            "Finalizing import of Merkle tree checkpoint for version {} with {leaf_count} leaves and {} nodes",
+            self.version,
+            self.imported_node_count
+        );
+
+        let manifest = Manifest {
+            version_count: self.version + 1,
+            tags: Some(TreeTags::new(&Blake2Hasher)),
+        };
+        self.db.apply_patch(PatchSet::from_manifest(manifest))?;
+        Ok(self.db)
+    }
+}
+
+fn parse_node_key(raw_key: &[u8]) -> anyhow::Result<NodeKey> {
+    anyhow::ensure!(raw_key.len() >= 9, "Node key is too short");
+    let nibble_count = usize::from(raw_key[8]);
+    anyhow::ensure!(
+        nibble_count <= 2 * KEY_SIZE && raw_key.len() == 9 + nibble_count.div_ceil(2),
+        "Node key {raw_key:?} is malformed"
+    );
+    Ok(NodeKey::from_db_key(raw_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::{Key, MerkleTree, TreeEntry};
+
+    fn create_tree(dir: &TempDir) -> MerkleTree<RocksDBWrapper> {
+        let db = RocksDBWrapper::new(dir.path()).unwrap();
+        let mut tree = MerkleTree::new(db).unwrap();
+        for version in 0..3_u64 {
+            let entries = (0..100_u64).map(|i| {
+                let key = Key::from(i * 1_000 + version);
+                TreeEntry::new(key, version * 100 + i + 1, ValueHash::from_low_u64_be(i))
+            });
+            tree.extend(entries.collect()).unwrap();
+        }
+        tree
+    }
+
+    fn export_checkpoint(
+        db: RocksDBWrapper,
+        version: u64,
+    ) -> (CheckpointInfo, Vec<Vec<CheckpointNode>>) {
+        let mut exporter = TreeCheckpointExporter::new(db, version).unwrap();
+        let mut chunks = vec![];
+        while let Some(chunk) = exporter.next_chunk(10).unwrap() {
+            assert!(chunk.len() <= 10);
+            chunks.push(chunk);
+        }
+        (exporter.info(), chunks)
+    }
+
+    #[test]
+    fn exporting_and_importing_checkpoint() {
+        let dir = TempDir::new().unwrap();
+        let tree = create_tree(&dir);
+        let (info, chunks) = export_checkpoint(tree.db.clone(), 1);
+        assert_eq!(info.version, 1);
+        assert_eq!(info.root_hash, tree.root_hash(1).unwrap());
+        assert_eq!(info.leaf_count, 200);
+
+        let imported_dir = TempDir::new().unwrap();
+        let db = RocksDBWrapper::new(imported_dir.path()).unwrap();
+        let mut importer = TreeCheckpointImporter::new(db, 1, info.root_hash).unwrap();
+        for chunk in &chunks {
+            importer.import_chunk(chunk).unwrap();
+        }
+        let node_count: usize = chunks.iter().map(Vec::len).sum();
+        assert_eq!(importer.imported_node_count(), node_count as u64);
+        let db = importer.finalize().unwrap();
+
+        let imported_tree = MerkleTree::new(db).unwrap();
+        assert_eq!(imported_tree.latest_version(), Some(1));
+        assert_eq!(imported_tree.latest_root_hash(), info.root_hash);
+        assert_eq!(imported_tree.first_retained_version(), Some(1));
+        imported_tree.verify_consistency(1, true).unwrap();
+
+        let keys: Vec<_> = (0..100_u64)
+            .flat_map(|i| [Key::from(i * 1_000), Key::from(i * 1_000 + 2)])
+            .collect();
+        let expected_entries = tree.entries(1, &keys).unwrap();
+        assert_eq!(imported_tree.entries(1, &keys).unwrap(), expected_entries);
+    }
+
+    #[test]
+    fn extending_imported_tree() {
+        let dir = TempDir::new().unwrap();
+        let mut tree = create_tree(&dir);
+        let (info, chunks) = export_checkpoint(tree.db.clone(), 2);
+
+        let imported_dir = TempDir::new().unwrap();
+        let db = RocksDBWrapper::new(imported_dir.path()).unwrap();
+        let mut importer = TreeCheckpointImporter::new(db, 2, info.root_hash).unwrap();
+        for chunk in &chunks {
+            importer.import_chunk(chunk).unwrap();
+        }
+        let mut imported_tree = MerkleTree::new(importer.finalize().unwrap()).unwrap();
+
+        let new_entries: Vec<_> = (0..50_u64)
+            .map(|i| TreeEntry::new(Key::from(i * 1_000 + 3), 301 + i, ValueHash::zero()))
+            .collect();
+        let output = tree.extend(new_entries.clone()).unwrap();
+        let imported_output = imported_tree.extend(new_entries).unwrap();
+        assert_eq!(imported_output.root_hash, output.root_hash);
+        imported_tree.verify_consistency(3, true).unwrap();
+    }
+
+    #[test]
+    fn importing_checkpoint_with_unexpected_root_hash() {
+        let dir = TempDir::new().unwrap();
+        let tree = create_tree(&dir);
+        let (_, chunks) = export_checkpoint(tree.db.clone(), 1);
+
+        let imported_dir = TempDir::new().unwrap();
+        let db = RocksDBWrapper::new(imported_dir.path()).unwrap();
+        let mut importer = TreeCheckpointImporter::new(db, 1, ValueHash::zero()).unwrap();
+        let err = importer.import_chunk(&chunks[0]).unwrap_err().to_string();
+        assert!(err.contains("differs from the expected root hash"), "{err}");
+    }
+
+    #[test]
+    fn importing_tampered_checkpoint() {
+        let dir = TempDir::new().unwrap();
+        let tree = create_tree(&dir);
+        let (info, mut chunks) = export_checkpoint(tree.db.clone(), 1);
+        // Change the value hash of the leaf with the zero key.
+        let tampered_leaf = chunks
+            .iter_mut()
+            .flatten()
+            .find(|node| node.value.len() > 64 && node.value[..32] == [0; 32])
+            .unwrap();
+        tampered_leaf.value[40] ^= 1;
+
+        let imported_dir = TempDir::new().unwrap();
+        let db = RocksDBWrapper::new(imported_dir.path()).unwrap();
+        let mut importer = TreeCheckpointImporter::new(db, 1, info.root_hash).unwrap();
+        let err = chunks
+            .iter()
+            .find_map(|chunk| importer.import_chunk(chunk).err())
+            .unwrap()
+            .to_string();
+        assert!(
+            err.contains("differs from the hash in its parent reference"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn finalizing_incomplete_checkpoint() {
+        let dir = TempDir::new().unwrap();
+        let tree = create_tree(&dir);
+        let (info, chunks) = export_checkpoint(tree.db.clone(), 1);
+
+        let imported_dir = TempDir::new().unwrap();
+        let db = RocksDBWrapper::new(imported_dir.path()).unwrap();
+        let mut importer = TreeCheckpointImporter::new(db, 1, info.root_hash).unwrap();
+        for chunk in &chunks[..chunks.len() - 1] {
+            importer.import_chunk(chunk).unwrap();
+        }
+        let err = importer.finalize().unwrap_err().to_string();
+        assert!(err.contains("Checkpoint is incomplete"), "{err}");
+    }
+}
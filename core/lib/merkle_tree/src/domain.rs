@@ -9,8 +9,8 @@ use crate::{
     consistency::ConsistencyError,
     storage::{PatchSet, Patched, RocksDBWrapper},
     types::{
-        Key, NodeKey, RawNode, Root, TreeEntry, TreeEntryWithProof, TreeInstruction, TreeLogEntry,
-        ValueHash, TREE_DEPTH,
+        Key, NodeKey, RawNode, Root, TreeEntriesWithMultiproof, TreeEntry, TreeEntryWithProof,
+        TreeInstruction, TreeLogEntry, ValueHash, TREE_DEPTH,
     },
     BlockOutput, HashTree, MerkleTree, MerkleTreePruner, MerkleTreePrunerHandle, NoVersionError,
     PruneDatabase,
@@ -453,6 +453,21 @@ impl ZkSyncTreeReader {
         self.0.entries_with_proofs(version, keys)
     }
 
+    /// Reads entries with the specified keys from the tree together with a multiproof of their authenticity.
+    /// The entries are sorted by key and deduplicated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing.
+    pub fn entries_with_multiproof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        keys: &[Key],
+    ) -> Result<TreeEntriesWithMultiproof, NoVersionError> {
+        let version = u64::from(l1_batch_number.0);
+        self.0.entries_with_multiproof(version, keys)
+    }
+
     /// Returns raw nodes for the specified `keys`.
     pub fn raw_nodes(&self, keys: &[NodeKey]) -> Vec<Option<RawNode>> {
         let raw_nodes = self.0.db.raw_nodes(keys).into_iter();
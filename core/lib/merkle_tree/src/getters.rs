@@ -4,7 +4,10 @@ use crate::{
     hasher::HasherWithStats,
     recovery::MerkleTreeRecovery,
     storage::{LoadAncestorsResult, SortedKeys, WorkingPatchSet},
-    types::{Nibbles, Node, ProfiledTreeOperation, TreeEntry, TreeEntryWithProof},
+    types::{
+        Nibbles, Node, ProfiledTreeOperation, TreeEntriesWithMultiproof, TreeEntry,
+        TreeEntryWithProof, TREE_DEPTH,
+    },
     Database, HashTree, Key, MerkleTree, NoVersionError, PruneDatabase, ValueHash,
 };
 
@@ -59,6 +62,69 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
             },
         )
     }
+
+    /// Reads entries with the specified keys from the tree together with a [multiproof](TreeEntriesWithMultiproof)
+    /// of their authenticity. Unlike with [`Self::entries_with_proofs()`], the returned entries are sorted by key
+    /// and deduplicated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing.
+    #[allow(clippy::missing_panics_doc)] // Merkle paths never have more than 256 hashes
+    pub fn entries_with_multiproof(
+        &self,
+        version: u64,
+        leaf_keys: &[Key],
+    ) -> Result<TreeEntriesWithMultiproof, NoVersionError> {
+        let mut sorted_keys = leaf_keys.to_vec();
+        sorted_keys.sort_unstable();
+        sorted_keys.dedup();
+        let entries = self.entries_with_proofs(version, &sorted_keys)?;
+
+        let mut sibling_hashes = vec![];
+        if !entries.is_empty() {
+            collect_sibling_hashes(&entries, TREE_DEPTH, &mut sibling_hashes);
+        }
+        let path_lengths = entries
+            .iter()
+            .map(|entry| u16::try_from(entry.merkle_path.len()).expect("Merkle path is too long"));
+        Ok(TreeEntriesWithMultiproof {
+            path_lengths: path_lengths.collect(),
+            entries: entries.into_iter().map(|entry| entry.base).collect(),
+            sibling_hashes,
+        })
+    }
+}
+
+/// Collects sibling hashes for a multiproof from the individual Merkle proofs for the subtree with the specified height
+/// containing the specified `entries`. This mirrors the traversal in [`TreeEntriesWithMultiproof::verify()`].
+/// Returns the number of bottom-most tree levels that are known to have empty sibling subtrees.
+fn collect_sibling_hashes(
+    entries: &[TreeEntryWithProof],
+    height: usize,
+    sibling_hashes: &mut Vec<ValueHash>,
+) -> usize {
+    if height == 0 {
+        return TREE_DEPTH - entries[0].merkle_path.len();
+    }
+
+    let depth = height - 1;
+    let split_idx = entries.partition_point(|entry| !entry.base.key.bit(depth));
+    if split_idx == 0 || split_idx == entries.len() {
+        let empty_levels = collect_sibling_hashes(entries, depth, sibling_hashes);
+        if depth >= empty_levels {
+            // All entries have the sibling hash for `depth` in their Merkle paths, and these hashes coincide.
+            let merkle_path = &entries[0].merkle_path;
+            let sibling_hash = merkle_path[depth + merkle_path.len() - TREE_DEPTH];
+            sibling_hashes.push(sibling_hash);
+        }
+        empty_levels
+    } else {
+        let (left_entries, right_entries) = entries.split_at(split_idx);
+        let left_empty_levels = collect_sibling_hashes(left_entries, depth, sibling_hashes);
+        let right_empty_levels = collect_sibling_hashes(right_entries, depth, sibling_hashes);
+        left_empty_levels.max(right_empty_levels)
+    }
 }
 
 fn load_and_transform_entries<T>(
@@ -134,6 +200,16 @@ mod tests {
         entries[0]
             .verify(&tree.hasher, tree.hasher.empty_tree_hash())
             .unwrap();
+
+        let multiproof = tree
+            .entries_with_multiproof(0, &[missing_key, Key::from(1)])
+            .unwrap();
+        assert_eq!(multiproof.entries.len(), 2);
+        assert!(multiproof.entries.iter().all(TreeEntry::is_empty));
+        assert!(multiproof.sibling_hashes.is_empty());
+        multiproof
+            .verify(&tree.hasher, tree.hasher.empty_tree_hash())
+            .unwrap();
     }
 
     #[test]
@@ -157,4 +233,66 @@ mod tests {
         assert!(entries[1].base.is_empty());
         entries[1].verify(&tree.hasher, output.root_hash).unwrap();
     }
+
+    #[test]
+    fn multiproof_basics() {
+        let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+        let entries = (1..=50_u64).map(|i| {
+            let key = Key::from(i) << 200 | Key::from(i);
+            TreeEntry::new(key, i, ValueHash::from_low_u64_be(i))
+        });
+        let entries: Vec<_> = entries.collect();
+        let output = tree.extend(entries.clone()).unwrap();
+
+        let mut keys: Vec<_> = entries.iter().rev().map(|entry| entry.key).collect();
+        keys.push(Key::from(12_345));
+        keys.push(entries[0].key); // duplicate key
+        let multiproof = tree.entries_with_multiproof(0, &keys).unwrap();
+        assert_eq!(multiproof.entries.len(), 51);
+        assert_eq!(multiproof.entries[0], TreeEntry::empty(Key::from(12_345)));
+        assert_eq!(multiproof.entries[1..], entries);
+        multiproof.verify(&tree.hasher, output.root_hash).unwrap();
+
+        let proofs = tree.entries_with_proofs(0, &keys).unwrap();
+        let individual_hash_count: usize = proofs.iter().map(|proof| proof.merkle_path.len()).sum();
+        assert!(
+            multiproof.sibling_hashes.len() * 10 < individual_hash_count,
+            "{} vs {individual_hash_count}",
+            multiproof.sibling_hashes.len()
+        );
+    }
+
+    #[test]
+    fn tampered_multiproof_is_rejected() {
+        let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+        let entries = (1..=20_u64)
+            .map(|i| TreeEntry::new(Key::from(i) << 240, i, ValueHash::from_low_u64_be(i)));
+        let output = tree.extend(entries.collect()).unwrap();
+        let keys: Vec<_> = (1..=25_u64).map(|i| Key::from(i) << 240).collect();
+        let multiproof = tree.entries_with_multiproof(0, &keys).unwrap();
+        multiproof.verify(&tree.hasher, output.root_hash).unwrap();
+
+        let mut tampered = multiproof.clone();
+        tampered.entries[3].value = ValueHash::repeat_byte(0xff);
+        let err = tampered.verify(&tree.hasher, output.root_hash).unwrap_err();
+        assert!(err.to_string().contains("Root hash mismatch"), "{err}");
+
+        let mut tampered = multiproof.clone();
+        tampered.sibling_hashes.pop();
+        let err = tampered.verify(&tree.hasher, output.root_hash).unwrap_err();
+        assert!(
+            err.to_string().contains("insufficient sibling hashes"),
+            "{err}"
+        );
+
+        let mut tampered = multiproof.clone();
+        tampered.sibling_hashes.push(ValueHash::zero());
+        let err = tampered.verify(&tree.hasher, output.root_hash).unwrap_err();
+        assert!(err.to_string().contains("unused sibling hashes"), "{err}");
+
+        let mut tampered = multiproof;
+        tampered.entries.swap(0, 1);
+        let err = tampered.verify(&tree.hasher, output.root_hash).unwrap_err();
+        assert!(err.to_string().contains("not sorted"), "{err}");
+    }
 }
@@ -2,13 +2,13 @@
 
 use std::mem;
 
-use anyhow::ensure;
+use anyhow::{ensure, Context as _};
 
 use crate::{
     hasher::{HashTree, HasherWithStats},
     types::{
-        BlockOutputWithProofs, Key, LeafNode, TreeEntry, TreeEntryWithProof, TreeInstruction,
-        TreeLogEntry, ValueHash, TREE_DEPTH,
+        BlockOutputWithProofs, Key, LeafNode, TreeEntriesWithMultiproof, TreeEntry,
+        TreeEntryWithProof, TreeInstruction, TreeLogEntry, ValueHash, TREE_DEPTH,
     },
     utils,
 };
@@ -104,6 +104,131 @@ impl TreeEntryWithProof {
     }
 }
 
+impl TreeEntriesWithMultiproof {
+    /// Verifies this multiproof.
+    ///
+    /// # How it works
+    ///
+    /// The tree is traversed recursively starting from the root, splitting the proven entries by the corresponding
+    /// key bit at each level. If both child subtrees contain proven entries, the hashes of both subtrees are restored
+    /// recursively (first, the left subtree, then the right one). If only one child subtree contains proven entries,
+    /// its hash is restored recursively, and then the hash of the other child subtree is taken from `sibling_hashes`.
+    /// The sibling hash is omitted if it corresponds to an empty subtree according to `path_lengths`
+    /// of the proven entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error <=> proof is invalid.
+    pub fn verify(
+        &self,
+        hasher: &dyn HashTree,
+        trusted_root_hash: ValueHash,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            self.entries.len() == self.path_lengths.len(),
+            "Number of entries ({}) differs from the number of path lengths ({})",
+            self.entries.len(),
+            self.path_lengths.len()
+        );
+        for window in self.entries.windows(2) {
+            ensure!(
+                window[0].key < window[1].key,
+                "Entries are not sorted by key or contain duplicate keys"
+            );
+        }
+        for (entry, &path_length) in self.entries.iter().zip(&self.path_lengths) {
+            ensure!(
+                usize::from(path_length) <= TREE_DEPTH,
+                "Merkle path length for key {:?} is too large",
+                entry.key
+            );
+            if entry.leaf_index == 0 {
+                ensure!(
+                    entry.value.is_zero(),
+                    "Invalid missing value specification for key {:?}: leaf index is zero, but value is non-default",
+                    entry.key
+                );
+            }
+        }
+
+        if self.entries.is_empty() {
+            ensure!(
+                self.sibling_hashes.is_empty(),
+                "Multiproof for no entries contains sibling hashes"
+            );
+            return Ok(());
+        }
+
+        let mut sibling_hashes = self.sibling_hashes.iter();
+        let (root_hash, _) = fold_multiproof(
+            hasher,
+            &self.entries,
+            &self.path_lengths,
+            TREE_DEPTH,
+            &mut sibling_hashes,
+        )?;
+        ensure!(
+            sibling_hashes.next().is_none(),
+            "Multiproof contains unused sibling hashes"
+        );
+        ensure!(
+            root_hash == trusted_root_hash,
+            "Root hash mismatch: got {root_hash}, want {trusted_root_hash}"
+        );
+        Ok(())
+    }
+}
+
+/// Restores the hash of the subtree with the specified height containing the specified `entries`.
+/// Returns the hash together with the number of bottom-most tree levels that are known to have empty sibling subtrees.
+fn fold_multiproof<'a>(
+    hasher: &dyn HashTree,
+    entries: &[TreeEntry],
+    path_lengths: &[u16],
+    height: usize,
+    sibling_hashes: &mut impl Iterator<Item = &'a ValueHash>,
+) -> anyhow::Result<(ValueHash, usize)> {
+    if height == 0 {
+        // Since keys are unique, there's exactly one entry at this point.
+        let entry = &entries[0];
+        let empty_levels = TREE_DEPTH - usize::from(path_lengths[0]);
+        return Ok((
+            hasher.hash_leaf(&entry.value, entry.leaf_index),
+            empty_levels,
+        ));
+    }
+
+    let depth = height - 1;
+    let split_idx = entries.partition_point(|entry| !entry.key.bit(depth));
+    let (left_entries, right_entries) = entries.split_at(split_idx);
+    let (left_lengths, right_lengths) = path_lengths.split_at(split_idx);
+
+    if left_entries.is_empty() || right_entries.is_empty() {
+        let (hash, empty_levels) =
+            fold_multiproof(hasher, entries, path_lengths, depth, sibling_hashes)?;
+        let sibling_hash = if depth < empty_levels {
+            hasher.empty_subtree_hash(depth)
+        } else {
+            *sibling_hashes
+                .next()
+                .context("Multiproof has insufficient sibling hashes")?
+        };
+        let hash = if left_entries.is_empty() {
+            hasher.hash_branch(&sibling_hash, &hash)
+        } else {
+            hasher.hash_branch(&hash, &sibling_hash)
+        };
+        Ok((hash, empty_levels))
+    } else {
+        let (left_hash, left_empty_levels) =
+            fold_multiproof(hasher, left_entries, left_lengths, depth, sibling_hashes)?;
+        let (right_hash, right_empty_levels) =
+            fold_multiproof(hasher, right_entries, right_lengths, depth, sibling_hashes)?;
+        let hash = hasher.hash_branch(&left_hash, &right_hash);
+        Ok((hash, left_empty_levels.max(right_empty_levels)))
+    }
+}
+
 /// Range digest in a Merkle tree allowing to compute its root hash based on the provided entries.
 ///
 /// - The entries must be ordered by key. I.e., the first entry must have the numerically smallest key,
@@ -57,12 +57,13 @@ pub use crate::{
         RocksDBWrapper,
     },
     types::{
-        BlockOutput, BlockOutputWithProofs, Key, TreeEntry, TreeEntryWithProof, TreeInstruction,
-        TreeLogEntry, TreeLogEntryWithProof, ValueHash,
+        BlockOutput, BlockOutputWithProofs, Key, TreeEntriesWithMultiproof, TreeEntry,
+        TreeEntryWithProof, TreeInstruction, TreeLogEntry, TreeLogEntryWithProof, ValueHash,
     },
 };
 use crate::{storage::Storage, types::Root};
 
+pub mod checkpoint;
 mod consistency;
 pub mod domain;
 mod errors;
@@ -20,6 +20,7 @@ use zksync_storage::{
 };
 
 use crate::{
+    checkpoint::CheckpointNode,
     errors::{DeserializeError, ErrorContext},
     metrics::ApplyPatchStats,
     repair::StaleKeysRepairData,
@@ -172,7 +173,7 @@ impl RocksDBWrapper {
             .collect()
     }
 
-    fn deserialize_node(
+    pub(crate) fn deserialize_node(
         raw_node: &[u8],
         key: &NodeKey,
         is_leaf: bool,
@@ -270,6 +271,17 @@ impl RocksDBWrapper {
         StaleKeysRepairData::deserialize(&raw_value).map(Some)
     }
 
+    /// Writes raw nodes (e.g., ones imported from a checkpoint) to the tree column family as is.
+    pub(crate) fn write_raw_nodes(&mut self, nodes: &[CheckpointNode]) -> anyhow::Result<()> {
+        let mut write_batch = self.db.new_write_batch();
+        for node in nodes {
+            write_batch.put_cf(MerkleTreeColumnFamily::Tree, &node.key, &node.value);
+        }
+        self.db
+            .write(write_batch)
+            .context("Failed writing a batch to RocksDB")
+    }
+
     /// Returns the wrapped RocksDB instance.
     pub fn into_inner(self) -> RocksDB<MerkleTreeColumnFamily> {
         self.db
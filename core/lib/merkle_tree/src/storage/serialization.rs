@@ -198,7 +198,7 @@ impl RawNode {
 }
 
 impl Root {
-    pub(super) fn deserialize(mut bytes: &[u8], strict: bool) -> Result<Self, DeserializeError> {
+    pub(crate) fn deserialize(mut bytes: &[u8], strict: bool) -> Result<Self, DeserializeError> {
         let leaf_count = leb128::read::unsigned(&mut bytes).map_err(|err| {
             DeserializeErrorKind::Leb128(err).with_context(ErrorContext::LeafCount)
         })?;
@@ -116,6 +116,21 @@ pub struct TreeEntryWithProof {
     pub merkle_path: Vec<ValueHash>,
 }
 
+/// Entries in a Merkle tree together with a *multiproof*, i.e. a single proof of authenticity for all entries
+/// in which hashes shared by Merkle paths of several entries are only included once.
+#[derive(Debug, Clone)]
+pub struct TreeEntriesWithMultiproof {
+    /// Proven entries sorted by key in the ascending order. Keys are unique.
+    pub entries: Vec<TreeEntry>,
+    /// Lengths of Merkle paths for each of `entries`, in the same sense as the length of
+    /// [`TreeEntryWithProof::merkle_path`]. That is, `256 - path_length` bottom-most levels of a path
+    /// correspond to empty subtrees; hashes for these levels are omitted from `sibling_hashes`.
+    pub path_lengths: Vec<u16>,
+    /// Hashes of subtrees adjacent to the proven entries necessary to restore the root hash of the tree.
+    /// Hashes are ordered as they are consumed during the tree traversal described in [`Self::verify()`].
+    pub sibling_hashes: Vec<ValueHash>,
+}
+
 /// Output of inserting a block of entries into a Merkle tree.
 #[derive(Debug, PartialEq, Eq)]
 pub struct BlockOutput {
@@ -114,6 +114,50 @@ fn entry_proofs_are_computed_correctly_on_empty_tree(kv_count: u64) {
     }
 }
 
+#[test_casing(8, KV_COUNTS)]
+fn multiproofs_are_computed_correctly_on_empty_tree(kv_count: u64) {
+    const RNG_SEED: u64 = 123;
+
+    let mut rng = StdRng::seed_from_u64(RNG_SEED);
+    let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+    let kvs = generate_key_value_pairs(0..kv_count);
+    let expected_hash = compute_tree_hash(kvs.iter().copied());
+    tree.extend(kvs.clone()).unwrap();
+
+    let existing_keys = kvs.iter().map(|entry| entry.key);
+    let adjacent_keys = kvs
+        .iter()
+        .map(|entry| entry.key ^ (U256::one() << rng.gen_range(0..256)));
+    let random_keys = generate_key_value_pairs(kv_count..(kv_count * 2))
+        .into_iter()
+        .map(|entry| entry.key);
+    let mut keys: Vec<_> = existing_keys
+        .chain(adjacent_keys)
+        .chain(random_keys)
+        .collect();
+    keys.shuffle(&mut rng);
+    keys.push(keys[0]); // Check key deduplication
+
+    let multiproof = tree.entries_with_multiproof(0, &keys).unwrap();
+    multiproof.verify(&Blake2Hasher, expected_hash).unwrap();
+
+    keys.sort_unstable();
+    keys.dedup();
+    let proofs = tree.entries_with_proofs(0, &keys).unwrap();
+    assert_eq!(multiproof.entries.len(), proofs.len());
+    for ((entry, &path_length), proof) in multiproof
+        .entries
+        .iter()
+        .zip(&multiproof.path_lengths)
+        .zip(&proofs)
+    {
+        assert_eq!(*entry, proof.base);
+        assert_eq!(usize::from(path_length), proof.merkle_path.len());
+    }
+    let individual_hash_count: usize = proofs.iter().map(|proof| proof.merkle_path.len()).sum();
+    assert!(multiproof.sibling_hashes.len() < individual_hash_count);
+}
+
 #[test]
 fn proofs_are_computed_correctly_for_mixed_instructions() {
     const RNG_SEED: u64 = 123;
@@ -0,0 +1,186 @@
+//! Runtime control of the node: listing tasks, pausing / resuming tasks and updating dynamic configuration
+//! without restarting the node.
+//!
+//! The entry point is [`NodeControl`], which is available to all wiring layers as an `Arc<NodeControl>` resource.
+//! The service registers all added tasks in it automatically. Tasks opt in to pausing by implementing
+//! [`Pausable`] and being registered via [`NodeControl::register_pausable()`]; dynamic configs are registered
+//! via [`NodeControl::register_config()`].
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use tokio::sync::watch;
+
+use crate::{
+    resource::{self, Resource},
+    task::{Task, TaskId, TaskKind},
+};
+
+/// Switch allowing to pause and resume a task at runtime. Cloned switches share the same state.
+#[derive(Debug, Clone)]
+pub struct PauseSwitch(Arc<watch::Sender<bool>>);
+
+impl Default for PauseSwitch {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+impl PauseSwitch {
+    /// Creates a switch with the specified initial state.
+    pub fn new(paused: bool) -> Self {
+        Self(Arc::new(watch::channel(paused).0))
+    }
+
+    /// Checks whether the switch is currently paused.
+    pub fn is_paused(&self) -> bool {
+        *self.0.borrow()
+    }
+
+    /// Sets the paused state. Returns `true` if the state has changed.
+    pub fn set_paused(&self, paused: bool) -> bool {
+        self.0.send_if_modified(|current| {
+            let changed = *current != paused;
+            *current = paused;
+            changed
+        })
+    }
+
+    /// Subscribes to the changes of the paused state.
+    pub fn subscribe(&self) -> watch::Receiver<bool> {
+        self.0.subscribe()
+    }
+}
+
+/// Task that can be paused and resumed at runtime.
+///
+/// A paused task is expected to keep running (i.e., to respond to stop requests and report its health),
+/// but to skip its main workload until it's resumed.
+pub trait Pausable {
+    /// Returns the switch controlling the task.
+    fn pause_switch(&self) -> PauseSwitch;
+}
+
+/// Configuration that can be updated at runtime.
+#[async_trait::async_trait]
+pub trait DynamicConfig: 'static + Send + Sync + fmt::Debug {
+    /// Name of the config used to address it.
+    fn name(&self) -> &'static str;
+
+    /// Returns the current config values.
+    async fn current(&self) -> serde_json::Value;
+
+    /// Updates the config. The update may contain only a subset of values; omitted values must be left intact.
+    async fn update(&self, update: serde_json::Value) -> anyhow::Result<()>;
+}
+
+/// Errors returned by [`NodeControl`] operations.
+#[derive(Debug, thiserror::Error)]
+pub enum ControlError {
+    #[error("task `{0}` does not exist")]
+    UnknownTask(String),
+    #[error("task `{0}` is not pausable")]
+    NotPausable(String),
+    #[error("dynamic config `{0}` is already registered")]
+    DuplicateConfig(&'static str),
+}
+
+/// Information about a task added to the service.
+#[derive(Debug, Clone)]
+pub struct TaskInfo {
+    pub id: TaskId,
+    pub kind: TaskKind,
+    /// Whether the task is paused; `None` if the task is not pausable.
+    pub paused: Option<bool>,
+}
+
+#[derive(Debug, Default)]
+struct NodeControlInner {
+    tasks: Vec<(TaskId, TaskKind)>,
+    pause_switches: HashMap<TaskId, PauseSwitch>,
+    configs: BTreeMap<&'static str, Arc<dyn DynamicConfig>>,
+}
+
+/// Registry of tasks, pause switches and dynamic configs of the node.
+#[derive(Debug, Default)]
+pub struct NodeControl {
+    inner: Mutex<NodeControlInner>,
+}
+
+impl Resource<resource::Shared> for NodeControl {
+    fn name() -> String {
+        "common/node_control".into()
+    }
+}
+
+impl NodeControl {
+    fn lock(&self) -> std::sync::MutexGuard<'_, NodeControlInner> {
+        self.inner.lock().expect("node control is poisoned")
+    }
+
+    pub(crate) fn add_task(&self, id: TaskId, kind: TaskKind) {
+        self.lock().tasks.push((id, kind));
+    }
+
+    /// Registers a pausable task. Should be called in the wiring layer adding the task.
+    pub fn register_pausable<T: Task + Pausable>(&self, task: &T) {
+        let task_id = task.id();
+        tracing::info!("Registered pausable task {task_id}");
+        self.lock()
+            .pause_switches
+            .insert(task_id, task.pause_switch());
+    }
+
+    /// Registers a dynamic config.
+    pub fn register_config(&self, config: Arc<dyn DynamicConfig>) -> Result<(), ControlError> {
+        let name = config.name();
+        let mut inner = self.lock();
+        if inner.configs.contains_key(name) {
+            return Err(ControlError::DuplicateConfig(name));
+        }
+        inner.configs.insert(name, config);
+        tracing::info!("Registered dynamic config `{name}`");
+        Ok(())
+    }
+
+    /// Lists all tasks added to the service in the order they were added.
+    pub fn tasks(&self) -> Vec<TaskInfo> {
+        let inner = self.lock();
+        inner
+            .tasks
+            .iter()
+            .map(|(id, kind)| TaskInfo {
+                id: id.clone(),
+                kind: *kind,
+                paused: inner.pause_switches.get(id).map(PauseSwitch::is_paused),
+            })
+            .collect()
+    }
+
+    /// Pauses or resumes the specified task. Returns `true` if the task state has changed.
+    pub fn set_paused(&self, task_id: &str, paused: bool) -> Result<bool, ControlError> {
+        let task_id = TaskId::from(task_id.to_owned());
+        let inner = self.lock();
+        let Some(switch) = inner.pause_switches.get(&task_id) else {
+            return Err(if inner.tasks.iter().any(|(id, _)| *id == task_id) {
+                ControlError::NotPausable(task_id.to_string())
+            } else {
+                ControlError::UnknownTask(task_id.to_string())
+            });
+        };
+        Ok(switch.set_paused(paused))
+    }
+
+    /// Lists all registered dynamic configs ordered by name.
+    pub fn configs(&self) -> Vec<Arc<dyn DynamicConfig>> {
+        self.lock().configs.values().cloned().collect()
+    }
+
+    /// Returns a dynamic config with the specified name.
+    pub fn config(&self, name: &str) -> Option<Arc<dyn DynamicConfig>> {
+        self.lock().configs.get(name).cloned()
+    }
+}
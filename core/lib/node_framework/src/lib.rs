@@ -9,7 +9,9 @@
 //! - [`ZkStackService`](service::ZkStackService) - a container for tasks and resources that takes care of initialization, running
 //!   and shutting down.
 //! - [`ZkStackServiceBuilder`](service::ZkStackServiceBuilder) - a builder for the service.
+//! - [`NodeControl`](control::NodeControl) - runtime control of the service tasks and dynamic configuration.
 
+pub mod control;
 mod metrics;
 pub mod resource;
 pub mod service;
@@ -53,6 +53,7 @@ impl<'a> ServiceContext<'a> {
     /// are met.
     pub fn add_task<T: Task>(&mut self, task: T) -> &mut Self {
         tracing::info!("Layer {} has added a new task: {}", self.layer, task.id());
+        self.service.node_control.add_task(task.id(), task.kind());
         self.service.runnables.tasks.push(Box::new(task));
         self
     }
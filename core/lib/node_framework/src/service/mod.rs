@@ -1,4 +1,4 @@
-use std::{collections::HashMap, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use tokio::{runtime::Runtime, sync::watch};
 use zksync_utils::panic_extractor::try_extract_panic_message;
@@ -11,6 +11,7 @@ pub use self::{
     stop_receiver::StopReceiver,
 };
 use crate::{
+    control::NodeControl,
     resource::{ResourceId, StoredResource},
     service::{
         named_future::TaskFuture,
@@ -100,11 +101,18 @@ impl ZkStackServiceBuilder {
     /// Builds the service.
     pub fn build(self) -> ZkStackService {
         let (stop_sender, _stop_receiver) = watch::channel(false);
+        let node_control = Arc::<NodeControl>::default();
+        let mut resources: HashMap<ResourceId, Box<dyn StoredResource>> = HashMap::new();
+        resources.insert(
+            ResourceId::of::<Arc<NodeControl>>(),
+            Box::new(node_control.clone()),
+        );
 
         ZkStackService {
             layers: self.layers,
-            resources: Default::default(),
+            resources,
             runnables: Default::default(),
+            node_control,
             stop_sender,
             runtime: self.runtime,
             errors: Vec::new(),
@@ -122,6 +130,8 @@ pub struct ZkStackService {
     layers: Vec<(&'static str, WireFn)>,
     /// Different kinds of tasks for the service.
     runnables: Runnables,
+    /// Registry of tasks and dynamic configs available for runtime control.
+    node_control: Arc<NodeControl>,
 
     /// Sender used to stop the tasks.
     stop_sender: watch::Sender<bool>,
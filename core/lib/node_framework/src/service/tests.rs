@@ -5,9 +5,10 @@ use assert_matches::assert_matches;
 use tokio::{runtime::Runtime, sync::Barrier};
 
 use crate::{
+    control::{ControlError, NodeControl, Pausable, PauseSwitch, TaskInfo},
     service::{StopReceiver, WiringError, WiringLayer, ZkStackServiceBuilder, ZkStackServiceError},
-    task::{Task, TaskId},
-    IntoContext,
+    task::{Task, TaskId, TaskKind},
+    FromContext, IntoContext,
 };
 
 // `ZkStack` Service's `new()` method has to have a check for nested runtime.
@@ -252,3 +253,121 @@ fn test_task_run() {
     let res2 = *remaining_task_was_run.lock().unwrap();
     assert!(res2, "Incorrect resource value");
 }
+
+#[derive(Debug)]
+struct PausableTaskLayer {
+    task_states: Arc<Mutex<Vec<TaskInfo>>>,
+}
+
+#[derive(Debug, FromContext)]
+#[context(crate = crate)]
+struct PausableTaskLayerInput {
+    #[context(default)]
+    node_control: Arc<NodeControl>,
+}
+
+#[derive(Debug, IntoContext)]
+#[context(crate = crate)]
+struct PausableTaskLayerOutput {
+    #[context(task)]
+    task: PausableTask,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for PausableTaskLayer {
+    type Input = PausableTaskLayerInput;
+    type Output = PausableTaskLayerOutput;
+
+    fn layer_name(&self) -> &'static str {
+        "pausable_task_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let task = PausableTask {
+            switch: PauseSwitch::default(),
+            node_control: input.node_control.clone(),
+            task_states: self.task_states,
+        };
+        input.node_control.register_pausable(&task);
+        Ok(PausableTaskLayerOutput { task })
+    }
+}
+
+#[derive(Debug)]
+struct PausableTask {
+    switch: PauseSwitch,
+    node_control: Arc<NodeControl>,
+    task_states: Arc<Mutex<Vec<TaskInfo>>>,
+}
+
+impl Pausable for PausableTask {
+    fn pause_switch(&self) -> PauseSwitch {
+        self.switch.clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for PausableTask {
+    fn kind(&self) -> TaskKind {
+        // Ensures that the task isn't skipped if `ErrorTask` exits early.
+        TaskKind::UnconstrainedTask
+    }
+
+    fn id(&self) -> TaskId {
+        "pausable_task".into()
+    }
+
+    async fn run(self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        let mut pause_receiver = self.switch.subscribe();
+        assert!(self.node_control.set_paused("pausable_task", true)?);
+        assert!(!self.node_control.set_paused("pausable_task", true)?);
+        pause_receiver.changed().await?;
+        assert!(self.switch.is_paused());
+        *self.task_states.lock().unwrap() = self.node_control.tasks();
+
+        assert_matches!(
+            self.node_control.set_paused("error_task", true),
+            Err(ControlError::NotPausable(_))
+        );
+        assert_matches!(
+            self.node_control.set_paused("unknown_task", true),
+            Err(ControlError::UnknownTask(_))
+        );
+        assert!(self.node_control.set_paused("pausable_task", false)?);
+        assert!(!self.switch.is_paused());
+
+        // The service is stopped by `ErrorTask`.
+        stop_receiver.0.changed().await?;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_node_control() {
+    let task_states = Arc::new(Mutex::new(vec![]));
+    let mut zk_stack_service = ZkStackServiceBuilder::new().unwrap();
+    zk_stack_service
+        .add_layer(PausableTaskLayer {
+            task_states: task_states.clone(),
+        })
+        .add_layer(TaskErrorLayer);
+    let result = zk_stack_service.build().run(());
+    let ZkStackServiceError::Task(errors) = result.unwrap_err() else {
+        panic!("unexpected error");
+    };
+    // Only `ErrorTask` should fail.
+    assert_eq!(errors.0.len(), 1, "{errors:?}");
+
+    let task_states = task_states.lock().unwrap();
+    let task_states: Vec<_> = task_states
+        .iter()
+        .map(|info| (info.id.to_string(), info.paused))
+        .collect();
+    assert_eq!(
+        task_states,
+        [
+            ("pausable_task".to_owned(), Some(true)),
+            ("error_task".to_owned(), None)
+        ]
+    );
+}
@@ -7,6 +7,7 @@ use flate2::{read::GzDecoder, write::GzEncoder, Compression};
 use prost::Message;
 use zksync_protobuf::{decode, ProtoFmt};
 use zksync_types::{
+    pruning::{PrunedL2BlocksArchive, PrunedL2BlocksArchiveKey},
     snapshots::{
         SnapshotFactoryDependencies, SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey,
     },
@@ -125,6 +126,31 @@ where
     }
 }
 
+impl StoredObject for PrunedL2BlocksArchive {
+    const BUCKET: Bucket = Bucket::PruningArchives;
+    type Key<'a> = PrunedL2BlocksArchiveKey;
+
+    fn encode_key(key: Self::Key<'_>) -> String {
+        format!(
+            "pruned_l2_blocks_{:0>10}_{:0>10}.json.gzip",
+            key.first_l2_block.0, key.last_l2_block.0
+        )
+    }
+
+    fn serialize(&self) -> Result<Vec<u8>, BoxedError> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        serde_json::to_writer(&mut encoder, self)?;
+        encoder.finish().map_err(From::from)
+    }
+
+    fn deserialize(bytes: Vec<u8>) -> Result<Self, BoxedError> {
+        let decoder = GzDecoder::new(&bytes[..]);
+        serde_json::from_reader(decoder)
+            .context("deserialization of PrunedL2BlocksArchive")
+            .map_err(From::from)
+    }
+}
+
 impl dyn ObjectStore + '_ {
     /// Fetches the value for the given key if it exists.
     ///
@@ -223,9 +249,11 @@ impl dyn ObjectStore + '_ {
 #[cfg(test)]
 mod tests {
     use zksync_types::{
+        api,
+        pruning::{ArchivedL2Block, ArchivedStorageLog},
         snapshots::{SnapshotFactoryDependency, SnapshotStorageLog},
         web3::Bytes,
-        H256,
+        L2BlockNumber, H256,
     };
 
     use super::*;
@@ -307,4 +335,41 @@ mod tests {
         let reconstructed_factory_deps = store.get(key).await.unwrap();
         assert_eq!(factory_deps, reconstructed_factory_deps);
     }
+
+    #[tokio::test]
+    async fn pruned_l2_blocks_archive_can_be_serialized_and_deserialized() {
+        let store = MockObjectStore::arc();
+        let key = PrunedL2BlocksArchiveKey::from(L2BlockNumber(10)..=L2BlockNumber(11));
+        assert_eq!(
+            <PrunedL2BlocksArchive>::encode_key(key),
+            "pruned_l2_blocks_0000000010_0000000011.json.gzip"
+        );
+
+        let archive = PrunedL2BlocksArchive {
+            l2_blocks: (10_u32..=11)
+                .map(|number| ArchivedL2Block {
+                    block: api::Block {
+                        number: number.into(),
+                        hash: H256::random(),
+                        transactions: vec![H256::random()],
+                        ..api::Block::default()
+                    },
+                    transactions: vec![],
+                    logs: vec![],
+                    storage_logs: vec![ArchivedStorageLog {
+                        hashed_key: H256::random(),
+                        value: H256::random(),
+                    }],
+                })
+                .collect(),
+        };
+        store.put(key, &archive).await.unwrap();
+        let reconstructed_archive: PrunedL2BlocksArchive = store.get(key).await.unwrap();
+        assert_eq!(reconstructed_archive, archive);
+        assert_eq!(
+            reconstructed_archive.l2_block(L2BlockNumber(11)),
+            Some(&archive.l2_blocks[1])
+        );
+        assert_eq!(reconstructed_archive.l2_block(L2BlockNumber(12)), None);
+    }
 }
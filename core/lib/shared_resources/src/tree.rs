@@ -33,6 +33,28 @@ impl TreeEntryWithProof {
     }
 }
 
+/// Entry in a [`TreeMultiproof`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TreeMultiproofEntry {
+    pub key: U256,
+    #[serde(default, skip_serializing_if = "H256::is_zero")]
+    pub value: H256,
+    #[serde(default, skip_serializing_if = "TreeEntryWithProof::is_zero")]
+    pub index: u64,
+    /// Length of the Merkle path for the entry; the path is not included into the proof.
+    pub path_length: u16,
+}
+
+/// Tree entries together with a multiproof of their authenticity, in which hashes shared by Merkle paths
+/// of several entries are only included once.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TreeMultiproof {
+    /// Proven entries sorted by key in the ascending order. Keys are unique.
+    pub entries: Vec<TreeMultiproofEntry>,
+    /// Sibling hashes in the order they are consumed during verification.
+    pub sibling_hashes: Vec<H256>,
+}
+
 /// Client-side tree API error used by [`TreeApiClient`].
 #[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
@@ -65,6 +87,13 @@ pub trait TreeApiClient: 'static + Send + Sync + fmt::Debug {
         l1_batch_number: L1BatchNumber,
         hashed_keys: Vec<U256>,
     ) -> Result<Vec<TreeEntryWithProof>, TreeApiError>;
+
+    /// Obtains a multiproof for the specified `hashed_keys` at the specified tree version (= L1 batch number).
+    async fn get_multiproof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<TreeMultiproof, TreeApiError>;
 }
 
 impl Resource<resource::Shared> for dyn TreeApiClient {
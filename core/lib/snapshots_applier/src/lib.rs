@@ -1,7 +1,8 @@
 //! Logic for applying application-level snapshots to Postgres storage.
 
 use std::{
-    cmp::Ordering, collections::HashMap, fmt, mem, num::NonZeroUsize, sync::Arc, time::Duration,
+    cmp::Ordering, collections::HashMap, fmt, mem, num::NonZeroUsize, ops, sync::Arc,
+    time::Duration,
 };
 
 use anyhow::Context as _;
@@ -15,8 +16,9 @@ use zksync_types::{
     api,
     bytecode::{BytecodeHash, BytecodeMarker},
     snapshots::{
-        SnapshotFactoryDependencies, SnapshotHeader, SnapshotRecoveryStatus, SnapshotStorageLog,
-        SnapshotStorageLogsChunk, SnapshotStorageLogsStorageKey, SnapshotVersion,
+        uniform_hashed_keys_chunk, SnapshotFactoryDependencies, SnapshotFactoryDependency,
+        SnapshotHeader, SnapshotRecoveryStatus, SnapshotStorageLog, SnapshotStorageLogsChunk,
+        SnapshotStorageLogsStorageKey, SnapshotVersion,
     },
     tokens::TokenInfo,
     L1BatchNumber, L2BlockNumber, OrStopped, StorageKey, H256,
@@ -171,7 +173,7 @@ impl SnapshotsApplierMainNodeClient for Box<DynClient<L2>> {
             .get_all_snapshots()
             .rpc_context("get_all_snapshots")
             .await?;
-        Ok(snapshots.snapshots_l1_batch_numbers.first().copied())
+        Ok(snapshots.newest_l1_batch_number())
     }
 
     async fn fetch_snapshot(
@@ -397,13 +399,84 @@ impl SnapshotsApplierTask {
     }
 }
 
+/// Chain of snapshots to recover from: a full snapshot followed by zero or more differential snapshots.
+#[derive(Debug, Clone)]
+struct SnapshotChain {
+    version: SnapshotVersion,
+    /// L1 batches of snapshots in the chain starting from the full snapshot. The last element is the L1 batch
+    /// of the snapshot being recovered.
+    l1_batch_numbers: Vec<L1BatchNumber>,
+}
+
+impl SnapshotChain {
+    async fn fetch(
+        main_node_client: &dyn SnapshotsApplierMainNodeClient,
+        header: &SnapshotHeader,
+    ) -> Result<Self, SnapshotsApplierError> {
+        let version = SnapshotRecoveryStrategy::check_snapshot_version(header.version)?;
+        let chunk_count = header.storage_logs_chunks.len();
+        let mut l1_batch_numbers = vec![header.l1_batch_number];
+        let mut next_base = header.base_l1_batch_number;
+        while let Some(base_l1_batch_number) = next_base {
+            let current_l1_batch_number = *l1_batch_numbers.last().unwrap();
+            if base_l1_batch_number >= current_l1_batch_number {
+                let err = anyhow::anyhow!(
+                    "snapshot for L1 batch #{current_l1_batch_number} has invalid base L1 batch #{base_l1_batch_number}"
+                );
+                return Err(err.into());
+            }
+
+            let base_header = main_node_client
+                .fetch_snapshot(base_l1_batch_number)
+                .await?
+                .with_context(|| {
+                    format!("base snapshot for L1 batch #{base_l1_batch_number} is not present on main node")
+                })?;
+            if base_header.version != header.version {
+                let err = anyhow::anyhow!(
+                    "base snapshot for L1 batch #{base_l1_batch_number} has version {}, which differs from \
+                     the recovered snapshot version {}",
+                    base_header.version,
+                    header.version
+                );
+                return Err(err.into());
+            }
+            if base_header.storage_logs_chunks.len() != chunk_count {
+                let err = anyhow::anyhow!(
+                    "base snapshot for L1 batch #{base_l1_batch_number} has {} storage log chunks, while \
+                     the recovered snapshot has {chunk_count}",
+                    base_header.storage_logs_chunks.len()
+                );
+                return Err(err.into());
+            }
+            l1_batch_numbers.push(base_l1_batch_number);
+            next_base = base_header.base_l1_batch_number;
+        }
+
+        if l1_batch_numbers.len() > 1 && version == SnapshotVersion::Version0 {
+            let err =
+                anyhow::anyhow!("differential snapshots are not supported for version {version:?}");
+            return Err(err.into());
+        }
+        l1_batch_numbers.reverse();
+        Ok(Self {
+            version,
+            l1_batch_numbers,
+        })
+    }
+
+    fn is_differential(&self) -> bool {
+        self.l1_batch_numbers.len() > 1
+    }
+}
+
 /// Strategy determining how snapshot recovery should proceed.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 enum SnapshotRecoveryStrategy {
     /// Snapshot recovery should proceed from scratch with the specified params.
-    New(SnapshotVersion),
+    New(SnapshotChain),
     /// Snapshot recovery should continue with the specified params.
-    Resumed(SnapshotVersion),
+    Resumed(SnapshotChain),
     /// Snapshot recovery has already been completed.
     Completed,
 }
@@ -436,11 +509,11 @@ impl SnapshotRecoveryStrategy {
                 })?;
             // Old snapshots can theoretically be removed by the node, but in this case the snapshot data may be removed as well,
             // so returning an error looks appropriate here.
-            let snapshot_version = Self::check_snapshot_version(snapshot_header.version)?;
+            let snapshot_chain = SnapshotChain::fetch(main_node_client, &snapshot_header).await?;
 
             let latency = latency.observe();
             tracing::info!("Re-initialized snapshots applier after reset/failure in {latency:?}");
-            Ok((Self::Resumed(snapshot_version), applied_snapshot_status))
+            Ok((Self::Resumed(snapshot_chain), applied_snapshot_status))
         } else {
             let is_genesis_needed = storage.blocks_dal().is_genesis_needed().await?;
             if !is_genesis_needed {
@@ -450,7 +523,7 @@ impl SnapshotRecoveryStrategy {
                 return Err(SnapshotsApplierError::Fatal(err));
             }
 
-            let (recovery_status, snapshot_version) =
+            let (recovery_status, snapshot_chain) =
                 Self::create_fresh_recovery_status(main_node_client, snapshot_l1_batch).await?;
 
             let storage_logs_count = storage
@@ -468,14 +541,14 @@ impl SnapshotRecoveryStrategy {
 
             let latency = latency.observe();
             tracing::info!("Initialized fresh snapshots applier in {latency:?}");
-            Ok((Self::New(snapshot_version), recovery_status))
+            Ok((Self::New(snapshot_chain), recovery_status))
         }
     }
 
     async fn create_fresh_recovery_status(
         main_node_client: &dyn SnapshotsApplierMainNodeClient,
         snapshot_l1_batch: Option<L1BatchNumber>,
-    ) -> Result<(SnapshotRecoveryStatus, SnapshotVersion), SnapshotsApplierError> {
+    ) -> Result<(SnapshotRecoveryStatus, SnapshotChain), SnapshotsApplierError> {
         let l1_batch_number = match snapshot_l1_batch {
             Some(num) => num,
             None => main_node_client
@@ -495,7 +568,13 @@ impl SnapshotRecoveryStrategy {
             version = snapshot.version,
             chunk_count = snapshot.storage_logs_chunks.len()
         );
-        let snapshot_version = Self::check_snapshot_version(snapshot.version)?;
+        let snapshot_chain = SnapshotChain::fetch(main_node_client, &snapshot).await?;
+        if snapshot_chain.is_differential() {
+            tracing::info!(
+                "Snapshot is differential; will recover from the chain of snapshots for L1 batches {:?}",
+                snapshot_chain.l1_batch_numbers
+            );
+        }
 
         let l1_batch = main_node_client
             .fetch_l1_batch_details(l1_batch_number)
@@ -533,7 +612,7 @@ impl SnapshotRecoveryStrategy {
             protocol_version,
             storage_logs_chunks_processed: vec![false; snapshot.storage_logs_chunks.len()],
         };
-        Ok((status, snapshot_version))
+        Ok((status, snapshot_chain))
     }
 
     fn check_snapshot_version(raw_version: u16) -> anyhow::Result<SnapshotVersion> {
@@ -608,6 +687,40 @@ impl StorageLogs {
         Ok(())
     }
 
+    /// Merges a chunk of a differential snapshot into this chunk, overwriting existing logs for the same keys.
+    fn merge_differential(
+        &mut self,
+        differential: Self,
+        hashed_keys_range: &ops::RangeInclusive<H256>,
+    ) -> anyhow::Result<()> {
+        let (Self::V1(logs), Self::V1(differential_logs)) = (&mut *self, differential) else {
+            anyhow::bail!("differential snapshots are only supported for version 1 snapshots");
+        };
+
+        let mut merged_logs: HashMap<_, _> = mem::take(logs)
+            .into_iter()
+            .map(|log| (log.key, log))
+            .collect();
+        for log in differential_logs {
+            anyhow::ensure!(
+                hashed_keys_range.contains(&log.key),
+                "storage log {log:?} in a differential snapshot is outside the chunk range {hashed_keys_range:?}"
+            );
+            if let Some(prev_log) = merged_logs.get(&log.key) {
+                anyhow::ensure!(
+                    prev_log.enumeration_index == log.enumeration_index
+                        && prev_log.l1_batch_number_of_initial_write
+                            == log.l1_batch_number_of_initial_write,
+                    "storage log {log:?} in a differential snapshot is inconsistent with the base snapshot log {prev_log:?}"
+                );
+            }
+            merged_logs.insert(log.key, log);
+        }
+        *logs = merged_logs.into_values().collect();
+        logs.sort_unstable_by_key(|log| log.key);
+        Ok(())
+    }
+
     fn drop_key_preimages(&mut self) {
         match self {
             Self::V0(logs) => {
@@ -641,7 +754,7 @@ struct SnapshotsApplier<'a> {
     blob_store: &'a dyn ObjectStore,
     applied_snapshot_status: SnapshotRecoveryStatus,
     health_updater: &'a HealthUpdater,
-    snapshot_version: SnapshotVersion,
+    snapshot_chain: SnapshotChain,
     max_concurrency: usize,
     drop_storage_key_preimages: bool,
     factory_deps_recovered: bool,
@@ -701,9 +814,9 @@ impl<'a> SnapshotsApplierOrStatus<'a> {
         )
         .await?;
         tracing::info!("Chosen snapshot recovery strategy: {strategy:?} with status: {applied_snapshot_status:?}");
-        let (created_from_scratch, snapshot_version) = match strategy {
-            SnapshotRecoveryStrategy::New(version) => (true, version),
-            SnapshotRecoveryStrategy::Resumed(version) => (false, version),
+        let (created_from_scratch, snapshot_chain) = match &strategy {
+            SnapshotRecoveryStrategy::New(chain) => (true, chain.clone()),
+            SnapshotRecoveryStrategy::Resumed(chain) => (false, chain.clone()),
             SnapshotRecoveryStrategy::Completed => {
                 return Ok(Self::CompletedStatus(applied_snapshot_status))
             }
@@ -715,7 +828,7 @@ impl<'a> SnapshotsApplierOrStatus<'a> {
             blob_store: task.blob_store.as_ref(),
             applied_snapshot_status,
             health_updater,
-            snapshot_version,
+            snapshot_chain,
             max_concurrency: task.config.max_concurrency.get(),
             drop_storage_key_preimages: task.drop_storage_key_preimages,
             factory_deps_recovered: !created_from_scratch,
@@ -800,23 +913,28 @@ impl<'a> SnapshotsApplier<'a> {
         let latency = METRICS.initial_stage_duration[&InitialStage::ApplyFactoryDeps].start();
 
         tracing::debug!("Fetching factory dependencies from object store");
-        let l1_batch_number = self.applied_snapshot_status.l1_batch_number;
-        let factory_deps: SnapshotFactoryDependencies =
-            self.blob_store.get(l1_batch_number).await.map_err(|err| {
-                let context = format!(
-                    "cannot fetch factory deps for L1 batch #{l1_batch_number} from object store"
-                );
-                SnapshotsApplierError::object_store(err, context)
-            })?;
+        // Differential snapshots only contain factory deps added after the base snapshot, so we need to merge deps
+        // from all snapshots in the chain.
+        let mut factory_deps = Vec::<SnapshotFactoryDependency>::new();
+        for &l1_batch_number in &self.snapshot_chain.l1_batch_numbers {
+            let snapshot_deps: SnapshotFactoryDependencies =
+                self.blob_store.get(l1_batch_number).await.map_err(|err| {
+                    let context = format!(
+                        "cannot fetch factory deps for L1 batch #{l1_batch_number} from object store"
+                    );
+                    SnapshotsApplierError::object_store(err, context)
+                })?;
+            factory_deps.extend(snapshot_deps.factory_deps);
+        }
         tracing::debug!(
             "Fetched {} factory dependencies from object store",
-            factory_deps.factory_deps.len()
+            factory_deps.len()
         );
 
         // we cannot insert all factory deps because of field size limit triggered by UNNEST
         // in underlying query, see `https://www.postgresql.org/docs/current/limits.html`
         // there were around 100 thousand contracts on mainnet, where this issue first manifested
-        for chunk in factory_deps.factory_deps.chunks(1000) {
+        for chunk in factory_deps.chunks(1000) {
             let chunk_deps_hashmap: HashMap<H256, Vec<u8>> = chunk
                 .iter()
                 .map(|dep| {
@@ -909,6 +1027,24 @@ impl<'a> SnapshotsApplier<'a> {
         Ok(())
     }
 
+    async fn load_storage_logs_chunk(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        chunk_id: u64,
+    ) -> Result<StorageLogs, SnapshotsApplierError> {
+        let storage_key = SnapshotStorageLogsStorageKey {
+            chunk_id,
+            l1_batch_number,
+        };
+        StorageLogs::load(self.blob_store, storage_key, self.snapshot_chain.version)
+            .await
+            .map_err(|err| {
+                let context =
+                    format!("cannot fetch storage logs {storage_key:?} from object store");
+                SnapshotsApplierError::object_store(err, context)
+            })
+    }
+
     #[tracing::instrument(level = "debug", err, skip(self, semaphore))]
     async fn recover_storage_logs_single_chunk(
         &self,
@@ -922,18 +1058,27 @@ impl<'a> SnapshotsApplier<'a> {
         let latency =
             METRICS.storage_logs_chunks_duration[&StorageLogsChunksStage::LoadFromGcs].start();
 
-        let storage_key = SnapshotStorageLogsStorageKey {
-            chunk_id,
-            l1_batch_number: self.applied_snapshot_status.l1_batch_number,
-        };
-        let mut storage_logs =
-            StorageLogs::load(self.blob_store, storage_key, self.snapshot_version)
-                .await
-                .map_err(|err| {
-                    let context =
-                        format!("cannot fetch storage logs {storage_key:?} from object store");
-                    SnapshotsApplierError::object_store(err, context)
-                })?;
+        let (&full_l1_batch_number, differential_l1_batch_numbers) = self
+            .snapshot_chain
+            .l1_batch_numbers
+            .split_first()
+            .context("empty snapshot chain")?;
+        let mut storage_logs = self
+            .load_storage_logs_chunk(full_l1_batch_number, chunk_id)
+            .await?;
+        if !differential_l1_batch_numbers.is_empty() {
+            let chunk_count = self
+                .applied_snapshot_status
+                .storage_logs_chunks_processed
+                .len() as u64;
+            let hashed_keys_range = uniform_hashed_keys_chunk(chunk_id, chunk_count);
+            for &l1_batch_number in differential_l1_batch_numbers {
+                let differential_logs = self
+                    .load_storage_logs_chunk(l1_batch_number, chunk_id)
+                    .await?;
+                storage_logs.merge_differential(differential_logs, &hashed_keys_range)?;
+            }
+        }
 
         storage_logs.validate(&self.applied_snapshot_status)?;
         if self.drop_storage_key_preimages {
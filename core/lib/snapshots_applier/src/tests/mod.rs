@@ -18,7 +18,8 @@ use zksync_types::{
 
 use self::utils::{
     mock_l2_block_header, mock_recovery_status, mock_snapshot_header, mock_tokens, prepare_clients,
-    random_storage_logs, MockMainNodeClient, ObjectStoreWithErrors,
+    prepare_clients_for_differential_snapshot, random_storage_logs, MockMainNodeClient,
+    ObjectStoreWithErrors,
 };
 use super::*;
 use crate::tests::utils::{mock_factory_deps, HangingObjectStore};
@@ -251,6 +252,87 @@ async fn applier_recovers_explicitly_specified_snapshot(bytecode_kind: Option<By
     assert_eq!(all_storage_logs.len(), storage_logs.len());
 }
 
+#[tokio::test]
+async fn applier_recovers_differential_snapshot() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let expected_status = mock_recovery_status();
+    let base_l1_batch_number = expected_status.l1_batch_number - 10;
+    let base_logs = random_storage_logs::<H256>(base_l1_batch_number, 200);
+    let updated_logs = base_logs.iter().step_by(3).map(|log| SnapshotStorageLog {
+        value: H256::random(),
+        ..log.clone()
+    });
+    let new_logs = random_storage_logs::<H256>(expected_status.l1_batch_number, 50)
+        .into_iter()
+        .map(|log| SnapshotStorageLog {
+            enumeration_index: log.enumeration_index + base_logs.len() as u64,
+            ..log
+        });
+    let differential_logs: Vec<_> = updated_logs.chain(new_logs).collect();
+    let (object_store, client) = prepare_clients_for_differential_snapshot(
+        &expected_status,
+        base_l1_batch_number,
+        &base_logs,
+        &differential_logs,
+    )
+    .await;
+
+    let task = SnapshotsApplierTask::new(
+        SnapshotsApplierConfig::for_tests(),
+        pool.clone(),
+        Box::new(client),
+        object_store,
+    );
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let stats = task.run(stop_receiver).await.unwrap();
+    assert!(stats.done_work);
+
+    let mut expected_logs: HashMap<_, _> =
+        base_logs.into_iter().map(|log| (log.key, log)).collect();
+    expected_logs.extend(differential_logs.into_iter().map(|log| (log.key, log)));
+    assert_eq!(expected_logs.len(), 250);
+
+    let mut storage = pool.connection().await.unwrap();
+    let current_db_status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await
+        .unwrap();
+    assert_eq!(current_db_status.unwrap(), expected_status);
+
+    let all_storage_logs = storage
+        .storage_logs_dal()
+        .dump_all_storage_logs_for_tests()
+        .await;
+    assert_eq!(all_storage_logs.len(), expected_logs.len());
+    for db_log in all_storage_logs {
+        let expected_log = &expected_logs[&db_log.hashed_key];
+        assert_eq!(db_log.value, expected_log.value);
+        assert_eq!(db_log.l2_block_number, expected_status.l2_block_number);
+    }
+
+    let all_initial_writes = storage
+        .storage_logs_dedup_dal()
+        .dump_all_initial_writes_for_tests()
+        .await;
+    assert_eq!(all_initial_writes.len(), expected_logs.len());
+    for initial_write in all_initial_writes {
+        let log = &expected_logs[&initial_write.hashed_key];
+        assert_eq!(
+            initial_write.l1_batch_number,
+            log.l1_batch_number_of_initial_write
+        );
+        assert_eq!(initial_write.index, log.enumeration_index);
+    }
+
+    // Factory deps from both the base and differential snapshots must be recovered.
+    let all_factory_deps = storage
+        .factory_deps_dal()
+        .dump_all_factory_deps_for_tests()
+        .await;
+    assert_eq!(all_factory_deps.len(), 2);
+}
+
 #[tokio::test]
 async fn applier_errors_on_unexpected_bytecode_hash() {
     let pool = ConnectionPool::<Core>::test_pool().await;
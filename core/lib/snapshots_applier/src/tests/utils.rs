@@ -14,8 +14,8 @@ use zksync_types::{
     block::L2BlockHeader,
     bytecode::{BytecodeHash, BytecodeMarker},
     snapshots::{
-        SnapshotFactoryDependencies, SnapshotFactoryDependency, SnapshotHeader,
-        SnapshotRecoveryStatus, SnapshotStorageLog, SnapshotStorageLogsChunk,
+        uniform_hashed_keys_chunk, SnapshotFactoryDependencies, SnapshotFactoryDependency,
+        SnapshotHeader, SnapshotRecoveryStatus, SnapshotStorageLog, SnapshotStorageLogsChunk,
         SnapshotStorageLogsChunkMetadata, SnapshotStorageLogsStorageKey, SnapshotVersion,
     },
     tokens::{TokenInfo, TokenMetadata},
@@ -54,6 +54,8 @@ pub(super) struct MockMainNodeClient {
     pub fetch_l1_batch_responses: HashMap<L1BatchNumber, api::L1BatchDetails>,
     pub fetch_l2_block_responses: HashMap<L2BlockNumber, api::BlockDetails>,
     pub fetch_newest_snapshot_response: Option<SnapshotHeader>,
+    /// Snapshots other than the newest one (e.g., base snapshots for differential snapshots).
+    pub other_snapshot_responses: HashMap<L1BatchNumber, SnapshotHeader>,
     pub tokens_response: Vec<TokenInfo>,
     pub tokens_response_error: Arc<RwLock<Option<EnrichedClientError>>>,
 }
@@ -102,7 +104,8 @@ impl SnapshotsApplierMainNodeClient for MockMainNodeClient {
         Ok(self
             .fetch_newest_snapshot_response
             .clone()
-            .filter(|response| response.l1_batch_number == l1_batch_number))
+            .filter(|response| response.l1_batch_number == l1_batch_number)
+            .or_else(|| self.other_snapshot_responses.get(&l1_batch_number).cloned()))
     }
 
     async fn fetch_tokens(
@@ -237,6 +240,7 @@ fn l1_batch_details(number: L1BatchNumber, root_hash: H256) -> api::L1BatchDetai
         number,
         commitment: Some(H256::repeat_byte(number.0 as u8)),
         base: block_details_base(root_hash),
+        tee_attestation: None,
     }
 }
 
@@ -316,6 +320,7 @@ pub(super) fn mock_snapshot_header(
         version,
         l1_batch_number: status.l1_batch_number,
         l2_block_number: status.l2_block_number,
+        base_l1_batch_number: None,
         storage_logs_chunks: (0..status.storage_logs_chunks_processed.len() as u64)
             .map(|chunk_id| SnapshotStorageLogsChunkMetadata {
                 chunk_id,
@@ -377,6 +382,86 @@ where
     (object_store, client)
 }
 
+/// Prepares clients for recovery from a differential snapshot for `status` based on a full snapshot
+/// for `base_l1_batch_number`. Unlike [`prepare_clients()`], storage logs are distributed among chunks
+/// according to their hashed keys, like in real snapshots.
+pub(super) async fn prepare_clients_for_differential_snapshot(
+    status: &SnapshotRecoveryStatus,
+    base_l1_batch_number: L1BatchNumber,
+    base_logs: &[SnapshotStorageLog],
+    differential_logs: &[SnapshotStorageLog],
+) -> (Arc<dyn ObjectStore>, MockMainNodeClient) {
+    let object_store = MockObjectStore::arc();
+    let mut client = MockMainNodeClient::default();
+    let chunk_count = status.storage_logs_chunks_processed.len() as u64;
+
+    let differential_factory_deps = SnapshotFactoryDependencies {
+        factory_deps: vec![SnapshotFactoryDependency {
+            bytecode: Bytes((0..96).collect()),
+            hash: None,
+        }],
+    };
+    let snapshots = [
+        (base_l1_batch_number, mock_factory_deps(None), base_logs),
+        (
+            status.l1_batch_number,
+            differential_factory_deps,
+            differential_logs,
+        ),
+    ];
+    for (l1_batch_number, factory_deps, logs) in snapshots {
+        object_store
+            .put(l1_batch_number, &factory_deps)
+            .await
+            .unwrap();
+        for chunk_id in 0..chunk_count {
+            let hashed_keys_range = uniform_hashed_keys_chunk(chunk_id, chunk_count);
+            let chunk_storage_logs = SnapshotStorageLogsChunk {
+                storage_logs: logs
+                    .iter()
+                    .filter(|log| hashed_keys_range.contains(&log.key))
+                    .cloned()
+                    .collect(),
+            };
+            let chunk_key = SnapshotStorageLogsStorageKey {
+                l1_batch_number,
+                chunk_id,
+            };
+            object_store
+                .put(chunk_key, &chunk_storage_logs)
+                .await
+                .unwrap();
+        }
+    }
+
+    let base_status = SnapshotRecoveryStatus {
+        l1_batch_number: base_l1_batch_number,
+        l2_block_number: L2BlockNumber(base_l1_batch_number.0),
+        ..status.clone()
+    };
+    let base_header = mock_snapshot_header(SnapshotVersion::Version1.into(), &base_status);
+    client
+        .other_snapshot_responses
+        .insert(base_l1_batch_number, base_header);
+    client.fetch_newest_snapshot_response = Some(SnapshotHeader {
+        base_l1_batch_number: Some(base_l1_batch_number),
+        ..mock_snapshot_header(SnapshotVersion::Version1.into(), status)
+    });
+    client.fetch_l1_batch_responses.insert(
+        status.l1_batch_number,
+        l1_batch_details(status.l1_batch_number, status.l1_batch_root_hash),
+    );
+    client.fetch_l2_block_responses.insert(
+        status.l2_block_number,
+        l2_block_details(
+            status.l2_block_number,
+            status.l1_batch_number,
+            status.l2_block_hash,
+        ),
+    );
+    (object_store, client)
+}
+
 /// Object store wrapper that hangs up after processing the specified number of requests.
 /// Used to emulate the snapshot applier being restarted since, if it's configured to have concurrency 1,
 /// the applier will request an object from the store strictly after fully processing all previously requested objects.
@@ -1017,6 +1017,33 @@ pub struct L1BatchDetails {
     pub commitment: Option<H256>,
     #[serde(flatten)]
     pub base: BlockDetailsBase,
+    /// TEE attestation status of the batch. Only set by nodes tracking a TEE quorum policy, and only
+    /// for batches with at least one submitted TEE proof.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tee_attestation: Option<TeeBatchAttestation>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TeeAttestationStatus {
+    /// TEE proofs were submitted for the batch, but they don't satisfy the quorum policy (yet).
+    Pending,
+    /// The quorum policy is satisfied for the batch root hash.
+    Attested,
+}
+
+/// Aggregated TEE attestation status of an L1 batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeeBatchAttestation {
+    pub status: TeeAttestationStatus,
+    /// Number of distinct TEE keys that have signed the batch root hash. For pending batches,
+    /// this includes signatures not counted towards the quorum (e.g., from keys with expired attestations).
+    pub signers: u32,
+    /// TEE types of the signers.
+    pub tee_types: Vec<TeeType>,
+    /// Time at which the quorum was reached.
+    pub attested_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1033,6 +1060,40 @@ pub struct StorageProof {
 pub struct Proof {
     pub address: Address,
     pub storage_proof: Vec<StorageProof>,
+    /// Multiproof for all storage slots. Only present for [`ProofFormat::Multiproof`]; in this case,
+    /// `proof` fields in `storage_proof` are empty.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multiproof: Option<StorageMultiproof>,
+}
+
+/// Format of storage proofs returned by `zks_getProof`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProofFormat {
+    /// Separate Merkle proof for each storage slot.
+    #[default]
+    Individual,
+    /// Single multiproof for all storage slots, in which hashes shared by Merkle paths of several slots
+    /// are only included once.
+    Multiproof,
+}
+
+/// Multiproof for storage slots returned by `zks_getProof`.
+///
+/// The multiproof covers unique storage slots ordered by their hashed keys (i.e., Merkle tree keys)
+/// in the ascending order. It is verified by traversing the Merkle tree from the root and splitting the slots
+/// by the corresponding hashed key bit on each level. If both child subtrees contain slots, their hashes
+/// are restored recursively (first, the left subtree, then the right one). Otherwise, the hash of the only
+/// child subtree with slots is restored recursively, and then the hash of the other child is taken
+/// from `sibling_hashes`, unless it is an empty subtree according to `path_lengths`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageMultiproof {
+    /// Merkle path lengths for the proven storage slots, ordered by the hashed key. A path of length `n`
+    /// means that `256 - n` bottom-most levels of the path correspond to empty subtrees.
+    pub path_lengths: Vec<u16>,
+    /// Sibling hashes in the order they are consumed during verification.
+    pub sibling_hashes: Vec<H256>,
 }
 
 #[serde_as]
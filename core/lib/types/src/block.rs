@@ -152,6 +152,18 @@ pub struct L2BlockHeader {
     pub rolling_txs_hash: Option<H256>,
 }
 
+/// Gas usage of a sealed L2 block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct L2BlockGasUsage {
+    pub number: L2BlockNumber,
+    /// Total number of (L1 and L2) transactions in the block.
+    pub tx_count: u32,
+    /// Gas spent by transactions in the block, i.e. their gas limits minus refunds.
+    pub gas_used: u64,
+    /// The formal gas limit for the L2 block.
+    pub gas_limit: u64,
+}
+
 /// Structure that represents the data is returned by the storage oracle during batch execution.
 pub struct StorageOracleInfo {
     /// The refunds returned by the storage oracle.
@@ -203,6 +203,11 @@ pub struct VerificationEvmSettings {
     pub evm_version: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub optimizer_runs: Option<usize>,
+    /// If set, constructor arguments are taken from the deployment transaction instead of being checked
+    /// against `constructor_arguments` in the request. Used for requests imported from Sourcify, which
+    /// only provides metadata and sources.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub constructor_arguments_from_deployment: bool,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
@@ -285,6 +290,10 @@ pub struct CompilationArtifacts {
     /// Defaults to empty if no immutables are found.
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub immutable_refs: HashMap<String, Vec<ImmutableReference>>,
+    /// Compiler metadata JSON (`metadata` in `solc` output). Only set for EVM contracts compiled with `solc`;
+    /// used to export verified contracts in the Sourcify format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<String>,
 }
 
 /// Stores each immutable reference offset and length in deployed bytecode.
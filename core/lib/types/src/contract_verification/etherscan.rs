@@ -132,14 +132,24 @@ impl EtherscanVerificationRequest {
             optimization_used: self.optimization_used.map(|x| x.to_bool()).unwrap_or(false),
             optimizer_mode: self.optimizer_mode,
             constructor_arguments: Bytes::from(
-                hex::decode(self.constructor_arguments)
-                    .map_err(|_| anyhow::anyhow!("Invalid constructor arguments"))?,
+                hex::decode(
+                    self.constructor_arguments
+                        .strip_prefix("0x")
+                        .unwrap_or(&self.constructor_arguments),
+                )
+                .map_err(|_| anyhow::anyhow!("Invalid constructor arguments"))?,
             ),
             is_system: self.is_system.map(|x| x.to_bool()).unwrap_or(false),
             force_evmla: self.force_evmla.map(|x| x.to_bool()).unwrap_or(false),
             evm_specific: VerificationEvmSettings {
                 evm_version: self.evm_version,
-                optimizer_runs: self.runs.map(|x| x.parse().unwrap()),
+                optimizer_runs: self
+                    .runs
+                    .filter(|runs| !runs.is_empty())
+                    .map(|runs| runs.parse())
+                    .transpose()
+                    .map_err(|_| anyhow::anyhow!("Invalid optimizer runs"))?,
+                constructor_arguments_from_deployment: false,
             },
         })
     }
@@ -325,12 +335,13 @@ pub enum EtherscanPostPayload {
 }
 
 /// Etherscan API response result. It can either be a string or a structured response containing the source code.
-#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(untagged)]
 pub enum EtherscanResult {
     String(String),
-    SourceCode(EtherscanSourceCodeResponse),
+    /// Like Etherscan, the source code is returned as a single-element array; tools such as `hardhat-verify`
+    /// rely on this shape.
+    SourceCode(Vec<EtherscanSourceCodeResponse>),
 }
 
 /// Response from Etherscan API. For all supported actions, the result is always a string.
@@ -490,7 +501,8 @@ mod tests {
             verification_req.evm_specific,
             VerificationEvmSettings {
                 evm_version: Some("london".to_string()),
-                optimizer_runs: Some(200)
+                optimizer_runs: Some(200),
+                constructor_arguments_from_deployment: false
             }
         );
     }
@@ -568,7 +580,8 @@ mod tests {
             verification_req.evm_specific,
             VerificationEvmSettings {
                 evm_version: None,
-                optimizer_runs: None
+                optimizer_runs: None,
+                constructor_arguments_from_deployment: false
             }
         );
     }
@@ -598,6 +611,36 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_to_verification_request_prefixed_args_and_invalid_runs() {
+        let mut etherscan_req = EtherscanVerificationRequest {
+            code_format: EtherscanCodeFormat::SingleFile,
+            source_code: "contract Test {}".to_string(),
+            constructor_arguments: "0x0102".to_string(),
+            contract_address: "0x3333333333333333333333333333333333333333"
+                .parse()
+                .unwrap(),
+            contract_name: "Test".to_string(),
+            compiler_version: "v0.8.20+commit.a1b2c3d4".to_string(),
+            zksolc_version: None,
+            optimization_used: None,
+            optimizer_mode: None,
+            runs: Some(String::new()),
+            evm_version: None,
+            compiler_mode: None,
+            is_system: None,
+            force_evmla: None,
+        };
+
+        let verification_req = etherscan_req.clone().to_verification_request().unwrap();
+        assert_eq!(verification_req.constructor_arguments, Bytes(vec![1, 2]));
+        assert_eq!(verification_req.evm_specific.optimizer_runs, None);
+
+        etherscan_req.runs = Some("many".to_string());
+        let err = etherscan_req.to_verification_request().unwrap_err();
+        assert_eq!(err.to_string(), "Invalid optimizer runs");
+    }
+
     #[test]
     fn test_etherscan_response_constructors() {
         let success_resp = EtherscanResponse::successful("Success Result".to_string());
@@ -726,6 +769,7 @@ mod tests {
                 evm_specific: VerificationEvmSettings {
                     evm_version: Some("london".to_string()),
                     optimizer_runs: Some(200),
+                    constructor_arguments_from_deployment: false,
                 },
             },
         };
@@ -735,6 +779,7 @@ mod tests {
             bytecode: vec![],
             deployed_bytecode: None,
             immutable_refs: Default::default(),
+            metadata: None,
         };
 
         let verification_info = VerificationInfo {
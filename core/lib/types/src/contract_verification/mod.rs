@@ -1,3 +1,4 @@
 pub mod api;
 pub mod contract_identifier;
 pub mod etherscan;
+pub mod sourcify;
@@ -0,0 +1,479 @@
+//! Types for interop with [Sourcify]: Solidity compiler metadata, the Sourcify repository layout
+//! and the Sourcify API v2.
+//!
+//! [Sourcify]: https://docs.sourcify.dev/
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::api::{
+    CompilerVersions, SourceCodeData, VerificationEvmSettings, VerificationIncomingRequest,
+    VerificationInfo,
+};
+use crate::{web3::keccak256, Address, L2ChainId, H256};
+
+/// Name of the metadata file in the Sourcify repository layout.
+pub const METADATA_FILE_NAME: &str = "metadata.json";
+/// Name of the directory with contract sources in the Sourcify repository layout.
+pub const SOURCES_DIR_NAME: &str = "sources";
+
+/// Errors converting Sourcify metadata and sources into a verification request.
+#[derive(Debug, thiserror::Error)]
+pub enum SourcifyError {
+    #[error("unsupported language `{0}`; only Solidity is supported")]
+    UnsupportedLanguage(String),
+    #[error("metadata must specify exactly one compilation target, got {0}")]
+    InvalidCompilationTarget(usize),
+    #[error("source `{0}` is missing")]
+    MissingSource(String),
+    #[error("content of source `{0}` doesn't match its keccak256 hash in metadata")]
+    SourceHashMismatch(String),
+}
+
+/// Solidity compiler metadata (`metadata.json`), as produced by `solc` and stored by Sourcify.
+/// Only the fields necessary for verification are parsed; other fields are preserved as is.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SolcMetadata {
+    pub language: String,
+    pub compiler: MetadataCompiler,
+    pub settings: MetadataSettings,
+    pub sources: BTreeMap<String, MetadataSource>,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetadataCompiler {
+    /// Full compiler version, e.g. `0.8.24+commit.e11b9ed9`.
+    pub version: String,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetadataSettings {
+    /// Map from the source path to the name of the compiled contract. Always contains a single entry.
+    pub compilation_target: BTreeMap<String, String>,
+    /// Linked libraries in the `path:Name => address` format (unlike the nested format in the standard JSON input).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub libraries: BTreeMap<String, Address>,
+    /// Other settings (`optimizer`, `evmVersion`, `remappings`, `metadata`, `viaIR` etc.) that have
+    /// the same format as in the standard JSON input.
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetadataSource {
+    pub keccak256: H256,
+    /// Source content; only present if the contract was compiled with `useLiteralContent`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+impl SolcMetadata {
+    /// Returns the compilation target as `(source path, contract name)`.
+    pub fn compilation_target(&self) -> Result<(&str, &str), SourcifyError> {
+        let target = &self.settings.compilation_target;
+        match target.iter().next() {
+            Some((path, name)) if target.len() == 1 => Ok((path, name)),
+            _ => Err(SourcifyError::InvalidCompilationTarget(target.len())),
+        }
+    }
+
+    /// Returns the `solc` version without the commit suffix, e.g. `0.8.24` for `0.8.24+commit.e11b9ed9`.
+    pub fn solc_version(&self) -> &str {
+        let version = &self.compiler.version;
+        let version = version.strip_prefix('v').unwrap_or(version);
+        version
+            .split_once('+')
+            .map_or(version, |(version, _)| version)
+    }
+
+    /// Resolves contents of all sources referenced by the metadata. A source is looked up in `sources` by its path,
+    /// then by its keccak256 hash (Sourcify allows renaming source files), and finally in the content embedded
+    /// into the metadata. Source contents are checked against their hashes in the metadata.
+    pub fn resolve_sources(
+        &self,
+        sources: &HashMap<String, String>,
+    ) -> Result<BTreeMap<String, String>, SourcifyError> {
+        let sources_by_hash: HashMap<_, _> = sources
+            .values()
+            .map(|content| (H256(keccak256(content.as_bytes())), content))
+            .collect();
+
+        let mut resolved = BTreeMap::new();
+        for (path, source) in &self.sources {
+            let content = sources
+                .get(path)
+                .or_else(|| sources_by_hash.get(&source.keccak256).copied())
+                .or(source.content.as_ref())
+                .ok_or_else(|| SourcifyError::MissingSource(path.clone()))?;
+            if H256(keccak256(content.as_bytes())) != source.keccak256 {
+                return Err(SourcifyError::SourceHashMismatch(path.clone()));
+            }
+            resolved.insert(path.clone(), content.clone());
+        }
+        Ok(resolved)
+    }
+
+    /// Returns standard JSON settings equivalent to the settings in the metadata.
+    fn standard_json_settings(&self) -> serde_json::Map<String, serde_json::Value> {
+        let mut settings = self.settings.other.clone();
+        if !self.settings.libraries.is_empty() {
+            let mut libraries = serde_json::Map::new();
+            for (qualified_name, address) in &self.settings.libraries {
+                // Libraries without a path are global, which is denoted by an empty path in the standard JSON.
+                let (path, name) = qualified_name
+                    .rsplit_once(':')
+                    .unwrap_or(("", qualified_name));
+                let path_libraries = libraries
+                    .entry(path)
+                    .or_insert_with(|| serde_json::json!({}));
+                path_libraries[name] = serde_json::json!(address);
+            }
+            settings.insert("libraries".to_owned(), libraries.into());
+        }
+        settings
+    }
+
+    /// Converts the metadata and sources to a verification request for a contract at `contract_address`.
+    /// Since Sourcify doesn't require constructor arguments, they are taken from the contract deployment.
+    pub fn to_verification_request(
+        &self,
+        contract_address: Address,
+        sources: &HashMap<String, String>,
+    ) -> Result<VerificationIncomingRequest, SourcifyError> {
+        if self.language != "Solidity" {
+            return Err(SourcifyError::UnsupportedLanguage(self.language.clone()));
+        }
+        let (target_path, target_name) = self.compilation_target()?;
+        let sources: serde_json::Map<_, _> = self
+            .resolve_sources(sources)?
+            .into_iter()
+            .map(|(path, content)| (path, serde_json::json!({ "content": content })))
+            .collect();
+        let settings = self.standard_json_settings();
+        let optimizer = settings.get("optimizer");
+        let optimization_used = optimizer
+            .and_then(|optimizer| optimizer.get("enabled")?.as_bool())
+            .unwrap_or(false);
+        let optimizer_runs = optimizer
+            .and_then(|optimizer| optimizer.get("runs")?.as_u64())
+            .map(|runs| runs as usize);
+        let evm_version = settings
+            .get("evmVersion")
+            .and_then(|version| Some(version.as_str()?.to_owned()));
+
+        let mut standard_json = serde_json::Map::new();
+        standard_json.insert("language".to_owned(), "Solidity".into());
+        standard_json.insert("sources".to_owned(), sources.into());
+        standard_json.insert("settings".to_owned(), settings.into());
+        Ok(VerificationIncomingRequest {
+            contract_address,
+            source_code_data: SourceCodeData::StandardJsonInput(standard_json),
+            contract_name: format!("{target_path}:{target_name}"),
+            compiler_versions: CompilerVersions::Solc {
+                compiler_zksolc_version: None,
+                compiler_solc_version: self.solc_version().to_owned(),
+            },
+            optimization_used,
+            optimizer_mode: None,
+            constructor_arguments: Default::default(),
+            is_system: false,
+            force_evmla: false,
+            evm_specific: VerificationEvmSettings {
+                evm_version,
+                optimizer_runs,
+                constructor_arguments_from_deployment: true,
+            },
+        })
+    }
+}
+
+/// Match kind as reported by Sourcify.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourcifyMatch {
+    /// Bytecode matches including the metadata hash (`full_match` in the repository layout).
+    ExactMatch,
+    /// Bytecode matches except for the metadata hash (`partial_match` in the repository layout).
+    Match,
+}
+
+impl SourcifyMatch {
+    /// Returns the match kind of verified contract.
+    pub fn for_verification_info(info: &VerificationInfo) -> Self {
+        if info.is_perfect_match() {
+            Self::ExactMatch
+        } else {
+            Self::Match
+        }
+    }
+
+    /// Returns the name of the directory in the Sourcify repository layout containing contracts with this match kind.
+    pub fn repository_dir(self) -> &'static str {
+        match self {
+            Self::ExactMatch => "full_match",
+            Self::Match => "partial_match",
+        }
+    }
+
+    pub const ALL: [Self; 2] = [Self::ExactMatch, Self::Match];
+}
+
+/// Returns the EIP-55 checksummed representation of the address, which is used in the Sourcify repository layout.
+pub fn checksum_address(address: &Address) -> String {
+    let hex_address = hex::encode(address.as_bytes());
+    let hash = keccak256(hex_address.as_bytes());
+    let checksummed: String = hex_address
+        .chars()
+        .enumerate()
+        .map(|(i, ch)| {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0xf;
+            if nibble >= 8 {
+                ch.to_ascii_uppercase()
+            } else {
+                ch
+            }
+        })
+        .collect();
+    format!("0x{checksummed}")
+}
+
+/// Files of a verified contract in the Sourcify repository layout, i.e.
+/// `contracts/{full_match|partial_match}/{chainId}/{address}/` with `metadata.json` and the `sources` directory.
+#[derive(Debug, Clone)]
+pub struct SourcifyContractFiles {
+    pub match_kind: SourcifyMatch,
+    /// Raw metadata JSON as output by the compiler.
+    pub metadata: String,
+    /// Map from the source path to its content.
+    pub sources: BTreeMap<String, String>,
+}
+
+impl SourcifyContractFiles {
+    /// Extracts files from the verification info. Returns `None` if the contract cannot be represented in the Sourcify
+    /// format, e.g. because it isn't an EVM contract compiled with `solc`.
+    pub fn from_verification_info(info: &VerificationInfo) -> Option<Self> {
+        let raw_metadata = info.artifacts.metadata.as_ref()?;
+        let metadata: SolcMetadata = serde_json::from_str(raw_metadata).ok()?;
+        let sources = match &info.request.req.source_code_data {
+            SourceCodeData::StandardJsonInput(input) => input
+                .get("sources")?
+                .as_object()?
+                .iter()
+                .filter_map(|(path, source)| {
+                    Some((path.clone(), source.get("content")?.as_str()?.to_owned()))
+                })
+                .collect(),
+            // Single-file contracts have a single source, but its path is determined during compilation.
+            SourceCodeData::SolSingleFile(content) if metadata.sources.len() == 1 => {
+                let path = metadata.sources.keys().next()?;
+                HashMap::from([(path.clone(), content.clone())])
+            }
+            _ => return None,
+        };
+        let sources = metadata.resolve_sources(&sources).ok()?;
+        Some(Self {
+            match_kind: SourcifyMatch::for_verification_info(info),
+            metadata: raw_metadata.clone(),
+            sources,
+        })
+    }
+}
+
+/// Request body for `POST /v2/verify/metadata/{chainId}/{address}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourcifyMetadataVerificationRequest {
+    /// Map from the source path to its content.
+    pub sources: HashMap<String, String>,
+    pub metadata: SolcMetadata,
+    /// Ignored; the deployment transaction is determined by the verifier.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creation_transaction_hash: Option<H256>,
+}
+
+/// Response for an accepted verification request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourcifyVerificationAccepted {
+    pub verification_id: String,
+}
+
+/// Verification status of a contract in the Sourcify API format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourcifyContract {
+    #[serde(rename = "match")]
+    pub match_kind: Option<SourcifyMatch>,
+    /// Creation bytecode is compared without the metadata hash (and only if the contract was deployed
+    /// by an EVM deployment transaction), so this is always equal to `runtime_match`.
+    pub creation_match: Option<SourcifyMatch>,
+    pub runtime_match: Option<SourcifyMatch>,
+    pub chain_id: String,
+    pub address: Address,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verified_at: Option<DateTime<Utc>>,
+}
+
+impl SourcifyContract {
+    pub fn new(chain_id: L2ChainId, address: Address, info: Option<&VerificationInfo>) -> Self {
+        let match_kind = info.map(SourcifyMatch::for_verification_info);
+        Self {
+            match_kind,
+            creation_match: match_kind,
+            runtime_match: match_kind,
+            chain_id: chain_id.as_u64().to_string(),
+            address,
+            verified_at: info.map(|info| info.verified_at),
+        }
+    }
+}
+
+/// Error in the Sourcify API format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourcifyErrorResponse {
+    /// Machine-readable error code, e.g. `no_match`.
+    pub custom_code: String,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_id: Option<String>,
+}
+
+/// Response for `GET /v2/verify/{verificationId}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourcifyVerificationJob {
+    pub is_job_completed: bool,
+    pub verification_id: String,
+    pub contract: SourcifyContract,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<SourcifyErrorResponse>,
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    const SOURCE: &str = "contract Counter { uint256 value; }";
+
+    fn mock_metadata() -> SolcMetadata {
+        let source_hash = H256(keccak256(SOURCE.as_bytes()));
+        serde_json::from_value(serde_json::json!({
+            "compiler": { "version": "0.8.24+commit.e11b9ed9" },
+            "language": "Solidity",
+            "output": { "abi": [] },
+            "settings": {
+                "compilationTarget": { "contracts/Counter.sol": "Counter" },
+                "evmVersion": "cancun",
+                "libraries": { "contracts/Lib.sol:Lib": "0x0101010101010101010101010101010101010101" },
+                "metadata": { "bytecodeHash": "ipfs" },
+                "optimizer": { "enabled": true, "runs": 200 },
+                "remappings": []
+            },
+            "sources": {
+                "contracts/Counter.sol": {
+                    "keccak256": source_hash,
+                    "license": "MIT",
+                    "urls": []
+                }
+            },
+            "version": 1
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn converting_metadata_to_verification_request() {
+        let metadata = mock_metadata();
+        assert_eq!(metadata.solc_version(), "0.8.24");
+        let address = Address::repeat_byte(2);
+        let sources = HashMap::from([("contracts/Counter.sol".to_owned(), SOURCE.to_owned())]);
+        let req = metadata.to_verification_request(address, &sources).unwrap();
+
+        assert_eq!(req.contract_address, address);
+        assert_eq!(req.contract_name, "contracts/Counter.sol:Counter");
+        assert_eq!(
+            req.compiler_versions,
+            CompilerVersions::Solc {
+                compiler_zksolc_version: None,
+                compiler_solc_version: "0.8.24".to_owned(),
+            }
+        );
+        assert!(req.optimization_used);
+        assert_eq!(
+            req.evm_specific,
+            VerificationEvmSettings {
+                evm_version: Some("cancun".to_owned()),
+                optimizer_runs: Some(200),
+                constructor_arguments_from_deployment: true,
+            }
+        );
+        let SourceCodeData::StandardJsonInput(input) = &req.source_code_data else {
+            panic!("unexpected source code data: {:?}", req.source_code_data);
+        };
+        assert_eq!(
+            input["sources"],
+            serde_json::json!({ "contracts/Counter.sol": { "content": SOURCE } })
+        );
+        let settings = &input["settings"];
+        assert_eq!(
+            settings["libraries"],
+            serde_json::json!({
+                "contracts/Lib.sol": { "Lib": "0x0101010101010101010101010101010101010101" }
+            })
+        );
+        assert_eq!(settings["metadata"]["bytecodeHash"], "ipfs");
+        assert!(settings.get("compilationTarget").is_none());
+    }
+
+    #[test]
+    fn resolving_sources() {
+        let metadata = mock_metadata();
+        // Renamed sources are looked up by hash.
+        let sources = HashMap::from([("Counter.sol".to_owned(), SOURCE.to_owned())]);
+        let resolved = metadata.resolve_sources(&sources).unwrap();
+        assert_eq!(resolved["contracts/Counter.sol"], SOURCE);
+
+        let err = metadata.resolve_sources(&HashMap::new()).unwrap_err();
+        assert_matches!(err, SourcifyError::MissingSource(path) if path == "contracts/Counter.sol");
+
+        let sources = HashMap::from([(
+            "contracts/Counter.sol".to_owned(),
+            "contract Other {}".to_owned(),
+        )]);
+        let err = metadata.resolve_sources(&sources).unwrap_err();
+        assert_matches!(err, SourcifyError::SourceHashMismatch(_));
+
+        let mut metadata = mock_metadata();
+        metadata
+            .sources
+            .get_mut("contracts/Counter.sol")
+            .unwrap()
+            .content = Some(SOURCE.to_owned());
+        let resolved = metadata.resolve_sources(&HashMap::new()).unwrap();
+        assert_eq!(resolved["contracts/Counter.sol"], SOURCE);
+    }
+
+    #[test]
+    fn checksumming_address() {
+        // Test vector from EIP-55.
+        let address: Address = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            checksum_address(&address),
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+        );
+    }
+}
@@ -0,0 +1,59 @@
+//! Bundles of L2 transactions executed atomically.
+
+use crate::{l2::L2Tx, web3::keccak256, H256};
+
+/// Bundle of L2 transactions that must be executed atomically: either all transactions in the bundle are included
+/// into the same L2 block in the specified order, or none of them are.
+#[derive(Debug, Clone, PartialEq)]
+pub struct L2TxBundle {
+    hash: H256,
+    txs: Vec<L2Tx>,
+}
+
+impl L2TxBundle {
+    /// Maximum number of transactions in a bundle.
+    pub const MAX_LEN: usize = 16;
+
+    /// Creates a bundle from the provided transactions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `txs` is empty.
+    pub fn new(txs: Vec<L2Tx>) -> Self {
+        assert!(!txs.is_empty(), "transaction bundle cannot be empty");
+        let hash = Self::compute_hash(txs.iter().map(L2Tx::hash));
+        Self { hash, txs }
+    }
+
+    /// Computes the bundle hash from the hashes of its transactions. The hash is the keccak256 digest
+    /// of the concatenated transaction hashes.
+    pub fn compute_hash(tx_hashes: impl IntoIterator<Item = H256>) -> H256 {
+        let preimage: Vec<u8> = tx_hashes.into_iter().flat_map(|hash| hash.0).collect();
+        H256(keccak256(&preimage))
+    }
+
+    /// Returns the bundle hash.
+    pub fn hash(&self) -> H256 {
+        self.hash
+    }
+
+    /// Returns transactions in the bundle in the execution order.
+    pub fn txs(&self) -> &[L2Tx] {
+        &self.txs
+    }
+
+    /// Returns the number of transactions in the bundle.
+    pub fn len(&self) -> usize {
+        self.txs.len()
+    }
+
+    /// Always returns `false` since bundles cannot be empty; provided for consistency with [`Self::len()`].
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Unwraps transactions in the bundle.
+    pub fn into_txs(self) -> Vec<L2Tx> {
+        self.txs
+    }
+}
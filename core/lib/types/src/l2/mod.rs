@@ -21,6 +21,7 @@ use crate::{
     U256, U64,
 };
 
+pub mod bundle;
 pub mod error;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
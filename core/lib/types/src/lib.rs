@@ -43,6 +43,7 @@ pub mod l2;
 pub mod l2_to_l1_log;
 pub mod priority_op_onchain_data;
 pub mod protocol_upgrade;
+pub mod pruning;
 pub mod snapshots;
 pub mod storage;
 pub mod system_contracts;
@@ -0,0 +1,72 @@
+//! Types used to archive pruned Postgres data in an object store.
+
+use std::ops;
+
+use serde::{Deserialize, Serialize};
+use zksync_basic_types::{L2BlockNumber, H256};
+
+use crate::api;
+
+/// Key of a [`PrunedL2BlocksArchive`] in an object store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PrunedL2BlocksArchiveKey {
+    pub first_l2_block: L2BlockNumber,
+    pub last_l2_block: L2BlockNumber,
+}
+
+impl PrunedL2BlocksArchiveKey {
+    /// Returns the range of L2 blocks covered by the archive.
+    pub fn l2_blocks(&self) -> ops::RangeInclusive<L2BlockNumber> {
+        self.first_l2_block..=self.last_l2_block
+    }
+}
+
+impl From<ops::RangeInclusive<L2BlockNumber>> for PrunedL2BlocksArchiveKey {
+    fn from(range: ops::RangeInclusive<L2BlockNumber>) -> Self {
+        Self {
+            first_l2_block: *range.start(),
+            last_l2_block: *range.end(),
+        }
+    }
+}
+
+/// Archive of a contiguous range of pruned L2 blocks. Archives are written once by the DB pruner before
+/// the corresponding data is soft-pruned, and are never modified afterwards.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrunedL2BlocksArchive {
+    /// Archived L2 blocks ordered by ascending number.
+    pub l2_blocks: Vec<ArchivedL2Block>,
+}
+
+impl PrunedL2BlocksArchive {
+    /// Returns an archived L2 block with the specified number, if it's present in the archive.
+    pub fn l2_block(&self, number: L2BlockNumber) -> Option<&ArchivedL2Block> {
+        let first_number = self.l2_blocks.first()?.block.number.as_u32();
+        let idx = number.0.checked_sub(first_number)?;
+        let block = self.l2_blocks.get(idx as usize)?;
+        (block.block.number.as_u32() == number.0).then_some(block)
+    }
+}
+
+/// Pruned L2 block together with all data necessary to serve it via the web3 API.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedL2Block {
+    /// Block header as returned by `eth_getBlockByNumber` without full transactions.
+    pub block: api::Block<H256>,
+    /// Transactions in the block ordered by their index in the block.
+    pub transactions: Vec<api::Transaction>,
+    /// Events emitted in the block ordered by their index in the block.
+    pub logs: Vec<api::Log>,
+    /// Storage logs produced by the block ordered by the operation number.
+    pub storage_logs: Vec<ArchivedStorageLog>,
+}
+
+/// Storage write in an [`ArchivedL2Block`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedStorageLog {
+    pub hashed_key: H256,
+    pub value: H256,
+}
@@ -10,9 +10,36 @@ use crate::{u256_to_h256, utils, web3::Bytes, ProtocolVersionId, StorageKey, Sto
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AllSnapshots {
-    /// L1 batch numbers for complete snapshots. Ordered by descending number (i.e., 0th element
+    /// L1 batch numbers for complete full snapshots. Ordered by descending number (i.e., 0th element
     /// corresponds to the newest snapshot).
     pub snapshots_l1_batch_numbers: Vec<L1BatchNumber>,
+    /// Complete differential snapshots. Ordered by descending L1 batch number.
+    ///
+    /// Differential snapshots are not included into `snapshots_l1_batch_numbers` since nodes unaware of them
+    /// would treat them as full snapshots.
+    #[serde(default)]
+    pub differential_snapshots: Vec<DifferentialSnapshotInfo>,
+}
+
+impl AllSnapshots {
+    /// Returns the L1 batch number of the newest snapshot (full or differential).
+    pub fn newest_l1_batch_number(&self) -> Option<L1BatchNumber> {
+        let newest_full = self.snapshots_l1_batch_numbers.first().copied();
+        let newest_differential = self
+            .differential_snapshots
+            .first()
+            .map(|snapshot| snapshot.l1_batch_number);
+        newest_full.max(newest_differential)
+    }
+}
+
+/// Brief information about a differential snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DifferentialSnapshotInfo {
+    pub l1_batch_number: L1BatchNumber,
+    /// L1 batch of the snapshot this snapshot is based on. The base snapshot may be differential itself.
+    pub base_l1_batch_number: L1BatchNumber,
 }
 
 /// Version of snapshot influencing the format of data stored in GCS.
@@ -33,6 +60,9 @@ pub struct SnapshotMetadata {
     pub version: SnapshotVersion,
     /// L1 batch for the snapshot. The data in the snapshot captures node storage at the end of this batch.
     pub l1_batch_number: L1BatchNumber,
+    /// For differential snapshots, L1 batch of the base snapshot. A differential snapshot contains only storage logs
+    /// and factory deps changed after the base L1 batch; chunking of its storage logs is the same as for the base snapshot.
+    pub base_l1_batch_number: Option<L1BatchNumber>,
     /// Path to the factory dependencies blob.
     pub factory_deps_filepath: String,
     /// Paths to the storage log blobs. Ordered by the chunk ID. If a certain chunk is not produced yet,
@@ -58,6 +88,10 @@ pub struct SnapshotHeader {
     pub l1_batch_number: L1BatchNumber,
     #[serde(rename = "miniblockNumber")] // legacy naming
     pub l2_block_number: L2BlockNumber,
+    /// For differential snapshots, L1 batch of the base snapshot. To recover from a differential snapshot,
+    /// storage log chunks of the base snapshot should be merged with the corresponding chunks of this snapshot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_l1_batch_number: Option<L1BatchNumber>,
     /// Ordered by chunk IDs.
     pub storage_logs_chunks: Vec<SnapshotStorageLogsChunkMetadata>,
     pub factory_deps_filepath: String,
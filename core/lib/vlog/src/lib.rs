@@ -245,6 +245,7 @@ mod tests {
                 tracing_endpoint: Some(bogus_url.clone()),
                 logging_endpoint: Some(bogus_url),
                 service: Default::default(),
+                tx_lifecycle_tracing: true,
             }))
             .try_build()
             .ok();
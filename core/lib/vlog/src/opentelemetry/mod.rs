@@ -7,6 +7,8 @@ use opentelemetry_semantic_conventions::attribute;
 use tracing_subscriber::{registry::LookupSpan, EnvFilter, Layer};
 use url::Url;
 
+pub mod tx_lifecycle;
+
 /// Information about the service.
 ///
 /// This information is initially filled as follows:
@@ -132,6 +134,8 @@ pub struct OpenTelemetry {
     pub logging_endpoint: Option<Url>,
     /// Information about service
     pub service: ServiceDescriptor,
+    /// Enables [transaction lifecycle tracing](tx_lifecycle). Has no effect if the tracing endpoint is not provided.
+    pub tx_lifecycle_tracing: bool,
 }
 
 impl OpenTelemetry {
@@ -153,9 +157,16 @@ impl OpenTelemetry {
             tracing_endpoint: parse_url(tracing_endpoint)?,
             logging_endpoint: parse_url(logging_endpoint)?,
             service: ServiceDescriptor::new(),
+            tx_lifecycle_tracing: false,
         })
     }
 
+    /// Enables or disables [transaction lifecycle tracing](tx_lifecycle).
+    pub fn with_tx_lifecycle_tracing(mut self, enabled: bool) -> Self {
+        self.tx_lifecycle_tracing = enabled;
+        self
+    }
+
     /// Can be used to override the service descriptor used by the layer.
     pub fn with_service_descriptor(mut self, service: ServiceDescriptor) -> Self {
         self.service = service;
@@ -242,6 +253,12 @@ impl OpenTelemetry {
         let tracer = provider.tracer(service_name);
 
         opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+        // Transaction lifecycle spans are recorded directly via the global tracer provider, bypassing `tracing`.
+        opentelemetry::global::set_tracer_provider(provider.clone());
+        if self.tx_lifecycle_tracing && !matches!(self.opentelemetry_level, OpenTelemetryLevel::OFF)
+        {
+            tx_lifecycle::enable();
+        }
         let layer = tracing_opentelemetry::layer()
             .with_tracer(tracer)
             .with_filter(filter);
@@ -0,0 +1,352 @@
+//! Tracing of the transaction lifecycle across node components.
+//!
+//! Each transaction gets its own trace spanning all lifecycle [stages](TxStage) from the API submission
+//! to the finalized execution of the containing L1 batch on L1. The trace context is derived from the transaction hash:
+//! the trace ID consists of the first 16 bytes of the hash, and the root span ID of the following 8 bytes.
+//! Since the hash is persisted together with the transaction (and L1 batches reference transactions
+//! via the persisted batch number), any component, including ones running on other nodes, can contribute
+//! spans to the transaction trace without explicitly propagating the context.
+//!
+//! Spans are recorded with explicit start and end timestamps, so that a stage may start in one component
+//! and finish in another one (e.g., waiting in the mempool starts when the transaction is received by the API server
+//! and ends when it's picked up by the state keeper).
+//!
+//! Several nodes may record spans for the same stage (e.g., external nodes re-execute transactions and seal L2 blocks
+//! / L1 batches after the main node). Such spans are distinguished by the `node.role` attribute, which is set
+//! to the [role](NodeRole) of the recording node.
+//!
+//! Lifecycle tracing is disabled by default; it's enabled by the OpenTelemetry tracing layer
+//! if [`OpenTelemetry::tx_lifecycle_tracing`](super::OpenTelemetry::tx_lifecycle_tracing) is set.
+
+use std::{
+    fmt::{self, Write as _},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+    time::SystemTime,
+};
+
+use opentelemetry::{
+    global,
+    trace::{
+        Span as _, SpanContext, SpanId, SpanKind, Status, TraceContextExt, TraceFlags, TraceId,
+        TraceState, Tracer,
+    },
+    Context, KeyValue, Value,
+};
+
+const TRACER_NAME: &str = "zksync_tx_lifecycle";
+
+static IS_ENABLED: AtomicBool = AtomicBool::new(false);
+static NODE_ROLE: OnceLock<NodeRole> = OnceLock::new();
+
+/// Enables transaction lifecycle tracing. Spans are recorded using the global tracer provider.
+///
+/// This is called automatically when installing the [OpenTelemetry layer](super::OpenTelemetry) with
+/// lifecycle tracing enabled; calling it manually is only useful in tests.
+pub fn enable() {
+    IS_ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Checks whether transaction lifecycle tracing is enabled. Can be used to skip expensive preparations
+/// (e.g., loading transaction hashes from the storage) before recording spans.
+pub fn is_enabled() -> bool {
+    IS_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Role of the node recording lifecycle spans.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum NodeRole {
+    /// Main node.
+    #[default]
+    Main,
+    /// External node.
+    External,
+}
+
+impl NodeRole {
+    /// Returns the value of the `node.role` span attribute for this role.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Main => "main",
+            Self::External => "external",
+        }
+    }
+}
+
+/// Sets the role of the node recording lifecycle spans. If not called, the role is [`NodeRole::Main`].
+/// Should be called once during node initialization; subsequent calls are ignored.
+pub fn set_node_role(role: NodeRole) {
+    if NODE_ROLE.set(role).is_err() {
+        tracing::warn!("Node role for transaction lifecycle tracing is already set");
+    }
+}
+
+fn node_role() -> NodeRole {
+    NODE_ROLE.get().copied().unwrap_or_default()
+}
+
+/// Stage of the transaction lifecycle corresponding to a span in the transaction trace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TxStage {
+    /// Submission of the transaction via the API server of the main node. This is the root span of the trace.
+    Submission,
+    /// Submission of the transaction via the API server of an external node, which proxies it to the main node.
+    ProxiedSubmission,
+    /// Insertion of the transaction into the mempool as a part of the submission.
+    MempoolInsertion,
+    /// Waiting in the mempool until the transaction is picked up by the state keeper.
+    MempoolWait,
+    /// Execution of the transaction by the state keeper. May be recorded multiple times if the transaction
+    /// is rolled back and then re-executed.
+    Execution,
+    /// Sealing the L2 block containing the transaction.
+    L2BlockSeal,
+    /// Sealing the L1 batch containing the transaction.
+    L1BatchSeal,
+    /// Committing the L1 batch containing the transaction on L1.
+    L1BatchCommit,
+    /// Publishing the proof for the L1 batch containing the transaction on L1.
+    L1BatchProve,
+    /// Executing the L1 batch containing the transaction on L1.
+    L1BatchExecute,
+    /// Waiting for finality of an L1 transaction committing, proving or executing the L1 batch containing the transaction.
+    /// Starts when the L1 transaction is included into an L1 block.
+    L1Finality,
+}
+
+impl TxStage {
+    /// Returns the name of the span corresponding to this stage.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Submission => "tx_submission",
+            Self::ProxiedSubmission => "tx_proxied_submission",
+            Self::MempoolInsertion => "tx_mempool_insertion",
+            Self::MempoolWait => "tx_mempool_wait",
+            Self::Execution => "tx_execution",
+            Self::L2BlockSeal => "tx_l2_block_seal",
+            Self::L1BatchSeal => "tx_l1_batch_seal",
+            Self::L1BatchCommit => "tx_l1_batch_commit",
+            Self::L1BatchProve => "tx_l1_batch_prove",
+            Self::L1BatchExecute => "tx_l1_batch_execute",
+            Self::L1Finality => "tx_l1_finality",
+        }
+    }
+}
+
+impl fmt::Display for TxStage {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.as_str())
+    }
+}
+
+/// Trace of a single transaction.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct TxTrace {
+    tx_hash: [u8; 32],
+}
+
+impl fmt::Debug for TxTrace {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("TxTrace")
+            .field("tx_hash", &self.hex_hash())
+            .finish()
+    }
+}
+
+impl TxTrace {
+    /// Creates a trace for the transaction with the specified hash.
+    pub fn new(tx_hash: [u8; 32]) -> Self {
+        Self { tx_hash }
+    }
+
+    /// Returns the ID of the trace.
+    pub fn trace_id(&self) -> TraceId {
+        TraceId::from_bytes(self.tx_hash[..16].try_into().unwrap())
+    }
+
+    fn root_span_id(&self) -> SpanId {
+        SpanId::from_bytes(self.tx_hash[16..24].try_into().unwrap())
+    }
+
+    fn hex_hash(&self) -> String {
+        let mut hex = String::with_capacity(66);
+        hex.push_str("0x");
+        for byte in self.tx_hash {
+            write!(hex, "{byte:02x}").unwrap();
+        }
+        hex
+    }
+
+    /// Starts a span for the specified lifecycle stage at the current moment.
+    pub fn start(self, stage: TxStage) -> TxSpan {
+        self.start_at(stage, SystemTime::now())
+    }
+
+    /// Starts a span for the specified lifecycle stage at the specified moment.
+    pub fn start_at(self, stage: TxStage, started_at: SystemTime) -> TxSpan {
+        TxSpan {
+            trace: self,
+            stage,
+            started_at,
+            attributes: vec![],
+            error: None,
+        }
+    }
+}
+
+/// Span for a stage of the transaction lifecycle. The span is only recorded once it's [finished](Self::finish());
+/// dropping the span discards it.
+#[derive(Debug)]
+#[must_use = "span must be finished to be recorded"]
+pub struct TxSpan {
+    trace: TxTrace,
+    stage: TxStage,
+    started_at: SystemTime,
+    attributes: Vec<KeyValue>,
+    error: Option<String>,
+}
+
+impl TxSpan {
+    /// Adds an attribute to the span.
+    pub fn with_attribute(mut self, key: &'static str, value: impl Into<Value>) -> Self {
+        self.attributes.push(KeyValue::new(key, value));
+        self
+    }
+
+    /// Marks the span as failed.
+    pub fn with_error(mut self, message: impl fmt::Display) -> Self {
+        self.error = Some(message.to_string());
+        self
+    }
+
+    /// Finishes the span at the current moment.
+    pub fn finish(self) {
+        self.finish_at(SystemTime::now());
+    }
+
+    /// Finishes the span at the specified moment.
+    pub fn finish_at(self, finished_at: SystemTime) {
+        if is_enabled() {
+            self.record(&global::tracer(TRACER_NAME), finished_at);
+        }
+    }
+
+    fn record<T: Tracer>(self, tracer: &T, finished_at: SystemTime) {
+        let trace_id = self.trace.trace_id();
+        let root_span_id = self.trace.root_span_id();
+        let mut builder = tracer
+            .span_builder(self.stage.as_str())
+            .with_kind(SpanKind::Internal)
+            .with_trace_id(trace_id)
+            .with_start_time(self.started_at)
+            .with_attributes(
+                [
+                    KeyValue::new("tx.hash", self.trace.hex_hash()),
+                    KeyValue::new("node.role", node_role().as_str()),
+                ]
+                .into_iter()
+                .chain(self.attributes),
+            );
+        if let Some(error) = self.error {
+            builder = builder.with_status(Status::error(error));
+        }
+
+        let parent_cx = if self.stage == TxStage::Submission {
+            builder = builder.with_span_id(root_span_id);
+            Context::new()
+        } else {
+            let root_span_context = SpanContext::new(
+                trace_id,
+                root_span_id,
+                TraceFlags::SAMPLED,
+                true,
+                TraceState::default(),
+            );
+            Context::new().with_remote_span_context(root_span_context)
+        };
+        let mut span = tracer.build_with_context(builder, &parent_cx);
+        span.end_with_timestamp(finished_at);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+
+    use super::*;
+
+    #[test]
+    fn trace_context_is_derived_from_tx_hash() {
+        let mut tx_hash = [0_u8; 32];
+        for (i, byte) in tx_hash.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let trace = TxTrace::new(tx_hash);
+
+        assert_eq!(
+            trace.trace_id().to_string(),
+            "000102030405060708090a0b0c0d0e0f"
+        );
+        assert_eq!(trace.root_span_id().to_string(), "1011121314151617");
+        assert_eq!(
+            trace.hex_hash(),
+            "0x000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"
+        );
+    }
+
+    #[test]
+    fn recording_spans() {
+        let exporter = InMemorySpanExporter::default();
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let tracer = provider.tracer("test");
+
+        let trace = TxTrace::new([1; 32]);
+        let started_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let finished_at = started_at + Duration::from_secs(5);
+        trace
+            .start_at(TxStage::Submission, started_at)
+            .record(&tracer, finished_at);
+        trace
+            .start_at(TxStage::Execution, finished_at)
+            .with_attribute("l2_block.number", 42_i64)
+            .with_error("rejected")
+            .record(&tracer, finished_at + Duration::from_secs(1));
+
+        let spans = exporter.get_finished_spans().unwrap();
+        assert_eq!(spans.len(), 2);
+        let [root_span, execution_span] = spans.as_slice() else {
+            unreachable!();
+        };
+
+        assert_eq!(root_span.name, "tx_submission");
+        assert_eq!(root_span.span_context.trace_id(), trace.trace_id());
+        assert_eq!(root_span.span_context.span_id(), trace.root_span_id());
+        assert_eq!(root_span.parent_span_id, SpanId::INVALID);
+        assert_eq!(root_span.start_time, started_at);
+        assert_eq!(root_span.end_time, finished_at);
+
+        assert_eq!(execution_span.name, "tx_execution");
+        assert_eq!(execution_span.span_context.trace_id(), trace.trace_id());
+        assert_ne!(execution_span.span_context.span_id(), trace.root_span_id());
+        assert_eq!(execution_span.parent_span_id, trace.root_span_id());
+        assert!(matches!(execution_span.status, Status::Error { .. }));
+        assert!(execution_span
+            .attributes
+            .contains(&KeyValue::new("l2_block.number", 42_i64)));
+        assert!(execution_span
+            .attributes
+            .contains(&KeyValue::new("tx.hash", trace.hex_hash())));
+        assert!(execution_span
+            .attributes
+            .contains(&KeyValue::new("node.role", "main")));
+    }
+}
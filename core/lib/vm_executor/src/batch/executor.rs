@@ -53,16 +53,19 @@ impl<S> HandleOrError<S> {
 pub struct MainBatchExecutor<S> {
     handle: HandleOrError<S>,
     commands: mpsc::Sender<Command>,
+    speculation_lookahead: usize,
 }
 
 impl<S: ReadStorage> MainBatchExecutor<S> {
     pub(super) fn new(
         handle: JoinHandle<anyhow::Result<StorageView<S>>>,
         commands: mpsc::Sender<Command>,
+        speculation_lookahead: usize,
     ) -> Self {
         Self {
             handle: HandleOrError::Handle(handle),
             commands,
+            speculation_lookahead,
         }
     }
 }
@@ -118,6 +121,32 @@ where
         Ok(res)
     }
 
+    #[tracing::instrument(skip_all, fields(txs.len = txs.len()))]
+    async fn execute_bundle(
+        &mut self,
+        txs: Vec<Transaction>,
+    ) -> anyhow::Result<Vec<BatchTransactionExecutionResult>> {
+        let (response_sender, response_receiver) = oneshot::channel();
+        let send_failed = self
+            .commands
+            .send(Command::ExecuteBundle(txs, response_sender))
+            .await
+            .is_err();
+        if send_failed {
+            return Err(self.handle.wait_for_error().await);
+        }
+
+        let latency = EXECUTOR_METRICS.batch_executor_command_response_time
+            [&ExecutorCommand::ExecuteBundle]
+            .start();
+        let res = match response_receiver.await {
+            Ok(res) => res,
+            Err(_) => return Err(self.handle.wait_for_error().await),
+        };
+        latency.observe();
+        Ok(res)
+    }
+
     #[tracing::instrument(skip_all)]
     async fn rollback_last_tx(&mut self) -> anyhow::Result<()> {
         // While we don't get anything from the channel, it's useful to have it as a confirmation that the operation
@@ -239,6 +268,36 @@ where
         latency.observe();
         Ok(())
     }
+
+    fn speculation_lookahead(&self) -> usize {
+        self.speculation_lookahead
+    }
+
+    #[tracing::instrument(skip_all, fields(txs.len = txs.len()))]
+    async fn speculate(&mut self, txs: Vec<Transaction>) -> anyhow::Result<()> {
+        if self.speculation_lookahead == 0 || txs.is_empty() {
+            return Ok(());
+        }
+
+        let (response_sender, response_receiver) = oneshot::channel();
+        let send_failed = self
+            .commands
+            .send(Command::Speculate(txs, response_sender))
+            .await
+            .is_err();
+        if send_failed {
+            return Err(self.handle.wait_for_error().await);
+        }
+
+        let latency = EXECUTOR_METRICS.batch_executor_command_response_time
+            [&ExecutorCommand::Speculate]
+            .start();
+        if response_receiver.await.is_err() {
+            return Err(self.handle.wait_for_error().await);
+        }
+        latency.observe();
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -252,4 +311,9 @@ pub(super) enum Command {
     FinishBatch(oneshot::Sender<FinishedL1Batch>),
     RollbackL2Block(oneshot::Sender<()>),
     CommitL2Block(oneshot::Sender<()>),
+    Speculate(Vec<Transaction>, oneshot::Sender<()>),
+    ExecuteBundle(
+        Vec<Transaction>,
+        oneshot::Sender<Vec<BatchTransactionExecutionResult>>,
+    ),
 }
@@ -1,4 +1,4 @@
-use std::{fmt, marker::PhantomData, rc::Rc, sync::Arc, time::Duration};
+use std::{fmt, marker::PhantomData, num::NonZeroUsize, rc::Rc, sync::Arc, time::Duration};
 
 use anyhow::Context as _;
 use once_cell::sync::OnceCell;
@@ -26,6 +26,7 @@ use zksync_types::{commitment::PubdataParams, vm::FastVmMode, Transaction};
 use super::{
     executor::{Command, MainBatchExecutor},
     metrics::{TxExecutionStage, BATCH_TIP_METRICS, EXECUTOR_METRICS, KEEPER_METRICS},
+    speculative::{MainVmStorage, SharedStorage, SpeculationOptions, Speculator},
 };
 use crate::shared::{InteractionType, RuntimeContextStorageMetrics, Sealed};
 
@@ -92,6 +93,7 @@ pub struct MainBatchExecutorFactory<Tr> {
     observe_storage_metrics: bool,
     skip_signature_verification: bool,
     divergence_handler: Option<DivergenceHandler>,
+    speculation: Option<SpeculationOptions>,
     _tracer: PhantomData<Tr>,
 }
 
@@ -103,6 +105,7 @@ impl<Tr: BatchTracer> MainBatchExecutorFactory<Tr> {
             observe_storage_metrics: false,
             skip_signature_verification: false,
             divergence_handler: None,
+            speculation: None,
             _tracer: PhantomData,
         }
     }
@@ -137,6 +140,21 @@ impl<Tr: BatchTracer> MainBatchExecutorFactory<Tr> {
     pub fn skip_signature_verification(&mut self) {
         self.skip_signature_verification = true;
     }
+
+    /// Enables speculative storage prefetching for upcoming transactions on `workers` threads. At most `lookahead`
+    /// transactions will be executed speculatively or queued for execution at any given time.
+    ///
+    /// Transactions are still executed one at a time by the main VM; speculative results are never reused. Speculation
+    /// only warms up the storage cache of the main VM (speculative results are compared with sequential ones
+    /// for diagnostics; the outcomes are reported as metrics).
+    pub fn enable_speculation(&mut self, workers: NonZeroUsize, lookahead: NonZeroUsize) {
+        tracing::info!(
+            workers = workers.get(),
+            lookahead = lookahead.get(),
+            "Enabled speculative storage prefetching"
+        );
+        self.speculation = Some(SpeculationOptions { workers, lookahead });
+    }
 }
 
 impl<S: ReadStorage + Send + 'static, Tr: BatchTracer> BatchExecutorFactory<S>
@@ -158,6 +176,7 @@ impl<S: ReadStorage + Send + 'static, Tr: BatchTracer> BatchExecutorFactory<S>
             observe_storage_metrics: self.observe_storage_metrics,
             skip_signature_verification: self.skip_signature_verification,
             divergence_handler: self.divergence_handler.clone(),
+            speculation: self.speculation,
             commands: commands_receiver,
             _storage: PhantomData,
             _tracer: PhantomData::<Tr>,
@@ -173,13 +192,20 @@ impl<S: ReadStorage + Send + 'static, Tr: BatchTracer> BatchExecutorFactory<S>
                 pubdata_params_to_builder(pubdata_params, system_env.version),
             )
         });
-        Box::new(MainBatchExecutor::new(handle, commands_sender))
+        let speculation_lookahead = self
+            .speculation
+            .map_or(0, |options| options.lookahead.get());
+        Box::new(MainBatchExecutor::new(
+            handle,
+            commands_sender,
+            speculation_lookahead,
+        ))
     }
 }
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug)]
-enum BatchVm<S: ReadStorage, Tr: BatchTracer> {
+pub(super) enum BatchVm<S: ReadStorage, Tr: BatchTracer> {
     Legacy(LegacyVmInstance<S, HistoryEnabled>),
     Fast(FastVmInstance<S, Tr::Fast>),
 }
@@ -194,7 +220,7 @@ macro_rules! dispatch_batch_vm {
 }
 
 impl<S: ReadStorage, Tr: BatchTracer> BatchVm<S, Tr> {
-    fn new(
+    pub(super) fn new(
         l1_batch_env: L1BatchEnv,
         system_env: SystemEnv,
         storage_ptr: StoragePtr<StorageView<S>>,
@@ -243,7 +269,7 @@ impl<S: ReadStorage, Tr: BatchTracer> BatchVm<S, Tr> {
         dispatch_batch_vm!(self.pop_front_snapshot_no_rollback());
     }
 
-    fn inspect_transaction(
+    pub(super) fn inspect_transaction(
         &mut self,
         tx: Transaction,
         with_compression: bool,
@@ -317,12 +343,13 @@ struct CommandReceiver<S, Tr> {
     observe_storage_metrics: bool,
     skip_signature_verification: bool,
     divergence_handler: Option<DivergenceHandler>,
+    speculation: Option<SpeculationOptions>,
     commands: mpsc::Receiver<Command>,
     _storage: PhantomData<S>,
     _tracer: PhantomData<Tr>,
 }
 
-impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
+impl<S: ReadStorage + Send + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
     pub(super) fn run(
         mut self,
         storage: S,
@@ -338,8 +365,26 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
             &l1_batch_params.number,
         );
 
+        // The storage is only shared (and thus guarded by a mutex) if speculation is enabled, so that the main VM
+        // doesn't pay for synchronization otherwise.
+        let (storage, mut speculator) = if let Some(options) = self.speculation {
+            let storage = SharedStorage::new(storage);
+            let speculator = Speculator::new(
+                options,
+                storage.clone(),
+                &l1_batch_params,
+                &system_env,
+                self.fast_vm_mode,
+                self.skip_signature_verification,
+                self.divergence_handler.clone(),
+            )?;
+            (MainVmStorage::Shared(storage), Some(speculator))
+        } else {
+            (MainVmStorage::Owned(storage), None)
+        };
+
         let storage_view = StorageView::new(storage).to_rc_ptr();
-        let mut vm = BatchVm::<S, Tr>::new(
+        let mut vm = BatchVm::<MainVmStorage<S>, Tr>::new(
             l1_batch_params,
             system_env,
             storage_view.clone(),
@@ -368,10 +413,16 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
                         vm.pop_snapshot_no_rollback();
                     }
                     let tx_hash = tx.hash();
+                    let speculative_output = speculator.as_mut().and_then(|speculator| {
+                        speculator.prefetch(tx_hash, &mut storage_view.borrow_mut())
+                    });
                     let (result, latency) = self.execute_tx(*tx, &mut vm).with_context(|| {
                         format!("fatal error executing transaction {tx_hash:?}")
                     })?;
                     has_snapshot_before_tx = true;
+                    if let Some(speculator) = &mut speculator {
+                        speculator.record_execution(tx_hash, &result, speculative_output);
+                    }
 
                     if self.observe_storage_metrics {
                         let storage_stats = storage_view.borrow().stats();
@@ -388,6 +439,45 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
                         break;
                     }
                 }
+                Command::ExecuteBundle(txs, resp) => {
+                    if has_snapshot_before_tx {
+                        vm.pop_snapshot_no_rollback();
+                    }
+                    let tx_hashes: Vec<_> = txs.iter().map(Transaction::hash).collect();
+                    let speculative_outputs: Vec<_> = tx_hashes
+                        .iter()
+                        .map(|&tx_hash| {
+                            let speculator = speculator.as_mut()?;
+                            speculator.prefetch(tx_hash, &mut storage_view.borrow_mut())
+                        })
+                        .collect();
+                    let (results, latency) =
+                        self.execute_bundle(txs, &mut vm).with_context(|| {
+                            format!("fatal error executing transaction bundle {tx_hashes:?}")
+                        })?;
+                    has_snapshot_before_tx = true;
+                    if let Some(speculator) = &mut speculator {
+                        let executed = tx_hashes.into_iter().zip(&results);
+                        for ((tx_hash, result), output) in executed.zip(speculative_outputs) {
+                            speculator.record_execution(tx_hash, result, output);
+                        }
+                    }
+
+                    if self.observe_storage_metrics {
+                        let storage_stats = storage_view.borrow().stats();
+                        let stats_diff = storage_stats.saturating_sub(&prev_storage_stats);
+                        RuntimeContextStorageMetrics::observe(
+                            &format!("Bundle of {} txs", results.len()),
+                            false,
+                            latency,
+                            &stats_diff,
+                        );
+                        prev_storage_stats = storage_stats;
+                    }
+                    if resp.send(results).is_err() {
+                        break;
+                    }
+                }
                 Command::RollbackLastTx(resp) => {
                     self.rollback_last_tx(&mut vm);
                     // Snapshot was popped.
@@ -437,6 +527,14 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
                         break;
                     }
                 }
+                Command::Speculate(txs, resp) => {
+                    if let Some(speculator) = &mut speculator {
+                        speculator.schedule(txs)?;
+                    }
+                    if resp.send(()).is_err() {
+                        break;
+                    }
+                }
                 Command::CommitL2Block(resp) => {
                     // Only `FastVmMode::Old` keeps snapshots for block rollback.
                     if self.fast_vm_mode == FastVmMode::Old {
@@ -450,9 +548,14 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
         }
 
         drop(vm);
+        if let Some(mut speculator) = speculator {
+            speculator.stop()?;
+        }
+        // All speculative workers are stopped at this point, so the storage is no longer shared.
         let storage_view = Rc::into_inner(storage_view)
             .context("storage view leaked")?
-            .into_inner();
+            .into_inner()
+            .map_storage(MainVmStorage::into_inner);
         if batch_finished {
             let stats = storage_view.stats();
             EXECUTOR_METRICS.batch_storage_interaction_duration[&InteractionType::GetValue]
@@ -470,7 +573,7 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
     fn execute_tx(
         &self,
         transaction: Transaction,
-        vm: &mut BatchVm<S, Tr>,
+        vm: &mut BatchVm<MainVmStorage<S>, Tr>,
     ) -> anyhow::Result<(BatchTransactionExecutionResult, Duration)> {
         let _guard = AllocationGuard::for_operation("batch_vm#execute_tx");
         // Save pre-execution VM snapshot.
@@ -494,8 +597,41 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
         Ok((result, latency))
     }
 
+    /// Executes a bundle of transactions with a single snapshot taken before the first transaction, so that
+    /// [`Self::rollback_last_tx()`] rolls back the entire bundle. Execution stops on the first failed transaction.
+    /// Bytecode compression is mandatory for bundled transactions since a transaction cannot be re-executed
+    /// without compression in the middle of a bundle.
+    #[tracing::instrument(level = "trace", skip_all, fields(txs.len = transactions.len()))]
+    fn execute_bundle(
+        &self,
+        transactions: Vec<Transaction>,
+        vm: &mut BatchVm<MainVmStorage<S>, Tr>,
+    ) -> anyhow::Result<(Vec<BatchTransactionExecutionResult>, Duration)> {
+        let _guard = AllocationGuard::for_operation("batch_vm#execute_bundle");
+        // Save pre-execution VM snapshot for the entire bundle.
+        vm.make_snapshot();
+
+        let latency = KEEPER_METRICS.tx_execution_time[&TxExecutionStage::Execution].start();
+        let mut results = Vec::with_capacity(transactions.len());
+        for transaction in &transactions {
+            let result = self.execute_tx_in_vm(transaction, vm)?;
+            let is_failed = result.tx_result.result.is_failed();
+            tracing::trace!(
+                tx.hash = ?transaction.hash(),
+                result.tx_result = ?result.tx_result.result,
+                "Executed bundled transaction"
+            );
+            results.push(result);
+            if is_failed {
+                break;
+            }
+        }
+        let latency = latency.observe();
+        Ok((results, latency))
+    }
+
     #[tracing::instrument(level = "trace", skip_all)]
-    fn rollback_last_tx(&self, vm: &mut BatchVm<S, Tr>) {
+    fn rollback_last_tx(&self, vm: &mut BatchVm<MainVmStorage<S>, Tr>) {
         let latency = KEEPER_METRICS.tx_execution_time[&TxExecutionStage::TxRollback].start();
         vm.rollback_to_the_latest_snapshot();
         let latency = latency.observe();
@@ -505,7 +641,7 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
     #[tracing::instrument(level = "trace", skip_all)]
     fn finish_batch(
         &self,
-        vm: &mut BatchVm<S, Tr>,
+        vm: &mut BatchVm<MainVmStorage<S>, Tr>,
         pubdata_builder: Rc<dyn PubdataBuilder>,
     ) -> anyhow::Result<FinishedL1Batch> {
         let guard = AllocationGuard::for_operation("batch_vm#finish_batch");
@@ -529,7 +665,7 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
     fn execute_tx_in_vm_with_optional_compression(
         &self,
         tx: &Transaction,
-        vm: &mut BatchVm<S, Tr>,
+        vm: &mut BatchVm<MainVmStorage<S>, Tr>,
     ) -> anyhow::Result<BatchTransactionExecutionResult> {
         // Note, that the space where we can put the calldata for compressing transactions
         // is limited and the transactions do not pay for taking it.
@@ -569,7 +705,7 @@ impl<S: ReadStorage + 'static, Tr: BatchTracer> CommandReceiver<S, Tr> {
     fn execute_tx_in_vm(
         &self,
         tx: &Transaction,
-        vm: &mut BatchVm<S, Tr>,
+        vm: &mut BatchVm<MainVmStorage<S>, Tr>,
     ) -> anyhow::Result<BatchTransactionExecutionResult> {
         let res = vm.inspect_transaction(tx.clone(), true);
         if res.compression_result.is_ok() {
@@ -2,7 +2,7 @@
 
 use std::time::Duration;
 
-use vise::{Buckets, EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics};
+use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics};
 use zksync_multivm::interface::VmExecutionResultAndLogs;
 
 use crate::shared::InteractionType;
@@ -17,6 +17,8 @@ pub(super) enum ExecutorCommand {
     FinishBatch,
     RollbackL2Block,
     CommitL2Block,
+    Speculate,
+    ExecuteBundle,
 }
 
 const GAS_PER_NANOSECOND_BUCKETS: Buckets = Buckets::values(&[
@@ -34,6 +36,22 @@ pub(super) enum TxExecutionStage {
     TxRollback,
 }
 
+/// Outcome of speculative execution for a transaction executed by the main VM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "outcome", rename_all = "snake_case")]
+pub(super) enum SpeculationOutcome {
+    /// Speculative result matches the sequential one.
+    Hit,
+    /// Speculative result differs from the sequential one although there were no conflicting reads.
+    Mismatch,
+    /// Transaction read storage slots written by the preceding transactions in the batch.
+    Conflict,
+    /// Transaction was scheduled for speculative execution, but it didn't complete in time.
+    Pending,
+    /// Transaction wasn't scheduled for speculative execution.
+    Missed,
+}
+
 /// Executor-related metrics.
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "state_keeper")]
@@ -55,6 +73,10 @@ pub(super) struct ExecutorMetrics {
     /// in the batch executor.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub batch_storage_interaction_duration: Family<InteractionType, Histogram<Duration>>,
+    /// Number of transactions executed by the main VM with speculative execution enabled, grouped by the speculation outcome.
+    pub speculative_txs: Family<SpeculationOutcome, Counter>,
+    /// Number of storage slots prefetched into the main VM storage cache based on speculative execution.
+    pub speculation_prefetched_keys: Counter,
 }
 
 #[vise::register]
@@ -10,3 +10,4 @@ pub use self::{
 mod executor;
 mod factory;
 mod metrics;
+mod speculative;
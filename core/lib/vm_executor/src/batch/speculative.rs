@@ -0,0 +1,421 @@
+//! Speculative storage prefetching in the batch executor.
+//!
+//! Upcoming transactions (as hinted by the executor client) are executed by a pool of worker threads, each transaction
+//! in a fresh VM on top of the state at the start of the L1 batch. Worker VMs share the underlying storage with the main VM,
+//! and the storage slots read by a worker are used to warm up the storage cache of the main VM before the transaction
+//! is executed for real. This is the only way speculation is used; it is **not** parallel transaction execution.
+//!
+//! Transactions are *always* executed sequentially by the main VM, so speculation doesn't influence produced blocks.
+//! Speculative results are never committed: the VM interface has no way to apply a transaction without executing it,
+//! and VM execution depends on the transaction position in the batch (e.g., via the bootloader memory, refunds
+//! and the pubdata accounting), which a worker VM doesn't reproduce. Speculative results are only compared with
+//! the sequential ones as a diagnostic: if the transaction didn't read any slots written earlier in the batch,
+//! the results are expected to match. Comparison outcomes are reported as metrics.
+//!
+//! The storage is only shared with workers (and guarded by a mutex) if speculation is enabled; otherwise, the main VM
+//! owns the storage exclusively. Even with speculation enabled, the main VM only locks the storage on cache misses
+//! of its storage view, which are rare for transactions prefetched by workers.
+
+use std::{
+    collections::{HashMap, HashSet},
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    thread,
+};
+
+use anyhow::Context as _;
+use zksync_multivm::{
+    interface::{
+        storage::{ReadStorage, StorageView, StorageViewCache},
+        utils::DivergenceHandler,
+        BatchTransactionExecutionResult, ExecutionResult, L1BatchEnv, SystemEnv,
+        VmExecutionResultAndLogs,
+    },
+    FastVmInstance,
+};
+use zksync_types::{vm::FastVmMode, StorageKey, StorageValue, Transaction, H256};
+
+use super::{
+    factory::BatchVm,
+    metrics::{SpeculationOutcome, EXECUTOR_METRICS},
+};
+
+/// Options for speculative transaction execution.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct SpeculationOptions {
+    /// Number of worker threads.
+    pub workers: NonZeroUsize,
+    /// Maximum number of transactions executed speculatively or queued for execution at any given time.
+    pub lookahead: NonZeroUsize,
+}
+
+/// Storage shared among the main VM and speculative workers.
+#[derive(Debug)]
+pub(super) struct SharedStorage<S>(Arc<Mutex<S>>);
+
+impl<S> Clone for SharedStorage<S> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<S> SharedStorage<S> {
+    pub fn new(storage: S) -> Self {
+        Self(Arc::new(Mutex::new(storage)))
+    }
+
+    /// Unwraps the storage.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the storage is still shared, i.e., if speculative workers weren't stopped.
+    pub fn into_inner(self) -> S {
+        let mutex = Arc::into_inner(self.0).expect("storage is still shared");
+        mutex.into_inner().expect("storage mutex is poisoned")
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, S> {
+        self.0.lock().expect("storage mutex is poisoned")
+    }
+}
+
+impl<S: ReadStorage> ReadStorage for SharedStorage<S> {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        self.lock().read_value(key)
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        self.lock().is_write_initial(key)
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        self.lock().load_factory_dep(hash)
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        self.lock().get_enumeration_index(key)
+    }
+}
+
+/// Storage of the main VM. The storage is only wrapped in [`SharedStorage`] if speculative execution is enabled;
+/// otherwise, the main VM accesses it directly without any synchronization.
+#[derive(Debug)]
+pub(super) enum MainVmStorage<S> {
+    Owned(S),
+    Shared(SharedStorage<S>),
+}
+
+impl<S> MainVmStorage<S> {
+    /// Unwraps the storage.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the storage is still shared, i.e., if speculative workers weren't stopped.
+    pub fn into_inner(self) -> S {
+        match self {
+            Self::Owned(storage) => storage,
+            Self::Shared(storage) => storage.into_inner(),
+        }
+    }
+}
+
+impl<S: ReadStorage> ReadStorage for MainVmStorage<S> {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        match self {
+            Self::Owned(storage) => storage.read_value(key),
+            Self::Shared(storage) => storage.read_value(key),
+        }
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        match self {
+            Self::Owned(storage) => storage.is_write_initial(key),
+            Self::Shared(storage) => storage.is_write_initial(key),
+        }
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        match self {
+            Self::Owned(storage) => storage.load_factory_dep(hash),
+            Self::Shared(storage) => storage.load_factory_dep(hash),
+        }
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        match self {
+            Self::Owned(storage) => storage.get_enumeration_index(key),
+            Self::Shared(storage) => storage.get_enumeration_index(key),
+        }
+    }
+}
+
+/// Output of a speculatively executed transaction.
+#[derive(Debug)]
+pub(super) struct SpeculativeOutput {
+    tx_hash: H256,
+    /// Storage cache of the worker VM. Since the worker VM is fresh, it contains all slots read by the transaction.
+    cache: StorageViewCache,
+    result: ExecutionResult,
+    writes: HashMap<StorageKey, StorageValue>,
+}
+
+fn storage_writes(result: &VmExecutionResultAndLogs) -> HashMap<StorageKey, StorageValue> {
+    result
+        .logs
+        .storage_logs
+        .iter()
+        .filter(|log| log.log.is_write())
+        .map(|log| (log.log.key, log.log.value))
+        .collect()
+}
+
+/// Worker executing transactions speculatively.
+#[derive(Debug)]
+struct Worker<S> {
+    storage: SharedStorage<S>,
+    l1_batch_env: L1BatchEnv,
+    system_env: SystemEnv,
+    fast_vm_mode: FastVmMode,
+    skip_signature_verification: bool,
+    divergence_handler: Option<DivergenceHandler>,
+}
+
+impl<S: ReadStorage> Worker<S> {
+    fn run(
+        self,
+        jobs: &Mutex<mpsc::Receiver<Transaction>>,
+        outputs: &mpsc::Sender<SpeculativeOutput>,
+        stop_flag: &AtomicBool,
+    ) {
+        loop {
+            // The lock is released before executing the received transaction.
+            let job = jobs.lock().expect("jobs mutex is poisoned").recv();
+            let Ok(tx) = job else {
+                break; // The main VM has finished
+            };
+            if stop_flag.load(Ordering::Relaxed) {
+                break;
+            }
+            if outputs.send(self.execute(tx)).is_err() {
+                break;
+            }
+        }
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, fields(tx.hash = ?tx.hash()))]
+    fn execute(&self, tx: Transaction) -> SpeculativeOutput {
+        let tx_hash = tx.hash();
+        let storage_view = StorageView::new(self.storage.clone()).to_rc_ptr();
+        let mut vm = BatchVm::<_, ()>::new(
+            self.l1_batch_env.clone(),
+            self.system_env.clone(),
+            storage_view.clone(),
+            self.fast_vm_mode,
+        );
+        if self.skip_signature_verification {
+            if let BatchVm::Fast(vm) = &mut vm {
+                vm.skip_signature_verification();
+            }
+        }
+        if let BatchVm::Fast(FastVmInstance::Shadowed(shadowed)) = &mut vm {
+            if let Some(handler) = &self.divergence_handler {
+                shadowed.set_divergence_handler(handler.clone());
+            }
+        }
+
+        let res = vm.inspect_transaction(tx, true);
+        drop(vm);
+        let cache = storage_view.borrow().cache();
+        tracing::trace!(result = ?res.tx_result.result, "Executed transaction speculatively");
+        SpeculativeOutput {
+            tx_hash,
+            cache,
+            writes: storage_writes(&res.tx_result),
+            result: res.tx_result.result,
+        }
+    }
+}
+
+/// Manages speculative workers for a single L1 batch.
+#[derive(Debug)]
+pub(super) struct Speculator {
+    lookahead: usize,
+    /// Sender of transactions to execute. Dropped when the speculator is stopped.
+    jobs_sender: Option<mpsc::Sender<Transaction>>,
+    /// Signals workers to skip the remaining queued transactions.
+    stop_flag: Arc<AtomicBool>,
+    outputs_receiver: mpsc::Receiver<SpeculativeOutput>,
+    workers: Vec<thread::JoinHandle<()>>,
+    /// Hashes of all transactions scheduled for speculative execution in this batch.
+    scheduled: HashSet<H256>,
+    /// Number of scheduled transactions for which outputs weren't received yet.
+    in_flight: usize,
+    ready: HashMap<H256, SpeculativeOutput>,
+    /// Storage slots written by the main VM in this batch.
+    written_keys: HashSet<StorageKey>,
+}
+
+impl Speculator {
+    pub fn new<S: ReadStorage + Send + 'static>(
+        options: SpeculationOptions,
+        storage: SharedStorage<S>,
+        l1_batch_env: &L1BatchEnv,
+        system_env: &SystemEnv,
+        fast_vm_mode: FastVmMode,
+        skip_signature_verification: bool,
+        divergence_handler: Option<DivergenceHandler>,
+    ) -> anyhow::Result<Self> {
+        let (jobs_sender, jobs_receiver) = mpsc::channel();
+        let jobs_receiver = Arc::new(Mutex::new(jobs_receiver));
+        let (outputs_sender, outputs_receiver) = mpsc::channel();
+        let stop_flag = Arc::new(AtomicBool::new(false));
+
+        let mut workers = Vec::with_capacity(options.workers.get());
+        for i in 0..options.workers.get() {
+            let worker = Worker {
+                storage: storage.clone(),
+                l1_batch_env: l1_batch_env.clone(),
+                system_env: system_env.clone(),
+                fast_vm_mode,
+                skip_signature_verification,
+                divergence_handler: divergence_handler.clone(),
+            };
+            let jobs_receiver = jobs_receiver.clone();
+            let outputs_sender = outputs_sender.clone();
+            let stop_flag = stop_flag.clone();
+            let span = tracing::Span::current();
+            let handle = thread::Builder::new()
+                .name(format!("vm-speculation-{i}"))
+                .spawn(move || {
+                    let _span_guard = span.entered();
+                    worker.run(&jobs_receiver, &outputs_sender, &stop_flag);
+                })
+                .context("failed spawning speculative worker thread")?;
+            workers.push(handle);
+        }
+
+        tracing::info!(
+            workers = options.workers.get(),
+            lookahead = options.lookahead.get(),
+            "Started speculative execution workers"
+        );
+        Ok(Self {
+            lookahead: options.lookahead.get(),
+            jobs_sender: Some(jobs_sender),
+            stop_flag,
+            outputs_receiver,
+            workers,
+            scheduled: HashSet::new(),
+            in_flight: 0,
+            ready: HashMap::new(),
+            written_keys: HashSet::new(),
+        })
+    }
+
+    fn drain_outputs(&mut self) {
+        while let Ok(output) = self.outputs_receiver.try_recv() {
+            self.in_flight -= 1;
+            self.ready.insert(output.tx_hash, output);
+        }
+    }
+
+    /// Schedules speculative execution for the provided upcoming transactions.
+    pub fn schedule(&mut self, txs: Vec<Transaction>) -> anyhow::Result<()> {
+        self.drain_outputs();
+        let upcoming: HashSet<_> = txs.iter().map(Transaction::hash).collect();
+        // Outputs for transactions that are no longer upcoming won't be used.
+        self.ready.retain(|hash, _| upcoming.contains(hash));
+
+        let jobs_sender = self.jobs_sender.as_ref().context("speculator is stopped")?;
+        for tx in txs {
+            if self.in_flight >= self.lookahead {
+                break;
+            }
+            if !self.scheduled.insert(tx.hash()) {
+                continue;
+            }
+            if jobs_sender.send(tx).is_err() {
+                self.stop()?;
+                anyhow::bail!("speculative execution workers have unexpectedly terminated");
+            }
+            self.in_flight += 1;
+        }
+        Ok(())
+    }
+
+    /// Takes the speculative output for the transaction (if any) and uses it to warm up the main VM storage.
+    /// The speculative execution result itself is only used for diagnostics in [`Self::record_execution()`].
+    pub fn prefetch<S>(
+        &mut self,
+        tx_hash: H256,
+        storage_view: &mut StorageView<S>,
+    ) -> Option<SpeculativeOutput> {
+        self.drain_outputs();
+        let output = self.ready.remove(&tx_hash)?;
+        EXECUTOR_METRICS
+            .speculation_prefetched_keys
+            .inc_by(output.cache.read_storage_keys().len() as u64);
+        // The worker VM cache only contains values from the underlying storage, so it's safe to reuse.
+        storage_view.extend_cache(output.cache.clone());
+        Some(output)
+    }
+
+    /// Records a transaction executed by the main VM, comparing its speculative output (if any)
+    /// with the sequential result.
+    pub fn record_execution(
+        &mut self,
+        tx_hash: H256,
+        result: &BatchTransactionExecutionResult,
+        output: Option<SpeculativeOutput>,
+    ) {
+        let writes = storage_writes(&result.tx_result);
+        let outcome = if let Some(output) = output {
+            let has_conflicts = output
+                .cache
+                .read_storage_keys()
+                .keys()
+                .any(|key| self.written_keys.contains(key));
+            if has_conflicts {
+                SpeculationOutcome::Conflict
+            } else if output.result == result.tx_result.result && output.writes == writes {
+                SpeculationOutcome::Hit
+            } else {
+                tracing::debug!(
+                    ?tx_hash,
+                    speculative_result = ?output.result,
+                    result = ?result.tx_result.result,
+                    "Speculative execution result differs from the sequential one"
+                );
+                SpeculationOutcome::Mismatch
+            }
+        } else if self.scheduled.contains(&tx_hash) {
+            SpeculationOutcome::Pending
+        } else {
+            SpeculationOutcome::Missed
+        };
+        EXECUTOR_METRICS.speculative_txs[&outcome].inc();
+        self.written_keys.extend(writes.into_keys());
+    }
+
+    /// Stops all workers, waiting for them to finish.
+    pub fn stop(&mut self) -> anyhow::Result<()> {
+        self.stop_flag.store(true, Ordering::Relaxed);
+        self.jobs_sender = None;
+        let mut panicked = false;
+        for handle in self.workers.drain(..) {
+            panicked |= handle.join().is_err();
+        }
+        anyhow::ensure!(!panicked, "speculative execution worker panicked");
+        Ok(())
+    }
+}
+
+impl Drop for Speculator {
+    fn drop(&mut self) {
+        // Makes workers exit as soon as possible if the speculator wasn't stopped explicitly (e.g., on a fatal error).
+        self.stop_flag.store(true, Ordering::Relaxed);
+    }
+}
@@ -13,4 +13,5 @@ mod shared;
 pub mod storage;
 #[cfg(test)]
 mod testonly;
+pub mod tx_policy;
 pub mod whitelist;
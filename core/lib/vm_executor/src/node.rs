@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use zksync_node_framework::Resource;
 
-use crate::{interface::TransactionFilter, whitelist::SharedAllowList};
+use crate::{interface::TransactionFilter, tx_policy::TxPolicyEngine, whitelist::SharedAllowList};
 
 impl Resource for SharedAllowList {
     fn name() -> String {
@@ -19,3 +19,9 @@ impl Resource for ApiTransactionFilter {
         "api/transaction_filter".into()
     }
 }
+
+impl Resource for TxPolicyEngine {
+    fn name() -> String {
+        "tx_policy_engine".into()
+    }
+}
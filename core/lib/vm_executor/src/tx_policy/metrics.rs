@@ -0,0 +1,21 @@
+//! Transaction policy metrics.
+
+use vise::{Counter, EncodeLabelSet, Family, Metrics};
+
+use super::TxPolicyStage;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet)]
+pub(super) struct PolicyLabels {
+    pub policy: &'static str,
+    pub stage: TxPolicyStage,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "tx_policy")]
+pub(super) struct TxPolicyMetrics {
+    /// Number of transactions rejected by a policy, grouped by the policy name and the stage.
+    pub rejected_transactions: Family<PolicyLabels, Counter>,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<TxPolicyMetrics> = vise::Global::new();
@@ -0,0 +1,443 @@
+//! Pluggable policies applied by the sequencer to L2 transactions.
+//!
+//! Policies are checked twice: when a transaction is submitted via the API ([`TxPolicyStage::Submission`]),
+//! and when the state keeper is about to include it into a block ([`TxPolicyStage::Inclusion`]). The second check
+//! ensures that transactions already in the mempool are not included after the policy has been tightened
+//! (e.g., after a screening list update).
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    num::NonZeroU32,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+use vise::EncodeLabelValue;
+use zksync_types::{Address, ExecuteTransactionCommon, Transaction, H256};
+
+use self::metrics::{PolicyLabels, METRICS};
+
+mod metrics;
+#[cfg(test)]
+mod tests;
+
+/// Stage of the transaction lifecycle at which policies are checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub enum TxPolicyStage {
+    /// Transaction is submitted via the API server.
+    Submission,
+    /// Transaction is about to be included into an L2 block by the state keeper.
+    Inclusion,
+}
+
+/// Violation of a [`TxPolicy`] by a transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TxPolicyViolation {
+    /// Name of the violated policy.
+    pub policy: &'static str,
+    /// Human-readable reason for the violation.
+    pub reason: String,
+}
+
+impl fmt::Display for TxPolicyViolation {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{} (policy: {})", self.reason, self.policy)
+    }
+}
+
+/// Policy for L2 transactions enforced by the sequencer.
+#[async_trait]
+pub trait TxPolicy: fmt::Debug + Send + Sync + 'static {
+    /// Returns the name of this policy used in logs and metrics.
+    fn name(&self) -> &'static str;
+
+    /// Checks a transaction at the specified stage. Returns a human-readable reason if the transaction violates the policy.
+    async fn check(&self, tx: &Transaction, stage: TxPolicyStage) -> Result<(), String>;
+
+    /// Records transactions that have passed all checks at the [`TxPolicyStage::Submission`] stage, including
+    /// their execution in the sandbox. Stateful policies should account transactions here rather than in [`Self::check()`],
+    /// so that transactions rejected by later validations don't affect the policy state. Since transactions
+    /// may be submitted concurrently, the policy may still reject them.
+    ///
+    /// Transactions are recorded atomically (e.g., if they form a bundle): if the policy rejects any of them,
+    /// none must be recorded.
+    async fn record_submission(&self, _txs: &[Transaction]) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Reverts a successful [`Self::record_submission()`] call for transactions that were not accepted
+    /// into the mempool after all (e.g., because they turned out to be duplicates).
+    async fn revert_submission(&self, _txs: &[Transaction]) {}
+}
+
+/// Ordered set of [`TxPolicy`]s applied to L2 transactions. L1 and protocol upgrade transactions are exempt
+/// since the sequencer cannot reject them.
+#[derive(Debug, Clone, Default)]
+pub struct TxPolicyEngine {
+    policies: Vec<Arc<dyn TxPolicy>>,
+}
+
+impl TxPolicyEngine {
+    /// Adds a policy to the engine. Policies are checked in the order they were added.
+    pub fn with_policy(mut self, policy: impl TxPolicy) -> Self {
+        self.policies.push(Arc::new(policy));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.policies.is_empty()
+    }
+
+    /// Checks the transaction against all policies, returning the first encountered violation.
+    pub async fn check_transaction(
+        &self,
+        tx: &Transaction,
+        stage: TxPolicyStage,
+    ) -> Result<(), TxPolicyViolation> {
+        if !matches!(tx.common_data, ExecuteTransactionCommon::L2(_)) {
+            return Ok(());
+        }
+
+        for policy in &self.policies {
+            if let Err(reason) = policy.check(tx, stage).await {
+                return Err(Self::report_violation(
+                    &[tx.hash()],
+                    policy.name(),
+                    stage,
+                    reason,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Records transactions that have passed [submission checks](Self::check_transaction()) and all other validations
+    /// performed by the API server. Transactions are recorded atomically: if any stateful policy rejects any of them
+    /// (e.g., because a rate limit was exhausted by concurrently submitted transactions), nothing is recorded,
+    /// and the first encountered violation is returned.
+    ///
+    /// Transactions that are not accepted into the mempool after recording must be passed to
+    /// [`Self::revert_submission()`].
+    pub async fn record_submission(&self, txs: &[Transaction]) -> Result<(), TxPolicyViolation> {
+        let txs = Self::l2_transactions(txs);
+        if txs.is_empty() {
+            return Ok(());
+        }
+
+        for (i, policy) in self.policies.iter().enumerate() {
+            if let Err(reason) = policy.record_submission(&txs).await {
+                for recorded_policy in &self.policies[..i] {
+                    recorded_policy.revert_submission(&txs).await;
+                }
+                let tx_hashes: Vec<_> = txs.iter().map(Transaction::hash).collect();
+                let stage = TxPolicyStage::Submission;
+                return Err(Self::report_violation(
+                    &tx_hashes,
+                    policy.name(),
+                    stage,
+                    reason,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reverts a successful [`Self::record_submission()`] call.
+    pub async fn revert_submission(&self, txs: &[Transaction]) {
+        let txs = Self::l2_transactions(txs);
+        if txs.is_empty() {
+            return;
+        }
+        for policy in &self.policies {
+            policy.revert_submission(&txs).await;
+        }
+    }
+
+    fn l2_transactions(txs: &[Transaction]) -> Vec<Transaction> {
+        let txs = txs
+            .iter()
+            .filter(|tx| matches!(tx.common_data, ExecuteTransactionCommon::L2(_)));
+        txs.cloned().collect()
+    }
+
+    fn report_violation(
+        tx_hashes: &[H256],
+        policy: &'static str,
+        stage: TxPolicyStage,
+        reason: String,
+    ) -> TxPolicyViolation {
+        tracing::info!(
+            "Transaction(s) {tx_hashes:?} violate policy `{policy}` at {stage:?} stage: {reason}"
+        );
+        METRICS.rejected_transactions[&PolicyLabels { policy, stage }].inc();
+        TxPolicyViolation { policy, reason }
+    }
+}
+
+/// Externally maintained list of screened addresses that can be updated at runtime.
+#[derive(Debug, Clone, Default)]
+pub struct ScreeningList {
+    inner: Arc<RwLock<HashSet<Address>>>,
+}
+
+impl ScreeningList {
+    /// Replaces the list contents.
+    pub async fn replace(&self, addresses: HashSet<Address>) {
+        *self.inner.write().await = addresses;
+    }
+
+    pub async fn contains(&self, address: &Address) -> bool {
+        self.inner.read().await.contains(address)
+    }
+
+    pub async fn len(&self) -> usize {
+        self.inner.read().await.len()
+    }
+}
+
+/// Denies transactions sent from or to the specified addresses. The addresses are taken from a static set
+/// and, optionally, from a [`ScreeningList`] updated at runtime.
+#[derive(Debug, Default)]
+pub struct AddressDenyListPolicy {
+    denied_addresses: HashSet<Address>,
+    screening_list: Option<ScreeningList>,
+}
+
+impl AddressDenyListPolicy {
+    pub fn new(denied_addresses: HashSet<Address>) -> Self {
+        Self {
+            denied_addresses,
+            screening_list: None,
+        }
+    }
+
+    pub fn with_screening_list(mut self, screening_list: ScreeningList) -> Self {
+        self.screening_list = Some(screening_list);
+        self
+    }
+
+    async fn is_denied(&self, address: &Address) -> bool {
+        if self.denied_addresses.contains(address) {
+            return true;
+        }
+        match &self.screening_list {
+            Some(list) => list.contains(address).await,
+            None => false,
+        }
+    }
+}
+
+#[async_trait]
+impl TxPolicy for AddressDenyListPolicy {
+    fn name(&self) -> &'static str {
+        "address_deny_list"
+    }
+
+    async fn check(&self, tx: &Transaction, _stage: TxPolicyStage) -> Result<(), String> {
+        let sender = tx.initiator_account();
+        if self.is_denied(&sender).await {
+            return Err(format!("sender {sender:?} is denied"));
+        }
+        if let Some(recipient) = tx.recipient_account() {
+            if self.is_denied(&recipient).await {
+                return Err(format!("recipient {recipient:?} is denied"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Function selector denied by [`SelectorDenyListPolicy`], optionally scoped to a single contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DeniedSelector {
+    pub contract: Option<Address>,
+    pub selector: [u8; 4],
+}
+
+impl FromStr for DeniedSelector {
+    type Err = anyhow::Error;
+
+    /// Parses a selector in the `0xaabbccdd` or `<contract address>:0xaabbccdd` format.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (contract, selector) = match s.split_once(':') {
+            Some((contract, selector)) => {
+                let contract = contract.strip_prefix("0x").unwrap_or(contract);
+                let contract = Address::from_str(contract)
+                    .with_context(|| format!("invalid contract address in `{s}`"))?;
+                (Some(contract), selector)
+            }
+            None => (None, s),
+        };
+
+        let selector = selector.strip_prefix("0x").unwrap_or(selector);
+        anyhow::ensure!(
+            selector.len() == 8,
+            "selector in `{s}` must consist of 4 hex-encoded bytes"
+        );
+        let selector = u32::from_str_radix(selector, 16)
+            .with_context(|| format!("invalid selector in `{s}`"))?;
+        Ok(Self {
+            contract,
+            selector: selector.to_be_bytes(),
+        })
+    }
+}
+
+/// Denies calls to the specified function selectors, either on any contract or on a specific one.
+#[derive(Debug, Default)]
+pub struct SelectorDenyListPolicy {
+    denied_selectors: HashSet<DeniedSelector>,
+}
+
+impl SelectorDenyListPolicy {
+    pub fn new(denied_selectors: impl IntoIterator<Item = DeniedSelector>) -> Self {
+        Self {
+            denied_selectors: denied_selectors.into_iter().collect(),
+        }
+    }
+}
+
+#[async_trait]
+impl TxPolicy for SelectorDenyListPolicy {
+    fn name(&self) -> &'static str {
+        "selector_deny_list"
+    }
+
+    async fn check(&self, tx: &Transaction, _stage: TxPolicyStage) -> Result<(), String> {
+        let Some(selector) = tx.execute.calldata.get(..4) else {
+            return Ok(());
+        };
+        let selector: [u8; 4] = selector.try_into().unwrap();
+        let contract = tx.recipient_account();
+
+        let is_denied = self.denied_selectors.contains(&DeniedSelector {
+            contract: None,
+            selector,
+        }) || contract.is_some_and(|contract| {
+            self.denied_selectors.contains(&DeniedSelector {
+                contract: Some(contract),
+                selector,
+            })
+        });
+        if is_denied {
+            let selector = u32::from_be_bytes(selector);
+            return Err(format!(
+                "calling selector 0x{selector:08x} on {contract:?} is denied"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Limits the number of transactions calling each of the specified contracts within a sliding time window.
+///
+/// The limit is only enforced at the [`TxPolicyStage::Submission`] stage, so that transactions already accepted
+/// into the mempool are not rejected during inclusion. Transactions are only counted once they are
+/// [recorded](TxPolicy::record_submission()), i.e. after passing all submission checks including execution
+/// in the sandbox. Records for transactions not accepted into the mempool are [reverted](TxPolicy::revert_submission()).
+#[derive(Debug)]
+pub struct ContractRateLimitPolicy {
+    limits: HashMap<Address, NonZeroU32>,
+    window: Duration,
+    /// Timestamps and hashes of recorded transactions for each rate-limited contract.
+    recent_txs: Mutex<HashMap<Address, VecDeque<(Instant, H256)>>>,
+}
+
+impl ContractRateLimitPolicy {
+    pub fn new(limits: HashMap<Address, NonZeroU32>, window: Duration) -> Self {
+        Self {
+            limits,
+            window,
+            recent_txs: Mutex::default(),
+        }
+    }
+
+    /// Checks whether transactions (represented by called contracts and tx hashes) fit into the limits at `now`.
+    /// If `record` is set and *all* transactions fit, they are counted towards the limits; otherwise, nothing is recorded.
+    fn check_at(&self, txs: &[(Address, H256)], now: Instant, record: bool) -> Result<(), String> {
+        let mut recent_txs = self.recent_txs.lock().unwrap();
+        let mut new_tx_counts = HashMap::<Address, usize>::new();
+        for &(contract, _) in txs {
+            let Some(&limit) = self.limits.get(&contract) else {
+                continue;
+            };
+            let recorded = recent_txs.entry(contract).or_default();
+            while recorded
+                .front()
+                .is_some_and(|&(timestamp, _)| now.duration_since(timestamp) >= self.window)
+            {
+                recorded.pop_front();
+            }
+
+            let new_tx_count = new_tx_counts.entry(contract).or_default();
+            *new_tx_count += 1;
+            if recorded.len() + *new_tx_count > limit.get() as usize {
+                return Err(format!(
+                    "rate limit of {limit} transactions per {:?} for contract {contract:?} is exceeded",
+                    self.window
+                ));
+            }
+        }
+
+        if record {
+            for &(contract, tx_hash) in txs {
+                if self.limits.contains_key(&contract) {
+                    recent_txs
+                        .entry(contract)
+                        .or_default()
+                        .push_back((now, tx_hash));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes records for the specified transactions.
+    fn revert(&self, txs: &[(Address, H256)]) {
+        let mut recent_txs = self.recent_txs.lock().unwrap();
+        for (contract, tx_hash) in txs {
+            if let Some(recorded) = recent_txs.get_mut(contract) {
+                if let Some(pos) = recorded.iter().position(|(_, hash)| hash == tx_hash) {
+                    recorded.remove(pos);
+                }
+            }
+        }
+    }
+
+    fn rate_limited_calls(txs: &[Transaction]) -> Vec<(Address, H256)> {
+        let calls = txs
+            .iter()
+            .filter_map(|tx| Some((tx.recipient_account()?, tx.hash())));
+        calls.collect()
+    }
+}
+
+#[async_trait]
+impl TxPolicy for ContractRateLimitPolicy {
+    fn name(&self) -> &'static str {
+        "contract_rate_limit"
+    }
+
+    async fn check(&self, tx: &Transaction, stage: TxPolicyStage) -> Result<(), String> {
+        match (stage, tx.recipient_account()) {
+            (TxPolicyStage::Submission, Some(contract)) => {
+                self.check_at(&[(contract, tx.hash())], Instant::now(), false)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    async fn record_submission(&self, txs: &[Transaction]) -> Result<(), String> {
+        self.check_at(&Self::rate_limited_calls(txs), Instant::now(), true)
+    }
+
+    async fn revert_submission(&self, txs: &[Transaction]) {
+        self.revert(&Self::rate_limited_calls(txs));
+    }
+}
@@ -0,0 +1,198 @@
+//! Tests for transaction policies.
+
+use zksync_types::{Nonce, U256};
+
+use super::*;
+use crate::testonly::create_l2_transaction;
+
+fn create_tx(sender: Address, contract: Address, calldata: Vec<u8>) -> Transaction {
+    let mut tx = create_l2_transaction(U256::zero(), Nonce(0));
+    tx.common_data.initiator_address = sender;
+    tx.execute.contract_address = Some(contract);
+    tx.execute.calldata = calldata;
+    tx.into()
+}
+
+#[test]
+fn parsing_denied_selectors() {
+    let selector: DeniedSelector = "0xa9059cbb".parse().unwrap();
+    assert_eq!(
+        selector,
+        DeniedSelector {
+            contract: None,
+            selector: [0xa9, 0x05, 0x9c, 0xbb],
+        }
+    );
+
+    let contract = Address::repeat_byte(0x11);
+    let selector: DeniedSelector = format!("{contract:?}:a9059cbb").parse().unwrap();
+    assert_eq!(
+        selector,
+        DeniedSelector {
+            contract: Some(contract),
+            selector: [0xa9, 0x05, 0x9c, 0xbb],
+        }
+    );
+
+    for invalid in ["0xa9059c", "0xa9059cbbcc", "0xzz059cbb", "0x11:0xa9059cbb"] {
+        invalid.parse::<DeniedSelector>().unwrap_err();
+    }
+}
+
+#[tokio::test]
+async fn address_deny_list_policy() {
+    let denied = Address::repeat_byte(0xde);
+    let screened = Address::repeat_byte(0x5c);
+    let allowed = Address::repeat_byte(0x01);
+    let screening_list = ScreeningList::default();
+    let engine = TxPolicyEngine::default().with_policy(
+        AddressDenyListPolicy::new(HashSet::from([denied]))
+            .with_screening_list(screening_list.clone()),
+    );
+
+    let stage = TxPolicyStage::Submission;
+    let tx = create_tx(allowed, allowed, vec![]);
+    engine.check_transaction(&tx, stage).await.unwrap();
+    let tx = create_tx(denied, allowed, vec![]);
+    let err = engine.check_transaction(&tx, stage).await.unwrap_err();
+    assert_eq!(err.policy, "address_deny_list");
+    assert!(err.reason.contains("sender"), "{err}");
+    let tx = create_tx(allowed, denied, vec![]);
+    let err = engine.check_transaction(&tx, stage).await.unwrap_err();
+    assert!(err.reason.contains("recipient"), "{err}");
+
+    let tx = create_tx(screened, allowed, vec![]);
+    engine.check_transaction(&tx, stage).await.unwrap();
+    screening_list.replace(HashSet::from([screened])).await;
+    engine
+        .check_transaction(&tx, TxPolicyStage::Inclusion)
+        .await
+        .unwrap_err();
+}
+
+#[tokio::test]
+async fn selector_deny_list_policy() {
+    let contract = Address::repeat_byte(0x11);
+    let other_contract = Address::repeat_byte(0x22);
+    let policy = SelectorDenyListPolicy::new([
+        "0xa9059cbb".parse().unwrap(),
+        format!("{contract:?}:0x095ea7b3").parse().unwrap(),
+    ]);
+    let stage = TxPolicyStage::Submission;
+    let sender = Address::repeat_byte(0x01);
+
+    let tx = create_tx(sender, other_contract, vec![0xa9, 0x05, 0x9c, 0xbb, 0]);
+    policy.check(&tx, stage).await.unwrap_err();
+    let tx = create_tx(sender, contract, vec![0x09, 0x5e, 0xa7, 0xb3]);
+    policy.check(&tx, stage).await.unwrap_err();
+    let tx = create_tx(sender, other_contract, vec![0x09, 0x5e, 0xa7, 0xb3]);
+    policy.check(&tx, stage).await.unwrap();
+    let tx = create_tx(sender, contract, vec![0xa9, 0x05]);
+    policy.check(&tx, stage).await.unwrap();
+}
+
+#[test]
+fn contract_rate_limit_policy() {
+    let contract = Address::repeat_byte(0x11);
+    let window = Duration::from_secs(10);
+    let policy = ContractRateLimitPolicy::new(
+        HashMap::from([(contract, NonZeroU32::new(2).unwrap())]),
+        window,
+    );
+    let call = [(contract, H256::zero())];
+
+    let start = Instant::now();
+    // Checks without recording don't consume the limit.
+    for _ in 0..10 {
+        policy.check_at(&call, start, false).unwrap();
+    }
+    policy.check_at(&call, start, true).unwrap();
+    policy
+        .check_at(&call, start + Duration::from_secs(1), true)
+        .unwrap();
+    policy
+        .check_at(&call, start + Duration::from_secs(2), false)
+        .unwrap_err();
+    policy
+        .check_at(&call, start + Duration::from_secs(2), true)
+        .unwrap_err();
+    // Other contracts are not limited.
+    for _ in 0..10 {
+        policy
+            .check_at(&[(Address::repeat_byte(0x22), H256::zero())], start, true)
+            .unwrap();
+    }
+
+    // The first transaction leaves the window.
+    policy.check_at(&call, start + window, true).unwrap();
+    policy
+        .check_at(&call, start + window + Duration::from_millis(500), true)
+        .unwrap_err();
+    policy
+        .check_at(&call, start + window + Duration::from_secs(1), true)
+        .unwrap();
+}
+
+#[test]
+fn contract_rate_limit_policy_records_transactions_atomically() {
+    let contract = Address::repeat_byte(0x11);
+    let policy = ContractRateLimitPolicy::new(
+        HashMap::from([(contract, NonZeroU32::new(2).unwrap())]),
+        Duration::from_secs(10),
+    );
+    let calls: Vec<_> = (1..=3)
+        .map(|i| (contract, H256::repeat_byte(i)))
+        .chain([(Address::repeat_byte(0x22), H256::repeat_byte(4))])
+        .collect();
+
+    let now = Instant::now();
+    // Transactions exceeding the limit together are not recorded at all.
+    policy.check_at(&calls, now, true).unwrap_err();
+    policy.check_at(&calls[..2], now, true).unwrap();
+    policy.check_at(&calls[2..], now, false).unwrap_err();
+
+    // Reverted transactions no longer consume the limit.
+    policy.revert(&calls[..1]);
+    policy.check_at(&calls[2..], now, true).unwrap();
+    policy.check_at(&calls[..1], now, false).unwrap_err();
+    policy.revert(&calls[1..]);
+    policy.check_at(&calls[..2], now, false).unwrap();
+}
+
+#[tokio::test]
+async fn rate_limit_is_consumed_by_recorded_submissions() {
+    let contract = Address::repeat_byte(0x11);
+    let engine = TxPolicyEngine::default().with_policy(ContractRateLimitPolicy::new(
+        HashMap::from([(contract, NonZeroU32::new(1).unwrap())]),
+        Duration::from_secs(60),
+    ));
+    let tx = create_tx(Address::repeat_byte(0x01), contract, vec![]);
+
+    // Transactions that haven't passed all submission checks must not consume the limit.
+    for _ in 0..3 {
+        engine
+            .check_transaction(&tx, TxPolicyStage::Submission)
+            .await
+            .unwrap();
+    }
+    let txs = [tx.clone()];
+    engine.record_submission(&txs).await.unwrap();
+    engine
+        .check_transaction(&tx, TxPolicyStage::Submission)
+        .await
+        .unwrap_err();
+    engine.record_submission(&txs).await.unwrap_err();
+
+    // Reverting the submission (e.g., if the transaction wasn't accepted into the mempool) releases the limit.
+    engine.revert_submission(&txs).await;
+    engine
+        .check_transaction(&tx, TxPolicyStage::Submission)
+        .await
+        .unwrap();
+    engine.record_submission(&txs).await.unwrap();
+    // The limit is not enforced on inclusion.
+    engine
+        .check_transaction(&tx, TxPolicyStage::Inclusion)
+        .await
+        .unwrap();
+}
@@ -37,6 +37,16 @@ pub trait BatchExecutor<S>: 'static + Send + fmt::Debug {
         tx: Transaction,
     ) -> anyhow::Result<BatchTransactionExecutionResult>;
 
+    /// Executes an atomic bundle of transactions in the specified order. Execution stops on the first failed transaction,
+    /// so the returned results may be shorter than `txs`. [`Self::rollback_last_tx()`] called after this method
+    /// rolls back the entire bundle.
+    async fn execute_bundle(
+        &mut self,
+        _txs: Vec<Transaction>,
+    ) -> anyhow::Result<Vec<BatchTransactionExecutionResult>> {
+        anyhow::bail!("transaction bundles are not supported by this executor")
+    }
+
     /// Rolls back the last executed transaction.
     async fn rollback_last_tx(&mut self) -> anyhow::Result<()>;
 
@@ -51,6 +61,19 @@ pub trait BatchExecutor<S>: 'static + Send + fmt::Debug {
 
     /// Commits the first uncommitted l2 block making so it cannot be rolled back anymore.
     async fn commit_l2_block(&mut self) -> anyhow::Result<()>;
+
+    /// Returns the maximum number of upcoming transactions the executor can speculatively pre-execute.
+    /// If 0 (the default), speculation is not supported, and [`Self::speculate()`] is a no-op.
+    fn speculation_lookahead(&self) -> usize {
+        0
+    }
+
+    /// Hints the executor about transactions that are likely to be executed next, in the expected execution order.
+    /// The executor may pre-execute these transactions in the background to warm up storage. Pre-execution results
+    /// must not be reused or influence results of [`Self::execute_tx()`] in any way.
+    async fn speculate(&mut self, _txs: Vec<Transaction>) -> anyhow::Result<()> {
+        Ok(())
+    }
 }
 
 /// VM executor capable of executing isolated transactions / calls (as opposed to [batch execution](BatchExecutor)).
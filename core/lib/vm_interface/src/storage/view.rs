@@ -113,6 +113,37 @@ impl<S> StorageView<S> {
     pub fn inner_mut(&mut self) -> &mut S {
         &mut self.storage_handle
     }
+
+    /// Maps the underlying storage, retaining modifications and cached data.
+    ///
+    /// # Warning
+    ///
+    /// The mapped storage must have the same contents as the original one; otherwise, `StorageView` invariants are broken.
+    #[doc(hidden)]
+    pub fn map_storage<T>(self, map: impl FnOnce(S) -> T) -> StorageView<T> {
+        StorageView {
+            storage_handle: map(self.storage_handle),
+            modified_storage_keys: self.modified_storage_keys,
+            cache: self.cache,
+            stats: self.stats,
+        }
+    }
+
+    /// Extends the cache with entries obtained from the same underlying storage (e.g., by another view
+    /// created for the same L1 batch). Existing cache entries are not overwritten.
+    ///
+    /// # Warning
+    ///
+    /// The provided entries must correspond to the underlying storage; otherwise, `StorageView` invariants are broken.
+    #[doc(hidden)]
+    pub fn extend_cache(&mut self, cache: StorageViewCache) {
+        for (key, value) in cache.read_storage_keys {
+            self.cache.read_storage_keys.entry(key).or_insert(value);
+        }
+        for (key, is_initial) in cache.initial_writes {
+            self.cache.initial_writes.entry(key).or_insert(is_initial);
+        }
+    }
 }
 
 impl<S> ReadStorage for Box<S>
@@ -0,0 +1,584 @@
+//! Client distributing requests among several upstream nodes with health-based failover.
+
+use std::{
+    collections::HashSet,
+    fmt, mem,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use jsonrpsee::{
+    core::{
+        client::{BatchResponse, ClientT, Error},
+        params::BatchRequestBuilder,
+        traits::ToRpcParams,
+    },
+    rpc_params,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use tokio::sync::watch;
+use zksync_types::{H256, U64};
+
+use super::{
+    boxed::RawParams, metrics::FAILOVER_METRICS, DynClient, ForWeb3Network, Network, TaggedClient,
+};
+use crate::error::is_retryable;
+
+/// Status of a single upstream shared among all clones of a [`FailoverClient`].
+#[derive(Debug, Clone, Copy)]
+struct UpstreamStatus {
+    /// Whether the last interaction with the upstream succeeded.
+    is_reachable: bool,
+    /// Whether the upstream is known to agree with the primary upstream on block hashes.
+    /// Always `true` for the primary upstream.
+    is_consistent: bool,
+}
+
+impl UpstreamStatus {
+    fn is_usable(self) -> bool {
+        self.is_reachable && self.is_consistent
+    }
+}
+
+/// Block used as a reference for consistency checks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ReferenceBlock {
+    number: U64,
+    hash: H256,
+}
+
+#[derive(Debug)]
+struct FailoverState {
+    statuses: Mutex<Vec<UpstreamStatus>>,
+    /// Latest block obtained from the primary upstream.
+    reference_block: Mutex<Option<ReferenceBlock>>,
+    /// Counter used to spread the load among upstreams.
+    next_upstream: AtomicUsize,
+}
+
+impl FailoverState {
+    fn new(upstream_count: usize) -> Self {
+        let statuses = (0..upstream_count)
+            .map(|i| UpstreamStatus {
+                is_reachable: true,
+                // Secondary upstreams are not used until they are checked to be consistent with the primary one.
+                is_consistent: i == 0,
+            })
+            .collect();
+        Self {
+            statuses: Mutex::new(statuses),
+            reference_block: Mutex::new(None),
+            next_upstream: AtomicUsize::new(0),
+        }
+    }
+
+    fn statuses(&self) -> Vec<UpstreamStatus> {
+        self.statuses.lock().expect("statuses are poisoned").clone()
+    }
+
+    fn update_status(&self, index: usize, update: impl FnOnce(&mut UpstreamStatus)) {
+        let mut statuses = self.statuses.lock().expect("statuses are poisoned");
+        update(&mut statuses[index]);
+        let reachable_count = statuses.iter().filter(|status| status.is_reachable).count();
+        let usable_count = statuses.iter().filter(|status| status.is_usable()).count();
+        drop(statuses);
+        FAILOVER_METRICS.reachable_upstreams.set(reachable_count);
+        FAILOVER_METRICS.usable_upstreams.set(usable_count);
+    }
+}
+
+/// JSON-RPC client with several upstream nodes. The first upstream is the *primary* one (e.g., the main node);
+/// other upstreams (e.g., trusted external nodes) are only used if they agree with the primary upstream on block hashes,
+/// which is periodically checked by [`UpstreamsChecker`].
+///
+/// Requests are sent to the primary upstream if it's reachable. If a request fails with a retryable error,
+/// it's retried with other usable upstreams. For components configured via [`Self::with_load_spreading_for()`],
+/// requests are spread among all usable upstreams in a round-robin fashion instead.
+pub struct FailoverClient<Net: Network> {
+    upstreams: Vec<Box<DynClient<Net>>>,
+    state: Arc<FailoverState>,
+    load_spreading_components: Arc<HashSet<&'static str>>,
+    component_name: &'static str,
+    network: Net,
+}
+
+impl<Net: Network> Clone for FailoverClient<Net> {
+    fn clone(&self) -> Self {
+        Self {
+            upstreams: self.upstreams.clone(),
+            state: self.state.clone(),
+            load_spreading_components: self.load_spreading_components.clone(),
+            component_name: self.component_name,
+            network: self.network,
+        }
+    }
+}
+
+impl<Net: Network> fmt::Debug for FailoverClient<Net> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("FailoverClient")
+            .field("upstreams", &self.upstreams)
+            .field("load_spreading_components", &self.load_spreading_components)
+            .field("component_name", &self.component_name)
+            .field("network", &self.network)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Net: Network> FailoverClient<Net> {
+    /// Creates a client with the specified upstreams; the first one is the primary upstream.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `upstreams` is empty.
+    pub fn new(upstreams: Vec<Box<DynClient<Net>>>) -> Self {
+        assert!(!upstreams.is_empty(), "no upstreams provided");
+        let network = upstreams[0].network();
+        Self {
+            state: Arc::new(FailoverState::new(upstreams.len())),
+            upstreams,
+            load_spreading_components: Arc::default(),
+            component_name: "",
+            network,
+        }
+    }
+
+    /// Spreads requests from the specified components (e.g., ones fetching historical data) among all usable upstreams.
+    #[must_use]
+    pub fn with_load_spreading_for(mut self, components: &[&'static str]) -> Self {
+        self.load_spreading_components = Arc::new(components.iter().copied().collect());
+        self
+    }
+
+    /// Creates a checker for upstream reachability and consistency. The checker must be run for secondary upstreams
+    /// to be used.
+    pub fn checker(&self, interval: Duration) -> UpstreamsChecker<Net> {
+        UpstreamsChecker {
+            upstreams: self
+                .upstreams
+                .iter()
+                .map(|upstream| upstream.clone().for_component("upstreams_checker"))
+                .collect(),
+            state: self.state.clone(),
+            interval,
+        }
+    }
+
+    /// Returns indices of upstreams in the order they should be tried for a request.
+    fn upstreams_order(&self) -> Vec<usize> {
+        let statuses = self.state.statuses();
+        let mut usable: Vec<_> = (0..statuses.len())
+            .filter(|&i| statuses[i].is_usable())
+            .collect();
+        if self.load_spreading_components.contains(self.component_name) && !usable.is_empty() {
+            let offset = self.state.next_upstream.fetch_add(1, Ordering::Relaxed);
+            usable.rotate_left(offset % usable.len());
+        }
+        // If the primary upstream is marked as unreachable, it's still tried as a last resort;
+        // it may have recovered since the last check.
+        if !usable.contains(&0) {
+            usable.push(0);
+        }
+        usable
+    }
+
+    /// Records the call result for the specified upstream. Returns `true` if the call should be retried
+    /// with another upstream.
+    fn record_call_result<T>(&self, index: usize, method: &str, result: &Result<T, Error>) -> bool {
+        match result {
+            Ok(_) => {
+                self.state
+                    .update_status(index, |status| status.is_reachable = true);
+                false
+            }
+            Err(err) if is_retryable(err) => {
+                tracing::info!(
+                    component = self.component_name,
+                    "Call to `{method}` failed on upstream #{index}, marking it as unreachable: {err}"
+                );
+                self.state
+                    .update_status(index, |status| status.is_reachable = false);
+                FAILOVER_METRICS.failed_calls[&self.component_name].inc();
+                true
+            }
+            Err(_) => {
+                // The upstream has responded, albeit with an error.
+                self.state
+                    .update_status(index, |status| status.is_reachable = true);
+                false
+            }
+        }
+    }
+}
+
+impl<Net: Network> ForWeb3Network for FailoverClient<Net> {
+    type Net = Net;
+
+    fn network(&self) -> Self::Net {
+        self.network
+    }
+
+    fn component(&self) -> &'static str {
+        self.component_name
+    }
+}
+
+impl<Net: Network> TaggedClient for FailoverClient<Net> {
+    fn set_component(&mut self, component_name: &'static str) {
+        self.component_name = component_name;
+        self.upstreams = mem::take(&mut self.upstreams)
+            .into_iter()
+            .map(|upstream| upstream.for_component(component_name))
+            .collect();
+    }
+}
+
+#[async_trait]
+impl<Net: Network> ClientT for FailoverClient<Net> {
+    async fn notification<Params>(&self, method: &str, params: Params) -> Result<(), Error>
+    where
+        Params: ToRpcParams + Send,
+    {
+        let params = RawParams::new(params)?;
+        let mut result = Err(Error::RequestTimeout);
+        for index in self.upstreams_order() {
+            let upstream = self.upstreams[index].as_ref();
+            result = ClientT::notification(&upstream, method, params.clone()).await;
+            if !self.record_call_result(index, method, &result) {
+                break;
+            }
+        }
+        result
+    }
+
+    async fn request<R, Params>(&self, method: &str, params: Params) -> Result<R, Error>
+    where
+        R: DeserializeOwned,
+        Params: ToRpcParams + Send,
+    {
+        let params = RawParams::new(params)?;
+        let mut result = Err(Error::RequestTimeout);
+        for index in self.upstreams_order() {
+            let upstream = self.upstreams[index].as_ref();
+            result = ClientT::request(&upstream, method, params.clone()).await;
+            if !self.record_call_result(index, method, &result) {
+                break;
+            }
+        }
+        result
+    }
+
+    async fn batch_request<'a, R>(
+        &self,
+        batch: BatchRequestBuilder<'a>,
+    ) -> Result<BatchResponse<'a, R>, Error>
+    where
+        R: DeserializeOwned + fmt::Debug + 'a,
+    {
+        let mut result = Err(Error::RequestTimeout);
+        for index in self.upstreams_order() {
+            let upstream = self.upstreams[index].as_ref();
+            result = ClientT::batch_request(&upstream, batch.clone()).await;
+            if !self.record_call_result(index, "batch", &result) {
+                break;
+            }
+        }
+        result
+    }
+}
+
+/// Minimal block header used in consistency checks.
+#[derive(Debug, Deserialize)]
+struct BlockHeader {
+    hash: H256,
+}
+
+/// Periodically checks reachability of [`FailoverClient`] upstreams and their consistency with the primary upstream.
+///
+/// A secondary upstream is considered consistent if its hash for the latest block it shares with the primary upstream
+/// matches the primary one. If the primary upstream is unreachable, the latest block obtained from it is used
+/// as a reference instead.
+#[derive(Debug)]
+pub struct UpstreamsChecker<Net: Network> {
+    upstreams: Vec<Box<DynClient<Net>>>,
+    state: Arc<FailoverState>,
+    interval: Duration,
+}
+
+impl<Net: Network> UpstreamsChecker<Net> {
+    async fn block_number(upstream: &DynClient<Net>) -> Result<U64, Error> {
+        ClientT::request(&upstream, "eth_blockNumber", rpc_params![]).await
+    }
+
+    async fn block_hash(upstream: &DynClient<Net>, number: U64) -> Result<Option<H256>, Error> {
+        let header: Option<BlockHeader> = ClientT::request(
+            &upstream,
+            "eth_getBlockByNumber",
+            rpc_params![number, false],
+        )
+        .await?;
+        Ok(header.map(|header| header.hash))
+    }
+
+    /// Checks the primary upstream and updates the reference block. Returns the primary upstream head.
+    async fn check_primary(&self) -> Option<U64> {
+        let primary = self.upstreams[0].as_ref();
+        let head = async {
+            let number = Self::block_number(primary).await?;
+            let hash = Self::block_hash(primary, number).await?;
+            Ok::<_, Error>((number, hash))
+        };
+        match head.await {
+            Ok((number, hash)) => {
+                self.state
+                    .update_status(0, |status| status.is_reachable = true);
+                if let Some(hash) = hash {
+                    *self
+                        .state
+                        .reference_block
+                        .lock()
+                        .expect("reference block is poisoned") =
+                        Some(ReferenceBlock { number, hash });
+                }
+                Some(number)
+            }
+            Err(err) => {
+                tracing::warn!("Primary upstream is unreachable: {err}");
+                self.state
+                    .update_status(0, |status| status.is_reachable = false);
+                None
+            }
+        }
+    }
+
+    /// Checks a secondary upstream. Returns its consistency with the primary upstream, or `None`
+    /// if consistency cannot be established (e.g., if the upstream is behind the reference block).
+    async fn check_secondary(
+        &self,
+        index: usize,
+        primary_head: Option<U64>,
+    ) -> Result<Option<bool>, Error> {
+        let upstream = self.upstreams[index].as_ref();
+        let head = Self::block_number(upstream).await?;
+        let reference_block = *self
+            .state
+            .reference_block
+            .lock()
+            .expect("reference block is poisoned");
+
+        let expected_block = match (primary_head, reference_block) {
+            // The primary upstream is reachable, so we can compare the latest common block.
+            (Some(primary_head), _) => {
+                let number = head.min(primary_head);
+                let primary_hash = Self::block_hash(self.upstreams[0].as_ref(), number).await;
+                // Errors of the primary upstream shouldn't influence the secondary upstream status.
+                match primary_hash {
+                    Ok(Some(hash)) => ReferenceBlock { number, hash },
+                    Ok(None) | Err(_) => return Ok(None),
+                }
+            }
+            (None, Some(reference_block)) if reference_block.number <= head => reference_block,
+            (None, _) => return Ok(None),
+        };
+        let hash = Self::block_hash(upstream, expected_block.number).await?;
+        Ok(hash.map(|hash| hash == expected_block.hash))
+    }
+
+    async fn check(&self) {
+        let primary_head = self.check_primary().await;
+        for index in 1..self.upstreams.len() {
+            match self.check_secondary(index, primary_head).await {
+                Ok(is_consistent) => {
+                    self.state.update_status(index, |status| {
+                        status.is_reachable = true;
+                        if let Some(is_consistent) = is_consistent {
+                            if status.is_consistent && !is_consistent {
+                                tracing::error!(
+                                    "Upstream #{index} diverged from the primary upstream; it won't be used"
+                                );
+                            } else if !status.is_consistent && is_consistent {
+                                tracing::info!(
+                                    "Upstream #{index} is consistent with the primary upstream"
+                                );
+                            }
+                            status.is_consistent = is_consistent;
+                        }
+                    });
+                }
+                Err(err) => {
+                    tracing::warn!("Upstream #{index} is unreachable: {err}");
+                    self.state
+                        .update_status(index, |status| status.is_reachable = false);
+                }
+            }
+        }
+    }
+
+    /// Runs this checker until a stop request is received.
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow_and_update() {
+            self.check().await;
+            if tokio::time::timeout(self.interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop request received, upstreams checker is shutting down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicBool;
+
+    use assert_matches::assert_matches;
+    use zksync_types::api;
+
+    use super::*;
+    use crate::{
+        client::{MockClient, L2},
+        namespaces::EthNamespaceClient,
+    };
+
+    /// Mock upstream serving blocks `0..=head` with hashes defined by `salt`.
+    fn mock_upstream(
+        head: u64,
+        salt: u8,
+        is_down: Arc<AtomicBool>,
+        calls: Arc<AtomicUsize>,
+    ) -> Box<DynClient<L2>> {
+        let is_down_for_hashes = is_down.clone();
+        let client = MockClient::builder(L2::default())
+            .method("eth_blockNumber", move || {
+                calls.fetch_add(1, Ordering::SeqCst);
+                if is_down.load(Ordering::SeqCst) {
+                    return Err(Error::RequestTimeout);
+                }
+                Ok(U64::from(head))
+            })
+            .method("eth_getBlockByNumber", move |number: U64, _full: bool| {
+                if is_down_for_hashes.load(Ordering::SeqCst) {
+                    return Err(Error::RequestTimeout);
+                }
+                Ok(
+                    (number.as_u64() <= head).then(|| api::Block::<api::TransactionVariant> {
+                        number,
+                        hash: H256::from_low_u64_be(number.as_u64() ^ (u64::from(salt) << 32)),
+                        ..api::Block::default()
+                    }),
+                )
+            })
+            .build();
+        Box::new(client)
+    }
+
+    #[derive(Debug, Default)]
+    struct UpstreamHandles {
+        is_down: Arc<AtomicBool>,
+        calls: Arc<AtomicUsize>,
+    }
+
+    fn mock_upstreams(heads_and_salts: &[(u64, u8)]) -> (FailoverClient<L2>, Vec<UpstreamHandles>) {
+        let handles: Vec<_> = heads_and_salts
+            .iter()
+            .map(|_| UpstreamHandles::default())
+            .collect();
+        let upstreams = heads_and_salts
+            .iter()
+            .zip(&handles)
+            .map(|(&(head, salt), handles)| {
+                mock_upstream(head, salt, handles.is_down.clone(), handles.calls.clone())
+            })
+            .collect();
+        let client = FailoverClient::new(upstreams).with_load_spreading_for(&["historical"]);
+        (client, handles)
+    }
+
+    #[tokio::test]
+    async fn secondary_upstreams_are_not_used_before_check() {
+        let (client, handles) = mock_upstreams(&[(10, 0), (8, 0)]);
+        handles[0].is_down.store(true, Ordering::SeqCst);
+
+        let err = client.get_block_number().await.unwrap_err();
+        assert_matches!(err, Error::RequestTimeout);
+        assert_eq!(handles[1].calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn failing_over_to_consistent_upstream() {
+        let (client, handles) = mock_upstreams(&[(10, 0), (8, 0), (12, 1)]);
+        let checker = client.checker(Duration::from_secs(1));
+        checker.check().await;
+        let statuses = client.state.statuses();
+        assert!(statuses[1].is_usable());
+        // The third upstream has different block hashes.
+        assert!(statuses[2].is_reachable);
+        assert!(!statuses[2].is_consistent);
+
+        assert_eq!(client.get_block_number().await.unwrap(), U64::from(10));
+        handles[0].is_down.store(true, Ordering::SeqCst);
+        assert_eq!(client.get_block_number().await.unwrap(), U64::from(8));
+        assert!(!client.state.statuses()[0].is_reachable);
+        assert_eq!(handles[2].calls.load(Ordering::SeqCst), 1); // only by the checker
+
+        // The secondary upstream should be checked against the reference block obtained from the primary one;
+        // it's behind the reference block, so its status should stay the same.
+        checker.check().await;
+        assert!(client.state.statuses()[1].is_usable());
+
+        // Once the primary upstream recovers, it should be used again.
+        handles[0].is_down.store(false, Ordering::SeqCst);
+        checker.check().await;
+        assert_eq!(client.get_block_number().await.unwrap(), U64::from(10));
+    }
+
+    #[tokio::test]
+    async fn checking_consistency_without_primary_upstream() {
+        let (client, handles) = mock_upstreams(&[(10, 0), (12, 0), (12, 1)]);
+        let checker = client.checker(Duration::from_secs(1));
+        checker.check().await;
+        handles[0].is_down.store(true, Ordering::SeqCst);
+        checker.check().await;
+
+        let reference_block = client.state.reference_block.lock().unwrap().unwrap();
+        assert_eq!(reference_block.number, U64::from(10));
+        let statuses = client.state.statuses();
+        assert!(!statuses[0].is_reachable);
+        assert!(statuses[1].is_usable());
+        assert!(!statuses[2].is_consistent);
+    }
+
+    #[tokio::test]
+    async fn spreading_load_among_upstreams() {
+        let (client, handles) = mock_upstreams(&[(10, 0), (10, 0)]);
+        client.checker(Duration::from_secs(1)).check().await;
+        let calls_after_check: Vec<_> = handles
+            .iter()
+            .map(|handles| handles.calls.load(Ordering::SeqCst))
+            .collect();
+
+        let historical_client = Box::new(client.clone()).for_component("historical");
+        for _ in 0..4 {
+            historical_client.get_block_number().await.unwrap();
+        }
+        for (handles, calls_after_check) in handles.iter().zip(calls_after_check) {
+            assert_eq!(handles.calls.load(Ordering::SeqCst) - calls_after_check, 2);
+        }
+
+        // Other components should only use the primary upstream.
+        let calls_to_secondary = handles[1].calls.load(Ordering::SeqCst);
+        let client = Box::new(client).for_component("other");
+        for _ in 0..4 {
+            client.get_block_number().await.unwrap();
+        }
+        assert_eq!(handles[1].calls.load(Ordering::SeqCst), calls_to_secondary);
+    }
+}
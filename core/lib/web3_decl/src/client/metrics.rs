@@ -4,8 +4,8 @@ use std::time::Duration;
 
 use jsonrpsee::{core::client, http_client::transport};
 use vise::{
-    Buckets, Counter, DurationAsSecs, EncodeLabelSet, EncodeLabelValue, Family, Histogram, Info,
-    LabeledFamily, Metrics, MetricsFamily, Unit,
+    Buckets, Counter, DurationAsSecs, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram,
+    Info, LabeledFamily, Metrics, MetricsFamily, Unit,
 };
 
 use super::{AcquireStats, CallOrigin, SharedRateLimit};
@@ -170,3 +170,18 @@ impl L2ClientMetrics {
 
 #[vise::register]
 pub(super) static METRICS: MetricsFamily<ClientLabels, L2ClientMetrics> = MetricsFamily::new();
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "l2_client_failover")]
+pub(super) struct FailoverMetrics {
+    /// Number of upstreams that were reachable during the last interaction.
+    pub reachable_upstreams: Gauge<usize>,
+    /// Number of reachable upstreams consistent with the primary upstream.
+    pub usable_upstreams: Gauge<usize>,
+    /// Number of calls failed on an upstream with a retryable error.
+    #[metrics(labels = ["component"])]
+    pub failed_calls: LabeledFamily<&'static str, Counter>,
+}
+
+#[vise::register]
+pub(super) static FAILOVER_METRICS: vise::Global<FailoverMetrics> = vise::Global::new();
@@ -10,6 +10,7 @@
 //!   where it's possible.
 //! - [`BoxedL2Client`] is a generic client (essentially, a wrapper around a trait object). Use it for dependency injection
 //!   instead of `L2Client`. Both `L2Client` and `MockL2Client` are convertible to `BoxedL2Client`.
+//! - [`FailoverClient`] combines several clients for different upstream nodes, failing over between them.
 
 use std::{
     any,
@@ -37,6 +38,7 @@ use zksync_types::url::SensitiveUrl;
 use self::metrics::{L2ClientMetrics, METRICS};
 pub use self::{
     boxed::{DynClient, ObjectSafeClient},
+    failover::{FailoverClient, UpstreamsChecker},
     mock::{MockClient, MockClientBuilder},
     network::{ForWeb3Network, Network, TaggedClient, L1, L2},
     shared::Shared,
@@ -44,6 +46,7 @@ pub use self::{
 use crate::client::metrics::{ClientLabels, INFO_METRICS};
 
 mod boxed;
+mod failover;
 mod metrics;
 mod mock;
 mod network;
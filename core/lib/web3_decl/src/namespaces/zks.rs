@@ -4,7 +4,7 @@ use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
     api::{
         state_override::StateOverride, BlockDetails, BridgeAddresses, InteropMode, L1BatchDetails,
-        L2ToL1LogProof, Proof, ProtocolVersion, TransactionDetails,
+        L2ToL1LogProof, Proof, ProofFormat, ProtocolVersion, TransactionDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -12,7 +12,10 @@ use zksync_types::{
     Address, L1BatchNumber, L2BlockNumber, H256, U256, U64,
 };
 
-use crate::client::{ForWeb3Network, L2};
+use crate::{
+    client::{ForWeb3Network, L2},
+    types::Bytes,
+};
 
 #[cfg_attr(
     feature = "server",
@@ -117,6 +120,7 @@ pub trait ZksNamespace {
         address: Address,
         keys: Vec<H256>,
         l1_batch_number: L1BatchNumber,
+        format: Option<ProofFormat>,
     ) -> RpcResult<Option<Proof>>;
 
     #[method(name = "getBatchFeeInput")]
@@ -124,4 +128,9 @@ pub trait ZksNamespace {
 
     #[method(name = "gasPerPubdata")]
     async fn gas_per_pubdata(&self) -> RpcResult<U256>;
+
+    /// Submits an atomic bundle of raw transactions. Bundle transactions are either all included
+    /// into the same L2 block in the provided order, or none of them is included. Returns the bundle hash.
+    #[method(name = "sendBundle")]
+    async fn send_bundle(&self, txs: Vec<Bytes>) -> RpcResult<H256>;
 }
@@ -1,23 +1,35 @@
-use std::{num::NonZeroUsize, sync::Arc};
+use std::{num::NonZeroUsize, sync::Arc, time::Duration};
 
 use anyhow::Context;
 use async_trait::async_trait;
 use zksync_health_check::{AppHealthCheck, CheckHealth, Health, HealthStatus};
 use zksync_node_framework::{
+    service::StopReceiver,
+    task::{Task, TaskId},
     wiring_layer::{WiringError, WiringLayer},
     FromContext, IntoContext,
 };
 use zksync_types::{url::SensitiveUrl, L2ChainId};
 
 use crate::{
-    client::{Client, DynClient, L2},
+    client::{Client, DynClient, FailoverClient, UpstreamsChecker, L2},
     namespaces::EthNamespaceClient,
 };
 
+/// Components fetching historical data. If there are several upstreams, requests from these components
+/// are spread among all of them.
+const HISTORICAL_DATA_COMPONENTS: &[&str] = &[
+    "tree_data_fetcher",
+    "batch_transaction_fetcher",
+    "data_availability_fetcher",
+];
+
 /// Wiring layer for main node client.
 #[derive(Debug)]
 pub struct MainNodeClientLayer {
     url: SensitiveUrl,
+    fallback_urls: Vec<SensitiveUrl>,
+    check_interval: Duration,
     rate_limit_rps: NonZeroUsize,
     l2_chain_id: L2ChainId,
 }
@@ -31,16 +43,38 @@ pub struct Input {
 #[derive(Debug, IntoContext)]
 pub struct Output {
     main_node_client: Box<DynClient<L2>>,
+    #[context(task)]
+    upstreams_checker: Option<UpstreamsChecker<L2>>,
 }
 
 impl MainNodeClientLayer {
     pub fn new(url: SensitiveUrl, rate_limit_rps: NonZeroUsize, l2_chain_id: L2ChainId) -> Self {
         Self {
             url,
+            fallback_urls: vec![],
+            check_interval: Duration::ZERO,
             rate_limit_rps,
             l2_chain_id,
         }
     }
+
+    /// Adds fallback upstreams (e.g., trusted external nodes) checked for reachability and consistency
+    /// with the main node with the specified interval.
+    #[must_use]
+    pub fn with_fallback_urls(mut self, urls: Vec<SensitiveUrl>, check_interval: Duration) -> Self {
+        self.fallback_urls = urls;
+        self.check_interval = check_interval;
+        self
+    }
+
+    fn build_client(&self, url: SensitiveUrl) -> anyhow::Result<Box<DynClient<L2>>> {
+        let client = Client::http(url)
+            .context("failed creating JSON-RPC client for main node")?
+            .for_network(self.l2_chain_id.into())
+            .with_allowed_requests_per_second(self.rate_limit_rps)
+            .build();
+        Ok(Box::new(client))
+    }
 }
 
 #[async_trait::async_trait]
@@ -53,13 +87,23 @@ impl WiringLayer for MainNodeClientLayer {
     }
 
     async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
-        let main_node_client = Client::http(self.url)
-            .context("failed creating JSON-RPC client for main node")?
-            .for_network(self.l2_chain_id.into())
-            .with_allowed_requests_per_second(self.rate_limit_rps)
-            .build();
-
-        let client = Box::new(main_node_client) as Box<DynClient<L2>>;
+        let main_node_client = self.build_client(self.url.clone())?;
+        let (client, upstreams_checker) = if self.fallback_urls.is_empty() {
+            (main_node_client, None)
+        } else {
+            tracing::info!(
+                "Using {} fallback upstream(s) for main node client",
+                self.fallback_urls.len()
+            );
+            let mut upstreams = vec![main_node_client];
+            for url in &self.fallback_urls {
+                upstreams.push(self.build_client(url.clone())?);
+            }
+            let client =
+                FailoverClient::new(upstreams).with_load_spreading_for(HISTORICAL_DATA_COMPONENTS);
+            let checker = client.checker(self.check_interval);
+            (Box::new(client) as Box<DynClient<L2>>, Some(checker))
+        };
 
         // Insert healthcheck
         input
@@ -69,10 +113,22 @@ impl WiringLayer for MainNodeClientLayer {
 
         Ok(Output {
             main_node_client: client,
+            upstreams_checker,
         })
     }
 }
 
+#[async_trait]
+impl Task for UpstreamsChecker<L2> {
+    fn id(&self) -> TaskId {
+        "main_node_upstreams_checker".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
+
 /// Main node health check.
 #[derive(Debug)]
 struct MainNodeHealthCheck(Box<DynClient<L2>>);
@@ -374,6 +374,61 @@ impl BatchTreeProof {
     }
 }
 
+/// Values for a set of keys in a [`MerkleTree`](crate::MerkleTree) together with a *multiproof* of their authenticity,
+/// i.e. a single read-only [`BatchTreeProof`] for all keys in which leaves and hashes shared by several keys
+/// are only included once.
+#[derive(Debug)]
+pub struct TreeEntriesWithMultiproof {
+    /// Proven keys together with their values, sorted by key in the ascending order. Keys are unique;
+    /// `None` values correspond to keys missing from the tree.
+    pub entries: Vec<(H256, Option<H256>)>,
+    /// Number of leaves in the tree (including 2 guard leaves) at the proven version.
+    pub leaf_count: u64,
+    /// Proof with read operations corresponding 1-to-1 to `entries`.
+    pub proof: BatchTreeProof,
+}
+
+impl TreeEntriesWithMultiproof {
+    /// Verifies this multiproof against the trusted root hash of the tree.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error <=> proof is invalid.
+    pub fn verify(
+        self,
+        hasher: &dyn HashTree,
+        tree_depth: u8,
+        trusted_root_hash: H256,
+    ) -> anyhow::Result<()> {
+        for window in self.entries.windows(2) {
+            anyhow::ensure!(
+                window[0].0 < window[1].0,
+                "Entries are not sorted by key or contain duplicate keys"
+            );
+        }
+        anyhow::ensure!(
+            self.proof.operations.is_empty(),
+            "Multiproof contains write operations"
+        );
+
+        let keys: Vec<_> = self.entries.iter().map(|(key, _)| *key).collect();
+        let tree_output = BatchOutput {
+            root_hash: trusted_root_hash,
+            leaf_count: self.leaf_count,
+        };
+        let tree_view = self
+            .proof
+            .verify_reads(hasher, tree_depth, tree_output, &keys)?;
+        for (key, value) in &self.entries {
+            anyhow::ensure!(
+                tree_view.read_entries.get(key) == Some(value),
+                "Value mismatch for key {key:?}"
+            );
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use zksync_crypto_primitives::hasher::blake2::Blake2Hasher;
@@ -8,7 +8,7 @@ pub use zksync_crypto_primitives::hasher::blake2::Blake2Hasher;
 
 pub use self::{
     errors::DeserializeError,
-    hasher::{BatchTreeProof, HashTree, TreeOperation},
+    hasher::{BatchTreeProof, HashTree, TreeEntriesWithMultiproof, TreeOperation},
     reader::MerkleTreeReader,
     storage::{Database, MerkleTreeColumnFamily, PatchSet, Patched, RocksDBWrapper},
     types::{BatchOutput, TreeEntry},
@@ -196,6 +196,42 @@ impl<DB: Database, P: TreeParams> MerkleTree<DB, P> {
         Ok(patch.create_batch_proof(&self.hasher, vec![], update.take_read_operations()))
     }
 
+    /// Reads entries with the specified `keys` from the tree at the specified version together with
+    /// a [multiproof](TreeEntriesWithMultiproof) of their authenticity. The returned entries are sorted by key
+    /// and deduplicated.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an error if the version doesn't exist.
+    /// - Proxies database errors.
+    pub fn entries_with_multiproof(
+        &self,
+        version: u64,
+        keys: &[H256],
+    ) -> anyhow::Result<TreeEntriesWithMultiproof> {
+        let (_, leaf_count) = self
+            .root_info(version)?
+            .with_context(|| format!("tree version {version} doesn't exist"))?;
+        let mut sorted_keys = keys.to_vec();
+        sorted_keys.sort_unstable();
+        sorted_keys.dedup();
+
+        let proof = self.prove(version, &sorted_keys)?;
+        let values = proof
+            .read_operations
+            .iter()
+            .map(|operation| match operation {
+                TreeOperation::Hit { index } => Some(proof.sorted_leaves[index].value),
+                TreeOperation::Miss { .. } => None,
+            });
+        let entries = sorted_keys.iter().copied().zip(values).collect();
+        Ok(TreeEntriesWithMultiproof {
+            entries,
+            leaf_count,
+            proof,
+        })
+    }
+
     /// Extends this tree by creating its new version.
     ///
     /// All keys in the provided entries must be distinct.
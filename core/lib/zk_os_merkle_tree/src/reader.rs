@@ -5,7 +5,7 @@ use std::fmt;
 use zksync_basic_types::H256;
 
 use crate::{
-    hasher::BatchTreeProof,
+    hasher::{BatchTreeProof, TreeEntriesWithMultiproof},
     types::{NodeKey, RawNode},
     Database, DefaultTreeParams, MerkleTree, RocksDBWrapper, TreeParams,
 };
@@ -75,6 +75,20 @@ impl<DB: Database, P: TreeParams> MerkleTreeReader<DB, P> {
     pub fn prove(&self, version: u64, keys: &[H256]) -> anyhow::Result<BatchTreeProof> {
         self.0.prove(version, keys)
     }
+
+    /// Reads entries with the specified `keys` together with a multiproof of their authenticity.
+    ///
+    /// # Errors
+    ///
+    /// - Returns an error if the version doesn't exist.
+    /// - Proxies database errors.
+    pub fn entries_with_multiproof(
+        &self,
+        version: u64,
+        keys: &[H256],
+    ) -> anyhow::Result<TreeEntriesWithMultiproof> {
+        self.0.entries_with_multiproof(version, keys)
+    }
 }
 
 impl<P: TreeParams> MerkleTreeReader<RocksDBWrapper, P> {
@@ -474,6 +474,85 @@ fn test_read_proofs(db: impl Database) {
     }
 }
 
+fn test_multiproofs(db: impl Database) {
+    const RNG_SEED: u64 = 222;
+
+    let mut tree = MerkleTree::new(db).unwrap();
+    let empty_tree_output = tree.extend(&[]).unwrap();
+
+    let mut rng = StdRng::seed_from_u64(RNG_SEED);
+    let nodes = (0..1_000).map(|_| TreeEntry {
+        key: H256(rng.gen()),
+        value: H256(rng.gen()),
+    });
+    let inserts: Vec<_> = nodes.collect();
+    let new_tree_output = tree.extend(&inserts).unwrap();
+    let values_by_key: HashMap<_, _> = inserts
+        .iter()
+        .map(|entry| (entry.key, entry.value))
+        .collect();
+
+    let mut keys: Vec<_> = inserts.iter().take(100).map(|entry| entry.key).collect();
+    keys.extend((0..50).map(|_| H256(rng.gen())));
+    keys.shuffle(&mut rng);
+    keys.push(keys[0]); // Check key deduplication
+
+    // Multiproof at version 0 (i.e., before inserting entries).
+    let multiproof = tree.entries_with_multiproof(0, &keys).unwrap();
+    assert_eq!(multiproof.entries.len(), 150);
+    assert!(multiproof.entries.iter().all(|(_, value)| value.is_none()));
+    multiproof
+        .verify(&Blake2Hasher, 64, empty_tree_output.root_hash)
+        .unwrap();
+
+    let multiproof = tree.entries_with_multiproof(1, &keys).unwrap();
+    assert_eq!(multiproof.entries.len(), 150);
+    assert_eq!(multiproof.leaf_count, new_tree_output.leaf_count);
+    assert!(multiproof.entries.is_sorted_by_key(|(key, _)| *key));
+    for (key, value) in &multiproof.entries {
+        assert_eq!(*value, values_by_key.get(key).copied());
+    }
+
+    let individual_hash_count: usize = keys[..150]
+        .iter()
+        .map(|key| tree.prove(1, &[*key]).unwrap().hashes.len())
+        .sum();
+    assert!(
+        multiproof.proof.hashes.len() < individual_hash_count,
+        "{} vs {individual_hash_count}",
+        multiproof.proof.hashes.len()
+    );
+    multiproof
+        .verify(&Blake2Hasher, 64, new_tree_output.root_hash)
+        .unwrap();
+
+    let err = tree
+        .entries_with_multiproof(1, &keys)
+        .unwrap()
+        .verify(&Blake2Hasher, 64, empty_tree_output.root_hash)
+        .unwrap_err();
+    assert!(err.to_string().contains("Mismatch"), "{err}");
+
+    let mut tampered = tree.entries_with_multiproof(1, &keys).unwrap();
+    let (_, value) = tampered
+        .entries
+        .iter_mut()
+        .find(|(_, value)| value.is_some())
+        .unwrap();
+    *value = Some(H256::zero());
+    let err = tampered
+        .verify(&Blake2Hasher, 64, new_tree_output.root_hash)
+        .unwrap_err();
+    assert!(err.to_string().contains("Value mismatch"), "{err}");
+
+    let mut tampered = tree.entries_with_multiproof(1, &keys).unwrap();
+    tampered.entries.swap(0, 1);
+    let err = tampered
+        .verify(&Blake2Hasher, 64, new_tree_output.root_hash)
+        .unwrap_err();
+    assert!(err.to_string().contains("not sorted"), "{err}");
+}
+
 fn test_using_patched_database(db: impl Database) {
     const RNG_SEED: u64 = 321;
     const FLUSH_PROBABILITY: f64 = 0.4;
@@ -526,6 +605,11 @@ fn read_proofs() {
     test_read_proofs(PatchSet::default());
 }
 
+#[test]
+fn multiproofs() {
+    test_multiproofs(PatchSet::default());
+}
+
 mod rocksdb {
     use serde::{Deserialize, Serialize};
     use serde_with::{hex::Hex, serde_as};
@@ -659,6 +743,13 @@ mod rocksdb {
         test_read_proofs(db);
     }
 
+    #[test]
+    fn multiproofs() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = RocksDBWrapper::new(temp_dir.path()).unwrap();
+        test_multiproofs(db);
+    }
+
     #[test]
     fn using_patched_database() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,262 @@
+//! Admin API allowing to control node tasks and dynamic configuration at runtime.
+
+use std::{net::SocketAddr, sync::Arc};
+
+use anyhow::Context as _;
+use axum::{
+    extract::{ConnectInfo, Path, Request, State},
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use secrecy::{ExposeSecret, SecretString};
+use serde::Serialize;
+use tokio::sync::watch;
+use tracing::Instrument as _;
+use zksync_health_check::{AppHealthCheck, Health};
+use zksync_node_framework::control::{ControlError, NodeControl};
+
+pub mod node;
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, thiserror::Error)]
+enum ApiError {
+    #[error("missing or invalid auth token")]
+    Unauthorized,
+    #[error(transparent)]
+    Control(#[from] ControlError),
+    #[error("dynamic config `{0}` does not exist")]
+    UnknownConfig(String),
+    #[error("invalid update for dynamic config `{0}`: {1:#}")]
+    InvalidConfigUpdate(String, anyhow::Error),
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            Self::Unauthorized => StatusCode::UNAUTHORIZED,
+            Self::Control(ControlError::UnknownTask(_)) | Self::UnknownConfig(_) => {
+                StatusCode::NOT_FOUND
+            }
+            Self::Control(_) | Self::InvalidConfigUpdate(..) => StatusCode::BAD_REQUEST,
+        };
+        let body = serde_json::json!({ "error": self.to_string() });
+        (status, Json(body)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct TaskResponse {
+    id: String,
+    kind: String,
+    /// `None` if the task is not pausable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    paused: Option<bool>,
+    /// Health of the component with the same name as the task, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    health: Option<Health>,
+}
+
+#[derive(Debug, Serialize)]
+struct PauseResponse {
+    id: String,
+    paused: bool,
+    /// Whether the task state has changed as a result of the request.
+    changed: bool,
+}
+
+#[derive(Debug, Clone)]
+struct AdminApiState {
+    node_control: Arc<NodeControl>,
+    app_health: Arc<AppHealthCheck>,
+}
+
+impl AdminApiState {
+    async fn tasks(State(this): State<Self>) -> Json<Vec<TaskResponse>> {
+        tracing::info!("Listing tasks via admin API");
+        let app_health = this.app_health.check_health().await;
+        let tasks = this.node_control.tasks().into_iter().map(|task| {
+            let id = task.id.to_string();
+            TaskResponse {
+                health: app_health.components().get(id.as_str()).cloned(),
+                kind: format!("{:?}", task.kind),
+                paused: task.paused,
+                id,
+            }
+        });
+        Json(tasks.collect())
+    }
+
+    async fn pause_task(
+        State(this): State<Self>,
+        Path(id): Path<String>,
+    ) -> Result<Json<PauseResponse>, ApiError> {
+        this.set_paused(id, true)
+    }
+
+    async fn resume_task(
+        State(this): State<Self>,
+        Path(id): Path<String>,
+    ) -> Result<Json<PauseResponse>, ApiError> {
+        this.set_paused(id, false)
+    }
+
+    fn set_paused(&self, id: String, paused: bool) -> Result<Json<PauseResponse>, ApiError> {
+        let action = if paused { "pause" } else { "resume" };
+        let changed = self
+            .node_control
+            .set_paused(&id, paused)
+            .inspect_err(|err| {
+                tracing::warn!(task = %id, action, "Admin API action failed: {err}");
+            })?;
+        tracing::info!(task = %id, action, changed, "Performed admin API action");
+        Ok(Json(PauseResponse {
+            id,
+            paused,
+            changed,
+        }))
+    }
+
+    async fn configs(State(this): State<Self>) -> Json<serde_json::Map<String, serde_json::Value>> {
+        tracing::info!("Listing dynamic configs via admin API");
+        let mut configs = serde_json::Map::new();
+        for config in this.node_control.configs() {
+            configs.insert(config.name().to_owned(), config.current().await);
+        }
+        Json(configs)
+    }
+
+    async fn config(
+        State(this): State<Self>,
+        Path(name): Path<String>,
+    ) -> Result<Json<serde_json::Value>, ApiError> {
+        tracing::info!(config = %name, "Getting dynamic config via admin API");
+        let Some(config) = this.node_control.config(&name) else {
+            tracing::warn!(config = %name, "Admin API action failed: unknown config");
+            return Err(ApiError::UnknownConfig(name));
+        };
+        Ok(Json(config.current().await))
+    }
+
+    async fn update_config(
+        State(this): State<Self>,
+        Path(name): Path<String>,
+        Json(update): Json<serde_json::Value>,
+    ) -> Result<Json<serde_json::Value>, ApiError> {
+        let Some(config) = this.node_control.config(&name) else {
+            tracing::warn!(config = %name, "Admin API action failed: unknown config");
+            return Err(ApiError::UnknownConfig(name));
+        };
+        tracing::info!(config = %name, %update, "Updating dynamic config via admin API");
+        if let Err(err) = config.update(update).await {
+            tracing::warn!(config = %name, "Admin API action failed: {err:#}");
+            return Err(ApiError::InvalidConfigUpdate(name, err));
+        }
+        let current = config.current().await;
+        tracing::info!(config = %name, %current, "Updated dynamic config via admin API");
+        Ok(Json(current))
+    }
+}
+
+fn constant_time_eq(lhs: &[u8], rhs: &[u8]) -> bool {
+    lhs.len() == rhs.len() && lhs.iter().zip(rhs).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Authorizes the request and wraps its processing in a span with the caller context, so that all actions
+/// performed via the API (including read-only ones) can be attributed to the caller.
+async fn authorize(auth_token: &SecretString, request: Request, next: Next) -> Response {
+    let span = tracing::info_span!(
+        "admin_api_request",
+        caller = tracing::field::Empty,
+        method = %request.method(),
+        uri = %request.uri()
+    );
+    if let Some(ConnectInfo(caller)) = request.extensions().get::<ConnectInfo<SocketAddr>>() {
+        span.record("caller", tracing::field::display(caller));
+    }
+
+    let provided_token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let is_authorized = provided_token.is_some_and(|token| {
+        constant_time_eq(token.as_bytes(), auth_token.expose_secret().as_bytes())
+    });
+    if !is_authorized {
+        span.in_scope(|| tracing::warn!("Rejected unauthorized admin API request"));
+        return ApiError::Unauthorized.into_response();
+    }
+    next.run(request).instrument(span).await
+}
+
+/// Admin API server. All requests must be authorized with a bearer token.
+#[derive(Debug)]
+pub struct AdminApi {
+    router: Router,
+    bind_address: SocketAddr,
+}
+
+impl AdminApi {
+    pub fn new(
+        node_control: Arc<NodeControl>,
+        app_health: Arc<AppHealthCheck>,
+        auth_token: SecretString,
+        bind_address: SocketAddr,
+    ) -> Self {
+        let state = AdminApiState {
+            node_control,
+            app_health,
+        };
+        Self {
+            router: Self::router(state, auth_token),
+            bind_address,
+        }
+    }
+
+    fn router(state: AdminApiState, auth_token: SecretString) -> Router {
+        let auth_token = Arc::new(auth_token);
+        let auth_middleware = middleware::from_fn(move |request: Request, next: Next| {
+            let auth_token = auth_token.clone();
+            async move { authorize(&auth_token, request, next).await }
+        });
+
+        Router::new()
+            .route("/tasks", get(AdminApiState::tasks))
+            .route("/tasks/{id}/pause", post(AdminApiState::pause_task))
+            .route("/tasks/{id}/resume", post(AdminApiState::resume_task))
+            .route("/configs", get(AdminApiState::configs))
+            .route(
+                "/configs/{name}",
+                get(AdminApiState::config).patch(AdminApiState::update_config),
+            )
+            .layer(auth_middleware)
+            .with_state(state)
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let bind_address = self.bind_address;
+        tracing::info!("Starting admin API server on {bind_address}");
+
+        let listener = tokio::net::TcpListener::bind(bind_address)
+            .await
+            .with_context(|| format!("Failed binding admin API server to {bind_address}"))?;
+        let service = self
+            .router
+            .into_make_service_with_connect_info::<SocketAddr>();
+        axum::serve(listener, service)
+            .with_graceful_shutdown(async move {
+                if stop_receiver.changed().await.is_err() {
+                    tracing::warn!("Stop request sender for admin API server was dropped without sending a signal");
+                }
+                tracing::info!("Stop request received, admin API server is shutting down");
+            })
+            .await
+            .context("Admin API server failed")?;
+        tracing::info!("Admin API server shut down");
+        Ok(())
+    }
+}
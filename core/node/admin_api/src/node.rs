@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use zksync_config::configs::{AdminApiConfig, AdminApiSecrets};
+use zksync_health_check::AppHealthCheck;
+use zksync_node_framework::{
+    control::NodeControl,
+    service::StopReceiver,
+    task::{Task, TaskId, TaskKind},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+use crate::AdminApi;
+
+/// Wiring layer for [`AdminApi`].
+///
+/// ## Requests resources
+///
+/// - `NodeControl`
+/// - `AppHealthCheckResource`
+///
+/// ## Adds tasks
+///
+/// - `AdminApi`
+#[derive(Debug)]
+pub struct AdminApiLayer {
+    pub config: AdminApiConfig,
+    pub secrets: AdminApiSecrets,
+}
+
+#[derive(Debug, FromContext)]
+pub struct Input {
+    #[context(default)]
+    node_control: Arc<NodeControl>,
+    #[context(default)]
+    app_health: Arc<AppHealthCheck>,
+}
+
+#[derive(Debug, IntoContext)]
+pub struct Output {
+    #[context(task)]
+    admin_api: AdminApi,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for AdminApiLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "admin_api_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let auth_token = self.secrets.auth_token.ok_or_else(|| {
+            WiringError::Configuration(
+                "admin API requires `auth_token` to be configured in secrets".into(),
+            )
+        })?;
+        let admin_api = AdminApi::new(
+            input.node_control,
+            input.app_health,
+            auth_token,
+            self.config.bind_addr(),
+        );
+        Ok(Output { admin_api })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for AdminApi {
+    fn kind(&self) -> TaskKind {
+        // Allows inspecting and controlling the node before all preconditions are met.
+        TaskKind::UnconstrainedTask
+    }
+
+    fn id(&self) -> TaskId {
+        "admin_api".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
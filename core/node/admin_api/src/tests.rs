@@ -0,0 +1,179 @@
+use std::sync::Mutex;
+
+use anyhow::Context as _;
+use axum::{
+    body::Body,
+    http::{Method, Request},
+};
+use http_body_util::BodyExt as _;
+use tower::ServiceExt as _;
+use zksync_node_framework::{
+    control::{DynamicConfig, Pausable, PauseSwitch},
+    task::{Task, TaskId},
+    StopReceiver,
+};
+
+use super::*;
+
+const AUTH_TOKEN: &str = "correct horse battery staple";
+
+#[derive(Debug, Default)]
+struct MockTask(PauseSwitch);
+
+impl Pausable for MockTask {
+    fn pause_switch(&self) -> PauseSwitch {
+        self.0.clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for MockTask {
+    fn id(&self) -> TaskId {
+        "mock".into()
+    }
+
+    async fn run(self: Box<Self>, _stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct MockConfig(Mutex<u64>);
+
+#[async_trait::async_trait]
+impl DynamicConfig for MockConfig {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    async fn current(&self) -> serde_json::Value {
+        serde_json::json!({ "value": *self.0.lock().unwrap() })
+    }
+
+    async fn update(&self, update: serde_json::Value) -> anyhow::Result<()> {
+        let value = update["value"]
+            .as_u64()
+            .context("`value` must be an integer")?;
+        *self.0.lock().unwrap() = value;
+        Ok(())
+    }
+}
+
+fn create_router() -> (Router, PauseSwitch) {
+    let node_control = Arc::<NodeControl>::default();
+    let task = MockTask::default();
+    node_control.register_pausable(&task);
+    node_control
+        .register_config(Arc::new(MockConfig::default()))
+        .unwrap();
+
+    let state = AdminApiState {
+        node_control,
+        app_health: Arc::default(),
+    };
+    let router = AdminApi::router(state, AUTH_TOKEN.to_owned().into());
+    (router, task.0)
+}
+
+async fn send(
+    router: &Router,
+    method: Method,
+    uri: &str,
+    auth_token: Option<&str>,
+    body: Option<serde_json::Value>,
+) -> (StatusCode, serde_json::Value) {
+    let mut request = Request::builder().method(method).uri(uri);
+    if let Some(token) = auth_token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+    }
+    let body = if let Some(body) = body {
+        request = request.header(header::CONTENT_TYPE, "application/json");
+        Body::from(serde_json::to_vec(&body).unwrap())
+    } else {
+        Body::empty()
+    };
+
+    let response = router
+        .clone()
+        .oneshot(request.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn unauthorized_requests_are_rejected() {
+    let (router, pause_switch) = create_router();
+
+    let (status, _) = send(&router, Method::GET, "/tasks", None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(&router, Method::GET, "/tasks", Some("wrong"), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(&router, Method::POST, "/tasks/mock/pause", None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert!(!pause_switch.is_paused());
+
+    let (status, _) = send(&router, Method::GET, "/tasks", Some(AUTH_TOKEN), None).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn pausing_and_resuming_tasks() {
+    let (router, pause_switch) = create_router();
+    let token = Some(AUTH_TOKEN);
+
+    let (status, body) = send(&router, Method::POST, "/tasks/mock/pause", token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body,
+        serde_json::json!({ "id": "mock", "paused": true, "changed": true })
+    );
+    assert!(pause_switch.is_paused());
+
+    let (status, body) = send(&router, Method::POST, "/tasks/mock/pause", token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["changed"], false);
+
+    let (status, body) = send(&router, Method::POST, "/tasks/mock/resume", token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["changed"], true);
+    assert!(!pause_switch.is_paused());
+
+    let (status, body) = send(&router, Method::POST, "/tasks/other/pause", token, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(body["error"].as_str().unwrap().contains("other"), "{body}");
+}
+
+#[tokio::test]
+async fn updating_dynamic_configs() {
+    let (router, _) = create_router();
+    let token = Some(AUTH_TOKEN);
+
+    let (status, body) = send(&router, Method::GET, "/configs", token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, serde_json::json!({ "mock": { "value": 0 } }));
+
+    let update = serde_json::json!({ "value": 42 });
+    let (status, body) = send(&router, Method::PATCH, "/configs/mock", token, Some(update)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, serde_json::json!({ "value": 42 }));
+    let (status, body) = send(&router, Method::GET, "/configs/mock", token, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, serde_json::json!({ "value": 42 }));
+
+    let update = serde_json::json!({ "value": "invalid" });
+    let (status, body) = send(&router, Method::PATCH, "/configs/mock", token, Some(update)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(
+        body["error"]
+            .as_str()
+            .unwrap()
+            .contains("must be an integer"),
+        "{body}"
+    );
+
+    let (status, _) = send(&router, Method::GET, "/configs/other", token, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
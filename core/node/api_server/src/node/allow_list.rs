@@ -1,20 +1,33 @@
+use std::{collections::HashSet, sync::Arc};
+
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use zksync_config::configs::chain::DeploymentAllowlist;
 use zksync_node_framework::{
+    control::{DynamicConfig, NodeControl},
     service::StopReceiver,
     task::{Task, TaskId, TaskKind},
     wiring_layer::{WiringError, WiringLayer},
-    IntoContext,
+    FromContext, IntoContext,
 };
+use zksync_types::Address;
 use zksync_vm_executor::whitelist::SharedAllowList;
 
 use crate::tx_sender::whitelist::AllowListTask;
 
-/// Wiring layer for [`AllowListTask`] that controls deployment allow list .
+/// Wiring layer for [`AllowListTask`] that controls deployment allow list.
+///
+/// A static allow list is registered as a dynamic config in [`NodeControl`], so that it can be replaced at runtime.
 pub struct DeploymentAllowListLayer {
     pub deployment_allowlist: DeploymentAllowlist,
 }
 
+#[derive(Debug, FromContext)]
+pub struct Input {
+    #[context(default)]
+    node_control: Arc<NodeControl>,
+}
+
 #[derive(Debug, IntoContext)]
 pub struct Output {
     shared_allow_list: SharedAllowList,
@@ -24,14 +37,14 @@ pub struct Output {
 
 #[async_trait]
 impl WiringLayer for DeploymentAllowListLayer {
-    type Input = ();
+    type Input = Input;
     type Output = Output;
 
     fn layer_name(&self) -> &'static str {
         "deployment_allowlist_layer"
     }
 
-    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
         let (task, shared_list) = match self.deployment_allowlist {
             DeploymentAllowlist::Dynamic(allow_list) => {
                 let allow_list_task = AllowListTask::from_config(allow_list);
@@ -40,7 +53,14 @@ impl WiringLayer for DeploymentAllowListLayer {
             }
             DeploymentAllowlist::Static {
                 addresses: allowed_deployers,
-            } => (None, SharedAllowList::new(allowed_deployers)),
+            } => {
+                let shared = SharedAllowList::new(allowed_deployers);
+                input
+                    .node_control
+                    .register_config(Arc::new(StaticAllowList(shared.clone())))
+                    .map_err(WiringError::internal)?;
+                (None, shared)
+            }
         };
 
         Ok(Output {
@@ -64,3 +84,41 @@ impl Task for AllowListTask {
         (*self).run(stop_receiver.0).await
     }
 }
+
+/// Contents of the deployment allow list exposed as a dynamic config.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AllowListContents {
+    addresses: Vec<Address>,
+}
+
+/// Static deployment allow list that can be replaced at runtime.
+#[derive(Debug)]
+struct StaticAllowList(SharedAllowList);
+
+#[async_trait]
+impl DynamicConfig for StaticAllowList {
+    fn name(&self) -> &'static str {
+        "deployment_allowlist"
+    }
+
+    async fn current(&self) -> serde_json::Value {
+        let mut addresses: Vec<_> = self.0.writer().read().await.iter().copied().collect();
+        addresses.sort_unstable();
+        serde_json::to_value(AllowListContents { addresses })
+            .expect("failed serializing allow list")
+    }
+
+    async fn update(&self, update: serde_json::Value) -> anyhow::Result<()> {
+        let update: AllowListContents = serde_json::from_value(update)?;
+        let new_list: HashSet<_> = update.addresses.into_iter().collect();
+        let new_len = new_list.len();
+        let prev_list = std::mem::replace(&mut *self.0.writer().write().await, new_list);
+        tracing::info!(
+            prev_len = prev_list.len(),
+            new_len,
+            "Replaced deployment allow list"
+        );
+        Ok(())
+    }
+}
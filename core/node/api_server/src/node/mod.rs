@@ -3,6 +3,7 @@ pub use self::{
     caches::MempoolCacheLayer,
     healtcheck_server::HealthCheckLayer,
     server::{Web3ServerLayer, Web3ServerOptionalConfig},
+    tx_policy::TxPolicyLayer,
     tx_sender::{PostgresStorageCachesConfig, TxSenderLayer},
     tx_sink::{MasterPoolSinkLayer, ProxySinkLayer, WhitelistedMasterPoolSinkLayer},
 };
@@ -12,5 +13,6 @@ mod caches;
 mod healtcheck_server;
 mod resources;
 mod server;
+mod tx_policy;
 mod tx_sender;
 mod tx_sink;
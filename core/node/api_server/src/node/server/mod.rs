@@ -1,6 +1,9 @@
 use std::{collections::HashSet, num::NonZeroU32, sync::Arc, time::Duration};
 
-use zksync_config::configs::api::{MaxResponseSize, Namespace};
+use zksync_config::{
+    configs::api::{MaxResponseSize, Namespace},
+    ObjectStoreConfig,
+};
 use zksync_dal::node::{PoolResource, ReplicaPool};
 use zksync_health_check::AppHealthCheck;
 use zksync_node_framework::{
@@ -9,6 +12,7 @@ use zksync_node_framework::{
     wiring_layer::{WiringError, WiringLayer},
     FromContext, IntoContext,
 };
+use zksync_object_store::ObjectStoreFactory;
 use zksync_shared_resources::{
     api::{BridgeAddressesHandle, SyncState},
     contracts::{L1ChainContractsResource, L1EcosystemContractsResource, L2ContractsResource},
@@ -48,6 +52,8 @@ pub struct Web3ServerOptionalConfig {
     pub polling_interval: Duration,
     // Used by the external node.
     pub pruning_info_refresh_interval: Duration,
+    /// Object store with archives of pruned L2 blocks. Used by the external node.
+    pub pruning_archive: Option<ObjectStoreConfig>,
 }
 
 impl Web3ServerOptionalConfig {
@@ -248,6 +254,10 @@ impl WiringLayer for Web3ServerLayer {
         if let Some(main_node_client) = input.main_node_client {
             api_builder = api_builder.with_l2_l1_log_proof_handler(main_node_client);
         }
+        if let Some(config) = self.optional_config.pruning_archive.clone() {
+            let object_store = ObjectStoreFactory::new(config).create_store().await?;
+            api_builder = api_builder.with_pruned_data_archive(object_store);
+        }
         api_builder = self.optional_config.apply(api_builder);
 
         let server = api_builder.build()?;
@@ -0,0 +1,81 @@
+use async_trait::async_trait;
+use zksync_config::configs::chain::TxPolicyConfig;
+use zksync_node_framework::{
+    service::StopReceiver,
+    task::{Task, TaskId, TaskKind},
+    wiring_layer::{WiringError, WiringLayer},
+    IntoContext,
+};
+use zksync_vm_executor::tx_policy::{
+    AddressDenyListPolicy, ContractRateLimitPolicy, DeniedSelector, SelectorDenyListPolicy,
+    TxPolicyEngine,
+};
+
+use crate::tx_sender::tx_policy::ScreeningListTask;
+
+/// Wiring layer for the [`TxPolicyEngine`] shared by the API server and the state keeper.
+/// If the screening list is configured, also adds [`ScreeningListTask`] to keep it updated.
+pub struct TxPolicyLayer {
+    pub config: TxPolicyConfig,
+}
+
+#[derive(Debug, IntoContext)]
+pub struct Output {
+    tx_policy: TxPolicyEngine,
+    #[context(task)]
+    screening_list_task: Option<ScreeningListTask>,
+}
+
+#[async_trait]
+impl WiringLayer for TxPolicyLayer {
+    type Input = ();
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "tx_policy_layer"
+    }
+
+    async fn wire(self, _input: Self::Input) -> Result<Self::Output, WiringError> {
+        let config = self.config;
+        let screening_list_task = config.screening_list.map(ScreeningListTask::from_config);
+
+        let mut address_policy = AddressDenyListPolicy::new(config.denied_addresses);
+        if let Some(task) = &screening_list_task {
+            address_policy = address_policy.with_screening_list(task.shared());
+        }
+        let denied_selectors = config
+            .denied_selectors
+            .iter()
+            .map(|selector| selector.parse::<DeniedSelector>())
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(|err| WiringError::Configuration(format!("{err:#}")))?;
+
+        let tx_policy = TxPolicyEngine::default()
+            .with_policy(address_policy)
+            .with_policy(SelectorDenyListPolicy::new(denied_selectors))
+            .with_policy(ContractRateLimitPolicy::new(
+                config.contract_rate_limits.into_inner(),
+                config.rate_limit_window,
+            ));
+
+        Ok(Output {
+            tx_policy,
+            screening_list_task,
+        })
+    }
+}
+
+#[async_trait]
+impl Task for ScreeningListTask {
+    fn id(&self) -> TaskId {
+        "screening_list_task".into()
+    }
+
+    fn kind(&self) -> TaskKind {
+        TaskKind::Task
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
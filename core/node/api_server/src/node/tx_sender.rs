@@ -1,6 +1,7 @@
 use std::{sync::Arc, time::Duration};
 
 use tokio::sync::RwLock;
+use zksync_circuit_breaker::CircuitBreakers;
 use zksync_config::configs::chain::TimestampAsserterConfig;
 use zksync_dal::node::{PoolResource, ReplicaPool};
 use zksync_health_check::AppHealthCheck;
@@ -15,7 +16,7 @@ use zksync_object_store::ObjectStore;
 use zksync_shared_resources::contracts::L2ContractsResource;
 use zksync_state::{PostgresStorageCaches, PostgresStorageCachesTask};
 use zksync_types::{vm::FastVmMode, AccountTreeId, Address};
-use zksync_vm_executor::node::ApiTransactionFilter;
+use zksync_vm_executor::{node::ApiTransactionFilter, tx_policy::TxPolicyEngine};
 use zksync_web3_decl::{
     client::{DynClient, L2},
     jsonrpsee,
@@ -47,6 +48,7 @@ pub struct PostgresStorageCachesConfig {
 /// - `PoolResource<ReplicaPool>`
 /// - `ConditionalSealerResource` (optional)
 /// - `FeeInputResource`
+/// - `CircuitBreakersResource` (pauses transaction acceptance)
 ///
 /// ## Adds resources
 ///
@@ -75,8 +77,11 @@ pub struct Input {
     fee_input: ApiFeeInputResource,
     main_node_client: Option<Box<DynClient<L2>>>,
     transaction_filter: Option<ApiTransactionFilter>,
+    tx_policy: Option<TxPolicyEngine>,
     l2_contracts: L2ContractsResource,
     core_object_store: Option<Arc<dyn ObjectStore>>,
+    #[context(default)]
+    circuit_breakers: Arc<CircuitBreakers>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -191,10 +196,14 @@ impl WiringLayer for TxSenderLayer {
         }
 
         // Build `TxSender`.
-        let mut tx_sender = TxSenderBuilder::new(config, replica_pool, tx_sink);
+        let mut tx_sender = TxSenderBuilder::new(config, replica_pool, tx_sink)
+            .with_tx_acceptance(input.circuit_breakers.tx_acceptance());
         if let Some(transaction_filter) = transaction_filter {
             tx_sender = tx_sender.with_transaction_filter(transaction_filter);
         }
+        if let Some(tx_policy) = input.tx_policy {
+            tx_sender = tx_sender.with_tx_policy(tx_policy);
+        }
 
         // Add the task for updating the whitelisted tokens for the AA cache.
         let whitelisted_tokens_for_aa_update_task = if self.whitelisted_tokens_for_aa_cache {
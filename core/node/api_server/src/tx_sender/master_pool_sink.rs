@@ -7,7 +7,10 @@ use tokio::sync::Mutex;
 use zksync_dal::{transactions_dal::L2TxSubmissionResult, ConnectionPool, Core, CoreDal, DalError};
 use zksync_multivm::interface::tracer::ValidationTraces;
 use zksync_shared_metrics::{TxStage, APP_METRICS};
-use zksync_types::{l2::L2Tx, Address, Nonce, H256};
+use zksync_types::{
+    l2::{bundle::L2TxBundle, L2Tx},
+    Address, Nonce, H256,
+};
 
 use super::{tx_sink::TxSink, SubmitTxError};
 use crate::{execution_sandbox::SandboxExecutionOutput, web3::metrics::API_METRICS};
@@ -105,4 +108,57 @@ impl TxSink for MasterPoolSink {
 
         Ok(result)
     }
+    async fn submit_bundle(
+        &self,
+        bundle: &L2TxBundle,
+        members: Vec<(SandboxExecutionOutput, ValidationTraces)>,
+    ) -> Result<L2TxSubmissionResult, SubmitTxError> {
+        let mut lock = self.inflight_requests.lock().await;
+        let mut _guards = Vec::with_capacity(bundle.len());
+        for tx in bundle.txs() {
+            let address_and_nonce = (tx.initiator_account(), tx.nonce());
+            match lock.entry(address_and_nonce) {
+                Entry::Occupied(entry) => {
+                    let submission_res_handle = if entry.get() == &tx.hash() {
+                        L2TxSubmissionResult::Duplicate
+                    } else {
+                        L2TxSubmissionResult::InsertionInProgress
+                    };
+                    APP_METRICS.processed_txs[&TxStage::Mempool(submission_res_handle)].inc();
+                    // Guards acquired for the previous bundle members are released on drop.
+                    return Ok(submission_res_handle);
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(tx.hash());
+                    API_METRICS.inflight_tx_submissions.inc_by(1);
+                    _guards.push(Guard::new(
+                        Arc::downgrade(&self.inflight_requests),
+                        address_and_nonce,
+                    ));
+                }
+            }
+        }
+        drop(lock);
+
+        let members = members
+            .into_iter()
+            .map(|(output, traces)| (output.metrics, traces))
+            .collect();
+        let mut connection = self
+            .master_pool
+            .connection_tagged("api")
+            .await
+            .map_err(DalError::generalize)?;
+        let result = connection
+            .transactions_dal()
+            .insert_transaction_bundle(bundle, members)
+            .await
+            .inspect(|submission_res_handle| {
+                APP_METRICS.processed_txs[&TxStage::Mempool(*submission_res_handle)]
+                    .inc_by(bundle.len() as u64);
+            })
+            .map_err(DalError::generalize)?;
+
+        Ok(result)
+    }
 }
@@ -1,17 +1,20 @@
 //! Helper module to submit transactions into the ZKsync Network.
 
 use std::{
+    collections::HashSet,
+    slice,
     sync::{
         atomic::{AtomicUsize, Ordering},
         Arc,
     },
-    time::Duration,
+    time::{Duration, SystemTime},
 };
 
 use anyhow::Context as _;
 use async_trait::async_trait;
 use serde::Serialize;
 use tokio::sync::RwLock;
+use zksync_circuit_breaker::TxAcceptance;
 use zksync_config::configs::{api::Web3JsonRpcConfig, chain::StateKeeperConfig};
 use zksync_dal::{
     transactions_dal::L2TxSubmissionResult, Connection, ConnectionPool, Core, CoreDal,
@@ -19,8 +22,8 @@ use zksync_dal::{
 use zksync_health_check::{CheckHealth, Health, HealthStatus};
 use zksync_multivm::{
     interface::{
-        tracer::TimestampAsserterParams as TracerTimestampAsserterParams, OneshotTracingParams,
-        TransactionExecutionMetrics,
+        tracer::{TimestampAsserterParams as TracerTimestampAsserterParams, ValidationTraces},
+        OneshotTracingParams, TransactionExecutionMetrics,
     },
     utils::{
         derive_base_fee_and_gas_per_pubdata, get_max_batch_gas_limit, get_max_new_factory_deps,
@@ -33,15 +36,17 @@ use zksync_types::{
     api::state_override::StateOverride,
     fee_model::BatchFeeInput,
     get_intrinsic_constants, h256_to_u256,
-    l2::{error::TxCheckError::TxDuplication, L2Tx},
+    l2::{bundle::L2TxBundle, error::TxCheckError::TxDuplication, L2Tx},
     transaction_request::CallOverrides,
     utils::storage_key_for_eth_balance,
     vm::FastVmMode,
     AccountTreeId, Address, L2ChainId, Nonce, ProtocolVersionId, Transaction, H160, H256, U256,
 };
+use zksync_vlog::opentelemetry::tx_lifecycle::{TxStage, TxTrace};
 use zksync_vm_executor::{
     interface::TransactionFilter,
     oneshot::{CallOrExecute, EstimateGas, MultiVmBaseSystemContracts, OneshotEnvParameters},
+    tx_policy::{TxPolicyEngine, TxPolicyStage},
 };
 
 pub(super) use self::{gas_estimation::BinarySearchKind, result::SubmitTxError};
@@ -57,6 +62,7 @@ pub mod proxy;
 mod result;
 #[cfg(test)]
 pub(crate) mod tests;
+pub mod tx_policy;
 pub mod tx_sink;
 pub mod whitelist;
 
@@ -179,8 +185,12 @@ pub struct TxSenderBuilder {
     tx_sink: Arc<dyn TxSink>,
     /// Transaction filter that can be used to reject transactions.
     transaction_filter: Option<Arc<dyn TransactionFilter>>,
+    /// Policies applied to submitted transactions.
+    tx_policy: TxPolicyEngine,
     /// Cache for tokens that are white-listed for AA.
     whitelisted_tokens_for_aa_cache: Option<Arc<RwLock<Vec<Address>>>>,
+    /// Switch pausing transaction acceptance, controlled by circuit breakers.
+    tx_acceptance: TxAcceptance,
 }
 
 impl TxSenderBuilder {
@@ -194,7 +204,9 @@ impl TxSenderBuilder {
             replica_connection_pool,
             tx_sink,
             transaction_filter: None,
+            tx_policy: TxPolicyEngine::default(),
             whitelisted_tokens_for_aa_cache: None,
+            tx_acceptance: TxAcceptance::default(),
         }
     }
 
@@ -203,11 +215,21 @@ impl TxSenderBuilder {
         self
     }
 
+    pub fn with_tx_policy(mut self, tx_policy: TxPolicyEngine) -> Self {
+        self.tx_policy = tx_policy;
+        self
+    }
+
     pub fn with_whitelisted_tokens_for_aa(mut self, cache: Arc<RwLock<Vec<Address>>>) -> Self {
         self.whitelisted_tokens_for_aa_cache = Some(cache);
         self
     }
 
+    pub fn with_tx_acceptance(mut self, tx_acceptance: TxAcceptance) -> Self {
+        self.tx_acceptance = tx_acceptance;
+        self
+    }
+
     pub fn build(
         self,
         batch_fee_input_provider: Arc<dyn BatchFeeModelInputProvider>,
@@ -245,6 +267,8 @@ impl TxSenderBuilder {
             vm_concurrency_limiter,
             whitelisted_tokens_for_aa_cache,
             transaction_filter,
+            tx_policy: self.tx_policy,
+            tx_acceptance: self.tx_acceptance,
             executor,
         }))
     }
@@ -317,6 +341,10 @@ pub struct TxSenderInner {
     pub(super) whitelisted_tokens_for_aa_cache: Arc<RwLock<Vec<Address>>>,
     /// Batch sealer used to check whether transaction can be executed by the sequencer.
     pub(super) transaction_filter: Arc<dyn TransactionFilter>,
+    /// Policies applied to submitted transactions.
+    pub(super) tx_policy: TxPolicyEngine,
+    /// Switch pausing transaction acceptance, controlled by circuit breakers.
+    pub(super) tx_acceptance: TxAcceptance,
     pub(super) executor: SandboxExecutor,
 }
 
@@ -400,15 +428,197 @@ impl TxSender {
             .context("failed acquiring connection to replica DB")
     }
 
+    fn ensure_tx_acceptance_not_paused(&self) -> Result<(), SubmitTxError> {
+        if self.0.tx_acceptance.is_paused() {
+            return Err(SubmitTxError::TxAcceptancePaused);
+        }
+        Ok(())
+    }
+
     #[tracing::instrument(level = "debug", name = "submit_tx", skip_all, fields(tx.hash = ?tx.hash()))]
     pub(crate) async fn submit_tx(
         &self,
         tx: L2Tx,
         block_args: BlockArgs,
     ) -> Result<SandboxExecutionOutput, SubmitTxError> {
+        self.ensure_tx_acceptance_not_paused()?;
+        let started_at = SystemTime::now();
+        let tx_hash = tx.hash();
+        let (execution_output, validation_traces) =
+            self.execute_and_validate_tx(&tx, block_args).await?;
+        let mut stage_latency =
+            SANDBOX_METRICS.start_tx_submit_stage(tx_hash, SubmitTxStage::DbInsert);
+        self.ensure_tx_executable(&tx.clone().into(), execution_output.metrics, true)
+            .await?;
+        self.record_tx_policy_submission(slice::from_ref(&tx))
+            .await?;
+
+        let insertion_started_at = SystemTime::now();
+        let submission_res_handle = self
+            .0
+            .tx_sink
+            .submit_tx(&tx, &execution_output, validation_traces)
+            .await;
+        if !Self::is_accepted(&submission_res_handle) {
+            self.revert_tx_policy_submission(slice::from_ref(&tx)).await;
+        }
+        let submission_res_handle = submission_res_handle?;
+        Self::trace_submission(&tx, submission_res_handle, started_at, insertion_started_at);
+
+        match submission_res_handle {
+            L2TxSubmissionResult::AlreadyExecuted => {
+                let initiator_account = tx.initiator_account();
+                let Nonce(expected_nonce) = self
+                    .get_expected_nonce(initiator_account)
+                    .await
+                    .with_context(|| {
+                        format!("failed getting expected nonce for {initiator_account:?}")
+                    })?;
+                Err(SubmitTxError::NonceIsTooLow(
+                    expected_nonce,
+                    expected_nonce + self.0.sender_config.max_nonce_ahead,
+                    tx.nonce().0,
+                ))
+            }
+            L2TxSubmissionResult::Duplicate => {
+                Err(SubmitTxError::IncorrectTx(TxDuplication(tx.hash())))
+            }
+            L2TxSubmissionResult::InsertionInProgress => Err(SubmitTxError::InsertionInProgress),
+            L2TxSubmissionResult::Conflict => Err(SubmitTxError::Internal(anyhow::anyhow!(
+                "tx sink reported a bundle conflict for a standalone transaction"
+            ))),
+            L2TxSubmissionResult::Proxied => {
+                stage_latency.set_stage(SubmitTxStage::TxProxy);
+                stage_latency.observe();
+                Ok(execution_output)
+            }
+            L2TxSubmissionResult::Added | L2TxSubmissionResult::Replaced => {
+                stage_latency.observe();
+                Ok(execution_output)
+            }
+        }
+    }
+
+    /// Submits an atomic bundle of transactions to the mempool. Each bundle transaction is validated and executed
+    /// in the sandbox independently, against the state of the provided block.
+    #[tracing::instrument(level = "debug", name = "submit_bundle", skip_all, fields(bundle.len = txs.len()))]
+    pub(crate) async fn submit_bundle(
+        &self,
+        txs: Vec<L2Tx>,
+        block_args: BlockArgs,
+    ) -> Result<L2TxBundle, SubmitTxError> {
+        self.ensure_tx_acceptance_not_paused()?;
+        let started_at = SystemTime::now();
+        if txs.is_empty() {
+            return Err(SubmitTxError::InvalidBundle("bundle is empty".to_owned()));
+        }
+        if txs.len() > L2TxBundle::MAX_LEN {
+            return Err(SubmitTxError::InvalidBundle(format!(
+                "bundle contains {} transactions, while at most {} are allowed",
+                txs.len(),
+                L2TxBundle::MAX_LEN
+            )));
+        }
+        let mut seen_nonces = HashSet::with_capacity(txs.len());
+        for tx in &txs {
+            if !seen_nonces.insert((tx.initiator_account(), tx.nonce())) {
+                return Err(SubmitTxError::InvalidBundle(format!(
+                    "bundle contains several transactions from {:?} with nonce {}",
+                    tx.initiator_account(),
+                    tx.nonce()
+                )));
+            }
+        }
+
+        let bundle = L2TxBundle::new(txs);
+        let mut members = Vec::with_capacity(bundle.len());
+        for tx in bundle.txs() {
+            let (execution_output, validation_traces) =
+                self.execute_and_validate_tx(tx, block_args.clone()).await?;
+            self.ensure_tx_executable(&tx.clone().into(), execution_output.metrics, true)
+                .await?;
+            members.push((execution_output, validation_traces));
+        }
+        self.record_tx_policy_submission(bundle.txs()).await?;
+
+        let insertion_started_at = SystemTime::now();
+        let submission_res_handle = self.0.tx_sink.submit_bundle(&bundle, members).await;
+        if !Self::is_accepted(&submission_res_handle) {
+            self.revert_tx_policy_submission(bundle.txs()).await;
+        }
+        let submission_res_handle = submission_res_handle?;
+        match submission_res_handle {
+            L2TxSubmissionResult::AlreadyExecuted => Err(SubmitTxError::InvalidBundle(
+                "bundle contains an already executed transaction".to_owned(),
+            )),
+            L2TxSubmissionResult::Duplicate => Err(SubmitTxError::InvalidBundle(
+                "bundle contains a transaction already present in the mempool".to_owned(),
+            )),
+            L2TxSubmissionResult::Conflict => Err(SubmitTxError::InvalidBundle(
+                "bundle contains a transaction conflicting with a pending mempool transaction \
+                 with the same initiator and nonce"
+                    .to_owned(),
+            )),
+            // Bundles are never inserted if they would replace pending mempool transactions.
+            L2TxSubmissionResult::Replaced => Err(SubmitTxError::Internal(anyhow::anyhow!(
+                "tx sink reported replacing a pending transaction with a bundle"
+            ))),
+            L2TxSubmissionResult::InsertionInProgress => Err(SubmitTxError::InsertionInProgress),
+            L2TxSubmissionResult::Added | L2TxSubmissionResult::Proxied => {
+                for tx in bundle.txs() {
+                    Self::trace_submission(
+                        tx,
+                        submission_res_handle,
+                        started_at,
+                        insertion_started_at,
+                    );
+                }
+                Ok(bundle)
+            }
+        }
+    }
+
+    /// Records transaction lifecycle spans for a submitted transaction. Only transactions accepted to the mempool
+    /// (or proxied to the main node) are traced.
+    fn trace_submission(
+        tx: &L2Tx,
+        submission_result: L2TxSubmissionResult,
+        started_at: SystemTime,
+        insertion_started_at: SystemTime,
+    ) {
+        let tx_trace = TxTrace::new(tx.hash().0);
+        let stage = match submission_result {
+            L2TxSubmissionResult::Added | L2TxSubmissionResult::Replaced => {
+                tx_trace
+                    .start_at(TxStage::MempoolInsertion, insertion_started_at)
+                    .with_attribute("result", submission_result.to_string())
+                    .finish();
+                TxStage::Submission
+            }
+            // The root span of the trace will be recorded by the main node.
+            L2TxSubmissionResult::Proxied => TxStage::ProxiedSubmission,
+            L2TxSubmissionResult::AlreadyExecuted
+            | L2TxSubmissionResult::Duplicate
+            | L2TxSubmissionResult::InsertionInProgress
+            | L2TxSubmissionResult::Conflict => return,
+        };
+        tx_trace
+            .start_at(stage, started_at)
+            .with_attribute("tx.initiator", format!("{:?}", tx.initiator_account()))
+            .with_attribute("tx.nonce", i64::from(tx.nonce().0))
+            .finish();
+    }
+
+    /// Validates the transaction, executes it in the sandbox and runs account validation on it.
+    async fn execute_and_validate_tx(
+        &self,
+        tx: &L2Tx,
+        block_args: BlockArgs,
+    ) -> Result<(SandboxExecutionOutput, ValidationTraces), SubmitTxError> {
         let tx_hash = tx.hash();
         let stage_latency = SANDBOX_METRICS.start_tx_submit_stage(tx_hash, SubmitTxStage::Validate);
-        self.validate_tx(&tx, block_args.protocol_version()).await?;
+        self.validate_tx(tx, block_args.protocol_version()).await?;
+        self.check_tx_policy(tx).await?;
         stage_latency.observe();
 
         let stage_latency = SANDBOX_METRICS.start_tx_submit_stage(tx_hash, SubmitTxStage::DryRun);
@@ -456,53 +666,59 @@ impl TxSender {
             .await;
         stage_latency.observe();
 
-        if let Err(err) = validation_result {
-            return Err(err.into());
-        }
+        let validation_traces = validation_result?;
         if !execution_output.are_published_bytecodes_ok {
             return Err(SubmitTxError::FailedToPublishCompressedBytecodes);
         }
-        let mut stage_latency =
-            SANDBOX_METRICS.start_tx_submit_stage(tx_hash, SubmitTxStage::DbInsert);
-        self.ensure_tx_executable(&tx.clone().into(), execution_output.metrics, true)
-            .await?;
+        Ok((execution_output, validation_traces))
+    }
 
-        let validation_traces = validation_result?;
-        let submission_res_handle = self
-            .0
-            .tx_sink
-            .submit_tx(&tx, &execution_output, validation_traces)
-            .await?;
+    async fn check_tx_policy(&self, tx: &L2Tx) -> Result<(), SubmitTxError> {
+        let tx_policy = &self.0.tx_policy;
+        if tx_policy.is_empty() {
+            return Ok(());
+        }
+        tx_policy
+            .check_transaction(&tx.clone().into(), TxPolicyStage::Submission)
+            .await
+            .map_err(|violation| SubmitTxError::PolicyViolation(violation.to_string()))
+    }
 
-        match submission_res_handle {
-            L2TxSubmissionResult::AlreadyExecuted => {
-                let initiator_account = tx.initiator_account();
-                let Nonce(expected_nonce) = self
-                    .get_expected_nonce(initiator_account)
-                    .await
-                    .with_context(|| {
-                        format!("failed getting expected nonce for {initiator_account:?}")
-                    })?;
-                Err(SubmitTxError::NonceIsTooLow(
-                    expected_nonce,
-                    expected_nonce + self.0.sender_config.max_nonce_ahead,
-                    tx.nonce().0,
-                ))
-            }
-            L2TxSubmissionResult::Duplicate => {
-                Err(SubmitTxError::IncorrectTx(TxDuplication(tx.hash())))
-            }
-            L2TxSubmissionResult::InsertionInProgress => Err(SubmitTxError::InsertionInProgress),
-            L2TxSubmissionResult::Proxied => {
-                stage_latency.set_stage(SubmitTxStage::TxProxy);
-                stage_latency.observe();
-                Ok(execution_output)
-            }
-            L2TxSubmissionResult::Added | L2TxSubmissionResult::Replaced => {
-                stage_latency.observe();
-                Ok(execution_output)
-            }
+    /// Records fully validated transactions in stateful tx policies (e.g., rate limits). Must be called
+    /// only after all other submission checks have passed, so that rejected transactions don't affect policies.
+    /// Transactions are recorded atomically, so a bundle either consumes policy limits as a whole or not at all.
+    /// If the transactions are not accepted by the tx sink, the record must be reverted
+    /// using [`Self::revert_tx_policy_submission()`].
+    async fn record_tx_policy_submission(&self, txs: &[L2Tx]) -> Result<(), SubmitTxError> {
+        let tx_policy = &self.0.tx_policy;
+        if tx_policy.is_empty() {
+            return Ok(());
+        }
+        let txs: Vec<Transaction> = txs.iter().cloned().map(Into::into).collect();
+        tx_policy
+            .record_submission(&txs)
+            .await
+            .map_err(|violation| SubmitTxError::PolicyViolation(violation.to_string()))
+    }
+
+    async fn revert_tx_policy_submission(&self, txs: &[L2Tx]) {
+        let tx_policy = &self.0.tx_policy;
+        if tx_policy.is_empty() {
+            return;
         }
+        let txs: Vec<Transaction> = txs.iter().cloned().map(Into::into).collect();
+        tx_policy.revert_submission(&txs).await;
+    }
+
+    /// Checks whether the tx sink has accepted submitted transactions into the mempool (or proxied them
+    /// to the main node). In particular, a bundle conflicting with pending transactions is not accepted.
+    fn is_accepted(submission_result: &Result<L2TxSubmissionResult, SubmitTxError>) -> bool {
+        matches!(
+            submission_result,
+            Ok(L2TxSubmissionResult::Added
+                | L2TxSubmissionResult::Replaced
+                | L2TxSubmissionResult::Proxied)
+        )
     }
 
     async fn validate_tx(
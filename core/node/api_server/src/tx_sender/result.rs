@@ -71,6 +71,14 @@ pub enum SubmitTxError {
     Internal(#[from] anyhow::Error),
     #[error("contract deployer address {0} is not in the allow list")]
     DeployerNotInAllowList(Address),
+    #[error("transaction rejected by policy: {0}")]
+    PolicyViolation(String),
+    #[error("transaction bundles are not supported by this node")]
+    BundlesNotSupported,
+    #[error("invalid transaction bundle: {0}")]
+    InvalidBundle(String),
+    #[error("transaction acceptance is temporarily paused")]
+    TxAcceptancePaused,
 }
 
 impl SubmitTxError {
@@ -102,6 +110,10 @@ impl SubmitTxError {
             Self::ProxyError(_) => "proxy-error",
             Self::Internal(_) => "internal",
             Self::DeployerNotInAllowList(_) => "deployer-not-in-allow-list",
+            Self::PolicyViolation(_) => "policy-violation",
+            Self::BundlesNotSupported => "bundles-not-supported",
+            Self::InvalidBundle(_) => "invalid-bundle",
+            Self::TxAcceptancePaused => "tx-acceptance-paused",
         }
     }
 
@@ -1,14 +1,27 @@
 //! Tests for sending raw transactions.
 
-use std::ops::Range;
+use std::{
+    collections::{HashMap, HashSet},
+    num::NonZeroU32,
+    ops::Range,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 use assert_matches::assert_matches;
 use chrono::NaiveDateTime;
+use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
 use test_casing::test_casing;
-use zksync_multivm::interface::{tracer::ValidationTraces, ExecutionResult};
+use zksync_circuit_breaker::{
+    CircuitBreaker, CircuitBreakerAction, CircuitBreakerError, CircuitBreakers,
+};
+use zksync_multivm::interface::{tracer::ValidationTraces, ExecutionResult, Halt};
 use zksync_node_fee_model::{BatchFeeModelInputProvider, MockBatchFeeParamsProvider};
 use zksync_node_test_utils::create_l2_transaction;
 use zksync_test_contracts::Account;
+use zksync_vlog::opentelemetry::tx_lifecycle::{self, TxTrace};
+use zksync_vm_executor::tx_policy::{
+    AddressDenyListPolicy, ContractRateLimitPolicy, ScreeningList,
+};
 
 use super::*;
 use crate::testonly::{StateBuilder, TestAccount};
@@ -66,6 +79,73 @@ async fn submitting_tx_requires_one_connection() {
     assert!(storage_tx.timestamp_asserter_range_start.is_none());
 }
 
+#[tokio::test]
+async fn submitting_tx_records_lifecycle_spans() {
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    opentelemetry::global::set_tracer_provider(provider);
+    tx_lifecycle::enable();
+
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+
+    let l2_chain_id = L2ChainId::default();
+    let fee_params_provider: &dyn BatchFeeModelInputProvider =
+        &MockBatchFeeParamsProvider::default();
+    let fee_input = fee_params_provider.get_batch_fee_input().await.unwrap();
+    let (base_fee, gas_per_pubdata) =
+        derive_base_fee_and_gas_per_pubdata(fee_input, ProtocolVersionId::latest().into());
+    let tx = create_l2_transaction(base_fee, gas_per_pubdata);
+    let tx_hash = tx.hash();
+    StateBuilder::default()
+        .with_balance(tx.initiator_account(), u64::MAX.into())
+        .apply(storage)
+        .await;
+
+    let mut tx_executor = MockOneshotExecutor::default();
+    tx_executor.set_tx_responses(|_, _| ExecutionResult::Success { output: vec![] });
+    let tx_executor = SandboxExecutor::mock(tx_executor).await;
+    let (tx_sender, _) = create_test_tx_sender(pool.clone(), l2_chain_id, tx_executor).await;
+    let block_args = pending_block_args(&tx_sender).await;
+    tx_sender.submit_tx(tx, block_args).await.unwrap();
+
+    // Spans from other tests may be exported as well, so we only consider the trace of the submitted transaction.
+    let trace_id = TxTrace::new(tx_hash.0).trace_id();
+    let spans: Vec<_> = exporter
+        .get_finished_spans()
+        .unwrap()
+        .into_iter()
+        .filter(|span| span.span_context.trace_id() == trace_id)
+        .collect();
+    let span_names: HashSet<_> = spans.iter().map(|span| span.name.as_ref()).collect();
+    assert_eq!(
+        span_names,
+        HashSet::from(["tx_submission", "tx_mempool_insertion"])
+    );
+
+    let root_span = spans
+        .iter()
+        .find(|span| span.name == "tx_submission")
+        .unwrap();
+    let insertion_span = spans
+        .iter()
+        .find(|span| span.name == "tx_mempool_insertion")
+        .unwrap();
+    assert_eq!(
+        insertion_span.parent_span_id,
+        root_span.span_context.span_id()
+    );
+    assert!(root_span.start_time <= insertion_span.start_time);
+    assert!(insertion_span.end_time <= root_span.end_time);
+    let expected_attribute = opentelemetry::KeyValue::new("tx.hash", format!("{tx_hash:?}"));
+    assert!(root_span.attributes.contains(&expected_attribute));
+}
+
 #[tokio::test]
 async fn nonce_validation_errors() {
     let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
@@ -116,6 +196,113 @@ async fn nonce_validation_errors() {
     );
 }
 
+#[tokio::test]
+async fn tx_policy_violations() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let l2_chain_id = L2ChainId::default();
+    let tx_executor = SandboxExecutor::mock(MockOneshotExecutor::default()).await;
+    let (mut tx_sender, _) = create_test_tx_sender(pool, l2_chain_id, tx_executor).await;
+    let tx = create_l2_transaction(55, 555);
+    tx_sender.check_tx_policy(&tx).await.unwrap();
+
+    let screening_list = ScreeningList::default();
+    let tx_policy = TxPolicyEngine::default().with_policy(
+        AddressDenyListPolicy::new(HashSet::new()).with_screening_list(screening_list.clone()),
+    );
+    Arc::get_mut(&mut tx_sender.0).unwrap().tx_policy = tx_policy;
+    tx_sender.check_tx_policy(&tx).await.unwrap();
+
+    screening_list
+        .replace(HashSet::from([tx.initiator_account()]))
+        .await;
+    let err = tx_sender.check_tx_policy(&tx).await.unwrap_err();
+    assert_matches!(&err, SubmitTxError::PolicyViolation(msg) if msg.contains("address_deny_list"));
+    assert_eq!(err.prom_error_code(), "policy-violation");
+}
+
+#[tokio::test]
+async fn rate_limit_is_not_consumed_by_rejected_transactions() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+
+    let l2_chain_id = L2ChainId::default();
+    let fee_params_provider: &dyn BatchFeeModelInputProvider =
+        &MockBatchFeeParamsProvider::default();
+    let fee_input = fee_params_provider.get_batch_fee_input().await.unwrap();
+    let (base_fee, gas_per_pubdata) =
+        derive_base_fee_and_gas_per_pubdata(fee_input, ProtocolVersionId::latest().into());
+    let contract = Address::repeat_byte(0x11);
+    let txs: Vec<_> = (0..4)
+        .map(|_| {
+            let mut tx = create_l2_transaction(base_fee, gas_per_pubdata);
+            tx.execute.contract_address = Some(contract);
+            tx
+        })
+        .collect();
+    let mut state = StateBuilder::default();
+    for tx in &txs {
+        state = state.with_balance(tx.initiator_account(), u64::MAX.into());
+    }
+    state.apply(storage).await;
+
+    let failing_tx_hash = txs[0].hash();
+    let mut tx_executor = MockOneshotExecutor::default();
+    tx_executor.set_tx_responses(move |tx, _| {
+        if tx.hash() == failing_tx_hash {
+            ExecutionResult::Halt {
+                reason: Halt::FromIsNotAnAccount,
+            }
+        } else {
+            ExecutionResult::Success { output: vec![] }
+        }
+    });
+    let tx_executor = SandboxExecutor::mock(tx_executor).await;
+    let (mut tx_sender, _) = create_test_tx_sender(pool, l2_chain_id, tx_executor).await;
+    let tx_policy = TxPolicyEngine::default().with_policy(ContractRateLimitPolicy::new(
+        HashMap::from([(contract, NonZeroU32::new(2).unwrap())]),
+        Duration::from_secs(60),
+    ));
+    Arc::get_mut(&mut tx_sender.0).unwrap().tx_policy = tx_policy;
+    let block_args = pending_block_args(&tx_sender).await;
+
+    // The transaction passes the rate limit check, but is rejected by sandbox validation.
+    let err = tx_sender
+        .submit_tx(txs[0].clone(), block_args.clone())
+        .await
+        .unwrap_err();
+    assert!(!matches!(err, SubmitTxError::PolicyViolation(_)), "{err:?}");
+    tx_sender
+        .submit_tx(txs[1].clone(), block_args.clone())
+        .await
+        .unwrap();
+    // The duplicate transaction passes all checks, but is rejected by the tx sink.
+    let err = tx_sender
+        .submit_tx(txs[1].clone(), block_args.clone())
+        .await
+        .unwrap_err();
+    assert_matches!(err, SubmitTxError::IncorrectTx(_));
+
+    // The bundle doesn't fit into the limit as a whole, so none of its transactions must consume the limit.
+    let err = tx_sender
+        .submit_bundle(txs[2..].to_vec(), block_args.clone())
+        .await
+        .unwrap_err();
+    assert_matches!(&err, SubmitTxError::PolicyViolation(msg) if msg.contains("contract_rate_limit"));
+
+    tx_sender
+        .submit_tx(txs[2].clone(), block_args.clone())
+        .await
+        .unwrap();
+    let err = tx_sender
+        .submit_tx(txs[3].clone(), block_args)
+        .await
+        .unwrap_err();
+    assert_matches!(&err, SubmitTxError::PolicyViolation(msg) if msg.contains("contract_rate_limit"));
+}
+
 #[tokio::test]
 async fn fee_validation_errors() {
     let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
@@ -239,6 +426,197 @@ async fn sending_transfer_with_incorrect_signature() {
     assert_matches!(err, SubmitTxError::ValidationFailed(_));
 }
 
+#[tokio::test]
+async fn sending_transaction_bundle() {
+    let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+    let tx_sender = create_real_tx_sender(pool.clone()).await;
+    let block_args = pending_block_args(&tx_sender).await;
+    let mut alice = Account::random();
+    let mut bob = Account::random();
+    let mut carol = Account::random();
+
+    let storage = tx_sender.acquire_replica_connection().await.unwrap();
+    StateBuilder::default()
+        .with_balance(alice.address(), u64::MAX.into())
+        .with_balance(bob.address(), u64::MAX.into())
+        .with_balance(carol.address(), u64::MAX.into())
+        .apply(storage)
+        .await;
+
+    let err = tx_sender
+        .submit_bundle(vec![], block_args.clone())
+        .await
+        .unwrap_err();
+    assert_matches!(err, SubmitTxError::InvalidBundle(_));
+
+    let transfer = alice.create_transfer(1_000_000_000.into());
+    let err = tx_sender
+        .submit_bundle(vec![transfer.clone(), transfer], block_args.clone())
+        .await
+        .unwrap_err();
+    assert_matches!(err, SubmitTxError::InvalidBundle(msg) if msg.contains("nonce"));
+
+    // A bundle with an invalid member must be rejected as a whole.
+    let valid_transfer = bob.create_transfer(1_000_000_000.into());
+    let mut invalid_transfer = carol.create_transfer(1_000_000_000.into());
+    invalid_transfer.execute.value = 1.into(); // This should invalidate tx signature
+    let err = tx_sender
+        .submit_bundle(
+            vec![valid_transfer.clone(), invalid_transfer],
+            block_args.clone(),
+        )
+        .await
+        .unwrap_err();
+    assert_matches!(err, SubmitTxError::ValidationFailed(_));
+    let mut storage = pool.connection().await.unwrap();
+    let storage_tx = storage
+        .transactions_dal()
+        .get_storage_tx_by_hash(valid_transfer.hash())
+        .await
+        .unwrap();
+    assert!(storage_tx.is_none());
+    drop(storage);
+
+    let mut alice = Account::random();
+    let storage = tx_sender.acquire_replica_connection().await.unwrap();
+    StateBuilder::default()
+        .with_balance(alice.address(), u64::MAX.into())
+        .apply(storage)
+        .await;
+    let txs = vec![
+        alice.create_transfer(1_000_000_000.into()),
+        alice.create_transfer(1_000_000_000.into()),
+        valid_transfer,
+    ];
+    let tx_hashes: Vec<_> = txs.iter().map(L2Tx::hash).collect();
+    let bundle = tx_sender
+        .submit_bundle(txs, block_args.clone())
+        .await
+        .unwrap();
+    assert_eq!(bundle.len(), 3);
+
+    let mut storage = pool.connection().await.unwrap();
+    for tx_hash in tx_hashes {
+        storage
+            .transactions_dal()
+            .get_storage_tx_by_hash(tx_hash)
+            .await
+            .unwrap()
+            .expect("transaction is not persisted");
+    }
+    drop(storage);
+
+    // A bundle must not replace a pending transaction with the same initiator and nonce.
+    let mut dave = Account::random();
+    let storage = tx_sender.acquire_replica_connection().await.unwrap();
+    StateBuilder::default()
+        .with_balance(dave.address(), u64::MAX.into())
+        .apply(storage)
+        .await;
+    let pending_transfer = dave.create_transfer(1_000_000_000.into());
+    tx_sender
+        .submit_tx(pending_transfer.clone(), block_args.clone())
+        .await
+        .unwrap();
+    dave.nonce = pending_transfer.nonce();
+    let conflicting_transfer = dave.create_transfer(2_000_000_000.into());
+    let other_transfer = alice.create_transfer(1_000_000_000.into());
+    let err = tx_sender
+        .submit_bundle(
+            vec![other_transfer.clone(), conflicting_transfer.clone()],
+            block_args,
+        )
+        .await
+        .unwrap_err();
+    assert_matches!(err, SubmitTxError::InvalidBundle(msg) if msg.contains("conflicting"));
+
+    let mut storage = pool.connection().await.unwrap();
+    for tx_hash in [other_transfer.hash(), conflicting_transfer.hash()] {
+        let storage_tx = storage
+            .transactions_dal()
+            .get_storage_tx_by_hash(tx_hash)
+            .await
+            .unwrap();
+        assert!(storage_tx.is_none());
+    }
+    storage
+        .transactions_dal()
+        .get_storage_tx_by_hash(pending_transfer.hash())
+        .await
+        .unwrap()
+        .expect("pending transaction was replaced");
+}
+
+#[derive(Debug)]
+struct PausingCircuitBreaker(Arc<AtomicBool>);
+
+#[async_trait::async_trait]
+impl CircuitBreaker for PausingCircuitBreaker {
+    fn name(&self) -> &'static str {
+        "pausing"
+    }
+
+    fn action(&self) -> CircuitBreakerAction {
+        CircuitBreakerAction::PauseTxAcceptance
+    }
+
+    async fn check(&self) -> Result<(), CircuitBreakerError> {
+        if self.0.load(Ordering::SeqCst) {
+            Err(CircuitBreakerError::FailedL1Transaction)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[tokio::test]
+async fn sending_transactions_with_paused_tx_acceptance() {
+    let pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+    let mut tx_sender = create_real_tx_sender(pool.clone()).await;
+    let circuit_breakers = CircuitBreakers::default();
+    let tripped = Arc::<AtomicBool>::default();
+    circuit_breakers
+        .insert(Box::new(PausingCircuitBreaker(tripped.clone())))
+        .await;
+    Arc::get_mut(&mut tx_sender.0).unwrap().tx_acceptance = circuit_breakers.tx_acceptance();
+
+    let block_args = pending_block_args(&tx_sender).await;
+    let mut alice = Account::random();
+    let storage = tx_sender.acquire_replica_connection().await.unwrap();
+    StateBuilder::default()
+        .with_balance(alice.address(), u64::MAX.into())
+        .apply(storage)
+        .await;
+
+    tripped.store(true, Ordering::SeqCst);
+    circuit_breakers.check().await.unwrap();
+    let transfer = alice.create_transfer(1_000_000_000.into());
+    let err = tx_sender
+        .submit_tx(transfer.clone(), block_args.clone())
+        .await
+        .unwrap_err();
+    assert_matches!(err, SubmitTxError::TxAcceptancePaused);
+    let err = tx_sender
+        .submit_bundle(vec![transfer.clone()], block_args.clone())
+        .await
+        .unwrap_err();
+    assert_matches!(err, SubmitTxError::TxAcceptancePaused);
+
+    let mut storage = pool.connection().await.unwrap();
+    let storage_tx = storage
+        .transactions_dal()
+        .get_storage_tx_by_hash(transfer.hash())
+        .await
+        .unwrap();
+    assert!(storage_tx.is_none());
+    drop(storage);
+
+    // Transactions must be accepted again once the circuit breaker is no longer tripped.
+    tripped.store(false, Ordering::SeqCst);
+    circuit_breakers.check().await.unwrap();
+    tx_sender.submit_tx(transfer, block_args).await.unwrap();
+}
+
 #[test_casing(5, LOAD_TEST_CASES)]
 #[tokio::test]
 async fn sending_load_test_transaction(tx_params: LoadnextContractExecutionParams) {
@@ -0,0 +1,126 @@
+//! Runtime updates for transaction policies.
+
+use std::{collections::HashSet, path::PathBuf, time::Duration};
+
+use anyhow::Context as _;
+use reqwest::Client;
+use serde::Deserialize;
+use tokio::sync::watch;
+use zksync_config::configs::chain::ScreeningListConfig;
+use zksync_types::Address;
+use zksync_vm_executor::tx_policy::ScreeningList;
+
+#[derive(Debug, Deserialize)]
+struct ScreeningListResponse {
+    addresses: Vec<Address>,
+}
+
+#[derive(Debug, Clone)]
+enum ScreeningListSource {
+    Http { url: String, client: Client },
+    File(PathBuf),
+}
+
+/// Task that periodically reloads the [`ScreeningList`] from a remote HTTP source or a local file.
+/// Both sources are expected to contain JSON in the `{ "addresses": [..] }` format.
+#[derive(Debug, Clone)]
+pub struct ScreeningListTask {
+    source: ScreeningListSource,
+    refresh_interval: Duration,
+    screening_list: ScreeningList,
+}
+
+impl ScreeningListTask {
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+    pub fn from_config(config: ScreeningListConfig) -> Self {
+        let (source, refresh_interval) = match config {
+            ScreeningListConfig::Http(config) => (
+                ScreeningListSource::Http {
+                    url: config.http_file_url,
+                    client: Client::new(),
+                },
+                config.refresh_interval,
+            ),
+            ScreeningListConfig::File(config) => (
+                ScreeningListSource::File(config.path),
+                config.refresh_interval,
+            ),
+        };
+        Self {
+            source,
+            refresh_interval,
+            screening_list: ScreeningList::default(),
+        }
+    }
+
+    pub fn shared(&self) -> ScreeningList {
+        self.screening_list.clone()
+    }
+
+    /// Fetches the list. Returns `None` if the list hasn't changed since the last fetch as per `current_etag`.
+    async fn fetch(
+        &self,
+        current_etag: Option<&str>,
+    ) -> anyhow::Result<Option<(HashSet<Address>, Option<String>)>> {
+        let (list, new_etag) = match &self.source {
+            ScreeningListSource::Http { url, client } => {
+                let mut request = client.get(url).timeout(Self::REQUEST_TIMEOUT);
+                if let Some(etag) = current_etag {
+                    request = request.header("If-None-Match", etag);
+                }
+
+                let response = request.send().await?;
+                if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+                    return Ok(None);
+                }
+                let response = response.error_for_status()?;
+                let new_etag = response
+                    .headers()
+                    .get("ETag")
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_owned);
+                (response.json::<ScreeningListResponse>().await?, new_etag)
+            }
+            ScreeningListSource::File(path) => {
+                let path = path.clone();
+                let contents = tokio::task::spawn_blocking(move || std::fs::read(&path))
+                    .await
+                    .context("panicked reading screening list")?
+                    .context("failed reading screening list")?;
+                let list =
+                    serde_json::from_slice(&contents).context("failed parsing screening list")?;
+                (list, None)
+            }
+        };
+        Ok(Some((list.addresses.into_iter().collect(), new_etag)))
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let mut etag: Option<String> = None;
+
+        while !*stop_receiver.borrow_and_update() {
+            match self.fetch(etag.as_deref()).await {
+                Ok(Some((new_list, new_etag))) => {
+                    let len = new_list.len();
+                    self.screening_list.replace(new_list).await;
+                    etag = new_etag;
+                    tracing::debug!("Screening list updated. {len} entries loaded.");
+                }
+                Ok(None) => {
+                    tracing::debug!("Screening list not updated (ETag matched).");
+                }
+                Err(err) => {
+                    // The previously loaded list stays in effect.
+                    tracing::warn!("Failed to refresh screening list: {err:#}");
+                }
+            }
+            tokio::time::timeout(self.refresh_interval, stop_receiver.changed())
+                .await
+                .ok();
+        }
+
+        tracing::info!("received a stop request; screening list task is shut down");
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@ use zksync_dal::{transactions_dal::L2TxSubmissionResult, Connection, Core};
 use zksync_multivm::interface::tracer::ValidationTraces;
 use zksync_types::{
     api::{Transaction, TransactionDetails, TransactionId},
-    l2::L2Tx,
+    l2::{bundle::L2TxBundle, L2Tx},
     Address, Nonce, H256,
 };
 use zksync_web3_decl::error::Web3Error;
@@ -32,6 +32,17 @@ pub trait TxSink: std::fmt::Debug + Send + Sync + 'static {
         validation_traces: ValidationTraces,
     ) -> Result<L2TxSubmissionResult, SubmitTxError>;
 
+    /// Ensures that an atomic bundle of transactions is propagated to the mempool. `members` contain execution outputs
+    /// and validation traces for each bundle transaction, in the bundle order.
+    /// By default, returns [`SubmitTxError::BundlesNotSupported`].
+    async fn submit_bundle(
+        &self,
+        _bundle: &L2TxBundle,
+        _members: Vec<(SandboxExecutionOutput, ValidationTraces)>,
+    ) -> Result<L2TxSubmissionResult, SubmitTxError> {
+        Err(SubmitTxError::BundlesNotSupported)
+    }
+
     /// Attempts to look up the pending nonce for the account in the sink-specific storage.
     /// By default, returns `Ok(None)`.
     async fn lookup_pending_nonce(
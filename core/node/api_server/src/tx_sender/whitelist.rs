@@ -6,7 +6,10 @@ use tokio::sync::watch;
 use zksync_config::configs::chain::DeploymentAllowlistDynamic;
 use zksync_dal::transactions_dal::L2TxSubmissionResult;
 use zksync_multivm::interface::tracer::ValidationTraces;
-use zksync_types::{l2::L2Tx, Address};
+use zksync_types::{
+    l2::{bundle::L2TxBundle, L2Tx},
+    Address,
+};
 use zksync_vm_executor::whitelist::{DeploymentTxFilter, SharedAllowList};
 
 use crate::{
@@ -65,6 +68,19 @@ impl TxSink for WhitelistedDeployPoolSink {
             .submit_tx(tx, execution_output, validation_traces)
             .await
     }
+
+    async fn submit_bundle(
+        &self,
+        bundle: &L2TxBundle,
+        members: Vec<(SandboxExecutionOutput, ValidationTraces)>,
+    ) -> Result<L2TxSubmissionResult, SubmitTxError> {
+        for (tx, (execution_output, _)) in bundle.txs().iter().zip(&members) {
+            self.check_if_deployment_allowed(tx, execution_output)
+                .await?;
+        }
+
+        self.master_pool_sink.submit_bundle(bundle, members).await
+    }
 }
 
 #[derive(Debug, Deserialize)]
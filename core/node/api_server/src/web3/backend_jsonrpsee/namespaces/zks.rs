@@ -1,11 +1,12 @@
 use zksync_types::{
     api::{
         state_override::StateOverride, BlockDetails, BridgeAddresses, InteropMode, L1BatchDetails,
-        L2ToL1LogProof, Proof, ProtocolVersion, TransactionDetails,
+        L2ToL1LogProof, Proof, ProofFormat, ProtocolVersion, TransactionDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
     transaction_request::CallRequest,
+    web3::Bytes,
     Address, L1BatchNumber, L2BlockNumber, H256, U256, U64,
 };
 use zksync_web3_decl::{
@@ -158,8 +159,9 @@ impl ZksNamespaceServer for ZksNamespace {
         address: Address,
         keys: Vec<H256>,
         l1_batch_number: L1BatchNumber,
+        format: Option<ProofFormat>,
     ) -> RpcResult<Option<Proof>> {
-        self.get_proofs_impl(address, keys, l1_batch_number)
+        self.get_proofs_impl(address, keys, l1_batch_number, format.unwrap_or_default())
             .await
             .map_err(|err| self.current_method().map_err(err))
     }
@@ -179,4 +181,10 @@ impl ZksNamespaceServer for ZksNamespace {
             .await
             .map_err(|err| self.current_method().map_err(err))
     }
+
+    async fn send_bundle(&self, txs: Vec<Bytes>) -> RpcResult<H256> {
+        self.send_bundle_impl(txs)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
 }
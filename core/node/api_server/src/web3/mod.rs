@@ -11,6 +11,7 @@ use tower_http::{cors::CorsLayer, metrics::InFlightRequestsLayer};
 use zksync_config::configs::api::{MaxResponseSize, MaxResponseSizeOverrides, Namespace};
 use zksync_dal::{helpers::wait_for_l1_batch, ConnectionPool, Core};
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_object_store::ObjectStore;
 use zksync_shared_resources::{
     api::{BridgeAddressesHandle, SyncState},
     tree::TreeApiClient,
@@ -43,6 +44,7 @@ use self::{
         DebugNamespace, EnNamespace, EthNamespace, NetNamespace, SnapshotsNamespace,
         UnstableNamespace, Web3Namespace, ZksNamespace,
     },
+    pruned_archive::PrunedDataArchive,
     pubsub::{EthSubscribe, EthSubscriptionIdProvider, PubSubEvent},
     receipts::AccountTypesCache,
     state::{Filters, InternalApiConfig, RpcState, SealedL2BlockNumber},
@@ -57,6 +59,7 @@ pub mod backend_jsonrpsee;
 pub mod mempool_cache;
 pub(super) mod metrics;
 pub mod namespaces;
+mod pruned_archive;
 pub(crate) mod pubsub;
 pub(super) mod receipts;
 pub mod state;
@@ -107,6 +110,7 @@ struct OptionalApiParams {
     mempool_cache: Option<MempoolCache>,
     extended_tracing: bool,
     l2_l1_log_proof_handler: Option<Box<DynClient<L2>>>,
+    pruned_data_archive: Option<Arc<PrunedDataArchive>>,
 }
 
 /// Structure capable of spawning a configured Web3 API server along with all the required
@@ -271,6 +275,12 @@ impl ApiBuilder {
         self
     }
 
+    /// Enables serving pruned L2 blocks and logs from archives in the specified object store.
+    pub fn with_pruned_data_archive(mut self, object_store: Arc<dyn ObjectStore>) -> Self {
+        self.optional.pruned_data_archive = Some(Arc::new(PrunedDataArchive::new(object_store)));
+        self
+    }
+
     // Intended for tests only.
     #[doc(hidden)]
     fn with_method_tracer(mut self, method_tracer: Arc<MethodTracer>) -> Self {
@@ -347,6 +357,7 @@ impl ApiServer {
             bridge_addresses_handle: self.bridge_addresses_handle,
             tree_api: self.optional.tree_api,
             l2_l1_log_proof_handler: self.optional.l2_l1_log_proof_handler,
+            pruned_data_archive: self.optional.pruned_data_archive,
         })
     }
 
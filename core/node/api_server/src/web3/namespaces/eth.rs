@@ -1,5 +1,5 @@
 use anyhow::Context as _;
-use zksync_dal::{CoreDal, DalError};
+use zksync_dal::{Connection, Core, CoreDal, DalError};
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
@@ -8,6 +8,7 @@ use zksync_types::{
     },
     bytecode::{trim_padded_evm_bytecode, BytecodeHash, BytecodeMarker},
     l2::{L2Tx, TransactionType},
+    pruning::ArchivedL2Block,
     transaction_request::CallRequest,
     u256_to_h256,
     web3::{self, Bytes, SyncInfo, SyncState},
@@ -219,6 +220,41 @@ impl EthNamespace {
         self.current_method().set_block_diff(diff);
     }
 
+    /// Loads a pruned L2 block from the archive. Returns `None` if archiving is disabled, the block is not pruned,
+    /// or it's not present in the archive.
+    async fn archived_l2_block(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        block_id: BlockId,
+    ) -> Result<Option<ArchivedL2Block>, Web3Error> {
+        let Some(archive) = &self.state.pruned_data_archive else {
+            return Ok(None);
+        };
+        let number = match block_id {
+            BlockId::Number(BlockNumber::Number(number)) => number,
+            BlockId::Number(BlockNumber::Earliest) => U64::zero(),
+            BlockId::Hash(hash) => {
+                let number = storage
+                    .pruning_dal()
+                    .get_pruned_l2_block_number(hash)
+                    .await
+                    .map_err(DalError::generalize)?;
+                let Some(number) = number else {
+                    return Ok(None);
+                };
+                number.0.into()
+            }
+            _ => return Ok(None),
+        };
+        let first_l2_block = self.state.start_info.first_l2_block(storage).await?;
+        if number >= first_l2_block.0.into() {
+            return Ok(None);
+        }
+        Ok(archive
+            .l2_block(storage, L2BlockNumber(number.as_u32()))
+            .await?)
+    }
+
     pub async fn get_logs_impl(&self, mut filter: Filter) -> Result<Vec<Log>, Web3Error> {
         self.state.resolve_filter_block_hash(&mut filter).await?;
         let (from_block, to_block) = self.state.resolve_filter_block_range(&filter).await?;
@@ -274,6 +310,24 @@ impl EthNamespace {
         }
 
         let mut storage = self.state.acquire_connection().await?;
+        if let Some(archived) = self.archived_l2_block(&mut storage, block_id).await? {
+            let transactions = if full_transactions {
+                archived
+                    .transactions
+                    .into_iter()
+                    .map(TransactionVariant::Full)
+                    .collect()
+            } else {
+                archived
+                    .block
+                    .transactions
+                    .iter()
+                    .copied()
+                    .map(TransactionVariant::Hash)
+                    .collect()
+            };
+            return Ok(Some(archived.block.with_transactions(transactions)));
+        }
         self.state
             .start_info
             .ensure_not_pruned(block_id, &mut storage)
@@ -343,6 +397,9 @@ impl EthNamespace {
         }
 
         let mut storage = self.state.acquire_connection().await?;
+        if let Some(archived) = self.archived_l2_block(&mut storage, block_id).await? {
+            return Ok(Some(archived.block.transactions.len().into()));
+        }
         self.state
             .start_info
             .ensure_not_pruned(block_id, &mut storage)
@@ -818,8 +875,18 @@ impl EthNamespace {
         effective_pubdata_price_history.reverse();
 
         let oldest_block = newest_l2_block.0 + 1 - base_fee_per_gas.len() as u32;
-        // We do not store gas used ratio for blocks, returns array of zeroes as a placeholder.
-        let gas_used_ratio = vec![0.0; base_fee_per_gas.len()];
+        let gas_usage = connection
+            .blocks_dal()
+            .get_l2_blocks_gas_usage(L2BlockNumber(oldest_block)..=newest_l2_block)
+            .await
+            .map_err(DalError::generalize)?;
+        let mut gas_used_ratio = vec![0.0; base_fee_per_gas.len()];
+        for usage in gas_usage {
+            let idx = (usage.number.0 - oldest_block) as usize;
+            if usage.gas_limit > 0 {
+                gas_used_ratio[idx] = usage.gas_used as f64 / usage.gas_limit as f64;
+            }
+        }
         // Effective priority gas price is currently 0.
         let reward = Some(vec![
             vec![U256::zero(); reward_percentiles.len()];
@@ -931,7 +998,7 @@ impl EthNamespace {
                     );
                 }
 
-                let get_logs_filter = GetLogsFilter {
+                let mut get_logs_filter = GetLogsFilter {
                     from_block: *from_block,
                     to_block,
                     addresses,
@@ -939,32 +1006,57 @@ impl EthNamespace {
                 };
 
                 let mut storage = self.state.acquire_connection().await?;
+                let limit = self.state.api_config.req_entities_limit;
+
+                // Logs for pruned L2 blocks are served from the archive (if it's enabled), and the remaining blocks from Postgres.
+                let mut logs = vec![];
+                if let Some(archive) = &self.state.pruned_data_archive {
+                    let first_l2_block = self.state.start_info.first_l2_block(&mut storage).await?;
+                    if get_logs_filter.from_block < first_l2_block {
+                        let archive_filter = GetLogsFilter {
+                            to_block: to_block.min(first_l2_block - 1),
+                            ..get_logs_filter.clone()
+                        };
+                        logs = archive.logs(&mut storage, &archive_filter, limit).await?;
+                        if *from_block != to_block && logs.len() > limit {
+                            let l2_block_number = logs[limit]
+                                .block_number
+                                .context("archived log without block number")?;
+                            return Err(Web3Error::LogsLimitExceeded(
+                                limit,
+                                from_block.0,
+                                from_block.0.max(l2_block_number.as_u32().saturating_sub(1)),
+                            ));
+                        }
+                        get_logs_filter.from_block = first_l2_block;
+                    }
+                }
 
-                // Check if there is more than one block in range and there are more than `req_entities_limit` logs that satisfies filter.
-                // In this case we should return error and suggest requesting logs with smaller block range.
-                if *from_block != to_block {
-                    if let Some(l2_block_number) = storage
+                if get_logs_filter.from_block <= get_logs_filter.to_block {
+                    // Check if there is more than one block in range and there are more than `req_entities_limit` logs that satisfies filter.
+                    // In this case we should return error and suggest requesting logs with smaller block range.
+                    if *from_block != to_block {
+                        if let Some(l2_block_number) = storage
+                            .events_web3_dal()
+                            .get_log_block_number(&get_logs_filter, limit - logs.len())
+                            .await
+                            .map_err(DalError::generalize)?
+                        {
+                            return Err(Web3Error::LogsLimitExceeded(
+                                limit,
+                                from_block.0,
+                                from_block.0.max(l2_block_number.0 - 1),
+                            ));
+                        }
+                    }
+
+                    let db_logs = storage
                         .events_web3_dal()
-                        .get_log_block_number(
-                            &get_logs_filter,
-                            self.state.api_config.req_entities_limit,
-                        )
+                        .get_logs(get_logs_filter, i32::MAX as usize)
                         .await
-                        .map_err(DalError::generalize)?
-                    {
-                        return Err(Web3Error::LogsLimitExceeded(
-                            self.state.api_config.req_entities_limit,
-                            from_block.0,
-                            from_block.0.max(l2_block_number.0 - 1),
-                        ));
-                    }
+                        .map_err(DalError::generalize)?;
+                    logs.extend(db_logs);
                 }
-
-                let logs = storage
-                    .events_web3_dal()
-                    .get_logs(get_logs_filter, i32::MAX as usize)
-                    .await
-                    .map_err(DalError::generalize)?;
                 *from_block = to_block + 1;
                 FilterChanges::Logs(logs)
             }
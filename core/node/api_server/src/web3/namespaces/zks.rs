@@ -6,7 +6,8 @@ use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
         state_override::StateOverride, BlockDetails, BridgeAddresses, InteropMode, L1BatchDetails,
-        L2ToL1LogProof, Proof, ProtocolVersion, StorageProof, TransactionDetails,
+        L2ToL1LogProof, Proof, ProofFormat, ProtocolVersion, StorageMultiproof, StorageProof,
+        TransactionDetails,
     },
     fee::Fee,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
@@ -14,6 +15,7 @@ use zksync_types::{
     l2::L2Tx,
     l2_to_l1_log::{l2_to_l1_logs_tree_size, L2ToL1Log, LOG_PROOF_SUPPORTED_METADATA_VERSION},
     transaction_request::CallRequest,
+    web3::Bytes,
     AccountTreeId, L1BatchNumber, L2BlockNumber, ProtocolVersionId, StorageKey, Transaction,
     REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_BYTE, U256, U64,
 };
@@ -429,11 +431,20 @@ impl ZksNamespace {
             .ensure_not_pruned(batch_number, &mut storage)
             .await?;
 
-        Ok(storage
+        let Some(mut details) = storage
             .blocks_web3_dal()
             .get_l1_batch_details(batch_number)
             .await
-            .map_err(DalError::generalize)?)
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+        details.tee_attestation = storage
+            .tee_proof_generation_dal()
+            .get_batch_attestation(batch_number)
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(Some(details))
     }
 
     pub async fn get_bytecode_by_hash_impl(
@@ -488,13 +499,14 @@ impl ZksNamespace {
         address: Address,
         keys: Vec<H256>,
         l1_batch_number: L1BatchNumber,
+        format: ProofFormat,
     ) -> Result<Option<Proof>, Web3Error> {
         let mut storage = self.state.acquire_connection().await?;
         self.state
             .start_info
             .ensure_not_pruned(l1_batch_number, &mut storage)
             .await?;
-        let hashed_keys = keys
+        let hashed_keys: Vec<_> = keys
             .iter()
             .map(|key| StorageKey::new(AccountTreeId::new(address), *key).hashed_key_u256())
             .collect();
@@ -503,46 +515,108 @@ impl ZksNamespace {
             .tree_api
             .as_deref()
             .ok_or(Web3Error::MethodNotImplemented)?;
-        let proofs_result = tree_api.get_proofs(l1_batch_number, hashed_keys).await;
-        let proofs = match proofs_result {
-            Ok(proofs) => proofs,
-            Err(TreeApiError::NotReady(_)) => return Err(Web3Error::TreeApiUnavailable),
+
+        match format {
+            ProofFormat::Individual => {
+                let proofs_result = tree_api.get_proofs(l1_batch_number, hashed_keys).await;
+                let Some(proofs) = Self::map_tree_api_result(proofs_result, l1_batch_number)?
+                else {
+                    return Ok(None);
+                };
+                let storage_proof = proofs
+                    .into_iter()
+                    .zip(keys)
+                    .map(|(proof, key)| StorageProof {
+                        key,
+                        proof: proof.merkle_path,
+                        value: proof.value,
+                        index: proof.index,
+                    })
+                    .collect();
+
+                Ok(Some(Proof {
+                    address,
+                    storage_proof,
+                    multiproof: None,
+                }))
+            }
+            ProofFormat::Multiproof => {
+                let multiproof_result = tree_api
+                    .get_multiproof(l1_batch_number, hashed_keys.clone())
+                    .await;
+                let Some(multiproof) =
+                    Self::map_tree_api_result(multiproof_result, l1_batch_number)?
+                else {
+                    return Ok(None);
+                };
+
+                // Entries in the multiproof are sorted by the hashed key and deduplicated, so we need to map them
+                // to the requested keys.
+                let mut storage_proof = Vec::with_capacity(keys.len());
+                for (key, hashed_key) in keys.into_iter().zip(&hashed_keys) {
+                    let entry = multiproof
+                        .entries
+                        .binary_search_by_key(hashed_key, |entry| entry.key)
+                        .map(|idx| &multiproof.entries[idx])
+                        .map_err(|_| {
+                            Web3Error::InternalError(anyhow::anyhow!(
+                                "Merkle tree multiproof doesn't contain requested key {key:?}"
+                            ))
+                        })?;
+                    storage_proof.push(StorageProof {
+                        key,
+                        proof: vec![],
+                        value: entry.value,
+                        index: entry.index,
+                    });
+                }
+                let path_lengths = multiproof
+                    .entries
+                    .iter()
+                    .map(|entry| entry.path_length)
+                    .collect();
+
+                Ok(Some(Proof {
+                    address,
+                    storage_proof,
+                    multiproof: Some(StorageMultiproof {
+                        path_lengths,
+                        sibling_hashes: multiproof.sibling_hashes,
+                    }),
+                }))
+            }
+        }
+    }
+
+    /// Maps the tree API result to the `zks_getProof` output. Returns `Ok(None)` if the requested L1 batch
+    /// is not yet processed by the tree.
+    fn map_tree_api_result<T>(
+        result: Result<T, TreeApiError>,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Option<T>, Web3Error> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(TreeApiError::NotReady(_)) => Err(Web3Error::TreeApiUnavailable),
             Err(TreeApiError::NoVersion {
                 missing_version,
                 version_count,
             }) => {
-                return if missing_version > version_count {
+                if missing_version > version_count {
                     Ok(None)
                 } else {
                     Err(Web3Error::InternalError(anyhow::anyhow!(
                         "L1 batch #{l1_batch_number} is pruned in Merkle tree, but not in Postgres"
                     )))
-                };
+                }
             }
-            Err(TreeApiError::Internal(err)) => return Err(Web3Error::InternalError(err)),
+            Err(TreeApiError::Internal(err)) => Err(Web3Error::InternalError(err)),
             Err(_) => {
                 // This branch is not expected to be executed, but has to be provided since the error is non-exhaustive.
-                return Err(Web3Error::InternalError(anyhow::anyhow!(
+                Err(Web3Error::InternalError(anyhow::anyhow!(
                     "Unspecified tree API error"
-                )));
+                )))
             }
-        };
-
-        let storage_proof = proofs
-            .into_iter()
-            .zip(keys)
-            .map(|(proof, key)| StorageProof {
-                key,
-                proof: proof.merkle_path,
-                value: proof.value,
-                index: proof.index,
-            })
-            .collect();
-
-        Ok(Some(Proof {
-            address,
-            storage_proof,
-        }))
+        }
     }
 
     pub fn get_base_token_l1_address_impl(&self) -> Result<Address, Web3Error> {
@@ -575,4 +649,24 @@ impl ZksNamespace {
         let gas_per_pubdata = gas_per_pubdata.max(1);
         Ok(gas_per_pubdata.into())
     }
+
+    pub async fn send_bundle_impl(&self, txs_bytes: Vec<Bytes>) -> Result<H256, Web3Error> {
+        let mut connection = self.state.acquire_connection().await?;
+        let block_args = BlockArgs::pending(&mut connection).await?;
+        drop(connection);
+
+        let mut txs = Vec::with_capacity(txs_bytes.len());
+        for tx_bytes in txs_bytes {
+            let (mut tx, hash) = self
+                .state
+                .parse_transaction_bytes(&tx_bytes.0, &block_args)?;
+            tx.set_input(tx_bytes.0, hash);
+            txs.push(tx);
+        }
+
+        let submit_result = self.state.tx_sender.submit_bundle(txs, block_args).await;
+        submit_result
+            .map(|bundle| bundle.hash())
+            .map_err(|err| self.current_method().map_submit_err(err))
+    }
 }
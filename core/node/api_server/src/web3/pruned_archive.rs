@@ -0,0 +1,162 @@
+//! Reader for archives of pruned L2 blocks persisted by the DB pruner.
+
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Context as _;
+use lru::LruCache;
+use zksync_dal::{Connection, Core, CoreDal};
+use zksync_object_store::ObjectStore;
+use zksync_types::{
+    api,
+    pruning::{ArchivedL2Block, PrunedL2BlocksArchive, PrunedL2BlocksArchiveKey},
+    L2BlockNumber,
+};
+
+/// Number of recently accessed archives kept in memory.
+const CACHE_CAPACITY: NonZeroUsize = NonZeroUsize::new(16).unwrap();
+
+/// Serves data for pruned L2 blocks from archives in an object store. Archives are located using
+/// the `pruning_archives` table in Postgres; pruned blocks are resolved by hash using the `pruned_l2_block_hashes` table.
+#[derive(Debug)]
+pub(crate) struct PrunedDataArchive {
+    object_store: Arc<dyn ObjectStore>,
+    cache: Mutex<LruCache<PrunedL2BlocksArchiveKey, Arc<PrunedL2BlocksArchive>>>,
+}
+
+impl PrunedDataArchive {
+    pub fn new(object_store: Arc<dyn ObjectStore>) -> Self {
+        Self {
+            object_store,
+            cache: Mutex::new(LruCache::new(CACHE_CAPACITY)),
+        }
+    }
+
+    async fn load(
+        &self,
+        key: PrunedL2BlocksArchiveKey,
+    ) -> anyhow::Result<Arc<PrunedL2BlocksArchive>> {
+        if let Some(archive) = self.cache.lock().unwrap().get(&key) {
+            return Ok(archive.clone());
+        }
+
+        let archive: PrunedL2BlocksArchive = self
+            .object_store
+            .get(key)
+            .await
+            .with_context(|| format!("failed loading pruning archive {key:?}"))?;
+        let archive = Arc::new(archive);
+        self.cache.lock().unwrap().put(key, archive.clone());
+        Ok(archive)
+    }
+
+    /// Returns an archived L2 block with the specified number, or `None` if the block is not archived.
+    pub async fn l2_block(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        number: L2BlockNumber,
+    ) -> anyhow::Result<Option<ArchivedL2Block>> {
+        let keys = storage
+            .pruning_dal()
+            .get_pruning_archives(number..=number)
+            .await?;
+        let Some(&key) = keys.first() else {
+            return Ok(None);
+        };
+        let archive = self.load(key).await?;
+        Ok(archive.l2_block(number).cloned())
+    }
+
+    /// Returns archived logs matching the specified filter, ordered by L2 block and index in block.
+    /// Stops collecting logs once more than `limit` logs are found.
+    pub async fn logs(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        filter: &api::GetLogsFilter,
+        limit: usize,
+    ) -> anyhow::Result<Vec<api::Log>> {
+        let keys = storage
+            .pruning_dal()
+            .get_pruning_archives(filter.from_block..=filter.to_block)
+            .await?;
+
+        let mut logs = vec![];
+        for key in keys {
+            let archive = self.load(key).await?;
+            let blocks = archive.l2_blocks.iter().filter(|block| {
+                let number = L2BlockNumber(block.block.number.as_u32());
+                (filter.from_block..=filter.to_block).contains(&number)
+            });
+            for block in blocks {
+                let matching_logs = block.logs.iter().filter(|log| log_matches(filter, log));
+                logs.extend(matching_logs.cloned());
+                if logs.len() > limit {
+                    return Ok(logs);
+                }
+            }
+        }
+        Ok(logs)
+    }
+}
+
+/// Checks whether `log` matches `filter` using the same semantics as the `events` table queries.
+fn log_matches(filter: &api::GetLogsFilter, log: &api::Log) -> bool {
+    if !filter.addresses.is_empty() && !filter.addresses.contains(&log.address) {
+        return false;
+    }
+    filter.topics.iter().all(|(topic_index, topics)| {
+        if topics.is_empty() {
+            return true;
+        }
+        let actual_topic = log.topics.get(*topic_index as usize - 1);
+        actual_topic.is_some_and(|topic| topics.contains(topic))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{Address, H256};
+
+    use super::*;
+
+    #[test]
+    fn matching_logs() {
+        let address = Address::repeat_byte(1);
+        let log = api::Log {
+            address,
+            topics: vec![H256::repeat_byte(2), H256::repeat_byte(3)],
+            data: Default::default(),
+            block_hash: None,
+            block_number: None,
+            l1_batch_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+            block_timestamp: None,
+        };
+        let mut filter = api::GetLogsFilter {
+            from_block: L2BlockNumber(0),
+            to_block: L2BlockNumber(10),
+            addresses: vec![],
+            topics: vec![],
+        };
+        assert!(log_matches(&filter, &log));
+
+        filter.addresses = vec![Address::repeat_byte(2), address];
+        assert!(log_matches(&filter, &log));
+        filter.topics = vec![(2, vec![H256::repeat_byte(3)])];
+        assert!(log_matches(&filter, &log));
+        filter.topics = vec![(1, vec![H256::repeat_byte(3)])];
+        assert!(!log_matches(&filter, &log));
+        filter.topics = vec![(3, vec![H256::repeat_byte(3)])];
+        assert!(!log_matches(&filter, &log));
+        filter.topics = vec![];
+        filter.addresses = vec![Address::repeat_byte(2)];
+        assert!(!log_matches(&filter, &log));
+    }
+}
@@ -44,6 +44,7 @@ use super::{
     backend_jsonrpsee::MethodTracer,
     mempool_cache::MempoolCache,
     metrics::{FilterType, FILTER_METRICS},
+    pruned_archive::PrunedDataArchive,
     receipts::AccountTypesCache,
     TypedFilter,
 };
@@ -327,6 +328,8 @@ pub(crate) struct RpcState {
     pub(super) last_sealed_l2_block: SealedL2BlockNumber,
     pub(super) bridge_addresses_handle: BridgeAddressesHandle,
     pub(super) l2_l1_log_proof_handler: Option<Box<DynClient<L2>>>,
+    /// Archive of pruned data used as a fallback for requests to pruned L2 blocks.
+    pub(super) pruned_data_archive: Option<Arc<PrunedDataArchive>>,
 }
 
 impl RpcState {
@@ -433,6 +436,12 @@ impl RpcState {
         if let Some(api::BlockNumber::Number(number)) = block_number {
             return Ok(Self::u64_to_block_number(number));
         }
+        if matches!(block_number, Some(api::BlockNumber::Earliest))
+            && self.pruned_data_archive.is_some()
+        {
+            // Pruned L2 blocks are served from the archive, so the earliest block is always available.
+            return Ok(L2BlockNumber(0));
+        }
 
         let block_number = block_number.unwrap_or(api::BlockNumber::Latest);
         let block_id = api::BlockId::Number(block_number);
@@ -456,9 +465,21 @@ impl RpcState {
         match (filter.block_hash, filter.from_block, filter.to_block) {
             (Some(block_hash), None, None) => {
                 let mut storage = self.acquire_connection().await?;
-                let block_number = self
-                    .resolve_block(&mut storage, api::BlockId::Hash(block_hash))
-                    .await?;
+                let pruned_block_number = if self.pruned_data_archive.is_some() {
+                    storage
+                        .pruning_dal()
+                        .get_pruned_l2_block_number(block_hash)
+                        .await
+                        .map_err(DalError::generalize)?
+                } else {
+                    None
+                };
+                let block_number = if let Some(number) = pruned_block_number {
+                    number
+                } else {
+                    self.resolve_block(&mut storage, api::BlockId::Hash(block_hash))
+                        .await?
+                };
                 filter.from_block = Some(api::BlockNumber::Number(block_number.0.into()));
                 filter.to_block = Some(api::BlockNumber::Number(block_number.0.into()));
                 Ok(())
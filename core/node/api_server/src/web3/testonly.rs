@@ -113,6 +113,7 @@ pub struct TestServerBuilder {
     tx_executor: MockOneshotExecutor,
     executor_options: Option<SandboxExecutorOptions>,
     method_tracer: Arc<MethodTracer>,
+    pruned_data_archive: Option<Arc<dyn ObjectStore>>,
 }
 
 impl TestServerBuilder {
@@ -125,6 +126,7 @@ impl TestServerBuilder {
             tx_executor: MockOneshotExecutor::default(),
             executor_options: None,
             method_tracer: Arc::default(),
+            pruned_data_archive: None,
         }
     }
 
@@ -148,6 +150,14 @@ impl TestServerBuilder {
         self
     }
 
+    /// Enables serving pruned data from archives in the specified object store. Pruning info is not cached
+    /// by the server in this case, so that tests can prune data after the server is started.
+    #[must_use]
+    pub fn with_pruned_data_archive(mut self, object_store: Arc<dyn ObjectStore>) -> Self {
+        self.pruned_data_archive = Some(object_store);
+        self
+    }
+
     #[must_use]
     pub fn with_request_timeout(mut self, timeout: Option<Duration>) -> Self {
         self.request_timeout = timeout;
@@ -188,6 +198,7 @@ impl TestServerBuilder {
             pool,
             api_config,
             method_tracer,
+            pruned_data_archive,
         } = self;
 
         let tx_executor = if let Some(options) = executor_options {
@@ -237,6 +248,11 @@ impl TestServerBuilder {
         if let Some(timeout) = request_timeout {
             server_builder = server_builder.with_request_timeout(timeout);
         }
+        if let Some(object_store) = pruned_data_archive {
+            server_builder = server_builder
+                .with_pruned_data_archive(object_store)
+                .with_pruning_info_refresh_interval(Duration::ZERO);
+        }
 
         let server = server_builder.build().expect("Unable to build API server");
         let health_check = server.health_check();
@@ -69,6 +69,7 @@ use crate::{
 
 mod debug;
 mod filters;
+mod pruned_archive;
 mod snapshots;
 mod unstable;
 mod vm;
@@ -162,6 +163,11 @@ trait HttpTest: Send + Sync {
         Web3JsonRpcConfig::for_tests()
     }
 
+    /// Object store with archives of pruned data. If set, the server will serve pruned data from it.
+    fn pruned_data_archive(&self) -> Option<Arc<dyn ObjectStore>> {
+        None
+    }
+
     async fn test(&self, client: &DynClient<L2>, pool: &ConnectionPool<Core>)
         -> anyhow::Result<()>;
 }
@@ -298,6 +304,9 @@ async fn test_http_server(test: impl HttpTest) {
     if let Some(executor_options) = test.executor_options() {
         server_builder = server_builder.with_executor_options(executor_options);
     }
+    if let Some(object_store) = test.pruned_data_archive() {
+        server_builder = server_builder.with_pruned_data_archive(object_store);
+    }
     let mut server_handles = server_builder.build_http(stop_receiver).await;
 
     let local_addr = server_handles.wait_until_ready().await;
@@ -1200,12 +1209,16 @@ impl HttpTest for FeeHistoryTest {
             ..create_l2_block(1)
         };
         store_custom_l2_block(&mut connection, &block1, &[]).await?;
+        let tx = create_l2_transaction(1, 2);
         let block2 = L2BlockHeader {
             batch_fee_input: scaled_sensible_fee_input(2.0),
             base_fee_per_gas: 200,
+            l2_tx_count: 1,
+            gas_limit: tx.common_data.fee.gas_limit.as_u64() * 4,
             ..create_l2_block(2)
         };
-        store_custom_l2_block(&mut connection, &block2, &[]).await?;
+        let tx_results = [mock_execute_transaction(tx.into())];
+        store_custom_l2_block(&mut connection, &block2, &tx_results).await?;
 
         let all_pubdata_prices = [
             0,
@@ -1223,8 +1236,8 @@ impl HttpTest for FeeHistoryTest {
             [0, 100, 200, 200].map(U256::from) // The latest value is duplicated
         );
         assert_eq!(history.l2_pubdata_price, all_pubdata_prices);
+        assert_eq!(history.inner.gas_used_ratio, [0.0, 0.0, 0.25]);
         // Values below are not filled.
-        assert_eq!(history.inner.gas_used_ratio, [0.0; 3]);
         assert_eq!(history.inner.base_fee_per_blob_gas, [U256::zero(); 4]);
         assert_eq!(history.inner.blob_gas_used_ratio, [0.0; 3]);
 
@@ -1271,6 +1284,7 @@ impl HttpTest for FeeHistoryTest {
             [100, 200, 200].map(U256::from)
         );
         assert_eq!(history.l2_pubdata_price, all_pubdata_prices[1..]);
+        assert_eq!(history.inner.gas_used_ratio, [0.0, 0.25]);
 
         // Blocks 1..=1
         let history = client
@@ -0,0 +1,231 @@
+//! Tests for serving pruned data from archives.
+
+use zksync_object_store::MockObjectStore;
+use zksync_types::{
+    api::GetLogsFilter,
+    pruning::{ArchivedL2Block, PrunedL2BlocksArchive, PrunedL2BlocksArchiveKey},
+};
+use zksync_web3_decl::types::Filter;
+
+use super::*;
+
+#[derive(Debug)]
+struct PrunedArchiveTest {
+    object_store: Arc<dyn ObjectStore>,
+}
+
+impl PrunedArchiveTest {
+    const LOGS_LIMIT: u32 = 10;
+    const LAST_PRUNED_BLOCK: L2BlockNumber = L2BlockNumber(2);
+
+    fn new() -> Self {
+        Self {
+            object_store: MockObjectStore::arc(),
+        }
+    }
+
+    async fn get_logs(
+        client: &DynClient<L2>,
+        from_block: api::BlockNumber,
+        to_block: api::BlockNumber,
+        address: Option<Address>,
+    ) -> Result<Vec<api::Log>, ClientError> {
+        let filter = Filter {
+            from_block: Some(from_block),
+            to_block: Some(to_block),
+            address: address.map(Into::into),
+            ..Filter::default()
+        };
+        client.get_logs(filter).await
+    }
+
+    /// Archives and prunes L1 batches / L2 blocks up to and including `LAST_PRUNED_BLOCK`, the same way the DB pruner does.
+    async fn archive_and_prune(&self, storage: &mut Connection<'_, Core>) -> anyhow::Result<()> {
+        let l2_chain_id = GenesisConfig::for_tests().l2_chain_id;
+        let mut l2_blocks = vec![];
+        for number in 0..=Self::LAST_PRUNED_BLOCK.0 {
+            let number = L2BlockNumber(number);
+            let block = storage
+                .blocks_web3_dal()
+                .get_api_block(number)
+                .await?
+                .context("no block")?;
+            let transactions = storage
+                .transactions_web3_dal()
+                .get_transactions(&block.transactions, l2_chain_id)
+                .await?;
+            let filter = GetLogsFilter {
+                from_block: number,
+                to_block: number,
+                addresses: vec![],
+                topics: vec![],
+            };
+            let logs = storage
+                .events_web3_dal()
+                .get_logs(filter, i32::MAX as usize)
+                .await?;
+            l2_blocks.push(ArchivedL2Block {
+                block,
+                transactions,
+                logs,
+                storage_logs: vec![],
+            });
+        }
+
+        let key = PrunedL2BlocksArchiveKey::from(L2BlockNumber(0)..=Self::LAST_PRUNED_BLOCK);
+        let block_hashes: Vec<_> = l2_blocks
+            .iter()
+            .map(|block| (L2BlockNumber(block.block.number.as_u32()), block.block.hash))
+            .collect();
+        self.object_store
+            .put(key, &PrunedL2BlocksArchive { l2_blocks })
+            .await?;
+
+        let last_pruned_batch = L1BatchNumber(Self::LAST_PRUNED_BLOCK.0);
+        let mut pruning_dal = storage.pruning_dal();
+        pruning_dal
+            .insert_pruned_l2_block_hashes(&block_hashes)
+            .await?;
+        pruning_dal.insert_pruning_archive(key).await?;
+        pruning_dal
+            .insert_soft_pruning_log(last_pruned_batch, Self::LAST_PRUNED_BLOCK)
+            .await?;
+        pruning_dal
+            .hard_prune_batches_range(last_pruned_batch, Self::LAST_PRUNED_BLOCK)
+            .await?;
+        pruning_dal
+            .insert_hard_pruning_log(last_pruned_batch, Self::LAST_PRUNED_BLOCK, H256::zero())
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HttpTest for PrunedArchiveTest {
+    fn web3_config(&self) -> Web3JsonRpcConfig {
+        Web3JsonRpcConfig {
+            req_entities_limit: Self::LOGS_LIMIT,
+            ..Web3JsonRpcConfig::for_tests()
+        }
+    }
+
+    fn pruned_data_archive(&self) -> Option<Arc<dyn ObjectStore>> {
+        Some(self.object_store.clone())
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut storage = pool.connection().await?;
+        // Each block contains 4 events, 2 of which are emitted by `Address::repeat_byte(23)`.
+        for number in 1..=3 {
+            store_events(&mut storage, number, 0).await?;
+            seal_l1_batch(&mut storage, L1BatchNumber(number)).await?;
+        }
+
+        let pruned_block = client
+            .get_block_by_number(api::BlockNumber::Number(1.into()), false)
+            .await?
+            .context("no block")?;
+        let earliest_block = client
+            .get_block_by_number(api::BlockNumber::Earliest, false)
+            .await?;
+        let archived_logs = Self::get_logs(
+            client,
+            api::BlockNumber::Earliest,
+            api::BlockNumber::Number(2.into()),
+            None,
+        )
+        .await?;
+        assert_eq!(archived_logs.len(), 8);
+        let merged_logs = Self::get_logs(
+            client,
+            api::BlockNumber::Number(2.into()),
+            api::BlockNumber::Latest,
+            None,
+        )
+        .await?;
+        assert_eq!(merged_logs.len(), 8);
+        let address = Some(Address::repeat_byte(23));
+        let address_logs = Self::get_logs(
+            client,
+            api::BlockNumber::Earliest,
+            api::BlockNumber::Latest,
+            address,
+        )
+        .await?;
+        assert_eq!(address_logs.len(), 6);
+        let limit_error = Self::get_logs(
+            client,
+            api::BlockNumber::Earliest,
+            api::BlockNumber::Latest,
+            None,
+        )
+        .await
+        .unwrap_err();
+
+        self.archive_and_prune(&mut storage).await?;
+        drop(storage);
+        // Wait until the server observes pruning; pruning info is cached for a short randomized period.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        // Check that pruned blocks are served from the archive, including lookups by hash.
+        let block = client
+            .get_block_by_number(api::BlockNumber::Number(1.into()), false)
+            .await?;
+        assert_eq!(block.as_ref(), Some(&pruned_block));
+        let block = client.get_block_by_hash(pruned_block.hash, false).await?;
+        assert_eq!(block.as_ref(), Some(&pruned_block));
+        let tx_count = client
+            .get_block_transaction_count_by_hash(pruned_block.hash)
+            .await?;
+        assert_eq!(tx_count, Some(pruned_block.transactions.len().into()));
+        let block = client
+            .get_block_by_number(api::BlockNumber::Earliest, false)
+            .await?;
+        assert_eq!(block, earliest_block);
+
+        // Check that archived logs are merged with logs from Postgres and the response limit is respected.
+        let logs = Self::get_logs(
+            client,
+            api::BlockNumber::Earliest,
+            api::BlockNumber::Number(2.into()),
+            None,
+        )
+        .await?;
+        assert_eq!(logs, archived_logs);
+        let logs = Self::get_logs(
+            client,
+            api::BlockNumber::Number(2.into()),
+            api::BlockNumber::Latest,
+            None,
+        )
+        .await?;
+        assert_eq!(logs, merged_logs);
+        let logs = Self::get_logs(
+            client,
+            api::BlockNumber::Earliest,
+            api::BlockNumber::Latest,
+            address,
+        )
+        .await?;
+        assert_eq!(logs, address_logs);
+        let error = Self::get_logs(
+            client,
+            api::BlockNumber::Earliest,
+            api::BlockNumber::Latest,
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error.to_string(), limit_error.to_string());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn serving_pruned_data_from_archive() {
+    test_http_server(PrunedArchiveTest::new()).await;
+}
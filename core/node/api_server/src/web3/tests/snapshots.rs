@@ -2,7 +2,7 @@
 
 use std::collections::HashSet;
 
-use zksync_types::snapshots::SnapshotVersion;
+use zksync_types::snapshots::{DifferentialSnapshotInfo, SnapshotVersion};
 use zksync_web3_decl::namespaces::SnapshotsNamespaceClient;
 
 use super::*;
@@ -46,6 +46,7 @@ impl HttpTest for SnapshotBasicsTest {
             .add_snapshot(
                 SnapshotVersion::Version0,
                 L1BatchNumber(1),
+                None,
                 Self::CHUNK_COUNT,
                 "file:///factory_deps",
             )
@@ -65,6 +66,7 @@ impl HttpTest for SnapshotBasicsTest {
         } else {
             assert_eq!(all_snapshots.snapshots_l1_batch_numbers, []);
         }
+        assert_eq!(all_snapshots.differential_snapshots, []);
 
         let snapshot_header = client
             .get_snapshot_by_l1_batch_number(L1BatchNumber(1))
@@ -78,6 +80,7 @@ impl HttpTest for SnapshotBasicsTest {
 
         assert_eq!(snapshot_header.l1_batch_number, L1BatchNumber(1));
         assert_eq!(snapshot_header.l2_block_number, L2BlockNumber(1));
+        assert_eq!(snapshot_header.base_l1_batch_number, None);
         assert_eq!(
             snapshot_header.factory_deps_filepath,
             "file:///factory_deps"
@@ -109,3 +112,75 @@ async fn snapshot_with_some_chunks() {
 async fn snapshot_with_all_chunks() {
     test_http_server(SnapshotBasicsTest::new(0..SnapshotBasicsTest::CHUNK_COUNT)).await;
 }
+
+#[derive(Debug)]
+struct DifferentialSnapshotTest;
+
+#[async_trait]
+impl HttpTest for DifferentialSnapshotTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut storage = pool.connection().await.unwrap();
+        for number in 1..=2 {
+            store_l2_block(
+                &mut storage,
+                L2BlockNumber(number),
+                &[mock_execute_transaction(create_l2_transaction(1, 2).into())],
+            )
+            .await?;
+            seal_l1_batch(&mut storage, L1BatchNumber(number)).await?;
+        }
+
+        let bases = [None, Some(L1BatchNumber(1))];
+        for (l1_batch_number, base_l1_batch_number) in (1..=2).map(L1BatchNumber).zip(bases) {
+            storage
+                .snapshots_dal()
+                .add_snapshot(
+                    SnapshotVersion::Version1,
+                    l1_batch_number,
+                    base_l1_batch_number,
+                    1,
+                    "file:///factory_deps",
+                )
+                .await?;
+            storage
+                .snapshots_dal()
+                .add_storage_logs_filepath_for_snapshot(
+                    l1_batch_number,
+                    0,
+                    "file:///storage_logs/chunk0",
+                )
+                .await?;
+        }
+
+        let all_snapshots = client.get_all_snapshots().await?;
+        assert_eq!(all_snapshots.snapshots_l1_batch_numbers, [L1BatchNumber(1)]);
+        assert_eq!(
+            all_snapshots.differential_snapshots,
+            [DifferentialSnapshotInfo {
+                l1_batch_number: L1BatchNumber(2),
+                base_l1_batch_number: L1BatchNumber(1),
+            }]
+        );
+        assert_eq!(
+            all_snapshots.newest_l1_batch_number(),
+            Some(L1BatchNumber(2))
+        );
+
+        let snapshot_header = client
+            .get_snapshot_by_l1_batch_number(L1BatchNumber(2))
+            .await?
+            .context("no snapshot for L1 batch #2")?;
+        assert_eq!(snapshot_header.l2_block_number, L2BlockNumber(2));
+        assert_eq!(snapshot_header.base_l1_batch_number, Some(L1BatchNumber(1)));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn differential_snapshot() {
+    test_http_server(DifferentialSnapshotTest).await;
+}
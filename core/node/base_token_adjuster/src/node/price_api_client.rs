@@ -1,11 +1,15 @@
 use std::{str::FromStr, sync::Arc};
 
+use anyhow::Context as _;
 use zksync_config::configs::ExternalPriceApiClientConfig;
+use zksync_eth_client::web3_decl::client::{DynClient, L1};
 use zksync_external_price_api::{
+    aggregator::AggregatingPriceApiClient, chainlink_price_client::ChainlinkPriceClient,
     cmc_api::CmcPriceApiClient, coingecko_api::CoinGeckoPriceAPIClient,
-    forced_price_client::ForcedPriceClient, NoOpPriceApiClient, PriceApiClient,
+    forced_price_client::ForcedPriceClient, uniswap_twap_price_client::UniswapV3TwapPriceClient,
+    NoOpPriceApiClient, PriceApiClient,
 };
-use zksync_node_framework::{WiringError, WiringLayer};
+use zksync_node_framework::{FromContext, WiringError, WiringLayer};
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
 enum ExternalPriceApiKind {
@@ -14,6 +18,9 @@ enum ExternalPriceApiKind {
     Forced,
     CoinGecko,
     CoinMarketCap,
+    Chainlink,
+    UniswapV3,
+    Aggregated,
 }
 
 impl FromStr for ExternalPriceApiKind {
@@ -25,19 +32,90 @@ impl FromStr for ExternalPriceApiKind {
             "forced" => Self::Forced,
             "coingecko" => Self::CoinGecko,
             "coinmarketcap" => Self::CoinMarketCap,
+            "chainlink" => Self::Chainlink,
+            "uniswap_v3" | "uniswapv3" => Self::UniswapV3,
+            "aggregated" => Self::Aggregated,
             _ => anyhow::bail!("Unknown external price API client source: {s:?}"),
         })
     }
 }
 
 impl ExternalPriceApiKind {
-    fn instantiate(&self, config: ExternalPriceApiClientConfig) -> Arc<dyn PriceApiClient> {
-        match self {
+    fn instantiate(
+        &self,
+        config: ExternalPriceApiClientConfig,
+        eth_client: Option<Box<DynClient<L1>>>,
+    ) -> anyhow::Result<Arc<dyn PriceApiClient>> {
+        Ok(match self {
             Self::NoOp => Arc::new(NoOpPriceApiClient),
             Self::Forced => Arc::new(ForcedPriceClient::new(config)),
             Self::CoinGecko => Arc::new(CoinGeckoPriceAPIClient::new(config)),
             Self::CoinMarketCap => Arc::new(CmcPriceApiClient::new(config)),
+            Self::Chainlink => {
+                let chainlink_config = config
+                    .chainlink
+                    .context("Chainlink price client requires `chainlink` config")?;
+                let eth_client = eth_client.context("Chainlink price client requires L1 client")?;
+                Arc::new(ChainlinkPriceClient::new(chainlink_config, eth_client))
+            }
+            Self::UniswapV3 => {
+                let uniswap_config = config
+                    .uniswap_v3
+                    .context("Uniswap V3 price client requires `uniswap_v3` config")?;
+                let eth_client =
+                    eth_client.context("Uniswap V3 price client requires L1 client")?;
+                Arc::new(UniswapV3TwapPriceClient::new(uniswap_config, eth_client))
+            }
+            Self::Aggregated => Arc::new(Self::instantiate_aggregated(config, eth_client)?),
+        })
+    }
+
+    fn instantiate_aggregated(
+        config: ExternalPriceApiClientConfig,
+        eth_client: Option<Box<DynClient<L1>>>,
+    ) -> anyhow::Result<AggregatingPriceApiClient> {
+        let aggregation = config
+            .aggregation
+            .as_ref()
+            .context("aggregated price client requires `aggregation` config")?;
+        anyhow::ensure!(
+            aggregation.min_quorum.get() <= aggregation.sources.len(),
+            "aggregation quorum ({}) exceeds the number of price sources ({})",
+            aggregation.min_quorum,
+            aggregation.sources.len()
+        );
+
+        let mut client = AggregatingPriceApiClient::new(aggregation);
+        let mut kinds = vec![];
+        for source in &aggregation.sources {
+            let kind: Self = source.parse()?;
+            anyhow::ensure!(
+                !matches!(kind, Self::NoOp | Self::Aggregated),
+                "price source {source:?} cannot be aggregated"
+            );
+            anyhow::ensure!(
+                !kinds.contains(&kind),
+                "price source {source:?} is specified multiple times"
+            );
+            kinds.push(kind);
+
+            let source_config = match kind {
+                Self::CoinGecko => aggregation.coingecko.as_ref(),
+                Self::CoinMarketCap => aggregation.coinmarketcap.as_ref(),
+                _ => None,
+            };
+            let mut config = config.clone();
+            config.source = source.clone();
+            if let Some(source_config) = source_config {
+                config.base_url = source_config.base_url.clone();
+                config.api_key = source_config.api_key.clone();
+            }
+            client = client.with_source(
+                source.clone(),
+                kind.instantiate(config, eth_client.clone())?,
+            );
         }
+        Ok(client)
     }
 }
 
@@ -58,16 +136,22 @@ impl TryFrom<ExternalPriceApiClientConfig> for ExternalPriceApiLayer {
     }
 }
 
+#[derive(Debug, FromContext)]
+pub struct Input {
+    /// L1 client used by on-chain price sources.
+    eth_client: Option<Box<DynClient<L1>>>,
+}
+
 #[async_trait::async_trait]
 impl WiringLayer for ExternalPriceApiLayer {
-    type Input = ();
+    type Input = Input;
     type Output = Arc<dyn PriceApiClient>;
 
     fn layer_name(&self) -> &'static str {
         "external_price_api"
     }
 
-    async fn wire(self, (): Self::Input) -> Result<Self::Output, WiringError> {
-        Ok(self.kind.instantiate(self.config))
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        Ok(self.kind.instantiate(self.config, input.eth_client)?)
     }
 }
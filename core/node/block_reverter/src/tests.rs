@@ -235,6 +235,7 @@ async fn create_mock_snapshot(
         .add_snapshot(
             SnapshotVersion::Version0,
             l1_batch_number,
+            None,
             storage_logs_chunk_count,
             &factory_deps_key,
         )
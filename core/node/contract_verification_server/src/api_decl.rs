@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use tower_http::cors::CorsLayer;
 use zksync_dal::{ConnectionPool, Core};
+use zksync_types::L2ChainId;
 
 use crate::cache::SupportedCompilersCache;
 
@@ -10,18 +11,22 @@ pub(crate) struct RestApi {
     pub(crate) master_connection_pool: ConnectionPool<Core>,
     pub(crate) replica_connection_pool: ConnectionPool<Core>,
     pub(crate) supported_compilers: Arc<SupportedCompilersCache>,
+    /// Chain ID checked in Sourcify-compatible endpoints.
+    pub(crate) chain_id: L2ChainId,
 }
 
 impl RestApi {
     pub fn new(
         master_connection_pool: ConnectionPool<Core>,
         replica_connection_pool: ConnectionPool<Core>,
+        chain_id: L2ChainId,
     ) -> Self {
         let supported_compilers = SupportedCompilersCache::new(replica_connection_pool.clone());
         Self {
             supported_compilers: Arc::new(supported_compilers),
             master_connection_pool,
             replica_connection_pool,
+            chain_id,
         }
     }
 
@@ -48,6 +53,12 @@ impl RestApi {
                 "/contract_verification/vyper_versions",
                 axum::routing::get(Self::vyper_versions),
             )
+            // Etherscan API is also served on the same path as by Etherscan, so that tools like
+            // `forge verify-contract --verifier etherscan` can be pointed to `<server URL>/api`.
+            .route(
+                "/api",
+                axum::routing::get(Self::etherscan_get_action).post(Self::post),
+            )
             .route(
                 "/contract_verification/{id}",
                 axum::routing::get(Self::verification_request_status),
@@ -56,6 +67,20 @@ impl RestApi {
                 "/contract_verification/info/{address}",
                 axum::routing::get(Self::verification_info),
             )
+            // Subset of the Sourcify API v2, so that e.g. `forge verify-contract --verifier sourcify`
+            // can be pointed to the server URL.
+            .route(
+                "/v2/verify/metadata/{chain_id}/{address}",
+                axum::routing::post(Self::sourcify_verify_metadata),
+            )
+            .route(
+                "/v2/verify/{verification_id}",
+                axum::routing::get(Self::sourcify_verification_job),
+            )
+            .route(
+                "/v2/contract/{chain_id}/{address}",
+                axum::routing::get(Self::sourcify_contract),
+            )
             .layer(CorsLayer::permissive())
             .with_state(Arc::new(self))
     }
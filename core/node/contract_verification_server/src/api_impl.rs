@@ -21,6 +21,11 @@ use zksync_types::{
             EtherscanGetParams, EtherscanGetPayload, EtherscanPostPayload, EtherscanPostRequest,
             EtherscanResponse, EtherscanResult,
         },
+        sourcify::{
+            SourcifyContract, SourcifyError, SourcifyErrorResponse,
+            SourcifyMetadataVerificationRequest, SourcifyVerificationAccepted,
+            SourcifyVerificationJob,
+        },
     },
     Address,
 };
@@ -49,6 +54,8 @@ pub(crate) enum ApiError {
     Internal(anyhow::Error),
     DeserializationError(anyhow::Error),
     UnsupportedContentType,
+    UnsupportedChain(String),
+    InvalidSourcifyInput(SourcifyError),
 }
 
 impl From<anyhow::Error> for ApiError {
@@ -82,13 +89,13 @@ impl ApiError {
             Self::Internal(_) => "internal server error".into(),
             Self::UnsupportedContentType => "Specified content type is not supported".into(),
             Self::DeserializationError(e) => format!("Failed to deserialize the request: {}", e),
+            Self::UnsupportedChain(chain_id) => format!("chain {chain_id} is not supported"),
+            Self::InvalidSourcifyInput(err) => format!("invalid metadata or sources: {err}"),
         }
     }
-}
 
-impl IntoResponse for ApiError {
-    fn into_response(self) -> Response {
-        let status_code = match &self {
+    fn status_code(&self) -> StatusCode {
+        match self {
             Self::IncorrectCompilerVersions
             | Self::UnsupportedCompilerVersions
             | Self::MissingZkCompilerVersion
@@ -96,7 +103,9 @@ impl IntoResponse for ApiError {
             | Self::NoDeployedContract
             | Self::AlreadyVerified
             | Self::ActiveRequestExists(_)
-            | Self::DeserializationError(_) => StatusCode::BAD_REQUEST,
+            | Self::DeserializationError(_)
+            | Self::UnsupportedChain(_)
+            | Self::InvalidSourcifyInput(_) => StatusCode::BAD_REQUEST,
 
             Self::UnsupportedContentType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
 
@@ -107,8 +116,55 @@ impl IntoResponse for ApiError {
                 tracing::warn!("Internal error: {err:#}");
                 StatusCode::INTERNAL_SERVER_ERROR
             }
+        }
+    }
+
+    /// Returns an error code for Sourcify-compatible endpoints.
+    fn sourcify_code(&self) -> &'static str {
+        match self {
+            Self::UnsupportedCompilerVersions => "unsupported_compiler_version",
+            Self::NoDeployedContract => "contract_not_deployed",
+            Self::AlreadyVerified => "already_verified",
+            Self::ActiveRequestExists(_) => "duplicate_verification_request",
+            Self::RequestNotFound => "job_not_found",
+            Self::VerificationInfoNotFound => "not_found",
+            Self::UnsupportedChain(_) => "unsupported_chain",
+            Self::Internal(_) => "internal_error",
+            Self::IncorrectCompilerVersions
+            | Self::MissingZkCompilerVersion
+            | Self::BogusZkCompilerVersion
+            | Self::DeserializationError(_)
+            | Self::UnsupportedContentType
+            | Self::InvalidSourcifyInput(_) => "invalid_parameter",
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status_code(), self.message()).into_response()
+    }
+}
+
+/// Error returned by Sourcify-compatible endpoints. Unlike [`ApiError`], it is serialized as JSON
+/// in the format expected by Sourcify clients.
+#[derive(Debug)]
+pub(crate) struct SourcifyApiError(pub ApiError);
+
+impl From<ApiError> for SourcifyApiError {
+    fn from(err: ApiError) -> Self {
+        Self(err)
+    }
+}
+
+impl IntoResponse for SourcifyApiError {
+    fn into_response(self) -> Response {
+        let body = SourcifyErrorResponse {
+            custom_code: self.0.sourcify_code().to_owned(),
+            message: self.0.message(),
+            error_id: None,
         };
-        (status_code, self.message()).into_response()
+        (self.0.status_code(), Json(body)).into_response()
     }
 }
 
@@ -160,16 +216,20 @@ impl RestApi {
         let (parts, body) = request.into_parts();
         let headers: HeaderMap = parts.headers;
 
+        // Ignore media type parameters (e.g., `charset=UTF-8` sent by `hardhat-verify`).
         let content_type = headers
             .get("content-type")
             .and_then(|v| v.to_str().ok())
-            .unwrap_or("");
+            .and_then(|v| v.split(';').next())
+            .unwrap_or("")
+            .trim()
+            .to_ascii_lowercase();
 
         let body_bytes = to_bytes(body, usize::MAX)
             .await
             .map_err(|e| ApiError::Internal(anyhow::anyhow!(e)))?;
 
-        match content_type {
+        match content_type.as_str() {
             // ZKsync verification request in JSON format
             "application/json" => {
                 let req: VerificationIncomingRequest = serde_json::from_slice(&body_bytes)
@@ -216,12 +276,12 @@ impl RestApi {
         EtherscanResponse {
             status: "1".to_string(),
             message: "OK".to_string(),
-            result: EtherscanResult::SourceCode(
+            result: EtherscanResult::SourceCode(vec![
                 // Return the source code only for the target address, omit other matches.
                 verification_info
                     .filter(|info| info.request.req.contract_address == address)
                     .into(),
-            ),
+            ]),
         }
     }
 
@@ -474,6 +534,120 @@ impl RestApi {
         method_latency.observe();
         Ok(Json(info))
     }
+
+    fn validate_chain_id(&self, chain_id: &str) -> Result<(), ApiError> {
+        if chain_id == self.chain_id.as_u64().to_string() {
+            Ok(())
+        } else {
+            Err(ApiError::UnsupportedChain(chain_id.to_owned()))
+        }
+    }
+
+    /// Returns verification status of the contract in the Sourcify format. Only verification info stored
+    /// for the contract address is taken into account; this includes info propagated from another contract
+    /// with the same bytecode, but not partial matches by bytecode without metadata.
+    async fn sourcify_contract_status(
+        &self,
+        address: Address,
+    ) -> Result<SourcifyContract, ApiError> {
+        let info = self
+            .replica_connection_pool
+            .connection_tagged("api")
+            .await?
+            .contract_verification_dal()
+            .get_contract_verification_info(address)
+            .await?;
+        Ok(SourcifyContract::new(self.chain_id, address, info.as_ref()))
+    }
+
+    /// Sourcify-compatible handler accepting `metadata.json` and sources for verification.
+    #[tracing::instrument(skip(self_, request))]
+    pub async fn sourcify_verify_metadata(
+        State(self_): State<Arc<Self>>,
+        Path((chain_id, address)): Path<(String, Address)>,
+        Json(request): Json<SourcifyMetadataVerificationRequest>,
+    ) -> Result<(StatusCode, Json<SourcifyVerificationAccepted>), SourcifyApiError> {
+        let method_latency = METRICS.call[&"sourcify_verify_metadata"].start();
+        self_.validate_chain_id(&chain_id)?;
+        let request = request
+            .metadata
+            .to_verification_request(address, &request.sources)
+            .map_err(ApiError::InvalidSourcifyInput)?;
+        let verification_id = Self::verification(State(self_), request).await?;
+        method_latency.observe();
+        let response = SourcifyVerificationAccepted {
+            verification_id: verification_id.to_string(),
+        };
+        Ok((StatusCode::ACCEPTED, Json(response)))
+    }
+
+    /// Sourcify-compatible handler returning the status of a verification job.
+    #[tracing::instrument(skip(self_))]
+    pub async fn sourcify_verification_job(
+        State(self_): State<Arc<Self>>,
+        Path(id): Path<usize>,
+    ) -> Result<Json<SourcifyVerificationJob>, SourcifyApiError> {
+        let method_latency = METRICS.call[&"sourcify_verification_job"].start();
+        let mut conn = self_
+            .replica_connection_pool
+            .connection_tagged("api")
+            .await
+            .map_err(ApiError::from)?;
+        let mut dal = conn.contract_verification_dal();
+        let status = dal
+            .get_verification_request_status(id)
+            .await
+            .map_err(ApiError::from)?
+            .ok_or(ApiError::RequestNotFound)?;
+        let address = dal
+            .get_verification_request_address(id)
+            .await
+            .map_err(ApiError::from)?
+            .ok_or(ApiError::RequestNotFound)?;
+        drop(conn);
+
+        let verification_id = id.to_string();
+        let is_job_completed = matches!(status.status.as_str(), "successful" | "failed");
+        let (contract, error) = match status.status.as_str() {
+            "successful" => (self_.sourcify_contract_status(address).await?, None),
+            "failed" => {
+                let (custom_code, message) = match status.compilation_errors {
+                    Some(errors) => ("compilation_error", errors.join("\n")),
+                    None => ("no_match", status.error.unwrap_or_default()),
+                };
+                let error = SourcifyErrorResponse {
+                    custom_code: custom_code.to_owned(),
+                    message,
+                    error_id: Some(verification_id.clone()),
+                };
+                (
+                    SourcifyContract::new(self_.chain_id, address, None),
+                    Some(error),
+                )
+            }
+            _ => (SourcifyContract::new(self_.chain_id, address, None), None),
+        };
+        method_latency.observe();
+        Ok(Json(SourcifyVerificationJob {
+            is_job_completed,
+            verification_id,
+            contract,
+            error,
+        }))
+    }
+
+    /// Sourcify-compatible handler returning verification status of a contract.
+    #[tracing::instrument(skip(self_))]
+    pub async fn sourcify_contract(
+        State(self_): State<Arc<Self>>,
+        Path((chain_id, address)): Path<(String, Address)>,
+    ) -> Result<Json<SourcifyContract>, SourcifyApiError> {
+        let method_latency = METRICS.call[&"sourcify_contract"].start();
+        self_.validate_chain_id(&chain_id)?;
+        let contract = self_.sourcify_contract_status(address).await?;
+        method_latency.observe();
+        Ok(Json(contract))
+    }
 }
 
 /// Tries to do a lookup for partial match verification info.
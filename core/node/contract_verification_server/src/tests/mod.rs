@@ -2,6 +2,8 @@
 
 use std::{str, vec};
 
+use axum::http::StatusCode;
+use http_body_util::BodyExt as _;
 use test_casing::test_casing;
 use utils::{mock_verification_info, MockApiClient, MockContractVerifier};
 use zksync_types::{
@@ -12,8 +14,10 @@ use zksync_types::{
             EtherscanBoolean, EtherscanCodeFormat, EtherscanPostPayload, EtherscanPostRequest,
             EtherscanResult, EtherscanSourceCodeResponse, EtherscanVerificationRequest,
         },
+        sourcify::{SourcifyMatch, SourcifyVerificationAccepted},
     },
-    Address,
+    web3::keccak256,
+    Address, H256,
 };
 
 use super::*;
@@ -236,7 +240,7 @@ async fn submitting_etherscan_requests(bytecode_kind: BytecodeMarker) {
     assert_eq!(contract_source_code.message, "OK");
     assert_eq!(
         contract_source_code.result,
-        EtherscanResult::SourceCode(EtherscanSourceCodeResponse {
+        EtherscanResult::SourceCode(vec![EtherscanSourceCodeResponse {
             source_code:
                 "{\"codeFormat\":\"solidity-single-file\",\"sourceCode\":\"contract Test {}\"}"
                     .to_string(),
@@ -259,7 +263,7 @@ async fn submitting_etherscan_requests(bytecode_kind: BytecodeMarker) {
             implementation: Default::default(),
             swarm_source: Default::default(),
             similar_match: Default::default(),
-        })
+        }])
     );
 
     let contract_source_code = client.etherscan_get_abi(address).await;
@@ -285,6 +289,61 @@ async fn submitting_etherscan_requests(bytecode_kind: BytecodeMarker) {
     );
 }
 
+#[tokio::test]
+async fn submitting_etherscan_requests_like_hardhat_verify() {
+    let pool = ConnectionPool::test_pool().await;
+    let client = MockApiClient::new(pool.clone());
+    let mut storage = pool.connection().await.unwrap();
+    prepare_storage(&mut storage).await;
+    let address = Address::repeat_byte(0x23);
+    mock_deploy_contract(&mut storage, address, BytecodeMarker::Evm).await;
+
+    // Extra params (`apikey`, `chainid`) must be ignored, and constructor args may be `0x`-prefixed.
+    let address_str = format!("{address:?}");
+    let compiler_version = format!("v{SOLC_VERSION}+commit.01234567");
+    let form = serde_urlencoded::to_string([
+        ("apikey", "unused"),
+        ("chainid", "270"),
+        ("module", "contract"),
+        ("action", "verifysourcecode"),
+        ("contractaddress", address_str.as_str()),
+        ("sourceCode", "contract Test {}"),
+        ("codeformat", "solidity-single-file"),
+        ("contractname", "Test"),
+        ("compilerversion", compiler_version.as_str()),
+        ("optimizationUsed", "1"),
+        ("runs", "200"),
+        ("constructorArguements", "0x"),
+    ])
+    .unwrap();
+    let response = client
+        .send_raw_etherscan_form(form, "application/x-www-form-urlencoded;charset=UTF-8")
+        .await;
+    assert_eq!(response.status, "1", "{response:?}");
+    assert_eq!(response.result, EtherscanResult::String("1".to_string()));
+
+    let response = client
+        .etherscan_get("chainid=270&module=contract&action=checkverifystatus&guid=1")
+        .await;
+    assert_eq!(
+        response.result,
+        EtherscanResult::String("Pending in queue".to_string())
+    );
+
+    // Unverified contracts are reported in a single-element array, like Etherscan does.
+    let response = client
+        .etherscan_get(&format!(
+            "module=contract&action=getsourcecode&address={:?}",
+            Address::repeat_byte(0x42)
+        ))
+        .await;
+    let EtherscanResult::SourceCode(source_code) = response.result else {
+        panic!("unexpected response: {response:?}");
+    };
+    assert_eq!(source_code.len(), 1);
+    assert_eq!(source_code[0].abi, "Contract source code not verified");
+}
+
 #[test_casing(2, [BytecodeMarker::EraVm, BytecodeMarker::Evm])]
 #[tokio::test]
 async fn partial_verification(bytecode_kind: BytecodeMarker) {
@@ -493,3 +552,101 @@ async fn mismatched_compiler_type() {
         )
         .await;
 }
+
+fn mock_sourcify_request(source: &str) -> serde_json::Value {
+    let source_hash = H256(keccak256(source.as_bytes()));
+    serde_json::json!({
+        "sources": { "contracts/Test.sol": source },
+        "metadata": {
+            "compiler": { "version": format!("{SOLC_VERSION}+commit.e11b9ed9") },
+            "language": "Solidity",
+            "settings": {
+                "compilationTarget": { "contracts/Test.sol": "Test" },
+                "optimizer": { "enabled": true, "runs": 200 },
+            },
+            "sources": {
+                "contracts/Test.sol": { "keccak256": source_hash },
+            },
+            "version": 1,
+        },
+    })
+}
+
+#[tokio::test]
+async fn submitting_sourcify_request() {
+    let pool = ConnectionPool::test_pool().await;
+    let contract_verifier = MockContractVerifier::new(pool.clone());
+    let client = MockApiClient::new(pool.clone());
+    let mut storage = pool.connection().await.unwrap();
+    prepare_storage(&mut storage).await;
+
+    let address = Address::repeat_byte(0x23);
+    mock_deploy_contract(&mut storage, address, BytecodeMarker::Evm).await;
+    let contract = client.sourcify_contract(address).await;
+    assert_eq!(contract.match_kind, None);
+    assert_eq!(contract.chain_id, "270");
+
+    let request = mock_sourcify_request("contract Test {}");
+    let response = client
+        .sourcify_verify_metadata(271, address, &request)
+        .await;
+    MockApiClient::assert_sourcify_error(response, StatusCode::BAD_REQUEST, "unsupported_chain")
+        .await;
+    // Source hash in the metadata doesn't match the provided source.
+    let mut invalid_request = mock_sourcify_request("contract Other {}");
+    invalid_request["sources"] = request["sources"].clone();
+    let response = client
+        .sourcify_verify_metadata(270, address, &invalid_request)
+        .await;
+    MockApiClient::assert_sourcify_error(response, StatusCode::BAD_REQUEST, "invalid_parameter")
+        .await;
+
+    let response = client
+        .sourcify_verify_metadata(270, address, &request)
+        .await;
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let accepted: SourcifyVerificationAccepted = serde_json::from_slice(&body).unwrap();
+    assert_eq!(accepted.verification_id, "1");
+
+    let job = client.sourcify_verification_job("1").await;
+    assert!(!job.is_job_completed);
+    assert_eq!(job.contract.address, address);
+    assert_eq!(job.contract.match_kind, None);
+
+    let stored_request = storage
+        .contract_verification_dal()
+        .get_next_queued_verification_request(std::time::Duration::from_secs(600))
+        .await
+        .unwrap()
+        .expect("request not persisted");
+    assert_eq!(stored_request.req.contract_name, "contracts/Test.sol:Test");
+    assert!(
+        stored_request
+            .req
+            .evm_specific
+            .constructor_arguments_from_deployment
+    );
+    let mut verification_info = mock_verification_info(
+        1,
+        &serde_json::json!({
+            "contractAddress": address,
+            "sourceCode": "contract Test {}",
+            "contractName": "Test",
+            "compilerSolcVersion": SOLC_VERSION,
+            "optimizationUsed": true,
+        }),
+        None,
+    );
+    verification_info.request = stored_request;
+    contract_verifier.verify_contract(verification_info).await;
+
+    let job = client.sourcify_verification_job("1").await;
+    assert!(job.is_job_completed);
+    assert!(job.error.is_none());
+    assert_eq!(job.contract.match_kind, Some(SourcifyMatch::ExactMatch));
+    let contract = client.sourcify_contract(address).await;
+    assert_eq!(contract.match_kind, Some(SourcifyMatch::ExactMatch));
+    assert_eq!(contract.runtime_match, Some(SourcifyMatch::ExactMatch));
+    assert!(contract.verified_at.is_some());
+}
@@ -18,8 +18,9 @@ use zksync_types::{
             VerificationRequest, VerificationRequestStatus,
         },
         etherscan::EtherscanResponse,
+        sourcify::{SourcifyContract, SourcifyErrorResponse, SourcifyVerificationJob},
     },
-    get_code_key, Address, L2BlockNumber, ProtocolVersion, StorageLog, H256,
+    get_code_key, Address, L2BlockNumber, L2ChainId, ProtocolVersion, StorageLog, H256,
 };
 
 use crate::{api_impl::ApiError, RestApi};
@@ -83,6 +84,7 @@ pub(super) fn mock_verification_info(
             deployed_bytecode: None,
             abi: abi.unwrap_or_default(),
             immutable_refs: Default::default(),
+            metadata: None,
         },
         verified_at: Default::default(),
         verification_problems: Vec::new(),
@@ -164,7 +166,7 @@ pub(super) struct MockApiClient {
 impl MockApiClient {
     pub fn new(pool: ConnectionPool<Core>) -> Self {
         Self {
-            router: RestApi::new(pool.clone(), pool).into_router(),
+            router: RestApi::new(pool.clone(), pool, L2ChainId::default()).into_router(),
         }
     }
 
@@ -183,6 +185,27 @@ impl MockApiClient {
         Self::json_response::<EtherscanResponse>(response).await
     }
 
+    /// Sends a raw form to the Etherscan-compatible `/api` endpoint with the specified content type.
+    pub async fn send_raw_etherscan_form(
+        &self,
+        form: String,
+        content_type: &str,
+    ) -> EtherscanResponse {
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/api?chainid=270")
+            .header(header::CONTENT_TYPE, content_type)
+            .body(Body::from(form))
+            .unwrap();
+        let response = self.router.clone().oneshot(req).await.unwrap();
+        Self::json_response::<EtherscanResponse>(response).await
+    }
+
+    pub async fn etherscan_get(&self, query: &str) -> EtherscanResponse {
+        let response = self.send_request(&format!("/api?{query}"), None).await;
+        Self::json_response::<EtherscanResponse>(response).await
+    }
+
     pub async fn etherscan_get_verification_status(&self, id: usize) -> EtherscanResponse {
         let response = self
             .send_request(
@@ -278,6 +301,43 @@ impl MockApiClient {
         Self::json_response::<Vec<String>>(response).await
     }
 
+    pub async fn sourcify_verify_metadata(
+        &self,
+        chain_id: u64,
+        address: Address,
+        request: &serde_json::Value,
+    ) -> Response<Body> {
+        self.send_request(
+            &format!("/v2/verify/metadata/{chain_id}/{address:?}"),
+            Some(request),
+        )
+        .await
+    }
+
+    pub async fn sourcify_verification_job(&self, id: &str) -> SourcifyVerificationJob {
+        let response = self.send_request(&format!("/v2/verify/{id}"), None).await;
+        Self::json_response::<SourcifyVerificationJob>(response).await
+    }
+
+    pub async fn sourcify_contract(&self, address: Address) -> SourcifyContract {
+        let response = self
+            .send_request(&format!("/v2/contract/270/{address:?}"), None)
+            .await;
+        Self::json_response::<SourcifyContract>(response).await
+    }
+
+    pub async fn assert_sourcify_error(
+        response: Response<Body>,
+        expected_status: StatusCode,
+        expected_code: &str,
+    ) {
+        assert_eq!(response.status(), expected_status);
+        let body = response.collect().await.unwrap().to_bytes();
+        let error: SourcifyErrorResponse =
+            serde_json::from_slice(&body).expect("Unable to deserialize error");
+        assert_eq!(error.custom_code, expected_code, "{error:?}");
+    }
+
     async fn send_request(&self, url: &str, body: Option<&serde_json::Value>) -> Response<Body> {
         let (method, body) = match body {
             Some(body) => (Method::POST, Body::from(serde_json::to_vec(body).unwrap())),
@@ -10,6 +10,7 @@ use zksync_da_client::{
     DataAvailabilityClient,
 };
 use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_node_framework::control::PauseSwitch;
 use zksync_types::{l2_to_l1_log::L2ToL1Log, Address, L1BatchNumber, H256};
 
 use crate::metrics::METRICS;
@@ -21,6 +22,8 @@ pub struct DataAvailabilityDispatcher {
     config: DADispatcherConfig,
     l2_contracts: L2Contracts,
     transitional_l2_da_validator_address: Option<Address>, // set only if inclusion_verification_transition_enabled is true
+    /// Pauses dispatching new blobs. Already dispatched blobs are still polled for inclusion.
+    pause_switch: PauseSwitch,
 }
 
 impl DataAvailabilityDispatcher {
@@ -36,9 +39,15 @@ impl DataAvailabilityDispatcher {
             client,
             l2_contracts,
             transitional_l2_da_validator_address: None,
+            pause_switch: PauseSwitch::default(),
         }
     }
 
+    /// Returns the switch pausing dispatching new blobs.
+    pub fn pause_switch(&self) -> PauseSwitch {
+        self.pause_switch.clone()
+    }
+
     pub async fn run(mut self, mut stop_receiver: Receiver<bool>) -> anyhow::Result<()> {
         self.check_for_misconfiguration().await?;
         let self_arc_dispatch = Arc::new(self.clone());
@@ -54,7 +63,9 @@ impl DataAvailabilityDispatcher {
                     break;
                 }
 
-                if let Err(err) = self_arc_dispatch.dispatch().await {
+                if self_arc_dispatch.pause_switch.is_paused() {
+                    tracing::debug!("da_dispatcher is paused; skipping dispatching blobs");
+                } else if let Err(err) = self_arc_dispatch.dispatch().await {
                     tracing::error!("dispatch error {err:?}");
                 }
 
@@ -1,7 +1,10 @@
+use std::sync::Arc;
+
 use zksync_config::configs::{chain::StateKeeperConfig, da_dispatcher::DADispatcherConfig};
 use zksync_da_client::DataAvailabilityClient;
 use zksync_dal::node::{MasterPool, PoolResource};
 use zksync_node_framework::{
+    control::{NodeControl, Pausable, PauseSwitch},
     service::StopReceiver,
     task::{Task, TaskId},
     wiring_layer::{WiringError, WiringLayer},
@@ -23,6 +26,8 @@ pub struct Input {
     master_pool: PoolResource<MasterPool>,
     da_client: Box<dyn DataAvailabilityClient>,
     l2_contracts: L2ContractsResource,
+    #[context(default)]
+    node_control: Arc<NodeControl>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -75,6 +80,7 @@ impl WiringLayer for DataAvailabilityDispatcherLayer {
             da_client,
             input.l2_contracts.0,
         );
+        input.node_control.register_pausable(&da_dispatcher_task);
 
         Ok(Output { da_dispatcher_task })
     }
@@ -90,3 +96,9 @@ impl Task for DataAvailabilityDispatcher {
         (*self).run(stop_receiver.0).await
     }
 }
+
+impl Pausable for DataAvailabilityDispatcher {
+    fn pause_switch(&self) -> PauseSwitch {
+        self.pause_switch()
+    }
+}
@@ -0,0 +1,147 @@
+//! Archiving of pruned data to an object store.
+
+use std::{collections::HashMap, ops, sync::Arc, time::Instant};
+
+use anyhow::Context as _;
+use zksync_dal::{Connection, Core, CoreDal};
+use zksync_object_store::ObjectStore;
+use zksync_types::{
+    api::GetLogsFilter,
+    pruning::{
+        ArchivedL2Block, ArchivedStorageLog, PrunedL2BlocksArchive, PrunedL2BlocksArchiveKey,
+    },
+    L2BlockNumber, L2ChainId,
+};
+
+use crate::metrics::METRICS;
+
+/// Exports L2 blocks that are about to be pruned to an object store.
+#[derive(Debug)]
+pub(crate) struct PruningArchiver {
+    object_store: Arc<dyn ObjectStore>,
+    l2_chain_id: L2ChainId,
+}
+
+impl PruningArchiver {
+    pub(crate) fn new(object_store: Arc<dyn ObjectStore>, l2_chain_id: L2ChainId) -> Self {
+        Self {
+            object_store,
+            l2_chain_id,
+        }
+    }
+
+    /// Archives the specified range of L2 blocks and records the archive, together with hashes of archived blocks,
+    /// in Postgres. Blocks in the range that are not present in Postgres (e.g., ones preceding the snapshot
+    /// the node was recovered from) are skipped.
+    ///
+    /// The entire archive is assembled in memory, so the range should be reasonably small.
+    pub(crate) async fn archive(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+    ) -> anyhow::Result<()> {
+        let started_at = Instant::now();
+        let archive = self.load_archive(storage, l2_blocks.clone()).await?;
+        let key = PrunedL2BlocksArchiveKey::from(l2_blocks);
+        self.object_store
+            .put(key, &archive)
+            .await
+            .with_context(|| format!("failed persisting pruning archive {key:?}"))?;
+
+        // Block hashes are persisted since otherwise, pruned blocks couldn't be looked up by hash.
+        let block_hashes: Vec<_> = archive
+            .l2_blocks
+            .iter()
+            .map(|block| (L2BlockNumber(block.block.number.as_u32()), block.block.hash))
+            .collect();
+        let mut pruning_dal = storage.pruning_dal();
+        pruning_dal
+            .insert_pruned_l2_block_hashes(&block_hashes)
+            .await?;
+        pruning_dal.insert_pruning_archive(key).await?;
+
+        let latency = started_at.elapsed();
+        METRICS
+            .archived_l2_blocks
+            .inc_by(archive.l2_blocks.len() as u64);
+        METRICS.archiving_latency.observe(latency);
+        tracing::info!(
+            "Archived {} L2 blocks to {key:?}, operation took {latency:?}",
+            archive.l2_blocks.len()
+        );
+        Ok(())
+    }
+
+    async fn load_archive(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+    ) -> anyhow::Result<PrunedL2BlocksArchive> {
+        let filter = GetLogsFilter {
+            from_block: *l2_blocks.start(),
+            to_block: *l2_blocks.end(),
+            addresses: vec![],
+            topics: vec![],
+        };
+        // The limit is bound as a 32-bit integer in the underlying query.
+        let logs = storage
+            .events_web3_dal()
+            .get_logs(filter, i32::MAX as usize)
+            .await?;
+        let mut logs_by_block = HashMap::<_, Vec<_>>::new();
+        for log in logs {
+            let block_number = log.block_number.context("log without block number")?;
+            logs_by_block
+                .entry(block_number.as_u32())
+                .or_default()
+                .push(log);
+        }
+
+        let storage_logs = storage
+            .storage_logs_dal()
+            .get_storage_logs_in_l2_blocks(l2_blocks.clone())
+            .await?;
+        let mut storage_logs_by_block = HashMap::<_, Vec<_>>::new();
+        for log in storage_logs {
+            storage_logs_by_block
+                .entry(log.l2_block_number.0)
+                .or_default()
+                .push(ArchivedStorageLog {
+                    hashed_key: log.hashed_key,
+                    value: log.value,
+                });
+        }
+
+        let mut archived_blocks = vec![];
+        for number in l2_blocks.start().0..=l2_blocks.end().0 {
+            let Some(block) = storage
+                .blocks_web3_dal()
+                .get_api_block(L2BlockNumber(number))
+                .await?
+            else {
+                continue;
+            };
+            let mut transactions = storage
+                .transactions_web3_dal()
+                .get_transactions(&block.transactions, self.l2_chain_id)
+                .await?;
+            anyhow::ensure!(
+                transactions.len() == block.transactions.len(),
+                "storage inconsistency: L2 block #{number} has {} tx hashes, but only {} transactions were loaded",
+                block.transactions.len(),
+                transactions.len()
+            );
+            transactions.sort_unstable_by_key(|tx| tx.transaction_index);
+
+            archived_blocks.push(ArchivedL2Block {
+                block,
+                transactions,
+                logs: logs_by_block.remove(&number).unwrap_or_default(),
+                storage_logs: storage_logs_by_block.remove(&number).unwrap_or_default(),
+            });
+        }
+        Ok(PrunedL2BlocksArchive {
+            l2_blocks: archived_blocks,
+        })
+    }
+}
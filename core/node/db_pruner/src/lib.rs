@@ -13,9 +13,11 @@ use zksync_dal::{
     Connection, ConnectionPool, Core, CoreDal,
 };
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
-use zksync_types::{L1BatchNumber, L2BlockNumber, OrStopped};
+use zksync_object_store::ObjectStore;
+use zksync_types::{L1BatchNumber, L2BlockNumber, L2ChainId, OrStopped};
 
 use self::{
+    archive::PruningArchiver,
     metrics::{ConditionOutcome, PruneType, METRICS},
     prune_conditions::{
         ConsistencyCheckerProcessedBatch, L1BatchExistsCondition, L1BatchOlderThanPruneCondition,
@@ -23,6 +25,7 @@ use self::{
     },
 };
 
+mod archive;
 mod metrics;
 pub mod node;
 mod prune_conditions;
@@ -81,6 +84,7 @@ pub struct DbPruner {
     connection_pool: ConnectionPool<Core>,
     health_updater: HealthUpdater,
     prune_conditions: Vec<Arc<dyn PruneCondition>>,
+    archiver: Option<PruningArchiver>,
 }
 
 impl DbPruner {
@@ -120,9 +124,20 @@ impl DbPruner {
             connection_pool,
             health_updater: ReactiveHealthCheck::new("db_pruner").1,
             prune_conditions,
+            archiver: None,
         }
     }
 
+    /// Enables archiving L2 blocks to the specified object store before they are pruned.
+    pub fn with_archive(
+        mut self,
+        object_store: Arc<dyn ObjectStore>,
+        l2_chain_id: L2ChainId,
+    ) -> Self {
+        self.archiver = Some(PruningArchiver::new(object_store, l2_chain_id));
+        self
+    }
+
     pub fn health_check(&self) -> ReactiveHealthCheck {
         self.health_updater.subscribe()
     }
@@ -204,6 +219,18 @@ impl DbPruner {
             .get_l2_block_range_of_l1_batch(next_l1_batch_to_prune)
             .await?
             .with_context(|| format!("L1 batch #{next_l1_batch_to_prune} is ready to be pruned, but has no L2 blocks"))?;
+        if let Some(archiver) = &self.archiver {
+            // Archiving before soft pruning ensures that the API server never observes pruned L2 blocks without an archive.
+            let first_l2_block_to_archive = current_pruning_info
+                .last_soft_pruned
+                .map_or(L2BlockNumber(0), |info| info.l2_block + 1);
+            archiver
+                .archive(
+                    &mut transaction,
+                    first_l2_block_to_archive..=next_l2_block_to_prune,
+                )
+                .await?;
+        }
         transaction
             .pruning_dal()
             .insert_soft_pruning_log(next_l1_batch_to_prune, next_l2_block_to_prune)
@@ -58,6 +58,11 @@ pub(super) struct DbPrunerMetrics {
     deleted_entities: Family<PrunedEntityType, Histogram<u64>>,
     /// Number of times a certain condition has resulted in a specific outcome (succeeded, failed, or errored).
     condition_outcomes: Family<ConditionOutcomeLabels, Counter>,
+    /// Number of L2 blocks archived to the object store.
+    pub archived_l2_blocks: Counter,
+    /// Latency of archiving a chunk of L2 blocks to the object store.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub archiving_latency: Histogram<Duration>,
 }
 
 impl DbPrunerMetrics {
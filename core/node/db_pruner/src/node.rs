@@ -1,5 +1,6 @@
 use std::{sync::Arc, time::Duration};
 
+use zksync_config::ObjectStoreConfig;
 use zksync_dal::node::{MasterPool, PoolResource};
 use zksync_health_check::AppHealthCheck;
 use zksync_node_framework::{
@@ -8,6 +9,8 @@ use zksync_node_framework::{
     wiring_layer::{WiringError, WiringLayer},
     FromContext, IntoContext,
 };
+use zksync_object_store::ObjectStoreFactory;
+use zksync_types::L2ChainId;
 
 use crate::{DbPruner, DbPrunerConfig};
 
@@ -17,6 +20,7 @@ pub struct PruningLayer {
     pruning_removal_delay: Duration,
     pruning_chunk_size: u32,
     minimum_l1_batch_age: Duration,
+    archive: Option<(ObjectStoreConfig, L2ChainId)>,
 }
 
 #[derive(Debug, FromContext)]
@@ -42,8 +46,15 @@ impl PruningLayer {
             pruning_removal_delay,
             pruning_chunk_size,
             minimum_l1_batch_age,
+            archive: None,
         }
     }
+
+    /// Sets the object store to archive L2 blocks to before they are pruned.
+    pub fn with_archive(mut self, config: ObjectStoreConfig, l2_chain_id: L2ChainId) -> Self {
+        self.archive = Some((config, l2_chain_id));
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -58,7 +69,7 @@ impl WiringLayer for PruningLayer {
     async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
         let main_pool = input.master_pool.get().await?;
 
-        let db_pruner = DbPruner::new(
+        let mut db_pruner = DbPruner::new(
             DbPrunerConfig {
                 removal_delay: self.pruning_removal_delay,
                 pruned_batch_chunk_size: self.pruning_chunk_size,
@@ -66,6 +77,10 @@ impl WiringLayer for PruningLayer {
             },
             main_pool,
         );
+        if let Some((config, l2_chain_id)) = self.archive {
+            let object_store = ObjectStoreFactory::new(config).create_store().await?;
+            db_pruner = db_pruner.with_archive(object_store, l2_chain_id);
+        }
 
         input
             .app_health
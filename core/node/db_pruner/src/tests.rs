@@ -11,8 +11,11 @@ use zksync_node_test_utils::{
     create_l1_batch, create_l1_batch_metadata, create_l2_block,
     l1_batch_metadata_to_commitment_artifacts,
 };
+use zksync_object_store::MockObjectStore;
 use zksync_types::{
-    aggregated_operations::L1BatchAggregatedActionType, eth_sender::EthTxFinalityStatus,
+    aggregated_operations::L1BatchAggregatedActionType,
+    eth_sender::EthTxFinalityStatus,
+    pruning::{PrunedL2BlocksArchive, PrunedL2BlocksArchiveKey},
     L2BlockNumber, ProtocolVersion, SLChainId, H256, U256,
 };
 
@@ -262,6 +265,70 @@ async fn unconstrained_pruner_with_fresh_database() {
     );
 }
 
+#[test(tokio::test)]
+async fn pruner_with_archive() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    insert_l2_blocks(&mut conn, 10, 2).await;
+
+    let object_store = MockObjectStore::arc();
+    let pruner = DbPruner::with_conditions(
+        DbPrunerConfig {
+            removal_delay: Duration::ZERO,
+            pruned_batch_chunk_size: 3,
+            minimum_l1_batch_age: Duration::ZERO,
+        },
+        pool.clone(),
+        vec![], //No checks, so every batch is prunable
+    )
+    .with_archive(object_store.clone(), L2ChainId::default());
+
+    let (_stop_sender, mut stop_receiver) = watch::channel(false);
+    for _ in 0..2 {
+        pruner
+            .run_single_iteration(&mut stop_receiver)
+            .await
+            .unwrap();
+    }
+    assert_eq!(
+        test_pruning_info(6, 13),
+        conn.pruning_dal().get_pruning_info().await.unwrap()
+    );
+
+    let archive_keys = conn
+        .pruning_dal()
+        .get_pruning_archives(L2BlockNumber(0)..=L2BlockNumber(u32::MAX))
+        .await
+        .unwrap();
+    let expected_keys = [
+        PrunedL2BlocksArchiveKey::from(L2BlockNumber(0)..=L2BlockNumber(7)),
+        PrunedL2BlocksArchiveKey::from(L2BlockNumber(8)..=L2BlockNumber(13)),
+    ];
+    assert_eq!(archive_keys, expected_keys);
+
+    for key in archive_keys {
+        let archive: PrunedL2BlocksArchive = object_store.get(key).await.unwrap();
+        let block_numbers: Vec<_> = archive
+            .l2_blocks
+            .iter()
+            .map(|block| L2BlockNumber(block.block.number.as_u32()))
+            .collect();
+        let expected_numbers: Vec<_> = (key.first_l2_block.0..=key.last_l2_block.0)
+            .map(L2BlockNumber)
+            .collect();
+        assert_eq!(block_numbers, expected_numbers);
+
+        for block in &archive.l2_blocks {
+            let number = conn
+                .pruning_dal()
+                .get_pruned_l2_block_number(block.block.hash)
+                .await
+                .unwrap();
+            assert_eq!(number, Some(L2BlockNumber(block.block.number.as_u32())));
+        }
+    }
+}
+
 #[test(tokio::test)]
 async fn pruning_blocked_after_first_chunk() {
     let pool = ConnectionPool::<Core>::test_pool().await;
@@ -10,7 +10,8 @@ use zksync_eth_client::{
 use zksync_types::web3;
 use zksync_types::{
     eth_sender::{EthTx, EthTxBlobSidecar, L1BlockNumbers},
-    Address, Nonce, EIP_1559_TX_TYPE, EIP_4844_TX_TYPE, EIP_712_TX_TYPE, H256, U256,
+    web3::{BlockId, BlockNumber},
+    Address, L1BlockNumber, Nonce, EIP_1559_TX_TYPE, EIP_4844_TX_TYPE, EIP_712_TX_TYPE, H256, U256,
 };
 
 use crate::EthSenderError;
@@ -86,6 +87,13 @@ pub(super) trait AbstractL1Interface: 'static + Sync + Send + fmt::Debug {
         &self,
         operator_type: OperatorType,
     ) -> Result<L1BlockNumbers, EthSenderError>;
+
+    /// Returns the timestamp of the specified L1 block, or `None` if the block is not available.
+    async fn get_l1_block_timestamp(
+        &self,
+        block_number: L1BlockNumber,
+        operator_type: OperatorType,
+    ) -> EnrichedClientResult<Option<u64>>;
 }
 
 #[derive(Debug)]
@@ -269,4 +277,16 @@ impl AbstractL1Interface for RealL1Interface {
             .await
             .map_err(Into::into)
     }
+
+    async fn get_l1_block_timestamp(
+        &self,
+        block_number: L1BlockNumber,
+        operator_type: OperatorType,
+    ) -> EnrichedClientResult<Option<u64>> {
+        let block = self
+            .query_client(operator_type)
+            .block(BlockId::Number(BlockNumber::Number(block_number.0.into())))
+            .await?;
+        Ok(block.map(|block| block.timestamp.as_u64()))
+    }
 }
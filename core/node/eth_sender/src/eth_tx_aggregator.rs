@@ -17,6 +17,7 @@ use zksync_l1_contract_interface::{
     multicall3::{Multicall3Call, Multicall3Result},
     Tokenizable, Tokenize,
 };
+use zksync_node_framework::control::PauseSwitch;
 use zksync_shared_metrics::L1Stage;
 use zksync_types::{
     aggregated_operations::{
@@ -106,6 +107,8 @@ pub struct EthTxAggregator {
     settlement_layer: Option<SettlementLayer>,
     initial_pending_nonces: HashMap<Address, u64>,
     needs_to_check_precommit: bool,
+    /// Pauses aggregation of all operations; initialized from `tx_aggregation_paused` config param.
+    pause_switch: PauseSwitch,
 }
 
 struct TxData {
@@ -144,6 +147,7 @@ impl EthTxAggregator {
         }
 
         let sl_chain_id = (*eth_client).as_ref().fetch_chain_id().await.unwrap();
+        let pause_switch = PauseSwitch::new(config.tx_aggregation_paused);
 
         Self {
             config,
@@ -163,6 +167,7 @@ impl EthTxAggregator {
             settlement_layer,
             initial_pending_nonces,
             needs_to_check_precommit: true,
+            pause_switch,
         }
     }
 
@@ -806,7 +811,7 @@ impl EthTxAggregator {
             // We only disable commit operations, the rest are allowed
         }
 
-        if self.config.tx_aggregation_paused {
+        if self.pause_switch.is_paused() {
             let reason = Some("tx aggregation is paused");
             op_restrictions.commit_restriction = reason;
             op_restrictions.prove_restriction = reason;
@@ -1333,6 +1338,11 @@ impl EthTxAggregator {
         self.health_updater.subscribe()
     }
 
+    /// Returns the switch pausing aggregation of all operations.
+    pub fn pause_switch(&self) -> PauseSwitch {
+        self.pause_switch.clone()
+    }
+
     async fn gateway_status(&self, storage: &mut Connection<'_, Core>) -> GatewayMigrationState {
         let notification = storage
             .server_notifications_dal()
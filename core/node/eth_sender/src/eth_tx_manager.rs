@@ -18,6 +18,7 @@ use zksync_types::{
     Address, L1BlockNumber, GATEWAY_CALLDATA_PROCESSING_ROLLUP_OVERHEAD_GAS, H256,
     L1_CALLDATA_PROCESSING_ROLLUP_OVERHEAD_GAS, L1_GAS_PER_PUBDATA_BYTE, U256,
 };
+use zksync_vlog::opentelemetry::tx_lifecycle::{self, TxStage, TxTrace};
 
 use super::{metrics::METRICS, EthSenderError};
 use crate::{
@@ -679,6 +680,10 @@ impl EthTxManager {
         METRICS
             .track_eth_tx_metrics(storage, L1Stage::Mined, tx)
             .await;
+        if eth_tx_finality_status == EthTxFinalityStatus::Finalized && tx_lifecycle::is_enabled() {
+            self.record_tx_lifecycle_spans(storage, tx, &tx_status)
+                .await;
+        }
 
         tracing::info!(
             "eth_tx {} with hash {tx_hash:?} for {} is {:?}. Gas spent: {gas_used:?}",
@@ -716,6 +721,86 @@ impl EthTxManager {
         METRICS.l1_blocks_waited_in_mempool[&tx_type_label].observe(waited_blocks.into());
     }
 
+    /// Records lifecycle spans for all transactions in L1 batches processed by the finalized `eth_tx`.
+    /// The stage span starts when the `eth_tx` is created and ends when it's included into an L1 block;
+    /// waiting for finality after that is recorded as a separate span.
+    async fn record_tx_lifecycle_spans(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        tx: &EthTx,
+        tx_status: &ExecutedTxStatus,
+    ) {
+        let stage = match tx.tx_type {
+            AggregatedActionType::L1Batch(L1BatchAggregatedActionType::Commit) => {
+                TxStage::L1BatchCommit
+            }
+            AggregatedActionType::L1Batch(L1BatchAggregatedActionType::PublishProofOnchain) => {
+                TxStage::L1BatchProve
+            }
+            AggregatedActionType::L1Batch(L1BatchAggregatedActionType::Execute) => {
+                TxStage::L1BatchExecute
+            }
+            AggregatedActionType::L2Block(_) => return,
+        };
+        let tx_hashes = match storage
+            .blocks_dal()
+            .get_tx_hashes_for_eth_tx_id(tx.id)
+            .await
+        {
+            Ok(tx_hashes) => tx_hashes,
+            Err(err) => {
+                tracing::warn!(
+                    "Failed loading transaction hashes for eth_tx {}: {err}",
+                    tx.id
+                );
+                return;
+            }
+        };
+
+        let l1_block_number = L1BlockNumber(tx_status.receipt.block_number.unwrap().as_u32());
+        let included_at = match self
+            .l1_interface
+            .get_l1_block_timestamp(l1_block_number, self.operator_type(tx))
+            .await
+        {
+            Ok(Some(timestamp)) => SystemTime::UNIX_EPOCH + Duration::from_secs(timestamp),
+            Ok(None) => {
+                tracing::warn!(
+                    "L1 block #{l1_block_number} with eth_tx {} is missing",
+                    tx.id
+                );
+                return;
+            }
+            Err(err) => {
+                tracing::warn!(
+                    "Failed loading L1 block #{l1_block_number} with eth_tx {}: {err}",
+                    tx.id
+                );
+                return;
+            }
+        };
+
+        let started_at = SystemTime::UNIX_EPOCH + Duration::from_secs(tx.created_at_timestamp);
+        let finalized_at = SystemTime::now();
+        let l1_tx_hash = format!("{:?}", tx_status.tx_hash);
+        for (l1_batch_number, tx_hash) in tx_hashes {
+            let trace = TxTrace::new(tx_hash.0);
+            trace
+                .start_at(stage, started_at)
+                .with_attribute("l1_batch.number", i64::from(l1_batch_number.0))
+                .with_attribute("eth_tx.id", i64::from(tx.id))
+                .with_attribute("eth_tx.hash", l1_tx_hash.clone())
+                .with_attribute("l1_block.number", i64::from(l1_block_number.0))
+                .finish_at(included_at);
+            trace
+                .start_at(TxStage::L1Finality, included_at)
+                .with_attribute("l1_batch.number", i64::from(l1_batch_number.0))
+                .with_attribute("eth_tx.id", i64::from(tx.id))
+                .with_attribute("eth_tx.type", tx.tx_type.to_string())
+                .finish_at(finalized_at);
+        }
+    }
+
     pub async fn run(mut self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         self.health_updater
             .update(Health::from(HealthStatus::Ready));
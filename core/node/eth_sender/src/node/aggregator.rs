@@ -13,6 +13,7 @@ use zksync_eth_client::{
 };
 use zksync_health_check::AppHealthCheck;
 use zksync_node_framework::{
+    control::{NodeControl, Pausable, PauseSwitch},
     service::StopReceiver,
     task::{Task, TaskId},
     wiring_layer::{WiringError, WiringLayer},
@@ -37,6 +38,7 @@ use crate::{Aggregator, EthTxAggregator};
 /// - `BoundEthInterfaceForBlobsResource` (optional)
 /// - `ObjectStoreResource`
 /// - `CircuitBreakersResource` (adds a circuit breaker)
+/// - `NodeControl` (registers the task as pausable)
 ///
 /// ## Adds tasks
 ///
@@ -61,6 +63,8 @@ pub struct Input {
     circuit_breakers: Arc<CircuitBreakers>,
     #[context(default)]
     app_health: Arc<AppHealthCheck>,
+    #[context(default)]
+    node_control: Arc<NodeControl>,
     sl_contracts: SettlementLayerContractsResource,
 }
 
@@ -168,6 +172,7 @@ impl WiringLayer for EthTxAggregatorLayer {
             .app_health
             .insert_component(eth_tx_aggregator.health_check())
             .map_err(WiringError::internal)?;
+        input.node_control.register_pausable(&eth_tx_aggregator);
 
         Ok(Output { eth_tx_aggregator })
     }
@@ -183,3 +188,9 @@ impl Task for EthTxAggregator {
         (*self).run(stop_receiver.0).await
     }
 }
+
+impl Pausable for EthTxAggregator {
+    fn pause_switch(&self) -> PauseSwitch {
+        self.pause_switch()
+    }
+}
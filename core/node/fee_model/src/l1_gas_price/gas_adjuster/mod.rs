@@ -5,6 +5,7 @@ use std::{
     sync::{Arc, RwLock},
 };
 
+use serde::{Deserialize, Serialize};
 use tokio::sync::watch;
 use zksync_config::GasAdjusterConfig;
 use zksync_dal::{ConnectionPool, Core};
@@ -23,6 +24,45 @@ mod metrics;
 #[cfg(test)]
 mod tests;
 
+/// Caps on the L1 fees used by [`GasAdjuster`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GasPriceCaps {
+    pub max_l1_gas_price: u64,
+    pub max_blob_base_fee: u64,
+}
+
+impl GasPriceCaps {
+    fn new(config: &GasAdjusterConfig) -> Self {
+        Self {
+            max_l1_gas_price: config.max_l1_gas_price,
+            max_blob_base_fee: config.max_blob_base_fee,
+        }
+    }
+}
+
+/// [`GasPriceCaps`] that can be updated at runtime. Cloned instances share the same caps.
+#[derive(Debug, Clone)]
+pub struct DynamicGasPriceCaps(Arc<RwLock<GasPriceCaps>>);
+
+impl DynamicGasPriceCaps {
+    /// Returns the current caps.
+    pub fn get(&self) -> GasPriceCaps {
+        *self.0.read().expect("gas price caps are poisoned")
+    }
+
+    /// Sets the caps, returning the previous value.
+    pub fn set(&self, caps: GasPriceCaps) -> GasPriceCaps {
+        let mut guard = self.0.write().expect("gas price caps are poisoned");
+        std::mem::replace(&mut *guard, caps)
+    }
+
+    /// Atomically modifies the caps using the provided closure, which is executed while holding the write lock.
+    pub fn modify<R>(&self, modifier: impl FnOnce(&mut GasPriceCaps) -> R) -> R {
+        let mut guard = self.0.write().expect("gas price caps are poisoned");
+        modifier(&mut guard)
+    }
+}
+
 #[derive(Debug)]
 pub struct GasAdjusterClient {
     pub(crate) inner: Box<dyn EthFeeInterface>,
@@ -63,6 +103,8 @@ pub struct GasAdjuster {
     pub(super) gas_per_pubdata_price_statistic: GasStatistics<u64>,
 
     pub(super) config: GasAdjusterConfig,
+    /// Caps initialized from `config`; can be updated at runtime.
+    caps: DynamicGasPriceCaps,
     pubdata_sending_mode: PubdataSendingMode,
     client: GasAdjusterClient,
     connection_pool: ConnectionPool<Core>,
@@ -117,12 +159,14 @@ impl GasAdjuster {
                 .map(|base_fee| base_fee.gas_per_pubdata()),
         );
 
+        let caps = DynamicGasPriceCaps(Arc::new(RwLock::new(GasPriceCaps::new(&config))));
         Ok(Self {
             base_fee_statistics,
             blob_base_fee_statistics,
             l2_pubdata_price_statistics,
             gas_per_pubdata_price_statistic,
             config,
+            caps,
             pubdata_sending_mode,
             client,
             commitment_mode,
@@ -169,7 +213,7 @@ impl GasAdjuster {
             {
                 // Blob base fee overflows `u64` only in very extreme cases.
                 // It isn't worth to observe exact value with metric because anyway values that can be used
-                // are capped by `self.config.max_blob_base_fee()` of `u64` type.
+                // are capped by `max_blob_base_fee` of `u64` type.
                 if current_blob_base_fee > U256::from(u64::MAX) {
                     tracing::error!("Failed to report current_blob_base_fee = {current_blob_base_fee}, it exceeds u64::MAX");
                 } else {
@@ -185,7 +229,7 @@ impl GasAdjuster {
             {
                 // L2 pubdata price overflows `u64` only in very extreme cases.
                 // It isn't worth to observe exact value with metric because anyway values that can be used
-                // are capped by `self.config.max_blob_base_fee()` of `u64` type.
+                // are capped by `max_blob_base_fee` of `u64` type.
                 if current_l2_pubdata_price > U256::from(u64::MAX) {
                     tracing::error!("Failed to report current_l2_pubdata_price = {current_l2_pubdata_price}, it exceeds u64::MAX");
                 } else {
@@ -203,8 +247,13 @@ impl GasAdjuster {
         Ok(())
     }
 
+    /// Returns caps on the L1 fees that can be updated at runtime.
+    pub fn caps(&self) -> DynamicGasPriceCaps {
+        self.caps.clone()
+    }
+
     fn bound_gas_price(&self, gas_price: u64) -> u64 {
-        let max_l1_gas_price = self.config.max_l1_gas_price;
+        let max_l1_gas_price = self.caps.get().max_l1_gas_price;
         if gas_price > max_l1_gas_price {
             tracing::warn!(
                 "Effective gas price is too high: {gas_price}, using max allowed: {}",
@@ -263,7 +312,7 @@ impl GasAdjuster {
 
                 // Check if blob base fee overflows `u64` before converting. Can happen only in very extreme cases.
                 if blob_base_fee_median > U256::from(u64::MAX) {
-                    let max_allowed = self.config.max_blob_base_fee;
+                    let max_allowed = self.caps.get().max_blob_base_fee;
                     tracing::error!("Blob base fee is too high: {blob_base_fee_median}, using max allowed: {max_allowed}");
                     return max_allowed;
                 }
@@ -311,7 +360,7 @@ impl GasAdjuster {
 
     fn cap_pubdata_fee(&self, pubdata_fee: f64) -> u64 {
         // We will treat the max blob base fee as the maximal fee that we can take for each byte of pubdata.
-        let max_blob_base_fee = self.config.max_blob_base_fee;
+        let max_blob_base_fee = self.caps.get().max_blob_base_fee;
         match self.commitment_mode {
             L1BatchCommitmentMode::Validium => {
                 // SYSCOIN For Validium mode, we still need to return the pubdata fee
@@ -10,7 +10,7 @@ use zksync_types::{
 };
 use zksync_web3_decl::client::{DynClient, L1, L2};
 
-use super::{GasAdjuster, GasStatistics, GasStatisticsInner};
+use super::{GasAdjuster, GasPriceCaps, GasStatistics, GasStatisticsInner};
 use crate::l1_gas_price::GasAdjusterClient;
 
 /// Check that we compute the median correctly
@@ -216,3 +216,46 @@ async fn kept_updated_l2(commitment_mode: L1BatchCommitmentMode) {
         expected_median_blob_base_fee.into()
     );
 }
+
+#[tokio::test]
+async fn gas_price_caps_can_be_updated() {
+    let base_fees = TEST_BLOCK_FEES
+        .into_iter()
+        .zip(TEST_BLOB_FEES)
+        .map(|(block, blob)| BaseFees {
+            base_fee_per_gas: block,
+            base_fee_per_blob_gas: blob.into(),
+            l2_pubdata_price: 0.into(),
+        })
+        .collect();
+    let eth_client = MockSettlementLayer::builder()
+        .with_fee_history(base_fees)
+        .build();
+    eth_client.advance_block_number(6, EthTxFinalityStatus::Finalized);
+
+    let client: Box<DynClient<L1>> = Box::new(eth_client.into_client());
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let adjuster = GasAdjuster::new(
+        GasAdjusterClient::from(client),
+        test_config(),
+        PubdataSendingMode::Calldata,
+        L1BatchCommitmentMode::Rollup,
+        pool,
+    )
+    .await
+    .unwrap();
+
+    let gas_price = adjuster.estimate_effective_gas_price();
+    assert!(gas_price > 1, "{gas_price}");
+    let pubdata_price = adjuster.estimate_effective_pubdata_price().await;
+    assert!(pubdata_price > 10, "{pubdata_price}");
+
+    let prev_caps = adjuster.caps().set(GasPriceCaps {
+        max_l1_gas_price: 1,
+        max_blob_base_fee: 10,
+    });
+    assert_eq!(prev_caps.max_l1_gas_price, u64::MAX);
+    assert_eq!(prev_caps.max_blob_base_fee, u64::MAX);
+    assert_eq!(adjuster.estimate_effective_gas_price(), 1);
+    assert_eq!(adjuster.estimate_effective_pubdata_price().await, 10);
+}
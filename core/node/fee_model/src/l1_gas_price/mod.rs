@@ -3,7 +3,7 @@
 use std::fmt;
 
 pub use self::{
-    gas_adjuster::{GasAdjuster, GasAdjusterClient},
+    gas_adjuster::{DynamicGasPriceCaps, GasAdjuster, GasAdjusterClient, GasPriceCaps},
     main_node_fetcher::MainNodeFeeParamsFetcher,
 };
 
@@ -0,0 +1,18 @@
+//! L2 congestion adjuster metrics.
+
+use vise::{Buckets, Gauge, Histogram, Metrics};
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_l2_congestion_adjuster")]
+pub(super) struct L2CongestionAdjusterMetrics {
+    /// Current multiplier applied to the minimal L2 gas price.
+    pub multiplier: Gauge<f64>,
+    /// Utilization of processed L2 blocks relative to their share of the batch capacity.
+    #[metrics(buckets = Buckets::linear(0.0..=2.0, 0.1))]
+    pub l2_block_utilization: Histogram<f64>,
+    /// Number of the last processed L2 block.
+    pub last_processed_l2_block: Gauge<u64>,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<L2CongestionAdjusterMetrics> = vise::Global::new();
@@ -0,0 +1,179 @@
+//! Congestion-based adjustment of the fair L2 gas price.
+
+use std::sync::{Arc, RwLock};
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_config::configs::chain::{CongestionPricingConfig, StateKeeperConfig};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_types::{block::L2BlockGasUsage, L2BlockNumber};
+
+use self::metrics::METRICS;
+
+mod metrics;
+#[cfg(test)]
+mod tests;
+
+/// Number of the latest L2 blocks replayed on start to restore the multiplier.
+const RESTORED_L2_BLOCK_COUNT: u32 = 256;
+/// Maximum number of L2 blocks loaded from the storage at once.
+const MAX_L2_BLOCKS_PER_QUERY: u32 = 1_000;
+
+/// Share of the batch capacity that a single L2 block is expected to consume.
+///
+/// The capacity is derived from the seal criteria: a batch is expected to be sealed on the commit deadline,
+/// so each of its L2 blocks gets an equal share of the gas and transaction slot limits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct L2BlockCapacity {
+    gas: f64,
+    tx_slots: f64,
+}
+
+impl L2BlockCapacity {
+    pub fn new(config: &StateKeeperConfig) -> Self {
+        let l2_blocks_per_batch = (config.l1_batch_commit_deadline.as_secs_f64()
+            / config.shared.l2_block_commit_deadline.as_secs_f64())
+        .max(1.0);
+        let batch_gas =
+            config.max_gas_per_batch as f64 * config.seal_criteria.close_block_at_gas_percentage;
+        Self {
+            gas: (batch_gas / l2_blocks_per_batch).max(1.0),
+            tx_slots: (config.seal_criteria.transaction_slots as f64 / l2_blocks_per_batch)
+                .max(1.0),
+        }
+    }
+
+    /// Returns the utilization of the block, i.e. the share of its capacity it has used along the most used dimension.
+    fn utilization(&self, usage: &L2BlockGasUsage) -> f64 {
+        let gas = usage.gas_used as f64 / self.gas;
+        let tx_slots = f64::from(usage.tx_count) / self.tx_slots;
+        gas.max(tx_slots)
+    }
+}
+
+/// Computes the multiplier after processing an L2 block with the specified utilization.
+fn next_multiplier(config: &CongestionPricingConfig, multiplier: f64, utilization: f64) -> f64 {
+    let target = config.target_utilization.max(f64::EPSILON);
+    let max_change = config.max_change_per_block;
+    let change = (max_change * (utilization - target) / target).clamp(-max_change, max_change);
+    (multiplier * (1.0 + change)).clamp(1.0, config.max_multiplier.max(1.0))
+}
+
+#[derive(Debug)]
+struct CongestionState {
+    multiplier: f64,
+    last_processed_l2_block: Option<L2BlockNumber>,
+}
+
+/// Tracks utilization of sealed L2 blocks and maintains a multiplier for the minimal L2 gas price.
+///
+/// Similar to EIP-1559, each L2 block with utilization above the target increases the multiplier, and each block
+/// below the target decreases it. The change per block is bounded by [`CongestionPricingConfig::max_change_per_block`],
+/// and the multiplier itself is kept within `[1, max_multiplier]`.
+#[derive(Debug)]
+pub struct L2CongestionAdjuster {
+    config: CongestionPricingConfig,
+    capacity: L2BlockCapacity,
+    pool: ConnectionPool<Core>,
+    state: RwLock<CongestionState>,
+}
+
+impl L2CongestionAdjuster {
+    pub fn new(
+        config: CongestionPricingConfig,
+        capacity: L2BlockCapacity,
+        pool: ConnectionPool<Core>,
+    ) -> Self {
+        Self {
+            config,
+            capacity,
+            pool,
+            state: RwLock::new(CongestionState {
+                multiplier: 1.0,
+                last_processed_l2_block: None,
+            }),
+        }
+    }
+
+    /// Returns the current multiplier for the minimal L2 gas price.
+    pub fn multiplier(&self) -> f64 {
+        self.state.read().unwrap().multiplier
+    }
+
+    /// Applies the current multiplier to the provided L2 gas price.
+    pub fn adjust_l2_gas_price(&self, price: u64) -> u64 {
+        (price as f64 * self.multiplier()) as u64
+    }
+
+    /// Processes L2 blocks sealed since the last call.
+    pub async fn update(&self) -> anyhow::Result<()> {
+        let mut conn = self
+            .pool
+            .connection_tagged("l2_congestion_adjuster")
+            .await?;
+        let Some(sealed_l2_block) = conn.blocks_dal().get_sealed_l2_block_number().await? else {
+            return Ok(());
+        };
+
+        let last_processed_l2_block = self.state.read().unwrap().last_processed_l2_block;
+        let mut next_l2_block = match last_processed_l2_block {
+            Some(number) => number + 1,
+            None => L2BlockNumber(
+                sealed_l2_block
+                    .0
+                    .saturating_sub(RESTORED_L2_BLOCK_COUNT - 1),
+            ),
+        };
+
+        while next_l2_block <= sealed_l2_block {
+            let last_l2_block = sealed_l2_block.min(next_l2_block + (MAX_L2_BLOCKS_PER_QUERY - 1));
+            let usages = conn
+                .blocks_dal()
+                .get_l2_blocks_gas_usage(next_l2_block..=last_l2_block)
+                .await
+                .context("get_l2_blocks_gas_usage()")?;
+
+            {
+                let mut state = self.state.write().unwrap();
+                for usage in &usages {
+                    let utilization = self.capacity.utilization(usage);
+                    METRICS.l2_block_utilization.observe(utilization);
+                    state.multiplier = next_multiplier(&self.config, state.multiplier, utilization);
+                }
+                state.last_processed_l2_block = Some(last_l2_block);
+            }
+            next_l2_block = last_l2_block + 1;
+        }
+
+        let multiplier = self.multiplier();
+        tracing::debug!(
+            %sealed_l2_block,
+            multiplier,
+            "Updated L2 gas price congestion multiplier"
+        );
+        METRICS.multiplier.set(multiplier);
+        METRICS
+            .last_processed_l2_block
+            .set(sealed_l2_block.0.into());
+        Ok(())
+    }
+
+    pub async fn run(
+        self: Arc<Self>,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow() {
+            if let Err(err) = self.update().await {
+                tracing::warn!("Cannot update L2 congestion multiplier: {err:#}");
+            }
+
+            // The stop receiver status will be checked immediately in the loop condition.
+            tokio::time::timeout(self.config.poll_interval, stop_receiver.changed())
+                .await
+                .ok();
+        }
+
+        tracing::info!("Stop request received, L2 congestion adjuster is shutting down");
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+//! Tests for the L2 congestion adjuster.
+
+use std::{ops, time::Duration};
+
+use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+use zksync_node_test_utils::create_l2_block;
+use zksync_types::block::L2BlockHeader;
+
+use super::*;
+
+fn test_config() -> CongestionPricingConfig {
+    CongestionPricingConfig {
+        target_utilization: 0.5,
+        max_change_per_block: 0.125,
+        max_multiplier: 2.0,
+        poll_interval: Duration::from_millis(100),
+    }
+}
+
+#[test]
+fn multiplier_changes_are_bounded() {
+    let config = test_config();
+    assert_eq!(next_multiplier(&config, 1.0, 0.5), 1.0);
+    assert_eq!(next_multiplier(&config, 1.0, 1.0), 1.125);
+    // Change per block is capped even if the block is much fuller than the target.
+    assert_eq!(next_multiplier(&config, 1.0, 10.0), 1.125);
+    assert_eq!(next_multiplier(&config, 1.5, 0.0), 1.3125);
+    assert_eq!(next_multiplier(&config, 1.5, 0.25), 1.40625);
+
+    // The multiplier is kept within `[1, max_multiplier]`.
+    assert_eq!(next_multiplier(&config, 1.0, 0.0), 1.0);
+    assert_eq!(next_multiplier(&config, 1.9, 1.0), 2.0);
+}
+
+#[test]
+fn l2_block_capacity_is_derived_from_seal_criteria() {
+    let config = StateKeeperConfig::for_tests();
+    let capacity = L2BlockCapacity::new(&config);
+    // Batch is sealed in 2.5s, L2 blocks are sealed each second.
+    assert_eq!(capacity.tx_slots, 100.0);
+    assert_eq!(capacity.gas, 200_000_000.0 * 0.95 / 2.5);
+
+    let usage = L2BlockGasUsage {
+        number: L2BlockNumber(1),
+        tx_count: 50,
+        gas_used: 0,
+        gas_limit: u64::MAX,
+    };
+    assert_eq!(capacity.utilization(&usage), 0.5);
+    let usage = L2BlockGasUsage {
+        gas_used: 76_000_000,
+        ..usage
+    };
+    assert_eq!(capacity.utilization(&usage), 1.0);
+}
+
+async fn store_l2_blocks(
+    pool: &ConnectionPool<Core>,
+    numbers: ops::RangeInclusive<u32>,
+    tx_count: u16,
+) {
+    let mut conn = pool.connection().await.unwrap();
+    for number in numbers {
+        let header = L2BlockHeader {
+            l2_tx_count: tx_count,
+            ..create_l2_block(number)
+        };
+        conn.blocks_dal().insert_l2_block(&header).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn multiplier_follows_l2_block_utilization() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut conn, &GenesisParams::mock())
+        .await
+        .unwrap();
+    drop(conn);
+
+    let capacity = L2BlockCapacity::new(&StateKeeperConfig::for_tests());
+    let adjuster = L2CongestionAdjuster::new(test_config(), capacity, pool.clone());
+    adjuster.update().await.unwrap();
+    assert_eq!(adjuster.multiplier(), 1.0);
+    assert_eq!(adjuster.adjust_l2_gas_price(1_000), 1_000);
+
+    // Full L2 blocks raise the price.
+    store_l2_blocks(&pool, 1..=2, 100).await;
+    adjuster.update().await.unwrap();
+    assert_eq!(adjuster.multiplier(), 1.125 * 1.125);
+    assert_eq!(adjuster.adjust_l2_gas_price(1_000), 1_265);
+
+    // Blocks at the target utilization keep the price.
+    store_l2_blocks(&pool, 3..=3, 50).await;
+    adjuster.update().await.unwrap();
+    assert_eq!(adjuster.multiplier(), 1.125 * 1.125);
+
+    // Many full blocks saturate the multiplier.
+    store_l2_blocks(&pool, 4..=20, 250).await;
+    adjuster.update().await.unwrap();
+    assert_eq!(adjuster.multiplier(), 2.0);
+
+    // Empty blocks bring the price back to the minimum.
+    store_l2_blocks(&pool, 21..=40, 0).await;
+    adjuster.update().await.unwrap();
+    assert_eq!(adjuster.multiplier(), 1.0);
+
+    // A restarted adjuster restores the multiplier from the latest blocks.
+    store_l2_blocks(&pool, 41..=41, 100).await;
+    adjuster.update().await.unwrap();
+    let restarted_adjuster = L2CongestionAdjuster::new(test_config(), capacity, pool);
+    restarted_adjuster.update().await.unwrap();
+    assert_eq!(restarted_adjuster.multiplier(), adjuster.multiplier());
+}
@@ -7,9 +7,10 @@ use zksync_types::fee_model::{
     BaseTokenConversionRatio, BatchFeeInput, FeeModelConfig, FeeParams, FeeParamsV1, FeeParamsV2,
 };
 
-use crate::l1_gas_price::GasAdjuster;
+use crate::{l1_gas_price::GasAdjuster, l2_congestion::L2CongestionAdjuster};
 
 pub mod l1_gas_price;
+pub mod l2_congestion;
 pub mod node;
 
 /// Trait responsible for providing numerator and denominator for adjusting gas price that is denominated
@@ -59,6 +60,7 @@ impl dyn BatchFeeModelInputProvider {
 pub struct MainNodeFeeInputProvider {
     provider: Arc<GasAdjuster>,
     base_token_ratio_provider: Arc<dyn BaseTokenRatioProvider>,
+    congestion_adjuster: Option<Arc<L2CongestionAdjuster>>,
     config: FeeModelConfig,
 }
 
@@ -66,16 +68,24 @@ pub struct MainNodeFeeInputProvider {
 impl BatchFeeModelInputProvider for MainNodeFeeInputProvider {
     async fn get_fee_model_params(&self) -> FeeParams {
         match self.config {
-            FeeModelConfig::V1(config) => FeeParams::V1(FeeParamsV1 {
-                config,
-                l1_gas_price: self.provider.estimate_effective_gas_price(),
-            }),
-            FeeModelConfig::V2(config) => FeeParams::V2(FeeParamsV2::new(
-                config,
-                self.provider.estimate_effective_gas_price(),
-                self.provider.estimate_effective_pubdata_price().await,
-                self.base_token_ratio_provider.get_conversion_ratio(),
-            )),
+            FeeModelConfig::V1(mut config) => {
+                config.minimal_l2_gas_price =
+                    self.minimal_l2_gas_price(config.minimal_l2_gas_price);
+                FeeParams::V1(FeeParamsV1 {
+                    config,
+                    l1_gas_price: self.provider.estimate_effective_gas_price(),
+                })
+            }
+            FeeModelConfig::V2(mut config) => {
+                config.minimal_l2_gas_price =
+                    self.minimal_l2_gas_price(config.minimal_l2_gas_price);
+                FeeParams::V2(FeeParamsV2::new(
+                    config,
+                    self.provider.estimate_effective_gas_price(),
+                    self.provider.estimate_effective_pubdata_price().await,
+                    self.base_token_ratio_provider.get_conversion_ratio(),
+                ))
+            }
         }
     }
 }
@@ -89,9 +99,23 @@ impl MainNodeFeeInputProvider {
         Self {
             provider,
             base_token_ratio_provider,
+            congestion_adjuster: None,
             config,
         }
     }
+
+    /// Makes the minimal L2 gas price depend on the L2 congestion tracked by the provided adjuster.
+    pub fn with_congestion_adjuster(mut self, adjuster: Arc<L2CongestionAdjuster>) -> Self {
+        self.congestion_adjuster = Some(adjuster);
+        self
+    }
+
+    fn minimal_l2_gas_price(&self, configured_price: u64) -> u64 {
+        match &self.congestion_adjuster {
+            Some(adjuster) => adjuster.adjust_l2_gas_price(configured_price),
+            None => configured_price,
+        }
+    }
 }
 
 /// The fee model provider to be used in the API. It returns the maximum batch fee input between the projected main node one and
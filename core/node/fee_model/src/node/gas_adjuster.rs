@@ -1,9 +1,11 @@
 use std::sync::Arc;
 
 use anyhow::Context;
+use serde::Deserialize;
 use zksync_config::{GasAdjusterConfig, GenesisConfig};
 use zksync_dal::node::{MasterPool, PoolResource};
 use zksync_node_framework::{
+    control::{DynamicConfig, NodeControl},
     service::StopReceiver,
     task::{Task, TaskId},
     wiring_layer::{WiringError, WiringLayer},
@@ -12,7 +14,7 @@ use zksync_node_framework::{
 use zksync_shared_resources::PubdataSendingModeResource;
 use zksync_web3_decl::node::SettlementLayerClient;
 
-use crate::l1_gas_price::GasAdjuster;
+use crate::l1_gas_price::{DynamicGasPriceCaps, GasAdjuster, GasPriceCaps};
 
 /// Wiring layer for sequencer L1 gas interfaces.
 /// Adds several resources that depend on L1 gas price.
@@ -27,6 +29,8 @@ pub struct Input {
     client: SettlementLayerClient,
     pubdata_sending_mode: PubdataSendingModeResource,
     master_pool: PoolResource<MasterPool>,
+    #[context(default)]
+    node_control: Arc<NodeControl>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -74,6 +78,10 @@ impl WiringLayer for GasAdjusterLayer {
         )
         .await
         .context("GasAdjuster::new()")?;
+        input
+            .node_control
+            .register_config(Arc::new(adjuster.caps()))
+            .map_err(WiringError::internal)?;
         let gas_adjuster = Arc::new(adjuster);
 
         Ok(Output {
@@ -108,3 +116,50 @@ impl Task for GasAdjusterTask {
         self.gas_adjuster.run(stop_receiver.0).await
     }
 }
+
+/// Partial update for [`GasPriceCaps`].
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GasPriceCapsUpdate {
+    max_l1_gas_price: Option<u64>,
+    max_blob_base_fee: Option<u64>,
+}
+
+#[async_trait::async_trait]
+impl DynamicConfig for DynamicGasPriceCaps {
+    fn name(&self) -> &'static str {
+        "gas_adjuster"
+    }
+
+    async fn current(&self) -> serde_json::Value {
+        serde_json::to_value(self.get()).expect("failed serializing gas price caps")
+    }
+
+    async fn update(&self, update: serde_json::Value) -> anyhow::Result<()> {
+        let update: GasPriceCapsUpdate = serde_json::from_value(update)?;
+        // Read and write caps under a single lock, so that concurrent partial updates don't overwrite each other.
+        let (prev_caps, caps) = self.modify(|caps| {
+            let prev_caps: GasPriceCaps = *caps;
+            let new_caps = GasPriceCaps {
+                max_l1_gas_price: update
+                    .max_l1_gas_price
+                    .unwrap_or(prev_caps.max_l1_gas_price),
+                max_blob_base_fee: update
+                    .max_blob_base_fee
+                    .unwrap_or(prev_caps.max_blob_base_fee),
+            };
+            anyhow::ensure!(
+                new_caps.max_l1_gas_price > 0,
+                "`max_l1_gas_price` must be positive"
+            );
+            anyhow::ensure!(
+                new_caps.max_blob_base_fee > 0,
+                "`max_blob_base_fee` must be positive"
+            );
+            *caps = new_caps;
+            Ok((prev_caps, new_caps))
+        })?;
+        tracing::info!(?prev_caps, ?caps, "Updated gas price caps");
+        Ok(())
+    }
+}
@@ -1,8 +1,10 @@
 use std::sync::Arc;
 
-use zksync_config::configs::chain::{FeeModelVersion, StateKeeperConfig};
+use zksync_config::configs::chain::{CongestionPricingConfig, FeeModelVersion, StateKeeperConfig};
 use zksync_dal::node::{PoolResource, ReplicaPool};
 use zksync_node_framework::{
+    service::StopReceiver,
+    task::{Task, TaskId},
     wiring_layer::{WiringError, WiringLayer},
     FromContext, IntoContext,
 };
@@ -13,6 +15,7 @@ use zksync_types::fee_model::{
 use super::resources::{ApiFeeInputResource, SequencerFeeInputResource};
 use crate::{
     l1_gas_price::{GasAdjuster, TxParamsProvider},
+    l2_congestion::{L2BlockCapacity, L2CongestionAdjuster},
     ApiFeeInputProvider, BaseTokenRatioProvider, MainNodeFeeInputProvider,
 };
 
@@ -22,6 +25,7 @@ use crate::{
 pub struct L1GasLayer {
     fee_model_config: FeeModelConfig,
     gas_price_scale_factor_open_batch: Option<f64>,
+    congestion_pricing: Option<(CongestionPricingConfig, L2BlockCapacity)>,
 }
 
 #[derive(Debug, FromContext)]
@@ -37,6 +41,9 @@ pub struct Output {
     sequencer_fee_input: SequencerFeeInputResource,
     api_fee_input: ApiFeeInputResource,
     l1_tx_params: Arc<dyn TxParamsProvider>,
+    /// Only present if congestion pricing is enabled.
+    #[context(task)]
+    congestion_adjuster_task: Option<L2CongestionAdjusterTask>,
 }
 
 impl L1GasLayer {
@@ -47,6 +54,10 @@ impl L1GasLayer {
         Self {
             fee_model_config: Self::map_config(state_keeper_config),
             gas_price_scale_factor_open_batch,
+            congestion_pricing: state_keeper_config
+                .congestion_pricing
+                .clone()
+                .map(|config| (config, L2BlockCapacity::new(state_keeper_config))),
         }
     }
 
@@ -81,13 +92,24 @@ impl WiringLayer for L1GasLayer {
             .base_token_ratio_provider
             .unwrap_or_else(|| Arc::<BaseTokenConversionRatio>::default());
 
-        let main_fee_input_provider = Arc::new(MainNodeFeeInputProvider::new(
+        let replica_pool = input.replica_pool.get().await?;
+        let mut main_fee_input_provider = MainNodeFeeInputProvider::new(
             input.gas_adjuster.clone(),
             ratio_provider,
             self.fee_model_config,
-        ));
+        );
+        let congestion_adjuster_task = self.congestion_pricing.map(|(config, capacity)| {
+            let adjuster = Arc::new(L2CongestionAdjuster::new(
+                config,
+                capacity,
+                replica_pool.clone(),
+            ));
+            main_fee_input_provider =
+                main_fee_input_provider.with_congestion_adjuster(adjuster.clone());
+            L2CongestionAdjusterTask { adjuster }
+        });
+        let main_fee_input_provider = Arc::new(main_fee_input_provider);
 
-        let replica_pool = input.replica_pool.get().await?;
         let api_fee_input_provider = Arc::new(ApiFeeInputProvider::new(
             main_fee_input_provider.clone(),
             replica_pool,
@@ -98,6 +120,23 @@ impl WiringLayer for L1GasLayer {
             sequencer_fee_input: main_fee_input_provider.into(),
             api_fee_input: api_fee_input_provider.into(),
             l1_tx_params: input.gas_adjuster,
+            congestion_adjuster_task,
         })
     }
 }
+
+#[derive(Debug)]
+pub struct L2CongestionAdjusterTask {
+    adjuster: Arc<L2CongestionAdjuster>,
+}
+
+#[async_trait::async_trait]
+impl Task for L2CongestionAdjusterTask {
+    fn id(&self) -> TaskId {
+        "l2_congestion_adjuster".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.adjuster.run(stop_receiver.0).await
+    }
+}
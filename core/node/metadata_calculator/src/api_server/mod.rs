@@ -18,7 +18,8 @@ use zksync_merkle_tree::{
     NoVersionError, ValueHash,
 };
 use zksync_shared_resources::tree::{
-    MerkleTreeInfo, TreeApiClient, TreeApiError, TreeEntryWithProof,
+    MerkleTreeInfo, TreeApiClient, TreeApiError, TreeEntryWithProof, TreeMultiproof,
+    TreeMultiproofEntry,
 };
 use zksync_types::{u256_to_h256, web3, L1BatchNumber, H256, U256};
 
@@ -50,6 +51,20 @@ fn map_entry_with_proof(src: zksync_merkle_tree::TreeEntryWithProof) -> TreeEntr
     }
 }
 
+fn map_multiproof(src: zksync_merkle_tree::TreeEntriesWithMultiproof) -> TreeMultiproof {
+    let entries = src.entries.into_iter().zip(src.path_lengths);
+    let entries = entries.map(|(entry, path_length)| TreeMultiproofEntry {
+        key: entry.key,
+        value: entry.value,
+        index: entry.leaf_index,
+        path_length,
+    });
+    TreeMultiproof {
+        entries: entries.collect(),
+        sibling_hashes: src.sibling_hashes,
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Hash)]
 struct HexNodeKey(NodeKey);
 
@@ -259,6 +274,24 @@ impl TreeApiClient for LazyAsyncTreeReader {
             Err(TreeApiError::NotReady(None))
         }
     }
+
+    async fn get_multiproof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<TreeMultiproof, TreeApiError> {
+        if let Some(reader) = self.read() {
+            reader
+                .get_multiproof_inner(l1_batch_number, hashed_keys)
+                .await
+                .map_err(|err| TreeApiError::NoVersion {
+                    missing_version: err.missing_version,
+                    version_count: err.version_count,
+                })
+        } else {
+            Err(TreeApiError::NotReady(None))
+        }
+    }
 }
 
 /// [`TreeApiClient`] implementation requesting data from a Merkle tree API server.
@@ -267,6 +300,7 @@ pub struct TreeApiHttpClient {
     inner: reqwest::Client,
     info_url: String,
     proofs_url: String,
+    multiproof_url: String,
 }
 
 impl TreeApiHttpClient {
@@ -281,6 +315,7 @@ impl TreeApiHttpClient {
             inner: client,
             info_url: url_base.to_owned(),
             proofs_url: format!("{url_base}/proofs"),
+            multiproof_url: format!("{url_base}/multiproof"),
         }
     }
 }
@@ -327,17 +362,49 @@ impl TreeApiClient for TreeApiHttpClient {
         l1_batch_number: L1BatchNumber,
         hashed_keys: Vec<U256>,
     ) -> Result<Vec<TreeEntryWithProof>, TreeApiError> {
+        let request = TreeProofsRequest {
+            l1_batch_number,
+            hashed_keys,
+        };
+        let response: TreeProofsResponse = self
+            .post_proofs_request(&self.proofs_url, &request, "proofs")
+            .await?;
+        Ok(response.entries)
+    }
+
+    async fn get_multiproof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<TreeMultiproof, TreeApiError> {
+        let request = TreeProofsRequest {
+            l1_batch_number,
+            hashed_keys,
+        };
+        self.post_proofs_request(&self.multiproof_url, &request, "multiproof")
+            .await
+    }
+}
+
+impl TreeApiHttpClient {
+    async fn post_proofs_request<R: de::DeserializeOwned>(
+        &self,
+        url: &str,
+        request: &TreeProofsRequest,
+        description: &str,
+    ) -> Result<R, TreeApiError> {
+        let l1_batch_number = request.l1_batch_number;
         let response = self
             .inner
-            .post(&self.proofs_url)
-            .json(&TreeProofsRequest {
-                l1_batch_number,
-                hashed_keys,
-            })
+            .post(url)
+            .json(request)
             .send()
             .await
             .map_err(|err| {
-                client_error(err, format_args!("proofs for L1 batch #{l1_batch_number}"))
+                client_error(
+                    err,
+                    format_args!("{description} for L1 batch #{l1_batch_number}"),
+                )
             })?;
 
         let is_problem = response
@@ -357,12 +424,13 @@ impl TreeApiClient for TreeApiHttpClient {
         }
 
         let response = response.error_for_status().with_context(|| {
-            format!("requesting proofs for L1 batch #{l1_batch_number} returned non-OK response")
-        })?;
-        let response: TreeProofsResponse = response.json().await.with_context(|| {
-            format!("failed deserializing proofs for L1 batch #{l1_batch_number}")
+            format!(
+                "requesting {description} for L1 batch #{l1_batch_number} returned non-OK response"
+            )
         })?;
-        Ok(response.entries)
+        Ok(response.json().await.with_context(|| {
+            format!("failed deserializing {description} for L1 batch #{l1_batch_number}")
+        })?)
     }
 }
 
@@ -400,6 +468,31 @@ impl AsyncTreeReader {
         Ok(Json(response))
     }
 
+    async fn get_multiproof_inner(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<TreeMultiproof, NoVersionError> {
+        let multiproof = self
+            .clone()
+            .entries_with_multiproof(l1_batch_number, hashed_keys)
+            .await?;
+        Ok(map_multiproof(multiproof))
+    }
+
+    async fn get_multiproof_handler(
+        State(this): State<Self>,
+        Json(request): Json<TreeProofsRequest>,
+    ) -> Result<Json<TreeMultiproof>, TreeApiServerError> {
+        let latency = API_METRICS.latency[&MerkleTreeApiMethod::GetMultiproof].start();
+        let multiproof = this
+            .get_multiproof_inner(request.l1_batch_number, request.hashed_keys)
+            .await
+            .map_err(TreeApiServerError::NoTreeVersion)?;
+        latency.observe();
+        Ok(Json(multiproof))
+    }
+
     async fn get_nodes_handler(
         State(this): State<Self>,
         Json(request): Json<TreeNodesRequest>,
@@ -450,6 +543,7 @@ impl AsyncTreeReader {
         let app = Router::new()
             .route("/", routing::get(Self::info_handler))
             .route("/proofs", routing::post(Self::get_proofs_handler))
+            .route("/multiproof", routing::post(Self::get_multiproof_handler))
             .route("/debug/nodes", routing::post(Self::get_nodes_handler))
             .route(
                 "/debug/stale-keys",
@@ -8,6 +8,7 @@ use tokio::{
     io::AsyncWriteExt,
     net::{TcpListener, TcpSocket},
 };
+use zksync_crypto_primitives::hasher::blake2::Blake2Hasher;
 use zksync_dal::{ConnectionPool, Core};
 
 use super::*;
@@ -52,7 +53,7 @@ async fn merkle_tree_api() {
     hashed_keys.extend((0_u8..10).map(|byte| U256::from_big_endian(&[byte; 32])));
 
     let proofs = api_client
-        .get_proofs(L1BatchNumber(5), hashed_keys)
+        .get_proofs(L1BatchNumber(5), hashed_keys.clone())
         .await
         .unwrap();
     assert_eq!(proofs.len(), 20);
@@ -62,6 +63,46 @@ async fn merkle_tree_api() {
         assert!(!proof.merkle_path.is_empty());
     }
 
+    let multiproof = api_client
+        .get_multiproof(L1BatchNumber(5), hashed_keys)
+        .await
+        .unwrap();
+    assert_eq!(multiproof.entries.len(), 20);
+    let present_count = multiproof
+        .entries
+        .iter()
+        .filter(|entry| entry.index != 0)
+        .count();
+    assert_eq!(present_count, 10);
+    let multiproof = zksync_merkle_tree::TreeEntriesWithMultiproof {
+        entries: multiproof
+            .entries
+            .iter()
+            .map(|entry| zksync_merkle_tree::TreeEntry::new(entry.key, entry.index, entry.value))
+            .collect(),
+        path_lengths: multiproof
+            .entries
+            .iter()
+            .map(|entry| entry.path_length)
+            .collect(),
+        sibling_hashes: multiproof.sibling_hashes,
+    };
+    multiproof
+        .verify(&Blake2Hasher, tree_info.root_hash)
+        .unwrap();
+
+    let err = api_client
+        .get_multiproof(L1BatchNumber(10), vec![])
+        .await
+        .unwrap_err();
+    assert_matches!(
+        err,
+        TreeApiError::NoVersion {
+            missing_version: 10,
+            ..
+        }
+    );
+
     let err = api_client
         .get_proofs(L1BatchNumber(10), vec![])
         .await
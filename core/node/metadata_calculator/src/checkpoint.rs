@@ -0,0 +1,320 @@
+//! Exporting Merkle tree checkpoints to an object store and importing them on tree initialization.
+//!
+//! A checkpoint for an L1 batch consists of a [`TreeCheckpointManifest`] and [`TreeCheckpointChunk`]s containing
+//! raw tree nodes. The manifest is persisted after all chunks, so its presence signals that the checkpoint is complete.
+//! See [`zksync_merkle_tree::checkpoint`] for details on how checkpoints are authenticated during import.
+
+use std::time::Instant;
+
+use anyhow::Context as _;
+use futures::{StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+use zksync_config::configs::database::MerkleTreeMode;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_health_check::HealthUpdater;
+use zksync_merkle_tree::{
+    checkpoint::{CheckpointNode, TreeCheckpointExporter, TreeCheckpointImporter},
+    RocksDBWrapper,
+};
+use zksync_object_store::{serialize_using_bincode, Bucket, ObjectStore, StoredObject};
+use zksync_types::{L1BatchNumber, OrStopped, H256};
+
+use crate::helpers::{AsyncTree, MerkleTreeHealth};
+
+/// Number of checkpoint chunks fetched from the object store concurrently during import.
+const CHUNK_FETCH_CONCURRENCY: usize = 4;
+
+/// Manifest of a Merkle tree checkpoint stored in an object store.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TreeCheckpointManifest {
+    /// L1 batch the checkpoint corresponds to (i.e., the last L1 batch applied to the tree).
+    pub l1_batch_number: L1BatchNumber,
+    /// Root hash of the tree after applying the L1 batch.
+    pub root_hash: H256,
+    /// Number of leaves in the tree.
+    pub leaf_count: u64,
+    /// Total number of nodes in the checkpoint.
+    pub node_count: u64,
+    /// Number of checkpoint chunks.
+    pub chunk_count: u64,
+}
+
+impl StoredObject for TreeCheckpointManifest {
+    const BUCKET: Bucket = Bucket::TreeCheckpoints;
+    type Key<'a> = L1BatchNumber;
+
+    fn encode_key(key: Self::Key<'_>) -> String {
+        format!("tree_checkpoint_l1_batch_{key}_manifest.bin")
+    }
+
+    serialize_using_bincode!();
+}
+
+/// Key of a [`TreeCheckpointChunk`] in an object store.
+#[derive(Debug, Clone, Copy)]
+pub struct TreeCheckpointChunkKey {
+    pub l1_batch_number: L1BatchNumber,
+    pub chunk_id: u64,
+}
+
+/// Chunk of a Merkle tree checkpoint containing raw tree nodes (keys and values).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TreeCheckpointChunk {
+    nodes: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl From<Vec<CheckpointNode>> for TreeCheckpointChunk {
+    fn from(nodes: Vec<CheckpointNode>) -> Self {
+        Self {
+            nodes: nodes
+                .into_iter()
+                .map(|node| (node.key, node.value))
+                .collect(),
+        }
+    }
+}
+
+impl From<TreeCheckpointChunk> for Vec<CheckpointNode> {
+    fn from(chunk: TreeCheckpointChunk) -> Self {
+        chunk
+            .nodes
+            .into_iter()
+            .map(|(key, value)| CheckpointNode { key, value })
+            .collect()
+    }
+}
+
+impl StoredObject for TreeCheckpointChunk {
+    const BUCKET: Bucket = Bucket::TreeCheckpoints;
+    type Key<'a> = TreeCheckpointChunkKey;
+
+    fn encode_key(key: Self::Key<'_>) -> String {
+        format!(
+            "tree_checkpoint_l1_batch_{}_part_{:0>6}.bin",
+            key.l1_batch_number, key.chunk_id
+        )
+    }
+
+    serialize_using_bincode!();
+}
+
+/// Exports a checkpoint of the Merkle tree stored in `db` for the specified L1 batch to the object store.
+/// The tree may be updated concurrently, but must not be pruned past the L1 batch.
+///
+/// # Arguments
+///
+/// - `max_nodes_per_chunk` is the maximum number of tree nodes in a single checkpoint chunk. Reasonable values
+///   are order of 100,000 (a node takes ~100–600 bytes, so a chunk is order of 10–50 MB).
+pub async fn export_tree_checkpoint(
+    db: RocksDBWrapper,
+    l1_batch_number: L1BatchNumber,
+    object_store: &dyn ObjectStore,
+    max_nodes_per_chunk: usize,
+) -> anyhow::Result<TreeCheckpointManifest> {
+    anyhow::ensure!(
+        max_nodes_per_chunk > 0,
+        "Checkpoint chunks must contain at least 1 node"
+    );
+    let started_at = Instant::now();
+    let version = l1_batch_number.0.into();
+    let mut exporter =
+        tokio::task::spawn_blocking(move || TreeCheckpointExporter::new(db, version))
+            .await
+            .context("panicked creating checkpoint exporter")??;
+    let info = exporter.info();
+    tracing::info!(
+        "Exporting Merkle tree checkpoint for L1 batch #{l1_batch_number} with root hash {:?} and {} leaves",
+        info.root_hash,
+        info.leaf_count
+    );
+
+    let mut chunk_count = 0;
+    let mut node_count = 0;
+    loop {
+        let (returned_exporter, chunk) = tokio::task::spawn_blocking(move || {
+            let chunk = exporter.next_chunk(max_nodes_per_chunk)?;
+            anyhow::Ok((exporter, chunk))
+        })
+        .await
+        .context("panicked exporting checkpoint chunk")??;
+        exporter = returned_exporter;
+        let Some(chunk) = chunk else {
+            break;
+        };
+
+        node_count += chunk.len() as u64;
+        let key = TreeCheckpointChunkKey {
+            l1_batch_number,
+            chunk_id: chunk_count,
+        };
+        object_store
+            .put(key, &TreeCheckpointChunk::from(chunk))
+            .await
+            .with_context(|| format!("failed persisting checkpoint chunk {key:?}"))?;
+        chunk_count += 1;
+        tracing::info!("Exported checkpoint chunk {key:?}; {node_count} nodes exported so far");
+    }
+
+    let manifest = TreeCheckpointManifest {
+        l1_batch_number,
+        root_hash: info.root_hash,
+        leaf_count: info.leaf_count,
+        node_count,
+        chunk_count,
+    };
+    object_store
+        .put(l1_batch_number, &manifest)
+        .await
+        .context("failed persisting checkpoint manifest")?;
+    tracing::info!(
+        "Exported Merkle tree checkpoint {manifest:?} in {:?}",
+        started_at.elapsed()
+    );
+    Ok(manifest)
+}
+
+/// Loads the expected root hash for the checkpoint from Postgres, checking that the tree will be able to catch up
+/// with Postgres after the import.
+async fn load_expected_root_hash(
+    pool: &ConnectionPool<Core>,
+    l1_batch_number: L1BatchNumber,
+) -> anyhow::Result<H256> {
+    let mut storage = pool.connection_tagged("metadata_calculator").await?;
+    let recovery_status = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await?;
+    let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+
+    let earliest_l1_batch = [
+        recovery_status
+            .as_ref()
+            .map(|recovery| recovery.l1_batch_number),
+        pruning_info
+            .last_hard_pruned
+            .as_ref()
+            .map(|pruned| pruned.l1_batch),
+    ]
+    .into_iter()
+    .flatten()
+    .max();
+    if let Some(earliest_l1_batch) = earliest_l1_batch {
+        anyhow::ensure!(
+            l1_batch_number >= earliest_l1_batch,
+            "Checkpoint L1 batch #{l1_batch_number} precedes the earliest L1 batch #{earliest_l1_batch} \
+             with storage logs in Postgres; the tree won't be able to catch up with Postgres after the import"
+        );
+    }
+
+    if let Some(recovery) = &recovery_status {
+        if recovery.l1_batch_number == l1_batch_number {
+            return Ok(recovery.l1_batch_root_hash);
+        }
+    }
+    if let Some(pruned) = &pruning_info.last_hard_pruned {
+        if pruned.l1_batch == l1_batch_number {
+            if let Some(root_hash) = pruned.l1_batch_root_hash {
+                return Ok(root_hash);
+            }
+        }
+    }
+    storage
+        .blocks_dal()
+        .get_l1_batch_state_root(l1_batch_number)
+        .await?
+        .with_context(|| {
+            format!(
+                "Root hash for L1 batch #{l1_batch_number} is not present in Postgres; cannot verify the tree checkpoint"
+            )
+        })
+}
+
+/// Imports a Merkle tree checkpoint for the specified L1 batch into an empty tree.
+pub(crate) async fn import_tree_checkpoint(
+    db: RocksDBWrapper,
+    mode: MerkleTreeMode,
+    l1_batch_number: L1BatchNumber,
+    object_store: &dyn ObjectStore,
+    pool: &ConnectionPool<Core>,
+    health_updater: &HealthUpdater,
+    stop_receiver: &watch::Receiver<bool>,
+) -> Result<AsyncTree, OrStopped> {
+    let expected_root_hash = load_expected_root_hash(pool, l1_batch_number).await?;
+    let manifest: TreeCheckpointManifest =
+        object_store.get(l1_batch_number).await.with_context(|| {
+            format!("failed loading tree checkpoint manifest for L1 batch #{l1_batch_number}")
+        })?;
+    tracing::info!("Importing Merkle tree checkpoint {manifest:?}");
+    if manifest.root_hash != expected_root_hash {
+        let err = anyhow::anyhow!(
+            "Root hash in the checkpoint manifest {:?} differs from the root hash in Postgres {expected_root_hash:?}",
+            manifest.root_hash
+        );
+        return Err(err.into());
+    }
+
+    let version = l1_batch_number.0.into();
+    let mut importer = tokio::task::spawn_blocking(move || {
+        TreeCheckpointImporter::new(db, version, expected_root_hash)
+    })
+    .await
+    .context("panicked creating checkpoint importer")??;
+
+    let chunk_count = manifest.chunk_count;
+    let mut chunks = futures::stream::iter(0..chunk_count)
+        .map(|chunk_id| {
+            let key = TreeCheckpointChunkKey {
+                l1_batch_number,
+                chunk_id,
+            };
+            async move {
+                let chunk: TreeCheckpointChunk = object_store
+                    .get(key)
+                    .await
+                    .with_context(|| format!("failed loading checkpoint chunk {key:?}"))?;
+                anyhow::Ok(chunk)
+            }
+        })
+        .buffered(CHUNK_FETCH_CONCURRENCY);
+
+    let mut imported_chunk_count = 0;
+    while let Some(chunk) = chunks.try_next().await? {
+        if *stop_receiver.borrow() {
+            return Err(OrStopped::Stopped);
+        }
+        importer = tokio::task::spawn_blocking(move || {
+            importer.import_chunk(&Vec::<CheckpointNode>::from(chunk))?;
+            anyhow::Ok(importer)
+        })
+        .await
+        .context("panicked importing checkpoint chunk")??;
+
+        imported_chunk_count += 1;
+        tracing::info!(
+            "Imported {imported_chunk_count}/{chunk_count} Merkle tree checkpoint chunks ({} nodes)",
+            importer.imported_node_count()
+        );
+        let health = MerkleTreeHealth::Recovery {
+            chunk_count,
+            recovered_chunk_count: imported_chunk_count,
+        };
+        health_updater.update(health.into());
+    }
+
+    if importer.imported_node_count() != manifest.node_count {
+        let err = anyhow::anyhow!(
+            "Number of imported nodes {} differs from the number of nodes in the checkpoint manifest {}",
+            importer.imported_node_count(),
+            manifest.node_count
+        );
+        return Err(err.into());
+    }
+    let tree = tokio::task::spawn_blocking(move || {
+        let db = importer.finalize()?;
+        AsyncTree::new(db, mode)
+    })
+    .await
+    .context("panicked finalizing checkpoint import")??;
+    Ok(tree)
+}
@@ -24,8 +24,8 @@ use zksync_merkle_tree::{
     recovery::{MerkleTreeRecovery, PersistenceThreadHandle},
     repair::StaleKeysRepairTask,
     unstable::{NodeKey, RawNode},
-    Database, Key, MerkleTreeColumnFamily, NoVersionError, RocksDBWrapper, TreeEntry,
-    TreeEntryWithProof, TreeInstruction,
+    Database, Key, MerkleTreeColumnFamily, NoVersionError, RocksDBWrapper,
+    TreeEntriesWithMultiproof, TreeEntry, TreeEntryWithProof, TreeInstruction,
 };
 use zksync_shared_metrics::tree::{LoadChangesStage, TreeUpdateStage, METRICS};
 use zksync_shared_resources::tree::MerkleTreeInfo;
@@ -406,6 +406,18 @@ impl AsyncTreeReader {
             .unwrap()
     }
 
+    pub async fn entries_with_multiproof(
+        self,
+        l1_batch_number: L1BatchNumber,
+        keys: Vec<Key>,
+    ) -> Result<TreeEntriesWithMultiproof, NoVersionError> {
+        tokio::task::spawn_blocking(move || {
+            self.inner.entries_with_multiproof(l1_batch_number, &keys)
+        })
+        .await
+        .unwrap()
+    }
+
     pub(crate) async fn raw_nodes(self, keys: Vec<NodeKey>) -> Vec<Option<RawNode>> {
         tokio::task::spawn_blocking(move || self.inner.raw_nodes(&keys))
             .await
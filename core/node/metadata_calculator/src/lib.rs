@@ -19,21 +19,25 @@ use zksync_dal::{ConnectionPool, Core};
 use zksync_health_check::{CheckHealth, HealthUpdater, ReactiveHealthCheck};
 use zksync_object_store::ObjectStore;
 use zksync_shared_metrics::tree::METRICS;
-use zksync_types::try_stoppable;
+use zksync_types::{try_stoppable, L1BatchNumber};
 
-use self::{
-    helpers::{create_db, Delayer, GenericAsyncTree, MerkleTreeHealth, MerkleTreeHealthCheck},
-    pruning::PruningHandles,
-    updater::TreeUpdater,
-};
 pub use self::{
+    checkpoint::{
+        export_tree_checkpoint, TreeCheckpointChunk, TreeCheckpointChunkKey, TreeCheckpointManifest,
+    },
     helpers::{AsyncTreeReader, LazyAsyncTreeReader},
     pruning::MerkleTreePruningTask,
     repair::StaleKeysRepairTask,
 };
+use self::{
+    helpers::{create_db, Delayer, GenericAsyncTree, MerkleTreeHealth, MerkleTreeHealthCheck},
+    pruning::PruningHandles,
+    updater::TreeUpdater,
+};
 use crate::helpers::create_readonly_db;
 
 pub mod api_server;
+mod checkpoint;
 mod helpers;
 mod metrics;
 pub mod node;
@@ -57,6 +61,9 @@ pub struct MetadataCalculatorRecoveryConfig {
     ///
     /// If set to `None`, parallel persistence will be disabled.
     pub parallel_persistence_buffer: Option<NonZeroUsize>,
+    /// If set, an empty tree will be initialized by importing a checkpoint for this L1 batch from the checkpoint
+    /// object store (see [`MetadataCalculator::with_checkpoint_store()`]) instead of being recovered from Postgres.
+    pub checkpoint_l1_batch: Option<L1BatchNumber>,
 }
 
 impl Default for MetadataCalculatorRecoveryConfig {
@@ -64,6 +71,7 @@ impl Default for MetadataCalculatorRecoveryConfig {
         Self {
             desired_chunk_size: 200_000,
             parallel_persistence_buffer: NonZeroUsize::new(4),
+            checkpoint_l1_batch: None,
         }
     }
 }
@@ -126,6 +134,7 @@ impl MetadataCalculatorConfig {
             recovery: MetadataCalculatorRecoveryConfig {
                 desired_chunk_size: recovery_config.chunk_size,
                 parallel_persistence_buffer: recovery_config.parallel_persistence_buffer,
+                checkpoint_l1_batch: recovery_config.checkpoint_l1_batch,
             },
         }
     }
@@ -137,6 +146,7 @@ pub struct MetadataCalculator {
     tree_reader: watch::Sender<Option<AsyncTreeReader>>,
     pruning_handles_sender: oneshot::Sender<PruningHandles>,
     object_store: Option<Arc<dyn ObjectStore>>,
+    checkpoint_store: Option<Arc<dyn ObjectStore>>,
     pool: ConnectionPool<Core>,
     recovery_pool: ConnectionPool<Core>,
     delayer: Delayer,
@@ -178,6 +188,7 @@ impl MetadataCalculator {
             tree_reader: watch::channel(None).0,
             pruning_handles_sender: oneshot::channel().0,
             object_store,
+            checkpoint_store: None,
             recovery_pool: pool.clone(),
             pool,
             delayer: Delayer::new(config.delay_interval),
@@ -194,6 +205,13 @@ impl MetadataCalculator {
         self
     }
 
+    /// Sets the object store to import Merkle tree checkpoints from. Only used if the checkpoint L1 batch
+    /// is specified in the recovery config, and the tree is empty.
+    pub fn with_checkpoint_store(mut self, checkpoint_store: Arc<dyn ObjectStore>) -> Self {
+        self.checkpoint_store = Some(checkpoint_store);
+        self
+    }
+
     /// Returns a health check for this calculator.
     pub fn tree_health_check(&self) -> impl CheckHealth {
         MerkleTreeHealthCheck::new(self.health_updater.subscribe(), self.tree_reader())
@@ -245,6 +263,7 @@ impl MetadataCalculator {
                 &self.config.recovery,
                 &self.pool,
                 self.recovery_pool,
+                self.checkpoint_store.as_deref(),
                 &self.health_updater,
                 &stop_receiver,
             )
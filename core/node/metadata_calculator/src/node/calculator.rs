@@ -1,14 +1,17 @@
 use std::{net::Ipv4Addr, sync::Arc, time::Duration};
 
 use anyhow::Context;
-use zksync_config::configs::{api::MerkleTreeApiConfig, database::MerkleTreeMode};
+use zksync_config::{
+    configs::{api::MerkleTreeApiConfig, database::MerkleTreeMode},
+    ObjectStoreConfig,
+};
 use zksync_dal::node::{MasterPool, PoolResource, ReplicaPool};
 use zksync_health_check::AppHealthCheck;
 use zksync_node_framework::{
     service::ShutdownHook, FromContext, IntoContext, StopReceiver, Task, TaskId, WiringError,
     WiringLayer,
 };
-use zksync_object_store::ObjectStore;
+use zksync_object_store::{ObjectStore, ObjectStoreFactory};
 use zksync_shared_resources::tree::TreeApiClient;
 use zksync_storage::RocksDB;
 
@@ -24,6 +27,7 @@ pub struct MetadataCalculatorLayer {
     tree_api_config: Option<MerkleTreeApiConfig>,
     pruning_config: Option<Duration>,
     stale_keys_repair_enabled: bool,
+    checkpoint_store_config: Option<ObjectStoreConfig>,
 }
 
 #[derive(Debug, FromContext)]
@@ -60,6 +64,7 @@ impl MetadataCalculatorLayer {
             tree_api_config: None,
             pruning_config: None,
             stale_keys_repair_enabled: false,
+            checkpoint_store_config: None,
         }
     }
 
@@ -77,6 +82,12 @@ impl MetadataCalculatorLayer {
         self.stale_keys_repair_enabled = true;
         self
     }
+
+    /// Sets the object store to import a tree checkpoint from if the tree is empty.
+    pub fn with_checkpoint_store(mut self, checkpoint_store_config: ObjectStoreConfig) -> Self {
+        self.checkpoint_store_config = Some(checkpoint_store_config);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -109,6 +120,10 @@ impl WiringLayer for MetadataCalculatorLayer {
         let mut metadata_calculator = MetadataCalculator::new(self.config, object_store, main_pool)
             .await?
             .with_recovery_pool(recovery_pool);
+        if let Some(config) = self.checkpoint_store_config {
+            let checkpoint_store = ObjectStoreFactory::new(config).create_store().await?;
+            metadata_calculator = metadata_calculator.with_checkpoint_store(checkpoint_store);
+        }
 
         app_health
             .insert_custom_component(Arc::new(metadata_calculator.tree_health_check()))
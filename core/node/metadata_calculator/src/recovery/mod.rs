@@ -6,6 +6,7 @@
 //! Depending on these states, we can have one of the following situations:
 //!
 //! - Tree is recovering.
+//! - Tree is empty and should be initialized from a tree checkpoint (i.e., the checkpoint L1 batch is configured).
 //! - Tree is empty and should be recovered (i.e., there's a snapshot in Postgres).
 //! - Tree is empty and should be built from scratch.
 //! - Tree is ready for normal operation (i.e., it's not empty and is not recovering).
@@ -40,12 +41,14 @@ use tokio::sync::{watch, Mutex, Semaphore};
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_health_check::HealthUpdater;
 use zksync_merkle_tree::TreeEntry;
+use zksync_object_store::ObjectStore;
 use zksync_shared_metrics::{SnapshotRecoveryStage, APP_METRICS};
 use zksync_types::{
     snapshots::uniform_hashed_keys_chunk, L1BatchNumber, L2BlockNumber, OrStopped, H256,
 };
 
 use super::{
+    checkpoint::import_tree_checkpoint,
     helpers::{AsyncTree, AsyncTreeRecovery, GenericAsyncTree, MerkleTreeHealth},
     metrics::{ChunkRecoveryStage, RecoveryStage, RECOVERY_METRICS},
     MetadataCalculatorRecoveryConfig,
@@ -240,7 +243,7 @@ struct RecoveryOptions<'a> {
 
 impl GenericAsyncTree {
     /// Ensures that the tree is ready for the normal operation, recovering it from a Postgres snapshot
-    /// if necessary.
+    /// or importing a tree checkpoint from `checkpoint_store` if necessary.
     ///
     /// `recovery_pool` is taken by value to free up its connection after recovery (provided that it's not shared
     /// with other components).
@@ -249,6 +252,7 @@ impl GenericAsyncTree {
         config: &MetadataCalculatorRecoveryConfig,
         main_pool: &ConnectionPool<Core>,
         recovery_pool: ConnectionPool<Core>,
+        checkpoint_store: Option<&dyn ObjectStore>,
         health_updater: &HealthUpdater,
         stop_receiver: &watch::Receiver<bool>,
     ) -> Result<AsyncTree, OrStopped> {
@@ -273,6 +277,30 @@ impl GenericAsyncTree {
                 (tree, params)
             }
             Self::Empty { db, mode } => {
+                if let Some(l1_batch) = config.checkpoint_l1_batch {
+                    let checkpoint_store = checkpoint_store.context(
+                        "Merkle tree checkpoint L1 batch is specified, but object store for checkpoints is not configured",
+                    )?;
+                    tracing::info!(
+                        "Initializing Merkle tree from checkpoint for L1 batch #{l1_batch}"
+                    );
+                    let tree = import_tree_checkpoint(
+                        db,
+                        mode,
+                        l1_batch,
+                        checkpoint_store,
+                        main_pool,
+                        health_updater,
+                        stop_receiver,
+                    )
+                    .await?;
+                    let elapsed = started_at.elapsed();
+                    APP_METRICS.snapshot_recovery_latency[&SnapshotRecoveryStage::Tree]
+                        .set(elapsed);
+                    tracing::info!("Imported Merkle tree checkpoint in {elapsed:?}");
+                    return Ok(tree);
+                }
+
                 if let Some(params) = InitParameters::new(main_pool, config).await? {
                     tracing::info!("Starting Merkle tree recovery with status {params:?}");
                     let l1_batch = params.l1_batch;
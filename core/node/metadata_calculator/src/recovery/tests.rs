@@ -21,11 +21,13 @@ use zksync_node_genesis::{
     insert_genesis_batch, insert_genesis_batch_with_custom_state, GenesisParams,
 };
 use zksync_node_test_utils::prepare_recovery_snapshot;
+use zksync_object_store::MockObjectStore;
 use zksync_storage::RocksDB;
 use zksync_types::{L1BatchNumber, StorageLog};
 
 use super::*;
 use crate::{
+    export_tree_checkpoint,
     helpers::create_db,
     tests::{
         extend_db_state, extend_db_state_from_l1_batch, gen_storage_logs, mock_config,
@@ -176,6 +178,7 @@ async fn recovery_workflow_for_partial_pruning() {
             &MetadataCalculatorRecoveryConfig::default(),
             &pool,
             pool.clone(),
+            None,
             &ReactiveHealthCheck::new("tree").1,
             &stop_receiver,
         )
@@ -716,3 +719,99 @@ async fn fault_tolerance_for_large_genesis_state() {
         .await
         .unwrap();
 }
+
+#[tokio::test]
+async fn tree_initialization_from_checkpoint() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    extend_db_state(&mut storage, gen_storage_logs(100..300, 2)).await;
+    drop(storage);
+
+    let init_path = temp_dir.path().join("init");
+    let (calculator, _) = setup_calculator(&init_path, pool.clone(), true).await;
+    let root_hash = run_calculator(calculator).await;
+
+    let object_store = MockObjectStore::arc();
+    let db = create_db(mock_config(&init_path.join("new")))
+        .await
+        .unwrap();
+    let manifest = export_tree_checkpoint(db, L1BatchNumber(2), &*object_store, 50)
+        .await
+        .unwrap();
+    assert_eq!(manifest.root_hash, root_hash);
+    assert!(manifest.chunk_count > 1, "{manifest:?}");
+
+    // Add more L1 batches so that the tree needs to catch up after the import.
+    let logs = gen_storage_logs(300..400, 2);
+    extend_db_state(&mut pool.connection().await.unwrap(), logs).await;
+    let (calculator, _) = setup_calculator(&init_path, pool.clone(), true).await;
+    let final_root_hash = run_calculator(calculator).await;
+
+    let tree_path = temp_dir.path().join("checkpoint");
+    let db = create_db(mock_config(&tree_path)).await.unwrap();
+    let tree = GenericAsyncTree::Empty {
+        db,
+        mode: MerkleTreeMode::Lightweight,
+    };
+    let config = MetadataCalculatorRecoveryConfig {
+        checkpoint_l1_batch: Some(L1BatchNumber(2)),
+        ..MetadataCalculatorRecoveryConfig::default()
+    };
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let tree = tree
+        .ensure_ready(
+            &config,
+            &pool,
+            pool.clone(),
+            Some(&*object_store),
+            &ReactiveHealthCheck::new("tree").1,
+            &stop_receiver,
+        )
+        .await
+        .unwrap();
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(3));
+    assert_eq!(tree.root_hash(), root_hash);
+    drop(tree); // Release exclusive lock on RocksDB
+
+    // Check that the tree catches up with Postgres after the import.
+    let (calculator, _) = setup_calculator(&tree_path, pool, true).await;
+    assert_eq!(run_calculator(calculator).await, final_root_hash);
+}
+
+#[tokio::test]
+async fn tree_initialization_from_checkpoint_without_object_store() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let db = create_db(mock_config(temp_dir.path())).await.unwrap();
+    let tree = GenericAsyncTree::Empty {
+        db,
+        mode: MerkleTreeMode::Lightweight,
+    };
+    let config = MetadataCalculatorRecoveryConfig {
+        checkpoint_l1_batch: Some(L1BatchNumber(2)),
+        ..MetadataCalculatorRecoveryConfig::default()
+    };
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let err = tree
+        .ensure_ready(
+            &config,
+            &pool,
+            pool.clone(),
+            None,
+            &ReactiveHealthCheck::new("tree").1,
+            &stop_receiver,
+        )
+        .await
+        .unwrap_err();
+    let OrStopped::Internal(err) = err else {
+        panic!("Unexpected error: {err:?}");
+    };
+    assert!(
+        format!("{err:#}").contains("object store for checkpoints is not configured"),
+        "{err:#}"
+    );
+}
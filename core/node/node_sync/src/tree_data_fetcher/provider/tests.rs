@@ -81,6 +81,7 @@ impl L2Parameters {
                     commitment: root_hash.copied(),
                     number,
                     base: mock_block_details_base(number.0, Some(hash)),
+                    tee_attestation: None,
                 }))
             })
             .method("zks_getBlockDetails", move |number: L2BlockNumber| {
@@ -91,6 +92,7 @@ impl L2Parameters {
                     operator_address: Address::zero(),
                     protocol_version: Some(ProtocolVersionId::latest()),
                     base: mock_block_details_base(number.0, Some(hash)),
+                    tee_attestation: None,
                 }))
             })
             .build()
@@ -0,0 +1,169 @@
+//! Deduplication and rate limiting of notifications.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    num::NonZeroU32,
+    time::Duration,
+};
+
+use tokio::time::Instant;
+
+use crate::{metrics::METRICS, Alert, Event, EventStatus};
+
+/// Destination for notifications, such as a webhook.
+#[async_trait::async_trait]
+pub trait EventSink: fmt::Debug + Send + Sync {
+    /// Name of the sink used in logs and metrics.
+    fn name(&self) -> &'static str;
+
+    async fn send(&self, event: &Event) -> anyhow::Result<()>;
+}
+
+/// Sliding window rate limiter.
+#[derive(Debug)]
+struct RateLimiter {
+    window: Duration,
+    max_events: usize,
+    sent_at: VecDeque<Instant>,
+}
+
+impl RateLimiter {
+    const WINDOW: Duration = Duration::from_secs(60);
+
+    fn new(max_events_per_minute: NonZeroU32) -> Self {
+        Self {
+            window: Self::WINDOW,
+            max_events: max_events_per_minute.get() as usize,
+            sent_at: VecDeque::new(),
+        }
+    }
+
+    fn try_acquire(&mut self, now: Instant) -> bool {
+        while let Some(&sent_at) = self.sent_at.front() {
+            if now.duration_since(sent_at) < self.window {
+                break;
+            }
+            self.sent_at.pop_front();
+        }
+
+        if self.sent_at.len() < self.max_events {
+            self.sent_at.push_back(now);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ActiveAlert {
+    alert: Alert,
+    last_sent_at: Instant,
+}
+
+/// Sends notifications about alerts to a single [`EventSink`].
+///
+/// An alert is reported when it's raised, when its severity changes, and once it's resolved. Reports for active alerts
+/// are repeated after `repeat_interval`. If a notification cannot be sent (because of rate limiting or a sink error),
+/// it is retried on the next dispatch.
+#[derive(Debug)]
+pub(crate) struct Dispatcher {
+    sink: Box<dyn EventSink>,
+    source: String,
+    repeat_interval: Duration,
+    rate_limiter: RateLimiter,
+    active_alerts: HashMap<String, ActiveAlert>,
+}
+
+impl Dispatcher {
+    pub(crate) fn new(
+        sink: Box<dyn EventSink>,
+        source: String,
+        repeat_interval: Duration,
+        max_events_per_minute: NonZeroU32,
+    ) -> Self {
+        Self {
+            sink,
+            source,
+            repeat_interval,
+            rate_limiter: RateLimiter::new(max_events_per_minute),
+            active_alerts: HashMap::new(),
+        }
+    }
+
+    pub(crate) async fn dispatch(&mut self, alerts: &[Alert]) {
+        let now = Instant::now();
+        for alert in alerts {
+            let should_send = match self.active_alerts.get(&alert.key) {
+                None => true,
+                Some(active) => {
+                    active.alert.severity != alert.severity
+                        || now.duration_since(active.last_sent_at) >= self.repeat_interval
+                }
+            };
+            if should_send && self.send(EventStatus::Triggered, alert, now).await {
+                let active = ActiveAlert {
+                    alert: alert.clone(),
+                    last_sent_at: now,
+                };
+                self.active_alerts.insert(alert.key.clone(), active);
+            }
+        }
+
+        let resolved_keys: Vec<_> = self
+            .active_alerts
+            .keys()
+            .filter(|&key| !alerts.iter().any(|alert| alert.key == *key))
+            .cloned()
+            .collect();
+        for key in resolved_keys {
+            let alert = self.active_alerts[&key].alert.clone();
+            if self.send(EventStatus::Resolved, &alert, now).await {
+                self.active_alerts.remove(&key);
+            }
+        }
+    }
+
+    /// Returns whether the event was successfully sent.
+    async fn send(&mut self, status: EventStatus, alert: &Alert, now: Instant) -> bool {
+        let sink_name = self.sink.name();
+        if !self.rate_limiter.try_acquire(now) {
+            tracing::debug!(
+                "Postponing notification about alert `{}` to `{sink_name}` because of rate limiting",
+                alert.key
+            );
+            METRICS.postponed_events[&sink_name].inc();
+            return false;
+        }
+
+        let event = Event::new(status, alert, &self.source);
+        match self.sink.send(&event).await {
+            Ok(()) => {
+                tracing::info!(
+                    "Sent notification about alert `{}` ({status:?}) to `{sink_name}`",
+                    alert.key
+                );
+                METRICS.sent_events[&sink_name].inc();
+                true
+            }
+            Err(err) => {
+                tracing::warn!(
+                    "Failed sending notification about alert `{}` to `{sink_name}`: {err:#}",
+                    alert.key
+                );
+                METRICS.failed_events[&sink_name].inc();
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+impl Dispatcher {
+    pub(crate) fn active_alert_keys(&self) -> Vec<&str> {
+        let mut keys: Vec<_> = self.active_alerts.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        keys
+    }
+}
@@ -0,0 +1,192 @@
+//! Notifier pushing alerts about the node state to webhooks.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use futures::future;
+use serde::Serialize;
+use tokio::sync::watch;
+use zksync_config::configs::NotifierConfig;
+
+use self::{dispatcher::Dispatcher, metrics::METRICS};
+pub use self::{
+    dispatcher::EventSink,
+    sources::AlertSource,
+    webhook::{Webhook, WebhookFormat},
+};
+
+mod dispatcher;
+mod metrics;
+pub mod node;
+pub mod sources;
+#[cfg(test)]
+mod tests;
+mod webhook;
+
+/// Severity of an [`Alert`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Warning,
+    Error,
+    Critical,
+}
+
+/// Condition of the node that should be reported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alert {
+    /// Stable identifier of the condition used for deduplication, e.g. `health:state_keeper`.
+    pub key: String,
+    pub severity: Severity,
+    /// Human-readable single-line description.
+    pub summary: String,
+    /// Structured details included into notifications.
+    pub details: serde_json::Value,
+}
+
+impl Alert {
+    pub fn new(key: impl Into<String>, severity: Severity, summary: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            severity,
+            summary: summary.into(),
+            details: serde_json::Value::Null,
+        }
+    }
+
+    #[must_use]
+    pub fn with_details(mut self, details: impl Serialize) -> Self {
+        self.details = serde_json::to_value(details).expect("failed serializing alert details");
+        self
+    }
+}
+
+/// Status of the alert reported by an [`Event`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventStatus {
+    /// Alert is raised, has changed its severity, or is repeated.
+    Triggered,
+    /// Alert is no longer active.
+    Resolved,
+}
+
+/// Notification about an [`Alert`] sent to webhooks.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Event {
+    pub status: EventStatus,
+    pub key: String,
+    pub severity: Severity,
+    pub summary: String,
+    pub details: serde_json::Value,
+    /// Name of the node sending the notification.
+    pub source: String,
+    /// UNIX timestamp of the event in seconds.
+    pub timestamp: u64,
+}
+
+impl Event {
+    fn new(status: EventStatus, alert: &Alert, source: &str) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        Self {
+            status,
+            key: alert.key.clone(),
+            severity: alert.severity,
+            summary: alert.summary.clone(),
+            details: alert.details.clone(),
+            source: source.to_owned(),
+            timestamp,
+        }
+    }
+}
+
+/// Periodically collects alerts from [sources](AlertSource) and sends notifications about them
+/// to [sinks](EventSink), deduplicating and rate-limiting notifications for each sink separately.
+/// Alerts are also collected as soon as a source reports an update, and once more after the stop request,
+/// so that conditions leading to the node shutdown (e.g., a halting circuit breaker) are still reported.
+#[derive(Debug)]
+pub struct Notifier {
+    sources: Vec<Box<dyn AlertSource>>,
+    dispatchers: Vec<Dispatcher>,
+    config: NotifierConfig,
+}
+
+impl Notifier {
+    pub fn new(config: NotifierConfig) -> Self {
+        Self {
+            sources: vec![],
+            dispatchers: vec![],
+            config,
+        }
+    }
+
+    pub fn add_source(&mut self, source: Box<dyn AlertSource>) {
+        self.sources.push(source);
+    }
+
+    pub fn add_sink(&mut self, sink: Box<dyn EventSink>) {
+        let dispatcher = Dispatcher::new(
+            sink,
+            self.config.source.clone(),
+            self.config.repeat_interval,
+            self.config.max_notifications_per_minute,
+        );
+        self.dispatchers.push(dispatcher);
+    }
+
+    pub fn has_sinks(&self) -> bool {
+        !self.dispatchers.is_empty()
+    }
+
+    async fn collect_alerts(&mut self) -> Vec<Alert> {
+        let mut alerts = vec![];
+        for source in &mut self.sources {
+            let name = source.name();
+            if let Err(err) = source.collect(&mut alerts).await {
+                tracing::warn!("Failed collecting alerts from source `{name}`: {err:#}");
+                let summary = format!("notifier failed checking `{name}`: {err}");
+                alerts.push(Alert::new(
+                    format!("notifier:{name}"),
+                    Severity::Error,
+                    summary,
+                ));
+            }
+        }
+        alerts
+    }
+
+    /// Runs a single iteration of collecting and dispatching alerts.
+    pub(crate) async fn step(&mut self) {
+        let alerts = self.collect_alerts().await;
+        METRICS.active_alerts.set(alerts.len());
+        for dispatcher in &mut self.dispatchers {
+            dispatcher.dispatch(&alerts).await;
+        }
+    }
+
+    async fn wait_for_source_updates(sources: &mut [Box<dyn AlertSource>]) {
+        if sources.is_empty() {
+            return future::pending().await;
+        }
+        let updates = sources.iter_mut().map(|source| source.wait_for_update());
+        let (_, idx, _) = future::select_all(updates).await;
+        tracing::debug!("Received update from source `{}`", sources[idx].name());
+    }
+
+    pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow_and_update() {
+            self.step().await;
+            tokio::select! {
+                _ = stop_receiver.changed() => {}
+                () = Self::wait_for_source_updates(&mut self.sources) => {}
+                () = tokio::time::sleep(self.config.poll_interval) => {}
+            }
+        }
+        // Flush alerts raised right before the stop request.
+        self.step().await;
+        tracing::info!("Received a stop request; notifier is shut down");
+        Ok(())
+    }
+}
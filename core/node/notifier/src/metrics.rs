@@ -0,0 +1,22 @@
+//! Notifier metrics.
+
+use vise::{Counter, Gauge, Global, LabeledFamily, Metrics};
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "notifier")]
+pub(crate) struct NotifierMetrics {
+    /// Number of currently active alerts.
+    pub active_alerts: Gauge<usize>,
+    /// Number of notifications successfully sent to each sink.
+    #[metrics(labels = ["sink"])]
+    pub sent_events: LabeledFamily<&'static str, Counter>,
+    /// Number of notifications that failed to be sent.
+    #[metrics(labels = ["sink"])]
+    pub failed_events: LabeledFamily<&'static str, Counter>,
+    /// Number of notifications postponed because of rate limiting.
+    #[metrics(labels = ["sink"])]
+    pub postponed_events: LabeledFamily<&'static str, Counter>,
+}
+
+#[vise::register]
+pub(crate) static METRICS: Global<NotifierMetrics> = Global::new();
@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use zksync_circuit_breaker::CircuitBreakers;
+use zksync_config::configs::{NotifierConfig, NotifierSecrets};
+use zksync_dal::node::{PoolResource, ReplicaPool};
+use zksync_health_check::AppHealthCheck;
+use zksync_node_framework::{
+    service::StopReceiver,
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+    FromContext, IntoContext,
+};
+
+use crate::{
+    sources::{
+        BatchLagLimits, BatchLagSource, CircuitBreakersSource, EthSenderSource, HealthSource,
+    },
+    Notifier, Webhook, WebhookFormat,
+};
+
+/// Wiring layer for [`Notifier`].
+///
+/// ## Requests resources
+///
+/// - `PoolResource<ReplicaPool>`
+/// - `AppHealthCheckResource`
+/// - `CircuitBreakersResource`
+///
+/// ## Adds tasks
+///
+/// - `Notifier`
+#[derive(Debug)]
+pub struct NotifierLayer {
+    pub config: NotifierConfig,
+    pub secrets: NotifierSecrets,
+}
+
+#[derive(Debug, FromContext)]
+pub struct Input {
+    replica_pool: PoolResource<ReplicaPool>,
+    #[context(default)]
+    app_health: Arc<AppHealthCheck>,
+    #[context(default)]
+    circuit_breakers: Arc<CircuitBreakers>,
+}
+
+#[derive(Debug, IntoContext)]
+pub struct Output {
+    #[context(task)]
+    notifier: Notifier,
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for NotifierLayer {
+    type Input = Input;
+    type Output = Output;
+
+    fn layer_name(&self) -> &'static str {
+        "notifier_layer"
+    }
+
+    async fn wire(self, input: Self::Input) -> Result<Self::Output, WiringError> {
+        let pool = input.replica_pool.get().await?;
+        let limits = BatchLagLimits {
+            max_commit_lag: self.config.max_commit_lag,
+            max_prove_lag: self.config.max_prove_lag,
+            max_execute_lag: self.config.max_execute_lag,
+        };
+
+        let mut notifier = Notifier::new(self.config);
+        notifier.add_source(Box::new(HealthSource::new(input.app_health)));
+        notifier.add_source(Box::new(CircuitBreakersSource::new(
+            &input.circuit_breakers,
+        )));
+        notifier.add_source(Box::new(EthSenderSource(pool.clone())));
+        if !limits.is_empty() {
+            notifier.add_source(Box::new(BatchLagSource::new(pool, limits)));
+        }
+
+        if let Some(url) = self.secrets.webhook_url {
+            notifier.add_sink(Box::new(Webhook::new(url, WebhookFormat::Generic)));
+        }
+        if let Some(url) = self.secrets.slack_webhook_url {
+            notifier.add_sink(Box::new(Webhook::new(url, WebhookFormat::Slack)));
+        }
+        if let Some(routing_key) = self.secrets.pagerduty_routing_key {
+            notifier.add_sink(Box::new(Webhook::pagerduty(routing_key)));
+        }
+        if !notifier.has_sinks() {
+            return Err(WiringError::Configuration(
+                "notifier requires at least one webhook to be configured in secrets".into(),
+            ));
+        }
+
+        Ok(Output { notifier })
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for Notifier {
+    fn id(&self) -> TaskId {
+        "notifier".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        (*self).run(stop_receiver.0).await
+    }
+}
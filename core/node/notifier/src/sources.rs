@@ -0,0 +1,254 @@
+//! Sources of alerts.
+
+use std::{
+    collections::HashSet,
+    fmt,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context as _;
+use serde_json::json;
+use tokio::sync::watch;
+use zksync_circuit_breaker::{CircuitBreakerAction, CircuitBreakers, TrippedCircuitBreakers};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_health_check::{AppHealthCheck, HealthStatus};
+
+use crate::{Alert, Severity};
+
+/// Source of [`Alert`]s polled by the notifier.
+#[async_trait::async_trait]
+pub trait AlertSource: fmt::Debug + Send + Sync {
+    /// Name of the source used in logs and alert keys.
+    fn name(&self) -> &'static str;
+
+    /// Pushes all currently active alerts to `alerts`.
+    async fn collect(&mut self, alerts: &mut Vec<Alert>) -> anyhow::Result<()>;
+
+    /// Waits until alerts reported by this source may have changed, so that they are dispatched immediately
+    /// rather than on the next poll. By default, waits indefinitely, i.e., the source is only polled.
+    async fn wait_for_update(&mut self) {
+        std::future::pending().await
+    }
+}
+
+/// Reports components that have become unhealthy. Components that have never been healthy (e.g., ones still initializing)
+/// or that are shutting down are not reported.
+#[derive(Debug)]
+pub struct HealthSource {
+    app_health: Arc<AppHealthCheck>,
+    seen_healthy: HashSet<&'static str>,
+}
+
+impl HealthSource {
+    pub fn new(app_health: Arc<AppHealthCheck>) -> Self {
+        Self {
+            app_health,
+            seen_healthy: HashSet::new(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AlertSource for HealthSource {
+    fn name(&self) -> &'static str {
+        "health"
+    }
+
+    async fn collect(&mut self, alerts: &mut Vec<Alert>) -> anyhow::Result<()> {
+        let app_health = self.app_health.check_health().await;
+        for (&component, health) in app_health.components() {
+            let status = health.status();
+            if status.is_healthy() {
+                self.seen_healthy.insert(component);
+                continue;
+            }
+            let is_shutting_down =
+                matches!(status, HealthStatus::ShuttingDown | HealthStatus::ShutDown);
+            if is_shutting_down || !self.seen_healthy.contains(component) {
+                continue;
+            }
+
+            let severity = if status == HealthStatus::Panicked {
+                Severity::Critical
+            } else {
+                Severity::Error
+            };
+            let summary = format!("component `{component}` has become unhealthy ({status:?})");
+            alerts.push(
+                Alert::new(format!("health:{component}"), severity, summary).with_details(health),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Reports tripped circuit breakers. Trips are pushed to the notifier immediately, so that they are reported
+/// even if the breaker halts the node.
+#[derive(Debug)]
+pub struct CircuitBreakersSource {
+    tripped: watch::Receiver<TrippedCircuitBreakers>,
+}
+
+impl CircuitBreakersSource {
+    pub fn new(circuit_breakers: &CircuitBreakers) -> Self {
+        Self {
+            tripped: circuit_breakers.subscribe(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AlertSource for CircuitBreakersSource {
+    fn name(&self) -> &'static str {
+        "circuit_breakers"
+    }
+
+    async fn collect(&mut self, alerts: &mut Vec<Alert>) -> anyhow::Result<()> {
+        let tripped_breakers: Vec<_> = self.tripped.borrow_and_update().values().cloned().collect();
+        for tripped in tripped_breakers {
+            let (severity, action) = match tripped.action {
+                CircuitBreakerAction::Halt => (Severity::Critical, "halt"),
+                CircuitBreakerAction::PauseTxAcceptance => {
+                    (Severity::Critical, "pause_tx_acceptance")
+                }
+                CircuitBreakerAction::Alert => (Severity::Warning, "alert"),
+            };
+            let summary = format!(
+                "circuit breaker `{}` has tripped: {}",
+                tripped.name, tripped.error
+            );
+            let alert = Alert::new(
+                format!("circuit_breaker:{}", tripped.name),
+                severity,
+                summary,
+            )
+            .with_details(json!({ "action": action }));
+            alerts.push(alert);
+        }
+        Ok(())
+    }
+
+    async fn wait_for_update(&mut self) {
+        if self.tripped.changed().await.is_err() {
+            // Circuit breakers are dropped; no more updates are possible.
+            std::future::pending().await
+        }
+    }
+}
+
+/// Reports failed L1 transactions sent by `eth_sender`.
+#[derive(Debug)]
+pub struct EthSenderSource(pub ConnectionPool<Core>);
+
+#[async_trait::async_trait]
+impl AlertSource for EthSenderSource {
+    fn name(&self) -> &'static str {
+        "eth_sender"
+    }
+
+    async fn collect(&mut self, alerts: &mut Vec<Alert>) -> anyhow::Result<()> {
+        let failed_tx_count = self
+            .0
+            .connection_tagged("notifier")
+            .await?
+            .eth_sender_dal()
+            .get_number_of_failed_transactions()
+            .await
+            .context("failed getting number of failed L1 transactions")?;
+        if failed_tx_count > 0 {
+            let summary =
+                format!("{failed_tx_count} L1 transaction(s) sent by eth_sender have failed");
+            let alert = Alert::new("eth_sender:failed_txs", Severity::Critical, summary)
+                .with_details(json!({ "failed_tx_count": failed_tx_count }));
+            alerts.push(alert);
+        }
+        Ok(())
+    }
+}
+
+/// Limits for the age of the oldest L1 batch at a certain stage.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BatchLagLimits {
+    pub max_commit_lag: Option<Duration>,
+    pub max_prove_lag: Option<Duration>,
+    pub max_execute_lag: Option<Duration>,
+}
+
+impl BatchLagLimits {
+    pub fn is_empty(&self) -> bool {
+        self.max_commit_lag.is_none()
+            && self.max_prove_lag.is_none()
+            && self.max_execute_lag.is_none()
+    }
+}
+
+/// Reports L1 batches stuck at a certain stage of L1 processing for too long.
+#[derive(Debug)]
+pub struct BatchLagSource {
+    pool: ConnectionPool<Core>,
+    limits: BatchLagLimits,
+}
+
+impl BatchLagSource {
+    pub fn new(pool: ConnectionPool<Core>, limits: BatchLagLimits) -> Self {
+        Self { pool, limits }
+    }
+}
+
+#[async_trait::async_trait]
+impl AlertSource for BatchLagSource {
+    fn name(&self) -> &'static str {
+        "batch_lag"
+    }
+
+    async fn collect(&mut self, alerts: &mut Vec<Alert>) -> anyhow::Result<()> {
+        let mut storage = self.pool.connection_tagged("notifier").await?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .context("incorrect system time")?
+            .as_secs();
+
+        let mut stages = vec![];
+        if let Some(limit) = self.limits.max_commit_lag {
+            let oldest_timestamp = storage
+                .blocks_dal()
+                .oldest_uncommitted_batch_timestamp()
+                .await?;
+            stages.push(("commit", "committed", limit, oldest_timestamp));
+        }
+        if let Some(limit) = self.limits.max_prove_lag {
+            let oldest_timestamp = storage
+                .blocks_dal()
+                .oldest_unproved_batch_timestamp()
+                .await?;
+            stages.push(("prove", "proven", limit, oldest_timestamp));
+        }
+        if let Some(limit) = self.limits.max_execute_lag {
+            let oldest_timestamp = storage
+                .blocks_dal()
+                .oldest_unexecuted_batch_timestamp()
+                .await?;
+            stages.push(("execute", "executed", limit, oldest_timestamp));
+        }
+        drop(storage);
+
+        for (stage, participle, limit, oldest_timestamp) in stages {
+            let Some(oldest_timestamp) = oldest_timestamp else {
+                continue;
+            };
+
+            let lag = Duration::from_secs(now.saturating_sub(oldest_timestamp));
+            if lag > limit {
+                let summary = format!("oldest L1 batch not {participle} on L1 was created {lag:?} ago (limit: {limit:?})");
+                let alert = Alert::new(format!("batch_lag:{stage}"), Severity::Error, summary)
+                    .with_details(json!({
+                        "lag_sec": lag.as_secs(),
+                        "limit_sec": limit.as_secs(),
+                    }));
+                alerts.push(alert);
+            }
+        }
+        Ok(())
+    }
+}
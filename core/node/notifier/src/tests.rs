@@ -0,0 +1,416 @@
+use std::{
+    num::NonZeroU32,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use secrecy::SecretString;
+use zksync_circuit_breaker::{CircuitBreaker, CircuitBreakerError, CircuitBreakers};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_health_check::{AppHealthCheck, HealthStatus, ReactiveHealthCheck};
+use zksync_node_test_utils::create_l1_batch;
+use zksync_types::{
+    aggregated_operations::{AggregatedActionType, L1BatchAggregatedActionType},
+    Address, ProtocolVersion,
+};
+
+use super::*;
+use crate::sources::{
+    BatchLagLimits, BatchLagSource, CircuitBreakersSource, EthSenderSource, HealthSource,
+};
+
+#[derive(Debug, Clone, Default)]
+struct MockSink {
+    events: Arc<Mutex<Vec<Event>>>,
+}
+
+impl MockSink {
+    fn take_events(&self) -> Vec<(EventStatus, String)> {
+        let events = std::mem::take(&mut *self.events.lock().unwrap());
+        events
+            .into_iter()
+            .map(|event| (event.status, event.key))
+            .collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl EventSink for MockSink {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    async fn send(&self, event: &Event) -> anyhow::Result<()> {
+        self.events.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+}
+
+fn create_dispatcher(max_events_per_minute: u32) -> (Dispatcher, MockSink) {
+    let sink = MockSink::default();
+    let dispatcher = Dispatcher::new(
+        Box::new(sink.clone()),
+        "test".to_owned(),
+        Duration::from_secs(3_600),
+        NonZeroU32::new(max_events_per_minute).unwrap(),
+    );
+    (dispatcher, sink)
+}
+
+fn triggered(key: &str) -> (EventStatus, String) {
+    (EventStatus::Triggered, key.to_owned())
+}
+
+fn resolved(key: &str) -> (EventStatus, String) {
+    (EventStatus::Resolved, key.to_owned())
+}
+
+#[tokio::test(start_paused = true)]
+async fn alerts_are_deduplicated_and_resolved() {
+    let (mut dispatcher, sink) = create_dispatcher(100);
+    let alert = Alert::new("health:tree", Severity::Error, "tree is unhealthy");
+
+    dispatcher.dispatch(&[alert.clone()]).await;
+    assert_eq!(sink.take_events(), [triggered("health:tree")]);
+    dispatcher.dispatch(&[alert.clone()]).await;
+    assert_eq!(sink.take_events(), []);
+
+    let escalated_alert = Alert {
+        severity: Severity::Critical,
+        ..alert.clone()
+    };
+    dispatcher.dispatch(&[escalated_alert.clone()]).await;
+    assert_eq!(sink.take_events(), [triggered("health:tree")]);
+
+    tokio::time::advance(Duration::from_secs(3_600)).await;
+    dispatcher.dispatch(&[escalated_alert]).await;
+    assert_eq!(sink.take_events(), [triggered("health:tree")]);
+
+    dispatcher.dispatch(&[]).await;
+    assert_eq!(sink.take_events(), [resolved("health:tree")]);
+    assert!(dispatcher.active_alert_keys().is_empty());
+    dispatcher.dispatch(&[]).await;
+    assert_eq!(sink.take_events(), []);
+}
+
+#[tokio::test(start_paused = true)]
+async fn rate_limited_events_are_postponed() {
+    let (mut dispatcher, sink) = create_dispatcher(2);
+    let alerts: Vec<_> = ["a", "b", "c"]
+        .into_iter()
+        .map(|key| Alert::new(key, Severity::Warning, "test"))
+        .collect();
+
+    dispatcher.dispatch(&alerts).await;
+    assert_eq!(sink.take_events(), [triggered("a"), triggered("b")]);
+    assert_eq!(dispatcher.active_alert_keys(), ["a", "b"]);
+
+    // The rate limit is still exceeded.
+    tokio::time::advance(Duration::from_secs(30)).await;
+    dispatcher.dispatch(&alerts).await;
+    assert_eq!(sink.take_events(), []);
+
+    tokio::time::advance(Duration::from_secs(30)).await;
+    dispatcher.dispatch(&alerts[1..]).await;
+    assert_eq!(sink.take_events(), [triggered("c"), resolved("a")]);
+    assert_eq!(dispatcher.active_alert_keys(), ["b", "c"]);
+}
+
+#[derive(Debug)]
+struct MockSource(Vec<Alert>);
+
+#[async_trait::async_trait]
+impl AlertSource for MockSource {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    async fn collect(&mut self, alerts: &mut Vec<Alert>) -> anyhow::Result<()> {
+        if self.0.is_empty() {
+            anyhow::bail!("oops");
+        }
+        alerts.extend(self.0.iter().cloned());
+        Ok(())
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn notifier_reports_source_errors() {
+    let sink = MockSink::default();
+    let mut notifier = Notifier::new(NotifierConfig::default());
+    notifier.add_sink(Box::new(sink.clone()));
+    notifier.add_source(Box::new(MockSource(vec![Alert::new(
+        "test",
+        Severity::Warning,
+        "test",
+    )])));
+    notifier.add_source(Box::new(MockSource(vec![])));
+
+    notifier.step().await;
+    assert_eq!(
+        sink.take_events(),
+        [triggered("test"), triggered("notifier:mock")]
+    );
+}
+
+fn test_event(status: EventStatus) -> Event {
+    let alert = Alert::new("batch_lag:prove", Severity::Error, "proving is slow")
+        .with_details(serde_json::json!({ "lag_sec": 7_200 }));
+    Event {
+        timestamp: 1_700_000_000,
+        ..Event::new(status, &alert, "sequencer")
+    }
+}
+
+#[test]
+fn generic_payload() {
+    let payload = WebhookFormat::Generic.payload(&test_event(EventStatus::Triggered));
+    assert_eq!(
+        payload,
+        serde_json::json!({
+            "status": "triggered",
+            "key": "batch_lag:prove",
+            "severity": "error",
+            "summary": "proving is slow",
+            "details": { "lag_sec": 7_200 },
+            "source": "sequencer",
+            "timestamp": 1_700_000_000,
+        })
+    );
+}
+
+#[test]
+fn slack_payload() {
+    let payload = WebhookFormat::Slack.payload(&test_event(EventStatus::Triggered));
+    let text = payload["text"].as_str().unwrap();
+    assert!(
+        text.starts_with(":red_circle: *ERROR* [sequencer] proving is slow"),
+        "{text}"
+    );
+    assert!(text.contains("\"lag_sec\": 7200"), "{text}");
+
+    let payload = WebhookFormat::Slack.payload(&test_event(EventStatus::Resolved));
+    let text = payload["text"].as_str().unwrap();
+    assert!(text.starts_with(":white_check_mark: *RESOLVED*"), "{text}");
+    assert!(!text.contains("lag_sec"), "{text}");
+}
+
+#[test]
+fn pagerduty_payload() {
+    let format = WebhookFormat::PagerDuty {
+        routing_key: SecretString::from("key"),
+    };
+    let payload = format.payload(&test_event(EventStatus::Triggered));
+    assert_eq!(
+        payload,
+        serde_json::json!({
+            "routing_key": "key",
+            "event_action": "trigger",
+            "dedup_key": "sequencer:batch_lag:prove",
+            "payload": {
+                "summary": "proving is slow",
+                "source": "sequencer",
+                "severity": "error",
+                "custom_details": { "lag_sec": 7_200 },
+            },
+        })
+    );
+
+    let payload = format.payload(&test_event(EventStatus::Resolved));
+    assert_eq!(
+        payload,
+        serde_json::json!({
+            "routing_key": "key",
+            "event_action": "resolve",
+            "dedup_key": "sequencer:batch_lag:prove",
+        })
+    );
+}
+
+#[derive(Debug)]
+struct HaltingBreaker(Arc<AtomicBool>);
+
+#[async_trait::async_trait]
+impl CircuitBreaker for HaltingBreaker {
+    fn name(&self) -> &'static str {
+        "halting"
+    }
+
+    async fn check(&self) -> Result<(), CircuitBreakerError> {
+        if self.0.load(Ordering::SeqCst) {
+            Err(CircuitBreakerError::FailedL1Transaction)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[tokio::test]
+async fn halting_circuit_breaker_is_reported_immediately() {
+    let breakers = CircuitBreakers::default();
+    let tripped = Arc::<AtomicBool>::default();
+    breakers
+        .insert(Box::new(HaltingBreaker(tripped.clone())))
+        .await;
+
+    let sink = MockSink::default();
+    let config = NotifierConfig {
+        poll_interval: Duration::from_secs(3_600),
+        ..NotifierConfig::default()
+    };
+    let mut notifier = Notifier::new(config);
+    notifier.add_sink(Box::new(sink.clone()));
+    notifier.add_source(Box::new(CircuitBreakersSource::new(&breakers)));
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let notifier_task = tokio::spawn(notifier.run(stop_receiver));
+
+    tripped.store(true, Ordering::SeqCst);
+    breakers.check().await.unwrap_err();
+
+    // The trip must be reported well before the next poll.
+    let mut events = vec![];
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while events.is_empty() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            events = sink.take_events();
+        }
+    })
+    .await
+    .expect("circuit breaker trip was not reported");
+    assert_eq!(events, [triggered("circuit_breaker:halting")]);
+
+    stop_sender.send_replace(true);
+    notifier_task.await.unwrap().unwrap();
+}
+
+#[tokio::test(start_paused = true)]
+async fn alerts_are_flushed_on_stop() {
+    let sink = MockSink::default();
+    let mut notifier = Notifier::new(NotifierConfig::default());
+    notifier.add_sink(Box::new(sink.clone()));
+    notifier.add_source(Box::new(MockSource(vec![Alert::new(
+        "test",
+        Severity::Critical,
+        "test",
+    )])));
+
+    let (_stop_sender, stop_receiver) = watch::channel(true);
+    notifier.run(stop_receiver).await.unwrap();
+    assert_eq!(sink.take_events(), [triggered("test")]);
+}
+
+#[tokio::test]
+async fn health_source_reports_components_that_became_unhealthy() {
+    let app_health = Arc::new(AppHealthCheck::default());
+    let (health_check, health_updater) = ReactiveHealthCheck::new("tree");
+    app_health.insert_component(health_check).unwrap();
+    let mut source = HealthSource::new(app_health);
+
+    // Components that have never been healthy are not reported.
+    let mut alerts = vec![];
+    source.collect(&mut alerts).await.unwrap();
+    assert!(alerts.is_empty(), "{alerts:?}");
+
+    health_updater.update(HealthStatus::Ready.into());
+    source.collect(&mut alerts).await.unwrap();
+    assert!(alerts.is_empty(), "{alerts:?}");
+
+    health_updater.update(HealthStatus::NotReady.into());
+    source.collect(&mut alerts).await.unwrap();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].key, "health:tree");
+    assert_eq!(alerts[0].severity, Severity::Error);
+
+    alerts.clear();
+    health_updater.update(HealthStatus::Panicked.into());
+    source.collect(&mut alerts).await.unwrap();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].key, "health:tree");
+    assert_eq!(alerts[0].severity, Severity::Critical);
+
+    alerts.clear();
+    health_updater.update(HealthStatus::ShuttingDown.into());
+    source.collect(&mut alerts).await.unwrap();
+    assert!(alerts.is_empty(), "{alerts:?}");
+}
+
+#[tokio::test]
+async fn eth_sender_source_reports_failed_txs() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut source = EthSenderSource(pool.clone());
+    let mut alerts = vec![];
+    source.collect(&mut alerts).await.unwrap();
+    assert!(alerts.is_empty(), "{alerts:?}");
+
+    let mut storage = pool.connection().await.unwrap();
+    let eth_tx = storage
+        .eth_sender_dal()
+        .save_eth_tx(
+            0,
+            vec![],
+            AggregatedActionType::L1Batch(L1BatchAggregatedActionType::Commit),
+            Address::repeat_byte(1),
+            Some(1),
+            None,
+            None,
+            false,
+        )
+        .await
+        .unwrap();
+    storage
+        .eth_sender_dal()
+        .mark_failed_transaction(eth_tx.id)
+        .await
+        .unwrap();
+    drop(storage);
+
+    source.collect(&mut alerts).await.unwrap();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].key, "eth_sender:failed_txs");
+    assert_eq!(alerts[0].severity, Severity::Critical);
+    assert_eq!(alerts[0].details["failed_tx_count"], 1);
+}
+
+#[tokio::test]
+async fn batch_lag_source_reports_lagging_stages() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let limits = BatchLagLimits {
+        max_commit_lag: Some(Duration::from_secs(3_600)),
+        max_prove_lag: None,
+        max_execute_lag: Some(Duration::from_secs(3 * 3_600)),
+    };
+    let mut source = BatchLagSource::new(pool.clone(), limits);
+    let mut alerts = vec![];
+    source.collect(&mut alerts).await.unwrap();
+    assert!(alerts.is_empty(), "{alerts:?}");
+
+    let mut storage = pool.connection().await.unwrap();
+    storage
+        .protocol_versions_dal()
+        .save_protocol_version_with_tx(&ProtocolVersion::default())
+        .await
+        .unwrap();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let mut header = create_l1_batch(1);
+    header.timestamp = now - 2 * 3_600;
+    storage
+        .blocks_dal()
+        .insert_mock_l1_batch(&header)
+        .await
+        .unwrap();
+    drop(storage);
+
+    // The batch is lagging for commitment, but not for execution.
+    source.collect(&mut alerts).await.unwrap();
+    assert_eq!(alerts.len(), 1, "{alerts:?}");
+    assert_eq!(alerts[0].key, "batch_lag:commit");
+    assert_eq!(alerts[0].severity, Severity::Error);
+    let lag_sec = alerts[0].details["lag_sec"].as_u64().unwrap();
+    assert!(lag_sec >= 2 * 3_600, "{lag_sec}");
+}
@@ -0,0 +1,144 @@
+//! Webhook sinks.
+
+use std::time::Duration;
+
+use anyhow::Context as _;
+use secrecy::{ExposeSecret, SecretString};
+use serde_json::json;
+use zksync_types::url::SensitiveUrl;
+
+use crate::{dispatcher::EventSink, Event, EventStatus, Severity};
+
+/// URL of the PagerDuty Events API v2.
+const PAGERDUTY_EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+
+/// Format of the webhook payload.
+#[derive(Debug, Clone)]
+pub enum WebhookFormat {
+    /// [`Event`] serialized as a JSON object.
+    Generic,
+    /// Slack incoming webhook message.
+    Slack,
+    /// PagerDuty Events API v2 event.
+    PagerDuty { routing_key: SecretString },
+}
+
+impl WebhookFormat {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Generic => "webhook",
+            Self::Slack => "slack",
+            Self::PagerDuty { .. } => "pagerduty",
+        }
+    }
+
+    pub(crate) fn payload(&self, event: &Event) -> serde_json::Value {
+        match self {
+            Self::Generic => serde_json::to_value(event).expect("failed serializing event"),
+            Self::Slack => Self::slack_payload(event),
+            Self::PagerDuty { routing_key } => Self::pagerduty_payload(event, routing_key),
+        }
+    }
+
+    fn slack_payload(event: &Event) -> serde_json::Value {
+        let header = match (event.status, event.severity) {
+            (EventStatus::Resolved, _) => ":white_check_mark: *RESOLVED*",
+            (EventStatus::Triggered, Severity::Critical) => ":rotating_light: *CRITICAL*",
+            (EventStatus::Triggered, Severity::Error) => ":red_circle: *ERROR*",
+            (EventStatus::Triggered, Severity::Warning) => ":warning: *WARNING*",
+        };
+        let mut text = format!(
+            "{header} [{}] {}\nAlert: `{}`",
+            event.source, event.summary, event.key
+        );
+        if event.status == EventStatus::Triggered && !event.details.is_null() {
+            let details = serde_json::to_string_pretty(&event.details)
+                .expect("failed serializing event details");
+            text.push_str(&format!("\n```{details}```"));
+        }
+        json!({ "text": text })
+    }
+
+    fn pagerduty_payload(event: &Event, routing_key: &SecretString) -> serde_json::Value {
+        let dedup_key = format!("{}:{}", event.source, event.key);
+        match event.status {
+            EventStatus::Triggered => {
+                let severity = match event.severity {
+                    Severity::Critical => "critical",
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                };
+                json!({
+                    "routing_key": routing_key.expose_secret(),
+                    "event_action": "trigger",
+                    "dedup_key": dedup_key,
+                    "payload": {
+                        "summary": event.summary,
+                        "source": event.source,
+                        "severity": severity,
+                        "custom_details": event.details,
+                    },
+                })
+            }
+            EventStatus::Resolved => json!({
+                "routing_key": routing_key.expose_secret(),
+                "event_action": "resolve",
+                "dedup_key": dedup_key,
+            }),
+        }
+    }
+}
+
+/// Webhook receiving notifications via HTTP POST requests.
+#[derive(Debug)]
+pub struct Webhook {
+    client: reqwest::Client,
+    url: SensitiveUrl,
+    format: WebhookFormat,
+}
+
+impl Webhook {
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+    pub fn new(url: SensitiveUrl, format: WebhookFormat) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Self::REQUEST_TIMEOUT)
+            .build()
+            .expect("failed building HTTP client");
+        Self {
+            client,
+            url,
+            format,
+        }
+    }
+
+    /// Creates a webhook sending events to PagerDuty.
+    pub fn pagerduty(routing_key: SecretString) -> Self {
+        let url = PAGERDUTY_EVENTS_URL.parse().unwrap();
+        Self::new(url, WebhookFormat::PagerDuty { routing_key })
+    }
+}
+
+#[async_trait::async_trait]
+impl EventSink for Webhook {
+    fn name(&self) -> &'static str {
+        self.format.name()
+    }
+
+    async fn send(&self, event: &Event) -> anyhow::Result<()> {
+        let payload = serde_json::to_vec(&self.format.payload(event))?;
+        // Errors are stripped of URLs since webhook URLs are secret.
+        self.client
+            .post(self.url.expose_url().clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(payload)
+            .send()
+            .await
+            .map_err(reqwest::Error::without_url)
+            .context("failed sending request")?
+            .error_for_status()
+            .map_err(reqwest::Error::without_url)
+            .context("webhook returned error")?;
+        Ok(())
+    }
+}
@@ -114,6 +114,7 @@ impl fmt::Display for TxStage {
             Self::Mempool(L2TxSubmissionResult::AlreadyExecuted) => {
                 formatter.write_str("already_executed")
             }
+            Self::Mempool(L2TxSubmissionResult::Conflict) => formatter.write_str("conflicting"),
             Self::Mempool(result) => write!(formatter, "mempool_{result}"),
             Self::Proxied => formatter.write_str("proxied"),
             Self::StateKeeper => formatter.write_str("state_keeper"),
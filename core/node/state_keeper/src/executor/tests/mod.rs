@@ -912,6 +912,60 @@ async fn execute_realistic_scenario(vm_mode: FastVmMode) {
     executor.finish_batch().await.unwrap();
 }
 
+/// Checks that speculative execution of upcoming transactions doesn't influence execution results.
+#[test_casing(3, FAST_VM_MODES)]
+#[tokio::test]
+async fn speculative_execution_does_not_change_results(vm_mode: FastVmMode) {
+    let connection_pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+    let mut accounts: Vec<_> = (0..3).map(|_| Account::random()).collect();
+    let mut tester = Tester::new(connection_pool, vm_mode);
+    tester.genesis().await;
+    let addresses: Vec<_> = accounts.iter().map(Account::address).collect();
+    tester.fund(&addresses).await;
+
+    // Interleave transactions from different accounts, so that some of them are independent.
+    let mut txs = vec![];
+    for _ in 0..3 {
+        txs.extend(accounts.iter_mut().map(|account| account.execute()));
+    }
+
+    let mut executor = tester.create_batch_executor(StorageType::Postgres).await;
+    assert_eq!(executor.speculation_lookahead(), 0);
+    let mut expected_results = vec![];
+    for tx in &txs {
+        let res = executor.execute_tx(tx.clone()).await.unwrap();
+        assert_executed(&res);
+        expected_results.push(res);
+    }
+    let (expected_batch, _) = executor.finish_batch().await.unwrap();
+
+    tester.set_config(TestConfig {
+        speculation_workers: 2,
+        ..TestConfig::new(vm_mode)
+    });
+    let mut executor = tester.create_batch_executor(StorageType::Postgres).await;
+    assert!(executor.speculation_lookahead() > 0);
+    for (i, tx) in txs.iter().enumerate() {
+        executor.speculate(txs[i..].to_vec()).await.unwrap();
+        let res = executor.execute_tx(tx.clone()).await.unwrap();
+        let expected = &expected_results[i];
+        assert_eq!(res.tx_result.result, expected.tx_result.result);
+        assert_eq!(
+            res.tx_result.logs.storage_logs,
+            expected.tx_result.logs.storage_logs
+        );
+    }
+    let (finished_batch, _) = executor.finish_batch().await.unwrap();
+    assert_eq!(
+        finished_batch.block_tip_execution_result.logs.storage_logs,
+        expected_batch.block_tip_execution_result.logs.storage_logs
+    );
+    assert_eq!(
+        finished_batch.final_execution_state,
+        expected_batch.final_execution_state
+    );
+}
+
 /// Checks that we handle the bootloader out of gas error on execution phase.
 #[test_casing(3, FAST_VM_MODES)]
 #[tokio::test]
@@ -926,6 +980,7 @@ async fn bootloader_out_of_gas_for_any_tx(vm_mode: FastVmMode) {
             vm_gas_limit: Some(10),
             validation_computational_gas_limit: u32::MAX,
             fast_vm_mode: vm_mode,
+            speculation_workers: 0,
         },
     );
 
@@ -977,6 +1032,7 @@ async fn bootloader_tip_out_of_gas() {
         ),
         validation_computational_gas_limit: u32::MAX,
         fast_vm_mode: FastVmMode::Old,
+        speculation_workers: 0,
     });
 
     let mut second_executor = tester
@@ -1,7 +1,7 @@
 //! Testing harness for the batch executor.
 //! Contains helper functionality to initialize test context and perform tests without too much boilerplate.
 
-use std::{collections::HashMap, fmt::Debug, path::PathBuf, sync::Arc};
+use std::{collections::HashMap, fmt::Debug, num::NonZeroUsize, path::PathBuf, sync::Arc};
 
 use assert_matches::assert_matches;
 use tempfile::TempDir;
@@ -56,6 +56,8 @@ pub(super) struct TestConfig {
     pub(super) vm_gas_limit: Option<u32>,
     pub(super) validation_computational_gas_limit: u32,
     pub(super) fast_vm_mode: FastVmMode,
+    /// Number of speculative execution workers; 0 disables speculation.
+    pub(super) speculation_workers: usize,
 }
 
 impl TestConfig {
@@ -66,6 +68,7 @@ impl TestConfig {
             vm_gas_limit: None,
             validation_computational_gas_limit: config.validation_computational_gas_limit,
             fast_vm_mode,
+            speculation_workers: 0,
         }
     }
 }
@@ -179,13 +182,21 @@ impl Tester {
             .access_storage(&stop_receiver, l1_batch_env.number - 1)
             .await
             .expect("failed creating VM storage");
+        let speculation = NonZeroUsize::new(self.config.speculation_workers)
+            .map(|workers| (workers, NonZeroUsize::new(16).unwrap()));
         if self.config.trace_calls {
             let mut executor = MainBatchExecutorFactory::<TraceCalls>::new(false);
             executor.set_fast_vm_mode(self.config.fast_vm_mode);
+            if let Some((workers, lookahead)) = speculation {
+                executor.enable_speculation(workers, lookahead);
+            }
             executor.init_batch(storage, l1_batch_env, system_env, pubdata_params)
         } else {
             let mut executor = MainBatchExecutorFactory::<()>::new(false);
             executor.set_fast_vm_mode(self.config.fast_vm_mode);
+            if let Some((workers, lookahead)) = speculation {
+                executor.enable_speculation(workers, lookahead);
+            }
             executor.init_batch(storage, l1_batch_env, system_env, pubdata_params)
         }
     }
@@ -1,8 +1,8 @@
 use std::{
     cmp,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::Arc,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
 };
 
 use anyhow::Context as _;
@@ -19,15 +19,19 @@ use zksync_node_fee_model::BatchFeeModelInputProvider;
 use zksync_types::{
     block::UnsealedL1BatchHeader,
     commitment::{PubdataParams, PubdataType},
-    l2::TransactionType,
+    l2::{bundle::L2TxBundle, TransactionType},
     protocol_upgrade::ProtocolUpgradeTx,
     server_notification::GatewayMigrationState,
     settlement::SettlementLayer,
     utils::display_timestamp,
     Address, ExecuteTransactionCommon, L1BatchNumber, L2BlockNumber, L2ChainId, ProtocolVersionId,
-    Transaction, H256, U256,
+    Transaction, TransactionTimeRangeConstraint, H256, U256,
+};
+use zksync_vlog::opentelemetry::tx_lifecycle::{TxStage, TxTrace};
+use zksync_vm_executor::{
+    storage::{get_base_system_contracts_by_version_id, L1BatchParamsProvider},
+    tx_policy::{TxPolicyEngine, TxPolicyStage},
 };
-use zksync_vm_executor::storage::{get_base_system_contracts_by_version_id, L1BatchParamsProvider};
 
 use crate::{
     io::{
@@ -72,6 +76,10 @@ pub struct MempoolIO {
     pubdata_limit: u64,
     last_batch_protocol_version: Option<ProtocolVersionId>,
     settlement_layer: Option<SettlementLayer>,
+    tx_policy: TxPolicyEngine,
+    /// Bundles returned to the state keeper that are not yet committed, together with their time range constraints.
+    /// Used to return bundles to the mempool as a whole if they are rolled back.
+    executed_bundles: Vec<(L2TxBundle, Vec<TransactionTimeRangeConstraint>)>,
 }
 
 #[async_trait]
@@ -189,6 +197,8 @@ impl StateKeeperIO for MempoolIO {
         cursor: &IoCursor,
         max_wait: Duration,
     ) -> anyhow::Result<Option<L1BatchParams>> {
+        // Bundles from the previous batch cannot be rolled back anymore.
+        self.executed_bundles.clear();
         let params = self
             .wait_for_new_batch_params_inner(cursor, max_wait)
             .await?;
@@ -268,40 +278,29 @@ impl StateKeeperIO for MempoolIO {
     ) -> anyhow::Result<Option<Transaction>> {
         let started_at = Instant::now();
         while started_at.elapsed() <= max_wait {
+            // Let the state keeper process a ready bundle first. Bundles are not ready while there are pending L1 transactions,
+            // so that the latter are still processed first.
+            if self.mempool.has_next_bundle(&self.filter) {
+                return Ok(None);
+            }
+
             let get_latency = KEEPER_METRICS.get_tx_from_mempool.start();
             let maybe_tx = self.mempool.next_transaction(&self.filter);
             get_latency.observe();
 
             if let Some((tx, constraint)) = maybe_tx {
-                // Reject transactions with too big gas limit. They are also rejected on the API level, but
-                // we need to secure ourselves in case some tx will somehow get into mempool.
-                if tx.gas_limit() > self.max_allowed_tx_gas_limit {
-                    tracing::warn!(
-                        "Found tx with too big gas limit in state keeper, hash: {:?}, gas_limit: {}",
-                        tx.hash(),
-                        tx.gas_limit()
-                    );
-                    self.reject(&tx, UnexecutableReason::Halt(Halt::TooBigGasLimit))
-                        .await?;
+                let rejection_reason = self
+                    .check_tx_inclusion(&tx, &constraint, l2_block_timestamp)
+                    .await;
+                Self::trace_mempool_wait(
+                    tx.hash(),
+                    tx.received_timestamp_ms,
+                    rejection_reason.as_ref(),
+                );
+                if let Some(reason) = rejection_reason {
+                    self.reject(&tx, reason).await?;
                     continue;
                 }
-
-                // Reject transactions that violate block.timestamp constraints. Such transactions should be
-                // rejected at the API level, but we need to protect ourselves in case if a transaction
-                // goes outside of the allowed range while being in the mempool
-                let matches_range = constraint
-                    .timestamp_asserter_range
-                    .is_none_or(|x| x.contains(&l2_block_timestamp));
-
-                if !matches_range {
-                    self.reject(
-                        &tx,
-                        UnexecutableReason::Halt(Halt::FailedBlockTimestampAssertion),
-                    )
-                    .await?;
-                    continue;
-                }
-
                 return Ok(Some(tx));
             } else {
                 tokio::time::sleep(self.delay_interval).await;
@@ -311,6 +310,53 @@ impl StateKeeperIO for MempoolIO {
         Ok(None)
     }
 
+    fn peek_next_txs(&self, limit: usize) -> Vec<Transaction> {
+        self.mempool.peek_l2_transactions(&self.filter, limit)
+    }
+
+    async fn next_bundle(&mut self, l2_block_timestamp: u64) -> anyhow::Result<Option<L2TxBundle>> {
+        'bundles: while let Some((bundle, constraints)) = self.mempool.next_bundle(&self.filter) {
+            for (tx, constraint) in bundle.txs().iter().zip(&constraints) {
+                let tx = Transaction::from(tx.clone());
+                if let Some(reason) = self
+                    .check_tx_inclusion(&tx, constraint, l2_block_timestamp)
+                    .await
+                {
+                    for tx in bundle.txs() {
+                        Self::trace_mempool_wait(
+                            tx.hash(),
+                            tx.received_timestamp_ms,
+                            Some(&reason),
+                        );
+                    }
+                    self.mark_bundle_as_rejected(&bundle, reason).await?;
+                    continue 'bundles;
+                }
+            }
+            for tx in bundle.txs() {
+                Self::trace_mempool_wait(tx.hash(), tx.received_timestamp_ms, None);
+            }
+            self.executed_bundles.push((bundle.clone(), constraints));
+            return Ok(Some(bundle));
+        }
+        Ok(None)
+    }
+
+    async fn rollback_bundle(&mut self, bundle: L2TxBundle) -> anyhow::Result<()> {
+        let (bundle, constraints) = self.take_executed_bundle(bundle.hash())?;
+        self.mempool.rollback_bundle(bundle, constraints);
+        Ok(())
+    }
+
+    async fn reject_bundle(
+        &mut self,
+        bundle: &L2TxBundle,
+        reason: UnexecutableReason,
+    ) -> anyhow::Result<()> {
+        self.take_executed_bundle(bundle.hash())?;
+        self.mark_bundle_as_rejected(bundle, reason).await
+    }
+
     async fn rollback(&mut self, tx: Transaction) -> anyhow::Result<()> {
         // Reset nonces in the mempool.
         let constraint = self.mempool.rollback(&tx);
@@ -320,10 +366,29 @@ impl StateKeeperIO for MempoolIO {
     }
 
     async fn rollback_l2_block(&mut self, txs: Vec<Transaction>) -> anyhow::Result<()> {
+        // Bundles must be returned to the mempool as a whole rather than as separate transactions.
+        let tx_hashes: HashSet<_> = txs.iter().map(Transaction::hash).collect();
+        let (rolled_back_bundles, executed_bundles) = self
+            .executed_bundles
+            .drain(..)
+            .partition::<Vec<_>, _>(|(bundle, _)| {
+                bundle.txs().iter().any(|tx| tx_hashes.contains(&tx.hash()))
+            });
+        self.executed_bundles = executed_bundles;
+        let bundled_tx_hashes: HashSet<_> = rolled_back_bundles
+            .iter()
+            .flat_map(|(bundle, _)| bundle.txs().iter().map(|tx| tx.hash()))
+            .collect();
+        // Bundles are pushed to the front of the mempool queue, so we process them in the reverse order.
+        for (bundle, constraints) in rolled_back_bundles.into_iter().rev() {
+            self.mempool.rollback_bundle(bundle, constraints);
+        }
+
         let mut to_add = Vec::with_capacity(txs.len());
         for tx in txs
             .into_iter()
             .filter(|tx| tx.tx_format() != TransactionType::ProtocolUpgradeTransaction)
+            .filter(|tx| !bundled_tx_hashes.contains(&tx.hash()))
             .rev()
         {
             let constraint = self.mempool.rollback(&tx);
@@ -339,6 +404,7 @@ impl StateKeeperIO for MempoolIO {
     async fn advance_mempool(&mut self, txs: Box<&mut (dyn Iterator<Item = &Transaction> + Send)>) {
         let mut next_account_nonces = HashMap::new();
         let mut next_priority_id = None;
+        let mut executed_tx_hashes = HashSet::new();
         for tx in txs.into_iter() {
             match &tx.common_data {
                 ExecuteTransactionCommon::L1(data) => {
@@ -349,7 +415,15 @@ impl StateKeeperIO for MempoolIO {
                 }
                 ExecuteTransactionCommon::ProtocolUpgrade(_) => {}
             }
+            executed_tx_hashes.insert(tx.hash());
         }
+        // Bundles in the processed block cannot be rolled back anymore.
+        self.executed_bundles.retain(|(bundle, _)| {
+            !bundle
+                .txs()
+                .iter()
+                .any(|tx| executed_tx_hashes.contains(&tx.hash()))
+        });
 
         let _guard = self.mempool.enter_critical().await;
         self.mempool.advance_after_block(AdvanceInput {
@@ -528,9 +602,113 @@ impl MempoolIO {
             pubdata_limit: config.seal_criteria.max_pubdata_per_batch.0,
             last_batch_protocol_version: None,
             settlement_layer,
+            tx_policy: TxPolicyEngine::default(),
+            executed_bundles: vec![],
         })
     }
 
+    /// Sets policies checked for each L2 transaction before it's included into a block.
+    pub fn with_tx_policy(mut self, tx_policy: TxPolicyEngine) -> Self {
+        self.tx_policy = tx_policy;
+        self
+    }
+
+    /// Checks whether a transaction obtained from the mempool can be included into an L2 block with the specified timestamp.
+    /// Returns the rejection reason if it cannot.
+    async fn check_tx_inclusion(
+        &self,
+        tx: &Transaction,
+        constraint: &TransactionTimeRangeConstraint,
+        l2_block_timestamp: u64,
+    ) -> Option<UnexecutableReason> {
+        // Reject transactions with too big gas limit. They are also rejected on the API level, but
+        // we need to secure ourselves in case some tx will somehow get into mempool.
+        if tx.gas_limit() > self.max_allowed_tx_gas_limit {
+            tracing::warn!(
+                "Found tx with too big gas limit in state keeper, hash: {:?}, gas_limit: {}",
+                tx.hash(),
+                tx.gas_limit()
+            );
+            return Some(UnexecutableReason::Halt(Halt::TooBigGasLimit));
+        }
+
+        // Reject transactions that violate block.timestamp constraints. Such transactions should be
+        // rejected at the API level, but we need to protect ourselves in case if a transaction
+        // goes outside of the allowed range while being in the mempool
+        let matches_range = constraint
+            .timestamp_asserter_range
+            .as_ref()
+            .is_none_or(|x| x.contains(&l2_block_timestamp));
+        if !matches_range {
+            return Some(UnexecutableReason::Halt(
+                Halt::FailedBlockTimestampAssertion,
+            ));
+        }
+
+        // Policies may have been tightened (e.g., the screening list may have been updated)
+        // since the transaction was accepted into the mempool.
+        if let Err(violation) = self
+            .tx_policy
+            .check_transaction(tx, TxPolicyStage::Inclusion)
+            .await
+        {
+            return Some(UnexecutableReason::PolicyViolation(violation.to_string()));
+        }
+        None
+    }
+
+    /// Records the lifecycle span for the time a transaction has spent in the mempool before being picked up
+    /// by the state keeper.
+    fn trace_mempool_wait(
+        tx_hash: H256,
+        received_timestamp_ms: u64,
+        rejection_reason: Option<&UnexecutableReason>,
+    ) {
+        let received_at = SystemTime::UNIX_EPOCH + Duration::from_millis(received_timestamp_ms);
+        let span = TxTrace::new(tx_hash.0).start_at(TxStage::MempoolWait, received_at);
+        if let Some(reason) = rejection_reason {
+            span.with_error(reason).finish();
+        } else {
+            span.finish();
+        }
+    }
+
+    fn take_executed_bundle(
+        &mut self,
+        bundle_hash: H256,
+    ) -> anyhow::Result<(L2TxBundle, Vec<TransactionTimeRangeConstraint>)> {
+        let position = self
+            .executed_bundles
+            .iter()
+            .position(|(bundle, _)| bundle.hash() == bundle_hash)
+            .with_context(|| format!("transaction bundle {bundle_hash:?} was not executed"))?;
+        Ok(self.executed_bundles.remove(position))
+    }
+
+    async fn mark_bundle_as_rejected(
+        &self,
+        bundle: &L2TxBundle,
+        reason: UnexecutableReason,
+    ) -> anyhow::Result<()> {
+        // Bundled transactions are not tracked in per-account mempool queues, so there are no nonces to reset.
+        let mut storage = self.pool.connection_tagged("state_keeper").await?;
+        tracing::warn!(
+            "Transaction bundle {:?} is rejected with error: {reason}",
+            bundle.hash()
+        );
+        let error = format!("rejected: bundle {:?}: {reason}", bundle.hash());
+        let mut transaction = storage.start_transaction().await?;
+        for tx in bundle.txs() {
+            KEEPER_METRICS.inc_rejected_txs(reason.as_metric_label());
+            transaction
+                .transactions_dal()
+                .mark_tx_as_rejected(tx.hash(), &error)
+                .await?;
+        }
+        transaction.commit().await?;
+        Ok(())
+    }
+
     fn pubdata_params(&self, protocol_version: ProtocolVersionId) -> anyhow::Result<PubdataParams> {
         let pubdata_params = match (
             protocol_version.is_pre_gateway(),
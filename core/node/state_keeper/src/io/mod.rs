@@ -5,8 +5,8 @@ use zksync_contracts::BaseSystemContracts;
 use zksync_multivm::interface::{L1BatchEnv, SystemEnv};
 use zksync_types::{
     block::L2BlockExecutionData, commitment::PubdataParams, fee_model::BatchFeeInput,
-    protocol_upgrade::ProtocolUpgradeTx, Address, InteropRoot, L1BatchNumber, L2ChainId,
-    ProtocolVersionId, Transaction, H256,
+    l2::bundle::L2TxBundle, protocol_upgrade::ProtocolUpgradeTx, Address, InteropRoot,
+    L1BatchNumber, L2ChainId, ProtocolVersionId, Transaction, H256,
 };
 use zksync_vm_executor::storage::l1_batch_params;
 
@@ -215,6 +215,42 @@ pub trait StateKeeperIO: 'static + Send + Sync + fmt::Debug + IoSealCriteria {
         l2_block_timestamp: u64,
     ) -> anyhow::Result<Option<Transaction>>;
 
+    /// Returns up to `limit` transactions that are likely to be returned by [`Self::wait_for_next_tx()`] next.
+    /// The returned transactions are not removed from the IO. Used as a hint for speculative execution;
+    /// the default implementation returns no transactions.
+    fn peek_next_txs(&self, _limit: usize) -> Vec<Transaction> {
+        vec![]
+    }
+
+    /// Returns the next atomic transaction bundle if one is available for execution. Unlike [`Self::wait_for_next_tx()`],
+    /// this method doesn't block. The default implementation doesn't support bundles and always returns `None`.
+    async fn next_bundle(
+        &mut self,
+        _l2_block_timestamp: u64,
+    ) -> anyhow::Result<Option<L2TxBundle>> {
+        Ok(None)
+    }
+
+    /// Marks the bundle as "not executed", so it can be retrieved from the IO again as a whole.
+    async fn rollback_bundle(&mut self, bundle: L2TxBundle) -> anyhow::Result<()> {
+        anyhow::bail!(
+            "unexpected rollback of transaction bundle {:?}",
+            bundle.hash()
+        )
+    }
+
+    /// Marks all transactions in the bundle as "rejected".
+    async fn reject_bundle(
+        &mut self,
+        bundle: &L2TxBundle,
+        reason: UnexecutableReason,
+    ) -> anyhow::Result<()> {
+        anyhow::bail!(
+            "unexpected rejection of transaction bundle {:?}: {reason}",
+            bundle.hash()
+        )
+    }
+
     /// Marks the transaction as "not executed", so it can be retrieved from the IO again.
     async fn rollback(&mut self, tx: Transaction) -> anyhow::Result<()>;
 
@@ -3,7 +3,7 @@
 
 use std::{
     ops,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
 };
 
 use anyhow::Context as _;
@@ -25,6 +25,7 @@ use zksync_types::{
     Address, BloomInput, ExecuteTransactionCommon, ProtocolVersionId, StorageKey, StorageLog,
     Transaction, H256,
 };
+use zksync_vlog::opentelemetry::tx_lifecycle::{self, TxStage, TxTrace};
 
 use crate::{
     io::seal_logic::l2_block_seal_subtasks::L2BlockSealProcess,
@@ -48,6 +49,7 @@ impl UpdatesManager {
         insert_protective_reads: bool,
     ) -> anyhow::Result<()> {
         let started_at = Instant::now();
+        let span_started_at = SystemTime::now();
         let finished_batch = self
             .committed_updates()
             .finished
@@ -152,6 +154,16 @@ impl UpdatesManager {
                 ZK_SYNC_BYTES_PER_BLOB as u64,
             )
             .await?;
+        if let Some(estimate) = self.proving_cost_estimate() {
+            transaction
+                .blocks_dal()
+                .set_l1_batch_proving_cost_estimate(
+                    self.l1_batch_number(),
+                    estimate.proving_time,
+                    estimate.cost,
+                )
+                .await?;
+        }
         progress.observe(None);
 
         let progress = L1_BATCH_METRICS.start(L1BatchSealStage::SetL1BatchNumberForL2Blocks);
@@ -250,9 +262,24 @@ impl UpdatesManager {
         );
 
         self.report_l1_batch_metrics(started_at, &writes_metrics);
+        self.trace_transactions(span_started_at);
         Ok(())
     }
 
+    fn trace_transactions(&self, started_at: SystemTime) {
+        if !tx_lifecycle::is_enabled() {
+            return;
+        }
+        let finished_at = SystemTime::now();
+        let l1_batch_number = i64::from(self.l1_batch_number().0);
+        for tx_hash in &self.committed_updates().executed_transaction_hashes {
+            TxTrace::new(tx_hash.0)
+                .start_at(TxStage::L1BatchSeal, started_at)
+                .with_attribute("l1_batch.number", l1_batch_number)
+                .finish_at(finished_at);
+        }
+    }
+
     fn report_l1_batch_metrics(
         &self,
         started_at: Instant,
@@ -351,6 +378,7 @@ impl L2BlockSealCommand {
         is_fictive: bool,
     ) -> anyhow::Result<()> {
         let started_at = Instant::now();
+        let span_started_at = SystemTime::now();
         self.ensure_valid_l2_block(is_fictive)
             .context("L2 block is invalid")?;
 
@@ -422,6 +450,7 @@ impl L2BlockSealCommand {
         progress.observe(Some(self.l2_block.executed_transactions.len()));
 
         self.report_l2_block_metrics(started_at);
+        self.trace_transactions(span_started_at);
         Ok(())
     }
 
@@ -543,6 +572,22 @@ impl L2BlockSealCommand {
         }
     }
 
+    fn trace_transactions(&self, started_at: SystemTime) {
+        if !tx_lifecycle::is_enabled() {
+            return;
+        }
+        let finished_at = SystemTime::now();
+        let l2_block_number = i64::from(self.l2_block.number.0);
+        let l1_batch_number = i64::from(self.l1_batch_number.0);
+        for tx in &self.l2_block.executed_transactions {
+            TxTrace::new(tx.hash.0)
+                .start_at(TxStage::L2BlockSeal, started_at)
+                .with_attribute("l2_block.number", l2_block_number)
+                .with_attribute("l1_batch.number", l1_batch_number)
+                .finish_at(finished_at);
+        }
+    }
+
     fn report_l2_block_metrics(&self, started_at: Instant) {
         let l2_block_number = self.l2_block.number;
 
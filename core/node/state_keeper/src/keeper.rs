@@ -14,17 +14,23 @@ use zksync_multivm::{
 use zksync_shared_metrics::{TxStage, APP_METRICS};
 use zksync_state::{OwnedStorage, ReadStorageFactory};
 use zksync_types::{
-    block::L2BlockExecutionData, commitment::PubdataParams, l2::TransactionType,
-    protocol_upgrade::ProtocolUpgradeTx, protocol_version::ProtocolVersionId, try_stoppable,
-    utils::display_timestamp, L1BatchNumber, L2BlockNumber, OrStopped, StopContext, Transaction,
+    block::L2BlockExecutionData,
+    commitment::PubdataParams,
+    l2::{bundle::L2TxBundle, TransactionType},
+    protocol_upgrade::ProtocolUpgradeTx,
+    protocol_version::ProtocolVersionId,
+    try_stoppable,
+    utils::display_timestamp,
+    L1BatchNumber, L2BlockNumber, OrStopped, StopContext, Transaction,
 };
+use zksync_vlog::opentelemetry::tx_lifecycle::{self, TxSpan, TxTrace};
 use zksync_vm_executor::whitelist::DeploymentTxFilter;
 
 use crate::{
     executor::TxExecutionResult,
     health::StateKeeperHealthDetails,
     io::{BatchInitParams, IoCursor, L1BatchParams, L2BlockParams, OutputHandler, StateKeeperIO},
-    metrics::{AGGREGATION_METRICS, KEEPER_METRICS},
+    metrics::{AGGREGATION_METRICS, KEEPER_METRICS, L1_BATCH_METRICS},
     seal_criteria::{ConditionalSealer, SealData, SealResolution, UnexecutableReason},
     updates::UpdatesManager,
     utils::is_canceled,
@@ -857,6 +863,137 @@ impl StateKeeperInner {
         Ok((resolution, exec_result))
     }
 
+    /// Executes an atomic transaction bundle in the batch executor, and then decides whether the batch should be sealed.
+    /// Unlike [`Self::process_one_tx()`], a failure (including a revert) of any bundle transaction makes the entire bundle
+    /// unexecutable. Seal criteria are applied to the bundle as a whole, treating it as a single transaction, so that
+    /// the bundle is never split between L1 batches.
+    /// Note: similarly to `process_one_tx()`, this method doesn't mutate `updates_manager` in the end.
+    #[tracing::instrument(skip_all, fields(bundle.hash = ?bundle.hash()))]
+    async fn process_bundle(
+        &mut self,
+        batch_executor: &mut dyn BatchExecutor<OwnedStorage>,
+        updates_manager: &mut UpdatesManager,
+        bundle: &L2TxBundle,
+    ) -> anyhow::Result<(SealResolution, Vec<TxExecutionResult>)> {
+        let txs: Vec<Transaction> = bundle.txs().iter().cloned().map(Into::into).collect();
+        let latency = KEEPER_METRICS.execute_tx_outer_time.start();
+        let exec_results = batch_executor
+            .execute_bundle(txs.clone())
+            .await
+            .with_context(|| format!("failed executing transaction bundle {:?}", bundle.hash()))?;
+        let exec_results: Vec<_> = exec_results
+            .into_iter()
+            .map(TxExecutionResult::new)
+            .collect();
+        latency.observe();
+        APP_METRICS.processed_txs[&TxStage::StateKeeper].inc_by(exec_results.len() as u64);
+
+        let latency = KEEPER_METRICS.determine_seal_resolution.start();
+        let is_first_tx = updates_manager.pending_executed_transactions_len() == 0;
+        let mut tx_data = SealData::default();
+        let mut all_storage_logs = vec![];
+        for (tx, exec_result) in txs.iter().zip(&exec_results) {
+            // See `process_one_tx()` for the explanation of out-of-gas errors handling.
+            let resolution = match exec_result {
+                TxExecutionResult::BootloaderOutOfGasForTx
+                | TxExecutionResult::RejectedByVm {
+                    reason: Halt::NotEnoughGasProvided,
+                } => {
+                    let (reason, criterion) = match exec_result {
+                        TxExecutionResult::BootloaderOutOfGasForTx => (
+                            UnexecutableReason::BootloaderOutOfGas,
+                            "bootloader_tx_out_of_gas",
+                        ),
+                        _ => (
+                            UnexecutableReason::NotEnoughGasProvided,
+                            "not_enough_gas_provided_to_start_tx",
+                        ),
+                    };
+                    let resolution = if is_first_tx {
+                        SealResolution::Unexecutable(reason)
+                    } else {
+                        SealResolution::ExcludeAndSeal
+                    };
+                    AGGREGATION_METRICS.l1_batch_reason_inc(criterion, &resolution);
+                    Some(resolution)
+                }
+                TxExecutionResult::RejectedByVm { reason } => {
+                    Some(UnexecutableReason::Halt(reason.clone()).into())
+                }
+                TxExecutionResult::Success { tx_result, .. } if tx_result.result.is_failed() => {
+                    Some(UnexecutableReason::BundleMemberReverted(tx.hash()).into())
+                }
+                TxExecutionResult::Success {
+                    tx_result,
+                    tx_metrics,
+                    gas_remaining,
+                    ..
+                } => {
+                    if let Some(tx_filter) = &self.deployment_tx_filter {
+                        if tx_filter
+                            .find_not_allowed_deployer(
+                                tx.initiator_account(),
+                                &tx_result.logs.events,
+                            )
+                            .await
+                            .is_some()
+                        {
+                            tracing::warn!(
+                                "Deployment transaction {tx:?} in bundle {:?} is not allowed. Mark the bundle as unexecutable.",
+                                bundle.hash()
+                            );
+                            return Ok((
+                                UnexecutableReason::DeploymentNotAllowed.into(),
+                                exec_results,
+                            ));
+                        }
+                    }
+
+                    tx_data.execution_metrics = tx_data.execution_metrics + **tx_metrics;
+                    tx_data.cumulative_size += tx.encoding_len();
+                    tx_data.gas_remaining = *gas_remaining;
+                    all_storage_logs.extend(tx_result.logs.storage_logs.iter());
+                    None
+                }
+            };
+            if let Some(resolution) = resolution {
+                latency.observe();
+                return Ok((resolution, exec_results));
+            }
+        }
+        anyhow::ensure!(
+            exec_results.len() == txs.len(),
+            "batch executor returned {} results for bundle {:?} with {} transactions",
+            exec_results.len(),
+            bundle.hash(),
+            txs.len()
+        );
+
+        let block_writes_metrics = updates_manager
+            .storage_writes_deduplicator_mut()
+            .apply_and_rollback(all_storage_logs.iter().copied());
+        tx_data.writes_metrics =
+            StorageWritesDeduplicator::apply_on_empty_state(all_storage_logs.iter().copied());
+        let block_data = SealData {
+            execution_metrics: tx_data.execution_metrics
+                + updates_manager.pending_execution_metrics(),
+            cumulative_size: tx_data.cumulative_size + updates_manager.pending_txs_encoding_size(),
+            writes_metrics: block_writes_metrics,
+            gas_remaining: tx_data.gas_remaining,
+        };
+        let resolution = self.sealer.should_seal_l1_batch(
+            updates_manager.l1_batch_number().0,
+            updates_manager.pending_executed_transactions_len() + txs.len(),
+            updates_manager.pending_l1_transactions_len(),
+            updates_manager.pending_interop_roots_len(),
+            &block_data,
+            &tx_data,
+            updates_manager.protocol_version(),
+        );
+        latency.observe();
+        Ok((resolution, exec_results))
+    }
+
     fn report_seal_criteria_capacity(&self, manager: &UpdatesManager) {
         let block_writes_metrics = manager.storage_writes_deduplicator().metrics();
 
@@ -1012,6 +1149,22 @@ impl StateKeeper {
                 .update_next_l2_block_timestamp(next_l2_block_timestamp);
         }
 
+        let bundle = inner
+            .io
+            .next_bundle(updates_manager.get_next_or_current_l2_block_timestamp())
+            .await
+            .context("error getting next transaction bundle")?;
+        if let Some(bundle) = bundle {
+            waiting_latency.observe();
+            if updates_manager.has_next_block_params() {
+                StateKeeperInner::start_next_l2_block(updates_manager, batch_executor).await?;
+            }
+            let outcome =
+                Self::process_bundle_iteration(updates_manager, batch_executor, inner, bundle)
+                    .await?;
+            return Ok(outcome);
+        }
+
         let Some(tx) = inner
             .io
             .wait_for_next_tx(
@@ -1035,9 +1188,20 @@ impl StateKeeper {
             StateKeeperInner::start_next_l2_block(updates_manager, batch_executor).await?;
         }
 
+        let speculation_lookahead = batch_executor.speculation_lookahead();
+        if speculation_lookahead > 0 {
+            let upcoming_txs = inner.io.peek_next_txs(speculation_lookahead);
+            batch_executor
+                .speculate(upcoming_txs)
+                .await
+                .context("failed scheduling speculative execution")?;
+        }
+
+        let tx_span = TxTrace::new(tx_hash.0).start(tx_lifecycle::TxStage::Execution);
         let (seal_resolution, exec_result) = inner
             .process_one_tx(batch_executor, updates_manager, tx.clone())
             .await?;
+        finish_execution_span(tx_span, updates_manager, &seal_resolution);
 
         let latency = KEEPER_METRICS.match_seal_resolution.start();
         match &seal_resolution {
@@ -1092,6 +1256,84 @@ impl StateKeeper {
         Ok(result)
     }
 
+    async fn process_bundle_iteration(
+        updates_manager: &mut UpdatesManager,
+        batch_executor: &mut dyn BatchExecutor<OwnedStorage>,
+        inner: &mut StateKeeperInner,
+        bundle: L2TxBundle,
+    ) -> anyhow::Result<Option<ProcessBlockIterationOutcome>> {
+        let bundle_hash = bundle.hash();
+        let tx_spans: Vec<_> = bundle
+            .txs()
+            .iter()
+            .map(|tx| {
+                TxTrace::new(tx.hash().0)
+                    .start(tx_lifecycle::TxStage::Execution)
+                    .with_attribute("bundle.hash", format!("{bundle_hash:?}"))
+            })
+            .collect();
+        let (seal_resolution, exec_results) = inner
+            .process_bundle(batch_executor, updates_manager, &bundle)
+            .await?;
+        for tx_span in tx_spans {
+            finish_execution_span(tx_span, updates_manager, &seal_resolution);
+        }
+
+        let latency = KEEPER_METRICS.match_seal_resolution.start();
+        match &seal_resolution {
+            SealResolution::NoSeal | SealResolution::IncludeAndSeal => {
+                for (tx, exec_result) in bundle.into_txs().into_iter().zip(exec_results) {
+                    let TxExecutionResult::Success {
+                        tx_result,
+                        tx_metrics: tx_execution_metrics,
+                        call_tracer_result,
+                        ..
+                    } = exec_result
+                    else {
+                        unreachable!(
+                            "Bundle inclusion seal resolution must be a result of a successful bundle execution",
+                        );
+                    };
+                    updates_manager.extend_from_executed_transaction(
+                        tx.into(),
+                        *tx_result,
+                        *tx_execution_metrics,
+                        call_tracer_result,
+                    );
+                }
+            }
+            SealResolution::ExcludeAndSeal => {
+                batch_executor.rollback_last_tx().await.with_context(|| {
+                    format!("failed rolling back bundle {bundle_hash:?} in batch executor")
+                })?;
+                inner.io.rollback_bundle(bundle).await.with_context(|| {
+                    format!("failed rolling back bundle {bundle_hash:?} in I/O")
+                })?;
+            }
+            SealResolution::Unexecutable(reason) => {
+                batch_executor.rollback_last_tx().await.with_context(|| {
+                    format!("failed rolling back bundle {bundle_hash:?} in batch executor")
+                })?;
+                inner
+                    .io
+                    .reject_bundle(&bundle, reason.clone())
+                    .await
+                    .with_context(|| format!("cannot reject bundle {bundle_hash:?}"))?;
+            }
+        };
+        let result = if seal_resolution.should_seal() {
+            tracing::debug!(
+                "L2 block #{} should be sealed with conditional sealer resolution {seal_resolution:?} after executing bundle {bundle_hash:?}",
+                updates_manager.last_pending_l2_block().number
+            );
+            Some(ProcessBlockIterationOutcome::SealBatch)
+        } else {
+            None
+        };
+        latency.observe();
+        Ok(result)
+    }
+
     async fn seal_batch(&mut self) -> anyhow::Result<()> {
         let mut state = self.batch_state.finish();
         assert!(!state.updates_manager.has_next_block_params());
@@ -1104,6 +1346,17 @@ impl StateKeeper {
         state.updates_manager.clear_interop_roots();
         let (finished_batch, _) = state.batch_executor.finish_batch().await?;
         state.updates_manager.finish_batch(finished_batch);
+        // Circuit statistics include the batch tip at this point.
+        let circuit_statistic = state
+            .updates_manager
+            .pending_execution_metrics()
+            .circuit_statistic;
+        if let Some(estimate) = self.inner.sealer.estimate_proving_cost(&circuit_statistic) {
+            L1_BATCH_METRICS
+                .estimated_proving_time
+                .observe(estimate.proving_time);
+            state.updates_manager.set_proving_cost_estimate(estimate);
+        }
         let l1_batch_number = state.updates_manager.l1_batch_number();
         self.inner
             .output_handler
@@ -1229,3 +1482,30 @@ impl StateKeeper {
         Ok(())
     }
 }
+
+/// Finishes the lifecycle span for a transaction executed by the state keeper.
+fn finish_execution_span(
+    span: TxSpan,
+    updates_manager: &UpdatesManager,
+    seal_resolution: &SealResolution,
+) {
+    let resolution = match seal_resolution {
+        SealResolution::NoSeal => "no_seal",
+        SealResolution::IncludeAndSeal => "include_and_seal",
+        SealResolution::ExcludeAndSeal => "exclude_and_seal",
+        SealResolution::Unexecutable(_) => "unexecutable",
+    };
+    let l2_block_number = updates_manager.last_pending_l2_block().number;
+    let span = span
+        .with_attribute(
+            "l1_batch.number",
+            i64::from(updates_manager.l1_batch_number().0),
+        )
+        .with_attribute("l2_block.number", i64::from(l2_block_number.0))
+        .with_attribute("seal_resolution", resolution);
+    if let SealResolution::Unexecutable(reason) = seal_resolution {
+        span.with_error(reason).finish();
+    } else {
+        span.finish();
+    }
+}
@@ -94,6 +94,9 @@ impl MempoolFetcher {
             KEEPER_METRICS
                 .mempool_purged_accounts
                 .set(mempool_info.purged_accounts.len());
+            KEEPER_METRICS
+                .mempool_purged_bundles
+                .set(mempool_info.purged_bundles.len());
 
             let protocol_version = storage_transaction
                 .blocks_dal()
@@ -132,6 +135,16 @@ impl MempoolFetcher {
                 )
                 .await
                 .context("failed syncing mempool")?;
+            let bundles = storage_transaction
+                .transactions_dal()
+                .sync_mempool_bundles(
+                    &mempool_info.purged_bundles,
+                    gas_per_pubdata,
+                    fee_per_gas,
+                    self.sync_batch_size,
+                )
+                .await
+                .context("failed syncing mempool bundles")?;
             storage_transaction.commit().await?;
 
             #[cfg(test)]
@@ -159,6 +172,8 @@ impl MempoolFetcher {
                 self.mempool.insert(chunk, nonces);
             }
             drop(connection);
+            // Bundles don't interact with per-account nonces in the mempool, so they can be inserted outside the critical section.
+            self.mempool.insert_bundles(bundles);
 
             #[cfg(test)]
             self.transaction_hashes_sender.send(transaction_hashes).ok();
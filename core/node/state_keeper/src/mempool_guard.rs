@@ -7,7 +7,8 @@ use tokio::sync::{Mutex as TokioMutex, MutexGuard};
 use zksync_dal::{Connection, Core, CoreDal};
 use zksync_mempool::{AdvanceInput, L2TxFilter, MempoolInfo, MempoolStore};
 use zksync_types::{
-    Address, Nonce, PriorityOpId, ProtocolVersionId, Transaction, TransactionTimeRangeConstraint,
+    l2::bundle::L2TxBundle, Address, Nonce, PriorityOpId, ProtocolVersionId, Transaction,
+    TransactionTimeRangeConstraint,
 };
 
 use super::metrics::StateKeeperGauges;
@@ -83,6 +84,48 @@ impl MempoolGuard {
             .next_transaction(filter)
     }
 
+    pub fn peek_l2_transactions(&self, filter: &L2TxFilter, limit: usize) -> Vec<Transaction> {
+        self.mempool
+            .lock()
+            .expect("failed to acquire mempool lock")
+            .peek_l2_transactions(filter, limit)
+    }
+
+    pub fn insert_bundles(&self, bundles: Vec<(L2TxBundle, Vec<TransactionTimeRangeConstraint>)>) {
+        self.mempool
+            .lock()
+            .expect("failed to acquire mempool lock")
+            .insert_bundles(bundles);
+    }
+
+    pub fn has_next_bundle(&self, filter: &L2TxFilter) -> bool {
+        self.mempool
+            .lock()
+            .expect("failed to acquire mempool lock")
+            .has_next_bundle(filter)
+    }
+
+    pub fn next_bundle(
+        &mut self,
+        filter: &L2TxFilter,
+    ) -> Option<(L2TxBundle, Vec<TransactionTimeRangeConstraint>)> {
+        self.mempool
+            .lock()
+            .expect("failed to acquire mempool lock")
+            .next_bundle(filter)
+    }
+
+    pub fn rollback_bundle(
+        &mut self,
+        bundle: L2TxBundle,
+        constraints: Vec<TransactionTimeRangeConstraint>,
+    ) {
+        self.mempool
+            .lock()
+            .expect("failed to acquire mempool lock")
+            .rollback_bundle(bundle, constraints);
+    }
+
     pub fn rollback(&mut self, rejected: &Transaction) -> TransactionTimeRangeConstraint {
         self.mempool
             .lock()
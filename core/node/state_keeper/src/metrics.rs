@@ -68,6 +68,8 @@ pub struct StateKeeperMetrics {
     pub mempool_stashed_accounts: Gauge<usize>,
     /// Number of purged accounts in mempool
     pub mempool_purged_accounts: Gauge<usize>,
+    /// Number of transaction bundles purged from mempool
+    pub mempool_purged_bundles: Gauge<usize>,
     /// Latency of the state keeper waiting for a transaction.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub waiting_for_tx: Histogram<Duration>,
@@ -153,6 +155,8 @@ pub(super) struct StateKeeperGauges {
     mempool_l2_size: Gauge<u64>,
     /// Current size of the L2 priority queue.
     l2_priority_queue_size: Gauge<usize>,
+    /// Current number of atomic transaction bundles in the mempool.
+    mempool_bundle_count: Gauge<usize>,
 }
 
 impl StateKeeperGauges {
@@ -171,6 +175,7 @@ impl StateKeeperGauges {
                 gauges
                     .l2_priority_queue_size
                     .set(stats.l2_priority_queue_size);
+                gauges.mempool_bundle_count.set(stats.bundle_count);
                 gauges
             })
         });
@@ -277,6 +282,11 @@ const COUNT_BUCKETS: Buckets = Buckets::values(&[
 const L1_BATCH_SEAL_DELTA_BUCKETS: Buckets = Buckets::values(&[
     0.1, 0.5, 1.0, 5.0, 10.0, 20.0, 30.0, 40.0, 60.0, 90.0, 120.0, 180.0, 240.0, 300.0,
 ]);
+/// Buckets for estimated prover time of L1 batches (in seconds), from 1 minute to 1 day.
+const ESTIMATED_PROVING_TIME_BUCKETS: Buckets = Buckets::values(&[
+    60.0, 120.0, 300.0, 600.0, 1_200.0, 1_800.0, 3_600.0, 7_200.0, 14_400.0, 28_800.0, 43_200.0,
+    86_400.0,
+]);
 
 /// Metrics related to L1 batch sealing.
 #[derive(Debug, Metrics)]
@@ -285,6 +295,9 @@ pub(crate) struct L1BatchMetrics {
     /// Delta between sealing consecutive L1 batches.
     #[metrics(buckets = L1_BATCH_SEAL_DELTA_BUCKETS)]
     pub seal_delta: Histogram<Duration>,
+    /// Prover time for a single L1 batch estimated by the proving cost seal criterion.
+    #[metrics(buckets = ESTIMATED_PROVING_TIME_BUCKETS)]
+    pub estimated_proving_time: Histogram<Duration>,
     /// Number of initial writes in a single L1 batch.
     #[metrics(buckets = COUNT_BUCKETS)]
     pub initial_writes: Histogram<usize>,
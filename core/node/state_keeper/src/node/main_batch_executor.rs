@@ -1,3 +1,5 @@
+use std::num::NonZeroUsize;
+
 use zksync_node_framework::wiring_layer::{WiringError, WiringLayer};
 use zksync_types::vm::FastVmMode;
 use zksync_vm_executor::batch::{BatchTracer, MainBatchExecutorFactory, TraceCalls};
@@ -10,6 +12,7 @@ pub struct MainBatchExecutorLayer {
     save_call_traces: bool,
     optional_bytecode_compression: bool,
     fast_vm_mode: FastVmMode,
+    speculation: Option<(NonZeroUsize, NonZeroUsize)>,
 }
 
 impl MainBatchExecutorLayer {
@@ -18,6 +21,7 @@ impl MainBatchExecutorLayer {
             save_call_traces,
             optional_bytecode_compression,
             fast_vm_mode: FastVmMode::default(),
+            speculation: None,
         }
     }
 
@@ -26,9 +30,19 @@ impl MainBatchExecutorLayer {
         self
     }
 
+    /// Enables speculative execution of upcoming transactions. Speculation is disabled if either `workers`
+    /// or `lookahead` is 0.
+    pub fn with_speculation(mut self, workers: usize, lookahead: usize) -> Self {
+        self.speculation = NonZeroUsize::new(workers).zip(NonZeroUsize::new(lookahead));
+        self
+    }
+
     fn create_executor<Tr: BatchTracer>(&self) -> BatchExecutorResource {
         let mut executor = MainBatchExecutorFactory::<Tr>::new(self.optional_bytecode_compression);
         executor.set_fast_vm_mode(self.fast_vm_mode);
+        if let Some((workers, lookahead)) = self.speculation {
+            executor.enable_speculation(workers, lookahead);
+        }
         executor.into()
     }
 }
@@ -16,7 +16,7 @@ use zksync_node_framework::{
 };
 use zksync_shared_resources::contracts::L2ContractsResource;
 use zksync_types::{commitment::PubdataType, L2ChainId};
-use zksync_vm_executor::node::ApiTransactionFilter;
+use zksync_vm_executor::{node::ApiTransactionFilter, tx_policy::TxPolicyEngine};
 
 use super::resources::StateKeeperIOResource;
 use crate::{
@@ -29,6 +29,7 @@ use crate::{
 ///
 /// - `FeeInputResource`
 /// - `PoolResource<MasterPool>`
+/// - `TxPolicyEngine` (optional)
 ///
 /// ## Adds resources
 ///
@@ -53,6 +54,7 @@ pub struct Input {
     master_pool: PoolResource<MasterPool>,
     l2_contracts: L2ContractsResource,
     settlement_mode: SettlementModeResource,
+    tx_policy: Option<TxPolicyEngine>,
 }
 
 #[derive(Debug, IntoContext)]
@@ -138,7 +140,7 @@ impl WiringLayer for MempoolIOLayer {
             .get_singleton()
             .await
             .context("Get master pool")?;
-        let io = MempoolIO::new(
+        let mut io = MempoolIO::new(
             mempool_guard,
             batch_fee_input_provider,
             mempool_db_pool,
@@ -150,6 +152,9 @@ impl WiringLayer for MempoolIOLayer {
             self.pubdata_type,
             input.settlement_mode.settlement_layer_for_sending_txs(),
         )?;
+        if let Some(tx_policy) = input.tx_policy {
+            io = io.with_tx_policy(tx_policy);
+        }
 
         // Create sealer.
         let sealer = Arc::new(SequencerSealer::new(self.state_keeper_config.seal_criteria));
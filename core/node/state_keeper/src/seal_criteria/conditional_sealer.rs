@@ -9,7 +9,7 @@ use std::fmt;
 use async_trait::async_trait;
 use zksync_config::configs::chain::SealCriteriaConfig;
 use zksync_multivm::{
-    interface::TransactionExecutionMetrics,
+    interface::{CircuitStatistic, TransactionExecutionMetrics},
     utils::{
         get_bootloader_max_txs_in_batch, get_max_batch_base_layer_circuits,
         get_max_vm_pubdata_per_batch,
@@ -18,7 +18,9 @@ use zksync_multivm::{
 use zksync_types::{commitment::L1BatchCommitmentMode, ProtocolVersionId, Transaction};
 use zksync_vm_executor::interface::TransactionFilter;
 
-use super::{criteria, SealCriterion, SealData, SealResolution, AGGREGATION_METRICS};
+use super::{
+    criteria, ProvingCostEstimate, SealCriterion, SealData, SealResolution, AGGREGATION_METRICS,
+};
 
 /// Checks if an L1 batch should be sealed after executing a transaction.
 pub trait ConditionalSealer: 'static + fmt::Debug + Send + Sync {
@@ -44,6 +46,15 @@ pub trait ConditionalSealer: 'static + fmt::Debug + Send + Sync {
         block_data: &SealData,
         protocol_version: ProtocolVersionId,
     ) -> Vec<(&'static str, f64)>;
+
+    /// Estimates the proving cost of an L1 batch with the specified base layer circuits.
+    /// Returns `None` if the sealer doesn't estimate proving costs.
+    fn estimate_proving_cost(
+        &self,
+        _circuit_statistic: &CircuitStatistic,
+    ) -> Option<ProvingCostEstimate> {
+        None
+    }
 }
 
 /// Implementation of [`ConditionalSealer`] used by the main node.
@@ -166,6 +177,18 @@ impl ConditionalSealer for SequencerSealer {
             })
             .collect()
     }
+
+    fn estimate_proving_cost(
+        &self,
+        circuit_statistic: &CircuitStatistic,
+    ) -> Option<ProvingCostEstimate> {
+        let config = self.config.proving_cost.as_ref()?;
+        let proving_time = criteria::estimate_proving_time(config, circuit_statistic);
+        Some(ProvingCostEstimate {
+            proving_time,
+            cost: config.proving_cost(proving_time),
+        })
+    }
 }
 
 /// Sealers excluding pubdata for verifying blocks produced by sequencer
@@ -191,7 +214,19 @@ fn default_sealers() -> Vec<Box<dyn SealCriterion>> {
 
 impl SequencerSealer {
     pub fn new(config: SealCriteriaConfig) -> Self {
-        let sealers = default_sealers();
+        let mut sealers = default_sealers();
+        if let Some(proving_cost) = &config.proving_cost {
+            for circuit_type in proving_cost.circuit_proving_time_overrides.circuit_types() {
+                if !criteria::CIRCUIT_TYPES.contains(&circuit_type) {
+                    tracing::warn!(
+                        "Proving time is overridden for unknown circuit type `{circuit_type}`; the override has no effect. \
+                         Known circuit types: {:?}",
+                        criteria::CIRCUIT_TYPES
+                    );
+                }
+            }
+            sealers.push(Box::new(criteria::ProvingCostCriterion));
+        }
         Self { config, sealers }
     }
 
@@ -279,6 +314,7 @@ impl PanicSealer {
             close_block_at_eth_params_percentage: 1.0,
             close_block_at_gas_percentage: 1.0,
             max_circuits_per_batch: get_max_batch_base_layer_circuits(protocol_version.into()),
+            proving_cost: None,
         }
     }
 }
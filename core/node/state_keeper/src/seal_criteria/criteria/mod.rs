@@ -3,13 +3,19 @@ mod geometry_seal_criteria;
 mod interop_roots;
 mod l1_l2_txs;
 mod l2_l1_logs;
+mod proving_cost;
 mod pubdata_bytes;
 mod slots;
 mod tx_encoding_size;
 
 pub(crate) use self::{
-    gas_for_batch_tip::GasForBatchTipCriterion, geometry_seal_criteria::CircuitsCriterion,
-    interop_roots::InteropRootsCriterion, l1_l2_txs::L1L2TxsCriterion,
-    l2_l1_logs::L2L1LogsCriterion, pubdata_bytes::PubDataBytesCriterion, slots::SlotsCriterion,
+    gas_for_batch_tip::GasForBatchTipCriterion,
+    geometry_seal_criteria::CircuitsCriterion,
+    interop_roots::InteropRootsCriterion,
+    l1_l2_txs::L1L2TxsCriterion,
+    l2_l1_logs::L2L1LogsCriterion,
+    proving_cost::{estimate_proving_time, ProvingCostCriterion, CIRCUIT_TYPES},
+    pubdata_bytes::PubDataBytesCriterion,
+    slots::SlotsCriterion,
     tx_encoding_size::TxEncodingSizeCriterion,
 };
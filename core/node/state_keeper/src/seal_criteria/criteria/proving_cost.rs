@@ -0,0 +1,264 @@
+use std::time::Duration;
+
+use zksync_config::configs::chain::{ProvingCostConfig, SealCriteriaConfig};
+use zksync_multivm::interface::CircuitStatistic;
+use zksync_types::ProtocolVersionId;
+
+use crate::seal_criteria::{SealCriterion, SealData, SealResolution};
+
+/// Names of base layer circuit types as used in [`ProvingCostConfig::circuit_proving_time_overrides`].
+pub(crate) const CIRCUIT_TYPES: [&str; 17] = [
+    "main_vm",
+    "ram_permutation",
+    "storage_application",
+    "storage_sorter",
+    "code_decommitter",
+    "code_decommitter_sorter",
+    "log_demuxer",
+    "events_sorter",
+    "keccak256",
+    "ecrecover",
+    "sha256",
+    "secp256k1_verify",
+    "transient_storage_checker",
+    "modexp",
+    "ecadd",
+    "ecmul",
+    "ecpairing",
+];
+
+fn circuit_counts(statistic: &CircuitStatistic) -> [f32; 17] {
+    [
+        statistic.main_vm,
+        statistic.ram_permutation,
+        statistic.storage_application,
+        statistic.storage_sorter,
+        statistic.code_decommitter,
+        statistic.code_decommitter_sorter,
+        statistic.log_demuxer,
+        statistic.events_sorter,
+        statistic.keccak256,
+        statistic.ecrecover,
+        statistic.sha256,
+        statistic.secp256k1_verify,
+        statistic.transient_storage_checker,
+        statistic.modexp,
+        statistic.ecadd,
+        statistic.ecmul,
+        statistic.ecpairing,
+    ]
+}
+
+/// Estimates the prover time for the specified base layer circuits. Circuit counts are rounded up
+/// for each circuit type, same as in [`CircuitStatistic::total()`].
+pub(crate) fn estimate_proving_time(
+    config: &ProvingCostConfig,
+    statistic: &CircuitStatistic,
+) -> Duration {
+    CIRCUIT_TYPES
+        .iter()
+        .zip(circuit_counts(statistic))
+        .map(|(&circuit_type, count)| {
+            let proving_time = config
+                .circuit_proving_time_overrides
+                .get(circuit_type)
+                .unwrap_or(config.circuit_proving_time);
+            proving_time * count.ceil() as u32
+        })
+        .sum()
+}
+
+/// Seals the batch when its estimated prover time or proving cost exceeds the configured budget.
+///
+/// Unlike [`CircuitsCriterion`](super::CircuitsCriterion), this criterion never rejects transactions:
+/// a transaction exceeding the budget on its own is included into a separate batch. The batch tip is not accounted for,
+/// since the bound on its circuits used by `CircuitsCriterion` is too conservative for cost estimation.
+#[derive(Debug)]
+pub struct ProvingCostCriterion;
+
+impl SealCriterion for ProvingCostCriterion {
+    fn should_seal(
+        &self,
+        config: &SealCriteriaConfig,
+        _tx_count: usize,
+        _l1_tx_count: usize,
+        _interop_roots_count: usize,
+        block_data: &SealData,
+        tx_data: &SealData,
+        _protocol_version: ProtocolVersionId,
+    ) -> SealResolution {
+        let Some(proving_cost_config) = &config.proving_cost else {
+            return SealResolution::NoSeal;
+        };
+        let Some(budget) = proving_cost_config.proving_time_budget() else {
+            return SealResolution::NoSeal;
+        };
+        let include_and_seal_bound = budget.mul_f64(config.close_block_at_geometry_percentage);
+        let block_proving_time = estimate_proving_time(
+            proving_cost_config,
+            &block_data.execution_metrics.circuit_statistic,
+        );
+
+        if block_proving_time >= budget {
+            let tx_proving_time = estimate_proving_time(
+                proving_cost_config,
+                &tx_data.execution_metrics.circuit_statistic,
+            );
+            if block_proving_time <= tx_proving_time {
+                // The batch consists only of the processed transaction(s); excluding them wouldn't help.
+                SealResolution::IncludeAndSeal
+            } else {
+                SealResolution::ExcludeAndSeal
+            }
+        } else if block_proving_time >= include_and_seal_bound {
+            SealResolution::IncludeAndSeal
+        } else {
+            SealResolution::NoSeal
+        }
+    }
+
+    fn capacity_filled(
+        &self,
+        config: &SealCriteriaConfig,
+        _tx_count: usize,
+        _l1_tx_count: usize,
+        _interop_roots_count: usize,
+        block_data: &SealData,
+        _protocol_version: ProtocolVersionId,
+    ) -> Option<f64> {
+        let proving_cost_config = config.proving_cost.as_ref()?;
+        let budget = proving_cost_config.proving_time_budget()?;
+        let proving_time = estimate_proving_time(
+            proving_cost_config,
+            &block_data.execution_metrics.circuit_statistic,
+        );
+        Some(proving_time.as_secs_f64() / budget.as_secs_f64())
+    }
+
+    fn prom_criterion_name(&self) -> &'static str {
+        "proving_cost"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_config::configs::chain::CircuitProvingTimeOverrides;
+    use zksync_multivm::interface::VmExecutionMetrics;
+
+    use super::*;
+
+    fn get_config() -> SealCriteriaConfig {
+        SealCriteriaConfig {
+            close_block_at_geometry_percentage: 0.9,
+            proving_cost: Some(ProvingCostConfig {
+                circuit_proving_time: Duration::from_secs(1),
+                circuit_proving_time_overrides: CircuitProvingTimeOverrides::from_iter([(
+                    "main_vm", 2_000,
+                )]),
+                prover_cost_per_hour: 2.0,
+                max_proving_time_per_batch: Some(Duration::from_secs(1_000)),
+                max_cost_per_batch: None,
+            }),
+            ..SealCriteriaConfig::for_tests()
+        }
+    }
+
+    fn seal_data(main_vm: f32, storage_application: f32) -> SealData {
+        SealData {
+            execution_metrics: VmExecutionMetrics {
+                circuit_statistic: CircuitStatistic {
+                    main_vm,
+                    storage_application,
+                    ..CircuitStatistic::default()
+                },
+                ..VmExecutionMetrics::default()
+            },
+            ..SealData::default()
+        }
+    }
+
+    #[test]
+    fn estimating_proving_time() {
+        let config = get_config();
+        let config = config.proving_cost.as_ref().unwrap();
+        let statistic = CircuitStatistic {
+            main_vm: 1.5,
+            storage_application: 3.0,
+            keccak256: 0.1,
+            ..CircuitStatistic::default()
+        };
+        // 2 main VM circuits * 2s + 3 storage application circuits * 1s + 1 keccak circuit * 1s
+        assert_eq!(
+            estimate_proving_time(config, &statistic),
+            Duration::from_secs(8)
+        );
+    }
+
+    #[test]
+    fn proving_time_budget() {
+        let mut config = get_config().proving_cost.unwrap();
+        assert_eq!(
+            config.proving_time_budget(),
+            Some(Duration::from_secs(1_000))
+        );
+        config.max_cost_per_batch = Some(0.25); // = 450s of prover time
+        assert_eq!(config.proving_time_budget(), Some(Duration::from_secs(450)));
+        config.max_proving_time_per_batch = None;
+        assert_eq!(config.proving_time_budget(), Some(Duration::from_secs(450)));
+        config.max_cost_per_batch = None;
+        assert_eq!(config.proving_time_budget(), None);
+
+        assert_eq!(config.proving_cost(Duration::from_secs(1_800)), 1.0);
+    }
+
+    #[test]
+    fn proving_cost_seal_criterion() {
+        let config = get_config();
+        let protocol_version = ProtocolVersionId::latest();
+        let criterion = ProvingCostCriterion;
+        let should_seal = |block_data: &SealData, tx_data: &SealData| {
+            criterion.should_seal(&config, 2, 0, 0, block_data, tx_data, protocol_version)
+        };
+
+        let tx_data = seal_data(1.0, 1.0);
+        let resolution = should_seal(&seal_data(100.0, 100.0), &tx_data);
+        assert_eq!(resolution, SealResolution::NoSeal);
+
+        // 2 * 400 + 100 >= 900
+        let resolution = should_seal(&seal_data(400.0, 100.0), &tx_data);
+        assert_eq!(resolution, SealResolution::IncludeAndSeal);
+
+        let resolution = should_seal(&seal_data(500.0, 100.0), &tx_data);
+        assert_eq!(resolution, SealResolution::ExcludeAndSeal);
+
+        // A single transaction exceeding the budget must be included.
+        let tx_data = seal_data(500.0, 100.0);
+        let resolution = should_seal(&seal_data(500.0, 100.0), &tx_data);
+        assert_eq!(resolution, SealResolution::IncludeAndSeal);
+
+        let capacity = criterion
+            .capacity_filled(&config, 2, 0, 0, &seal_data(200.0, 0.0), protocol_version)
+            .unwrap();
+        assert!((capacity - 0.4).abs() < 1e-9, "{capacity}");
+    }
+
+    #[test]
+    fn proving_cost_criterion_without_config() {
+        let config = SealCriteriaConfig::for_tests();
+        let criterion = ProvingCostCriterion;
+        let data = seal_data(1_000_000.0, 0.0);
+        let resolution = criterion.should_seal(
+            &config,
+            2,
+            0,
+            0,
+            &data,
+            &SealData::default(),
+            ProtocolVersionId::latest(),
+        );
+        assert_eq!(resolution, SealResolution::NoSeal);
+        let capacity =
+            criterion.capacity_filled(&config, 2, 0, 0, &data, ProtocolVersionId::latest());
+        assert_eq!(capacity, None);
+    }
+}
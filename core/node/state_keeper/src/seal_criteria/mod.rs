@@ -10,14 +10,14 @@
 //! Maintaining all the criteria in one place has proven itself to be very error-prone,
 //! thus now every criterion is independent of the others.
 
-use std::fmt;
+use std::{fmt, time::Duration};
 
 use zksync_config::configs::chain::SealCriteriaConfig;
 use zksync_multivm::{
     interface::{DeduplicatedWritesMetrics, Halt, TransactionExecutionMetrics, VmExecutionMetrics},
     vm_latest::TransactionVmExt,
 };
-use zksync_types::{ProtocolVersionId, Transaction};
+use zksync_types::{ProtocolVersionId, Transaction, H256};
 
 pub use self::{
     conditional_sealer::{ConditionalSealer, NoopSealer, PanicSealer, SequencerSealer},
@@ -68,6 +68,8 @@ pub enum UnexecutableReason {
     NotEnoughGasProvided,
     TooMuchUserL2L1Logs,
     DeploymentNotAllowed,
+    PolicyViolation(String),
+    BundleMemberReverted(H256),
 }
 
 impl UnexecutableReason {
@@ -84,6 +86,8 @@ impl UnexecutableReason {
             UnexecutableReason::NotEnoughGasProvided => "NotEnoughGasProvided",
             UnexecutableReason::TooMuchUserL2L1Logs => "TooMuchUserL2L1Logs",
             UnexecutableReason::DeploymentNotAllowed => "DeploymentNotAllowed",
+            UnexecutableReason::PolicyViolation(_) => "PolicyViolation",
+            UnexecutableReason::BundleMemberReverted(_) => "BundleMemberReverted",
         }
     }
 }
@@ -110,6 +114,12 @@ impl fmt::Display for UnexecutableReason {
             UnexecutableReason::NotEnoughGasProvided => write!(f, "Not enough gas provided"),
             UnexecutableReason::TooMuchUserL2L1Logs => write!(f, "Too much user l2 l1 logs"),
             UnexecutableReason::DeploymentNotAllowed => write!(f, "Deployment not allowed"),
+            UnexecutableReason::PolicyViolation(reason) => {
+                write!(f, "Transaction policy violated: {reason}")
+            }
+            UnexecutableReason::BundleMemberReverted(tx_hash) => {
+                write!(f, "Bundle transaction {tx_hash:?} reverted")
+            }
         }
     }
 }
@@ -158,6 +168,15 @@ impl SealResolution {
     }
 }
 
+/// Estimated proving cost of an L1 batch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProvingCostEstimate {
+    /// Estimated prover time for base layer circuits of the batch.
+    pub proving_time: Duration,
+    /// Estimated proving cost in the units configured for the sealer.
+    pub cost: f64,
+}
+
 /// Information about transaction or block applicable either to a single transaction, or
 /// to the entire L2 block / L1 batch.
 #[derive(Debug, Default)]
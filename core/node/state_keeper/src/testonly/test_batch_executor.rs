@@ -27,9 +27,9 @@ use zksync_multivm::{
 use zksync_node_test_utils::create_l2_transaction;
 use zksync_state::{interface::StorageView, OwnedStorage, ReadStorageFactory};
 use zksync_types::{
-    commitment::PubdataParams, fee_model::BatchFeeInput, l2_to_l1_log::UserL2ToL1Log,
-    protocol_upgrade::ProtocolUpgradeTx, Address, L1BatchNumber, L2BlockNumber, L2ChainId,
-    OrStopped, ProtocolVersionId, Transaction, H256,
+    commitment::PubdataParams, fee_model::BatchFeeInput, l2::bundle::L2TxBundle,
+    l2_to_l1_log::UserL2ToL1Log, protocol_upgrade::ProtocolUpgradeTx, Address, L1BatchNumber,
+    L2BlockNumber, L2ChainId, OrStopped, ProtocolVersionId, Transaction, H256,
 };
 
 use crate::{
@@ -119,6 +119,38 @@ impl TestScenario {
         self
     }
 
+    /// Expect the state keeper to request a transaction bundle from IO. Adds outcomes of all bundle transactions that would
+    /// be returned to the state keeper from the batch executor; they must be truncated after the first failed transaction.
+    pub(crate) fn next_bundle(
+        mut self,
+        description: &'static str,
+        bundle: L2TxBundle,
+        results: Vec<BatchTransactionExecutionResult>,
+    ) -> Self {
+        self.actions
+            .push_back(ScenarioItem::Bundle(description, bundle, results));
+        self
+    }
+
+    /// Expect the state keeper to rollback the transaction bundle (i.e. return to the mempool).
+    pub(crate) fn bundle_rollback(mut self, description: &'static str, bundle: L2TxBundle) -> Self {
+        self.actions
+            .push_back(ScenarioItem::RollbackBundle(description, bundle));
+        self
+    }
+
+    /// Expect the state keeper to reject the transaction bundle.
+    pub(crate) fn bundle_rejected(
+        mut self,
+        description: &'static str,
+        bundle: L2TxBundle,
+        err: UnexecutableReason,
+    ) -> Self {
+        self.actions
+            .push_back(ScenarioItem::RejectBundle(description, bundle, err));
+        self
+    }
+
     /// Expect the state keeper to rollback the transaction (i.e. return to the mempool).
     pub(crate) fn tx_rollback(mut self, description: &'static str, tx: Transaction) -> Self {
         self.actions
@@ -303,6 +335,15 @@ pub(crate) fn random_tx(tx_number: u64) -> Transaction {
     tx.into()
 }
 
+/// Creates a bundle of random transactions with the specified numbers (see [`random_tx()`]).
+pub(crate) fn random_bundle(tx_numbers: &[u64]) -> L2TxBundle {
+    let txs = tx_numbers
+        .iter()
+        .map(|&tx_number| random_tx(tx_number).try_into().unwrap())
+        .collect();
+    L2TxBundle::new(txs)
+}
+
 /// Creates a random protocol upgrade transaction. Provided tx number would be used as a transaction hash,
 /// so it's easier to understand which transaction caused test to fail.
 pub(crate) fn random_upgrade_tx(tx_number: u64) -> ProtocolUpgradeTx {
@@ -348,9 +389,16 @@ enum ScenarioItem {
     /// Increments protocol version in IO state.
     IncrementProtocolVersion(&'static str),
     Tx(&'static str, Transaction, BatchTransactionExecutionResult),
+    Bundle(
+        &'static str,
+        L2TxBundle,
+        Vec<BatchTransactionExecutionResult>,
+    ),
     Rollback(&'static str, Transaction),
+    RollbackBundle(&'static str, L2TxBundle),
     RollbackBlock(&'static str, L2BlockNumber, Vec<Transaction>),
     Reject(&'static str, Transaction, UnexecutableReason),
+    RejectBundle(&'static str, L2TxBundle, UnexecutableReason),
     L2BlockSeal(
         &'static str,
         Option<Box<dyn FnOnce(&UpdatesManager) + Send>>,
@@ -380,11 +428,22 @@ impl fmt::Debug for ScenarioItem {
                 .field(tx)
                 .field(result)
                 .finish(),
+            Self::Bundle(descr, bundle, results) => formatter
+                .debug_tuple("Bundle")
+                .field(descr)
+                .field(bundle)
+                .field(results)
+                .finish(),
             Self::Rollback(descr, tx) => formatter
                 .debug_tuple("Rollback")
                 .field(descr)
                 .field(tx)
                 .finish(),
+            Self::RollbackBundle(descr, bundle) => formatter
+                .debug_tuple("RollbackBundle")
+                .field(descr)
+                .field(&bundle.hash())
+                .finish(),
             Self::RollbackBlock(descr, number, _) => formatter
                 .debug_tuple("RollbackBlock")
                 .field(descr)
@@ -396,6 +455,12 @@ impl fmt::Debug for ScenarioItem {
                 .field(tx)
                 .field(err)
                 .finish(),
+            Self::RejectBundle(descr, bundle, err) => formatter
+                .debug_tuple("RejectBundle")
+                .field(descr)
+                .field(&bundle.hash())
+                .field(err)
+                .finish(),
             Self::L2BlockSeal(descr, _) => {
                 formatter.debug_tuple("L2BlockSeal").field(descr).finish()
             }
@@ -460,9 +525,23 @@ impl TestBatchExecutorBuilder {
                         batch_txs.insert(tx.hash(), VecDeque::from([result]));
                     }
                 }
+                ScenarioItem::Bundle(_, bundle, results) => {
+                    for (tx, result) in bundle.txs().iter().zip(results) {
+                        let result = BatchTransactionExecutionResult {
+                            tx_result: result.tx_result.clone(),
+                            compression_result: Ok(()),
+                            call_traces: result.call_traces.clone(),
+                        };
+                        batch_txs.entry(tx.hash()).or_default().push_back(result);
+                    }
+                }
                 ScenarioItem::Rollback(_, tx) => {
                     tx_rollback_set.insert(tx.hash());
                 }
+                ScenarioItem::RollbackBundle(_, bundle)
+                | ScenarioItem::RejectBundle(_, bundle, _) => {
+                    tx_rollback_set.extend(bundle.txs().iter().map(|tx| tx.hash()));
+                }
                 ScenarioItem::RollbackBlock(_, number, _) => {
                     block_rollback_set.insert(*number);
                 }
@@ -568,6 +647,22 @@ impl BatchExecutor<OwnedStorage> for TestBatchExecutor {
         Ok(result)
     }
 
+    async fn execute_bundle(
+        &mut self,
+        txs: Vec<Transaction>,
+    ) -> anyhow::Result<Vec<BatchTransactionExecutionResult>> {
+        let mut results = Vec::with_capacity(txs.len());
+        for tx in txs {
+            let result = self.execute_tx(tx).await?;
+            let is_failed = result.tx_result.result.is_failed();
+            results.push(result);
+            if is_failed {
+                break;
+            }
+        }
+        Ok(results)
+    }
+
     async fn rollback_last_tx(&mut self) -> anyhow::Result<()> {
         // This is an additional safety check: IO would check that every rollback is included in the
         // test scenario, but here we want to additionally check that each such request goes to the
@@ -872,6 +967,61 @@ impl StateKeeperIO for TestIO {
         Ok(Some(tx))
     }
 
+    async fn next_bundle(
+        &mut self,
+        _l2_block_timestamp: u64,
+    ) -> anyhow::Result<Option<L2TxBundle>> {
+        if self.skipping_txs {
+            return Ok(None);
+        }
+        // Unlike `wait_for_next_tx()`, bundles are requested on each iteration, so we only pop the next action
+        // if it is a bundle.
+        let is_bundle_next = matches!(
+            self.actions.lock().unwrap().front(),
+            Some(ScenarioItem::Bundle(..))
+        );
+        if !is_bundle_next {
+            return Ok(None);
+        }
+        let ScenarioItem::Bundle(_, bundle, _) = self.pop_next_item("next_bundle") else {
+            unreachable!();
+        };
+        Ok(Some(bundle))
+    }
+
+    async fn rollback_bundle(&mut self, bundle: L2TxBundle) -> anyhow::Result<()> {
+        let action = self.pop_next_item("rollback_bundle");
+        let ScenarioItem::RollbackBundle(_, expected_bundle) = action else {
+            panic!("Unexpected action: {:?}", action);
+        };
+        assert_eq!(
+            bundle.hash(),
+            expected_bundle.hash(),
+            "Incorrect bundle has been rolled back"
+        );
+        self.skipping_txs = false;
+        Ok(())
+    }
+
+    async fn reject_bundle(
+        &mut self,
+        bundle: &L2TxBundle,
+        reason: UnexecutableReason,
+    ) -> anyhow::Result<()> {
+        let action = self.pop_next_item("reject_bundle");
+        let ScenarioItem::RejectBundle(_, expected_bundle, expected_err) = action else {
+            panic!("Unexpected action: {:?}", action);
+        };
+        assert_eq!(
+            bundle.hash(),
+            expected_bundle.hash(),
+            "Incorrect bundle has been rejected"
+        );
+        assert_eq!(reason, expected_err);
+        self.skipping_txs = false;
+        Ok(())
+    }
+
     async fn rollback(&mut self, tx: Transaction) -> anyhow::Result<()> {
         let action = self.pop_next_item("rollback");
         let ScenarioItem::Rollback(_, expected_tx) = action else {
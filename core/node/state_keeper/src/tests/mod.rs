@@ -3,15 +3,17 @@ use std::{
         atomic::{AtomicBool, AtomicU64, Ordering},
         Arc,
     },
-    time::{Instant, SystemTime, UNIX_EPOCH},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use tokio::sync::watch;
-use zksync_config::configs::chain::SealCriteriaConfig;
+use zksync_config::configs::chain::{
+    CircuitProvingTimeOverrides, ProvingCostConfig, SealCriteriaConfig,
+};
 use zksync_multivm::{
     interface::{
-        Halt, SystemEnv, TxExecutionMode, VmExecutionLogs, VmExecutionResultAndLogs,
-        VmExecutionStatistics,
+        BatchTransactionExecutionResult, CircuitStatistic, Halt, SystemEnv, TxExecutionMode,
+        VmExecutionLogs, VmExecutionResultAndLogs, VmExecutionStatistics,
     },
     vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
 };
@@ -33,7 +35,7 @@ use crate::{
     testonly::{
         successful_exec,
         test_batch_executor::{
-            random_tx, random_upgrade_tx, rejected_exec, MockReadStorageFactory,
+            random_bundle, random_tx, random_upgrade_tx, rejected_exec, MockReadStorageFactory,
             TestBatchExecutorBuilder, TestIO, TestScenario, FEE_ACCOUNT,
         },
         BASE_SYSTEM_CONTRACTS,
@@ -165,6 +167,57 @@ async fn sealed_by_number_of_txs() {
         .await;
 }
 
+fn exec_with_main_vm_circuits(main_vm: f32) -> BatchTransactionExecutionResult {
+    let mut result = successful_exec();
+    result.tx_result.statistics.circuit_statistic = CircuitStatistic {
+        main_vm,
+        ..CircuitStatistic::default()
+    };
+    result
+}
+
+#[tokio::test]
+async fn sealed_by_proving_cost() {
+    let config = SealCriteriaConfig {
+        proving_cost: Some(ProvingCostConfig {
+            circuit_proving_time: Duration::from_secs(1),
+            circuit_proving_time_overrides: CircuitProvingTimeOverrides::empty(),
+            prover_cost_per_hour: 3_600.0,
+            max_proving_time_per_batch: Some(Duration::from_secs(1_000)),
+            max_cost_per_batch: None,
+        }),
+        ..SealCriteriaConfig::for_tests()
+    };
+    let sealer = SequencerSealer::new(config);
+
+    let second_tx = random_tx(2);
+    TestScenario::new()
+        .seal_l2_block_when(|updates| {
+            updates.last_pending_l2_block().executed_transactions.len() == 1
+        })
+        .next_tx("First tx", random_tx(1), exec_with_main_vm_circuits(600.0))
+        .l2_block_sealed("L2 block with 1st tx")
+        .next_tx(
+            "Second tx exceeding proving time budget",
+            second_tx.clone(),
+            exec_with_main_vm_circuits(600.0),
+        )
+        .tx_rollback("Second tx rolled back to seal the batch", second_tx.clone())
+        .batch_sealed_with("Batch sealed with 1 tx", |updates| {
+            let estimate = updates.proving_cost_estimate().unwrap();
+            assert_eq!(estimate.proving_time, Duration::from_secs(600));
+            assert_eq!(estimate.cost, 600.0);
+        })
+        .next_tx(
+            "Second tx in new batch",
+            second_tx,
+            exec_with_main_vm_circuits(600.0),
+        )
+        .l2_block_sealed("L2 block with 2nd tx")
+        .run_success(Arc::new(sealer))
+        .await;
+}
+
 /// Test slots criterion that seals if we have >= 2 transactions
 #[derive(Debug)]
 struct TestSlotsCriterion;
@@ -304,6 +357,101 @@ async fn rejected_tx() {
         .await;
 }
 
+#[tokio::test]
+async fn transaction_bundle_is_included_atomically() {
+    let config = SealCriteriaConfig {
+        transaction_slots: 3,
+        ..SealCriteriaConfig::for_tests()
+    };
+    let sealer = SequencerSealer::with_sealers(config, vec![Box::new(SlotsCriterion)]);
+
+    TestScenario::new()
+        .seal_l2_block_when(|updates| {
+            updates.last_pending_l2_block().executed_transactions.len() == 1
+        })
+        .next_tx("First tx", random_tx(1), successful_exec())
+        .l2_block_sealed("L2 block 1")
+        .next_bundle(
+            "Bundle with 2 txs",
+            random_bundle(&[2, 3]),
+            vec![successful_exec(), successful_exec()],
+        )
+        .l2_block_sealed_with("L2 block with the entire bundle", |updates| {
+            assert_eq!(
+                updates.last_pending_l2_block().executed_transactions.len(),
+                2,
+                "The L2 block should contain all bundle txs"
+            );
+        })
+        .batch_sealed("Batch 1")
+        .run_success(Arc::new(sealer))
+        .await;
+}
+
+#[tokio::test]
+async fn transaction_bundle_is_not_split_between_batches() {
+    let sealer = SequencerSealer::with_sealers(
+        SealCriteriaConfig::for_tests(),
+        vec![Box::new(TestSlotsCriterion)],
+    );
+
+    let bundle = random_bundle(&[2, 3]);
+    TestScenario::new()
+        .seal_l2_block_when(|updates| {
+            updates.last_pending_l2_block().executed_transactions.len() == 1
+        })
+        .next_tx("First tx", random_tx(1), successful_exec())
+        .l2_block_sealed("L2 block 1")
+        .next_bundle(
+            "Bundle not fitting into the batch",
+            bundle.clone(),
+            vec![successful_exec(), successful_exec()],
+        )
+        .bundle_rollback("Bundle rolled back to seal the batch", bundle.clone())
+        .batch_sealed("Batch sealed with 1 tx")
+        .next_bundle(
+            "Same bundle in the next batch",
+            bundle,
+            vec![successful_exec(), successful_exec()],
+        )
+        .l2_block_sealed("L2 block with the bundle")
+        .batch_sealed("Batch sealed with the bundle")
+        .run_success(Arc::new(sealer))
+        .await;
+}
+
+#[tokio::test]
+async fn transaction_bundle_with_failed_tx_is_rejected() {
+    let config = SealCriteriaConfig {
+        transaction_slots: 2,
+        ..SealCriteriaConfig::for_tests()
+    };
+    let sealer = SequencerSealer::with_sealers(config, vec![Box::new(SlotsCriterion)]);
+
+    let bundle = random_bundle(&[1, 2, 3]);
+    TestScenario::new()
+        .seal_l2_block_when(|updates| {
+            updates.last_pending_l2_block().executed_transactions.len() == 1
+        })
+        .next_bundle(
+            "Bundle with a failing tx",
+            bundle.clone(),
+            vec![successful_exec(), rejected_exec(Halt::InnerTxError)],
+        )
+        .bundle_rejected(
+            "Bundle got rejected",
+            bundle,
+            UnexecutableReason::Halt(Halt::InnerTxError),
+        )
+        .next_tx("Successful tx", random_tx(4), successful_exec())
+        .l2_block_sealed("L2 block with successful tx")
+        .next_tx("Second successful tx", random_tx(5), successful_exec())
+        .l2_block_sealed("Second L2 block")
+        .batch_sealed("Batch with 2 successful txs")
+        .run_success(Arc::new(sealer))
+        .await;
+}
+
 #[tokio::test]
 async fn bootloader_tip_out_of_gas_flow() {
     let config = SealCriteriaConfig {
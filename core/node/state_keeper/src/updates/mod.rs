@@ -22,6 +22,7 @@ use super::{
 };
 use crate::{
     metrics::{L2BlockSealStage, L2_BLOCK_METRICS},
+    seal_criteria::ProvingCostEstimate,
     updates::l2_block_updates::RollingTxHashUpdates,
 };
 
@@ -62,6 +63,7 @@ pub struct UpdatesManager {
     pending_l2_blocks: VecDeque<L2BlockUpdates>,
     storage_writes_deduplicator: StorageWritesDeduplicator,
     next_l2_block_params: Option<L2BlockParams>,
+    proving_cost_estimate: Option<ProvingCostEstimate>,
 }
 
 impl UpdatesManager {
@@ -126,6 +128,7 @@ impl UpdatesManager {
             },
             storage_writes_deduplicator,
             next_l2_block_params: None,
+            proving_cost_estimate: None,
         }
     }
 
@@ -481,6 +484,15 @@ impl UpdatesManager {
     pub fn pubdata_limit(&self) -> Option<u64> {
         self.pubdata_limit
     }
+
+    /// Returns the proving cost of the finished batch estimated by the conditional sealer, if any.
+    pub fn proving_cost_estimate(&self) -> Option<ProvingCostEstimate> {
+        self.proving_cost_estimate
+    }
+
+    pub(crate) fn set_proving_cost_estimate(&mut self, estimate: ProvingCostEstimate) {
+        self.proving_cost_estimate = Some(estimate);
+    }
 }
 
 /// Command to seal an L2 block containing all necessary data for it.
@@ -0,0 +1,386 @@
+//! Local DCAP collateral: the Intel SGX root CA, TCB info and QE identity documents, and CRLs as returned
+//! by the Intel PCS / PCCS APIs.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context as _;
+use chrono::{DateTime, Utc};
+use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::value::RawValue;
+use x509_cert::{
+    crl::CertificateList,
+    der::{Decode, DecodePem, Encode},
+    time::Time,
+    Certificate,
+};
+use zksync_types::tee_types::TeeType;
+
+use super::{
+    pck::{self, PckTcb},
+    quote::SgxReportBody,
+    AttestationError, TcbStatus,
+};
+
+/// TCB info document; the signature covers the exact bytes of the `tcbInfo` field.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TcbInfoDocument {
+    tcb_info: Box<RawValue>,
+    #[serde(with = "hex")]
+    signature: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct TcbInfo {
+    /// `SGX` or `TDX`.
+    pub id: String,
+    pub next_update: DateTime<Utc>,
+    pub fmspc: String,
+    pub tcb_levels: Vec<TcbLevel>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct TcbLevel {
+    pub tcb: Tcb,
+    pub tcb_status: TcbStatus,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(super) struct Tcb {
+    pub sgxtcbcomponents: Vec<TcbComponent>,
+    pub pcesvn: u16,
+    #[serde(default)]
+    pub tdxtcbcomponents: Vec<TcbComponent>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(super) struct TcbComponent {
+    pub svn: u8,
+}
+
+/// QE identity document; the signature covers the exact bytes of the `enclaveIdentity` field.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EnclaveIdentityDocument {
+    enclave_identity: Box<RawValue>,
+    #[serde(with = "hex")]
+    signature: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct EnclaveIdentity {
+    /// `QE` for SGX or `TD_QE` for TDX.
+    pub id: String,
+    pub next_update: DateTime<Utc>,
+    #[serde(with = "hex")]
+    pub mrsigner: Vec<u8>,
+    pub isvprodid: u16,
+    pub tcb_levels: Vec<EnclaveTcbLevel>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct EnclaveTcbLevel {
+    pub tcb: EnclaveTcb,
+    pub tcb_status: TcbStatus,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(super) struct EnclaveTcb {
+    pub isvsvn: u16,
+}
+
+/// Certificate revocation list issued by an Intel CA.
+#[derive(Debug, Clone)]
+pub(super) struct Crl(CertificateList);
+
+impl Crl {
+    pub fn from_pem(pem: &[u8]) -> anyhow::Result<Self> {
+        let (label, der) = x509_cert::der::pem::decode_vec(pem)
+            .map_err(|err| anyhow::anyhow!("invalid PEM: {err}"))?;
+        anyhow::ensure!(label == "X509 CRL", "unexpected PEM label `{label}`");
+        let list = CertificateList::from_der(&der).context("failed parsing CRL")?;
+        anyhow::ensure!(
+            list.tbs_cert_list.next_update.is_some(),
+            "CRL doesn't specify next update"
+        );
+        Ok(Self(list))
+    }
+
+    pub fn issuer(&self) -> String {
+        self.0.tbs_cert_list.issuer.to_string()
+    }
+
+    fn next_update(&self) -> DateTime<Utc> {
+        self.0
+            .tbs_cert_list
+            .next_update
+            .map_or(DateTime::<Utc>::MIN_UTC, to_date_time)
+    }
+
+    fn verify_signed_by(&self, issuer: &Certificate) -> Result<(), AttestationError> {
+        let tbs = self
+            .0
+            .tbs_cert_list
+            .to_der()
+            .map_err(|err| AttestationError::InvalidCertificate(err.to_string()))?;
+        pck::verify_signed_data(&tbs, &self.0.signature_algorithm, &self.0.signature, issuer)
+            .map_err(|err| {
+                AttestationError::InvalidCertificate(format!(
+                    "CRL is not signed by `{}`: {err}",
+                    issuer.tbs_certificate.subject
+                ))
+            })
+    }
+
+    fn is_revoked(&self, cert: &Certificate) -> bool {
+        self.0
+            .tbs_cert_list
+            .revoked_certificates
+            .iter()
+            .flatten()
+            .any(|revoked| revoked.serial_number == cert.tbs_certificate.serial_number)
+    }
+}
+
+/// Collateral loaded from a local directory with the following layout:
+///
+/// - `root_ca.pem`: Intel SGX root CA certificate;
+/// - `tcb_signing_chain.pem`: TCB signing certificate chain (returned by PCS in the `TCB-Info-Issuer-Chain` header);
+/// - `tcb_info/*.json`: TCB info documents for all supported platforms (FMSPCs), both SGX and TDX;
+/// - `qe_identity/*.json`: QE identity documents (`QE` for SGX and `TD_QE` for TDX);
+/// - `crl/*.pem`: CRLs issued by the root CA and by the PCK platform / processor CAs.
+///
+/// TCB info and QE identity signatures are verified on load against the TCB signing chain rooted in the root CA.
+/// Expiration of documents and revocation of certificates depend on the current time, so they are checked
+/// during attestation verification.
+#[derive(Debug)]
+pub(crate) struct Collateral {
+    pub(super) root_ca: Certificate,
+    tcb_signing_chain: Vec<Certificate>,
+    tcb_infos: HashMap<(String, String), TcbInfo>,
+    qe_identities: HashMap<String, EnclaveIdentity>,
+    /// CRLs keyed by the issuer name.
+    pub(super) crls: HashMap<String, Crl>,
+}
+
+impl Collateral {
+    pub fn load(dir: &Path) -> anyhow::Result<Self> {
+        let root_ca_path = dir.join("root_ca.pem");
+        let root_ca = fs::read(&root_ca_path)
+            .with_context(|| format!("failed reading `{}`", root_ca_path.display()))?;
+        let root_ca = Certificate::from_pem(&root_ca).context("failed parsing root CA")?;
+
+        let signing_chain_path = dir.join("tcb_signing_chain.pem");
+        let signing_chain = fs::read(&signing_chain_path)
+            .with_context(|| format!("failed reading `{}`", signing_chain_path.display()))?;
+        let tcb_signing_chain =
+            pck::verify_chain(&signing_chain, &root_ca).context("invalid TCB signing chain")?;
+        let signing_key = pck::verifying_key(&tcb_signing_chain[0])?;
+
+        let mut tcb_infos = HashMap::new();
+        for (path, contents) in read_files(&dir.join("tcb_info"), "json")? {
+            let info = parse_tcb_info(&contents, &signing_key)
+                .with_context(|| format!("invalid TCB info `{}`", path.display()))?;
+            tcb_infos.insert((info.id.clone(), info.fmspc.to_lowercase()), info);
+        }
+        let mut qe_identities = HashMap::new();
+        for (path, contents) in read_files(&dir.join("qe_identity"), "json")? {
+            let identity = parse_qe_identity(&contents, &signing_key)
+                .with_context(|| format!("invalid QE identity `{}`", path.display()))?;
+            qe_identities.insert(identity.id.clone(), identity);
+        }
+        let mut crls = HashMap::new();
+        for (path, contents) in read_files(&dir.join("crl"), "pem")? {
+            let crl = Crl::from_pem(&contents)
+                .with_context(|| format!("invalid CRL `{}`", path.display()))?;
+            crls.insert(crl.issuer(), crl);
+        }
+
+        Ok(Self {
+            root_ca,
+            tcb_signing_chain,
+            tcb_infos,
+            qe_identities,
+            crls,
+        })
+    }
+
+    /// Checks that none of the `chain` certificates (leaf first, excluding the root CA) is revoked.
+    /// Returns the time at which the used CRLs go stale.
+    pub(super) fn check_revocation(
+        &self,
+        chain: &[Certificate],
+        now: DateTime<Utc>,
+    ) -> Result<DateTime<Utc>, AttestationError> {
+        let mut expires_at = DateTime::<Utc>::MAX_UTC;
+        for (i, cert) in chain.iter().enumerate() {
+            let issuer_name = cert.tbs_certificate.issuer.to_string();
+            let crl = self.crls.get(&issuer_name).ok_or_else(|| {
+                AttestationError::MissingCollateral(format!("CRL issued by `{issuer_name}`"))
+            })?;
+            crl.verify_signed_by(chain.get(i + 1).unwrap_or(&self.root_ca))?;
+            let next_update = crl.next_update();
+            if next_update <= now {
+                return Err(AttestationError::StaleCollateral(next_update));
+            }
+            if crl.is_revoked(cert) {
+                return Err(AttestationError::RevokedCertificate(
+                    cert.tbs_certificate.subject.to_string(),
+                ));
+            }
+            expires_at = expires_at.min(next_update);
+        }
+        Ok(expires_at)
+    }
+
+    fn tcb_info(&self, tee_type: TeeType, fmspc: &[u8; 6]) -> Result<&TcbInfo, AttestationError> {
+        let id = match tee_type {
+            TeeType::Tdx => "TDX",
+            _ => "SGX",
+        };
+        let fmspc = hex::encode(fmspc);
+        self.tcb_infos
+            .get(&(id.to_owned(), fmspc.clone()))
+            .ok_or_else(|| {
+                AttestationError::MissingCollateral(format!("{id} TCB info for FMSPC {fmspc}"))
+            })
+    }
+
+    fn qe_identity(&self, tee_type: TeeType) -> Result<&EnclaveIdentity, AttestationError> {
+        let id = match tee_type {
+            TeeType::Tdx => "TD_QE",
+            _ => "QE",
+        };
+        self.qe_identities
+            .get(id)
+            .ok_or_else(|| AttestationError::MissingCollateral(format!("{id} identity")))
+    }
+
+    /// Evaluates the TCB status of the platform and the quoting enclave, returning the worst of the two
+    /// together with the time at which the used collateral becomes stale.
+    pub(super) fn evaluate_tcb(
+        &self,
+        tee_type: TeeType,
+        pck_tcb: &PckTcb,
+        tee_tcb_svn: Option<&[u8; 16]>,
+        qe_report: &SgxReportBody,
+        now: DateTime<Utc>,
+    ) -> Result<(TcbStatus, DateTime<Utc>), AttestationError> {
+        for cert in self.tcb_signing_chain.iter().chain([&self.root_ca]) {
+            pck::check_validity(cert, now)?;
+        }
+        let signing_chain_expires_at = self.check_revocation(&self.tcb_signing_chain, now)?;
+
+        let tcb_info = self.tcb_info(tee_type, &pck_tcb.fmspc)?;
+        let qe_identity = self.qe_identity(tee_type)?;
+        let expires_at = tcb_info.next_update.min(qe_identity.next_update);
+        if expires_at <= now {
+            return Err(AttestationError::StaleCollateral(expires_at));
+        }
+
+        let platform_status = tcb_info
+            .tcb_levels
+            .iter()
+            .find(|level| level.tcb.matches(pck_tcb, tee_tcb_svn))
+            .map_or(TcbStatus::Revoked, |level| level.tcb_status);
+
+        if qe_report.mr_signer[..] != qe_identity.mrsigner[..]
+            || qe_report.isv_prod_id != qe_identity.isvprodid
+        {
+            return Err(AttestationError::InvalidSignature(
+                "QE report does not match QE identity",
+            ));
+        }
+        let qe_status = qe_identity
+            .tcb_levels
+            .iter()
+            .find(|level| qe_report.isv_svn >= level.tcb.isvsvn)
+            .map_or(TcbStatus::Revoked, |level| level.tcb_status);
+
+        let expires_at = expires_at.min(signing_chain_expires_at);
+        Ok((platform_status.max(qe_status), expires_at))
+    }
+}
+
+impl Tcb {
+    fn matches(&self, pck_tcb: &PckTcb, tee_tcb_svn: Option<&[u8; 16]>) -> bool {
+        let sgx_matches = self.sgxtcbcomponents.len() == 16
+            && pck_tcb
+                .sgx_components
+                .iter()
+                .zip(&self.sgxtcbcomponents)
+                .all(|(&svn, component)| svn >= component.svn);
+        if !sgx_matches || pck_tcb.pce_svn < self.pcesvn {
+            return false;
+        }
+        match tee_tcb_svn {
+            None => true,
+            Some(tee_tcb_svn) => {
+                self.tdxtcbcomponents.len() == 16
+                    && tee_tcb_svn
+                        .iter()
+                        .zip(&self.tdxtcbcomponents)
+                        .all(|(&svn, component)| svn >= component.svn)
+            }
+        }
+    }
+}
+
+pub(super) fn parse_tcb_info(
+    contents: &[u8],
+    signing_key: &VerifyingKey,
+) -> anyhow::Result<TcbInfo> {
+    let document: TcbInfoDocument = serde_json::from_slice(contents)?;
+    parse_signed(&document.tcb_info, &document.signature, signing_key)
+}
+
+fn parse_qe_identity(
+    contents: &[u8],
+    signing_key: &VerifyingKey,
+) -> anyhow::Result<EnclaveIdentity> {
+    let document: EnclaveIdentityDocument = serde_json::from_slice(contents)?;
+    parse_signed(&document.enclave_identity, &document.signature, signing_key)
+}
+
+/// Verifies the Intel signature (raw `r || s` ECDSA P-256 signature) over the document body and parses the body.
+fn parse_signed<T: DeserializeOwned>(
+    body: &RawValue,
+    signature: &[u8],
+    signing_key: &VerifyingKey,
+) -> anyhow::Result<T> {
+    let signature = Signature::from_slice(signature).context("malformed signature")?;
+    signing_key
+        .verify(body.get().as_bytes(), &signature)
+        .context("signature is not produced by the TCB signing key")?;
+    serde_json::from_str(body.get()).context("failed parsing document")
+}
+
+fn to_date_time(time: Time) -> DateTime<Utc> {
+    let timestamp = time.to_unix_duration().as_secs();
+    DateTime::from_timestamp(timestamp.try_into().unwrap_or(i64::MAX), 0)
+        .unwrap_or(DateTime::<Utc>::MAX_UTC)
+}
+
+fn read_files(dir: &Path, extension: &str) -> anyhow::Result<Vec<(PathBuf, Vec<u8>)>> {
+    let mut files = vec![];
+    let entries =
+        fs::read_dir(dir).with_context(|| format!("failed reading `{}`", dir.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == extension) {
+            let contents =
+                fs::read(&path).with_context(|| format!("failed reading `{}`", path.display()))?;
+            files.push((path, contents));
+        }
+    }
+    Ok(files)
+}
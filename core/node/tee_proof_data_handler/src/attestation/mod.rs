@@ -0,0 +1,184 @@
+//! Verification of Intel DCAP attestation quotes submitted by TEE provers on registration.
+
+use std::{collections::HashSet, path::PathBuf};
+
+use anyhow::Context as _;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use zksync_config::configs::tee_proof_data_handler::TeeAttestationPolicyConfig;
+use zksync_types::tee_types::TeeType;
+
+pub(crate) use self::collateral::Collateral;
+use self::{
+    pck::PckChain,
+    quote::{Quote, ReportBody},
+};
+
+mod collateral;
+mod pck;
+mod quote;
+#[cfg(test)]
+mod tests;
+
+/// TCB status as reported by Intel collateral. Variants are ordered from the most to the least secure.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Deserialize,
+    strum::EnumString,
+    strum::Display,
+)]
+pub(crate) enum TcbStatus {
+    UpToDate,
+    SWHardeningNeeded,
+    ConfigurationNeeded,
+    ConfigurationAndSWHardeningNeeded,
+    OutOfDate,
+    OutOfDateConfigurationNeeded,
+    Revoked,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum AttestationError {
+    #[error("malformed attestation quote: {0}")]
+    MalformedQuote(&'static str),
+    #[error("unsupported attestation quote: {0}")]
+    UnsupportedQuote(String),
+    #[error("invalid {0} signature")]
+    InvalidSignature(&'static str),
+    #[error("invalid certificate: {0}")]
+    InvalidCertificate(String),
+    #[error("certificate `{0}` is revoked")]
+    RevokedCertificate(String),
+    #[error("missing collateral: {0}")]
+    MissingCollateral(String),
+    #[error("collateral has gone stale at {0}")]
+    StaleCollateral(DateTime<Utc>),
+    #[error("report data is not bound to the registered public key")]
+    ReportDataMismatch,
+    #[error("{tee_type} measurement {measurement} is not allowed")]
+    MeasurementNotAllowed {
+        tee_type: TeeType,
+        measurement: String,
+    },
+    #[error("TCB status `{0}` is not allowed")]
+    TcbStatusNotAllowed(TcbStatus),
+}
+
+/// Outcome of a successful attestation verification.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct VerifiedAttestation {
+    pub tee_type: TeeType,
+    pub tcb_status: TcbStatus,
+    /// Time at which the collateral used for verification goes stale. The attested key must not be
+    /// trusted after this moment until it is re-registered with fresh collateral.
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Policy applied to attestation quotes.
+#[derive(Debug, Clone)]
+pub(crate) struct AttestationPolicy {
+    pub collateral_path: PathBuf,
+    allowed_sgx_mrenclaves: HashSet<Vec<u8>>,
+    allowed_tdx_mrtds: HashSet<Vec<u8>>,
+    allowed_tcb_statuses: HashSet<TcbStatus>,
+}
+
+impl AttestationPolicy {
+    pub fn new(config: &TeeAttestationPolicyConfig) -> anyhow::Result<Self> {
+        let decode_all = |values: &[String]| {
+            values
+                .iter()
+                .map(|value| {
+                    hex::decode(value.strip_prefix("0x").unwrap_or(value))
+                        .with_context(|| format!("invalid measurement `{value}`"))
+                })
+                .collect::<anyhow::Result<HashSet<_>>>()
+        };
+        let allowed_tcb_statuses = config
+            .allowed_tcb_statuses
+            .iter()
+            .map(|status| {
+                status
+                    .parse()
+                    .with_context(|| format!("invalid TCB status `{status}`"))
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(Self {
+            collateral_path: config.collateral_path.clone(),
+            allowed_sgx_mrenclaves: decode_all(&config.allowed_sgx_mrenclaves)?,
+            allowed_tdx_mrtds: decode_all(&config.allowed_tdx_mrtds)?,
+            allowed_tcb_statuses,
+        })
+    }
+
+    /// Verifies `quote_bytes` against `collateral` and checks that the quote binds `pubkey`.
+    pub fn verify(
+        &self,
+        collateral: &Collateral,
+        pubkey: &[u8],
+        quote_bytes: &[u8],
+        now: DateTime<Utc>,
+    ) -> Result<VerifiedAttestation, AttestationError> {
+        let quote = Quote::parse(quote_bytes)?;
+        let tee_type = quote.tee_type();
+
+        if !is_report_data_bound(quote.report_data(), pubkey) {
+            return Err(AttestationError::ReportDataMismatch);
+        }
+        let allowed_measurements = match tee_type {
+            TeeType::Tdx => &self.allowed_tdx_mrtds,
+            _ => &self.allowed_sgx_mrenclaves,
+        };
+        if !allowed_measurements.contains(quote.measurement()) {
+            return Err(AttestationError::MeasurementNotAllowed {
+                tee_type,
+                measurement: hex::encode(quote.measurement()),
+            });
+        }
+
+        quote.verify_attestation_key()?;
+        let pck_chain = PckChain::verify(&quote.pck_chain_pem, &collateral.root_ca, now)?;
+        let crls_expire_at = collateral.check_revocation(&pck_chain.certificates, now)?;
+        pck_chain.verify_qe_report(&quote.qe_report_bytes, &quote.qe_report_signature)?;
+
+        let tee_tcb_svn = match &quote.body {
+            ReportBody::Sgx(_) => None,
+            ReportBody::Tdx(body) => Some(&body.tee_tcb_svn),
+        };
+        let (tcb_status, expires_at) = collateral.evaluate_tcb(
+            tee_type,
+            &pck_chain.tcb,
+            tee_tcb_svn,
+            &quote.qe_report,
+            now,
+        )?;
+        if !self.allowed_tcb_statuses.contains(&tcb_status) {
+            return Err(AttestationError::TcbStatusNotAllowed(tcb_status));
+        }
+
+        Ok(VerifiedAttestation {
+            tee_type,
+            tcb_status,
+            expires_at: expires_at.min(crls_expire_at),
+        })
+    }
+}
+
+/// Checks that the report data commits to the public key. Two encodings are accepted: the SHA-256 digest
+/// of the key in the first 32 bytes, or the key itself; in both cases, the remaining bytes must be zero.
+fn is_report_data_bound(report_data: &[u8; 64], pubkey: &[u8]) -> bool {
+    let digest = Sha256::digest(pubkey);
+    let matches_prefix = |prefix: &[u8]| {
+        report_data.starts_with(prefix) && report_data[prefix.len()..].iter().all(|&b| b == 0)
+    };
+    !pubkey.is_empty() && (matches_prefix(&digest) || matches_prefix(pubkey))
+}
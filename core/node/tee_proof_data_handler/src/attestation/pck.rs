@@ -0,0 +1,275 @@
+//! Verification of the PCK certificate chain embedded into DCAP quotes.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use der::{
+    asn1::{BitString, ObjectIdentifier},
+    Encode,
+};
+use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+use x509_cert::{spki::AlgorithmIdentifierOwned, Certificate};
+
+use super::AttestationError;
+
+const ECDSA_WITH_SHA256: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2");
+/// Intel SGX extensions of PCK certificates.
+const SGX_EXTENSIONS: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113741.1.13.1");
+const SGX_EXT_TCB: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113741.1.13.1.2");
+const SGX_EXT_PCESVN: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113741.1.13.1.2.17");
+const SGX_EXT_FMSPC: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113741.1.13.1.4");
+
+/// DER tags used in the SGX extension.
+const TAG_INTEGER: u8 = 0x02;
+const TAG_OCTET_STRING: u8 = 0x04;
+const TAG_OID: u8 = 0x06;
+const TAG_SEQUENCE: u8 = 0x30;
+
+/// Platform TCB information extracted from the leaf PCK certificate.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct PckTcb {
+    pub fmspc: [u8; 6],
+    pub sgx_components: [u8; 16],
+    pub pce_svn: u16,
+}
+
+/// Verified PCK certificate chain.
+#[derive(Debug)]
+pub(super) struct PckChain {
+    /// Chain certificates (leaf first) excluding the trusted root.
+    pub certificates: Vec<Certificate>,
+    pub leaf_key: VerifyingKey,
+    pub tcb: PckTcb,
+}
+
+impl PckChain {
+    /// Parses the PEM chain (leaf first) and verifies that it is rooted in `trusted_root`
+    /// and that all certificates are valid at `now`.
+    pub fn verify(
+        chain_pem: &[u8],
+        trusted_root: &Certificate,
+        now: DateTime<Utc>,
+    ) -> Result<Self, AttestationError> {
+        let certificates = verify_chain(chain_pem, trusted_root)?;
+        for cert in certificates.iter().chain([trusted_root]) {
+            check_validity(cert, now)?;
+        }
+
+        let leaf = &certificates[0];
+        Ok(Self {
+            leaf_key: verifying_key(leaf)?,
+            tcb: parse_sgx_extensions(leaf)?,
+            certificates,
+        })
+    }
+
+    pub fn verify_qe_report(
+        &self,
+        qe_report: &[u8],
+        signature: &[u8],
+    ) -> Result<(), AttestationError> {
+        let signature = Signature::from_slice(signature)
+            .map_err(|_| AttestationError::MalformedQuote("QE report signature"))?;
+        self.leaf_key
+            .verify(qe_report, &signature)
+            .map_err(|_| AttestationError::InvalidSignature("QE report"))
+    }
+}
+
+/// Parses a PEM certificate chain (leaf first) and verifies its signatures up to `trusted_root`. Returns the chain
+/// excluding the trusted root. Certificate validity periods are not checked.
+pub(super) fn verify_chain(
+    chain_pem: &[u8],
+    trusted_root: &Certificate,
+) -> Result<Vec<Certificate>, AttestationError> {
+    // Cert data is sometimes NUL-terminated.
+    let chain_pem = chain_pem.strip_suffix(&[0]).unwrap_or(chain_pem);
+    let mut chain = Certificate::load_pem_chain(chain_pem).map_err(|err| {
+        AttestationError::InvalidCertificate(format!("cannot parse certificate chain: {err}"))
+    })?;
+    if chain.last() == Some(trusted_root) {
+        chain.pop();
+    }
+    if chain.is_empty() {
+        return Err(AttestationError::InvalidCertificate(
+            "empty certificate chain".into(),
+        ));
+    }
+
+    // The last certificate in the chain must be issued by the trusted root.
+    for (i, cert) in chain.iter().enumerate() {
+        let issuer = chain.get(i + 1).unwrap_or(trusted_root);
+        let tbs = cert
+            .tbs_certificate
+            .to_der()
+            .map_err(|err| AttestationError::InvalidCertificate(err.to_string()))?;
+        verify_signed_data(&tbs, &cert.signature_algorithm, &cert.signature, issuer).map_err(
+            |err| {
+                AttestationError::InvalidCertificate(format!(
+                    "certificate `{}` is not signed by `{}`: {err}",
+                    cert.tbs_certificate.subject, issuer.tbs_certificate.subject
+                ))
+            },
+        )?;
+    }
+    Ok(chain)
+}
+
+pub(super) fn check_validity(
+    cert: &Certificate,
+    now: DateTime<Utc>,
+) -> Result<(), AttestationError> {
+    let validity = &cert.tbs_certificate.validity;
+    let not_before = validity.not_before.to_unix_duration();
+    let not_after = validity.not_after.to_unix_duration();
+    let now = Duration::from_secs(now.timestamp().try_into().unwrap_or(0));
+    if now < not_before || now > not_after {
+        return Err(AttestationError::InvalidCertificate(format!(
+            "certificate `{}` is not valid at the current time",
+            cert.tbs_certificate.subject
+        )));
+    }
+    Ok(())
+}
+
+pub(super) fn verifying_key(cert: &Certificate) -> Result<VerifyingKey, AttestationError> {
+    let spki = &cert.tbs_certificate.subject_public_key_info;
+    VerifyingKey::from_sec1_bytes(spki.subject_public_key.raw_bytes()).map_err(|_| {
+        AttestationError::InvalidCertificate(format!(
+            "certificate `{}` has unsupported public key",
+            cert.tbs_certificate.subject
+        ))
+    })
+}
+
+/// Verifies an X.509 signature (i.e., one for a certificate or a CRL) over DER-encoded `tbs` data.
+pub(super) fn verify_signed_data(
+    tbs: &[u8],
+    algorithm: &AlgorithmIdentifierOwned,
+    signature: &BitString,
+    issuer: &Certificate,
+) -> Result<(), String> {
+    if algorithm.oid != ECDSA_WITH_SHA256 {
+        return Err(format!("unsupported signature algorithm {}", algorithm.oid));
+    }
+    let signature =
+        Signature::from_der(signature.raw_bytes()).map_err(|_| "malformed signature".to_owned())?;
+    verifying_key(issuer)
+        .map_err(|err| err.to_string())?
+        .verify(tbs, &signature)
+        .map_err(|_| "signature mismatch".to_owned())
+}
+
+fn parse_sgx_extensions(cert: &Certificate) -> Result<PckTcb, AttestationError> {
+    let missing =
+        |what: &str| AttestationError::InvalidCertificate(format!("PCK certificate misses {what}"));
+    let extension = cert
+        .tbs_certificate
+        .extensions
+        .iter()
+        .flatten()
+        .find(|ext| ext.extn_id == SGX_EXTENSIONS)
+        .ok_or_else(|| missing("SGX extensions"))?;
+
+    let mut fmspc = None;
+    let mut tcb = None;
+    for (oid, value) in oid_value_pairs(extension.extn_value.as_bytes())? {
+        if oid == SGX_EXT_FMSPC {
+            let bytes = expect_tag(value, TAG_OCTET_STRING)?;
+            fmspc = Some(bytes.try_into().map_err(|_| missing("6-byte FMSPC"))?);
+        } else if oid == SGX_EXT_TCB {
+            tcb = Some(parse_tcb(value)?);
+        }
+    }
+    let (sgx_components, pce_svn) = tcb.ok_or_else(|| missing("TCB"))?;
+    Ok(PckTcb {
+        fmspc: fmspc.ok_or_else(|| missing("FMSPC"))?,
+        sgx_components,
+        pce_svn,
+    })
+}
+
+fn parse_tcb(value: &[u8]) -> Result<([u8; 16], u16), AttestationError> {
+    let mut components = [0_u8; 16];
+    let mut pce_svn = None;
+    for (oid, value) in oid_value_pairs(value)? {
+        if oid == SGX_EXT_PCESVN {
+            pce_svn = Some(decode_uint(expect_tag(value, TAG_INTEGER)?)? as u16);
+        } else if oid.parent() == Some(SGX_EXT_TCB) {
+            // TCB components are identified by the last OID arc (1..=16).
+            let index = oid.arcs().last().unwrap_or(0) as usize;
+            if (1..=16).contains(&index) {
+                components[index - 1] = decode_uint(expect_tag(value, TAG_INTEGER)?)? as u8;
+            }
+        }
+    }
+    let pce_svn = pce_svn.ok_or_else(|| {
+        AttestationError::InvalidCertificate("PCK certificate misses PCESVN".into())
+    })?;
+    Ok((components, pce_svn))
+}
+
+/// Iterates over `SEQUENCE OF SEQUENCE { OID, ANY }`, returning OIDs and raw TLV-encoded values.
+fn oid_value_pairs(bytes: &[u8]) -> Result<Vec<(ObjectIdentifier, &[u8])>, AttestationError> {
+    let mut items = expect_tag(bytes, TAG_SEQUENCE)?;
+    let mut pairs = vec![];
+    while !items.is_empty() {
+        let (tag, content, rest) = read_tlv(items)?;
+        if tag != TAG_SEQUENCE {
+            return Err(malformed_extension());
+        }
+        items = rest;
+
+        let (tag, oid_bytes, value) = read_tlv(content)?;
+        if tag != TAG_OID {
+            return Err(malformed_extension());
+        }
+        let oid = ObjectIdentifier::from_bytes(oid_bytes).map_err(|_| malformed_extension())?;
+        pairs.push((oid, value));
+    }
+    Ok(pairs)
+}
+
+fn expect_tag(bytes: &[u8], expected_tag: u8) -> Result<&[u8], AttestationError> {
+    match read_tlv(bytes)? {
+        (tag, content, []) if tag == expected_tag => Ok(content),
+        _ => Err(malformed_extension()),
+    }
+}
+
+/// Reads a single DER TLV, returning its tag, content and the remaining bytes.
+fn read_tlv(bytes: &[u8]) -> Result<(u8, &[u8], &[u8]), AttestationError> {
+    let (&tag, bytes) = bytes.split_first().ok_or_else(malformed_extension)?;
+    let (&len_byte, mut bytes) = bytes.split_first().ok_or_else(malformed_extension)?;
+    let len = if len_byte < 0x80 {
+        usize::from(len_byte)
+    } else {
+        let len_len = usize::from(len_byte & 0x7f);
+        if len_len == 0 || len_len > 4 || bytes.len() < len_len {
+            return Err(malformed_extension());
+        }
+        let (len_bytes, rest) = bytes.split_at(len_len);
+        bytes = rest;
+        len_bytes
+            .iter()
+            .fold(0_usize, |acc, &b| (acc << 8) | usize::from(b))
+    };
+    if bytes.len() < len {
+        return Err(malformed_extension());
+    }
+    let (content, rest) = bytes.split_at(len);
+    Ok((tag, content, rest))
+}
+
+fn decode_uint(bytes: &[u8]) -> Result<u32, AttestationError> {
+    if bytes.is_empty() || bytes.len() > 5 || bytes[0] & 0x80 != 0 {
+        return Err(malformed_extension());
+    }
+    Ok(bytes
+        .iter()
+        .fold(0_u64, |acc, &b| (acc << 8) | u64::from(b)) as u32)
+}
+
+fn malformed_extension() -> AttestationError {
+    AttestationError::InvalidCertificate("malformed SGX extension in PCK certificate".into())
+}
@@ -0,0 +1,267 @@
+//! Parsing of Intel DCAP attestation quotes (SGX v3 / v4 and TDX v4).
+
+use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+use zksync_types::tee_types::TeeType;
+
+use super::AttestationError;
+
+const HEADER_LEN: usize = 48;
+const SGX_REPORT_BODY_LEN: usize = 384;
+const TDX_REPORT_BODY_LEN: usize = 584;
+const ECDSA_SIGNATURE_LEN: usize = 64;
+const ECDSA_PUBKEY_LEN: usize = 64;
+
+/// Attestation key type for ECDSA-256 with P-256 curve; the only type supported by DCAP.
+const ATT_KEY_TYPE_ECDSA_P256: u16 = 2;
+const TEE_TYPE_SGX: u32 = 0x0000_0000;
+const TEE_TYPE_TDX: u32 = 0x0000_0081;
+
+/// Certification data containing the QE report and its signature (used in v4 quotes).
+const CERT_DATA_TYPE_QE_REPORT: u16 = 6;
+/// Certification data containing the PEM-encoded PCK certificate chain.
+const CERT_DATA_TYPE_PCK_CHAIN: u16 = 5;
+
+/// Minimal cursor over the quote bytes returning errors instead of panicking on short input.
+#[derive(Debug)]
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    fn take(&mut self, len: usize, what: &'static str) -> Result<&'a [u8], AttestationError> {
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or(AttestationError::MalformedQuote(what))?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn u16(&mut self, what: &'static str) -> Result<u16, AttestationError> {
+        let bytes = self.take(2, what)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self, what: &'static str) -> Result<u32, AttestationError> {
+        let bytes = self.take(4, what)?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn rest(&self) -> &'a [u8] {
+        &self.bytes[self.position..]
+    }
+}
+
+/// SGX enclave report body. Used both for SGX application enclaves and for the quoting enclave (QE)
+/// report embedded in the quote signature data.
+#[derive(Debug, Clone)]
+pub(super) struct SgxReportBody {
+    pub mr_enclave: [u8; 32],
+    pub mr_signer: [u8; 32],
+    pub isv_prod_id: u16,
+    pub isv_svn: u16,
+    pub report_data: [u8; 64],
+}
+
+impl SgxReportBody {
+    fn parse(bytes: &[u8]) -> Self {
+        assert_eq!(bytes.len(), SGX_REPORT_BODY_LEN);
+        Self {
+            mr_enclave: bytes[64..96].try_into().unwrap(),
+            mr_signer: bytes[128..160].try_into().unwrap(),
+            isv_prod_id: u16::from_le_bytes([bytes[256], bytes[257]]),
+            isv_svn: u16::from_le_bytes([bytes[258], bytes[259]]),
+            report_data: bytes[320..384].try_into().unwrap(),
+        }
+    }
+}
+
+/// TDX trust domain report body (v4 quote layout).
+#[derive(Debug, Clone)]
+pub(super) struct TdxReportBody {
+    pub tee_tcb_svn: [u8; 16],
+    pub mr_td: [u8; 48],
+    pub report_data: [u8; 64],
+}
+
+impl TdxReportBody {
+    fn parse(bytes: &[u8]) -> Self {
+        assert_eq!(bytes.len(), TDX_REPORT_BODY_LEN);
+        Self {
+            tee_tcb_svn: bytes[0..16].try_into().unwrap(),
+            mr_td: bytes[136..184].try_into().unwrap(),
+            report_data: bytes[520..584].try_into().unwrap(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum ReportBody {
+    Sgx(SgxReportBody),
+    Tdx(TdxReportBody),
+}
+
+/// Parsed DCAP quote. Only the fields needed for verification are retained.
+#[derive(Debug, Clone)]
+pub(super) struct Quote {
+    pub body: ReportBody,
+    /// Bytes covered by the attestation key signature (header and report body).
+    signed_bytes: Vec<u8>,
+    signature: [u8; ECDSA_SIGNATURE_LEN],
+    attestation_key: [u8; ECDSA_PUBKEY_LEN],
+    pub qe_report: SgxReportBody,
+    /// Raw QE report, which is signed by the PCK certificate key.
+    pub qe_report_bytes: Vec<u8>,
+    pub qe_report_signature: [u8; ECDSA_SIGNATURE_LEN],
+    qe_auth_data: Vec<u8>,
+    /// PEM-encoded PCK certificate chain, leaf first.
+    pub pck_chain_pem: Vec<u8>,
+}
+
+impl Quote {
+    pub fn parse(bytes: &[u8]) -> Result<Self, AttestationError> {
+        let mut reader = Reader::new(bytes);
+        let header = reader.take(HEADER_LEN, "header")?;
+        let version = u16::from_le_bytes([header[0], header[1]]);
+        let att_key_type = u16::from_le_bytes([header[2], header[3]]);
+        let tee_type = u32::from_le_bytes(header[4..8].try_into().unwrap());
+
+        if att_key_type != ATT_KEY_TYPE_ECDSA_P256 {
+            return Err(AttestationError::UnsupportedQuote(format!(
+                "attestation key type {att_key_type}"
+            )));
+        }
+        let body = match (version, tee_type) {
+            (3 | 4, TEE_TYPE_SGX) => ReportBody::Sgx(SgxReportBody::parse(
+                reader.take(SGX_REPORT_BODY_LEN, "SGX report body")?,
+            )),
+            (4, TEE_TYPE_TDX) => ReportBody::Tdx(TdxReportBody::parse(
+                reader.take(TDX_REPORT_BODY_LEN, "TD report body")?,
+            )),
+            _ => {
+                return Err(AttestationError::UnsupportedQuote(format!(
+                    "version {version}, TEE type {tee_type:#x}"
+                )));
+            }
+        };
+        let signed_bytes = bytes[..reader.position].to_vec();
+
+        let signature_data_len = reader.u32("signature data length")? as usize;
+        let signature_data = reader.take(signature_data_len, "signature data")?;
+        if !reader.rest().is_empty() {
+            return Err(AttestationError::MalformedQuote("trailing bytes"));
+        }
+
+        let mut reader = Reader::new(signature_data);
+        let signature = reader
+            .take(ECDSA_SIGNATURE_LEN, "quote signature")?
+            .try_into()
+            .unwrap();
+        let attestation_key = reader
+            .take(ECDSA_PUBKEY_LEN, "attestation key")?
+            .try_into()
+            .unwrap();
+        if version >= 4 {
+            // In v4 quotes, the QE report is wrapped into certification data of its own type.
+            let cert_data_type = reader.u16("certification data type")?;
+            if cert_data_type != CERT_DATA_TYPE_QE_REPORT {
+                return Err(AttestationError::UnsupportedQuote(format!(
+                    "outer certification data type {cert_data_type}"
+                )));
+            }
+            let cert_data_len = reader.u32("certification data length")? as usize;
+            reader = Reader::new(reader.take(cert_data_len, "certification data")?);
+        }
+
+        let qe_report_bytes = reader.take(SGX_REPORT_BODY_LEN, "QE report")?;
+        let qe_report = SgxReportBody::parse(qe_report_bytes);
+        let qe_report_signature = reader
+            .take(ECDSA_SIGNATURE_LEN, "QE report signature")?
+            .try_into()
+            .unwrap();
+        let qe_auth_data_len = reader.u16("QE auth data length")? as usize;
+        let qe_auth_data = reader.take(qe_auth_data_len, "QE auth data")?.to_vec();
+        let cert_data_type = reader.u16("PCK certification data type")?;
+        if cert_data_type != CERT_DATA_TYPE_PCK_CHAIN {
+            return Err(AttestationError::UnsupportedQuote(format!(
+                "certification data type {cert_data_type}"
+            )));
+        }
+        let cert_data_len = reader.u32("PCK certification data length")? as usize;
+        let pck_chain_pem = reader
+            .take(cert_data_len, "PCK certification data")?
+            .to_vec();
+
+        Ok(Self {
+            body,
+            signed_bytes,
+            signature,
+            attestation_key,
+            qe_report,
+            qe_report_bytes: qe_report_bytes.to_vec(),
+            qe_report_signature,
+            qe_auth_data,
+            pck_chain_pem,
+        })
+    }
+
+    pub fn tee_type(&self) -> TeeType {
+        match &self.body {
+            ReportBody::Sgx(_) => TeeType::Sgx,
+            ReportBody::Tdx(_) => TeeType::Tdx,
+        }
+    }
+
+    pub fn report_data(&self) -> &[u8; 64] {
+        match &self.body {
+            ReportBody::Sgx(body) => &body.report_data,
+            ReportBody::Tdx(body) => &body.report_data,
+        }
+    }
+
+    /// Returns the enclave measurement: MRENCLAVE for SGX or MRTD for TDX.
+    pub fn measurement(&self) -> &[u8] {
+        match &self.body {
+            ReportBody::Sgx(body) => &body.mr_enclave,
+            ReportBody::Tdx(body) => &body.mr_td,
+        }
+    }
+
+    /// Verifies the quote signature made by the attestation key, and that the attestation key
+    /// is bound to the QE report (which is in turn signed by the PCK key; this is checked separately).
+    pub fn verify_attestation_key(&self) -> Result<(), AttestationError> {
+        let key = verifying_key_from_raw(&self.attestation_key)?;
+        let signature = Signature::from_slice(&self.signature)
+            .map_err(|_| AttestationError::MalformedQuote("quote signature"))?;
+        key.verify(&self.signed_bytes, &signature)
+            .map_err(|_| AttestationError::InvalidSignature("quote"))?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(self.attestation_key);
+        hasher.update(&self.qe_auth_data);
+        let expected_hash = hasher.finalize();
+        let report_data = &self.qe_report.report_data;
+        if report_data[..32] != expected_hash[..] || report_data[32..].iter().any(|&b| b != 0) {
+            return Err(AttestationError::InvalidSignature(
+                "QE report data does not commit to the attestation key",
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn verifying_key_from_raw(raw: &[u8; ECDSA_PUBKEY_LEN]) -> Result<VerifyingKey, AttestationError> {
+    let mut sec1 = [0_u8; ECDSA_PUBKEY_LEN + 1];
+    sec1[0] = 4; // uncompressed point tag
+    sec1[1..].copy_from_slice(raw);
+    VerifyingKey::from_sec1_bytes(&sec1)
+        .map_err(|_| AttestationError::MalformedQuote("attestation key"))
+}
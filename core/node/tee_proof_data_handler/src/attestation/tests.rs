@@ -0,0 +1,330 @@
+use std::path::Path;
+
+use chrono::TimeZone;
+use p256::ecdsa::{signature::Signer, Signature, SigningKey};
+
+use super::{
+    collateral::{parse_tcb_info, Crl},
+    *,
+};
+
+const QE_MRSIGNER: &str = "8c4f5775d796503e96137f77c68a829a0056ac8ded70140b081b094490c57bff";
+const PUBKEY: [u8; 33] = [2; 33];
+
+fn collateral() -> Collateral {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/attestation/testdata/collateral");
+    Collateral::load(&path).unwrap()
+}
+
+fn pck_key() -> SigningKey {
+    let key = hex::decode(include_str!("testdata/pck_key.hex").trim()).unwrap();
+    SigningKey::from_slice(&key).unwrap()
+}
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap()
+}
+
+fn policy() -> AttestationPolicy {
+    AttestationPolicy::new(&TeeAttestationPolicyConfig {
+        collateral_path: "unused".into(),
+        allowed_sgx_mrenclaves: vec![hex::encode([0x11; 32])],
+        allowed_tdx_mrtds: vec![hex::encode([0x22; 48])],
+        allowed_tcb_statuses: vec!["UpToDate".into(), "SWHardeningNeeded".into()],
+    })
+    .unwrap()
+}
+
+/// Builder for synthetic DCAP quotes signed by the test PCK key.
+#[derive(Debug, Clone)]
+struct QuoteBuilder {
+    tee_type: TeeType,
+    version: u16,
+    measurement: Vec<u8>,
+    report_data: [u8; 64],
+    tee_tcb_svn: [u8; 16],
+    qe_isv_svn: u16,
+}
+
+impl QuoteBuilder {
+    fn new(tee_type: TeeType) -> Self {
+        let mut report_data = [0; 64];
+        report_data[..32].copy_from_slice(&Sha256::digest(PUBKEY));
+        Self {
+            tee_type,
+            version: 4,
+            measurement: match tee_type {
+                TeeType::Tdx => vec![0x22; 48],
+                _ => vec![0x11; 32],
+            },
+            report_data,
+            tee_tcb_svn: [4; 16],
+            qe_isv_svn: 8,
+        }
+    }
+
+    fn build(&self) -> Vec<u8> {
+        let mut quote = vec![];
+        quote.extend_from_slice(&self.version.to_le_bytes());
+        quote.extend_from_slice(&2_u16.to_le_bytes());
+        let tee_type: u32 = match self.tee_type {
+            TeeType::Tdx => 0x81,
+            _ => 0,
+        };
+        quote.extend_from_slice(&tee_type.to_le_bytes());
+        quote.resize(48, 0);
+
+        match self.tee_type {
+            TeeType::Tdx => {
+                let mut body = [0_u8; 584];
+                body[..16].copy_from_slice(&self.tee_tcb_svn);
+                body[136..184].copy_from_slice(&self.measurement);
+                body[520..].copy_from_slice(&self.report_data);
+                quote.extend_from_slice(&body);
+            }
+            _ => {
+                let mut body = [0_u8; 384];
+                body[64..96].copy_from_slice(&self.measurement);
+                body[320..].copy_from_slice(&self.report_data);
+                quote.extend_from_slice(&body);
+            }
+        }
+
+        let attestation_key = SigningKey::from_slice(&[7; 32]).unwrap();
+        let signature: Signature = attestation_key.sign(&quote);
+        let raw_attestation_key = &attestation_key
+            .verifying_key()
+            .to_encoded_point(false)
+            .to_bytes()[1..];
+        let qe_auth_data = [1_u8; 32];
+
+        let mut qe_report = [0_u8; 384];
+        qe_report[128..160].copy_from_slice(&hex::decode(QE_MRSIGNER).unwrap());
+        let qe_prod_id: u16 = match self.tee_type {
+            TeeType::Tdx => 2,
+            _ => 1,
+        };
+        qe_report[256..258].copy_from_slice(&qe_prod_id.to_le_bytes());
+        qe_report[258..260].copy_from_slice(&self.qe_isv_svn.to_le_bytes());
+        let mut hasher = Sha256::new();
+        hasher.update(raw_attestation_key);
+        hasher.update(qe_auth_data);
+        qe_report[320..352].copy_from_slice(&hasher.finalize());
+        let qe_report_signature: Signature = pck_key().sign(&qe_report);
+
+        let pck_chain = include_bytes!("testdata/pck_chain.pem");
+        let mut qe_cert_data = vec![];
+        qe_cert_data.extend_from_slice(&qe_report);
+        qe_cert_data.extend_from_slice(&qe_report_signature.to_bytes());
+        qe_cert_data.extend_from_slice(&(qe_auth_data.len() as u16).to_le_bytes());
+        qe_cert_data.extend_from_slice(&qe_auth_data);
+        qe_cert_data.extend_from_slice(&5_u16.to_le_bytes());
+        qe_cert_data.extend_from_slice(&(pck_chain.len() as u32).to_le_bytes());
+        qe_cert_data.extend_from_slice(pck_chain);
+
+        let mut signature_data = vec![];
+        signature_data.extend_from_slice(&signature.to_bytes());
+        signature_data.extend_from_slice(raw_attestation_key);
+        if self.version >= 4 {
+            signature_data.extend_from_slice(&6_u16.to_le_bytes());
+            signature_data.extend_from_slice(&(qe_cert_data.len() as u32).to_le_bytes());
+        }
+        signature_data.extend_from_slice(&qe_cert_data);
+
+        quote.extend_from_slice(&(signature_data.len() as u32).to_le_bytes());
+        quote.extend_from_slice(&signature_data);
+        quote
+    }
+}
+
+#[test]
+fn verifying_sgx_quote() {
+    for version in [3, 4] {
+        let quote = QuoteBuilder {
+            version,
+            ..QuoteBuilder::new(TeeType::Sgx)
+        };
+        let verified = policy()
+            .verify(&collateral(), &PUBKEY, &quote.build(), now())
+            .unwrap();
+        assert_eq!(
+            verified,
+            VerifiedAttestation {
+                tee_type: TeeType::Sgx,
+                // The PCK certificate has all TCB components set to 3
+                tcb_status: TcbStatus::SWHardeningNeeded,
+                expires_at: Utc.with_ymd_and_hms(2099, 1, 1, 0, 0, 0).unwrap(),
+            }
+        );
+    }
+}
+
+#[test]
+fn verifying_tdx_quote() {
+    let quote = QuoteBuilder::new(TeeType::Tdx).build();
+    let verified = policy()
+        .verify(&collateral(), &PUBKEY, &quote, now())
+        .unwrap();
+    assert_eq!(verified.tee_type, TeeType::Tdx);
+    assert_eq!(verified.tcb_status, TcbStatus::SWHardeningNeeded);
+
+    // TDX module SVNs below all TCB levels.
+    let quote = QuoteBuilder {
+        tee_tcb_svn: [1; 16],
+        ..QuoteBuilder::new(TeeType::Tdx)
+    };
+    let err = policy()
+        .verify(&collateral(), &PUBKEY, &quote.build(), now())
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            AttestationError::TcbStatusNotAllowed(TcbStatus::Revoked)
+        ),
+        "{err}"
+    );
+}
+
+#[test]
+fn report_data_binding() {
+    let mut report_data = [0; 64];
+    report_data[..33].copy_from_slice(&PUBKEY);
+    assert!(is_report_data_bound(&report_data, &PUBKEY));
+    report_data[63] = 1;
+    assert!(!is_report_data_bound(&report_data, &PUBKEY));
+
+    let quote = QuoteBuilder::new(TeeType::Sgx).build();
+    let err = policy()
+        .verify(&collateral(), &[3; 33], &quote, now())
+        .unwrap_err();
+    assert!(matches!(err, AttestationError::ReportDataMismatch), "{err}");
+}
+
+#[test]
+fn rejecting_unknown_measurement() {
+    let quote = QuoteBuilder {
+        measurement: vec![0x33; 32],
+        ..QuoteBuilder::new(TeeType::Sgx)
+    };
+    let err = policy()
+        .verify(&collateral(), &PUBKEY, &quote.build(), now())
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            AttestationError::MeasurementNotAllowed {
+                tee_type: TeeType::Sgx,
+                ..
+            }
+        ),
+        "{err}"
+    );
+}
+
+#[test]
+fn rejecting_tampered_quote() {
+    let mut quote = QuoteBuilder::new(TeeType::Sgx).build();
+    quote[100] ^= 1; // inside the report body, but outside measurement / report data
+    let err = policy()
+        .verify(&collateral(), &PUBKEY, &quote, now())
+        .unwrap_err();
+    assert!(
+        matches!(err, AttestationError::InvalidSignature("quote")),
+        "{err}"
+    );
+
+    let quote = QuoteBuilder::new(TeeType::Sgx).build();
+    for len in [0, 47, 48 + 384, quote.len() - 1] {
+        let err = policy()
+            .verify(&collateral(), &PUBKEY, &quote[..len], now())
+            .unwrap_err();
+        assert!(matches!(err, AttestationError::MalformedQuote(_)), "{err}");
+    }
+}
+
+#[test]
+fn applying_tcb_policy() {
+    let quote = QuoteBuilder {
+        qe_isv_svn: 1,
+        ..QuoteBuilder::new(TeeType::Sgx)
+    };
+    let err = policy()
+        .verify(&collateral(), &PUBKEY, &quote.build(), now())
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            AttestationError::TcbStatusNotAllowed(TcbStatus::OutOfDate)
+        ),
+        "{err}"
+    );
+}
+
+#[test]
+fn rejecting_stale_collateral() {
+    let quote = QuoteBuilder::new(TeeType::Sgx).build();
+    let now = Utc.with_ymd_and_hms(2099, 1, 2, 0, 0, 0).unwrap();
+    let err = policy()
+        .verify(&collateral(), &PUBKEY, &quote, now)
+        .unwrap_err();
+    let documents_expire_at = Utc.with_ymd_and_hms(2099, 1, 1, 0, 0, 0).unwrap();
+    assert!(
+        matches!(err, AttestationError::StaleCollateral(at) if at == documents_expire_at),
+        "{err}"
+    );
+
+    // CRLs go stale later than TCB info and QE identity documents.
+    let now = Utc.with_ymd_and_hms(2099, 7, 1, 0, 0, 0).unwrap();
+    let err = policy()
+        .verify(&collateral(), &PUBKEY, &quote, now)
+        .unwrap_err();
+    let crls_expire_at = Utc.with_ymd_and_hms(2099, 6, 1, 0, 0, 0).unwrap();
+    assert!(
+        matches!(err, AttestationError::StaleCollateral(at) if at == crls_expire_at),
+        "{err}"
+    );
+}
+
+#[test]
+fn rejecting_tampered_collateral() {
+    let signing_chain = pck::verify_chain(
+        include_bytes!("testdata/collateral/tcb_signing_chain.pem"),
+        &collateral().root_ca,
+    )
+    .unwrap();
+    let signing_key = pck::verifying_key(&signing_chain[0]).unwrap();
+    let document = include_str!("testdata/collateral/tcb_info/sgx_00906ed50000.json");
+    parse_tcb_info(document.as_bytes(), &signing_key).unwrap();
+
+    let tampered_document = document.replacen("SWHardeningNeeded", "UpToDate", 1);
+    let err = parse_tcb_info(tampered_document.as_bytes(), &signing_key).unwrap_err();
+    assert!(format!("{err:#}").contains("signature"), "{err:#}");
+
+    let other_key = *pck_key().verifying_key();
+    let err = parse_tcb_info(document.as_bytes(), &other_key).unwrap_err();
+    assert!(format!("{err:#}").contains("signature"), "{err:#}");
+}
+
+#[test]
+fn rejecting_revoked_pck_certificate() {
+    let quote = QuoteBuilder::new(TeeType::Sgx).build();
+    let mut collateral = collateral();
+    let crl = Crl::from_pem(include_bytes!("testdata/revoking_platform_ca_crl.pem")).unwrap();
+    collateral.crls.insert(crl.issuer(), crl);
+    let err = policy()
+        .verify(&collateral, &PUBKEY, &quote, now())
+        .unwrap_err();
+    assert!(
+        matches!(&err, AttestationError::RevokedCertificate(subject) if subject.contains("PCK Certificate")),
+        "{err}"
+    );
+
+    collateral.crls.clear();
+    let err = policy()
+        .verify(&collateral, &PUBKEY, &quote, now())
+        .unwrap_err();
+    assert!(
+        matches!(err, AttestationError::MissingCollateral(_)),
+        "{err}"
+    );
+}
@@ -5,6 +5,8 @@ use axum::{
 use zksync_dal::DalError;
 use zksync_object_store::ObjectStoreError;
 
+use crate::attestation::AttestationError;
+
 #[derive(Debug, thiserror::Error)]
 pub enum TeeProcessorError {
     #[error("General error: {0}")]
@@ -16,6 +18,12 @@ pub enum TeeProcessorError {
     },
     #[error("Failed fetching/saving from db: {0}")]
     Dal(#[from] DalError),
+    #[error("Attestation rejected: {0}")]
+    Attestation(#[from] AttestationError),
+    #[error("Key is not attested: {0}")]
+    UnattestedKey(String),
+    #[error("Proof signature does not match the public key and root hash")]
+    InvalidProofSignature,
 }
 
 impl TeeProcessorError {
@@ -24,6 +32,9 @@ impl TeeProcessorError {
             Self::GeneralError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Self::ObjectStore { .. } => StatusCode::INTERNAL_SERVER_ERROR,
             Self::Dal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::Attestation(_) => StatusCode::BAD_REQUEST,
+            Self::UnattestedKey(_) => StatusCode::FORBIDDEN,
+            Self::InvalidProofSignature => StatusCode::BAD_REQUEST,
         }
     }
 }
@@ -18,9 +18,11 @@ use zksync_tee_prover_interface::api::{
 };
 use zksync_types::{commitment::L1BatchCommitmentMode, L2ChainId};
 
+mod attestation;
 mod errors;
 mod metrics;
 pub mod node;
+mod quorum;
 mod tee_request_processor;
 #[cfg(test)]
 mod tests;
@@ -41,7 +43,7 @@ pub async fn run_server(
         config,
         commitment_mode,
         l2_chain_id,
-    );
+    )?;
 
     let listener = tokio::net::TcpListener::bind(bind_address)
         .await
@@ -65,9 +67,9 @@ fn create_proof_processing_router(
     config: TeeProofDataHandlerConfig,
     _commitment_mode: L1BatchCommitmentMode,
     l2_chain_id: L2ChainId,
-) -> Router {
+) -> anyhow::Result<Router> {
     let get_tee_proof_gen_processor =
-        TeeRequestProcessor::new(blob_store, connection_pool, config.clone(), l2_chain_id);
+        TeeRequestProcessor::new(blob_store, connection_pool, config.clone(), l2_chain_id)?;
     let submit_tee_proof_processor = get_tee_proof_gen_processor.clone();
     let register_tee_attestation_processor = get_tee_proof_gen_processor.clone();
     let observe_tee_proof_gen_processor = get_tee_proof_gen_processor.clone();
@@ -136,7 +138,7 @@ fn create_proof_processing_router(
             ),
         );
 
-    router
+    Ok(router
         .layer(tower_http::compression::CompressionLayer::new())
-        .layer(tower_http::decompression::RequestDecompressionLayer::new().zstd(true))
+        .layer(tower_http::decompression::RequestDecompressionLayer::new().zstd(true)))
 }
@@ -1,12 +1,25 @@
 use std::{fmt, time::Duration};
 
-use vise::{EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics, Unit};
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit,
+};
 use zksync_types::tee_types::TeeType;
 
 #[derive(Debug, Metrics)]
 pub(super) struct TeeProofDataHandlerMetrics {
     #[metrics(buckets = vise::Buckets::LATENCIES, unit = Unit::Seconds)]
     pub tee_proof_roundtrip_time: Family<MetricsTeeType, Histogram<Duration>>,
+    /// Number of attestations that passed verification against the attestation policy.
+    pub verified_attestations: Family<MetricsTeeType, Counter>,
+    /// Number of attestations rejected by the attestation policy.
+    pub rejected_attestations: Counter,
+    /// Number of proofs rejected because their key had no valid attestation.
+    pub proofs_from_unattested_keys: Counter,
+    /// Number of distinct signers for batches that have reached the TEE quorum.
+    #[metrics(buckets = Buckets::linear(1.0..=10.0, 1.0))]
+    pub attested_batch_signers: Histogram<u64>,
+    /// Latest L1 batch that has reached the TEE quorum.
+    pub last_attested_batch: Gauge<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet, EncodeLabelValue)]
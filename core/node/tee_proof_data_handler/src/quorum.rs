@@ -0,0 +1,134 @@
+//! Aggregation of TEE proofs into a single attestation status per batch according to a quorum policy.
+
+use anyhow::Context as _;
+use secp256k1::{ecdsa::Signature, Message, PublicKey, SECP256K1};
+use tokio::sync::watch;
+use zksync_config::configs::tee_proof_data_handler::TeeQuorumConfig;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+
+use crate::metrics::METRICS;
+
+/// Checks that `signature` is a valid secp256k1 signature of `root_hash` made by `pubkey`. The signature is expected
+/// in the 65-byte format produced by TEE provers; the trailing recovery ID is ignored.
+pub(crate) fn is_valid_root_signature(pubkey: &[u8], signature: &[u8], root_hash: &[u8]) -> bool {
+    let Ok(pubkey) = PublicKey::from_slice(pubkey) else {
+        return false;
+    };
+    let Ok(message) = Message::from_slice(root_hash) else {
+        return false;
+    };
+    let Some(Ok(signature)) = signature.get(..64).map(Signature::from_compact) else {
+        return false;
+    };
+    SECP256K1
+        .verify_ecdsa(&message, &signature, &pubkey)
+        .is_ok()
+}
+
+/// Periodically marks batches as TEE-attested once signatures of their root hash satisfy the quorum policy.
+#[derive(Debug)]
+pub(crate) struct TeeQuorumTracker {
+    pool: ConnectionPool<Core>,
+    config: TeeQuorumConfig,
+    require_verified_attestations: bool,
+}
+
+impl TeeQuorumTracker {
+    pub(crate) fn new(
+        pool: ConnectionPool<Core>,
+        config: TeeQuorumConfig,
+        require_verified_attestations: bool,
+    ) -> Self {
+        Self {
+            pool,
+            config,
+            require_verified_attestations,
+        }
+    }
+
+    async fn step(&self) -> anyhow::Result<()> {
+        let mut connection = self.pool.connection_tagged("tee_quorum_tracker").await?;
+        let attested_batches = connection
+            .tee_proof_generation_dal()
+            .mark_attested_batches(
+                self.config.min_signers,
+                self.config.min_tee_types,
+                self.require_verified_attestations,
+            )
+            .await
+            .context("failed marking attested batches")?;
+
+        for &(l1_batch_number, signers) in &attested_batches {
+            tracing::info!(%l1_batch_number, signers, "L1 batch is TEE-attested");
+            METRICS.attested_batch_signers.observe(signers.into());
+        }
+        if let Some(&(max_batch_number, _)) = attested_batches.iter().max() {
+            let max_batch_number = max_batch_number.0.into();
+            if METRICS.last_attested_batch.get() < max_batch_number {
+                METRICS.last_attested_batch.set(max_batch_number);
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        tracing::info!(
+            min_signers = self.config.min_signers,
+            min_tee_types = self.config.min_tee_types,
+            require_verified_attestations = self.require_verified_attestations,
+            "Starting TEE quorum tracker"
+        );
+        while !*stop_receiver.borrow() {
+            self.step().await?;
+            if tokio::time::timeout(self.config.poll_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop request received, TEE quorum tracker is shutting down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_crypto_primitives::{sign, K256PrivateKey};
+    use zksync_types::H256;
+
+    use super::*;
+
+    #[test]
+    fn verifying_root_signatures() {
+        let private_key = K256PrivateKey::random();
+        let pubkey = PublicKey::from_secret_key(SECP256K1, private_key.expose_secret());
+        let pubkey = pubkey.serialize();
+        let root_hash = H256::repeat_byte(0x23);
+        let signature = sign(&private_key, &root_hash).unwrap().into_electrum();
+
+        assert!(is_valid_root_signature(
+            &pubkey,
+            &signature,
+            root_hash.as_bytes()
+        ));
+        let other_hash = H256::repeat_byte(0x24);
+        assert!(!is_valid_root_signature(
+            &pubkey,
+            &signature,
+            other_hash.as_bytes()
+        ));
+        let other_pubkey =
+            PublicKey::from_secret_key(SECP256K1, K256PrivateKey::random().expose_secret());
+        assert!(!is_valid_root_signature(
+            &other_pubkey.serialize(),
+            &signature,
+            root_hash.as_bytes()
+        ));
+        assert!(!is_valid_root_signature(
+            &pubkey,
+            &[0; 10],
+            root_hash.as_bytes()
+        ));
+    }
+}
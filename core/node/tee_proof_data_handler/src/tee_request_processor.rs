@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use anyhow::Context as _;
 use axum::{extract::Path, Json};
 use chrono::{Duration as ChronoDuration, Utc};
 use zksync_config::configs::TeeProofDataHandlerConfig;
@@ -20,7 +21,12 @@ use zksync_tee_prover_interface::{
 use zksync_types::{tee_types::TeeType, L1BatchNumber, L2ChainId};
 use zksync_vm_executor::storage::{L1BatchParamsProvider, RestoredL1BatchEnv};
 
-use crate::{errors::TeeProcessorError, metrics::METRICS};
+use crate::{
+    attestation::{AttestationPolicy, Collateral},
+    errors::TeeProcessorError,
+    metrics::METRICS,
+    quorum::is_valid_root_signature,
+};
 
 #[derive(Clone)]
 pub(crate) struct TeeRequestProcessor {
@@ -28,6 +34,7 @@ pub(crate) struct TeeRequestProcessor {
     pool: ConnectionPool<Core>,
     config: TeeProofDataHandlerConfig,
     l2_chain_id: L2ChainId,
+    attestation_policy: Option<Arc<AttestationPolicy>>,
 }
 
 impl TeeRequestProcessor {
@@ -36,13 +43,21 @@ impl TeeRequestProcessor {
         pool: ConnectionPool<Core>,
         config: TeeProofDataHandlerConfig,
         l2_chain_id: L2ChainId,
-    ) -> Self {
-        Self {
+    ) -> anyhow::Result<Self> {
+        let attestation_policy = config
+            .attestation_policy
+            .as_ref()
+            .map(AttestationPolicy::new)
+            .transpose()
+            .context("invalid TEE attestation policy")?
+            .map(Arc::new);
+        Ok(Self {
             blob_store,
             pool,
             config,
             l2_chain_id,
-        }
+            attestation_policy,
+        })
     }
 
     pub(crate) async fn get_proof_generation_data(
@@ -280,8 +295,19 @@ impl TeeRequestProcessor {
         Json(proof): Json<SubmitTeeProofRequest>,
     ) -> Result<Json<SubmitTeeProofResponse>, TeeProcessorError> {
         let l1_batch_number = L1BatchNumber(l1_batch_number);
+        let is_valid_signature =
+            is_valid_root_signature(&proof.0.pubkey, &proof.0.signature, &proof.0.proof);
+        if self.config.quorum.is_some() && !is_valid_signature {
+            return Err(TeeProcessorError::InvalidProofSignature);
+        }
+        if self.attestation_policy.is_some() {
+            self.ensure_key_attested(&proof.0.pubkey, proof.0.tee_type)
+                .await?;
+        }
+
         let mut connection = self.pool.connection_tagged("tee_request_processor").await?;
-        let mut dal = connection.tee_proof_generation_dal();
+        let mut transaction = connection.start_transaction().await?;
+        let mut dal = transaction.tee_proof_generation_dal();
         dal.save_proof_artifacts_metadata(
             l1_batch_number,
             proof.0.tee_type,
@@ -290,6 +316,20 @@ impl TeeRequestProcessor {
             &proof.0.proof,
         )
         .await?;
+        // The proof is the signed batch root hash; it's recorded per key for quorum tracking.
+        // Recorded signatures are counted towards the quorum without further checks (the quorum may be enabled
+        // after the proof was submitted), so invalid signatures are never recorded.
+        if is_valid_signature {
+            dal.save_proof_signature(
+                l1_batch_number,
+                proof.0.tee_type,
+                &proof.0.pubkey,
+                &proof.0.signature,
+                &proof.0.proof,
+            )
+            .await?;
+        }
+        transaction.commit().await?;
 
         let sealed_at = connection
             .blocks_dal()
@@ -315,16 +355,81 @@ impl TeeRequestProcessor {
         Ok(Json(SubmitTeeProofResponse::Success))
     }
 
+    /// Checks that `pubkey` has a verified attestation for `tee_type` that hasn't expired yet.
+    async fn ensure_key_attested(
+        &self,
+        pubkey: &[u8],
+        tee_type: TeeType,
+    ) -> Result<(), TeeProcessorError> {
+        let expires_at = self
+            .pool
+            .connection_tagged("tee_request_processor")
+            .await?
+            .tee_proof_generation_dal()
+            .get_attestation_expiration(pubkey, tee_type)
+            .await?;
+        let reason = match expires_at {
+            Some(expires_at) if expires_at > Utc::now() => return Ok(()),
+            Some(expires_at) => format!("{tee_type} attestation has expired at {expires_at}"),
+            None => format!("no verified {tee_type} attestation"),
+        };
+        METRICS.proofs_from_unattested_keys.inc();
+        Err(TeeProcessorError::UnattestedKey(reason))
+    }
+
     pub(crate) async fn register_tee_attestation(
         &self,
         Json(payload): Json<RegisterTeeAttestationRequest>,
     ) -> Result<Json<RegisterTeeAttestationResponse>, TeeProcessorError> {
         tracing::info!("Received attestation: {:?}", payload);
 
-        let mut connection = self.pool.connection_tagged("tee_request_processor").await?;
-        let mut dal = connection.tee_proof_generation_dal();
+        let Some(policy) = self.attestation_policy.clone() else {
+            let mut connection = self.pool.connection_tagged("tee_request_processor").await?;
+            connection
+                .tee_proof_generation_dal()
+                .save_attestation(&payload.pubkey, &payload.attestation)
+                .await?;
+            return Ok(Json(RegisterTeeAttestationResponse::Success));
+        };
 
-        dal.save_attestation(&payload.pubkey, &payload.attestation)
+        // Collateral is reloaded on each registration so that it can be refreshed without a restart;
+        // registrations are rare, so this isn't a performance concern.
+        let (pubkey, attestation) = (payload.pubkey.clone(), payload.attestation.clone());
+        let verification_result = tokio::task::spawn_blocking(move || {
+            let collateral = Collateral::load(&policy.collateral_path).map_err(|err| {
+                TeeProcessorError::GeneralError(format!("Failed loading TEE collateral: {err:#}"))
+            })?;
+            policy
+                .verify(&collateral, &pubkey, &attestation, Utc::now())
+                .map_err(TeeProcessorError::from)
+        })
+        .await
+        .map_err(|err| TeeProcessorError::GeneralError(err.to_string()))?;
+
+        let verified = verification_result.inspect_err(|err| {
+            if matches!(err, TeeProcessorError::Attestation(_)) {
+                METRICS.rejected_attestations.inc();
+            }
+        })?;
+        tracing::info!(
+            tee_type = %verified.tee_type,
+            tcb_status = %verified.tcb_status,
+            expires_at = %verified.expires_at,
+            "Verified attestation for public key 0x{}",
+            hex::encode(&payload.pubkey)
+        );
+        METRICS.verified_attestations[&verified.tee_type.into()].inc();
+
+        let mut connection = self.pool.connection_tagged("tee_request_processor").await?;
+        connection
+            .tee_proof_generation_dal()
+            .save_verified_attestation(
+                &payload.pubkey,
+                &payload.attestation,
+                verified.tee_type,
+                &verified.tcb_status.to_string(),
+                verified.expires_at,
+            )
             .await?;
 
         Ok(Json(RegisterTeeAttestationResponse::Success))
@@ -6,16 +6,25 @@ use axum::{
     response::Response,
     Router,
 };
+use chrono::Utc;
+use secp256k1::{PublicKey, SECP256K1};
 use serde_json::json;
 use tower::ServiceExt;
-use zksync_config::configs::TeeProofDataHandlerConfig;
+use zksync_config::configs::{
+    tee_proof_data_handler::{TeeAttestationPolicyConfig, TeeQuorumConfig},
+    TeeProofDataHandlerConfig,
+};
 use zksync_contracts::BaseSystemContractsHashes;
+use zksync_crypto_primitives::{sign, K256PrivateKey};
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_object_store::MockObjectStore;
-use zksync_tee_prover_interface::api::SubmitTeeProofRequest;
+use zksync_tee_prover_interface::{
+    api::{RegisterTeeAttestationRequest, SubmitTeeProofRequest},
+    outputs::L1BatchTeeProofForL1,
+};
 use zksync_types::{
-    block::L1BatchHeader, commitment::L1BatchCommitmentMode, tee_types::TeeType, L1BatchNumber,
-    L2ChainId, ProtocolVersion, ProtocolVersionId, H256,
+    api::TeeAttestationStatus, block::L1BatchHeader, commitment::L1BatchCommitmentMode,
+    tee_types::TeeType, L1BatchNumber, L2ChainId, ProtocolVersion, ProtocolVersionId, H256,
 };
 
 use crate::create_proof_processing_router;
@@ -26,6 +35,24 @@ fn test_config() -> TeeProofDataHandlerConfig {
         first_processed_batch: L1BatchNumber(0),
         proof_generation_timeout: Duration::from_secs(600),
         batch_permanently_ignored_timeout: Duration::from_secs(10 * 24 * 3_600),
+        attestation_policy: None,
+        quorum: None,
+    }
+}
+
+fn test_config_with_attestation_policy() -> TeeProofDataHandlerConfig {
+    TeeProofDataHandlerConfig {
+        attestation_policy: Some(TeeAttestationPolicyConfig {
+            collateral_path: concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/src/attestation/testdata/collateral"
+            )
+            .into(),
+            allowed_sgx_mrenclaves: vec![hex::encode([0x11; 32])],
+            allowed_tdx_mrtds: vec![],
+            allowed_tcb_statuses: vec!["UpToDate".into()],
+        }),
+        ..test_config()
     }
 }
 
@@ -39,7 +66,8 @@ async fn request_tee_proof_inputs() {
         test_config(),
         L1BatchCommitmentMode::Rollup,
         L2ChainId::default(),
-    );
+    )
+    .unwrap();
     let test_cases = vec![
         (json!({ "tee_type": "sgx" }), StatusCode::NO_CONTENT),
         (
@@ -76,7 +104,8 @@ async fn request_tee_proof_inputs_no_lock_returns_404_for_missing_batch() {
         test_config(),
         L1BatchCommitmentMode::Rollup,
         L2ChainId::default(),
-    );
+    )
+    .unwrap();
 
     let response = app
         .oneshot(
@@ -104,7 +133,8 @@ async fn request_tee_proof_inputs_no_lock_returns_404_for_missing_blob_data() {
         test_config(),
         L1BatchCommitmentMode::Rollup,
         L2ChainId::default(),
-    );
+    )
+    .unwrap();
 
     let response = app
         .oneshot(
@@ -129,7 +159,8 @@ async fn present_batches_returns_null_fields_when_empty() {
         test_config(),
         L1BatchCommitmentMode::Rollup,
         L2ChainId::default(),
-    );
+    )
+    .unwrap();
 
     let response = app
         .oneshot(
@@ -161,7 +192,8 @@ async fn present_batches_returns_oldest_and_latest_batches() {
         test_config(),
         L1BatchCommitmentMode::Rollup,
         L2ChainId::default(),
-    );
+    )
+    .unwrap();
 
     let response = app
         .oneshot(
@@ -202,7 +234,8 @@ async fn submit_tee_proof() {
         test_config(),
         L1BatchCommitmentMode::Rollup,
         L2ChainId::default(),
-    );
+    )
+    .unwrap();
 
     // this should fail because we haven't saved the attestation for the pubkey yet
 
@@ -256,6 +289,244 @@ async fn submit_tee_proof() {
     assert_eq!(proof.pubkey.as_ref().unwrap(), &tee_proof_request.0.pubkey);
 }
 
+#[tokio::test]
+async fn submit_tee_proof_with_quorum_policy() {
+    let batch_number = L1BatchNumber::from(1);
+    let db_conn_pool = ConnectionPool::test_pool().await;
+    mock_tee_batch_status(db_conn_pool.clone(), batch_number).await;
+
+    let private_key = K256PrivateKey::random();
+    let pubkey = PublicKey::from_secret_key(SECP256K1, private_key.expose_secret()).serialize();
+    db_conn_pool
+        .connection()
+        .await
+        .unwrap()
+        .tee_proof_generation_dal()
+        .save_attestation(&pubkey, &[1, 2, 3])
+        .await
+        .unwrap();
+
+    let config = TeeProofDataHandlerConfig {
+        quorum: Some(TeeQuorumConfig {
+            min_signers: 1,
+            min_tee_types: 1,
+            poll_interval: Duration::from_secs(1),
+        }),
+        ..test_config()
+    };
+    let app = create_proof_processing_router(
+        MockObjectStore::arc(),
+        db_conn_pool.clone(),
+        config,
+        L1BatchCommitmentMode::Rollup,
+        L2ChainId::default(),
+    )
+    .unwrap();
+    let uri = format!("/tee/submit_proofs/{}", batch_number.0);
+
+    let root_hash = H256::repeat_byte(0x42);
+    let signature = sign(&private_key, &root_hash).unwrap().into_electrum();
+    let mut tee_proof_request = SubmitTeeProofRequest(Box::new(L1BatchTeeProofForL1 {
+        signature: signature.into(),
+        pubkey: pubkey.into(),
+        proof: H256::repeat_byte(0x43).as_bytes().into(),
+        tee_type: TeeType::Sgx,
+    }));
+    let response = send_submit_tee_proof_request(&app, &uri, &tee_proof_request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    tee_proof_request.0.proof = root_hash.as_bytes().into();
+    let response = send_submit_tee_proof_request(&app, &uri, &tee_proof_request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let status = db_conn_pool
+        .connection()
+        .await
+        .unwrap()
+        .tee_proof_generation_dal()
+        .get_batch_attestation(batch_number)
+        .await
+        .unwrap()
+        .expect("no signatures recorded");
+    assert_eq!(status.status, TeeAttestationStatus::Pending);
+    assert_eq!(status.signers, 1);
+    assert_eq!(status.tee_types, [TeeType::Sgx]);
+}
+
+#[tokio::test]
+async fn unverified_signatures_are_not_counted_after_enabling_quorum() {
+    let batch_number = L1BatchNumber::from(1);
+    let db_conn_pool = ConnectionPool::test_pool().await;
+    mock_tee_batch_status(db_conn_pool.clone(), batch_number).await;
+
+    let private_key = K256PrivateKey::random();
+    let pubkey = PublicKey::from_secret_key(SECP256K1, private_key.expose_secret()).serialize();
+    db_conn_pool
+        .connection()
+        .await
+        .unwrap()
+        .tee_proof_generation_dal()
+        .save_attestation(&pubkey, &[1, 2, 3])
+        .await
+        .unwrap();
+
+    // Submit a proof with a signature that doesn't match the root hash while the quorum is disabled.
+    let app = create_proof_processing_router(
+        MockObjectStore::arc(),
+        db_conn_pool.clone(),
+        test_config(),
+        L1BatchCommitmentMode::Rollup,
+        L2ChainId::default(),
+    )
+    .unwrap();
+    let uri = format!("/tee/submit_proofs/{}", batch_number.0);
+    let signature = sign(&private_key, &H256::repeat_byte(0x42))
+        .unwrap()
+        .into_electrum();
+    let tee_proof_request = SubmitTeeProofRequest(Box::new(L1BatchTeeProofForL1 {
+        signature: signature.into(),
+        pubkey: pubkey.into(),
+        proof: H256::repeat_byte(0x43).as_bytes().into(),
+        tee_type: TeeType::Sgx,
+    }));
+    let response = send_submit_tee_proof_request(&app, &uri, &tee_proof_request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Enable the quorum; the unverified signature must not be counted towards it.
+    let mut conn = db_conn_pool.connection().await.unwrap();
+    let attested_batches = conn
+        .tee_proof_generation_dal()
+        .mark_attested_batches(1, 1, false)
+        .await
+        .unwrap();
+    assert!(attested_batches.is_empty(), "{attested_batches:?}");
+    let status = conn
+        .tee_proof_generation_dal()
+        .get_batch_attestation(batch_number)
+        .await
+        .unwrap();
+    assert!(
+        status
+            .as_ref()
+            .is_none_or(|status| status.status != TeeAttestationStatus::Attested),
+        "{status:?}"
+    );
+}
+
+#[tokio::test]
+async fn submit_tee_proof_with_attestation_policy() {
+    let batch_number = L1BatchNumber::from(1);
+    let db_conn_pool = ConnectionPool::test_pool().await;
+    mock_tee_batch_status(db_conn_pool.clone(), batch_number).await;
+
+    let tee_proof_request_str = r#"{
+        "signature": "0001020304",
+        "pubkey": "0506070809",
+        "proof": "0A0B0C0D0E",
+        "tee_type": "sgx"
+    }"#;
+    let tee_proof_request =
+        serde_json::from_str::<SubmitTeeProofRequest>(tee_proof_request_str).unwrap();
+    let pubkey = tee_proof_request.0.pubkey.clone();
+    let uri = format!("/tee/submit_proofs/{}", batch_number.0);
+    let app = create_proof_processing_router(
+        MockObjectStore::arc(),
+        db_conn_pool.clone(),
+        test_config_with_attestation_policy(),
+        L1BatchCommitmentMode::Rollup,
+        L2ChainId::default(),
+    )
+    .unwrap();
+
+    // An unverified attestation isn't enough.
+    let mut conn = db_conn_pool.connection().await.unwrap();
+    conn.tee_proof_generation_dal()
+        .save_attestation(&pubkey, &[1, 2, 3])
+        .await
+        .unwrap();
+    let response = send_submit_tee_proof_request(&app, &uri, &tee_proof_request).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // Neither is an expired one.
+    conn.tee_proof_generation_dal()
+        .save_verified_attestation(
+            &pubkey,
+            &[1, 2, 3],
+            TeeType::Sgx,
+            "UpToDate",
+            Utc::now() - chrono::Duration::hours(1),
+        )
+        .await
+        .unwrap();
+    let response = send_submit_tee_proof_request(&app, &uri, &tee_proof_request).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // A valid attestation for another TEE type is rejected as well.
+    conn.tee_proof_generation_dal()
+        .save_verified_attestation(
+            &pubkey,
+            &[1, 2, 3],
+            TeeType::Tdx,
+            "UpToDate",
+            Utc::now() + chrono::Duration::hours(1),
+        )
+        .await
+        .unwrap();
+    let response = send_submit_tee_proof_request(&app, &uri, &tee_proof_request).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    conn.tee_proof_generation_dal()
+        .save_verified_attestation(
+            &pubkey,
+            &[1, 2, 3],
+            TeeType::Sgx,
+            "UpToDate",
+            Utc::now() + chrono::Duration::hours(1),
+        )
+        .await
+        .unwrap();
+    let response = send_submit_tee_proof_request(&app, &uri, &tee_proof_request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn register_invalid_attestation_with_policy() {
+    let db_conn_pool = ConnectionPool::test_pool().await;
+    let app = create_proof_processing_router(
+        MockObjectStore::arc(),
+        db_conn_pool.clone(),
+        test_config_with_attestation_policy(),
+        L1BatchCommitmentMode::Rollup,
+        L2ChainId::default(),
+    )
+    .unwrap();
+
+    let request = RegisterTeeAttestationRequest {
+        attestation: vec![0; 100],
+        pubkey: vec![2; 33],
+    };
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/tee/register_attestation")
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .body(Body::from(serde_json::to_vec(&request).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let mut conn = db_conn_pool.connection().await.unwrap();
+    let expiration = conn
+        .tee_proof_generation_dal()
+        .get_attestation_expiration(&request.pubkey, TeeType::Sgx)
+        .await
+        .unwrap();
+    assert_eq!(expiration, None);
+}
+
 // Mock SQL db with information about the status of the TEE proof generation
 async fn mock_tee_batch_status(
     db_conn_pool: ConnectionPool<zksync_dal::Core>,
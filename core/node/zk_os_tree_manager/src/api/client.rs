@@ -4,11 +4,13 @@ use std::fmt;
 
 use anyhow::Context;
 use axum::http::header;
-use zk_os_merkle_tree::BatchTreeProof;
+use zk_os_merkle_tree::{BatchTreeProof, TreeEntriesWithMultiproof};
 use zksync_health_check::{async_trait, CheckHealth, Health, HealthStatus};
 use zksync_types::{L1BatchNumber, H256};
 
-use super::{Problem, TreeProofRequest, TreeProofResponse, PROBLEM_CONTENT_TYPE};
+use super::{
+    Problem, TreeMultiproofResponse, TreeProofRequest, TreeProofResponse, PROBLEM_CONTENT_TYPE,
+};
 use crate::{health::MerkleTreeInfo, LazyAsyncTreeReader};
 
 /// Client-side tree API error used by [`TreeApiClient`].
@@ -49,6 +51,14 @@ pub trait TreeApiClient: 'static + Send + Sync + fmt::Debug {
         l1_batch_number: L1BatchNumber,
         hashed_keys: Vec<H256>,
     ) -> Result<BatchTreeProof, TreeApiError>;
+
+    /// Obtains values for the specified `hashed_keys` together with a multiproof at the specified tree version
+    /// (= L1 batch number). Returned entries are sorted by key and deduplicated.
+    async fn get_multiproof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<H256>,
+    ) -> Result<TreeEntriesWithMultiproof, TreeApiError>;
 }
 
 /// In-process API client implementation.
@@ -76,6 +86,21 @@ impl TreeApiClient for LazyAsyncTreeReader {
             Err(TreeApiError::NotReady(None))
         }
     }
+
+    async fn get_multiproof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<H256>,
+    ) -> Result<TreeEntriesWithMultiproof, TreeApiError> {
+        if let Some(reader) = self.read() {
+            reader
+                .entries_with_multiproof(l1_batch_number.0.into(), hashed_keys)
+                .await
+                .map_err(TreeApiError::Internal)
+        } else {
+            Err(TreeApiError::NotReady(None))
+        }
+    }
 }
 
 /// [`TreeApiClient`] implementation requesting data from a Merkle tree API server.
@@ -84,6 +109,7 @@ pub struct TreeApiHttpClient {
     inner: reqwest::Client,
     info_url: String,
     proofs_url: String,
+    multiproof_url: String,
 }
 
 impl TreeApiHttpClient {
@@ -98,6 +124,7 @@ impl TreeApiHttpClient {
             inner: client,
             info_url: url_base.to_owned(),
             proofs_url: format!("{url_base}/proofs"),
+            multiproof_url: format!("{url_base}/multiproof"),
         }
     }
 
@@ -169,19 +196,50 @@ impl TreeApiClient for TreeApiHttpClient {
         l1_batch_number: L1BatchNumber,
         hashed_keys: Vec<H256>,
     ) -> Result<BatchTreeProof, TreeApiError> {
+        let request = TreeProofRequest {
+            l1_batch_number,
+            hashed_keys,
+        };
+        let response: TreeProofResponse = self
+            .post_proof_request(&self.proofs_url, &request, "proofs")
+            .await?;
+        Ok(response.proof.into())
+    }
+
+    async fn get_multiproof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<H256>,
+    ) -> Result<TreeEntriesWithMultiproof, TreeApiError> {
+        let request = TreeProofRequest {
+            l1_batch_number,
+            hashed_keys,
+        };
+        let response: TreeMultiproofResponse = self
+            .post_proof_request(&self.multiproof_url, &request, "multiproof")
+            .await?;
+        Ok(response.into())
+    }
+}
+
+impl TreeApiHttpClient {
+    async fn post_proof_request<R: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+        request: &TreeProofRequest,
+        description: &str,
+    ) -> Result<R, TreeApiError> {
+        let l1_batch_number = request.l1_batch_number;
         let response = self
             .inner
-            .post(&self.proofs_url)
-            .json(&TreeProofRequest {
-                l1_batch_number,
-                hashed_keys,
-            })
+            .post(url)
+            .json(request)
             .send()
             .await
             .map_err(|err| {
                 TreeApiError::for_request(
                     err,
-                    format_args!("proofs for L1 batch #{l1_batch_number}"),
+                    format_args!("{description} for L1 batch #{l1_batch_number}"),
                 )
             })?;
 
@@ -199,17 +257,18 @@ impl TreeApiClient for TreeApiHttpClient {
                 TreeApiError::NoVersion
             } else {
                 TreeApiError::Internal(anyhow::anyhow!(
-                    "requesting proofs for L1 batch #{l1_batch_number} failed: {problem:?}"
+                    "requesting {description} for L1 batch #{l1_batch_number} failed: {problem:?}"
                 ))
             });
         }
 
         let response = response.error_for_status().with_context(|| {
-            format!("requesting proofs for L1 batch #{l1_batch_number} returned non-OK response")
-        })?;
-        let response: TreeProofResponse = response.json().await.with_context(|| {
-            format!("failed deserializing proofs for L1 batch #{l1_batch_number}")
+            format!(
+                "requesting {description} for L1 batch #{l1_batch_number} returned non-OK response"
+            )
         })?;
-        Ok(response.proof.into())
+        Ok(response.json().await.with_context(|| {
+            format!("failed deserializing {description} for L1 batch #{l1_batch_number}")
+        })?)
     }
 }
@@ -18,7 +18,7 @@ use axum::{
 };
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use tokio::sync::watch;
-use zk_os_merkle_tree::{unstable, BatchTreeProof, TreeOperation};
+use zk_os_merkle_tree::{unstable, BatchTreeProof, TreeEntriesWithMultiproof, TreeOperation};
 use zksync_types::{web3, L1BatchNumber, H256};
 
 pub use self::client::{TreeApiClient, TreeApiError, TreeApiHttpClient};
@@ -99,6 +99,50 @@ struct TreeProofResponse {
     proof: ApiBatchTreeProof,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+struct ApiTreeEntry {
+    key: H256,
+    /// `None` if the key is missing from the tree.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    value: Option<H256>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TreeMultiproofResponse {
+    /// Entries sorted by key; read operations in `proof` correspond 1-to-1 to them.
+    entries: Vec<ApiTreeEntry>,
+    leaf_count: u64,
+    proof: ApiBatchTreeProof,
+}
+
+impl From<TreeEntriesWithMultiproof> for TreeMultiproofResponse {
+    fn from(multiproof: TreeEntriesWithMultiproof) -> Self {
+        let entries = multiproof
+            .entries
+            .into_iter()
+            .map(|(key, value)| ApiTreeEntry { key, value });
+        Self {
+            entries: entries.collect(),
+            leaf_count: multiproof.leaf_count,
+            proof: multiproof.proof.into(),
+        }
+    }
+}
+
+impl From<TreeMultiproofResponse> for TreeEntriesWithMultiproof {
+    fn from(response: TreeMultiproofResponse) -> Self {
+        let entries = response
+            .entries
+            .into_iter()
+            .map(|entry| (entry.key, entry.value));
+        Self {
+            entries: entries.collect(),
+            leaf_count: response.leaf_count,
+            proof: response.proof.into(),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Hash)]
 struct SerdeNodeKey(unstable::NodeKey);
 
@@ -268,11 +312,10 @@ impl AsyncTreeReader {
         Ok(Json(info))
     }
 
-    async fn get_proofs_inner(
+    async fn ensure_version_available(
         &self,
         l1_batch_number: L1BatchNumber,
-        hashed_keys: Vec<H256>,
-    ) -> Result<BatchTreeProof, ApiServerError> {
+    ) -> Result<(), ApiServerError> {
         let info = self.clone().info().await?;
         let available_versions = info.min_version.unwrap_or(0)..info.next_version;
         let requested_version = l1_batch_number.0.into();
@@ -282,7 +325,15 @@ impl AsyncTreeReader {
                 available: available_versions,
             });
         }
+        Ok(())
+    }
 
+    async fn get_proofs_inner(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<H256>,
+    ) -> Result<BatchTreeProof, ApiServerError> {
+        self.ensure_version_available(l1_batch_number).await?;
         // There's a potential for TOCTOU here once tree pruning is implemented, but its possibility is reasonably low.
         self.clone()
             .prove(l1_batch_number.0.into(), hashed_keys)
@@ -305,6 +356,31 @@ impl AsyncTreeReader {
         Ok(Json(response))
     }
 
+    async fn get_multiproof_inner(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<H256>,
+    ) -> Result<TreeEntriesWithMultiproof, ApiServerError> {
+        self.ensure_version_available(l1_batch_number).await?;
+        self.clone()
+            .entries_with_multiproof(l1_batch_number.0.into(), hashed_keys)
+            .await
+            .map_err(ApiServerError::Internal)
+    }
+
+    async fn get_multiproof_handler(
+        State(this): State<Self>,
+        Json(request): Json<TreeProofRequest>,
+    ) -> Result<Json<TreeMultiproofResponse>, ApiServerError> {
+        let latency = API_METRICS.latency[&MerkleTreeApiMethod::GetMultiproof].start();
+        let multiproof = this
+            .get_multiproof_inner(request.l1_batch_number, request.hashed_keys)
+            .await?;
+        let response = multiproof.into();
+        latency.observe();
+        Ok(Json(response))
+    }
+
     async fn get_nodes_handler(
         State(this): State<Self>,
         Json(request): Json<TreeNodesRequest>,
@@ -333,6 +409,7 @@ impl AsyncTreeReader {
         let app = Router::new()
             .route("/", routing::get(Self::info_handler))
             .route("/proofs", routing::post(Self::get_proofs_handler))
+            .route("/multiproof", routing::post(Self::get_multiproof_handler))
             .route("/debug/nodes", routing::post(Self::get_nodes_handler))
             .with_state(self);
 
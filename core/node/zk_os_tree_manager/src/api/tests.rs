@@ -53,6 +53,20 @@ async fn in_process_client_basics() {
             &keys,
         )
         .unwrap();
+
+    let multiproof = client
+        .get_multiproof(L1BatchNumber(0), keys.clone())
+        .await
+        .unwrap();
+    assert_eq!(multiproof.entries.len(), keys.len());
+    assert_eq!(multiproof.leaf_count, info.leaf_count);
+    multiproof
+        .verify(
+            &Blake2Hasher,
+            <DefaultTreeParams>::TREE_DEPTH,
+            info.root_hash,
+        )
+        .unwrap();
 }
 
 #[tokio::test]
@@ -104,6 +118,28 @@ async fn api_client_basics() {
         )
         .unwrap();
 
+    let mut multiproof_keys = keys.clone();
+    multiproof_keys.push(H256::repeat_byte(1)); // duplicate key
+    let multiproof = api_client
+        .get_multiproof(L1BatchNumber(0), multiproof_keys)
+        .await
+        .unwrap();
+    let multiproof_keys: Vec<_> = multiproof.entries.iter().map(|(key, _)| *key).collect();
+    assert_eq!(multiproof_keys, keys); // `keys` are already sorted
+    multiproof
+        .verify(
+            &Blake2Hasher,
+            <DefaultTreeParams>::TREE_DEPTH,
+            info.root_hash,
+        )
+        .unwrap();
+
+    let err = api_client
+        .get_multiproof(L1BatchNumber(1), keys.clone())
+        .await
+        .unwrap_err();
+    assert_matches!(err, TreeApiError::NoVersion);
+
     // Check raw nodes API
     let request = serde_json::json!({
         "keys": [
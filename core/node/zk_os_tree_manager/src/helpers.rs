@@ -2,7 +2,7 @@ use anyhow::Context as _;
 use tokio::sync::watch;
 use zk_os_merkle_tree::{
     unstable, BatchTreeProof, MerkleTree, MerkleTreeColumnFamily, MerkleTreeReader, Patched,
-    RocksDBWrapper,
+    RocksDBWrapper, TreeEntriesWithMultiproof,
 };
 use zksync_storage::{RocksDB, RocksDBOptions, StalledWritesRetries, WeakRocksDB};
 use zksync_types::{block::L1BatchTreeData, L1BatchNumber, H256};
@@ -67,6 +67,16 @@ impl AsyncTreeReader {
             .context("getting proof panicked")?
     }
 
+    pub(crate) async fn entries_with_multiproof(
+        self,
+        version: u64,
+        keys: Vec<H256>,
+    ) -> anyhow::Result<TreeEntriesWithMultiproof> {
+        tokio::task::spawn_blocking(move || self.inner.entries_with_multiproof(version, &keys))
+            .await
+            .context("getting multiproof panicked")?
+    }
+
     pub(crate) async fn raw_nodes(
         self,
         keys: Vec<unstable::NodeKey>,
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, hash::Hash, time::Duration};
+use std::{collections::HashMap, hash::Hash, path::PathBuf, time::Duration};
 
 use serde::{Deserialize, Serialize};
 use smart_config::{
@@ -67,7 +67,11 @@ pub struct ProverAutoscalerScalerConfig {
     #[config(default_t = "http://localhost:3074/queue_report".to_string())]
     pub prover_job_monitor_url: String,
     /// List of ProverAutoscaler Agent base URLs to get cluster data from.
+    #[config(default)]
     pub agents: Vec<String>,
+    /// Non-Kubernetes cluster backends, used in addition to `agents`.
+    #[config(default)]
+    pub backends: Vec<ScalerBackendConfig>,
     /// Mapping of namespaces to protocol versions.
     pub protocol_versions: HashMap<NamespaceName, String>,
     /// Default priorities, which cluster to prefer when there is no other information.
@@ -198,6 +202,52 @@ impl ScalerTarget {
     }
 }
 
+/// Cluster backend for the global Scaler not backed by a ProverAutoscaler Agent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScalerBackendConfig {
+    /// Delegates scaling to an HTTP webhook.
+    Webhook(WebhookBackendConfig),
+    /// Runs replicas as processes spawned by the Scaler.
+    Process(ProcessBackendConfig),
+}
+
+impl WellKnown for ScalerBackendConfig {
+    type Deserializer = Serde![object];
+    const DE: Self::Deserializer = Serde![object];
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookBackendConfig {
+    /// Name of the cluster, as used in `cluster_priorities` and `max_replicas`.
+    pub cluster_name: ClusterName,
+    /// URL to `POST` scale requests to.
+    pub scale_url: String,
+    /// Optional URL returning the cluster state in the Agent `/cluster` format. If not set, replicas
+    /// from the last successful scale requests are assumed to be running.
+    #[serde(default)]
+    pub status_url: Option<String>,
+    /// Deployments managed by the webhook, per namespace.
+    pub deployments: HashMap<NamespaceName, Vec<DeploymentName>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessBackendConfig {
+    /// Name of the cluster, as used in `cluster_priorities` and `max_replicas`.
+    pub cluster_name: ClusterName,
+    /// Commands to run for each deployment, per namespace. Each replica runs as a separate process.
+    pub deployments: HashMap<NamespaceName, HashMap<DeploymentName, ProcessCommand>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessCommand {
+    pub program: PathBuf,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
 #[cfg(test)]
 mod tests {
     use smart_config::{testing::test_complete, Yaml};
@@ -222,6 +272,19 @@ mod tests {
               prover_job_monitor_url: http://prover_job_monitor_url/queue_report
               agents:
                 - http://prover-autoscaler/
+              backends:
+                - type: webhook
+                  cluster_name: bare-metal
+                  scale_url: http://nomad-adapter/scale
+                  deployments:
+                    prover-blue: [circuit-prover-gpu]
+                - type: process
+                  cluster_name: local
+                  deployments:
+                    prover-blue:
+                      witness-generator-basic-fri:
+                        program: /usr/bin/zksync_witness_generator
+                        args: [--all_rounds]
               scaler_run_interval: 60s
               protocol_versions:
                 prover-blue: 0.26.0
@@ -318,6 +381,25 @@ mod tests {
             Duration::from_secs(600)
         );
         assert_eq!(scaler_config.scaler_targets.len(), 7);
+        assert_eq!(scaler_config.backends.len(), 2);
+        assert_eq!(
+            scaler_config.backends[0],
+            ScalerBackendConfig::Webhook(WebhookBackendConfig {
+                cluster_name: "bare-metal".into(),
+                scale_url: "http://nomad-adapter/scale".into(),
+                status_url: None,
+                deployments: HashMap::from([(
+                    "prover-blue".into(),
+                    vec!["circuit-prover-gpu".into()]
+                )]),
+            })
+        );
+        let ScalerBackendConfig::Process(process_config) = &scaler_config.backends[1] else {
+            panic!("unexpected backend: {:?}", scaler_config.backends[1]);
+        };
+        let command = &process_config.deployments[&NamespaceName::from("prover-blue")]
+            [&DeploymentName::from("witness-generator-basic-fri")];
+        assert_eq!(command.args, ["--all_rounds"]);
         assert_eq!(scaler_config.aggressive_mode_threshold, 50);
         assert_eq!(
             scaler_config.aggressive_mode_cooldown,
@@ -0,0 +1,86 @@
+use anyhow::Context as _;
+use reqwest::{
+    header::{HeaderMap, HeaderValue, CONTENT_TYPE},
+    Method,
+};
+use url::Url;
+
+use super::ScalerBackend;
+use crate::{
+    agent::{ScaleRequest, ScaleResponse},
+    cluster_types::Cluster,
+    http_client::HttpClient,
+};
+
+/// Backend talking to a ProverAutoscaler Agent, which manages deployments in a Kubernetes cluster.
+#[derive(Clone)]
+pub struct AgentBackend {
+    url: Url,
+    http_client: HttpClient,
+}
+
+impl std::fmt::Debug for AgentBackend {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("AgentBackend")
+            .field("url", &self.url.as_str())
+            .finish_non_exhaustive()
+    }
+}
+
+impl AgentBackend {
+    pub fn new(url: &str, http_client: HttpClient) -> anyhow::Result<Self> {
+        let url = Url::parse(url).with_context(|| format!("Unparsable Agent URL {url}"))?;
+        Ok(Self { url, http_client })
+    }
+}
+
+#[async_trait::async_trait]
+impl ScalerBackend for AgentBackend {
+    fn id(&self) -> String {
+        self.url.to_string()
+    }
+
+    async fn get_cluster(&self) -> anyhow::Result<Cluster> {
+        let url = self
+            .url
+            .join("/cluster")
+            .context("Failed to join URL with /cluster")?
+            .to_string();
+        tracing::debug!("Getting cluster data from agent {url}");
+        let response = self
+            .http_client
+            .send_request_with_retries(&url, Method::GET, None, None)
+            .await
+            .map_err(|err| anyhow::anyhow!("Failed fetching cluster data from {url}: {err:?}"))?;
+        response
+            .json::<Cluster>()
+            .await
+            .with_context(|| format!("Failed to parse JSON cluster data from {url}"))
+    }
+
+    async fn scale(&self, request: ScaleRequest) -> anyhow::Result<ScaleResponse> {
+        let url = self
+            .url
+            .join("/scale")
+            .context("Failed to join URL with /scale")?
+            .to_string();
+        tracing::debug!("Sending scale request to {url}, data: {request:?}");
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        let response = self
+            .http_client
+            .send_request_with_retries(
+                &url,
+                Method::POST,
+                Some(headers),
+                Some(serde_json::to_vec(&request)?),
+            )
+            .await
+            .map_err(|err| anyhow::anyhow!("Failed sending scale request to {url}: {err:?}"))?;
+        response
+            .json::<ScaleResponse>()
+            .await
+            .context("Failed to read response as json")
+    }
+}
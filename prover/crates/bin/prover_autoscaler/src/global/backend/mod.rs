@@ -0,0 +1,62 @@
+//! Backends used by the global Scaler to observe and scale prover clusters.
+//!
+//! The scaling decisions (see [`super::scaler`]) are computed from the queue and the [`Cluster`] state
+//! reported by backends, so they don't depend on how a cluster is actually run. Supported backends are:
+//!
+//! - [`AgentBackend`]: a ProverAutoscaler Agent running in a Kubernetes cluster;
+//! - [`WebhookBackend`]: a generic HTTP webhook, e.g. a thin adapter over Nomad or systemd;
+//! - [`ProcessBackend`]: local processes spawned by the Scaler itself.
+
+use std::{fmt, sync::Arc};
+
+pub use self::{agent::AgentBackend, process::ProcessBackend, webhook::WebhookBackend};
+use crate::{
+    agent::{ScaleRequest, ScaleResponse},
+    cluster_types::Cluster,
+    config::{ProverAutoscalerScalerConfig, ScalerBackendConfig},
+    http_client::HttpClient,
+};
+
+mod agent;
+mod process;
+#[cfg(test)]
+mod tests;
+mod webhook;
+
+/// Cluster backend used by the global Scaler.
+#[async_trait::async_trait]
+pub trait ScalerBackend: fmt::Debug + Send + Sync {
+    /// Identifier of the backend used in logs and metrics (e.g., the agent URL).
+    fn id(&self) -> String;
+
+    /// Returns the current state of the cluster managed by this backend.
+    async fn get_cluster(&self) -> anyhow::Result<Cluster>;
+
+    /// Scales deployments in the cluster. Per-deployment errors are returned in the response, in the same
+    /// order as deployments in the request; an empty string means success.
+    async fn scale(&self, request: ScaleRequest) -> anyhow::Result<ScaleResponse>;
+}
+
+/// Creates backends for all agents and backends specified in the config.
+pub fn create_backends(
+    config: &ProverAutoscalerScalerConfig,
+    http_client: &HttpClient,
+) -> anyhow::Result<Vec<Arc<dyn ScalerBackend>>> {
+    let mut backends: Vec<Arc<dyn ScalerBackend>> = vec![];
+    for url in &config.agents {
+        backends.push(Arc::new(AgentBackend::new(url, http_client.clone())?));
+    }
+    for backend_config in &config.backends {
+        backends.push(match backend_config {
+            ScalerBackendConfig::Webhook(config) => {
+                Arc::new(WebhookBackend::new(config.clone(), http_client.clone())?)
+            }
+            ScalerBackendConfig::Process(config) => Arc::new(ProcessBackend::new(config.clone())),
+        });
+    }
+    anyhow::ensure!(
+        !backends.is_empty(),
+        "no agents or scaler backends configured"
+    );
+    Ok(backends)
+}
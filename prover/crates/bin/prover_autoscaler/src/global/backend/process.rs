@@ -0,0 +1,204 @@
+use std::{
+    collections::HashMap,
+    process::Stdio,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use anyhow::Context as _;
+use chrono::{DateTime, Utc};
+use tokio::{
+    process::{Child, Command},
+    sync::Mutex,
+};
+
+use super::ScalerBackend;
+use crate::{
+    agent::{ScaleRequest, ScaleResponse},
+    cluster_types::{Cluster, Deployment, DeploymentName, Namespace, NamespaceName, Pod},
+    config::ProcessBackendConfig,
+};
+
+#[derive(Debug)]
+struct Replica {
+    id: usize,
+    child: Child,
+    started_at: DateTime<Utc>,
+}
+
+/// Backend running replicas as local processes spawned by the Scaler.
+///
+/// Each replica of a deployment is a separate process started with the command configured for the deployment.
+/// The process receives `PROVER_AUTOSCALER_NAMESPACE`, `PROVER_AUTOSCALER_DEPLOYMENT` and `PROVER_AUTOSCALER_REPLICA`
+/// env variables. Excess replicas are killed, newest first; replicas are also killed when the Scaler exits.
+/// Replicas that exit on their own are not restarted by the backend; instead, they are no longer reported,
+/// so that the Scaler requests replacements on its next run.
+#[derive(Debug)]
+pub struct ProcessBackend {
+    config: ProcessBackendConfig,
+    replicas: Mutex<HashMap<(NamespaceName, DeploymentName), Vec<Replica>>>,
+    next_replica_id: AtomicUsize,
+}
+
+impl ProcessBackend {
+    pub fn new(config: ProcessBackendConfig) -> Self {
+        Self {
+            config,
+            replicas: Mutex::default(),
+            next_replica_id: AtomicUsize::new(0),
+        }
+    }
+
+    fn spawn_replica(
+        &self,
+        namespace: &NamespaceName,
+        deployment: &DeploymentName,
+    ) -> anyhow::Result<Replica> {
+        let command_config = self
+            .config
+            .deployments
+            .get(namespace)
+            .and_then(|deployments| deployments.get(deployment))
+            .with_context(|| format!("deployment {namespace}/{deployment} is not configured"))?;
+        let id = self.next_replica_id.fetch_add(1, Ordering::Relaxed);
+        let child = Command::new(&command_config.program)
+            .args(&command_config.args)
+            .envs(&command_config.env)
+            .env("PROVER_AUTOSCALER_NAMESPACE", namespace.to_str())
+            .env("PROVER_AUTOSCALER_DEPLOYMENT", deployment.to_str())
+            .env("PROVER_AUTOSCALER_REPLICA", id.to_string())
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| {
+                format!(
+                    "failed spawning `{}` for {namespace}/{deployment}",
+                    command_config.program.display()
+                )
+            })?;
+        tracing::info!(
+            "Spawned replica {id} of {namespace}/{deployment} (pid {:?})",
+            child.id()
+        );
+        Ok(Replica {
+            id,
+            child,
+            started_at: Utc::now(),
+        })
+    }
+
+    async fn scale_deployment(
+        &self,
+        replicas: &mut Vec<Replica>,
+        namespace: &NamespaceName,
+        deployment: &DeploymentName,
+        size: usize,
+    ) -> anyhow::Result<()> {
+        while replicas.len() < size {
+            replicas.push(self.spawn_replica(namespace, deployment)?);
+        }
+        while replicas.len() > size {
+            let mut replica = replicas.pop().unwrap();
+            if let Err(err) = replica.child.kill().await {
+                tracing::warn!(
+                    "Failed killing replica {} of {namespace}/{deployment}: {err}",
+                    replica.id
+                );
+            }
+        }
+        tracing::info!("Scaled {namespace}/{deployment} to {size} replica(s)");
+        Ok(())
+    }
+}
+
+/// Removes replicas that have exited.
+fn reap_exited(
+    namespace: &NamespaceName,
+    deployment: &DeploymentName,
+    replicas: &mut Vec<Replica>,
+) {
+    replicas.retain_mut(|replica| match replica.child.try_wait() {
+        Ok(None) => true,
+        Ok(Some(status)) => {
+            tracing::warn!(
+                "Replica {} of {namespace}/{deployment} exited with {status}",
+                replica.id
+            );
+            false
+        }
+        Err(err) => {
+            tracing::warn!(
+                "Failed checking status of replica {} of {namespace}/{deployment}: {err}",
+                replica.id
+            );
+            true
+        }
+    });
+}
+
+#[async_trait::async_trait]
+impl ScalerBackend for ProcessBackend {
+    fn id(&self) -> String {
+        format!("process:{}", self.config.cluster_name)
+    }
+
+    async fn get_cluster(&self) -> anyhow::Result<Cluster> {
+        let mut all_replicas = self.replicas.lock().await;
+        let mut namespaces = HashMap::new();
+        for (namespace, deployments) in &self.config.deployments {
+            let namespace_value: &mut Namespace = namespaces.entry(namespace.clone()).or_default();
+            for deployment in deployments.keys() {
+                let replicas = all_replicas
+                    .entry((namespace.clone(), deployment.clone()))
+                    .or_default();
+                reap_exited(namespace, deployment, replicas);
+
+                namespace_value.deployments.insert(
+                    deployment.clone(),
+                    Deployment {
+                        running: replicas.len(),
+                        desired: replicas.len(),
+                    },
+                );
+                for replica in replicas.iter() {
+                    namespace_value.pods.insert(
+                        format!("{deployment}-{}", replica.id),
+                        Pod {
+                            owner: deployment.to_string(),
+                            status: "Running".to_owned(),
+                            changed: replica.started_at,
+                            out_of_resources: false,
+                        },
+                    );
+                }
+            }
+        }
+        Ok(Cluster {
+            name: self.config.cluster_name.clone(),
+            namespaces,
+        })
+    }
+
+    async fn scale(&self, request: ScaleRequest) -> anyhow::Result<ScaleResponse> {
+        let mut all_replicas = self.replicas.lock().await;
+        let mut scale_result = Vec::with_capacity(request.deployments.len());
+        for deployment in &request.deployments {
+            let replicas = all_replicas
+                .entry((deployment.namespace.clone(), deployment.name.clone()))
+                .or_default();
+            reap_exited(&deployment.namespace, &deployment.name, replicas);
+            let result = self
+                .scale_deployment(
+                    replicas,
+                    &deployment.namespace,
+                    &deployment.name,
+                    deployment.size,
+                )
+                .await;
+            scale_result.push(match result {
+                Ok(()) => String::new(),
+                Err(err) => format!("{err:#}"),
+            });
+        }
+        Ok(ScaleResponse { scale_result })
+    }
+}
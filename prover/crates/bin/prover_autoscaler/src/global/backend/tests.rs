@@ -0,0 +1,276 @@
+use std::{collections::HashMap, net::SocketAddr, sync::Arc};
+
+use axum::{
+    extract::State,
+    routing::{get, post},
+    Json, Router,
+};
+use tokio::sync::Mutex;
+
+use super::*;
+use crate::{
+    agent::ScaleDeploymentRequest,
+    cluster_types::{Deployment, DeploymentName, Namespace, NamespaceName},
+    config::{ProcessBackendConfig, ProcessCommand, WebhookBackendConfig},
+    global::watcher::Watcher,
+};
+
+/// Local stub server recording requests sent to it.
+#[derive(Debug, Clone, Default)]
+struct StubServer {
+    scale_requests: Arc<Mutex<Vec<serde_json::Value>>>,
+    cluster: Arc<Mutex<Cluster>>,
+}
+
+impl StubServer {
+    /// Starts the server on a random local port. `scale_response` is returned verbatim from `/scale`.
+    async fn spawn(self, scale_response: Option<ScaleResponse>) -> SocketAddr {
+        async fn scale(
+            State((server, response)): State<(StubServer, String)>,
+            Json(payload): Json<serde_json::Value>,
+        ) -> String {
+            server.scale_requests.lock().await.push(payload);
+            response
+        }
+
+        async fn cluster(State((server, _)): State<(StubServer, String)>) -> Json<Cluster> {
+            Json(server.cluster.lock().await.clone())
+        }
+
+        let scale_response = scale_response
+            .map(|response| serde_json::to_string(&response).unwrap())
+            .unwrap_or_default();
+        let router = Router::new()
+            .route("/scale", post(scale))
+            .route("/cluster", get(cluster))
+            .with_state((self, scale_response));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        local_addr
+    }
+}
+
+fn scale_request(deployments: &[(&str, usize)]) -> ScaleRequest {
+    ScaleRequest {
+        deployments: deployments
+            .iter()
+            .map(|&(name, size)| ScaleDeploymentRequest {
+                namespace: "prover-blue".into(),
+                name: name.into(),
+                size,
+            })
+            .collect(),
+    }
+}
+
+fn deployment<'a>(cluster: &'a Cluster, name: &str) -> &'a Deployment {
+    &cluster.namespaces[&NamespaceName::from("prover-blue")].deployments
+        [&DeploymentName::from(name)]
+}
+
+#[tokio::test]
+async fn agent_backend_with_stub_server() {
+    let server = StubServer::default();
+    *server.cluster.lock().await = Cluster {
+        name: "gke".into(),
+        namespaces: HashMap::from([("prover-blue".into(), Namespace::default())]),
+    };
+    let scale_response = ScaleResponse {
+        scale_result: vec![String::new()],
+    };
+    let addr = server.clone().spawn(Some(scale_response)).await;
+
+    let backend = AgentBackend::new(&format!("http://{addr}/"), HttpClient::default()).unwrap();
+    let cluster = backend.get_cluster().await.unwrap();
+    assert_eq!(cluster.name, "gke".into());
+
+    let response = backend
+        .scale(scale_request(&[("circuit-prover-gpu", 3)]))
+        .await
+        .unwrap();
+    assert_eq!(response.scale_result, [""]);
+    let requests = server.scale_requests.lock().await;
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0]["deployments"][0]["size"], 3);
+}
+
+#[tokio::test]
+async fn webhook_backend_with_stub_server() {
+    let server = StubServer::default();
+    let addr = server.clone().spawn(None).await;
+    let config = WebhookBackendConfig {
+        cluster_name: "bare-metal".into(),
+        scale_url: format!("http://{addr}/scale"),
+        status_url: None,
+        deployments: HashMap::from([(
+            "prover-blue".into(),
+            vec![
+                "circuit-prover-gpu".into(),
+                "circuit-prover-gpu-h100".into(),
+            ],
+        )]),
+    };
+    let backend = WebhookBackend::new(config, HttpClient::default()).unwrap();
+
+    let cluster = backend.get_cluster().await.unwrap();
+    assert_eq!(cluster.name, "bare-metal".into());
+    assert_eq!(deployment(&cluster, "circuit-prover-gpu").desired, 0);
+    assert!(cluster.namespaces[&NamespaceName::from("prover-blue")]
+        .pods
+        .is_empty());
+
+    let response = backend
+        .scale(scale_request(&[("circuit-prover-gpu-h100", 2)]))
+        .await
+        .unwrap();
+    assert_eq!(response.scale_result, [""]);
+    let requests = server.scale_requests.lock().await.clone();
+    assert_eq!(
+        requests,
+        [serde_json::json!({
+            "cluster": "bare-metal",
+            "deployments": [{
+                "namespace": "prover-blue",
+                "name": "circuit-prover-gpu-h100",
+                "size": 2,
+            }],
+        })]
+    );
+
+    let cluster = backend.get_cluster().await.unwrap();
+    let h100 = deployment(&cluster, "circuit-prover-gpu-h100");
+    assert_eq!((h100.running, h100.desired), (2, 2));
+    let pods = &cluster.namespaces[&NamespaceName::from("prover-blue")].pods;
+    assert_eq!(pods.len(), 2);
+    assert!(pods.contains_key("circuit-prover-gpu-h100-0"));
+}
+
+#[tokio::test]
+async fn webhook_backend_with_status_url() {
+    let server = StubServer::default();
+    let mut namespace = Namespace::default();
+    namespace.deployments.insert(
+        "circuit-prover-gpu".into(),
+        Deployment {
+            running: 1,
+            desired: 2,
+        },
+    );
+    *server.cluster.lock().await = Cluster {
+        name: "nomad".into(),
+        namespaces: HashMap::from([("prover-blue".into(), namespace)]),
+    };
+    let scale_response = ScaleResponse {
+        scale_result: vec!["no capacity".into()],
+    };
+    let addr = server.clone().spawn(Some(scale_response)).await;
+    let config = WebhookBackendConfig {
+        cluster_name: "bare-metal".into(),
+        scale_url: format!("http://{addr}/scale"),
+        status_url: Some(format!("http://{addr}/cluster")),
+        deployments: HashMap::new(),
+    };
+    let backend = WebhookBackend::new(config, HttpClient::default()).unwrap();
+
+    let cluster = backend.get_cluster().await.unwrap();
+    assert_eq!(cluster.name, "bare-metal".into());
+    let prover = deployment(&cluster, "circuit-prover-gpu");
+    assert_eq!((prover.running, prover.desired), (1, 2));
+
+    let response = backend
+        .scale(scale_request(&[("circuit-prover-gpu", 5)]))
+        .await
+        .unwrap();
+    assert_eq!(response.scale_result, ["no capacity"]);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn process_backend_spawns_and_kills_replicas() {
+    let command = ProcessCommand {
+        program: "sleep".into(),
+        args: vec!["60".into()],
+        env: HashMap::new(),
+    };
+    let config = ProcessBackendConfig {
+        cluster_name: "local".into(),
+        deployments: HashMap::from([(
+            "prover-blue".into(),
+            HashMap::from([("witness-generator-basic-fri".into(), command)]),
+        )]),
+    };
+    let backend = ProcessBackend::new(config);
+
+    let cluster = backend.get_cluster().await.unwrap();
+    assert_eq!(cluster.name, "local".into());
+    assert_eq!(
+        deployment(&cluster, "witness-generator-basic-fri").running,
+        0
+    );
+
+    let response = backend
+        .scale(scale_request(&[
+            ("witness-generator-basic-fri", 3),
+            ("unknown", 1),
+        ]))
+        .await
+        .unwrap();
+    assert_eq!(response.scale_result[0], "");
+    assert!(
+        response.scale_result[1].contains("not configured"),
+        "{response:?}"
+    );
+
+    let cluster = backend.get_cluster().await.unwrap();
+    let witness_generator = deployment(&cluster, "witness-generator-basic-fri");
+    assert_eq!(
+        (witness_generator.running, witness_generator.desired),
+        (3, 3)
+    );
+    assert_eq!(
+        cluster.namespaces[&NamespaceName::from("prover-blue")]
+            .pods
+            .len(),
+        3
+    );
+
+    let response = backend
+        .scale(scale_request(&[("witness-generator-basic-fri", 1)]))
+        .await
+        .unwrap();
+    assert_eq!(response.scale_result, [""]);
+    let cluster = backend.get_cluster().await.unwrap();
+    assert_eq!(
+        deployment(&cluster, "witness-generator-basic-fri").running,
+        1
+    );
+}
+
+#[tokio::test]
+async fn watcher_routes_scale_requests_to_backends() {
+    let server = StubServer::default();
+    let addr = server.clone().spawn(None).await;
+    let webhook = WebhookBackend::new(
+        WebhookBackendConfig {
+            cluster_name: "bare-metal".into(),
+            scale_url: format!("http://{addr}/scale"),
+            status_url: None,
+            deployments: HashMap::from([("prover-blue".into(), vec!["circuit-prover-gpu".into()])]),
+        },
+        HttpClient::default(),
+    )
+    .unwrap();
+    let watcher = Watcher::new(vec![Arc::new(webhook)], false);
+    for poller in watcher.create_poller_tasks() {
+        zksync_prover_task::Task::invoke(&poller).await.unwrap();
+    }
+    watcher.check_is_ready(&*watcher.data.lock().await).unwrap();
+
+    let requests = HashMap::from([(
+        "bare-metal".into(),
+        scale_request(&[("circuit-prover-gpu", 4)]),
+    )]);
+    watcher.send_scale(requests).await.unwrap();
+    assert_eq!(server.scale_requests.lock().await.len(), 1);
+}
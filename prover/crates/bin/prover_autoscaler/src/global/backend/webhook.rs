@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+use chrono::{DateTime, Utc};
+use reqwest::{
+    header::{HeaderMap, HeaderValue, CONTENT_TYPE},
+    Method,
+};
+use serde::Serialize;
+use tokio::sync::Mutex;
+use url::Url;
+
+use super::ScalerBackend;
+use crate::{
+    agent::{ScaleRequest, ScaleResponse},
+    cluster_types::{
+        Cluster, ClusterName, Deployment, DeploymentName, Namespace, NamespaceName, Pod,
+    },
+    config::WebhookBackendConfig,
+    http_client::HttpClient,
+};
+
+/// Body of the scale request sent to the webhook.
+#[derive(Debug, Serialize)]
+struct WebhookScaleRequest<'a> {
+    cluster: &'a ClusterName,
+    #[serde(flatten)]
+    request: &'a ScaleRequest,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DesiredReplicas {
+    size: usize,
+    changed: DateTime<Utc>,
+}
+
+/// Backend delegating scaling to an arbitrary HTTP webhook.
+///
+/// Scale requests are `POST`ed to the webhook as JSON with the cluster name and the list of deployments
+/// (same as the Agent `/scale` payload). The webhook may respond with an empty body or with the Agent
+/// `/scale` response listing per-deployment errors. The cluster state is fetched from the status URL
+/// (in the Agent `/cluster` format) if it's configured; otherwise, all replicas requested by the last
+/// successful scale request are assumed to be running.
+pub struct WebhookBackend {
+    config: WebhookBackendConfig,
+    scale_url: Url,
+    status_url: Option<Url>,
+    http_client: HttpClient,
+    desired: Mutex<HashMap<(NamespaceName, DeploymentName), DesiredReplicas>>,
+}
+
+impl std::fmt::Debug for WebhookBackend {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("WebhookBackend")
+            .field("cluster_name", &self.config.cluster_name)
+            .field("scale_url", &self.scale_url.as_str())
+            .field("status_url", &self.status_url.as_ref().map(Url::as_str))
+            .finish_non_exhaustive()
+    }
+}
+
+impl WebhookBackend {
+    pub fn new(config: WebhookBackendConfig, http_client: HttpClient) -> anyhow::Result<Self> {
+        let scale_url = Url::parse(&config.scale_url)
+            .with_context(|| format!("Unparsable webhook URL {}", config.scale_url))?;
+        let status_url = config
+            .status_url
+            .as_deref()
+            .map(|url| Url::parse(url).with_context(|| format!("Unparsable status URL {url}")))
+            .transpose()?;
+        Ok(Self {
+            config,
+            scale_url,
+            status_url,
+            http_client,
+            desired: Mutex::default(),
+        })
+    }
+
+    async fn derived_cluster(&self) -> Cluster {
+        let desired = self.desired.lock().await;
+        let namespaces = self
+            .config
+            .deployments
+            .iter()
+            .map(|(namespace, deployments)| {
+                let mut namespace_value = Namespace::default();
+                for deployment in deployments {
+                    let replicas = desired.get(&(namespace.clone(), deployment.clone()));
+                    let size = replicas.map_or(0, |replicas| replicas.size);
+                    namespace_value.deployments.insert(
+                        deployment.clone(),
+                        Deployment {
+                            running: size,
+                            desired: size,
+                        },
+                    );
+                    for i in 0..size {
+                        namespace_value.pods.insert(
+                            format!("{deployment}-{i}"),
+                            Pod {
+                                owner: deployment.to_string(),
+                                status: "Running".to_owned(),
+                                changed: replicas
+                                    .map_or_else(Utc::now, |replicas| replicas.changed),
+                                out_of_resources: false,
+                            },
+                        );
+                    }
+                }
+                (namespace.clone(), namespace_value)
+            })
+            .collect();
+        Cluster {
+            name: self.config.cluster_name.clone(),
+            namespaces,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ScalerBackend for WebhookBackend {
+    fn id(&self) -> String {
+        self.scale_url.to_string()
+    }
+
+    async fn get_cluster(&self) -> anyhow::Result<Cluster> {
+        let Some(status_url) = &self.status_url else {
+            return Ok(self.derived_cluster().await);
+        };
+        let response = self
+            .http_client
+            .send_request_with_retries(status_url.as_str(), Method::GET, None, None)
+            .await
+            .map_err(|err| {
+                anyhow::anyhow!("Failed fetching cluster data from {status_url}: {err:?}")
+            })?;
+        let mut cluster = response
+            .json::<Cluster>()
+            .await
+            .with_context(|| format!("Failed to parse JSON cluster data from {status_url}"))?;
+        // The cluster name is used to match the cluster with the config, so it must be consistent.
+        cluster.name = self.config.cluster_name.clone();
+        Ok(cluster)
+    }
+
+    async fn scale(&self, request: ScaleRequest) -> anyhow::Result<ScaleResponse> {
+        let body = WebhookScaleRequest {
+            cluster: &self.config.cluster_name,
+            request: &request,
+        };
+        tracing::debug!(
+            "Sending scale request to webhook {}: {body:?}",
+            self.scale_url
+        );
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        let response = self
+            .http_client
+            .send_request_with_retries(
+                self.scale_url.as_str(),
+                Method::POST,
+                Some(headers),
+                Some(serde_json::to_vec(&body)?),
+            )
+            .await
+            .map_err(|err| {
+                anyhow::anyhow!(
+                    "Failed sending scale request to {}: {err:?}",
+                    self.scale_url
+                )
+            })?;
+        let response_body = response
+            .bytes()
+            .await
+            .context("Failed to read webhook response")?;
+        let response = if response_body.iter().all(u8::is_ascii_whitespace) {
+            ScaleResponse {
+                scale_result: vec![String::new(); request.deployments.len()],
+            }
+        } else {
+            serde_json::from_slice(&response_body).context("Failed to read response as json")?
+        };
+
+        let mut desired = self.desired.lock().await;
+        let now = Utc::now();
+        for (deployment, result) in request.deployments.iter().zip(&response.scale_result) {
+            if result.is_empty() {
+                desired.insert(
+                    (deployment.namespace.clone(), deployment.name.clone()),
+                    DesiredReplicas {
+                        size: deployment.size,
+                        changed: now,
+                    },
+                );
+            }
+        }
+        Ok(response)
+    }
+}
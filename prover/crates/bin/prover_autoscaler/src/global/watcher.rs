@@ -1,19 +1,14 @@
-use std::{collections::HashMap, result::Result, sync::Arc};
+use std::{collections::HashMap, sync::Arc};
 
-use anyhow::{anyhow, Context, Ok};
+use anyhow::anyhow;
 use futures::future;
-use reqwest::{
-    header::{HeaderMap, HeaderValue, CONTENT_TYPE},
-    Method,
-};
 use tokio::sync::Mutex;
-use url::Url;
 use zksync_prover_task::Task;
 
+use super::backend::ScalerBackend;
 use crate::{
     agent::{ScaleRequest, ScaleResponse},
-    cluster_types::{Cluster, ClusterName, Clusters},
-    http_client::HttpClient,
+    cluster_types::{ClusterName, Clusters},
     metrics::AUTOSCALER_METRICS,
 };
 
@@ -25,27 +20,17 @@ pub struct WatchedData {
 
 #[derive(Clone)]
 pub struct Watcher {
-    http_client: HttpClient,
-    /// List of base URLs of all agents.
-    pub cluster_agents: Vec<Arc<Url>>,
+    /// Backends for all clusters (e.g., ProverAutoscaler Agents).
+    pub backends: Vec<Arc<dyn ScalerBackend>>,
     pub dry_run: bool,
     pub data: Arc<Mutex<WatchedData>>,
 }
 
 impl Watcher {
-    pub fn new(http_client: HttpClient, agent_urls: Vec<String>, dry_run: bool) -> Self {
-        let size = agent_urls.len();
+    pub fn new(backends: Vec<Arc<dyn ScalerBackend>>, dry_run: bool) -> Self {
+        let size = backends.len();
         Self {
-            http_client,
-            cluster_agents: agent_urls
-                .into_iter()
-                .map(|u| {
-                    Arc::new(
-                        Url::parse(&u)
-                            .unwrap_or_else(|e| panic!("Unparsable Agent URL {}: {}", u, e)),
-                    )
-                })
-                .collect(),
+            backends,
             dry_run,
             data: Arc::new(Mutex::new(WatchedData {
                 clusters: Clusters::default(),
@@ -60,9 +45,9 @@ impl Watcher {
             if *is_agent_ready {
                 ready_count += 1;
             } else {
-                let agent_url_str = self.cluster_agents[id].to_string();
-                AUTOSCALER_METRICS.agent_not_ready[&agent_url_str].inc();
-                tracing::warn!("Agent at URL '{}' (id {}) is not ready.", agent_url_str, id);
+                let backend_id = self.backends[id].id();
+                AUTOSCALER_METRICS.agent_not_ready[&backend_id].inc();
+                tracing::warn!("Agent '{}' (id {}) is not ready.", backend_id, id);
             }
         }
 
@@ -105,36 +90,15 @@ impl Watcher {
         let handles: Vec<_> = id_requests
             .into_iter()
             .map(|(id, sr)| {
-                let url: String = self.cluster_agents[id]
-                    .clone()
-                    .join("/scale")
-                    .unwrap()
-                    .to_string();
-                tracing::debug!("Sending scale request to {}, data: {:?}", url, sr);
-                let http_client = self.http_client.clone();
+                let backend = self.backends[id].clone();
+                tracing::debug!("Sending scale request to {}, data: {:?}", backend.id(), sr);
                 tokio::spawn(async move {
-                    let mut headers = HeaderMap::new();
-                    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
                     if dry_run {
                         tracing::info!("Dry-run mode, not sending the request.");
-                        return Ok((id, Ok(ScaleResponse::default())));
+                        return Ok((id, ScaleResponse::default()));
                     }
-                    let response = http_client
-                        .send_request_with_retries(
-                            &url,
-                            Method::POST,
-                            Some(headers),
-                            Some(serde_json::to_vec(&sr)?),
-                        )
-                        .await;
-                    let response = response.map_err(|err| {
-                        anyhow::anyhow!("Failed fetching cluster from url: {url}: {err:?}")
-                    })?;
-                    let response = response
-                        .json::<ScaleResponse>()
-                        .await
-                        .context("Failed to read response as json");
-                    Ok((id, response))
+                    let response = backend.scale(sr).await?;
+                    anyhow::Ok((id, response))
                 })
             })
             .collect();
@@ -147,7 +111,6 @@ impl Watcher {
                     let (id, res) = h??;
 
                     let errors: Vec<_> = res
-                        .expect("failed to do request to Agent")
                         .scale_result
                         .iter()
                         .filter_map(|e| {
@@ -172,30 +135,28 @@ impl Watcher {
     }
 
     pub fn create_poller_tasks(&self) -> impl Iterator<Item = AgentPoller> + '_ {
-        self.cluster_agents.iter().enumerate().map(|(id, url)| {
-            AgentPoller::new(id, url.clone(), self.http_client.clone(), self.data.clone())
-        })
+        self.backends
+            .iter()
+            .enumerate()
+            .map(|(id, backend)| AgentPoller::new(id, backend.clone(), self.data.clone()))
     }
 }
 
 pub struct AgentPoller {
     agent_id: usize,
-    agent_url: Arc<Url>,
-    http_client: HttpClient,
+    backend: Arc<dyn ScalerBackend>,
     data: Arc<Mutex<WatchedData>>,
 }
 
 impl AgentPoller {
     pub fn new(
         agent_id: usize,
-        agent_url: Arc<Url>,
-        http_client: HttpClient,
+        backend: Arc<dyn ScalerBackend>,
         data: Arc<Mutex<WatchedData>>,
     ) -> Self {
         Self {
             agent_id,
-            agent_url,
-            http_client,
+            backend,
             data,
         }
     }
@@ -204,53 +165,31 @@ impl AgentPoller {
 #[async_trait::async_trait]
 impl Task for AgentPoller {
     async fn invoke(&self) -> anyhow::Result<()> {
-        let url = self
-            .agent_url
-            .join("/cluster")
-            .context("Failed to join URL with /cluster")?
-            .to_string();
-        let agent_url = self.agent_url.to_string();
-
-        tracing::debug!("AgentPoller: Getting cluster data from agent {}", url);
-        match self
-            .http_client
-            .send_request_with_retries(&url, Method::GET, None, None)
-            .await
-        {
-            Err(http_err) => {
+        let backend_id = self.backend.id();
+        tracing::debug!("AgentPoller: Getting cluster data from {}", backend_id);
+        match self.backend.get_cluster().await {
+            Err(err) => {
                 tracing::error!(
-                    "AgentPoller: Failed fetching cluster data from agent {}: {:?}",
-                    agent_url,
-                    http_err
+                    "AgentPoller: Failed getting cluster data from {}: {:#}",
+                    backend_id,
+                    err
                 );
-                AUTOSCALER_METRICS.agent_not_ready[&agent_url].inc();
+                AUTOSCALER_METRICS.agent_not_ready[&backend_id].inc();
                 let mut guard = self.data.lock().await;
                 guard.is_ready[self.agent_id] = false;
             }
-            Result::Ok(response) => match response.json::<Cluster>().await {
-                Err(json_err) => {
-                    tracing::error!(
-                        "AgentPoller: Failed to parse JSON cluster data from agent {}: {:?}",
-                        agent_url,
-                        json_err
-                    );
-                    AUTOSCALER_METRICS.agent_not_ready[&agent_url].inc();
-                    let mut guard = self.data.lock().await;
-                    guard.is_ready[self.agent_id] = false;
-                }
-                Result::Ok(cluster) => {
-                    let mut guard = self.data.lock().await;
-                    guard
-                        .clusters
-                        .agent_ids
-                        .insert(cluster.name.clone(), self.agent_id);
-                    guard
-                        .clusters
-                        .clusters
-                        .insert(cluster.name.clone(), cluster);
-                    guard.is_ready[self.agent_id] = true;
-                }
-            },
+            Ok(cluster) => {
+                let mut guard = self.data.lock().await;
+                guard
+                    .clusters
+                    .agent_ids
+                    .insert(cluster.name.clone(), self.agent_id);
+                guard
+                    .clusters
+                    .clusters
+                    .insert(cluster.name.clone(), cluster);
+                guard.is_ready[self.agent_id] = true;
+            }
         }
         Ok(())
     }
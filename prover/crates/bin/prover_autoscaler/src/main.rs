@@ -7,7 +7,7 @@ use zksync_prover_autoscaler::{
     agent,
     cluster_types::ClusterName,
     config::ProverAutoscalerConfig,
-    global::{manager::Manager, queuer::Queuer, watcher},
+    global::{backend, manager::Manager, queuer::Queuer, watcher},
     http_client::HttpClient,
     k8s::{Scaler, Watcher},
 };
@@ -110,11 +110,8 @@ async fn main() -> anyhow::Result<()> {
             let exporter_config = PrometheusExporterConfig::pull(scaler_config.prometheus_port);
             tasks.push(tokio::spawn(exporter_config.run(stop_receiver.clone())));
 
-            let watcher = watcher::Watcher::new(
-                http_client.clone(),
-                scaler_config.agents.clone(),
-                scaler_config.dry_run,
-            );
+            let backends = backend::create_backends(&scaler_config, &http_client)?;
+            let watcher = watcher::Watcher::new(backends, scaler_config.dry_run);
             let queuer = Queuer::new(http_client, scaler_config.prover_job_monitor_url.clone());
             let manager = Manager::new(watcher.clone(), queuer, scaler_config);
 
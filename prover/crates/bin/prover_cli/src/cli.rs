@@ -2,8 +2,8 @@ use clap::{command, Args, Parser, Subcommand};
 use zksync_types::url::SensitiveUrl;
 
 use crate::commands::{
-    config, debug_proof, delete, get_file_info, insert_batch, insert_version, requeue, restart,
-    stats, status::StatusCommand,
+    config, debug_proof, delete, get_file_info, insert_batch, insert_version,
+    priority::PriorityCommand, report, requeue, restart, stats, status::StatusCommand,
 };
 
 pub const VERSION_STRING: &str = env!("CARGO_PKG_VERSION");
@@ -30,6 +30,8 @@ impl ProverCLI {
             ProverCommand::Stats(args) => stats::run(args, self.config).await?,
             ProverCommand::InsertVersion(args) => insert_version::run(args, self.config).await?,
             ProverCommand::InsertBatch(args) => insert_batch::run(args, self.config).await?,
+            ProverCommand::Report(args) => report::run(args, self.config).await?,
+            ProverCommand::Priority(cmd) => cmd.run(self.config).await?,
         };
         Ok(())
     }
@@ -62,4 +64,8 @@ pub enum ProverCommand {
     Stats(stats::Options),
     InsertVersion(insert_version::Args),
     InsertBatch(insert_batch::Args),
+    #[command(about = "Displays proving timeline and bottlenecks for a range of L1 batches")]
+    Report(report::Args),
+    #[command(subcommand, about = "Manages proving priorities of L1 batches")]
+    Priority(PriorityCommand),
 }
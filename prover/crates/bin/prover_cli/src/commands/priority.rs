@@ -0,0 +1,86 @@
+use anyhow::Context;
+use clap::{Args as ClapArgs, Subcommand};
+use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
+use zksync_types::{L1BatchId, L1BatchNumber, L2ChainId};
+
+use crate::cli::ProverCLIConfig;
+
+#[derive(Subcommand)]
+pub enum PriorityCommand {
+    #[command(about = "Sets the proving priority of an L1 batch")]
+    Set(SetArgs),
+    #[command(about = "Raises the proving priority of an L1 batch")]
+    Raise(ChangeArgs),
+    #[command(about = "Lowers the proving priority of an L1 batch")]
+    Lower(ChangeArgs),
+    #[command(about = "Lists L1 batches with a non-default proving priority")]
+    List,
+}
+
+#[derive(ClapArgs)]
+pub struct SetArgs {
+    /// Batch number to set the priority for
+    #[clap(short, long)]
+    batch: L1BatchNumber,
+    /// New priority of the batch. Jobs with higher priority are picked first, the default is 0
+    #[clap(short, long, allow_negative_numbers = true)]
+    priority: i32,
+}
+
+#[derive(ClapArgs)]
+pub struct ChangeArgs {
+    /// Batch number to change the priority for
+    #[clap(short, long)]
+    batch: L1BatchNumber,
+    /// Amount by which the priority is changed
+    #[clap(long, default_value_t = 1)]
+    by: u16,
+}
+
+impl PriorityCommand {
+    pub(crate) async fn run(self, config: ProverCLIConfig) -> anyhow::Result<()> {
+        let prover_connection_pool = ConnectionPool::<Prover>::singleton(config.db_url)
+            .build()
+            .await
+            .context("failed to build a prover_connection_pool")?;
+        let mut conn = prover_connection_pool
+            .connection()
+            .await
+            .context("failed to get a connection")?;
+        let mut dal = conn.fri_batch_priority_dal();
+
+        let (batch, priority) = match self {
+            PriorityCommand::Set(args) => (
+                args.batch,
+                dal.set_batch_priority(batch_id(args.batch), args.priority)
+                    .await?,
+            ),
+            PriorityCommand::Raise(args) => (
+                args.batch,
+                dal.change_batch_priority(batch_id(args.batch), args.by.into())
+                    .await?,
+            ),
+            PriorityCommand::Lower(args) => (
+                args.batch,
+                dal.change_batch_priority(batch_id(args.batch), -i32::from(args.by))
+                    .await?,
+            ),
+            PriorityCommand::List => {
+                let priorities = dal.get_batch_priorities().await?;
+                if priorities.is_empty() {
+                    println!("No batches with non-default priority");
+                }
+                for (batch_id, priority) in priorities {
+                    println!("Batch {}: priority {priority}", batch_id.batch_number());
+                }
+                return Ok(());
+            }
+        };
+        println!("Batch {batch}: priority {priority}");
+        Ok(())
+    }
+}
+
+fn batch_id(batch: L1BatchNumber) -> L1BatchId {
+    L1BatchId::new(L2ChainId::zero(), batch)
+}
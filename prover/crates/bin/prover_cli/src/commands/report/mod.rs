@@ -0,0 +1,211 @@
+use anyhow::Context as _;
+use clap::{Args as ClapArgs, ValueEnum};
+use colored::*;
+use zksync_prover_dal::{Connection, ConnectionPool, Prover, ProverDal};
+use zksync_types::{basic_fri_types::AggregationRound, L1BatchId, L1BatchNumber, L2ChainId};
+
+use self::timeline::{BatchReport, Job, Step};
+use crate::cli::ProverCLIConfig;
+
+mod timeline;
+
+#[derive(ValueEnum, Clone, Copy, Default)]
+enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// First batch of the range.
+    #[clap(short, long)]
+    from: L1BatchNumber,
+    /// Last batch of the range (inclusive). Defaults to `--from`.
+    #[clap(short, long)]
+    to: Option<L1BatchNumber>,
+    #[clap(long, value_enum, default_value_t)]
+    format: OutputFormat,
+}
+
+pub(crate) async fn run(args: Args, config: ProverCLIConfig) -> anyhow::Result<()> {
+    let to = args.to.unwrap_or(args.from);
+    anyhow::ensure!(args.from <= to, "invalid batch range: {} > {to}", args.from);
+
+    let prover_connection_pool = ConnectionPool::<Prover>::singleton(config.db_url)
+        .build()
+        .await
+        .context("failed to build a prover_connection_pool")?;
+    let mut conn = prover_connection_pool
+        .connection()
+        .await
+        .context("failed to get a connection")?;
+
+    let mut reports = vec![];
+    for batch_number in args.from.0..=to.0 {
+        let jobs = get_batch_jobs(L1BatchNumber(batch_number), &mut conn).await;
+        reports.push(BatchReport::new(batch_number, jobs));
+    }
+
+    match args.format {
+        OutputFormat::Table => display_table(&reports),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&reports)?),
+        OutputFormat::Csv => display_csv(&reports),
+    }
+    Ok(())
+}
+
+async fn get_batch_jobs(
+    batch_number: L1BatchNumber,
+    conn: &mut Connection<'_, Prover>,
+) -> Vec<Job> {
+    let batch_id = L1BatchId::new(L2ChainId::zero(), batch_number);
+    let mut jobs = vec![];
+
+    if let Some(info) = conn
+        .fri_basic_witness_generator_dal()
+        .get_basic_witness_generator_job_for_batch(batch_id)
+        .await
+    {
+        jobs.push(Job::from(&info));
+    }
+    let leaf_jobs = conn
+        .fri_leaf_witness_generator_dal()
+        .get_leaf_witness_generator_jobs_for_batch(batch_id)
+        .await;
+    jobs.extend(leaf_jobs.iter().map(Job::from));
+    let node_jobs = conn
+        .fri_node_witness_generator_dal()
+        .get_node_witness_generator_jobs_for_batch(batch_id)
+        .await;
+    jobs.extend(node_jobs.iter().map(Job::from));
+    if let Some(info) = conn
+        .fri_recursion_tip_witness_generator_dal()
+        .get_recursion_tip_witness_generator_jobs_for_batch(batch_id)
+        .await
+    {
+        jobs.push(Job::from(&info));
+    }
+    if let Some(info) = conn
+        .fri_scheduler_witness_generator_dal()
+        .get_scheduler_witness_generator_jobs_for_batch(batch_id)
+        .await
+    {
+        jobs.push(Job::from(&info));
+    }
+    if let Some(info) = conn
+        .fri_proof_compressor_dal()
+        .get_proof_compression_job_for_batch(batch_id)
+        .await
+    {
+        jobs.push(Job::from(&info));
+    }
+
+    for round in [
+        AggregationRound::BasicCircuits,
+        AggregationRound::LeafAggregation,
+        AggregationRound::NodeAggregation,
+        AggregationRound::RecursionTip,
+        AggregationRound::Scheduler,
+    ] {
+        let prover_jobs = conn
+            .fri_prover_jobs_dal()
+            .get_prover_jobs_stats_for_batch(batch_id, round)
+            .await;
+        jobs.extend(prover_jobs.iter().map(Job::from));
+    }
+    jobs
+}
+
+fn format_secs(secs: Option<i64>) -> String {
+    let Some(secs) = secs else {
+        return "-".to_owned();
+    };
+    let (hours, minutes, secs) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}h {minutes:02}m {secs:02}s")
+    } else if minutes > 0 {
+        format!("{minutes}m {secs:02}s")
+    } else {
+        format!("{secs}s")
+    }
+}
+
+fn format_optional<T: ToString>(value: Option<T>) -> String {
+    value.map_or_else(|| "-".to_owned(), |value| value.to_string())
+}
+
+fn display_table(reports: &[BatchReport]) {
+    for report in reports {
+        println!(
+            "== {} ==",
+            format!("Batch {} Report", report.l1_batch_number).bold()
+        );
+        if report.steps.is_empty() {
+            println!("> No batch found. 🚫");
+            continue;
+        }
+
+        println!(
+            "  {:<32}{:>8}{:>7}{:>7}{:>10}{:>14}{:>14}",
+            "Stage", "Circuit", "Depth", "Jobs", "Attempts", "Queue wait", "Processing"
+        );
+        for step in &report.steps {
+            let row = format!(
+                "{} {:<32}{:>8}{:>7}{:>7}{:>10}{:>14}{:>14}",
+                if step.on_longest_path { "*" } else { " " },
+                step.stage.to_string(),
+                format_optional(step.circuit_id),
+                format_optional(step.depth),
+                step.jobs,
+                step.attempts,
+                format_secs(step.queue_wait_secs),
+                format_secs(step.processing_secs),
+            );
+            if step.on_longest_path {
+                println!("{}", row.bold());
+            } else {
+                println!("{row}");
+            }
+        }
+        println!(
+            "> Total: {}; longest path (*): {}",
+            format_secs(report.duration_secs),
+            report.longest_path.join(" -> ")
+        );
+    }
+}
+
+fn display_csv(reports: &[BatchReport]) {
+    println!(
+        "l1_batch_number,stage,circuit_id,depth,jobs,attempts,ready_at,started_at,finished_at,\
+         queue_wait_secs,processing_secs,on_longest_path"
+    );
+    for report in reports {
+        for step in &report.steps {
+            println!("{},{}", report.l1_batch_number, csv_row(step));
+        }
+    }
+}
+
+fn csv_row(step: &Step) -> String {
+    fn optional<T: ToString>(value: Option<T>) -> String {
+        value.map(|value| value.to_string()).unwrap_or_default()
+    }
+
+    [
+        step.stage.to_string(),
+        optional(step.circuit_id),
+        optional(step.depth),
+        step.jobs.to_string(),
+        step.attempts.to_string(),
+        step.ready_at.to_string(),
+        optional(step.started_at),
+        optional(step.finished_at),
+        optional(step.queue_wait_secs),
+        optional(step.processing_secs),
+        step.on_longest_path.to_string(),
+    ]
+    .join(",")
+}
@@ -0,0 +1,495 @@
+//! Per-batch proving timeline built from the prover job tables.
+
+use std::{collections::BTreeMap, fmt};
+
+use chrono::{NaiveDateTime, NaiveTime};
+use serde::Serialize;
+use zksync_types::{
+    basic_fri_types::AggregationRound,
+    prover_dal::{
+        BasicWitnessGeneratorJobInfo, LeafWitnessGeneratorJobInfo, NodeWitnessGeneratorJobInfo,
+        ProofCompressionJobInfo, ProofCompressionJobStatus, ProverJobFriInfo, ProverJobStatus,
+        RecursionTipWitnessGeneratorJobInfo, SchedulerWitnessGeneratorJobInfo, WitnessJobStatus,
+    },
+};
+
+/// Proving stage. Variants are ordered as they are executed for a batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, strum::Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum Stage {
+    BasicWitnessGenerator,
+    BasicProver,
+    LeafWitnessGenerator,
+    LeafProver,
+    NodeWitnessGenerator,
+    NodeProver,
+    RecursionTipWitnessGenerator,
+    RecursionTipProver,
+    SchedulerWitnessGenerator,
+    SchedulerProver,
+    Compressor,
+}
+
+impl Stage {
+    fn for_prover_job(job: &ProverJobFriInfo) -> Self {
+        match job.aggregation_round {
+            AggregationRound::BasicCircuits => Self::BasicProver,
+            AggregationRound::LeafAggregation => Self::LeafProver,
+            AggregationRound::NodeAggregation => Self::NodeProver,
+            AggregationRound::RecursionTip => Self::RecursionTipProver,
+            AggregationRound::Scheduler => Self::SchedulerProver,
+        }
+    }
+
+    /// Whether jobs of this stage are split by circuit ID.
+    fn is_per_circuit(self) -> bool {
+        matches!(
+            self,
+            Self::BasicProver
+                | Self::LeafWitnessGenerator
+                | Self::LeafProver
+                | Self::NodeWitnessGenerator
+                | Self::NodeProver
+        )
+    }
+}
+
+/// Timing of a single job.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct JobTiming {
+    pub created_at: NaiveDateTime,
+    /// Start of the last processing attempt.
+    pub started_at: Option<NaiveDateTime>,
+    /// Only set for successfully processed jobs.
+    pub finished_at: Option<NaiveDateTime>,
+    pub attempts: u32,
+}
+
+impl JobTiming {
+    fn new(
+        created_at: NaiveDateTime,
+        started_at: Option<NaiveDateTime>,
+        time_taken: Option<NaiveTime>,
+        updated_at: NaiveDateTime,
+        is_successful: bool,
+        attempts: u32,
+    ) -> Self {
+        let finished_at = is_successful.then(|| match (started_at, time_taken) {
+            (Some(started_at), Some(time_taken)) => {
+                started_at + time_taken.signed_duration_since(NaiveTime::MIN)
+            }
+            _ => updated_at,
+        });
+        Self {
+            created_at,
+            started_at,
+            finished_at,
+            attempts,
+        }
+    }
+}
+
+/// Job belonging to a certain stage of batch proving.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Job {
+    pub stage: Stage,
+    pub circuit_id: Option<u32>,
+    pub depth: Option<u32>,
+    pub timing: JobTiming,
+}
+
+impl From<&BasicWitnessGeneratorJobInfo> for Job {
+    fn from(info: &BasicWitnessGeneratorJobInfo) -> Self {
+        Self {
+            stage: Stage::BasicWitnessGenerator,
+            circuit_id: None,
+            depth: None,
+            timing: JobTiming::new(
+                info.created_at,
+                info.processing_started_at,
+                info.time_taken,
+                info.updated_at,
+                matches!(info.status, WitnessJobStatus::Successful(_)),
+                info.attempts,
+            ),
+        }
+    }
+}
+
+impl From<&LeafWitnessGeneratorJobInfo> for Job {
+    fn from(info: &LeafWitnessGeneratorJobInfo) -> Self {
+        Self {
+            stage: Stage::LeafWitnessGenerator,
+            circuit_id: Some(info.circuit_id),
+            depth: None,
+            timing: JobTiming::new(
+                info.created_at,
+                info.processing_started_at,
+                info.time_taken,
+                info.updated_at,
+                matches!(info.status, WitnessJobStatus::Successful(_)),
+                info.attempts,
+            ),
+        }
+    }
+}
+
+impl From<&NodeWitnessGeneratorJobInfo> for Job {
+    fn from(info: &NodeWitnessGeneratorJobInfo) -> Self {
+        Self {
+            stage: Stage::NodeWitnessGenerator,
+            circuit_id: Some(info.circuit_id),
+            depth: Some(info.depth),
+            timing: JobTiming::new(
+                info.created_at,
+                info.processing_started_at,
+                info.time_taken,
+                info.updated_at,
+                matches!(info.status, WitnessJobStatus::Successful(_)),
+                info.attempts,
+            ),
+        }
+    }
+}
+
+impl From<&RecursionTipWitnessGeneratorJobInfo> for Job {
+    fn from(info: &RecursionTipWitnessGeneratorJobInfo) -> Self {
+        Self {
+            stage: Stage::RecursionTipWitnessGenerator,
+            circuit_id: None,
+            depth: None,
+            timing: JobTiming::new(
+                info.created_at,
+                info.processing_started_at,
+                info.time_taken,
+                info.updated_at,
+                matches!(info.status, WitnessJobStatus::Successful(_)),
+                info.attempts,
+            ),
+        }
+    }
+}
+
+impl From<&SchedulerWitnessGeneratorJobInfo> for Job {
+    fn from(info: &SchedulerWitnessGeneratorJobInfo) -> Self {
+        Self {
+            stage: Stage::SchedulerWitnessGenerator,
+            circuit_id: None,
+            depth: None,
+            timing: JobTiming::new(
+                info.created_at,
+                info.processing_started_at,
+                info.time_taken,
+                info.updated_at,
+                matches!(info.status, WitnessJobStatus::Successful(_)),
+                info.attempts,
+            ),
+        }
+    }
+}
+
+impl From<&ProofCompressionJobInfo> for Job {
+    fn from(info: &ProofCompressionJobInfo) -> Self {
+        Self {
+            stage: Stage::Compressor,
+            circuit_id: None,
+            depth: None,
+            timing: JobTiming::new(
+                info.created_at,
+                info.processing_started_at,
+                info.time_taken,
+                info.updated_at,
+                matches!(
+                    info.status,
+                    ProofCompressionJobStatus::Successful | ProofCompressionJobStatus::SentToServer
+                ),
+                info.attempts,
+            ),
+        }
+    }
+}
+
+impl From<&ProverJobFriInfo> for Job {
+    fn from(info: &ProverJobFriInfo) -> Self {
+        let stage = Stage::for_prover_job(info);
+        Self {
+            stage,
+            circuit_id: stage.is_per_circuit().then_some(info.circuit_id),
+            depth: (stage == Stage::NodeProver).then_some(info.depth),
+            timing: JobTiming::new(
+                info.created_at,
+                info.processing_started_at,
+                info.time_taken,
+                info.updated_at,
+                matches!(info.status, ProverJobStatus::Successful(_)),
+                info.attempts.into(),
+            ),
+        }
+    }
+}
+
+/// Jobs of the same stage, circuit and depth aggregated together.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Step {
+    pub stage: Stage,
+    pub circuit_id: Option<u32>,
+    pub depth: Option<u32>,
+    pub jobs: usize,
+    /// Total number of attempts across all jobs.
+    pub attempts: u32,
+    /// Time at which the step could start, i.e. when all jobs it depends on were processed.
+    pub ready_at: NaiveDateTime,
+    pub started_at: Option<NaiveDateTime>,
+    /// Set once all jobs in the step are processed.
+    pub finished_at: Option<NaiveDateTime>,
+    /// Time between the step becoming ready and its first job being picked.
+    pub queue_wait_secs: Option<i64>,
+    /// Time between the first job being picked and the last job being processed.
+    pub processing_secs: Option<i64>,
+    pub on_longest_path: bool,
+    #[serde(skip)]
+    dependencies: Vec<usize>,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}", self.stage)?;
+        if let Some(circuit_id) = self.circuit_id {
+            write!(formatter, " #{circuit_id}")?;
+        }
+        if let Some(depth) = self.depth {
+            write!(formatter, " depth {depth}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Step {
+    fn new(stage: Stage, circuit_id: Option<u32>, depth: Option<u32>, jobs: &[JobTiming]) -> Self {
+        let all_finished = jobs.iter().all(|job| job.finished_at.is_some());
+        Self {
+            stage,
+            circuit_id,
+            depth,
+            jobs: jobs.len(),
+            attempts: jobs.iter().map(|job| job.attempts).sum(),
+            ready_at: jobs.iter().map(|job| job.created_at).min().unwrap(),
+            started_at: jobs.iter().filter_map(|job| job.started_at).min(),
+            finished_at: if all_finished {
+                jobs.iter().filter_map(|job| job.finished_at).max()
+            } else {
+                None
+            },
+            queue_wait_secs: None,
+            processing_secs: None,
+            on_longest_path: false,
+            dependencies: vec![],
+        }
+    }
+
+    fn depends_on(&self, other: &Self) -> bool {
+        let same_circuit = self.circuit_id == other.circuit_id;
+        match self.stage {
+            Stage::BasicWitnessGenerator => false,
+            Stage::BasicProver => other.stage == Stage::BasicWitnessGenerator,
+            Stage::LeafWitnessGenerator => other.stage == Stage::BasicProver && same_circuit,
+            Stage::LeafProver => other.stage == Stage::LeafWitnessGenerator && same_circuit,
+            Stage::NodeWitnessGenerator => match self.depth {
+                None | Some(0) => other.stage == Stage::LeafProver && same_circuit,
+                Some(depth) => {
+                    other.stage == Stage::NodeProver
+                        && same_circuit
+                        && other.depth == Some(depth - 1)
+                }
+            },
+            Stage::NodeProver => {
+                other.stage == Stage::NodeWitnessGenerator
+                    && same_circuit
+                    && other.depth == self.depth
+            }
+            Stage::RecursionTipWitnessGenerator => other.stage == Stage::NodeProver,
+            Stage::RecursionTipProver => other.stage == Stage::RecursionTipWitnessGenerator,
+            Stage::SchedulerWitnessGenerator => other.stage == Stage::RecursionTipProver,
+            Stage::SchedulerProver => other.stage == Stage::SchedulerWitnessGenerator,
+            Stage::Compressor => other.stage == Stage::SchedulerProver,
+        }
+    }
+
+    /// Latest known moment of the step; used to find the longest path for unfinished batches.
+    fn end(&self) -> NaiveDateTime {
+        self.finished_at
+            .or(self.started_at)
+            .unwrap_or(self.ready_at)
+    }
+}
+
+/// Proving timeline of a single batch.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct BatchReport {
+    pub l1_batch_number: u32,
+    /// Time from the basic witness generator job being created to the last processed job.
+    pub duration_secs: Option<i64>,
+    /// Chain of steps that determined the batch proving time, from first to last.
+    pub longest_path: Vec<String>,
+    pub steps: Vec<Step>,
+}
+
+impl BatchReport {
+    pub fn new(l1_batch_number: u32, jobs: impl IntoIterator<Item = Job>) -> Self {
+        let mut groups = BTreeMap::<_, Vec<_>>::new();
+        for job in jobs {
+            groups
+                .entry((job.stage, job.circuit_id, job.depth))
+                .or_default()
+                .push(job.timing);
+        }
+        // Dependencies are looked up across all steps: the `BTreeMap` ordering doesn't match
+        // the dependency order for recursion layers (e.g., a node witness generator at depth 1
+        // depends on node provers at depth 0, which are ordered after it). Only `finished_at`
+        // of dependencies is used below, and it isn't modified, so the processing order doesn't matter.
+        let mut steps: Vec<_> = groups
+            .into_iter()
+            .map(|((stage, circuit_id, depth), jobs)| Step::new(stage, circuit_id, depth, &jobs))
+            .collect();
+
+        for i in 0..steps.len() {
+            let dependencies: Vec<_> = (0..steps.len())
+                .filter(|&j| j != i && steps[i].depends_on(&steps[j]))
+                .collect();
+            let dependencies_finished_at = dependencies
+                .iter()
+                .map(|&j| steps[j].finished_at)
+                .collect::<Option<Vec<_>>>()
+                .and_then(|finished_at| finished_at.into_iter().max());
+
+            let step = &mut steps[i];
+            if let Some(finished_at) = dependencies_finished_at {
+                step.ready_at = step.ready_at.max(finished_at);
+            }
+            step.queue_wait_secs = step
+                .started_at
+                .map(|started_at| (started_at - step.ready_at).num_seconds().max(0));
+            step.processing_secs = step
+                .started_at
+                .zip(step.finished_at)
+                .map(|(started_at, finished_at)| (finished_at - started_at).num_seconds());
+            step.dependencies = dependencies;
+        }
+
+        let longest_path = Self::mark_longest_path(&mut steps);
+        let duration_secs = steps
+            .iter()
+            .map(|step| step.ready_at)
+            .min()
+            .zip(steps.iter().filter_map(|step| step.finished_at).max())
+            .map(|(start, end)| (end - start).num_seconds());
+        Self {
+            l1_batch_number,
+            duration_secs,
+            longest_path,
+            steps,
+        }
+    }
+
+    fn mark_longest_path(steps: &mut [Step]) -> Vec<String> {
+        let last_step = (0..steps.len()).max_by_key(|&i| (steps[i].end(), steps[i].stage));
+        let mut path = vec![];
+        let mut current = last_step;
+        while let Some(i) = current {
+            steps[i].on_longest_path = true;
+            path.push(steps[i].to_string());
+            current = steps[i]
+                .dependencies
+                .iter()
+                .copied()
+                .max_by_key(|&j| steps[j].end());
+        }
+        path.reverse();
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use super::*;
+
+    fn at(secs: i64) -> NaiveDateTime {
+        DateTime::from_timestamp(secs, 0).unwrap().naive_utc()
+    }
+
+    fn job(stage: Stage, circuit_id: Option<u32>, depth: Option<u32>, times: [i64; 3]) -> Job {
+        let [created_at, started_at, finished_at] = times;
+        Job {
+            stage,
+            circuit_id,
+            depth,
+            timing: JobTiming {
+                created_at: at(created_at),
+                started_at: Some(at(started_at)),
+                finished_at: Some(at(finished_at)),
+                attempts: 1,
+            },
+        }
+    }
+
+    #[test]
+    fn recursion_layers_depend_on_previous_depth() {
+        // All jobs are created upfront, so readiness is fully determined by dependencies.
+        let jobs = [
+            job(Stage::BasicWitnessGenerator, None, None, [0, 0, 10]),
+            job(Stage::BasicProver, Some(1), None, [0, 10, 20]),
+            job(Stage::LeafWitnessGenerator, Some(1), None, [0, 20, 30]),
+            job(Stage::LeafProver, Some(1), None, [0, 30, 40]),
+            job(Stage::NodeWitnessGenerator, Some(1), Some(0), [0, 40, 50]),
+            job(Stage::NodeProver, Some(1), Some(0), [0, 50, 60]),
+            job(Stage::NodeWitnessGenerator, Some(1), Some(1), [0, 70, 80]),
+            job(Stage::NodeProver, Some(1), Some(1), [0, 80, 90]),
+            job(
+                Stage::RecursionTipWitnessGenerator,
+                None,
+                None,
+                [0, 90, 100],
+            ),
+        ];
+        let report = BatchReport::new(1, jobs);
+
+        let node_wg_1 = report
+            .steps
+            .iter()
+            .find(|step| step.stage == Stage::NodeWitnessGenerator && step.depth == Some(1))
+            .unwrap();
+        assert_eq!(node_wg_1.ready_at, at(60));
+        assert_eq!(node_wg_1.queue_wait_secs, Some(10));
+
+        let node_prover_1 = report
+            .steps
+            .iter()
+            .find(|step| step.stage == Stage::NodeProver && step.depth == Some(1))
+            .unwrap();
+        assert_eq!(node_prover_1.ready_at, at(80));
+        assert_eq!(node_prover_1.queue_wait_secs, Some(0));
+
+        assert_eq!(
+            report.longest_path,
+            [
+                "basic_witness_generator",
+                "basic_prover #1",
+                "leaf_witness_generator #1",
+                "leaf_prover #1",
+                "node_witness_generator #1 depth 0",
+                "node_prover #1 depth 0",
+                "node_witness_generator #1 depth 1",
+                "node_prover #1 depth 1",
+                "recursion_tip_witness_generator",
+            ]
+        );
+        assert!(report.steps.iter().all(|step| step.on_longest_path));
+        assert_eq!(
+            report.duration_secs,
+            Some(Duration::seconds(100).num_seconds())
+        );
+    }
+}
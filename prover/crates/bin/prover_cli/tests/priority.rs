@@ -0,0 +1,81 @@
+use assert_cmd::Command;
+use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
+use zksync_types::{L1BatchId, L1BatchNumber, L2ChainId};
+
+#[test]
+#[doc = "prover_cli priority"]
+fn pli_priority_empty_fails() {
+    Command::cargo_bin("prover_cli")
+        .unwrap()
+        .arg("priority")
+        .assert()
+        .failure();
+}
+
+#[test]
+#[doc = "prover_cli priority --help"]
+fn pli_priority_help_succeeds() {
+    Command::cargo_bin("prover_cli")
+        .unwrap()
+        .arg("priority")
+        .arg("help")
+        .assert()
+        .success();
+}
+
+#[tokio::test]
+#[doc = "prover_cli priority set/raise/lower/list"]
+async fn pli_priority_changes_are_persisted() {
+    let connection_pool = ConnectionPool::<Prover>::prover_test_pool().await;
+    let db_url = connection_pool.database_url().expose_str().to_owned();
+
+    Command::cargo_bin("prover_cli")
+        .unwrap()
+        .arg(&db_url)
+        .arg("priority")
+        .arg("list")
+        .assert()
+        .success()
+        .stdout("No batches with non-default priority\n");
+
+    Command::cargo_bin("prover_cli")
+        .unwrap()
+        .arg(&db_url)
+        .args(["priority", "set", "--batch", "1", "--priority", "5"])
+        .assert()
+        .success()
+        .stdout("Batch 1: priority 5\n");
+
+    Command::cargo_bin("prover_cli")
+        .unwrap()
+        .arg(&db_url)
+        .args(["priority", "raise", "--batch", "1", "--by", "2"])
+        .assert()
+        .success()
+        .stdout("Batch 1: priority 7\n");
+
+    Command::cargo_bin("prover_cli")
+        .unwrap()
+        .arg(&db_url)
+        .args(["priority", "lower", "--batch", "2"])
+        .assert()
+        .success()
+        .stdout("Batch 2: priority -1\n");
+
+    Command::cargo_bin("prover_cli")
+        .unwrap()
+        .arg(&db_url)
+        .arg("priority")
+        .arg("list")
+        .assert()
+        .success()
+        .stdout("Batch 1: priority 7\nBatch 2: priority -1\n");
+
+    let mut connection = connection_pool.connection().await.unwrap();
+    let priority = connection
+        .fri_batch_priority_dal()
+        .get_batch_priority(L1BatchId::new(L2ChainId::zero(), L1BatchNumber(1)))
+        .await
+        .unwrap();
+    assert_eq!(priority, 7);
+}
@@ -0,0 +1,178 @@
+use assert_cmd::Command;
+use chrono::{DateTime, Utc};
+use circuit_definitions::zkevm_circuits::scheduler::aux::BaseLayerCircuitType;
+use zksync_prover_dal::{
+    fri_witness_generator_dal::FriWitnessJobStatus, ConnectionPool, Prover, ProverDal,
+};
+use zksync_types::{
+    basic_fri_types::AggregationRound,
+    protocol_version::{L1VerifierConfig, ProtocolSemanticVersion},
+    prover_dal::{ProverJobStatus, ProverJobStatusInProgress, ProverJobStatusSuccessful},
+    L1BatchId, L1BatchNumber, L2ChainId,
+};
+
+const NON_EXISTING_BATCHES_REPORT_STDOUT: &str = "== Batch 10000 Report ==
+> No batch found. 🚫
+== Batch 10001 Report ==
+> No batch found. 🚫
+";
+
+#[test]
+#[doc = "prover_cli report"]
+fn pli_report_empty_fails() {
+    Command::cargo_bin("prover_cli")
+        .unwrap()
+        .arg("report")
+        .assert()
+        .failure();
+}
+
+#[test]
+#[doc = "prover_cli report --help"]
+fn pli_report_help_succeeds() {
+    Command::cargo_bin("prover_cli")
+        .unwrap()
+        .arg("report")
+        .arg("--help")
+        .assert()
+        .success();
+}
+
+#[tokio::test]
+#[doc = "prover_cli report --from 10000 --to 10001"]
+async fn pli_report_of_non_existing_batches_succeeds() {
+    let connection_pool = ConnectionPool::<Prover>::prover_test_pool().await;
+    let mut connection = connection_pool.connection().await.unwrap();
+
+    connection
+        .fri_protocol_versions_dal()
+        .save_prover_protocol_version(
+            ProtocolSemanticVersion::default(),
+            L1VerifierConfig::default(),
+        )
+        .await
+        .unwrap();
+
+    Command::cargo_bin("prover_cli")
+        .unwrap()
+        .arg(connection_pool.database_url().expose_str())
+        .arg("report")
+        .args(["--from", "10000", "--to", "10001"])
+        .assert()
+        .success()
+        .stdout(NON_EXISTING_BATCHES_REPORT_STDOUT);
+}
+
+#[tokio::test]
+#[doc = "prover_cli report --from 0 --format json"]
+async fn pli_report_json_succeeds() {
+    let connection_pool = ConnectionPool::<Prover>::prover_test_pool().await;
+    let mut connection = connection_pool.connection().await.unwrap();
+    let batch_id = L1BatchId::new(L2ChainId::zero(), L1BatchNumber(0));
+
+    connection
+        .fri_protocol_versions_dal()
+        .save_prover_protocol_version(
+            ProtocolSemanticVersion::default(),
+            L1VerifierConfig::default(),
+        )
+        .await
+        .unwrap();
+    connection
+        .fri_basic_witness_generator_dal()
+        .save_witness_inputs(
+            batch_id,
+            "",
+            ProtocolSemanticVersion::default(),
+            DateTime::<Utc>::default(),
+        )
+        .await
+        .unwrap();
+    connection
+        .fri_basic_witness_generator_dal()
+        .set_status_for_basic_witness_job(FriWitnessJobStatus::Successful, batch_id)
+        .await;
+
+    let prover_jobs = [
+        (
+            BaseLayerCircuitType::VM,
+            ProverJobStatus::Successful(ProverJobStatusSuccessful::default()),
+        ),
+        (
+            BaseLayerCircuitType::DecommitmentsFilter,
+            ProverJobStatus::InProgress(ProverJobStatusInProgress::default()),
+        ),
+    ];
+    for (sequence_number, (circuit_id, status)) in prover_jobs.into_iter().enumerate() {
+        connection
+            .fri_prover_jobs_dal()
+            .insert_prover_job(
+                batch_id,
+                circuit_id as u8,
+                0,
+                sequence_number,
+                AggregationRound::BasicCircuits,
+                "",
+                false,
+                ProtocolSemanticVersion::default(),
+                DateTime::<Utc>::default(),
+            )
+            .await;
+        connection
+            .cli_test_dal()
+            .update_prover_job(
+                status,
+                circuit_id as u8,
+                AggregationRound::BasicCircuits as i64,
+                L1BatchNumber(0),
+                sequence_number,
+            )
+            .await;
+    }
+
+    let output = Command::cargo_bin("prover_cli")
+        .unwrap()
+        .arg(connection_pool.database_url().expose_str())
+        .arg("report")
+        .args(["--from", "0", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    let reports: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let report = &reports.as_array().unwrap()[0];
+    assert_eq!(report["l1_batch_number"], 0);
+
+    let steps = report["steps"].as_array().unwrap();
+    let stages: Vec<_> = steps
+        .iter()
+        .map(|step| {
+            (
+                step["stage"].as_str().unwrap(),
+                step["circuit_id"].as_u64(),
+                step["finished_at"].is_string(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        stages,
+        [
+            ("basic_witness_generator", None, true),
+            ("basic_prover", Some(BaseLayerCircuitType::VM as u64), true),
+            (
+                "basic_prover",
+                Some(BaseLayerCircuitType::DecommitmentsFilter as u64),
+                false
+            ),
+        ]
+    );
+    let longest_path: Vec<_> = report["longest_path"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|step| step.as_str().unwrap())
+        .collect();
+    assert_eq!(longest_path.len(), 2);
+    assert_eq!(longest_path[0], "basic_witness_generator");
+    assert!(longest_path[1].starts_with("basic_prover"));
+    assert!(report["duration_secs"].is_number());
+}
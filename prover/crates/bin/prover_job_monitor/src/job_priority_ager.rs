@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use zksync_prover_dal::{ConnectionPool, Prover, ProverDal};
+use zksync_prover_task::Task;
+
+use crate::metrics::PROVER_JOB_MONITOR_METRICS;
+
+/// `JobPriorityAger` is a task that bumps the priority of jobs that have been queued for too long.
+///
+/// Every run increments the priority of each job queued for longer than `min_wait`, up to `max_priority`.
+/// Note: This component protects deprioritized batches from starvation; batches with priority manually raised
+/// above `max_priority` are not affected and keep precedence.
+///
+/// The component itself is stateless: aged priorities are persisted in the `priority` column of the queued jobs,
+/// so restarts of the monitor don't reset aging. Aging is intentionally not propagated to the batch priority
+/// stored in `prover_batch_priorities`. That value is set by the operator and is used for jobs created
+/// for the next proving stages, which are then aged based on their own queueing time.
+#[derive(Debug)]
+pub struct JobPriorityAger {
+    pool: ConnectionPool<Prover>,
+    /// duration a job must be queued for before its priority is bumped
+    min_wait: Duration,
+    /// priority up to which jobs are aged
+    max_priority: i32,
+}
+
+impl JobPriorityAger {
+    pub fn new(pool: ConnectionPool<Prover>, min_wait: Duration, max_priority: i32) -> Self {
+        Self {
+            pool,
+            min_wait,
+            max_priority,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for JobPriorityAger {
+    async fn invoke(&self) -> anyhow::Result<()> {
+        let mut connection = self
+            .pool
+            .connection()
+            .await
+            .context("failed to get database connection")?;
+        let aged_jobs = connection
+            .fri_batch_priority_dal()
+            .age_queued_jobs(self.min_wait, self.max_priority)
+            .await?;
+        if aged_jobs > 0 {
+            tracing::info!(
+                "Bumped priority of {:?} jobs queued for longer than {:?}",
+                aged_jobs,
+                self.min_wait
+            );
+        }
+        PROVER_JOB_MONITOR_METRICS
+            .jobs_priority_aged
+            .inc_by(aged_jobs);
+
+        Ok(())
+    }
+}
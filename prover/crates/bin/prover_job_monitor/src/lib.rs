@@ -1,5 +1,6 @@
 pub mod attempts_reporter;
 pub mod autoscaler_queue_reporter;
+pub mod job_priority_ager;
 pub mod job_requeuer;
 pub(crate) mod metrics;
 pub mod queue_reporter;
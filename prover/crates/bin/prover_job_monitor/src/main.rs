@@ -18,6 +18,7 @@ use zksync_prover_dal::{ConnectionPool, Prover};
 use zksync_prover_job_monitor::{
     attempts_reporter::ProverJobAttemptsReporter,
     autoscaler_queue_reporter::get_queue_reporter_router,
+    job_priority_ager::JobPriorityAger,
     job_requeuer::{ProofCompressorJobRequeuer, ProverJobRequeuer, WitnessGeneratorJobRequeuer},
     prover_jobs_archiver::ProverJobsArchiver,
     queue_reporter::{
@@ -228,6 +229,18 @@ fn get_tasks(
         witness_job_queuer,
     );
 
+    // job priority ager
+    let job_priority_ager = JobPriorityAger::new(
+        connection_pool.clone(),
+        prover_job_monitor_config.job_priority_ager_run_interval,
+        prover_job_monitor_config.job_priority_ager_max_priority,
+    );
+    task_runner.add(
+        "JobPriorityAger",
+        prover_job_monitor_config.job_priority_ager_run_interval,
+        job_priority_ager,
+    );
+
     // Reporter for reaching max attempts of jobs
     let attempts_reporter = ProverJobAttemptsReporter::new(
         connection_pool,
@@ -6,6 +6,7 @@ use zksync_types::{protocol_version::ProtocolSemanticVersion, L2ChainId};
 pub(crate) struct ProverJobMonitorMetrics {
     pub prover_job_archived: Counter,
     pub gpu_prover_archived: Counter,
+    pub jobs_priority_aged: Counter,
     #[metrics(labels = ["job_type"])]
     pub reached_max_attempts: LabeledFamily<JobType, Gauge>,
 }
@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS prover_batch_priorities (
+    l1_batch_number BIGINT NOT NULL,
+    chain_id INTEGER NOT NULL,
+    priority INTEGER NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (l1_batch_number, chain_id)
+);
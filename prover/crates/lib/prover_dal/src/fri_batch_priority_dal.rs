@@ -0,0 +1,399 @@
+use std::time::Duration;
+
+use zksync_basic_types::L1BatchId;
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+
+use crate::{pg_interval_from_duration, Prover};
+
+/// Manages per-batch proving priorities.
+///
+/// Batch priority is stored in `prover_batch_priorities` and is copied to the `priority` column of all jobs
+/// of the batch (both existing and ones created later); job pickers order queued jobs by this column.
+/// Priority may be set before the batch reaches the prover subsystem.
+#[derive(Debug)]
+pub struct FriBatchPriorityDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Prover>,
+}
+
+impl FriBatchPriorityDal<'_, '_> {
+    /// Sets the priority of the batch and returns it.
+    pub async fn set_batch_priority(
+        &mut self,
+        batch_id: L1BatchId,
+        priority: i32,
+    ) -> DalResult<i32> {
+        self.update_batch_priority(batch_id, priority, false).await
+    }
+
+    /// Changes the priority of the batch by `delta` (batches without priority have priority 0)
+    /// and returns the new priority.
+    pub async fn change_batch_priority(
+        &mut self,
+        batch_id: L1BatchId,
+        delta: i32,
+    ) -> DalResult<i32> {
+        self.update_batch_priority(batch_id, delta, true).await
+    }
+
+    async fn update_batch_priority(
+        &mut self,
+        batch_id: L1BatchId,
+        priority: i32,
+        is_relative: bool,
+    ) -> DalResult<i32> {
+        let row = sqlx::query!(
+            r#"
+            WITH
+            batch_priority AS (
+                INSERT INTO
+                prover_batch_priorities (
+                    l1_batch_number, chain_id, priority, created_at, updated_at
+                )
+                VALUES
+                ($1, $2, $3, NOW(), NOW())
+                ON CONFLICT (l1_batch_number, chain_id) DO
+                UPDATE
+                SET
+                priority = CASE
+                    WHEN $4 THEN prover_batch_priorities.priority + excluded.priority
+                    ELSE excluded.priority
+                END,
+                updated_at = NOW()
+                RETURNING
+                priority
+            ),
+
+            witness_inputs AS (
+                UPDATE witness_inputs_fri
+                SET
+                    priority = (SELECT priority FROM batch_priority)
+                WHERE
+                    l1_batch_number = $1
+                    AND chain_id = $2
+            ),
+
+            leaf_aggregation_jobs AS (
+                UPDATE leaf_aggregation_witness_jobs_fri
+                SET
+                    priority = (SELECT priority FROM batch_priority)
+                WHERE
+                    l1_batch_number = $1
+                    AND chain_id = $2
+            ),
+
+            node_aggregation_jobs AS (
+                UPDATE node_aggregation_witness_jobs_fri
+                SET
+                    priority = (SELECT priority FROM batch_priority)
+                WHERE
+                    l1_batch_number = $1
+                    AND chain_id = $2
+            ),
+
+            recursion_tip_jobs AS (
+                UPDATE recursion_tip_witness_jobs_fri
+                SET
+                    priority = (SELECT priority FROM batch_priority)
+                WHERE
+                    l1_batch_number = $1
+                    AND chain_id = $2
+            ),
+
+            scheduler_jobs AS (
+                UPDATE scheduler_witness_jobs_fri
+                SET
+                    priority = (SELECT priority FROM batch_priority)
+                WHERE
+                    l1_batch_number = $1
+                    AND chain_id = $2
+            ),
+
+            proof_compression_jobs AS (
+                UPDATE proof_compression_jobs_fri
+                SET
+                    priority = (SELECT priority FROM batch_priority)
+                WHERE
+                    l1_batch_number = $1
+                    AND chain_id = $2
+            ),
+
+            prover_jobs AS (
+                UPDATE prover_jobs_fri
+                SET
+                    priority = (SELECT priority FROM batch_priority)
+                WHERE
+                    l1_batch_number = $1
+                    AND chain_id = $2
+            )
+
+            SELECT
+                priority AS "priority!"
+            FROM
+                batch_priority
+            "#,
+            batch_id.batch_number().0 as i64,
+            batch_id.chain_id().inner() as i64,
+            priority,
+            is_relative,
+        )
+        .instrument("update_batch_priority")
+        .with_arg("batch_id", &batch_id)
+        .with_arg("priority", &priority)
+        .with_arg("is_relative", &is_relative)
+        .fetch_one(self.storage)
+        .await?;
+        Ok(row.priority)
+    }
+
+    /// Returns the priority of the batch, or 0 if it wasn't set.
+    pub async fn get_batch_priority(&mut self, batch_id: L1BatchId) -> DalResult<i32> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                COALESCE(MAX(priority), 0) AS "priority!"
+            FROM
+                prover_batch_priorities
+            WHERE
+                l1_batch_number = $1
+                AND chain_id = $2
+            "#,
+            batch_id.batch_number().0 as i64,
+            batch_id.chain_id().inner() as i64,
+        )
+        .instrument("get_batch_priority")
+        .with_arg("batch_id", &batch_id)
+        .fetch_one(self.storage)
+        .await?;
+        Ok(row.priority)
+    }
+
+    /// Returns all batches with non-zero priority, the highest priority first.
+    pub async fn get_batch_priorities(&mut self) -> DalResult<Vec<(L1BatchId, i32)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number,
+                chain_id,
+                priority
+            FROM
+                prover_batch_priorities
+            WHERE
+                priority <> 0
+            ORDER BY
+                priority DESC,
+                l1_batch_number ASC
+            "#
+        )
+        .instrument("get_batch_priorities")
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                (
+                    L1BatchId::from_raw(row.chain_id as u64, row.l1_batch_number as u32),
+                    row.priority,
+                )
+            })
+            .collect())
+    }
+
+    /// Increments the priority of all jobs that have been queued for at least `min_wait`, up to `max_priority`.
+    /// When run periodically, this gradually raises the priority of waiting jobs, so that low-priority batches
+    /// are not starved by higher-priority ones. Returns the number of updated jobs.
+    ///
+    /// Only job priorities are updated; batch priorities in `prover_batch_priorities` are left intact.
+    /// Hence, aging is reset if the batch priority is explicitly set, and jobs for the next proving stages
+    /// start from the batch priority.
+    pub async fn age_queued_jobs(
+        &mut self,
+        min_wait: Duration,
+        max_priority: i32,
+    ) -> DalResult<u64> {
+        let min_wait = pg_interval_from_duration(min_wait);
+        let row = sqlx::query!(
+            r#"
+            WITH
+            witness_inputs AS (
+                UPDATE witness_inputs_fri
+                SET
+                    priority = priority + 1
+                WHERE
+                    status = 'queued'
+                    AND priority < $1
+                    AND created_at < NOW() - $2::INTERVAL
+                RETURNING
+                1
+            ),
+
+            leaf_aggregation_jobs AS (
+                UPDATE leaf_aggregation_witness_jobs_fri
+                SET
+                    priority = priority + 1
+                WHERE
+                    status = 'queued'
+                    AND priority < $1
+                    AND created_at < NOW() - $2::INTERVAL
+                RETURNING
+                1
+            ),
+
+            node_aggregation_jobs AS (
+                UPDATE node_aggregation_witness_jobs_fri
+                SET
+                    priority = priority + 1
+                WHERE
+                    status = 'queued'
+                    AND priority < $1
+                    AND created_at < NOW() - $2::INTERVAL
+                RETURNING
+                1
+            ),
+
+            recursion_tip_jobs AS (
+                UPDATE recursion_tip_witness_jobs_fri
+                SET
+                    priority = priority + 1
+                WHERE
+                    status = 'queued'
+                    AND priority < $1
+                    AND created_at < NOW() - $2::INTERVAL
+                RETURNING
+                1
+            ),
+
+            scheduler_jobs AS (
+                UPDATE scheduler_witness_jobs_fri
+                SET
+                    priority = priority + 1
+                WHERE
+                    status = 'queued'
+                    AND priority < $1
+                    AND created_at < NOW() - $2::INTERVAL
+                RETURNING
+                1
+            ),
+
+            proof_compression_jobs AS (
+                UPDATE proof_compression_jobs_fri
+                SET
+                    priority = priority + 1
+                WHERE
+                    status = 'queued'
+                    AND priority < $1
+                    AND created_at < NOW() - $2::INTERVAL
+                RETURNING
+                1
+            ),
+
+            prover_jobs AS (
+                UPDATE prover_jobs_fri
+                SET
+                    priority = priority + 1
+                WHERE
+                    status = 'queued'
+                    AND priority < $1
+                    AND created_at < NOW() - $2::INTERVAL
+                RETURNING
+                1
+            )
+
+            SELECT
+                (SELECT COUNT(*) FROM witness_inputs)
+                + (SELECT COUNT(*) FROM leaf_aggregation_jobs)
+                + (SELECT COUNT(*) FROM node_aggregation_jobs)
+                + (SELECT COUNT(*) FROM recursion_tip_jobs)
+                + (SELECT COUNT(*) FROM scheduler_jobs)
+                + (SELECT COUNT(*) FROM proof_compression_jobs)
+                + (SELECT COUNT(*) FROM prover_jobs) AS "count!"
+            "#,
+            max_priority,
+            &min_wait,
+        )
+        .instrument("age_queued_jobs")
+        .with_arg("max_priority", &max_priority)
+        .fetch_one(self.storage)
+        .await?;
+        Ok(row.count as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::types::chrono::{DateTime, Utc};
+    use zksync_basic_types::protocol_version::{L1VerifierConfig, ProtocolSemanticVersion};
+    use zksync_db_connection::connection_pool::ConnectionPool;
+
+    use super::*;
+    use crate::ProverDal;
+
+    #[tokio::test]
+    async fn batch_priority_is_honored_by_pickers() {
+        let pool = ConnectionPool::<Prover>::prover_test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let protocol_version = ProtocolSemanticVersion::default();
+        conn.fri_protocol_versions_dal()
+            .save_prover_protocol_version(protocol_version, L1VerifierConfig::default())
+            .await
+            .unwrap();
+
+        let old_batch = L1BatchId::from_raw(270, 1);
+        let urgent_batch = L1BatchId::from_raw(270, 2);
+        // Priority can be set before the batch reaches the prover subsystem.
+        let priority = conn
+            .fri_batch_priority_dal()
+            .set_batch_priority(urgent_batch, 5)
+            .await
+            .unwrap();
+        assert_eq!(priority, 5);
+
+        for (batch_id, batch_sealed_at) in [
+            (old_batch, DateTime::<Utc>::default()),
+            (urgent_batch, Utc::now()),
+        ] {
+            conn.fri_basic_witness_generator_dal()
+                .save_witness_inputs(batch_id, "", protocol_version, batch_sealed_at)
+                .await
+                .unwrap();
+        }
+        let picked_batch = conn
+            .fri_basic_witness_generator_dal()
+            .get_next_basic_circuit_witness_job(protocol_version, "test")
+            .await;
+        assert_eq!(picked_batch, Some(urgent_batch));
+
+        let priority = conn
+            .fri_batch_priority_dal()
+            .change_batch_priority(old_batch, -3)
+            .await
+            .unwrap();
+        assert_eq!(priority, -3);
+        let priorities = conn
+            .fri_batch_priority_dal()
+            .get_batch_priorities()
+            .await
+            .unwrap();
+        assert_eq!(priorities, [(urgent_batch, 5), (old_batch, -3)]);
+
+        // Only the queued job of the deprioritized batch is aged.
+        let aged_jobs = conn
+            .fri_batch_priority_dal()
+            .age_queued_jobs(Duration::ZERO, 0)
+            .await
+            .unwrap();
+        assert_eq!(aged_jobs, 1);
+        let aged_jobs = conn
+            .fri_batch_priority_dal()
+            .age_queued_jobs(Duration::ZERO, -2)
+            .await
+            .unwrap();
+        assert_eq!(aged_jobs, 0);
+
+        let picked_batch = conn
+            .fri_basic_witness_generator_dal()
+            .get_next_basic_circuit_witness_job(protocol_version, "test")
+            .await;
+        assert_eq!(picked_batch, Some(old_batch));
+    }
+}
@@ -24,7 +24,7 @@ use zksync_db_connection::{
     connection::Connection, instrument::InstrumentExt, metrics::MethodLatency,
 };
 
-use crate::{duration_to_naive_time, pg_interval_from_duration, Prover};
+use crate::{duration_to_naive_time, pg_interval_from_duration, Prover, ProverDal};
 
 /// Among the zoo of circuits each circuit type has its own peak RAM utilization,
 /// average execution time and proportional share. Here we pay attention to
@@ -63,8 +63,8 @@ pub struct FriProverDal<'a, 'c> {
 impl FriProverDal<'_, '_> {
     // Postgres has a limit of 65535 push_bind parameters per query.
     // We need to split the insert into chunks to avoid hitting this limit.
-    // A single row in insert_prover_jobs push_binds 10 parameters, therefore
-    // the limit is 65k / 10 ~ 6500 jobs chunk.
+    // A single row in insert_prover_jobs push_binds 11 parameters, therefore
+    // the limit is 65k / 11 ~ 5900 jobs chunk.
     const INSERT_JOBS_CHUNK_SIZE: usize = 5400;
 
     pub async fn insert_prover_jobs(
@@ -80,6 +80,12 @@ impl FriProverDal<'_, '_> {
         if circuit_ids_sequence_numbers_and_urls.is_empty() {
             return;
         }
+        let priority = self
+            .storage
+            .fri_batch_priority_dal()
+            .get_batch_priority(batch_id)
+            .await
+            .unwrap();
 
         for chunk in circuit_ids_sequence_numbers_and_urls.chunks(Self::INSERT_JOBS_CHUNK_SIZE) {
             // Build multi-row INSERT for the current chunk
@@ -99,7 +105,8 @@ impl FriProverDal<'_, '_> {
                     created_at,
                     updated_at,
                     protocol_version_patch,
-                    batch_sealed_at
+                    batch_sealed_at,
+                    priority
                 )
                 "#,
             );
@@ -120,7 +127,8 @@ impl FriProverDal<'_, '_> {
                         .push("NOW()") // created_at
                         .push("NOW()") // updated_at
                         .push_bind(protocol_version_id.patch.0 as i32)
-                        .push_bind(batch_sealed_at.naive_utc()); // batch_sealed_at
+                        .push_bind(batch_sealed_at.naive_utc()) // batch_sealed_at
+                        .push_bind(priority);
                 },
             );
 
@@ -554,10 +562,22 @@ impl FriProverDal<'_, '_> {
                 created_at,
                 updated_at,
                 protocol_version_patch,
-                batch_sealed_at
+                batch_sealed_at,
+                priority
             )
             VALUES
-            ($1, $2, $3, $4, $5, $6, $7, $8, $9, 'queued', NOW(), NOW(), $10, $11)
+            (
+                $1, $2, $3, $4, $5, $6, $7, $8, $9, 'queued', NOW(), NOW(), $10, $11,
+                (
+                    SELECT
+                        COALESCE(MAX(priority), 0)
+                    FROM
+                        prover_batch_priorities
+                    WHERE
+                        l1_batch_number = $1
+                        AND chain_id = $2
+                )
+            )
             ON CONFLICT (
                 l1_batch_number,
                 chain_id,
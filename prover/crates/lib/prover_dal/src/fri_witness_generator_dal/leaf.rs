@@ -277,7 +277,8 @@ impl FriLeafWitnessGeneratorDal<'_, '_> {
                 created_at,
                 updated_at,
                 protocol_version_patch,
-                batch_sealed_at
+                batch_sealed_at,
+                priority
             )
             VALUES
             (
@@ -291,7 +292,16 @@ impl FriLeafWitnessGeneratorDal<'_, '_> {
                 NOW(),
                 NOW(),
                 $7,
-                $8
+                $8,
+                (
+                    SELECT
+                        COALESCE(MAX(priority), 0)
+                    FROM
+                        prover_batch_priorities
+                    WHERE
+                        l1_batch_number = $1
+                        AND chain_id = $2
+                )
             )
             ON CONFLICT (l1_batch_number, chain_id, circuit_id) DO
             UPDATE
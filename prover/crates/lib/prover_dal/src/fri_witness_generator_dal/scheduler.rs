@@ -300,10 +300,22 @@ impl FriSchedulerWitnessGeneratorDal<'_, '_> {
                 created_at,
                 updated_at,
                 protocol_version_patch,
-                batch_sealed_at
+                batch_sealed_at,
+                priority
             )
             VALUES
-            ($1, $2, $3, $4, 'waiting_for_proofs', NOW(), NOW(), $5, $6)
+            (
+                $1, $2, $3, $4, 'waiting_for_proofs', NOW(), NOW(), $5, $6,
+                (
+                    SELECT
+                        COALESCE(MAX(priority), 0)
+                    FROM
+                        prover_batch_priorities
+                    WHERE
+                        l1_batch_number = $1
+                        AND chain_id = $2
+                )
+            )
             ON CONFLICT (l1_batch_number, chain_id) DO
             UPDATE
             SET